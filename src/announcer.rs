@@ -0,0 +1,416 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::TAU;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+use crate::config::{AnnounceMethod, AnnouncerConfig};
+use crate::satellite::{Satellite, SatellitePosition};
+
+const CW_SAMPLE_RATE: u32 = 8000;
+
+/// Speaks or keys pass events: upcoming AOS, AOS/LOS, and elevation milestones
+#[derive(Debug, Default)]
+pub struct Announcer {
+    announced_aos: HashSet<(String, DateTime<Utc>)>, // (satellite, AOS) already called out
+    last_elevation: HashMap<String, f64>,
+    playing: Vec<(Child, Option<PathBuf>)>, // announcements under way, with their CW WAV file
+}
+
+impl Announcer {
+    /// Check for new events and hand them to the configured output
+    pub fn update(
+        &mut self,
+        config: Option<&AnnouncerConfig>,
+        satellites: &[Satellite],
+        positions: &[SatellitePosition],
+        aliases: &HashMap<String, String>,
+        now: DateTime<Utc>,
+    ) {
+        self.reap();
+        let Some(config) = config else {
+            return;
+        };
+        for message in self.events(config, satellites, positions, aliases, now) {
+            self.announce(config, &message);
+        }
+    }
+
+    /// Run the announcement without blocking the UI
+    pub fn announce(&mut self, config: &AnnouncerConfig, message: &str) {
+        let (mut command, wav) = match config.method {
+            AnnounceMethod::Speech => {
+                let script = format!(
+                    "Add-Type -AssemblyName System.Speech; \
+                     (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak('{}')",
+                    powershell_quote(message)
+                );
+                (command(&config.command, message, &script), None)
+            }
+            AnnounceMethod::Cw => {
+                let samples = cw_samples(&morse(message), config.cw_wpm, config.cw_tone_hz);
+                let path = cw_wav_path();
+                if std::fs::write(&path, wav_bytes(&samples)).is_err() {
+                    return;
+                }
+                let file = path.to_string_lossy();
+                let script = format!(
+                    "(New-Object Media.SoundPlayer '{}').PlaySync()",
+                    powershell_quote(&file)
+                );
+                (command(&config.player, &file, &script), Some(path))
+            }
+        };
+        // Output from the player would be drawn over the TUI
+        let spawned = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(child) => self.playing.push((child, wav)),
+            Err(_) => {
+                if let Some(path) = wav {
+                    let _ = std::fs::remove_file(path);
+                }
+            }
+        }
+    }
+
+    /// Wait for announcements that have finished and delete their WAV files
+    fn reap(&mut self) {
+        self.playing.retain_mut(|(child, wav)| {
+            if matches!(child.try_wait(), Ok(None)) {
+                return true;
+            }
+            if let Some(path) = wav {
+                let _ = std::fs::remove_file(path);
+            }
+            false
+        });
+    }
+
+    /// Pass events since the last call, as short sentences naming satellites
+    /// by their alias when they have one
+    pub fn events(
+        &mut self,
+        config: &AnnouncerConfig,
+        satellites: &[Satellite],
+        positions: &[SatellitePosition],
+        aliases: &HashMap<String, String>,
+        now: DateTime<Utc>,
+    ) -> Vec<String> {
+        let wanted = |name: &str| {
+            config.satellites.is_empty() || config.satellites.iter().any(|s| s == name)
+        };
+        let mut messages = Vec::new();
+
+        let lead = Duration::minutes(config.aos_lead_minutes);
+        for satellite in satellites.iter().filter(|sat| wanted(&sat.name)) {
+            let Some(pass) = satellite.get_next_pass() else {
+                continue;
+            };
+            let key = (satellite.name.clone(), pass.aos_time);
+            if pass.aos_time > now
+                && pass.aos_time - now <= lead
+                && !self.announced_aos.contains(&key)
+            {
+                let minutes = ((pass.aos_time - now).num_seconds() as f64 / 60.0).ceil() as i64;
+                messages.push(format!(
+                    "AOS {} in {} {}",
+                    aliases.get(&satellite.name).unwrap_or(&satellite.name),
+                    minutes,
+                    if minutes == 1 { "minute" } else { "minutes" }
+                ));
+                self.announced_aos.insert(key);
+            }
+        }
+        self.announced_aos
+            .retain(|(_, aos)| *aos > now - Duration::days(1));
+
+        for position in positions.iter().filter(|pos| wanted(&pos.name)) {
+            let previous = self
+                .last_elevation
+                .insert(position.name.clone(), position.elevation);
+            let Some(previous) = previous else {
+                continue;
+            };
+            let name = aliases.get(&position.name).unwrap_or(&position.name);
+
+            if previous <= 0.0 && position.elevation > 0.0 {
+                messages.push(format!("AOS {}", name));
+            } else if previous > 0.0 && position.elevation <= 0.0 {
+                messages.push(format!("LOS {}", name));
+            }
+
+            for milestone in &config.elevation_milestones {
+                if previous < *milestone && position.elevation >= *milestone {
+                    messages.push(format!("{} elevation {:.0} degrees", name, milestone));
+                }
+            }
+        }
+
+        messages
+    }
+}
+
+impl Drop for Announcer {
+    fn drop(&mut self) {
+        for (child, wav) in self.playing.iter_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+            if let Some(path) = wav {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// `program` with `argument`, or with `script` when the program is PowerShell.
+/// Windows has no espeak or aplay, so the defaults there use PowerShell's
+/// speech synthesizer and sound player, which need a script rather than a
+/// bare argument.
+fn command(program: &str, argument: &str, script: &str) -> Command {
+    let mut command = Command::new(program);
+    if is_powershell(program) {
+        command.args(["-NoProfile", "-NonInteractive", "-Command", script]);
+    } else {
+        command.arg(argument);
+    }
+    command
+}
+
+fn is_powershell(program: &str) -> bool {
+    let name = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    let name = name.to_ascii_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    name == "powershell" || name == "pwsh"
+}
+
+/// Escape text for a single-quoted PowerShell string
+fn powershell_quote(text: &str) -> String {
+    text.replace('\'', "''")
+}
+
+fn cw_wav_path() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "crabtrack-cw-{}.wav",
+        Utc::now().timestamp_millis()
+    ))
+}
+
+fn morse_code(c: char) -> Option<&'static str> {
+    let code = match c.to_ascii_uppercase() {
+        'A' => ".-",
+        'B' => "-...",
+        'C' => "-.-.",
+        'D' => "-..",
+        'E' => ".",
+        'F' => "..-.",
+        'G' => "--.",
+        'H' => "....",
+        'I' => "..",
+        'J' => ".---",
+        'K' => "-.-",
+        'L' => ".-..",
+        'M' => "--",
+        'N' => "-.",
+        'O' => "---",
+        'P' => ".--.",
+        'Q' => "--.-",
+        'R' => ".-.",
+        'S' => "...",
+        'T' => "-",
+        'U' => "..-",
+        'V' => "...-",
+        'W' => ".--",
+        'X' => "-..-",
+        'Y' => "-.--",
+        'Z' => "--..",
+        '0' => "-----",
+        '1' => ".----",
+        '2' => "..---",
+        '3' => "...--",
+        '4' => "....-",
+        '5' => ".....",
+        '6' => "-....",
+        '7' => "--...",
+        '8' => "---..",
+        '9' => "----.",
+        '/' => "-..-.",
+        '-' => "-....-",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Morse for a message: letters separated by spaces, words by " / "
+pub fn morse(message: &str) -> String {
+    message
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter_map(morse_code)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+/// Render Morse as 16-bit PCM using standard PARIS timing
+fn cw_samples(morse: &str, wpm: u32, tone_hz: f64) -> Vec<i16> {
+    let unit = (1.2 / wpm.max(1) as f64 * CW_SAMPLE_RATE as f64).round() as usize;
+    let ramp = (CW_SAMPLE_RATE as usize / 200).min(unit / 2); // 5 ms, avoids key clicks
+    let mut samples = Vec::new();
+
+    let tone = |samples: &mut Vec<i16>, units: usize| {
+        let len = unit * units;
+        for i in 0..len {
+            let envelope = (i.min(len - i) as f64 / ramp.max(1) as f64).min(1.0);
+            let t = i as f64 / CW_SAMPLE_RATE as f64;
+            samples.push(((TAU * tone_hz * t).sin() * envelope * 0.5 * i16::MAX as f64) as i16);
+        }
+    };
+    let silence = |samples: &mut Vec<i16>, units: usize| {
+        samples.extend(std::iter::repeat_n(0, unit * units));
+    };
+
+    for symbol in morse.chars() {
+        match symbol {
+            '.' => tone(&mut samples, 1),
+            '-' => tone(&mut samples, 3),
+            ' ' => silence(&mut samples, 2), // plus the trailing gap: 3 units between letters
+            '/' => silence(&mut samples, 2), // between two letter gaps: 7 units between words
+            _ => continue,
+        }
+        if symbol == '.' || symbol == '-' {
+            silence(&mut samples, 1);
+        }
+    }
+
+    samples
+}
+
+fn wav_bytes(samples: &[i16]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&CW_SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(CW_SAMPLE_RATE * 2).to_le_bytes()); // byte rate
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // block align
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_morse() {
+        assert_eq!(morse("AOS AO-91"), ".- --- ... / .- --- -....- ----. .----");
+        assert_eq!(morse("  sos  "), "... --- ...");
+    }
+
+    #[test]
+    fn test_cw_timing() {
+        // "E" at 12 WPM: one 100 ms dot followed by a one-unit gap
+        let samples = cw_samples(".", 12, 700.0);
+        assert_eq!(samples.len(), 2 * 800);
+
+        let wav = wav_bytes(&samples);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(wav.len(), 44 + samples.len() * 2);
+    }
+
+    #[test]
+    fn test_powershell_command() {
+        let script = "Speak('AOS ISS')";
+        let espeak = command("espeak", "AOS ISS", script);
+        assert_eq!(espeak.get_args().collect::<Vec<_>>(), ["AOS ISS"]);
+
+        let powershell = command(r"C:\Windows\System32\powershell.exe", "AOS ISS", script);
+        assert_eq!(powershell.get_args().last(), Some(script.as_ref()));
+        assert!(is_powershell("pwsh"));
+        assert_eq!(powershell_quote("AO-7's beacon"), "AO-7''s beacon");
+    }
+
+    #[test]
+    fn test_cw_file_removed_after_playing() {
+        let config = AnnouncerConfig {
+            method: AnnounceMethod::Cw,
+            player: "true".to_string(),
+            ..AnnouncerConfig::default()
+        };
+        let mut announcer = Announcer::default();
+        announcer.announce(&config, "AOS");
+        let path = announcer.playing[0].1.clone().unwrap();
+        assert!(path.exists());
+        for _ in 0..100 {
+            announcer.reap();
+            if announcer.playing.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(announcer.playing.is_empty());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_elevation_events() {
+        let config = AnnouncerConfig {
+            elevation_milestones: vec![30.0],
+            ..Default::default()
+        };
+        let position = |elevation: f64| SatellitePosition {
+            name: "AO-91".to_string(),
+            time: Utc::now(),
+            latitude: 0.0,
+            longitude: 0.0,
+            altitude_km: 500.0,
+            velocity_km_s: 7.6,
+            range_rate_km_s: 0.0,
+            azimuth: 0.0,
+            elevation,
+            range_km: 1000.0,
+            is_visible: elevation > 0.0,
+            doppler: None,
+            comm_window: None,
+        };
+
+        let mut announcer = Announcer::default();
+        let aliases = HashMap::new();
+        let now = Utc::now();
+        assert!(announcer
+            .events(&config, &[], &[position(-2.0)], &aliases, now)
+            .is_empty());
+        assert_eq!(
+            announcer.events(&config, &[], &[position(1.0)], &aliases, now),
+            vec!["AOS AO-91"]
+        );
+        assert_eq!(
+            announcer.events(&config, &[], &[position(31.0)], &aliases, now),
+            vec!["AO-91 elevation 30 degrees"]
+        );
+        assert!(announcer
+            .events(&config, &[], &[position(25.0)], &aliases, now)
+            .is_empty());
+        assert_eq!(
+            announcer.events(&config, &[], &[position(-0.5)], &aliases, now),
+            vec!["LOS AO-91"]
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::Duration;
+use nalgebra::Vector3;
+
+use crate::astro::angular_separation;
+use crate::config::AntennaConfig;
+use crate::horizon;
+use crate::link_budget::AntennaGain;
+use crate::observer::Observer;
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::Satellite;
+
+/// Fall-off of a main lobe without a pattern: 12 dB at twice the half-power
+/// angle, as in ITU-R S.580 and the like
+const MAIN_LOBE_DB: f64 = 12.0;
+
+/// How far below its peak a main lobe without a pattern falls at most
+const SIDELOBE_DB: f64 = 20.0;
+
+/// Measured or modelled gain of an antenna over the sky
+#[derive(Debug, Clone, Default)]
+pub struct AntennaPattern {
+    points: Vec<(Vector3<f64>, f64)>, // unit direction and gain in dBi
+}
+
+fn unit_vector(azimuth: f64, elevation: f64) -> Vector3<f64> {
+    let (az, el) = (azimuth.to_radians(), elevation.to_radians());
+    Vector3::new(el.cos() * az.cos(), el.cos() * az.sin(), el.sin())
+}
+
+impl AntennaPattern {
+    /// Build a pattern from (azimuth, elevation, gain dBi) samples
+    pub fn from_points(points: &[(f64, f64, f64)]) -> Self {
+        Self {
+            points: points
+                .iter()
+                .map(|&(azimuth, elevation, gain)| (unit_vector(azimuth, elevation), gain))
+                .collect(),
+        }
+    }
+
+    /// Load a pattern from NEC-2 output (its radiation pattern table) or a CSV
+    /// file with `azimuth,elevation,gain_dbi` rows
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("reading antenna pattern '{}'", path.display()))?;
+        let pattern = if contents.contains("RADIATION PATTERNS") {
+            Self::parse_nec(&contents)
+        } else {
+            Self::parse_csv(&contents)
+        }
+        .with_context(|| format!("in antenna pattern '{}'", path.display()))?;
+        anyhow::ensure!(!pattern.points.is_empty(), "no gains in '{}'", path.display());
+        Ok(pattern)
+    }
+
+    /// `azimuth,elevation,gain_dbi` rows, as read by `horizon::csv_rows`
+    fn parse_csv(contents: &str) -> Result<Self> {
+        let mut points = Vec::new();
+        for (line_no, first, line) in horizon::csv_rows(contents) {
+            let fields: Vec<Result<f64, _>> =
+                line.split(',').map(|field| field.trim().parse::<f64>()).collect();
+            match fields.as_slice() {
+                [Ok(az), Ok(el), Ok(gain), ..] => points.push((*az, *el, *gain)),
+                _ if first => continue, // header row
+                _ => anyhow::bail!(
+                    "line {}: expected 'azimuth,elevation,gain_dbi', got '{}'",
+                    line_no,
+                    line
+                ),
+            }
+        }
+        Ok(Self::from_points(&points))
+    }
+
+    /// Rows of THETA, PHI and the vertical, horizontal and total gains under
+    /// NEC's RADIATION PATTERNS heading. THETA is from the zenith, and PHI
+    /// counter-clockwise from the model's x axis, which is taken to point north.
+    fn parse_nec(contents: &str) -> Result<Self> {
+        let mut points = Vec::new();
+        let table = contents
+            .split_once("RADIATION PATTERNS")
+            .map_or("", |(_, table)| table);
+        for line in table.lines() {
+            let fields: Vec<f64> = line
+                .split_whitespace()
+                .take(5)
+                .map_while(|field| field.parse().ok())
+                .collect();
+            if let [theta, phi, _, _, total] = fields[..] {
+                points.push(((-phi).rem_euclid(360.0), 90.0 - theta, total));
+            } else if !points.is_empty() && !line.trim().is_empty() {
+                // The end of the table
+                break;
+            }
+        }
+        Ok(Self::from_points(&points))
+    }
+
+    pub fn peak_dbi(&self) -> f64 {
+        self.points
+            .iter()
+            .map(|(_, gain)| *gain)
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    /// Gain of the closest sample to a direction
+    pub fn gain_at(&self, azimuth: f64, elevation: f64) -> f64 {
+        let direction = unit_vector(azimuth, elevation);
+        self.points
+            .iter()
+            .max_by(|a, b| direction.dot(&a.0).total_cmp(&direction.dot(&b.0)))
+            .map_or(f64::NEG_INFINITY, |(_, gain)| *gain)
+    }
+
+    /// Gain of the sample closest to `angle` degrees off a boresight at
+    /// azimuth and elevation 0, in whichever direction
+    pub fn gain_off_axis(&self, angle: f64) -> f64 {
+        let distance = |direction: &Vector3<f64>| {
+            (direction.x.clamp(-1.0, 1.0).acos().to_degrees() - angle).abs()
+        };
+        self.points
+            .iter()
+            .min_by(|a, b| distance(&a.0).total_cmp(&distance(&b.0)))
+            .map_or(f64::NEG_INFINITY, |(_, gain)| *gain)
+    }
+}
+
+/// Portion of a pass that falls inside the antenna beam
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeamCoverage {
+    pub inside_seconds: f64,
+    pub total_seconds: f64,
+}
+
+impl BeamCoverage {
+    pub fn fraction(&self) -> f64 {
+        if self.total_seconds > 0.0 {
+            self.inside_seconds / self.total_seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+impl AntennaConfig {
+    /// Direction the antenna points: fixed, or following the target
+    pub fn pointing(&self, target_azimuth: f64, target_elevation: f64) -> (f64, f64) {
+        if self.fixed {
+            (self.fixed_azimuth, self.fixed_elevation)
+        } else {
+            (target_azimuth, target_elevation.max(0.0))
+        }
+    }
+
+    /// Whether a direction lies within the half-power beam around the pointing direction
+    pub fn in_beam(&self, pointing: (f64, f64), azimuth: f64, elevation: f64) -> bool {
+        angular_separation(pointing.0, pointing.1, azimuth, elevation) <= self.beamwidth_deg / 2.0
+    }
+
+    /// Gain toward a satellite at `azimuth`/`elevation`, next to the peak.
+    /// A fixed antenna's pattern is looked up in the sky as mounted; a
+    /// tracking antenna's is taken to have its boresight at azimuth and
+    /// elevation 0 and is looked up by how far off the target it points,
+    /// treating the beam as round.
+    /// Without a pattern, `gain_dbi` falls off across a parabolic main lobe.
+    pub fn gain_toward(
+        &self,
+        pattern: Option<&AntennaPattern>,
+        azimuth: f64,
+        elevation: f64,
+    ) -> Option<AntennaGain> {
+        let pointing = self.pointing(azimuth, elevation);
+        let off_axis = angular_separation(pointing.0, pointing.1, azimuth, elevation);
+        if let Some(pattern) = pattern {
+            let toward_dbi = if self.fixed {
+                pattern.gain_at(azimuth, elevation)
+            } else {
+                pattern.gain_off_axis(off_axis)
+            };
+            return Some(AntennaGain {
+                toward_dbi,
+                peak_dbi: pattern.peak_dbi(),
+            });
+        }
+        self.gain_dbi.map(|peak_dbi| {
+            let fall_off = MAIN_LOBE_DB * (off_axis / self.beamwidth_deg).powi(2);
+            AntennaGain {
+                toward_dbi: peak_dbi - fall_off.min(SIDELOBE_DB),
+                peak_dbi,
+            }
+        })
+    }
+
+    /// Outline of the beam cone as (azimuth, elevation) points, for plotting
+    pub fn beam_outline(&self, pointing: (f64, f64), segments: usize) -> Vec<(f64, f64)> {
+        let (az0, el0) = (pointing.0.to_radians(), pointing.1.to_radians());
+        let radius = (self.beamwidth_deg / 2.0).to_radians();
+
+        (0..=segments)
+            .map(|i| {
+                let bearing = (i as f64 / segments as f64) * std::f64::consts::TAU;
+                let el = (el0.sin() * radius.cos() + el0.cos() * radius.sin() * bearing.cos())
+                    .clamp(-1.0, 1.0)
+                    .asin();
+                let az = az0
+                    + (bearing.sin() * radius.sin() * el0.cos())
+                        .atan2(radius.cos() - el0.sin() * el.sin());
+                (az.to_degrees().rem_euclid(360.0), el.to_degrees())
+            })
+            .collect()
+    }
+}
+
+/// Sample a pass and measure how long the satellite stays inside a fixed beam
+pub fn pass_beam_coverage(
+    satellite: &Satellite,
+    pass: &SatellitePass,
+    observer: &Observer,
+    antenna: &AntennaConfig,
+) -> BeamCoverage {
+    let step = Duration::seconds(10);
+    let mut inside_seconds = 0.0;
+    let mut time = pass.aos_time;
+
+    while time < pass.los_time {
+        if let Ok(position) = satellite.calculate_position(time, observer) {
+            let pointing = antenna.pointing(position.azimuth, position.elevation);
+            if antenna.in_beam(pointing, position.azimuth, position.elevation) {
+                inside_seconds += step.num_seconds() as f64;
+            }
+        }
+        time += step;
+    }
+
+    BeamCoverage {
+        inside_seconds: inside_seconds.min(pass.duration_seconds),
+        total_seconds: pass.duration_seconds,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eggbeater() -> AntennaConfig {
+        AntennaConfig {
+            name: "eggbeater".to_string(),
+            beamwidth_deg: 90.0,
+            fixed: true,
+            fixed_azimuth: 0.0,
+            fixed_elevation: 90.0,
+            gain_dbi: None,
+            pattern_file: None,
+            polarization: None,
+            mapping: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_fixed_zenith_beam() {
+        let antenna = eggbeater();
+        let pointing = antenna.pointing(123.0, 10.0);
+        assert_eq!(pointing, (0.0, 90.0));
+        assert!(antenna.in_beam(pointing, 200.0, 60.0));
+        assert!(!antenna.in_beam(pointing, 200.0, 30.0));
+    }
+
+    #[test]
+    fn test_tracking_beam_follows_target() {
+        let antenna = AntennaConfig {
+            fixed: false,
+            ..eggbeater()
+        };
+        let pointing = antenna.pointing(123.0, 10.0);
+        assert!(antenna.in_beam(pointing, 123.0, 10.0));
+    }
+
+    #[test]
+    fn test_beam_outline_radius() {
+        let antenna = AntennaConfig {
+            beamwidth_deg: 30.0,
+            ..eggbeater()
+        };
+        for (az, el) in antenna.beam_outline((90.0, 40.0), 16) {
+            let separation = angular_separation(90.0, 40.0, az, el);
+            assert!((separation - 15.0).abs() < 1e-6, "separation {}", separation);
+        }
+    }
+
+    #[test]
+    fn test_antenna_gain() {
+        // Without a pattern: 3 dB down at the edge of the beam
+        let antenna = AntennaConfig {
+            gain_dbi: Some(3.0),
+            ..eggbeater()
+        };
+        let gain = antenna.gain_toward(None, 10.0, 90.0).unwrap();
+        assert_eq!(gain.toward_dbi, 3.0);
+        let gain = antenna.gain_toward(None, 10.0, 45.0).unwrap();
+        assert!((gain.toward_dbi - 0.0).abs() < 1e-9);
+        assert_eq!(antenna.gain_toward(None, 10.0, 0.0).unwrap().toward_dbi, -9.0);
+        assert!(eggbeater().gain_toward(None, 10.0, 45.0).is_none());
+
+        // A fixed eggbeater's pattern, looked up where the satellite is
+        let csv = "azimuth,elevation,gain_dbi\n# eggbeater\n\
+                   0,90,3.0\n0,45,0.5\n180,45,0.0\n0,5,-6.0\n";
+        let pattern = AntennaPattern::parse_csv(csv).unwrap();
+        assert_eq!(pattern.peak_dbi(), 3.0);
+        let gain = eggbeater().gain_toward(Some(&pattern), 170.0, 40.0).unwrap();
+        assert_eq!((gain.toward_dbi, gain.peak_dbi), (0.0, 3.0));
+        assert_eq!(eggbeater().gain_toward(Some(&pattern), 20.0, 8.0).unwrap().toward_dbi, -6.0);
+        assert!(AntennaPattern::parse_csv("0,90,3\n0,x,1\n").is_err());
+
+        // A tracking yagi modelled in NEC along x, looked up off its boresight
+        let nec = "
+                               - - - RADIATION PATTERNS - - -
+
+  - - ANGLES - -         - POWER GAINS -       - - - POLARIZATION - - -
+  THETA     PHI       VERT.   HOR.    TOTAL     AXIAL     TILT  SENSE
+ DEGREES  DEGREES      DB      DB      DB       RATIO     DEG.
+   90.00      0.00    -999.99  11.20   11.20   0.00000    90.00 LINEAR
+   90.00     20.00    -999.99   8.10    8.10   0.00000    90.00 LINEAR
+   90.00     40.00    -999.99   1.30    1.30   0.00000    90.00 LINEAR
+
+ ***** DATA CARD NO.   9 EN   0     0     0      0  0.00000E+00
+";
+        let pattern = AntennaPattern::parse_nec(nec).unwrap();
+        assert_eq!(pattern.points.len(), 3);
+        assert_eq!(pattern.gain_at(340.0, 0.0), 8.1);
+        let yagi = AntennaConfig {
+            fixed: false,
+            ..eggbeater()
+        };
+        assert_eq!(yagi.gain_toward(Some(&pattern), 123.0, 30.0).unwrap().toward_dbi, 11.2);
+        // Parked above the horizon while the satellite is still below it
+        let gain = yagi.gain_toward(Some(&pattern), 123.0, -18.0).unwrap();
+        assert_eq!(gain.toward_dbi, 8.1);
+    }
+}
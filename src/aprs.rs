@@ -0,0 +1,225 @@
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::config::{AprsConfig, DigipeaterConfig};
+use crate::observer::Observer;
+use crate::pass_prediction::SatellitePass;
+use crate::pass_process::{self, PassProcess};
+use crate::satellite::Satellite;
+use crate::transponder;
+
+impl DigipeaterConfig {
+    /// TNC2-style header to send through this digipeater
+    pub fn path_for(&self, callsign: &str) -> String {
+        format!("{}>APRS,{}", callsign, self.path)
+    }
+}
+
+impl AprsConfig {
+    /// Digipeater settings for a satellite, if it is one
+    pub fn digipeater_for(&self, satellite_name: &str) -> Option<&DigipeaterConfig> {
+        self.digipeaters
+            .iter()
+            .find(|digi| transponder::matches_satellite(&digi.satellite, satellite_name))
+    }
+}
+
+/// Part of a pass above `min_elevation`, found by stepping through it
+pub fn window_above(
+    aos: DateTime<Utc>,
+    los: DateTime<Utc>,
+    min_elevation: f64,
+    elevation_at: impl Fn(DateTime<Utc>) -> Option<f64>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let step = Duration::seconds(5);
+    let mut window: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+    let mut time = aos;
+    while time <= los {
+        if elevation_at(time).is_some_and(|el| el >= min_elevation) {
+            window = Some(window.map_or((time, time), |(start, _)| (start, time)));
+        }
+        time += step;
+    }
+    window
+}
+
+/// Usable high-elevation part of a pass for APRS
+pub fn high_elevation_window(
+    satellite: &Satellite,
+    pass: &SatellitePass,
+    observer: &Observer,
+    min_elevation: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    window_above(pass.aos_time, pass.los_time, min_elevation, |time| {
+        satellite
+            .calculate_position(time, observer)
+            .ok()
+            .map(|pos| pos.elevation)
+    })
+}
+
+/// Direwolf configuration for working a digipeater from the observer's location
+pub fn direwolf_config(
+    config: &AprsConfig,
+    digipeater: &DigipeaterConfig,
+    observer: &Observer,
+) -> String {
+    let mut lines = vec![
+        format!("ADEVICE {}", config.audio_device),
+        "CHANNEL 0".to_string(),
+        format!("MYCALL {}", config.callsign),
+        "MODEM 1200".to_string(),
+    ];
+    if let Some(comment) = &config.beacon_comment {
+        lines.push(format!(
+            "PBEACON delay=0:10 every=1:00 via={} lat={:.4} long={:.4} comment=\"{}\"",
+            digipeater.path, observer.latitude, observer.longitude, comment
+        ));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Starts Direwolf at AOS of a digipeater pass and stops it at LOS
+pub struct DirewolfLauncher {
+    pub status: String,
+    process: Option<(PassProcess, DateTime<Utc>)>, // running Direwolf and the LOS it stops at
+    attempted_aos: Option<DateTime<Utc>>,
+}
+
+impl DirewolfLauncher {
+    pub fn new() -> Self {
+        Self {
+            status: "Waiting for AOS".to_string(),
+            process: None,
+            attempted_aos: None,
+        }
+    }
+
+    /// The generated configuration, kept with the rest of the user's crabtrack data
+    fn config_path() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("crabtrack")
+            .join("direwolf.conf")
+    }
+
+    fn launch(command: &str, config_text: &str) -> Result<PassProcess> {
+        let path = Self::config_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        std::fs::write(&path, config_text)
+            .with_context(|| format!("writing {}", path.display()))?;
+        let mut command = pass_process::command(command, &[])?;
+        command
+            .arg("-c")
+            .arg(&path)
+            .arg("-t")
+            .arg("0")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        PassProcess::spawn(&mut command)
+    }
+
+    /// Launch Direwolf when a pass of any configured digipeater begins
+    pub fn update(
+        &mut self,
+        config: &AprsConfig,
+        satellites: &[Satellite],
+        observer: &Observer,
+        now: DateTime<Utc>,
+    ) {
+        let Some(command) = &config.direwolf_command else {
+            return;
+        };
+        if self.process.as_ref().is_some_and(|(_, los)| now >= *los) {
+            self.process = None; // stops Direwolf
+            self.status = "Waiting for AOS".to_string();
+        }
+        if self.process.is_some() {
+            return;
+        }
+
+        let current = satellites.iter().find_map(|satellite| {
+            let digipeater = config.digipeater_for(&satellite.name)?;
+            let pass = satellite.get_current_pass(now)?;
+            Some((satellite, digipeater, pass))
+        });
+        let Some((satellite, digipeater, pass)) = current else {
+            return;
+        };
+        // One launch per pass, so a bad command isn't retried every frame
+        if self.attempted_aos == Some(pass.aos_time) {
+            return;
+        }
+        self.attempted_aos = Some(pass.aos_time);
+
+        match Self::launch(command, &direwolf_config(config, digipeater, observer)) {
+            Ok(process) => {
+                self.process = Some((process, pass.los_time));
+                self.status = format!("Running for {}", satellite.name);
+            }
+            Err(e) => self.status = format!("Error: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_digipeater_lookup_and_path() {
+        let config = AprsConfig {
+            callsign: "N0CALL-6".to_string(),
+            ..Default::default()
+        };
+        let iss = config.digipeater_for("ISS (ZARYA)").unwrap();
+        assert_eq!(iss.path_for(&config.callsign), "N0CALL-6>APRS,ARISS");
+        assert_eq!(iss.frequency_mhz, 145.825);
+        assert!(config.digipeater_for("PSAT (NO-84)").is_some());
+        assert!(config.digipeater_for("AO-91").is_none());
+    }
+
+    #[test]
+    fn test_window_above() {
+        let aos = Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap();
+        let los = aos + Duration::minutes(10);
+        // Symmetric pass peaking at 50° five minutes in
+        let elevation = |time: DateTime<Utc>| {
+            let minutes = (time - aos).num_seconds() as f64 / 60.0;
+            Some(50.0 - 2.0 * (minutes - 5.0).powi(2))
+        };
+
+        let (start, end) = window_above(aos, los, 20.0, elevation).unwrap();
+        // 50 - 2(m-5)^2 >= 20 for |m-5| <= 3.87
+        assert_eq!(start, aos + Duration::seconds(70));
+        assert_eq!(end, aos + Duration::seconds(530));
+        assert!(window_above(aos, los, 60.0, elevation).is_none());
+    }
+
+    #[test]
+    fn test_direwolf_config() {
+        let mut config = AprsConfig {
+            callsign: "N0CALL-6".to_string(),
+            ..Default::default()
+        };
+        let observer = Observer::new("Home".to_string(), 40.0, -105.25, 1600.0);
+        let iss = config.digipeater_for("ISS").unwrap().clone();
+
+        let text = direwolf_config(&config, &iss, &observer);
+        assert!(text.contains("MYCALL N0CALL-6\n"));
+        assert!(text.contains("MODEM 1200\n"));
+        assert!(!text.contains("PBEACON"));
+
+        config.beacon_comment = Some("via crabtrack".to_string());
+        let text = direwolf_config(&config, &iss, &observer);
+        assert!(text.contains("via=ARISS lat=40.0000 long=-105.2500 comment=\"via crabtrack\""));
+    }
+}
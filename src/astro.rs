@@ -0,0 +1,313 @@
+use chrono::{DateTime, Utc};
+use nalgebra::Vector3;
+
+use crate::observer::{normalize_longitude, Observer};
+use crate::pass_prediction::{calculate_gmst, calculate_look_angles, LookAngles};
+
+const AU_KM: f64 = 149_597_870.7;
+const EARTH_RADIUS_KM: f64 = 6378.137;
+
+/// Twilight state of the observer, derived from the Sun's elevation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwilightState {
+    Day,
+    Civil,
+    Nautical,
+    Astronomical,
+    Night,
+}
+
+impl TwilightState {
+    pub fn from_sun_elevation(elevation: f64) -> Self {
+        if elevation >= -0.833 {
+            TwilightState::Day
+        } else if elevation >= -6.0 {
+            TwilightState::Civil
+        } else if elevation >= -12.0 {
+            TwilightState::Nautical
+        } else if elevation >= -18.0 {
+            TwilightState::Astronomical
+        } else {
+            TwilightState::Night
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            TwilightState::Day => "Day",
+            TwilightState::Civil => "Civil twilight",
+            TwilightState::Nautical => "Nautical twilight",
+            TwilightState::Astronomical => "Astronomical twilight",
+            TwilightState::Night => "Night",
+        }
+    }
+
+    pub fn short_str(&self) -> &str {
+        match self {
+            TwilightState::Day => "Day",
+            TwilightState::Civil => "Civil",
+            TwilightState::Nautical => "Naut",
+            TwilightState::Astronomical => "Astro",
+            TwilightState::Night => "Night",
+        }
+    }
+
+    /// Dark enough to spot a sunlit satellite by eye
+    pub fn is_dark_enough(&self) -> bool {
+        !matches!(self, TwilightState::Day | TwilightState::Civil)
+    }
+}
+
+/// Days since J2000.0 (2000-01-01 12:00 UTC)
+fn days_since_j2000(time: DateTime<Utc>) -> f64 {
+    let j2000 = chrono::NaiveDate::from_ymd_opt(2000, 1, 1)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+        .and_utc();
+
+    time.signed_duration_since(j2000).num_milliseconds() as f64 / 86400000.0
+}
+
+/// Low-precision Sun position in ECI coordinates (km), accurate to ~0.01°
+pub fn sun_position_eci(time: DateTime<Utc>) -> Vector3<f64> {
+    let n = days_since_j2000(time);
+
+    let mean_longitude = (280.460 + 0.9856474 * n).to_radians();
+    let mean_anomaly = (357.528 + 0.9856003 * n).to_radians();
+
+    let ecliptic_longitude = mean_longitude
+        + (1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin()).to_radians();
+    let obliquity = (23.439 - 0.0000004 * n).to_radians();
+    let distance_km = (1.00014
+        - 0.01671 * mean_anomaly.cos()
+        - 0.00014 * (2.0 * mean_anomaly).cos())
+        * AU_KM;
+
+    Vector3::new(
+        distance_km * ecliptic_longitude.cos(),
+        distance_km * obliquity.cos() * ecliptic_longitude.sin(),
+        distance_km * obliquity.sin() * ecliptic_longitude.sin(),
+    )
+}
+
+/// Low-precision Moon position in ECI coordinates (km), accurate to ~0.3°
+pub fn moon_position_eci(time: DateTime<Utc>) -> Vector3<f64> {
+    let t = days_since_j2000(time) / 36525.0;
+    let sin_deg = |d: f64| d.to_radians().sin();
+    let cos_deg = |d: f64| d.to_radians().cos();
+
+    let longitude = 218.32 + 481267.881 * t
+        + 6.29 * sin_deg(135.0 + 477198.87 * t)
+        - 1.27 * sin_deg(259.3 - 413335.36 * t)
+        + 0.66 * sin_deg(235.7 + 890534.22 * t)
+        + 0.21 * sin_deg(269.9 + 954397.74 * t)
+        - 0.19 * sin_deg(357.5 + 35999.05 * t)
+        - 0.11 * sin_deg(186.5 + 966404.03 * t);
+
+    let latitude = 5.13 * sin_deg(93.3 + 483202.02 * t)
+        + 0.28 * sin_deg(228.2 + 960400.89 * t)
+        - 0.28 * sin_deg(318.3 + 6003.15 * t)
+        - 0.17 * sin_deg(217.6 - 407332.21 * t);
+
+    let parallax = 0.9508
+        + 0.0518 * cos_deg(135.0 + 477198.87 * t)
+        + 0.0095 * cos_deg(259.3 - 413335.36 * t)
+        + 0.0078 * cos_deg(235.7 + 890534.22 * t)
+        + 0.0028 * cos_deg(269.9 + 954397.74 * t);
+
+    let distance_km = EARTH_RADIUS_KM / sin_deg(parallax);
+
+    // Ecliptic to equatorial direction cosines
+    let l = cos_deg(latitude) * cos_deg(longitude);
+    let m = 0.9175 * cos_deg(latitude) * sin_deg(longitude) - 0.3978 * sin_deg(latitude);
+    let n = 0.3978 * cos_deg(latitude) * sin_deg(longitude) + 0.9175 * sin_deg(latitude);
+
+    Vector3::new(l, m, n) * distance_km
+}
+
+/// Look angles from the observer to a body given in ECI km
+fn body_look_angles(body_eci_km: &Vector3<f64>, time: DateTime<Utc>, observer: &Observer) -> LookAngles {
+    calculate_look_angles(
+        &(body_eci_km * 1000.0),
+        &observer.to_ecef(),
+        calculate_gmst(time),
+        observer.latitude,
+        observer.longitude,
+    )
+}
+
+/// Azimuth/elevation of the Sun as seen by the observer
+pub fn sun_look_angles(time: DateTime<Utc>, observer: &Observer) -> LookAngles {
+    body_look_angles(&sun_position_eci(time), time, observer)
+}
+
+/// Azimuth/elevation of the Moon as seen by the observer
+pub fn moon_look_angles(time: DateTime<Utc>, observer: &Observer) -> LookAngles {
+    body_look_angles(&moon_position_eci(time), time, observer)
+}
+
+/// Illuminated fraction of the Moon's disk (0.0 = new, 1.0 = full)
+pub fn moon_illumination(time: DateTime<Utc>) -> f64 {
+    let sun = sun_position_eci(time).normalize();
+    let moon = moon_position_eci(time).normalize();
+    let elongation = sun.dot(&moon).clamp(-1.0, 1.0).acos();
+    (1.0 - elongation.cos()) / 2.0
+}
+
+/// Great-circle separation in degrees between two azimuth/elevation directions
+pub fn angular_separation(az1: f64, el1: f64, az2: f64, el2: f64) -> f64 {
+    let (az1, el1, az2, el2) = (
+        az1.to_radians(),
+        el1.to_radians(),
+        az2.to_radians(),
+        el2.to_radians(),
+    );
+    let cos_sep = el1.sin() * el2.sin() + el1.cos() * el2.cos() * (az1 - az2).cos();
+    cos_sep.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// Whether a satellite at the given ECI position (km) is illuminated by the Sun,
+/// using a cylindrical Earth shadow model
+pub fn is_sunlit(sat_eci_km: &Vector3<f64>, time: DateTime<Utc>) -> bool {
+    let sun_dir = sun_position_eci(time).normalize();
+    let along_sun = sat_eci_km.dot(&sun_dir);
+    if along_sun > 0.0 {
+        return true;
+    }
+    let perpendicular = (sat_eci_km - sun_dir * along_sun).norm();
+    perpendicular > EARTH_RADIUS_KM
+}
+
+/// Latitude and longitude in degrees of the point where the Sun is overhead
+pub fn subsolar_point(time: DateTime<Utc>) -> (f64, f64) {
+    let sun = sun_position_eci(time);
+    let longitude = sun.y.atan2(sun.x) - calculate_gmst(time);
+    (
+        (sun.z / sun.norm()).asin().to_degrees(),
+        normalize_longitude(longitude.to_degrees()),
+    )
+}
+
+/// The day/night terminator at `time`, as the latitude where the Sun is on the
+/// horizon for each longitude from -180° to 180° in `step` degrees
+pub fn terminator(time: DateTime<Utc>, step: f64) -> Vec<(f64, f64)> {
+    let (latitude, longitude) = subsolar_point(time);
+    // At an equinox the terminator runs straight from pole to pole; keep
+    // clear of dividing by zero
+    let declination = match latitude.to_radians() {
+        d if d.abs() < 1e-6 => 1e-6_f64.copysign(d),
+        d => d,
+    };
+    let steps = (360.0 / step).round() as usize;
+    (0..=steps)
+        .map(|index| {
+            let lon = -180.0 + 360.0 * index as f64 / steps as f64;
+            let hour_angle = (lon - longitude).to_radians();
+            let lat = (-hour_angle.cos() / declination.tan()).atan().to_degrees();
+            (lon, lat)
+        })
+        .collect()
+}
+
+/// Whether the Sun is below the horizon at a point on the ground
+pub fn is_night(latitude: f64, longitude: f64, subsolar: (f64, f64)) -> bool {
+    let (lat, lon) = (latitude.to_radians(), longitude.to_radians());
+    let (sun_lat, sun_lon) = (subsolar.0.to_radians(), subsolar.1.to_radians());
+    lat.sin() * sun_lat.sin() + lat.cos() * sun_lat.cos() * (lon - sun_lon).cos() < 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_twilight_thresholds() {
+        assert_eq!(TwilightState::from_sun_elevation(30.0), TwilightState::Day);
+        assert_eq!(TwilightState::from_sun_elevation(-3.0), TwilightState::Civil);
+        assert_eq!(TwilightState::from_sun_elevation(-9.0), TwilightState::Nautical);
+        assert_eq!(TwilightState::from_sun_elevation(-15.0), TwilightState::Astronomical);
+        assert_eq!(TwilightState::from_sun_elevation(-40.0), TwilightState::Night);
+        assert!(!TwilightState::Civil.is_dark_enough());
+        assert!(TwilightState::Nautical.is_dark_enough());
+    }
+
+    #[test]
+    fn test_sun_near_vernal_equinox() {
+        // Around the March equinox the Sun sits near RA 0h, Dec 0°
+        let time = Utc.with_ymd_and_hms(2024, 3, 20, 3, 6, 0).unwrap();
+        let sun = sun_position_eci(time).normalize();
+        assert!(sun.x > 0.999);
+        assert!(sun.z.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_sun_elevation_day_and_night() {
+        let observer = Observer::new("Greenwich".to_string(), 51.4779, 0.0, 0.0);
+        let noon = Utc.with_ymd_and_hms(2024, 6, 21, 12, 0, 0).unwrap();
+        let midnight = Utc.with_ymd_and_hms(2024, 6, 21, 0, 0, 0).unwrap();
+
+        let noon_el = sun_look_angles(noon, &observer).elevation;
+        let midnight_el = sun_look_angles(midnight, &observer).elevation;
+
+        // Summer solstice noon elevation at Greenwich is ~62°
+        assert!((noon_el - 62.0).abs() < 1.0, "noon elevation {}", noon_el);
+        assert!(midnight_el < -10.0, "midnight elevation {}", midnight_el);
+    }
+
+    #[test]
+    fn test_moon_distance_range() {
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let distance = moon_position_eci(time).norm();
+        assert!(distance > 356_000.0 && distance < 407_000.0);
+    }
+
+    #[test]
+    fn test_moon_illumination_phases() {
+        // Full Moon on 2024-01-25 17:54 UTC, new Moon on 2024-02-09 22:59 UTC
+        let full = Utc.with_ymd_and_hms(2024, 1, 25, 18, 0, 0).unwrap();
+        let new = Utc.with_ymd_and_hms(2024, 2, 9, 23, 0, 0).unwrap();
+        assert!(moon_illumination(full) > 0.98);
+        assert!(moon_illumination(new) < 0.02);
+    }
+
+    #[test]
+    fn test_angular_separation() {
+        assert!(angular_separation(0.0, 90.0, 180.0, 90.0).abs() < 1e-6);
+        assert!((angular_separation(0.0, 0.0, 90.0, 0.0) - 90.0).abs() < 1e-6);
+        assert!((angular_separation(350.0, 10.0, 10.0, 10.0) - 19.7).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_subsolar_point_and_terminator() {
+        // June solstice noon at Greenwich: overhead on the Tropic of Cancer,
+        // a minute or two of the equation of time off the prime meridian
+        let time = Utc.with_ymd_and_hms(2024, 6, 20, 12, 0, 0).unwrap();
+        let (latitude, longitude) = subsolar_point(time);
+        assert!((latitude - 23.44).abs() < 0.1, "latitude {}", latitude);
+        assert!(longitude.abs() < 1.0, "longitude {}", longitude);
+
+        let line = terminator(time, 10.0);
+        assert_eq!(line.len(), 37);
+        // Under the terminator the Sun is on the horizon
+        let observer = |lat: f64, lon: f64| Observer::new("T".to_string(), lat, lon, 0.0);
+        for &(lon, lat) in &line {
+            let elevation = sun_look_angles(time, &observer(lat, lon)).elevation;
+            assert!(elevation.abs() < 0.5, "{} at {}, {}", elevation, lat, lon);
+        }
+        // The North Pole has midnight sun; the far side of the globe is dark
+        assert!(!is_night(89.0, 180.0, (latitude, longitude)));
+        assert!(is_night(0.0, 180.0, (latitude, longitude)));
+    }
+
+    #[test]
+    fn test_shadow_model() {
+        let time = Utc.with_ymd_and_hms(2024, 3, 20, 3, 6, 0).unwrap();
+        let sun_dir = sun_position_eci(time).normalize();
+        let radius = EARTH_RADIUS_KM + 400.0;
+
+        assert!(is_sunlit(&(sun_dir * radius), time));
+        assert!(!is_sunlit(&(-sun_dir * radius), time));
+    }
+}
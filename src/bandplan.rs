@@ -0,0 +1,204 @@
+/// Direction a satellite sub-band may be used in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentDirection {
+    Both,
+    UplinkOnly,   // Earth-to-space
+    DownlinkOnly, // Space-to-Earth
+}
+
+/// An amateur-satellite sub-band within an amateur allocation
+#[derive(Debug, Clone)]
+pub struct SatelliteSegment {
+    pub start_mhz: f64,
+    pub end_mhz: f64,
+    pub direction: SegmentDirection,
+}
+
+/// An amateur band and its satellite sub-bands (IARU band plans)
+#[derive(Debug, Clone)]
+pub struct AmateurBand {
+    pub name: &'static str,
+    pub start_mhz: f64,
+    pub end_mhz: f64,
+    pub satellite_segments: &'static [SatelliteSegment],
+}
+
+pub const AMATEUR_BANDS: &[AmateurBand] = &[
+    AmateurBand {
+        name: "15m",
+        start_mhz: 21.0,
+        end_mhz: 21.45,
+        satellite_segments: &[SatelliteSegment {
+            start_mhz: 21.0,
+            end_mhz: 21.45,
+            direction: SegmentDirection::Both,
+        }],
+    },
+    AmateurBand {
+        name: "10m",
+        start_mhz: 28.0,
+        end_mhz: 29.7,
+        satellite_segments: &[SatelliteSegment {
+            start_mhz: 29.3,
+            end_mhz: 29.51,
+            direction: SegmentDirection::DownlinkOnly,
+        }],
+    },
+    AmateurBand {
+        name: "2m",
+        start_mhz: 144.0,
+        end_mhz: 148.0,
+        satellite_segments: &[SatelliteSegment {
+            start_mhz: 145.8,
+            end_mhz: 146.0,
+            direction: SegmentDirection::Both,
+        }],
+    },
+    AmateurBand {
+        name: "70cm",
+        start_mhz: 420.0,
+        end_mhz: 450.0,
+        satellite_segments: &[SatelliteSegment {
+            start_mhz: 435.0,
+            end_mhz: 438.0,
+            direction: SegmentDirection::Both,
+        }],
+    },
+    AmateurBand {
+        name: "23cm",
+        start_mhz: 1240.0,
+        end_mhz: 1300.0,
+        satellite_segments: &[SatelliteSegment {
+            start_mhz: 1260.0,
+            end_mhz: 1270.0,
+            direction: SegmentDirection::UplinkOnly,
+        }],
+    },
+    AmateurBand {
+        name: "13cm",
+        start_mhz: 2300.0,
+        end_mhz: 2450.0,
+        satellite_segments: &[SatelliteSegment {
+            start_mhz: 2400.0,
+            end_mhz: 2450.0,
+            direction: SegmentDirection::Both,
+        }],
+    },
+    AmateurBand {
+        name: "5cm",
+        start_mhz: 5650.0,
+        end_mhz: 5850.0,
+        satellite_segments: &[
+            SatelliteSegment {
+                start_mhz: 5650.0,
+                end_mhz: 5670.0,
+                direction: SegmentDirection::UplinkOnly,
+            },
+            SatelliteSegment {
+                start_mhz: 5830.0,
+                end_mhz: 5850.0,
+                direction: SegmentDirection::DownlinkOnly,
+            },
+        ],
+    },
+    AmateurBand {
+        name: "3cm",
+        start_mhz: 10000.0,
+        end_mhz: 10500.0,
+        satellite_segments: &[SatelliteSegment {
+            start_mhz: 10450.0,
+            end_mhz: 10500.0,
+            direction: SegmentDirection::Both,
+        }],
+    },
+    AmateurBand {
+        name: "1.2cm",
+        start_mhz: 24000.0,
+        end_mhz: 24250.0,
+        satellite_segments: &[SatelliteSegment {
+            start_mhz: 24000.0,
+            end_mhz: 24050.0,
+            direction: SegmentDirection::Both,
+        }],
+    },
+];
+
+fn contains(start: f64, end: f64, freq_mhz: f64) -> bool {
+    freq_mhz >= start && freq_mhz <= end
+}
+
+/// Find the amateur band a frequency falls in
+pub fn band_for(freq_mhz: f64) -> Option<&'static AmateurBand> {
+    AMATEUR_BANDS
+        .iter()
+        .find(|band| contains(band.start_mhz, band.end_mhz, freq_mhz))
+}
+
+/// Warning for transmitting on the given uplink frequency, if any
+pub fn uplink_warning(freq_mhz: f64) -> Option<String> {
+    let Some(band) = band_for(freq_mhz) else {
+        return Some(format!(
+            "Uplink {:.3} MHz is outside the amateur allocations",
+            freq_mhz
+        ));
+    };
+
+    let segment = band
+        .satellite_segments
+        .iter()
+        .find(|seg| contains(seg.start_mhz, seg.end_mhz, freq_mhz));
+
+    match segment {
+        None => Some(format!(
+            "Uplink {:.3} MHz is outside the {} satellite sub-band",
+            freq_mhz, band.name
+        )),
+        Some(seg) if seg.direction == SegmentDirection::DownlinkOnly => Some(format!(
+            "Uplink {:.3} MHz is in a downlink-only {} segment",
+            freq_mhz, band.name
+        )),
+        Some(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uplink_in_satellite_segment() {
+        assert!(uplink_warning(145.900).is_none());
+        assert!(uplink_warning(435.500).is_none());
+        assert!(uplink_warning(1268.0).is_none());
+        // QO-100's uplink and the 15 m satellite band
+        assert!(uplink_warning(2400.1).is_none());
+        assert!(uplink_warning(21.2).is_none());
+        assert!(uplink_warning(5668.0).is_none());
+        assert!(uplink_warning(5840.0).unwrap().contains("downlink-only 5cm"));
+    }
+
+    #[test]
+    fn test_uplink_outside_satellite_segment() {
+        let warning = uplink_warning(145.500).unwrap();
+        assert!(warning.contains("2m satellite sub-band"));
+    }
+
+    #[test]
+    fn test_uplink_on_downlink_only_segment() {
+        let warning = uplink_warning(29.400).unwrap();
+        assert!(warning.contains("downlink-only 10m"));
+    }
+
+    #[test]
+    fn test_uplink_outside_amateur_bands() {
+        let warning = uplink_warning(137.100).unwrap();
+        assert!(warning.contains("outside the amateur allocations"));
+    }
+
+    #[test]
+    fn test_band_lookup() {
+        assert_eq!(band_for(436.795).map(|b| b.name), Some("70cm"));
+        assert_eq!(band_for(10489.5).map(|b| b.name), Some("3cm"));
+        assert!(band_for(3400.0).is_none());
+    }
+}
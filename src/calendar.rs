@@ -0,0 +1,299 @@
+//! Pass calendars that calendar clients subscribe to. `crabtrack daemon
+//! --calendar ADDRESS` serves an iCalendar feed of every tracked satellite's
+//! passes at `/passes.ics` and one per satellite at `/<satellite>.ics`,
+//! re-predicted every hour, so subscribed calendars follow new elements
+//! without anything being exported and imported again.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::slug;
+use crate::ui::azimuth_to_cardinal;
+
+/// How often the passes are predicted again, and how often clients are asked
+/// to fetch the feed
+pub const REFRESH_MINUTES: i64 = 60;
+
+/// Passes the feeds are rendered from
+#[derive(Debug, Default)]
+struct Feed {
+    observer: String,
+    passes: Vec<(String, SatellitePass)>, // (satellite, pass), ordered by AOS
+    updated: Option<DateTime<Utc>>,
+}
+
+/// Text with the characters iCalendar treats specially escaped
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// A content line, folded into lines of at most 75 octets ending in CRLF
+fn push_line(ics: &mut String, line: &str) {
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            ics.push_str("\r\n ");
+            length = 1;
+        }
+        ics.push(c);
+        length += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// An iCalendar of `passes`, one event from AOS to LOS for each
+pub fn render(
+    title: &str,
+    observer: &str,
+    passes: &[(String, SatellitePass)],
+    now: DateTime<Utc>,
+) -> String {
+    let mut ics = String::new();
+    let refresh = format!("PT{}M", REFRESH_MINUTES);
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//crabtrack//passes//EN",
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+        &format!("X-WR-CALNAME:{}", escape(title)),
+        &format!("REFRESH-INTERVAL;VALUE=DURATION:{}", refresh),
+        &format!("X-PUBLISHED-TTL:{}", refresh),
+    ] {
+        push_line(&mut ics, line);
+    }
+    for (name, pass) in passes {
+        let mut description = format!(
+            "AOS {:.0}° {}, max {:.1}° at {:.0}° {}, LOS {:.0}° {}, {:.0} min",
+            pass.aos_azimuth,
+            azimuth_to_cardinal(pass.aos_azimuth),
+            pass.max_elevation,
+            pass.max_azimuth,
+            azimuth_to_cardinal(pass.max_azimuth),
+            pass.los_azimuth,
+            azimuth_to_cardinal(pass.los_azimuth),
+            pass.duration_minutes()
+        );
+        if pass.is_visual() {
+            description.push_str(". Visible by eye");
+        }
+        for line in [
+            "BEGIN:VEVENT",
+            // Passes keep their id when re-predicted unless AOS moves by a minute
+            &format!(
+                "UID:{}-{}@crabtrack",
+                slug(name),
+                pass.aos_time.timestamp() / 60
+            ),
+            &format!("DTSTAMP:{}", timestamp(now)),
+            &format!("DTSTART:{}", timestamp(pass.aos_time)),
+            &format!("DTEND:{}", timestamp(pass.los_time)),
+            &format!(
+                "SUMMARY:{}",
+                escape(&format!("{} pass, max {:.0}°", name, pass.max_elevation))
+            ),
+            &format!("LOCATION:{}", escape(observer)),
+            &format!("DESCRIPTION:{}", escape(&description)),
+            "TRANSP:TRANSPARENT",
+            "END:VEVENT",
+        ] {
+            push_line(&mut ics, line);
+        }
+    }
+    push_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+/// Status line, content type and body for a request of `path`
+fn respond(feed: &Feed, path: &str, now: DateTime<Utc>) -> (&'static str, &'static str, String) {
+    let path = path.split('?').next().unwrap_or_default();
+    let upcoming: Vec<(String, SatellitePass)> = feed
+        .passes
+        .iter()
+        .filter(|(_, pass)| pass.los_time > now)
+        .cloned()
+        .collect();
+    let mut names: Vec<&str> = Vec::new();
+    for (name, _) in &feed.passes {
+        if !names.contains(&name.as_str()) {
+            names.push(name);
+        }
+    }
+
+    if path == "/" {
+        let mut index = String::from("/passes.ics\tall satellites\n");
+        for name in &names {
+            index.push_str(&format!("/{}.ics\t{}\n", slug(name), name));
+        }
+        return ("200 OK", "text/plain; charset=utf-8", index);
+    }
+    let Some(feed_name) = path.strip_prefix('/').and_then(|p| p.strip_suffix(".ics")) else {
+        return ("404 Not Found", "text/plain", "Not found\n".to_string());
+    };
+    if feed_name == "passes" {
+        let title = format!("Satellite passes for {}", feed.observer);
+        return (
+            "200 OK",
+            "text/calendar; charset=utf-8",
+            render(&title, &feed.observer, &upcoming, now),
+        );
+    }
+    match names.iter().find(|name| slug(name) == feed_name) {
+        Some(name) => {
+            let passes: Vec<(String, SatellitePass)> = upcoming
+                .into_iter()
+                .filter(|(satellite, _)| satellite == name)
+                .collect();
+            let title = format!("{} passes for {}", name, feed.observer);
+            (
+                "200 OK",
+                "text/calendar; charset=utf-8",
+                render(&title, &feed.observer, &passes, now),
+            )
+        }
+        None => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+    }
+}
+
+/// Answer one HTTP request and close the connection
+fn serve(feed: &Mutex<Feed>, stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or("/");
+    let (status, content_type, body) = match method {
+        "GET" | "HEAD" => respond(&feed.lock().unwrap(), path, Utc::now()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method not allowed\n".to_string(),
+        ),
+    };
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+    if method != "HEAD" {
+        stream.write_all(body.as_bytes())?;
+    }
+    stream.flush()?;
+    Ok(())
+}
+
+/// HTTP server for the feeds, answering from the passes last given to `update`
+pub struct CalendarServer {
+    pub address: SocketAddr,
+    feed: Arc<Mutex<Feed>>,
+}
+
+impl CalendarServer {
+    pub fn bind(address: &str) -> Result<Self> {
+        let listener = TcpListener::bind(address)
+            .with_context(|| format!("listening for calendar clients on {}", address))?;
+        let address = listener.local_addr()?;
+        let feed = Arc::new(Mutex::new(Feed::default()));
+        let shared = feed.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let feed = shared.clone();
+                std::thread::spawn(move || {
+                    let _ = serve(&feed, stream);
+                });
+            }
+        });
+        Ok(Self { address, feed })
+    }
+
+    /// Whether the passes are due to be predicted again
+    pub fn needs_update(&self, now: DateTime<Utc>) -> bool {
+        let updated = self.feed.lock().unwrap().updated;
+        updated.is_none_or(|updated| now - updated >= Duration::minutes(REFRESH_MINUTES))
+    }
+
+    pub fn update(&self, observer: &str, passes: Vec<(String, SatellitePass)>, now: DateTime<Utc>) {
+        let mut feed = self.feed.lock().unwrap();
+        feed.observer = observer.to_string();
+        feed.passes = passes;
+        feed.updated = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::sample_pass;
+    use chrono::TimeZone;
+    use std::io::Read;
+
+    #[test]
+    fn test_calendar_feeds() {
+        let now = Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 0).unwrap();
+        let server = CalendarServer::bind("127.0.0.1:0").unwrap();
+        assert!(server.needs_update(now));
+        let pass = |name, aos| sample_pass(name, aos, aos + Duration::minutes(10), 52.3);
+        server.update(
+            "Home, Boulder",
+            vec![
+                pass("ISS (ZARYA)", now - Duration::hours(1)),
+                pass("ISS (ZARYA)", now + Duration::hours(1)),
+                pass("SO-50", now + Duration::hours(2)),
+            ],
+            now,
+        );
+        assert!(!server.needs_update(now + Duration::minutes(59)));
+
+        let feed = server.feed.lock().unwrap();
+        let (status, _, ics) = respond(&feed, "/passes.ics", now);
+        assert_eq!(status, "200 OK");
+        // The pass that has ended is left out
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART:20260421T190000Z\r\nDTEND:20260421T191000Z\r\n"));
+        assert!(ics.contains("SUMMARY:ISS (ZARYA) pass\\, max 52°\r\n"));
+        assert!(ics.contains("LOCATION:Home\\, Boulder\r\n"));
+        assert!(ics.lines().all(|line| line.len() <= 75));
+
+        let (_, _, ics) = respond(&feed, "/so_50.ics", now);
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("X-WR-CALNAME:SO-50 passes for Home\\, Boulder\r\n"));
+        assert_eq!(respond(&feed, "/ao_91.ics", now).0, "404 Not Found");
+        let (_, _, index) = respond(&feed, "/", now);
+        assert_eq!(
+            index,
+            "/passes.ics\tall satellites\n/iss_zarya.ics\tISS (ZARYA)\n/so_50.ics\tSO-50\n"
+        );
+        drop(feed);
+
+        let mut stream = TcpStream::connect(server.address).unwrap();
+        stream
+            .write_all(b"GET /nothing HTTP/1.1\r\nHost: x\r\n\r\n")
+            .unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+        assert!(reply.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
@@ -0,0 +1,293 @@
+use anyhow::Result;
+
+use crate::database::SatelliteDetails;
+
+const GP_URL: &str = "https://celestrak.org/NORAD/elements/gp.php";
+const SATCAT_URL: &str = "https://celestrak.org/satcat/records.php";
+
+/// A single satellite returned by a Celestrak GP query
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogEntry {
+    pub name: String,
+    pub norad_id: u32,
+    pub tle_line1: String,
+    pub tle_line2: String,
+}
+
+/// Fetch a URL and return the body as text
+pub fn fetch_text(url: &str) -> Result<String> {
+    use std::io::Read;
+
+    let response = ureq::get(url)
+        .timeout(std::time::Duration::from_secs(30))
+        .call()
+        .map_err(|e| anyhow::anyhow!("HTTP request failed: {}", e))?;
+
+    if response.status() != 200 {
+        return Err(anyhow::anyhow!(
+            "Celestrak returned status: {}",
+            response.status()
+        ));
+    }
+
+    let mut body = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| anyhow::anyhow!("Failed to read response: {}", e))?;
+    Ok(body)
+}
+
+/// Minimal percent-encoding for query parameter values
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Search the Celestrak GP catalog for satellites whose name contains `name`
+pub fn search_by_name(name: &str) -> Result<Vec<CatalogEntry>> {
+    let url = format!("{}?NAME={}&FORMAT=tle", GP_URL, encode_query_value(name.trim()));
+    Ok(parse_entries(&fetch_text(&url)?))
+}
+
+/// How a not-yet-cataloged launch is identified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchIdentifier {
+    NoradId(u32),
+    Designator(String), // e.g. "2026-042" or "2026-042B"
+}
+
+impl LaunchIdentifier {
+    /// Parse a NORAD catalog number or an international designator
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_uppercase();
+        if let Ok(id) = value.parse::<u32>() {
+            return Some(LaunchIdentifier::NoradId(id));
+        }
+
+        let (year, rest) = value.split_once('-')?;
+        let launch_number: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let piece = &rest[launch_number.len()..];
+        let valid = year.len() == 4
+            && year.chars().all(|c| c.is_ascii_digit())
+            && launch_number.len() == 3
+            && piece.chars().all(|c| c.is_ascii_alphabetic());
+        valid.then_some(LaunchIdentifier::Designator(value))
+    }
+
+    fn query(&self) -> String {
+        match self {
+            LaunchIdentifier::NoradId(id) => format!("CATNR={}", id),
+            LaunchIdentifier::Designator(designator) => {
+                format!("INTDES={}", encode_query_value(designator))
+            }
+        }
+    }
+}
+
+/// Fetch element sets for a launch; empty until Celestrak publishes them
+pub fn fetch_launch(identifier: &LaunchIdentifier) -> Result<Vec<CatalogEntry>> {
+    let url = format!("{}?{}&FORMAT=tle", GP_URL, identifier.query());
+    Ok(parse_entries(&fetch_text(&url)?))
+}
+
+/// NORAD catalog number from columns 3-7 of TLE line 1
+pub fn norad_id(tle_line1: &str) -> Option<u32> {
+    tle_line1.get(2..7).and_then(|s| s.trim().parse().ok())
+}
+
+/// Catalog metadata for one object from Celestrak SATCAT
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SatcatRecord {
+    pub norad_id: u32,
+    pub object_type: String, // PAY, R/B, DEB or UNK
+    pub owner: String,       // SATCAT ownership code, e.g. "US", "ISS", "AMSAT"
+    pub launch_date: String,
+    pub launch_site: String,
+}
+
+impl SatcatRecord {
+    pub fn object_type_description(&self) -> &str {
+        match self.object_type.as_str() {
+            "PAY" => "Payload",
+            "R/B" => "Rocket body",
+            "DEB" => "Debris",
+            "UNK" => "Unknown",
+            other => other,
+        }
+    }
+
+    /// Fill empty detail fields; returns how many were filled. SATCAT records
+    /// ownership only, so the owner code also stands in for a missing operator.
+    pub fn apply_to(&self, details: &mut SatelliteDetails) -> usize {
+        let mut filled = 0;
+        let mut fill = |field: &mut Option<String>, value: &str| {
+            if field.as_deref().is_none_or(str::is_empty) && !value.is_empty() {
+                *field = Some(value.to_string());
+                filled += 1;
+            }
+        };
+        fill(&mut details.launch_date, &self.launch_date);
+        fill(&mut details.launch_site, &self.launch_site);
+        fill(&mut details.country_of_origin, &self.owner);
+        fill(&mut details.operator, &self.owner);
+        fill(&mut details.satellite_type, self.object_type_description());
+        filled
+    }
+}
+
+/// Fetch the SATCAT record for a NORAD catalog number
+pub fn fetch_satcat(norad_id: u32) -> Result<SatcatRecord> {
+    let url = format!("{}?CATNR={}&FORMAT=csv", SATCAT_URL, norad_id);
+    parse_satcat_csv(&fetch_text(&url)?)
+        .ok_or_else(|| anyhow::anyhow!("No SATCAT record for {}", norad_id))
+}
+
+/// Split one CSV line, honoring double-quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Parse the first record of a SATCAT CSV response, locating columns by header
+pub fn parse_satcat_csv(csv: &str) -> Option<SatcatRecord> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let header = split_csv_line(lines.next()?);
+    let record = split_csv_line(lines.next()?);
+
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim() == name)
+            .and_then(|idx| record.get(idx))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    Some(SatcatRecord {
+        norad_id: column("NORAD_CAT_ID").parse().ok()?,
+        object_type: column("OBJECT_TYPE"),
+        owner: column("OWNER"),
+        launch_date: column("LAUNCH_DATE"),
+        launch_site: column("LAUNCH_SITE"),
+    })
+}
+
+/// Parse three-line TLE text into catalog entries, skipping anything malformed
+pub fn parse_entries(tle_data: &str) -> Vec<CatalogEntry> {
+    let lines: Vec<&str> = tle_data.lines().map(str::trim_end).collect();
+    let mut entries = Vec::new();
+
+    let mut i = 0;
+    while i + 2 < lines.len() {
+        let (name, line1, line2) = (lines[i].trim(), lines[i + 1], lines[i + 2]);
+        if !name.is_empty() && line1.starts_with('1') && line2.starts_with('2') {
+            if let Some(norad_id) = norad_id(line1) {
+                entries.push(CatalogEntry {
+                    name: name.to_string(),
+                    norad_id,
+                    tle_line1: line1.to_string(),
+                    tle_line2: line2.to_string(),
+                });
+            }
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entries() {
+        let data = "ISS (ZARYA)\n\
+            1 25544U 98067A   24001.50000000  .00016717  00000-0  10270-3 0  9005\n\
+            2 25544  51.6400 297.8590 0001218  88.9267 338.1310 15.49000000 12345\n\
+            No GP data found\n";
+        let entries = parse_entries(data);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "ISS (ZARYA)");
+        assert_eq!(entries[0].norad_id, 25544);
+
+        assert!(parse_entries("No GP data found").is_empty());
+    }
+
+    #[test]
+    fn test_launch_identifier() {
+        assert_eq!(
+            LaunchIdentifier::parse("25544"),
+            Some(LaunchIdentifier::NoradId(25544))
+        );
+        assert_eq!(
+            LaunchIdentifier::parse("2026-042b"),
+            Some(LaunchIdentifier::Designator("2026-042B".to_string()))
+        );
+        assert_eq!(
+            LaunchIdentifier::NoradId(25544).query(),
+            "CATNR=25544".to_string()
+        );
+        assert_eq!(LaunchIdentifier::parse("ISS"), None);
+        assert_eq!(LaunchIdentifier::parse("26-042"), None);
+    }
+
+    #[test]
+    fn test_parse_satcat_csv() {
+        let csv = "OBJECT_NAME,OBJECT_ID,NORAD_CAT_ID,OBJECT_TYPE,OPS_STATUS_CODE,OWNER,LAUNCH_DATE,LAUNCH_SITE,DECAY_DATE\n\
+            \"FOX-1B (AO-91)\",2017-073E,43017,PAY,+,US,2017-11-18,AFWTR,\n";
+        let record = parse_satcat_csv(csv).unwrap();
+        assert_eq!(record.norad_id, 43017);
+        assert_eq!(record.owner, "US");
+        assert_eq!(record.launch_site, "AFWTR");
+        assert_eq!(record.object_type_description(), "Payload");
+
+        let mut details = SatelliteDetails::new("AO-91".to_string());
+        details.operator = Some("AMSAT".to_string());
+        assert_eq!(record.apply_to(&mut details), 4);
+        assert_eq!(details.launch_date.as_deref(), Some("2017-11-18"));
+        assert_eq!(details.operator.as_deref(), Some("AMSAT"));
+
+        assert!(parse_satcat_csv("OBJECT_NAME,NORAD_CAT_ID\n").is_none());
+    }
+
+    #[test]
+    fn test_split_csv_line() {
+        assert_eq!(
+            split_csv_line("a,\"b, c\",\"say \"\"hi\"\"\","),
+            vec!["a", "b, c", "say \"hi\"", ""]
+        );
+    }
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!(encode_query_value("ISS (ZARYA)"), "ISS%20%28ZARYA%29");
+        assert_eq!(encode_query_value("AO-91"), "AO-91");
+    }
+}
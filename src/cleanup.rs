@@ -0,0 +1,128 @@
+//! Satellites left behind in the database: ones whose elements haven't been
+//! refreshed in a while, or that the TLE file doesn't list, typically from old
+//! imports and one-off catalog lookups. `crabtrack cleanup` lists them and can
+//! refresh, archive or purge them.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use crate::celestrak::{self, CatalogEntry, LaunchIdentifier};
+use crate::database::SatelliteDetails;
+
+/// A database satellite that may need attention
+#[derive(Debug, Clone)]
+pub struct Orphan {
+    pub details: SatelliteDetails,
+    pub age_days: Option<f64>, // of its elements; None when it has none
+    pub in_source: bool,       // listed in the TLE file, by name or catalog number
+}
+
+impl Orphan {
+    /// Why the satellite is listed, e.g. "elements 45 days old, not in the TLE file"
+    pub fn reason(&self, max_age_days: f64) -> String {
+        let mut reasons = Vec::new();
+        match self.age_days {
+            None => reasons.push("no elements".to_string()),
+            Some(age) if age > max_age_days => {
+                reasons.push(format!("elements {:.0} days old", age))
+            }
+            Some(_) => {}
+        }
+        if !self.in_source {
+            reasons.push("not in the TLE file".to_string());
+        }
+        reasons.join(", ")
+    }
+}
+
+fn epoch(tle_line1: &str) -> Option<DateTime<Utc>> {
+    (tle_line1.len() >= 32).then(|| crate::parse_tle_epoch(tle_line1))
+}
+
+fn source_entry<'a>(
+    details: &SatelliteDetails,
+    source: &'a [CatalogEntry],
+) -> Option<&'a CatalogEntry> {
+    let norad_id = celestrak::norad_id(&details.tle_line1);
+    source
+        .iter()
+        .find(|entry| Some(entry.norad_id) == norad_id || entry.name == details.name)
+}
+
+/// The satellites in `details` whose elements are missing or older than
+/// `max_age_days`, or that `source` doesn't list
+pub fn find(
+    details: &[SatelliteDetails],
+    source: &[CatalogEntry],
+    max_age_days: f64,
+    now: DateTime<Utc>,
+) -> Vec<Orphan> {
+    details
+        .iter()
+        .map(|details| Orphan {
+            age_days: epoch(&details.tle_line1)
+                .map(|epoch| (now - epoch).num_seconds() as f64 / 86400.0),
+            in_source: source_entry(details, source).is_some(),
+            details: details.clone(),
+        })
+        .filter(|orphan| !orphan.in_source || orphan.age_days.is_none_or(|age| age > max_age_days))
+        .collect()
+}
+
+/// Elements newer than the orphan's own, from the TLE file when it has them
+/// and otherwise from Celestrak by catalog number
+pub fn fresh_elements(orphan: &Orphan, source: &[CatalogEntry]) -> Result<Option<CatalogEntry>> {
+    let current = epoch(&orphan.details.tle_line1);
+    let newer = |entry: &CatalogEntry| {
+        epoch(&entry.tle_line1).is_some_and(|epoch| current.is_none_or(|current| epoch > current))
+    };
+    if let Some(entry) = source_entry(&orphan.details, source).filter(|entry| newer(entry)) {
+        return Ok(Some(entry.clone()));
+    }
+    let Some(norad_id) = celestrak::norad_id(&orphan.details.tle_line1) else {
+        return Ok(None);
+    };
+    let fetched = celestrak::fetch_launch(&LaunchIdentifier::NoradId(norad_id))?;
+    Ok(fetched.into_iter().find(newer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::{SAMPLE_TLE_LINE1 as ISS_LINE1, SAMPLE_TLE_LINE2 as ISS_LINE2};
+    use chrono::TimeZone;
+
+    fn details(name: &str, tle_line1: &str) -> SatelliteDetails {
+        SatelliteDetails {
+            tle_line1: tle_line1.to_string(),
+            ..SatelliteDetails::new(name.to_string())
+        }
+    }
+
+    #[test]
+    fn test_find_orphans() {
+        let epoch = Utc.with_ymd_and_hms(2008, 9, 20, 12, 25, 40).unwrap();
+        let source =
+            celestrak::parse_entries(&format!("ISS (ZARYA)\n{}\n{}\n", ISS_LINE1, ISS_LINE2));
+        let stored = [
+            // Renamed since, but the catalog number still matches
+            details("ISS", ISS_LINE1),
+            details("MANUAL ENTRY", ""),
+        ];
+
+        let orphans = find(&stored, &source, 30.0, epoch + chrono::Duration::days(10));
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].details.name, "MANUAL ENTRY");
+        assert_eq!(orphans[0].reason(30.0), "no elements, not in the TLE file");
+
+        let orphans = find(&stored, &source, 30.0, epoch + chrono::Duration::days(45));
+        assert_eq!(orphans.len(), 2);
+        assert_eq!(orphans[0].reason(30.0), "elements 45 days old");
+
+        // Older elements are refreshed from the TLE file without going online
+        let older = ISS_LINE1.replace("08264.51782528", "08200.00000000");
+        let orphans = find(&[details("ISS", &older)], &source, 30.0, epoch);
+        let fresh = fresh_elements(&orphans[0], &source).unwrap().unwrap();
+        assert_eq!(fresh.tle_line1, ISS_LINE1);
+    }
+}
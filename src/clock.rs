@@ -0,0 +1,433 @@
+//! Sanity checks of the system clock.
+//!
+//! Every prediction is relative to the system clock, so a station that booted
+//! without a real-time clock or network time shows passes at the wrong times
+//! without any other sign of trouble. Two checks catch this: TLE epochs that
+//! are implausible for the current time, and the `Date` header of an HTTP
+//! server disagreeing with the clock.
+//!
+//! The monitor also asks the local time daemon (chrony, ntpd or gpsd) which
+//! source it follows and how far off it believes the clock is, for the header.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::config::ClockConfig;
+
+/// Offset of the system clock from network time in seconds (positive = ahead), or an error
+pub type NetworkTimeResult = Arc<Mutex<Option<Result<f64, String>>>>;
+
+/// Describes a clock that TLE epochs say can't be right. Element sets are
+/// published within hours of their epoch, so one from the future means the
+/// clock is behind, and a newest one over a year old suggests it is far ahead.
+pub fn epoch_warning(epochs: &[DateTime<Utc>], now: DateTime<Utc>) -> Option<String> {
+    let newest = epochs.iter().max()?;
+    if *newest > now + Duration::days(1) {
+        return Some(format!(
+            "System clock reads {} but TLEs are dated {}; the clock is probably behind",
+            now.format("%Y-%m-%d %H:%M UTC"),
+            newest.format("%Y-%m-%d %H:%M UTC")
+        ));
+    }
+    if now - *newest > Duration::days(365) {
+        return Some(format!(
+            "System clock reads {}, over a year after the newest TLE ({}); check the clock",
+            now.format("%Y-%m-%d"),
+            newest.format("%Y-%m-%d")
+        ));
+    }
+    None
+}
+
+/// Offset of `local` from the server time in an HTTP `Date` header, taking the
+/// server's reading as made halfway through the request
+pub fn offset_from_date_header(
+    date: &str,
+    sent: DateTime<Utc>,
+    received: DateTime<Utc>,
+) -> Result<f64> {
+    let server = DateTime::parse_from_rfc2822(date.trim())
+        .with_context(|| format!("invalid Date header '{}'", date))?;
+    let local = sent + (received - sent) / 2;
+    Ok((local - server.with_timezone(&Utc)).num_milliseconds() as f64 / 1000.0)
+}
+
+/// Asks an HTTP server for its time and returns the local clock's offset from it
+pub fn network_offset(url: &str) -> Result<f64> {
+    // No redirects: a plain-HTTP answer still works when a badly wrong clock
+    // makes every TLS certificate look invalid
+    let agent = ureq::AgentBuilder::new()
+        .redirects(0)
+        .timeout(std::time::Duration::from_secs(10))
+        .build();
+    let sent = Utc::now();
+    let response = match agent.head(url).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => {
+            let hint = if e.to_string().contains("certificate") {
+                " (a certificate error can mean the clock is wrong)"
+            } else {
+                ""
+            };
+            anyhow::bail!("time check against {} failed: {}{}", url, e, hint);
+        }
+    };
+    let received = Utc::now();
+    let date = response
+        .header("Date")
+        .with_context(|| format!("{} sent no Date header", url))?;
+    offset_from_date_header(date, sent, received)
+}
+
+/// Result slot for a background time daemon query
+pub type TimeSourceResult = Arc<Mutex<Option<Result<TimeSourceStatus, String>>>>;
+
+/// The source the local time daemon follows and how far off it thinks the clock is
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSourceStatus {
+    pub daemon: &'static str,        // "chrony", "ntpd" or "gpsd"
+    pub reference: String,           // e.g. "GPS", "PPS" or an NTP server
+    pub offset_seconds: Option<f64>, // system clock minus reference time, positive = ahead
+    pub synchronized: bool,
+}
+
+impl TimeSourceStatus {
+    /// Short form for the header, e.g. "chrony GPS +0.4 ms"
+    pub fn label(&self) -> String {
+        if !self.synchronized {
+            return format!("{} unsynchronized", self.daemon);
+        }
+        let offset = match self.offset_seconds {
+            Some(offset) if offset.abs() < 1.0 => format!(" {:+.1} ms", offset * 1000.0),
+            Some(offset) => format!(" {:+.1} s", offset),
+            None => String::new(),
+        };
+        format!("{} {}{}", self.daemon, self.reference, offset)
+    }
+}
+
+/// Parses `chronyc -c tracking`. Its "system time" field is how far the clock is
+/// slow of NTP time, so the sign is flipped to get how far it is ahead.
+pub fn parse_chrony_tracking(csv: &str) -> Option<TimeSourceStatus> {
+    let fields: Vec<&str> = csv.lines().next()?.split(',').collect();
+    if fields.len() < 14 {
+        return None;
+    }
+    let stratum: u32 = fields[2].parse().ok()?;
+    let slow_by: f64 = fields[4].parse().ok()?;
+    Some(TimeSourceStatus {
+        daemon: "chrony",
+        reference: fields[1].to_string(),
+        offset_seconds: Some(-slow_by),
+        synchronized: stratum > 0 && fields[13] != "Not synchronised",
+    })
+}
+
+/// Parses the system variables from `ntpq -c rv`. ntpd's offset (milliseconds)
+/// is of the reference relative to this host, so it is negated.
+pub fn parse_ntpq_rv(text: &str) -> Option<TimeSourceStatus> {
+    let variables: std::collections::HashMap<&str, &str> = text
+        .split([',', '\n'])
+        .filter_map(|pair| pair.trim().split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim().trim_matches('"')))
+        .collect();
+    let stratum: u32 = variables.get("stratum")?.parse().ok()?;
+    let offset_ms: Option<f64> = variables.get("offset").and_then(|v| v.parse().ok());
+    Some(TimeSourceStatus {
+        daemon: "ntpd",
+        reference: variables.get("refid").unwrap_or(&"?").to_string(),
+        offset_seconds: offset_ms.map(|ms| -ms / 1000.0),
+        synchronized: stratum < 16 && !text.contains("sync_unspec"),
+    })
+}
+
+/// Parses a gpsd TPV report received at `received`. The offset includes the
+/// serial and network latency of the report, typically tens of milliseconds.
+pub fn parse_gpsd_tpv(line: &str, received: DateTime<Utc>) -> Option<TimeSourceStatus> {
+    let report: serde_json::Value = serde_json::from_str(line).ok()?;
+    if report["class"] != "TPV" {
+        return None;
+    }
+    let time = DateTime::parse_from_rfc3339(report["time"].as_str()?).ok()?;
+    let mode = report["mode"].as_u64().unwrap_or(0);
+    Some(TimeSourceStatus {
+        daemon: "gpsd",
+        reference: "GPS".to_string(),
+        offset_seconds: Some(
+            (received - time.with_timezone(&Utc)).num_milliseconds() as f64 / 1000.0,
+        ),
+        synchronized: mode >= 2,
+    })
+}
+
+fn command_output(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("could not run {}", program))?;
+    if !output.status.success() {
+        anyhow::bail!("{} exited with {}", program, output.status);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn query_gpsd(address: &str) -> Result<TimeSourceStatus> {
+    let timeout = std::time::Duration::from_secs(3);
+    let socket = address
+        .to_socket_addrs()?
+        .next()
+        .with_context(|| format!("could not resolve {}", address))?;
+    let mut stream = TcpStream::connect_timeout(&socket, timeout)
+        .with_context(|| format!("could not connect to gpsd at {}", address))?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.write_all(b"?WATCH={\"enable\":true,\"json\":true};\n")?;
+
+    // gpsd sends VERSION, DEVICES and WATCH first; a fix reports about once a second
+    for line in BufReader::new(stream).lines().take(20) {
+        if let Some(status) = parse_gpsd_tpv(&line?, Utc::now()) {
+            return Ok(status);
+        }
+    }
+    anyhow::bail!("gpsd at {} reported no time", address)
+}
+
+/// Asks the configured time daemon, or with "auto" the first of chrony, ntpd and gpsd to answer
+pub fn query_time_source(config: &ClockConfig) -> Result<TimeSourceStatus> {
+    let chrony = || {
+        parse_chrony_tracking(&command_output("chronyc", &["-c", "tracking"])?)
+            .context("unexpected chronyc output")
+    };
+    let ntpd = || {
+        parse_ntpq_rv(&command_output("ntpq", &["-c", "rv"])?).context("unexpected ntpq output")
+    };
+    match config.time_source.as_str() {
+        "chrony" => chrony(),
+        "ntpd" => ntpd(),
+        "gpsd" => query_gpsd(&config.gpsd_address),
+        "auto" => chrony()
+            .or_else(|_| ntpd())
+            .or_else(|_| query_gpsd(&config.gpsd_address))
+            .context("no time daemon found"),
+        other => anyhow::bail!("unknown time_source '{}'", other),
+    }
+}
+
+/// Periodically runs both checks and keeps the resulting warnings for the header
+pub struct ClockMonitor {
+    pub config: ClockConfig,
+    pub epoch_warning: Option<String>,
+    pub network_offset: Option<f64>,
+    pub network_error: Option<String>,
+    pub time_source: Option<Result<TimeSourceStatus, String>>,
+    pending: Option<NetworkTimeResult>,
+    source_pending: Option<TimeSourceResult>,
+    last_check: Option<Instant>, // monotonic, so a clock step doesn't stall the checks
+    last_source_check: Option<Instant>,
+}
+
+impl ClockMonitor {
+    pub fn new(config: ClockConfig) -> Self {
+        Self {
+            config,
+            epoch_warning: None,
+            network_offset: None,
+            network_error: None,
+            time_source: None,
+            pending: None,
+            source_pending: None,
+            last_check: None,
+            last_source_check: None,
+        }
+    }
+
+    /// Collects finished background checks, and starts each again when due
+    pub fn update(&mut self, epochs: &[DateTime<Utc>], now: DateTime<Utc>) {
+        self.update_time_source();
+
+        if let Some(result) = self.pending.as_ref().and_then(|p| p.lock().unwrap().take()) {
+            self.pending = None;
+            match result {
+                Ok(offset) => {
+                    self.network_offset = Some(offset);
+                    self.network_error = None;
+                }
+                Err(e) => self.network_error = Some(e),
+            }
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.check_minutes.max(1) * 60);
+        if self.last_check.is_some_and(|last| last.elapsed() < interval) {
+            return;
+        }
+        self.last_check = Some(Instant::now());
+        self.epoch_warning = epoch_warning(epochs, now);
+
+        if !self.config.time_url.is_empty() && self.pending.is_none() {
+            let slot: NetworkTimeResult = Arc::new(Mutex::new(None));
+            self.pending = Some(Arc::clone(&slot));
+            let url = self.config.time_url.clone();
+            std::thread::spawn(move || {
+                let result = network_offset(&url).map_err(|e| format!("{:#}", e));
+                *slot.lock().unwrap() = Some(result);
+            });
+        }
+    }
+
+    fn update_time_source(&mut self) {
+        if let Some(result) = self.source_pending.as_ref().and_then(|p| p.lock().unwrap().take()) {
+            self.source_pending = None;
+            self.time_source = Some(result);
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.source_check_seconds.max(1));
+        if self.config.time_source == "none"
+            || self.source_pending.is_some()
+            || self.last_source_check.is_some_and(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        self.last_source_check = Some(Instant::now());
+
+        let slot: TimeSourceResult = Arc::new(Mutex::new(None));
+        self.source_pending = Some(Arc::clone(&slot));
+        let config = self.config.clone();
+        std::thread::spawn(move || {
+            let result = query_time_source(&config).map_err(|e| format!("{:#}", e));
+            *slot.lock().unwrap() = Some(result);
+        });
+    }
+
+    /// Problems worth a banner; an unreachable time server alone is not one
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings: Vec<String> = self.epoch_warning.iter().cloned().collect();
+        if let Some(offset) = self
+            .network_offset
+            .filter(|offset| offset.abs() > self.config.max_offset_seconds)
+        {
+            let direction = if offset > 0.0 { "ahead of" } else { "behind" };
+            warnings.push(format!(
+                "System clock is {:.0} s {} network time; predictions and Doppler will be off",
+                offset.abs(),
+                direction
+            ));
+        }
+        if let Some(error) = self
+            .network_error
+            .as_ref()
+            .filter(|error| error.contains("certificate"))
+        {
+            warnings.push(error.clone());
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_epoch_warning() {
+        let now = Utc.with_ymd_and_hms(2026, 4, 21, 12, 0, 0).unwrap();
+        let recent = vec![now - Duration::days(3), now - Duration::hours(6)];
+        assert_eq!(epoch_warning(&recent, now), None);
+        assert_eq!(epoch_warning(&[], now), None);
+
+        // A Pi that booted at its last saved time, months ago
+        let behind = now - Duration::days(200);
+        let warning = epoch_warning(&recent, behind).unwrap();
+        assert!(warning.contains("behind"), "{}", warning);
+
+        // Or one that came up in 2038
+        let ahead = Utc.with_ymd_and_hms(2038, 1, 19, 3, 14, 8).unwrap();
+        assert!(epoch_warning(&recent, ahead).unwrap().contains("over a year"));
+    }
+
+    #[test]
+    fn test_offset_from_date_header() {
+        let sent = Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 30).unwrap();
+        let received = sent + Duration::seconds(2);
+        let offset =
+            offset_from_date_header("Tue, 21 Apr 2026 18:00:01 GMT", sent, received).unwrap();
+        assert_eq!(offset, 30.0);
+
+        let offset =
+            offset_from_date_header("Tue, 21 Apr 2026 18:01:31 +0000", sent, received).unwrap();
+        assert_eq!(offset, -60.0);
+
+        assert!(offset_from_date_header("yesterday", sent, received).is_err());
+    }
+
+    #[test]
+    fn test_parse_chrony_tracking() {
+        let csv = "50505300,PPS,1,1713722400.123456789,-0.000000412,0.000000102,\
+                   0.000000300,-11.942,0.000,0.015,0.000000001,0.000012000,16.0,Normal\n";
+        let status = parse_chrony_tracking(csv).unwrap();
+        assert_eq!(status.reference, "PPS");
+        assert!(status.synchronized);
+        assert!((status.offset_seconds.unwrap() - 0.000000412).abs() < 1e-12);
+        assert_eq!(status.label(), "chrony PPS +0.0 ms");
+
+        let unsynced = "00000000,,0,0.000000000,0.000000000,0.000000000,0.000000000,\
+                        0.000,0.000,0.000,1.000000000,1.000000000,0.0,Not synchronised\n";
+        let status = parse_chrony_tracking(unsynced).unwrap();
+        assert!(!status.synchronized);
+        assert_eq!(status.label(), "chrony unsynchronized");
+
+        assert!(parse_chrony_tracking("506 Cannot talk to daemon").is_none());
+    }
+
+    #[test]
+    fn test_parse_ntpq_rv() {
+        let text = "associd=0 status=0615 leap_none, sync_ntp, 1 event, clock_sync,\n\
+                    version=\"ntpd 4.2.8p15\", processor=\"x86_64\", leap=00, stratum=2,\n\
+                    precision=-24, rootdelay=1.234, refid=192.168.1.10,\n\
+                    offset=-2.345678, frequency=-11.942, sys_jitter=0.123";
+        let status = parse_ntpq_rv(text).unwrap();
+        assert_eq!(status.reference, "192.168.1.10");
+        assert!(status.synchronized);
+        assert!((status.offset_seconds.unwrap() - 0.002345678).abs() < 1e-12);
+        assert_eq!(status.label(), "ntpd 192.168.1.10 +2.3 ms");
+
+        let unsynced = "associd=0 status=c016 leap_alarm, sync_unspec, 1 event,\n\
+                        stratum=16, refid=INIT, offset=0.000000";
+        assert!(!parse_ntpq_rv(unsynced).unwrap().synchronized);
+        assert!(parse_ntpq_rv("ntpq: read: Connection refused").is_none());
+    }
+
+    #[test]
+    fn test_parse_gpsd_tpv() {
+        let received = Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 2).unwrap();
+        let line = r#"{"class":"TPV","mode":3,"time":"2026-04-21T18:00:00.500Z","lat":41.5}"#;
+        let status = parse_gpsd_tpv(line, received).unwrap();
+        assert!(status.synchronized);
+        assert_eq!(status.offset_seconds, Some(1.5));
+        assert_eq!(status.label(), "gpsd GPS +1.5 s");
+
+        let no_fix = r#"{"class":"TPV","mode":1,"time":"2026-04-21T18:00:01.000Z"}"#;
+        assert!(!parse_gpsd_tpv(no_fix, received).unwrap().synchronized);
+        assert!(parse_gpsd_tpv(r#"{"class":"VERSION","release":"3.25"}"#, received).is_none());
+    }
+
+    #[test]
+    fn test_warnings() {
+        let mut monitor = ClockMonitor::new(ClockConfig::default());
+        assert!(monitor.warnings().is_empty());
+
+        monitor.network_offset = Some(-3.0);
+        assert!(monitor.warnings().is_empty());
+        monitor.network_offset = Some(-120.0);
+        assert!(monitor.warnings()[0].contains("120 s behind"));
+
+        monitor.network_offset = None;
+        monitor.network_error = Some("connection refused".to_string());
+        assert!(monitor.warnings().is_empty());
+    }
+}
@@ -1,7 +1,10 @@
-use anyhow::Result;
-use serde::Deserialize;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::{bandplan, elevation, observer, radio};
+
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub observer: ObserverConfig,
@@ -10,14 +13,71 @@ pub struct Config {
     pub display: DisplayConfig,
     pub radio: RadioConfig,
     pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub transits: TransitConfig,
+    pub antenna: Option<AntennaConfig>,
+    #[serde(default)]
+    pub antennas: Vec<AntennaConfig>, // additional antennas, mapped by band or satellite
+    pub announcer: Option<AnnouncerConfig>,
+    pub rotator: Option<RotatorConfig>,
+    #[serde(default)]
+    pub rotators: Vec<RotatorConfig>,
+    pub rig: Option<RigConfig>,
+    pub sdr: Option<SdrConfig>,
+    pub recording: Option<RecordingConfig>,
+    #[serde(default)]
+    pub geostationary: GeostationaryConfig,
+    #[serde(default)]
+    pub decoders: Vec<DecoderConfig>,
+    pub satnogs: Option<SatnogsConfig>,
+    pub aprs: Option<AprsConfig>,
+    pub home_assistant: Option<HomeAssistantConfig>,
+    #[serde(default)]
+    pub clock: ClockConfig,
+    #[serde(default)]
+    pub kiosk: KioskConfig,
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+    #[serde(default)]
+    pub scripting: ScriptingConfig,
+    pub sync: Option<SyncConfig>,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub power: PowerConfig,
+    #[serde(default)]
+    pub profiles: Vec<ProfileConfig>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ObserverConfig {
     pub name: String,
-    pub latitude: f64,
-    pub longitude: f64,
-    pub altitude: f64,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub grid: Option<String>, // Maidenhead locator, used when latitude/longitude are unset
+    pub altitude: Option<f64>, // meters; looked up or assumed sea level when unset
+    #[serde(default)]
+    pub elevation_lookup: bool, // ask the Open-Elevation API for a missing altitude
+    pub dem_file: Option<PathBuf>, // SRTM .hgt tile to read a missing altitude from
+    pub horizon_file: Option<PathBuf>, // CSV of azimuth,min elevation
+    pub noise_file: Option<PathBuf>, // CSV of azimuth,noise floor in dB
+}
+
+impl ObserverConfig {
+    /// Latitude and longitude, from the config or the centre of the grid square
+    pub fn coordinates(&self) -> Result<(f64, f64)> {
+        match (self.latitude, self.longitude, &self.grid) {
+            (Some(latitude), _, _) if !(-90.0..=90.0).contains(&latitude) => {
+                anyhow::bail!("[observer] latitude {} is outside -90 to 90", latitude)
+            }
+            (Some(latitude), Some(longitude), _) => {
+                Ok((latitude, observer::normalize_longitude(longitude)))
+            }
+            (_, _, Some(grid)) => elevation::grid_to_latlon(grid)
+                .with_context(|| format!("invalid grid square '{}'", grid)),
+            _ => anyhow::bail!("[observer] needs latitude and longitude, or a grid square"),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -25,6 +85,12 @@ pub struct SatellitesConfig {
     pub tle_file: PathBuf,
     pub tracked_satellites: Vec<String>,
     pub max_satellites: usize,
+    #[serde(default = "default_pending_check_minutes")]
+    pub pending_check_minutes: u64, // how often to look for pending launch TLEs
+}
+
+fn default_pending_check_minutes() -> u64 {
+    60
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,22 +99,214 @@ pub struct PredictionConfig {
     pub min_elevation: f64,
     pub search_days: f64,
     pub time_step: f64,
+    #[serde(default)]
+    pub light_time_correction: bool, // correct look angles and Doppler for signal travel time
+    #[serde(default)]
+    pub precise_frames: bool, // TEME -> ITRF with UT1 and polar motion instead of plain GMST
+    pub eop_file: Option<PathBuf>, // Celestrak EOP CSV; defaults to the database directory
+    #[serde(default)]
+    pub filter: PassFilter,
+    #[serde(default)]
+    pub satellite_filters: HashMap<String, PassFilter>, // keyed by satellite name
+}
+
+/// Which part of the day a pass must culminate in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LightingFilter {
+    #[default]
+    Any,
+    Daylight,
+    Night,
+}
+
+/// Filters deciding which predicted passes are worth showing
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PassFilter {
+    pub min_duration_minutes: Option<f64>,
+    pub min_max_elevation: Option<f64>,
+    pub lighting: Option<LightingFilter>,
+}
+
+impl PassFilter {
+    /// Overlay per-satellite settings on top of this (global) filter
+    pub fn merged_with(&self, other: Option<&PassFilter>) -> PassFilter {
+        match other {
+            Some(other) => PassFilter {
+                min_duration_minutes: other.min_duration_minutes.or(self.min_duration_minutes),
+                min_max_elevation: other.min_max_elevation.or(self.min_max_elevation),
+                lighting: other.lighting.or(self.lighting),
+            },
+            None => self.clone(),
+        }
+    }
+}
+
+impl PredictionConfig {
+    /// Effective pass filter for a satellite
+    pub fn filter_for(&self, satellite_name: &str) -> PassFilter {
+        self.filter
+            .merged_with(self.satellite_filters.get(satellite_name))
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct DisplayConfig {
-    pub refresh_rate: u64,
+    pub refresh_rate: u64, // ms between screen redraws, which keeps countdowns moving
+    pub position_refresh_ms: Option<u64>, // propagate positions this often; refresh_rate if unset
+    pub sky_map_refresh_ms: Option<u64>, // move the sky map's satellites this often
+    #[serde(default = "default_pass_refresh_seconds")]
+    pub pass_refresh_seconds: u64, // predict the pass tables again this often; 0 never
     pub show_current_position: bool,
     pub show_all_positions: bool,
     pub show_sky_map: bool,
+    #[serde(default)]
+    pub auto_follow: bool, // start with the selection following the most relevant satellite
+    #[serde(default)]
+    pub accessible: bool, // plain labelled text instead of charts, for screen readers
+    #[serde(default)]
+    pub ascii: bool, // ASCII characters only, for consoles without Unicode glyphs
+    #[serde(default)]
+    pub sky_map_marker: SkyMapMarker,
+    #[serde(default = "default_constellation_min_size")]
+    pub constellation_min_size: usize, // members before a name prefix counts as a constellation
+    #[serde(default)]
+    pub group_keys: HashMap<String, String>, // "1".."9" to the satellite group they show
+}
+
+fn default_constellation_min_size() -> usize {
+    10
+}
+
+fn default_pass_refresh_seconds() -> u64 {
+    60
+}
+
+impl DisplayConfig {
+    pub fn position_refresh_ms(&self) -> u64 {
+        self.position_refresh_ms.unwrap_or(self.refresh_rate)
+    }
+
+    pub fn sky_map_refresh_ms(&self) -> u64 {
+        self.sky_map_refresh_ms
+            .unwrap_or_else(|| self.position_refresh_ms())
+    }
+}
+
+/// How the sky map draws its grid and satellites
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkyMapMarker {
+    #[default]
+    Braille, // finest detail, but some fonts lack or misalign the glyphs
+    Block,   // full blocks, for fonts without braille
+    Dot,     // small dots
+    Letters, // dotted grid, each satellite drawn as the letter shown in the legend
 }
 
 #[derive(Debug, Deserialize)]
 pub struct RadioConfig {
     pub enabled: bool,
+    #[serde(deserialize_with = "frequency_mhz")]
     pub downlink_frequency_mhz: f64,
+    #[serde(deserialize_with = "frequency_mhz")]
     pub uplink_frequency_mhz: f64,
     pub show_doppler: bool,
+    #[serde(default = "default_tuning_step_khz")]
+    pub tuning_step_khz: f64, // memory channel spacing for manual Doppler tuning
+    #[serde(default = "default_offset_step_hz")]
+    pub offset_step_hz: f64, // step for manual corrections of off-frequency satellites
+    #[serde(default)]
+    pub satellites: HashMap<String, SatelliteRadioConfig>, // keyed by satellite name
+    #[serde(default = "default_tec_units")]
+    pub tec_units: f64, // vertical TEC for the Faraday rotation estimate, 10^16 electrons/m²
+}
+
+/// A frequency written as MHz, or as a string with a unit such as "145800k"
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FrequencyValue {
+    Number(f64),
+    Text(String),
+}
+
+impl FrequencyValue {
+    fn to_mhz<E: serde::de::Error>(&self) -> std::result::Result<f64, E> {
+        match self {
+            FrequencyValue::Number(value) => radio::parse_frequency_mhz(&value.to_string()),
+            FrequencyValue::Text(text) => radio::parse_frequency_mhz(text),
+        }
+        .map_err(E::custom)
+    }
+}
+
+fn frequency_mhz<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    FrequencyValue::deserialize(deserializer)?.to_mhz()
+}
+
+fn optional_frequency_mhz<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<f64>, D::Error> {
+    Option::<FrequencyValue>::deserialize(deserializer)?
+        .map(|value| value.to_mhz())
+        .transpose()
+}
+
+fn default_tuning_step_khz() -> f64 {
+    5.0
+}
+
+fn default_offset_step_hz() -> f64 {
+    100.0
+}
+
+fn default_tec_units() -> f64 {
+    20.0
+}
+
+/// Per-satellite transponder details; unset frequencies fall back to [radio]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SatelliteRadioConfig {
+    #[serde(default, deserialize_with = "optional_frequency_mhz")]
+    pub downlink_frequency_mhz: Option<f64>,
+    #[serde(default, deserialize_with = "optional_frequency_mhz")]
+    pub uplink_frequency_mhz: Option<f64>,
+    pub mode: Option<String>, // e.g. "FM", "SSB", "CW"
+    pub ctcss_hz: Option<f64>,
+    pub dcs_code: Option<u16>,
+    pub arm_ctcss_hz: Option<f64>, // SO-50 style timer arming tone
+    #[serde(default, deserialize_with = "optional_frequency_mhz")]
+    pub arm_frequency_mhz: Option<f64>, // defaults to the uplink frequency
+    pub polarization: Option<Polarization>, // of the downlink; linear when unset
+}
+
+/// Polarization of an antenna or a satellite's downlink
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Polarization {
+    Linear, // vertical, horizontal, or a satellite's monopoles
+    Rhcp,
+    Lhcp,
+}
+
+impl RadioConfig {
+    /// Transponder details configured for a satellite, if any
+    pub fn for_satellite(&self, satellite_name: &str) -> Option<&SatelliteRadioConfig> {
+        self.satellites.get(satellite_name)
+    }
+
+    /// Effective (downlink, uplink) frequencies in MHz for a satellite
+    pub fn frequencies_for(&self, satellite_name: &str) -> (f64, f64) {
+        let radio = self.for_satellite(satellite_name);
+        (
+            radio
+                .and_then(|r| r.downlink_frequency_mhz)
+                .unwrap_or(self.downlink_frequency_mhz),
+            radio
+                .and_then(|r| r.uplink_frequency_mhz)
+                .unwrap_or(self.uplink_frequency_mhz),
+        )
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,14 +314,720 @@ pub struct AlertsConfig {
     pub enabled: bool,
     pub alert_before_pass: i64, // minutes
     pub min_elevation_for_alert: f64,
-    #[allow(dead_code)]
-    pub play_sound: bool,
+    pub play_sound: bool, // ring the terminal bell at AOS of an alerted pass
+    #[serde(default)]
+    pub priority: Vec<String>, // satellites preferred when passes overlap, most wanted first
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransitConfig {
+    pub search_radius_km: f64, // max distance from the transit center line
+}
+
+impl Default for TransitConfig {
+    fn default() -> Self {
+        Self {
+            search_radius_km: 10.0,
+        }
+    }
+}
+
+/// Checks that the system clock is plausible and agrees with network time
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ClockConfig {
+    pub time_url: String, // HTTP server whose Date header is compared; empty disables
+    pub check_minutes: u64,
+    pub max_offset_seconds: f64, // warn when the clock is further off than this
+    pub time_source: String,     // "auto", "chrony", "ntpd", "gpsd" or "none"
+    pub gpsd_address: String,
+    pub source_check_seconds: u64,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            time_url: "http://celestrak.org".to_string(),
+            check_minutes: 60,
+            max_offset_seconds: 5.0,
+            time_source: "auto".to_string(),
+            gpsd_address: "localhost:2947".to_string(),
+            source_check_seconds: 30,
+        }
+    }
+}
+
+/// Recording computed positions during passes, to plot against signal logs
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    pub record: bool,
+    pub interval_seconds: u64,
+    pub satellites: Vec<String>, // recorded while above the horizon; empty for the selected one
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            record: false,
+            interval_seconds: 5,
+            satellites: Vec::new(),
+        }
+    }
+}
+
+/// Saving battery at a portable station between passes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PowerConfig {
+    pub saver: bool,
+    pub idle_refresh_ms: u64, // redraw this often while no pass is near
+    pub wake_minutes: f64,    // back to full rate this long before AOS
+    pub below_horizon_seconds: u64, // propagate satellites below the horizon this often
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            saver: false,
+            idle_refresh_ms: 5000,
+            wake_minutes: 5.0,
+            below_horizon_seconds: 60,
+        }
+    }
+}
+
+/// Unattended wall display started with `--kiosk`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct KioskConfig {
+    pub rotate_seconds: u64, // how long each satellite stays featured
+    pub rotate_among: usize, // feature only this many of the most relevant satellites
+}
+
+impl Default for KioskConfig {
+    fn default() -> Self {
+        Self {
+            rotate_seconds: 20,
+            rotate_among: 5,
+        }
+    }
+}
+
+/// Compiled-in plugins to run, with any settings each takes in a table of its name
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PluginsConfig {
+    pub enabled: Vec<String>,
+    #[serde(flatten)]
+    pub settings: HashMap<String, toml::Table>,
+}
+
+/// Rhai scripts called on AOS, LOS and alerts
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ScriptingConfig {
+    pub files: Vec<PathBuf>,
+}
+
+/// Which satellites a piece of station hardware is used for. Hardware with
+/// neither list set is used for everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HardwareMapping {
+    #[serde(default)]
+    pub bands: Vec<String>, // amateur band names, e.g. "2m", "70cm"
+    #[serde(default)]
+    pub satellites: Vec<String>,
+}
+
+impl HardwareMapping {
+    /// How specifically this hardware is meant for a satellite: 0 when listed
+    /// by name, 1 when one of its bands matches, 2 for catch-all hardware
+    pub fn rank(&self, satellite_name: &str, satellite_bands: &[&str]) -> Option<u8> {
+        if self.satellites.iter().any(|name| name == satellite_name) {
+            Some(0)
+        } else if self.bands.iter().any(|band| {
+            satellite_bands
+                .iter()
+                .any(|wanted| band.eq_ignore_ascii_case(wanted))
+        }) {
+            Some(1)
+        } else if self.bands.is_empty() && self.satellites.is_empty() {
+            Some(2)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AntennaConfig {
+    #[serde(default)]
+    pub name: String,
+    pub beamwidth_deg: f64, // half-power beamwidth
+    #[serde(default)]
+    pub fixed: bool, // e.g. an eggbeater or turnstile that doesn't track
+    #[serde(default)]
+    pub fixed_azimuth: f64,
+    #[serde(default = "default_fixed_elevation")]
+    pub fixed_elevation: f64,
+    pub gain_dbi: Option<f64>, // at boresight, when there is no pattern file
+    pub pattern_file: Option<PathBuf>, // azimuth,elevation,gain CSV or NEC output
+    pub polarization: Option<Polarization>,
+    #[serde(flatten)]
+    pub mapping: HardwareMapping,
+}
+
+fn default_fixed_elevation() -> f64 {
+    90.0
+}
+
+/// How pass events are announced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnounceMethod {
+    #[default]
+    Speech, // run `command` with the message text, e.g. espeak
+    Cw,     // play generated Morse through `player`
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AnnouncerConfig {
+    pub method: AnnounceMethod,
+    pub command: String,
+    pub player: String,
+    pub aos_lead_minutes: i64,
+    pub elevation_milestones: Vec<f64>,
+    pub cw_wpm: u32,
+    pub cw_tone_hz: f64,
+    pub satellites: Vec<String>, // empty announces every tracked satellite
+}
+
+impl Default for AnnouncerConfig {
+    fn default() -> Self {
+        Self {
+            method: AnnounceMethod::Speech,
+            command: default_program("espeak").to_string(),
+            player: default_program("aplay").to_string(),
+            aos_lead_minutes: 1,
+            elevation_milestones: vec![30.0, 60.0],
+            cw_wpm: 20,
+            cw_tone_hz: 700.0,
+            satellites: Vec::new(),
+        }
+    }
+}
+
+/// The announcer's speech and audio programs go through PowerShell on
+/// Windows, which has neither espeak nor aplay
+fn default_program(unix: &'static str) -> &'static str {
+    if cfg!(windows) {
+        "powershell"
+    } else {
+        unix
+    }
+}
+
+/// Antenna rotator controlled through Hamlib's rotctld
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RotatorConfig {
+    pub name: String, // identifies the stored calibration
+    pub host: String,
+    pub port: u16,
+    pub min_elevation: f64, // don't move for passes below this
+    pub update_interval_seconds: u64,
+    pub tolerance_deg: f64, // skip moves smaller than this
+    pub min_azimuth: f64,   // azimuth travel, e.g. 0-450 for overlap rotators
+    pub max_azimuth: f64,
+    pub max_elevation: f64, // 180 for rotators that can flip over the top
+    pub azimuth_rate_deg_s: f64,
+    pub elevation_rate_deg_s: f64,
+    pub max_pointing_error_deg: f64, // warn before AOS when a pass plan exceeds this
+    pub park: Option<(f64, f64)>,    // (az, el) to rest at between passes
+    #[serde(flatten)]
+    pub mapping: HardwareMapping,
+}
+
+impl Default for RotatorConfig {
+    fn default() -> Self {
+        Self {
+            name: "rotator".to_string(),
+            host: "localhost".to_string(),
+            port: 4533,
+            min_elevation: 0.0,
+            update_interval_seconds: 2,
+            tolerance_deg: 1.0,
+            min_azimuth: 0.0,
+            max_azimuth: 360.0,
+            max_elevation: 90.0,
+            azimuth_rate_deg_s: 6.0, // Yaesu G-5500 class
+            elevation_rate_deg_s: 3.0,
+            max_pointing_error_deg: 10.0,
+            park: None,
+            mapping: HardwareMapping::default(),
+        }
+    }
+}
+
+/// Transceiver controlled through Hamlib's rigctld
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RigConfig {
+    pub host: String,
+    pub port: u16,
+    pub set_mode_at_aos: bool, // switch mode and filter for the transponder when a pass begins
+    pub fm_passband_hz: u32,
+    pub ssb_passband_hz: u32,
+    pub cw_passband_hz: u32,
+    pub data_passband_hz: u32,
+    pub s_meter_interval_ms: u64, // read the S-meter during passes this often; 0 turns it off
+}
+
+impl Default for RigConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 4532,
+            set_mode_at_aos: true,
+            fm_passband_hz: 15000,
+            ssb_passband_hz: 2400,
+            cw_passband_hz: 500,
+            data_passband_hz: 3000,
+            s_meter_interval_ms: 1000,
+        }
+    }
+}
+
+/// SDR receiver retuned through GQRX's remote control or SDR++'s rigctl server
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SdrConfig {
+    pub host: String,
+    pub port: u16,        // 7356 for GQRX, 4532 for SDR++
+    pub interval_ms: u64, // how often to retune during a pass
+    pub min_step_hz: u64, // smaller Doppler changes are left until they add up
+    pub set_mode: bool,   // switch the demodulator for the transponder when a pass begins
+}
+
+impl Default for SdrConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 7356,
+            interval_ms: 500,
+            min_step_hz: 10,
+            set_mode: true,
+        }
+    }
+}
+
+/// Audio recorded through each pass of the selected satellite from an audio
+/// input, as 16-bit WAV at the input's own sample rate
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RecordingConfig {
+    pub directory: Option<String>, // default: recordings/ next to the database
+    pub device: Option<String>,    // input device name; the system default when unset
+}
+
+/// Dish, LNB and slots for the geostationary pointing page
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GeostationaryConfig {
+    #[serde(deserialize_with = "frequency_mhz")]
+    pub lnb_lo_mhz: f64,
+    pub lnb_offset_hz: f64, // how far the LNB's oscillator is off, until measured on a beacon
+    #[serde(deserialize_with = "frequency_mhz")]
+    pub uplink_lo_mhz: f64, // of an upconverter or transverter; 0 transmits directly
+    pub sun_outage_deg: f64, // Sun this close to the pointing drowns the downlink
+    pub satellites: Vec<GeostationarySlot>,
+}
+
+impl Default for GeostationaryConfig {
+    fn default() -> Self {
+        Self {
+            lnb_lo_mhz: 9750.0,
+            lnb_offset_hz: 0.0,
+            uplink_lo_mhz: 0.0,
+            sun_outage_deg: 2.0,
+            satellites: vec![GeostationarySlot::qo100()],
+        }
+    }
+}
+
+/// A satellite in a geostationary slot, with its transponders' passbands
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeostationarySlot {
+    pub name: String,
+    pub longitude: f64, // east positive
+    #[serde(default, deserialize_with = "optional_frequency_mhz")]
+    pub beacon_mhz: Option<f64>, // measured to find the LNB offset
+    #[serde(default)]
+    pub uplink_offset_mhz: f64, // uplink minus downlink, for linear transponders
+    #[serde(default)]
+    pub segments: Vec<PassbandSegment>,
+}
+
+/// Part of a downlink passband, e.g. a beacon or the SSB segment
+#[derive(Debug, Clone, Deserialize)]
+pub struct PassbandSegment {
+    #[serde(deserialize_with = "frequency_mhz")]
+    pub from_mhz: f64,
+    #[serde(deserialize_with = "frequency_mhz")]
+    pub to_mhz: f64,
+    pub label: String,
+}
+
+impl GeostationarySlot {
+    /// Es'hail-2 at 25.9°E, with the AMSAT-DL bandplan of its transponders
+    fn qo100() -> Self {
+        let segment = |from_mhz: f64, to_mhz: f64, label: &str| PassbandSegment {
+            from_mhz,
+            to_mhz,
+            label: label.to_string(),
+        };
+        Self {
+            name: "QO-100".to_string(),
+            longitude: 25.9,
+            beacon_mhz: Some(10489.750),
+            uplink_offset_mhz: -8089.5,
+            segments: vec![
+                segment(10489.500, 10489.505, "Lower beacon (CW)"),
+                segment(10489.505, 10489.540, "CW"),
+                segment(10489.540, 10489.650, "Digital modes"),
+                segment(10489.650, 10489.745, "SSB"),
+                segment(10489.745, 10489.755, "Middle beacon (PSK)"),
+                segment(10489.755, 10489.990, "SSB"),
+                segment(10489.990, 10490.000, "Upper beacon"),
+                segment(10491.000, 10499.000, "Wideband transponder (DATV)"),
+            ],
+        }
+    }
+}
+
+/// External telemetry decoder started for a satellite's passes. Both commands
+/// are run without a shell; the source's stdout (audio or IQ) is piped into
+/// the decoder, and every line the decoder prints is stored as a frame.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecoderConfig {
+    pub satellite: String,
+    pub source: String,  // e.g. "rtl_fm -f 145.935M -s 48k -"
+    pub decoder: String, // e.g. "dv_decoder --stdin"
+}
+
+/// SatNOGS Network ground station whose observations are shown and scheduled
+#[derive(Debug, Clone, Deserialize)]
+pub struct SatnogsConfig {
+    pub ground_station: u32,
+    pub api_token: Option<String>, // from the Network dashboard; needed to schedule
+    #[serde(default = "default_satnogs_network_url")]
+    pub network_url: String,
+    #[serde(default = "default_satnogs_db_url")]
+    pub db_url: String,
+}
+
+fn default_satnogs_network_url() -> String {
+    "https://network.satnogs.org/api".to_string()
+}
+
+fn default_satnogs_db_url() -> String {
+    "https://db.satnogs.org/api".to_string()
+}
+
+/// Shared satellite list a club keeps in step with `crabtrack sync`
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncConfig {
+    pub remote: String, // directory, such as a git checkout, or WebDAV URL of the file
+    pub username: Option<String>, // WebDAV basic authentication
+    pub password: Option<String>,
+}
+
+/// APRS through digipeating satellites
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AprsConfig {
+    pub callsign: String,
+    pub min_elevation: f64, // elevation at which the digipeater is reliably usable
+    pub digipeaters: Vec<DigipeaterConfig>,
+    pub direwolf_command: Option<String>, // launched at AOS with a generated config
+    pub audio_device: String,             // Direwolf ADEVICE
+    pub beacon_comment: Option<String>,   // beacon the observer's position when set
+}
+
+impl Default for AprsConfig {
+    fn default() -> Self {
+        Self {
+            callsign: "N0CALL".to_string(),
+            min_elevation: 20.0,
+            digipeaters: vec![
+                DigipeaterConfig {
+                    satellite: "ISS".to_string(),
+                    frequency_mhz: 145.825,
+                    path: "ARISS".to_string(),
+                },
+                DigipeaterConfig {
+                    satellite: "NO-84".to_string(),
+                    frequency_mhz: 145.825,
+                    path: "ARISS".to_string(),
+                },
+            ],
+            direwolf_command: None,
+            audio_device: "default".to_string(),
+            beacon_comment: None,
+        }
+    }
+}
+
+/// MQTT broker Home Assistant listens on for discovered sensors
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HomeAssistantConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub client_id: String,        // also prefixes the entity ids
+    pub discovery_prefix: String, // Home Assistant's MQTT discovery prefix
+    pub topic_prefix: String,     // state topics are <topic_prefix>/<satellite>/...
+    pub interval_seconds: u64,
+    pub good_pass_elevation: f64, // passes this high are flagged "good" in the attributes
+}
+
+impl Default for HomeAssistantConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 1883,
+            username: None,
+            password: None,
+            client_id: "crabtrack".to_string(),
+            discovery_prefix: "homeassistant".to_string(),
+            topic_prefix: "crabtrack".to_string(),
+            interval_seconds: 30,
+            good_pass_elevation: 30.0,
+        }
+    }
+}
+
+/// A station setup switched as a whole: the location and the antennas,
+/// rotators and rig used there. Sections a profile leaves out keep their
+/// values from the rest of the file; one that sets `antenna` or `antennas`
+/// replaces both, and likewise for rotators.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProfileConfig {
+    pub name: String,
+    pub observer: Option<ObserverConfig>,
+    pub antenna: Option<AntennaConfig>,
+    pub antennas: Option<Vec<AntennaConfig>>,
+    pub rotator: Option<RotatorConfig>,
+    pub rotators: Option<Vec<RotatorConfig>>,
+    pub rig: Option<RigConfig>,
+}
+
+/// A satellite carrying an APRS digipeater
+#[derive(Debug, Clone, Deserialize)]
+pub struct DigipeaterConfig {
+    pub satellite: String, // matched like transponder names, e.g. "ISS"
+    #[serde(deserialize_with = "frequency_mhz")]
+    pub frequency_mhz: f64,
+    pub path: String, // digipeater alias(es), e.g. "ARISS"
 }
 
 impl Config {
     pub fn load(path: &str) -> Result<Self> {
         let contents = std::fs::read_to_string(path)?;
         let config: Config = toml::from_str(&contents)?;
+        config.check()?;
         Ok(config)
     }
+
+    /// Settings that parse but can't work together
+    fn check(&self) -> Result<()> {
+        if let Some(home_assistant) = &self.home_assistant {
+            // MQTT only allows a password after a username
+            if home_assistant.password.is_some() && home_assistant.username.is_none() {
+                anyhow::bail!("[home_assistant] password needs a username too");
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the station sections with those of the named profile
+    pub fn apply_profile(&mut self, name: &str) -> Result<()> {
+        let Some(profile) = self
+            .profiles
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+            .cloned()
+        else {
+            anyhow::bail!("no profile named '{}' in the configuration", name);
+        };
+        if let Some(observer) = profile.observer {
+            self.observer = observer;
+        }
+        if profile.antenna.is_some() || profile.antennas.is_some() {
+            self.antenna = profile.antenna;
+            self.antennas = profile.antennas.unwrap_or_default();
+        }
+        if profile.rotator.is_some() || profile.rotators.is_some() {
+            self.rotator = profile.rotator;
+            self.rotators = profile.rotators.unwrap_or_default();
+        }
+        if profile.rig.is_some() {
+            self.rig = profile.rig;
+        }
+        Ok(())
+    }
+
+    /// Every configured rotator: `[rotator]` first, then `[[rotators]]`
+    pub fn all_rotators(&self) -> Vec<RotatorConfig> {
+        self.rotator
+            .iter()
+            .chain(&self.rotators)
+            .cloned()
+            .collect()
+    }
+
+    /// The antenna best suited to a satellite, preferring ones that name it,
+    /// then ones covering one of its bands
+    pub fn antenna_for(
+        &self,
+        satellite_name: &str,
+        satellite_bands: &[&str],
+    ) -> Option<&AntennaConfig> {
+        self.antenna
+            .iter()
+            .chain(&self.antennas)
+            .filter_map(|antenna| {
+                antenna
+                    .mapping
+                    .rank(satellite_name, satellite_bands)
+                    .map(|rank| (rank, antenna))
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, antenna)| antenna)
+    }
+
+    /// Amateur bands a satellite's configured downlink and uplink fall in
+    pub fn satellite_bands(&self, satellite_name: &str) -> Vec<&'static str> {
+        let (downlink, uplink) = self.radio.frequencies_for(satellite_name);
+        let mut bands: Vec<&'static str> = [downlink, uplink]
+            .into_iter()
+            .filter_map(|freq| bandplan::band_for(freq).map(|band| band.name))
+            .collect();
+        bands.dedup();
+        bands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observer_coordinates() {
+        let observer: ObserverConfig =
+            toml::from_str("name = \"Home\"\ngrid = \"FN31\"\n").unwrap();
+        assert_eq!(observer.coordinates().unwrap(), (41.5, -73.0));
+        assert_eq!(observer.altitude, None);
+
+        let observer: ObserverConfig = toml::from_str(
+            "name = \"Home\"\nlatitude = 28.5\nlongitude = -80.6\ngrid = \"FN31\"\n",
+        )
+        .unwrap();
+        assert_eq!(observer.coordinates().unwrap(), (28.5, -80.6));
+
+        let observer: ObserverConfig = toml::from_str("name = \"Home\"\nlatitude = 28.5\n").unwrap();
+        assert!(observer.coordinates().is_err());
+
+        // East longitudes past 180 wrap; latitudes past the poles are rejected
+        let observer: ObserverConfig =
+            toml::from_str("name = \"Home\"\nlatitude = -51.7\nlongitude = 302.1\n").unwrap();
+        let (_, longitude) = observer.coordinates().unwrap();
+        assert!((longitude + 57.9).abs() < 1e-9);
+        let observer: ObserverConfig =
+            toml::from_str("name = \"Home\"\nlatitude = 91.0\nlongitude = 0.0\n").unwrap();
+        assert!(observer.coordinates().is_err());
+    }
+
+    #[test]
+    fn test_hardware_mapping_rank() {
+        let uhf = HardwareMapping {
+            bands: vec!["70cm".to_string()],
+            satellites: vec!["NOAA 19".to_string()],
+        };
+        assert_eq!(uhf.rank("NOAA 19", &[]), Some(0));
+        assert_eq!(uhf.rank("AO-91", &["70CM", "2m"]), Some(1));
+        assert_eq!(uhf.rank("AO-7", &["2m", "10m"]), None);
+        assert_eq!(HardwareMapping::default().rank("AO-7", &[]), Some(2));
+    }
+
+    #[test]
+    fn test_rotator_mapping_from_toml() {
+        let rotator: RotatorConfig =
+            toml::from_str("name = \"70cm yagi\"\nport = 4535\nbands = [\"70cm\"]\n").unwrap();
+        assert_eq!(rotator.port, 4535);
+        assert_eq!(rotator.host, "localhost");
+        assert_eq!(rotator.mapping.bands, vec!["70cm"]);
+        assert_eq!(rotator.mapping.rank("AO-7", &["2m"]), None);
+    }
+
+    #[test]
+    fn test_frequencies_with_units() {
+        let radio: SatelliteRadioConfig = toml::from_str(
+            "downlink_frequency_mhz = \"436795k\"\nuplink_frequency_mhz = 145850000\n",
+        )
+        .unwrap();
+        assert!((radio.downlink_frequency_mhz.unwrap() - 436.795).abs() < 1e-9);
+        assert!((radio.uplink_frequency_mhz.unwrap() - 145.85).abs() < 1e-9);
+        assert_eq!(radio.arm_frequency_mhz, None);
+
+        let digipeater: DigipeaterConfig =
+            toml::from_str("satellite = \"ISS\"\nfrequency_mhz = 145.825\npath = \"ARISS\"\n")
+                .unwrap();
+        assert_eq!(digipeater.frequency_mhz, 145.825);
+        let unknown_unit = "downlink_frequency_mhz = \"145.8 MC\"\n";
+        assert!(toml::from_str::<SatelliteRadioConfig>(unknown_unit).is_err());
+    }
+
+    #[test]
+    fn test_apply_profile() {
+        let profile = r#"
+[[profiles]]
+name = "Portable"
+rotators = []
+
+[profiles.observer]
+name = "Field Day"
+grid = "FN42"
+
+[profiles.rig]
+host = "192.168.4.1"
+"#;
+        let example = include_str!("../example.config.toml");
+        let mut config: Config = toml::from_str(&format!("{}{}", example, profile)).unwrap();
+        let home_antennas = config.antennas.len();
+        assert!(config.apply_profile("field").is_err());
+
+        config.apply_profile("portable").unwrap();
+        assert_eq!(config.observer.name, "Field Day");
+        assert!(config.all_rotators().is_empty());
+        assert_eq!(config.rig.as_ref().unwrap().host, "192.168.4.1");
+        assert_eq!(config.rig.as_ref().unwrap().port, 4532);
+        assert_eq!(config.antennas.len(), home_antennas);
+    }
+
+    #[test]
+    fn test_home_assistant_password_needs_username() {
+        let example = include_str!("../example.config.toml");
+        let config = |section: &str| {
+            toml::from_str::<Config>(&format!("{}\n[home_assistant]\n{}", example, section))
+                .unwrap()
+        };
+        assert!(config("").check().is_ok());
+        assert!(config("username = \"a\"\npassword = \"b\"").check().is_ok());
+        assert!(config("password = \"b\"").check().is_err());
+    }
 }
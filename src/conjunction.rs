@@ -0,0 +1,236 @@
+//! Screening the tracked objects pairwise for close approaches, for operators
+//! keeping an eye on debris near their satellite. Pairs whose altitude shells
+//! never come near each other are dropped first; the rest are sampled once a
+//! minute, and each closing-then-opening interval that could hold an approach
+//! inside the distance is refined to the time of closest approach (TCA).
+
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::Vector3;
+use sgp4::{Constants, MinutesSinceEpoch};
+
+use crate::satellite::Satellite;
+
+/// Earth's gravitational parameter, km³/s²
+const MU_KM3_S2: f64 = 398600.4418;
+
+/// Coarse sampling interval
+const STEP_SECONDS: i64 = 60;
+
+/// Refined TCAs are accurate to this
+const TOLERANCE_SECONDS: f64 = 0.001;
+
+/// Slack on the mean-element perigee and apogee, which the propagated orbit
+/// strays from by a few kilometres
+const SHELL_MARGIN_KM: f64 = 50.0;
+
+/// A close approach between two tracked objects
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conjunction {
+    pub first: String,
+    pub second: String,
+    pub tca: DateTime<Utc>,
+    pub miss_km: f64,
+    pub relative_velocity_km_s: f64,
+}
+
+/// Position and velocity in TEME, km and km/s
+type State = (Vector3<f64>, Vector3<f64>);
+
+struct Ephemeris<'a> {
+    satellite: &'a Satellite,
+    constants: Constants,
+    perigee_km: f64,
+    apogee_km: f64,
+    samples: Vec<Option<State>>,
+}
+
+impl<'a> Ephemeris<'a> {
+    fn new(satellite: &'a Satellite, from: DateTime<Utc>, steps: i64) -> Option<Self> {
+        let elements = &satellite.elements;
+        let constants = Constants::from_elements(elements).ok()?;
+        let mean_motion = elements.mean_motion * 2.0 * std::f64::consts::PI / 86400.0;
+        let semi_major_axis = (MU_KM3_S2 / (mean_motion * mean_motion)).cbrt();
+        let mut ephemeris = Self {
+            satellite,
+            constants,
+            perigee_km: semi_major_axis * (1.0 - elements.eccentricity),
+            apogee_km: semi_major_axis * (1.0 + elements.eccentricity),
+            samples: Vec::new(),
+        };
+        ephemeris.samples = (0..=steps)
+            .map(|step| ephemeris.state(from + Duration::seconds(step * STEP_SECONDS)))
+            .collect();
+        Some(ephemeris)
+    }
+
+    fn state(&self, time: DateTime<Utc>) -> Option<State> {
+        let epoch = self.satellite.elements.datetime.and_utc();
+        let minutes = (time - epoch).num_milliseconds() as f64 / 60000.0;
+        let prediction = self.constants.propagate(MinutesSinceEpoch(minutes)).ok()?;
+        Some((
+            Vector3::from(prediction.position),
+            Vector3::from(prediction.velocity),
+        ))
+    }
+
+    /// Whether the two orbits' altitude ranges come within `distance_km`
+    fn shells_overlap(&self, other: &Self, distance_km: f64) -> bool {
+        let slack = distance_km + SHELL_MARGIN_KM;
+        self.perigee_km <= other.apogee_km + slack && other.perigee_km <= self.apogee_km + slack
+    }
+}
+
+/// Relative position and velocity of `b` as seen from `a`
+fn relative(a: &State, b: &State) -> State {
+    (b.0 - a.0, b.1 - a.1)
+}
+
+/// Find the closest approach of `a` and `b` between `start` and `start + span`,
+/// over which their separation first shrinks and then grows
+fn refine(a: &Ephemeris, b: &Ephemeris, start: DateTime<Utc>, span: f64) -> Option<(f64, State)> {
+    let at = |offset: f64| {
+        let time = start + Duration::microseconds((offset * 1e6) as i64);
+        Some(relative(&a.state(time)?, &b.state(time)?))
+    };
+    let distance = |offset: f64| at(offset).map(|(r, _)| r.norm()).unwrap_or(f64::MAX);
+
+    // Golden-section search; the separation has a single minimum here
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut low, mut high) = (0.0, span);
+    let mut x1 = high - ratio * (high - low);
+    let mut x2 = low + ratio * (high - low);
+    let (mut d1, mut d2) = (distance(x1), distance(x2));
+    while high - low > TOLERANCE_SECONDS {
+        if d1 < d2 {
+            high = x2;
+            x2 = x1;
+            d2 = d1;
+            x1 = high - ratio * (high - low);
+            d1 = distance(x1);
+        } else {
+            low = x1;
+            x1 = x2;
+            d1 = d2;
+            x2 = low + ratio * (high - low);
+            d2 = distance(x2);
+        }
+    }
+    let offset = (low + high) / 2.0;
+    Some((offset, at(offset)?))
+}
+
+/// Close approaches within `distance_km` between `from` and `to`, by TCA.
+/// With `only`, just that satellite is screened against the others.
+pub fn screen(
+    satellites: &[Satellite],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    distance_km: f64,
+    only: Option<&str>,
+) -> Vec<Conjunction> {
+    let steps = (to - from).num_seconds() / STEP_SECONDS;
+    let ephemerides: Vec<Ephemeris> = satellites
+        .iter()
+        .filter_map(|satellite| Ephemeris::new(satellite, from, steps))
+        .collect();
+    let step = STEP_SECONDS as f64;
+
+    let mut conjunctions = Vec::new();
+    for (index, a) in ephemerides.iter().enumerate() {
+        for b in &ephemerides[index + 1..] {
+            let involved =
+                |e: &Ephemeris| only.is_none_or(|name| e.satellite.name.eq_ignore_ascii_case(name));
+            if !(involved(a) || involved(b)) || !a.shells_overlap(b, distance_km) {
+                continue;
+            }
+            let samples: Vec<Option<State>> = a
+                .samples
+                .iter()
+                .zip(&b.samples)
+                .map(|(sa, sb)| Some(relative(sa.as_ref()?, sb.as_ref()?)))
+                .collect();
+            for (k, pair) in samples.windows(2).enumerate() {
+                let [Some((r0, v0)), Some((r1, v1))] = pair else {
+                    continue;
+                };
+                // Separation shrinking at the start and growing at the end
+                if r0.dot(v0) >= 0.0 || r1.dot(v1) < 0.0 {
+                    continue;
+                }
+                // Moving at most this fast, they can't get closer than this
+                let speed = v0.norm().max(v1.norm());
+                let closest_possible = (r0.norm() + r1.norm() - speed * step) / 2.0;
+                if closest_possible > distance_km {
+                    continue;
+                }
+                let start = from + Duration::seconds(k as i64 * STEP_SECONDS);
+                let Some((offset, (r, v))) = refine(a, b, start, step) else {
+                    continue;
+                };
+                if r.norm() <= distance_km {
+                    conjunctions.push(Conjunction {
+                        first: a.satellite.name.clone(),
+                        second: b.satellite.name.clone(),
+                        tca: start + Duration::microseconds((offset * 1e6) as i64),
+                        miss_km: r.norm(),
+                        relative_velocity_km_s: v.norm(),
+                    });
+                }
+            }
+        }
+    }
+    conjunctions.sort_by_key(|conjunction| conjunction.tca);
+    conjunctions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::sample_satellite;
+
+    fn satellite(name: &str, tilt: f64) -> Satellite {
+        let mut satellite = sample_satellite(name);
+        satellite.elements.inclination += tilt;
+        satellite
+    }
+
+    #[test]
+    fn test_screen() {
+        // Same orbit with the plane tilted slightly: the two meet at each
+        // node, twice an orbit
+        let mut satellites = vec![
+            satellite("CUBESAT", 0.0),
+            satellite("DEBRIS", 0.5),
+            satellite("HIGH", 0.0),
+        ];
+        satellites[2].elements.mean_motion = 14.0;
+        let from = satellites[0].epoch;
+        let to = from + Duration::hours(3);
+
+        let conjunctions = screen(&satellites, from, to, 5.0, None);
+        assert!(conjunctions.len() >= 3, "{:?}", conjunctions);
+        for conjunction in &conjunctions {
+            assert_eq!(
+                (conjunction.first.as_str(), conjunction.second.as_str()),
+                ("CUBESAT", "DEBRIS")
+            );
+            assert!(conjunction.miss_km < 5.0);
+            // Nearly parallel tracks: metres per second, not kilometres
+            assert!(conjunction.relative_velocity_km_s < 0.1);
+        }
+        assert!(conjunctions.windows(2).all(|w| w[0].tca <= w[1].tca));
+
+        // No sample a second either side of the TCA is closer
+        let first = &conjunctions[0];
+        let a = Ephemeris::new(&satellites[0], first.tca, 0).unwrap();
+        let b = Ephemeris::new(&satellites[1], first.tca, 0).unwrap();
+        for offset in [-1, 1] {
+            let time = first.tca + Duration::seconds(offset);
+            let (r, _) = relative(&a.state(time).unwrap(), &b.state(time).unwrap());
+            assert!(r.norm() > first.miss_km);
+        }
+
+        assert!(screen(&satellites, from, to, 5.0, Some("HIGH")).is_empty());
+        assert!(screen(&satellites, from, to, 0.001, None).is_empty());
+    }
+}
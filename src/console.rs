@@ -0,0 +1,354 @@
+//! What the terminal can display, and fallbacks for consoles that can't show
+//! everything the UI draws.
+//!
+//! Windows Terminal and most Unix terminals handle box drawing, braille and
+//! 24-bit colour, but the legacy Windows console (conhost, which PowerShell
+//! uses when started on its own) and serial or Linux virtual consoles often
+//! manage neither. Rather than teach every widget about that, the backend
+//! wrapper below rewrites each cell on its way to the screen.
+
+use crossterm::event::{self, Event, KeyEventKind};
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::Cell,
+    layout::{Position, Size},
+    style::{Color, Modifier},
+    text::Span,
+};
+use std::io;
+
+/// How many colours the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    Monochrome,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Guess from the environment, the same variables other terminal programs use
+    pub fn detect() -> Self {
+        Self::from_env(|name| std::env::var(name).ok(), cfg!(windows))
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>, windows: bool) -> Self {
+        // https://no-color.org
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return ColorDepth::Monochrome;
+        }
+        let colorterm = var("COLORTERM").unwrap_or_default();
+        // Windows Terminal sets WT_SESSION but no TERM or COLORTERM
+        if colorterm == "truecolor" || colorterm == "24bit" || var("WT_SESSION").is_some() {
+            return ColorDepth::TrueColor;
+        }
+        match var("TERM") {
+            Some(term) if term == "dumb" => ColorDepth::Monochrome,
+            Some(term) if term.contains("256color") => ColorDepth::Ansi256,
+            Some(_) => ColorDepth::Ansi16,
+            // Without TERM on Windows this is conhost, which has the 16 console colours
+            None if windows => ColorDepth::Ansi16,
+            None => ColorDepth::Monochrome,
+        }
+    }
+}
+
+/// RGB values of the 16 ANSI colours, as xterm shows them
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// RGB value of an entry in the xterm 256-colour palette
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[index as usize].1,
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Nearest of the 16 ANSI colours
+fn nearest_ansi16((r, g, b): (u8, u8, u8)) -> Color {
+    let distance = |(r2, g2, b2): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, r2) + d(g, g2) + d(b, b2)
+    };
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// Nearest entry in the 6x6x6 colour cube of the 256-colour palette
+fn nearest_indexed((r, g, b): (u8, u8, u8)) -> Color {
+    let step = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (v - 35) / 40,
+    };
+    Color::Indexed(16 + 36 * step(r) + 6 * step(g) + step(b))
+}
+
+/// A colour the terminal can show, or `None` where it can't show any
+fn reduce_color(color: Color, depth: ColorDepth) -> Option<Color> {
+    match (depth, color) {
+        (ColorDepth::Monochrome, Color::Reset) => Some(Color::Reset),
+        (ColorDepth::Monochrome, _) => None,
+        (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => Some(nearest_ansi16((r, g, b))),
+        (ColorDepth::Ansi16, Color::Indexed(index)) => Some(nearest_ansi16(indexed_rgb(index))),
+        (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Some(nearest_indexed((r, g, b))),
+        _ => Some(color),
+    }
+}
+
+/// ASCII stand-in for a symbol the UI draws, or `None` when it is ASCII already
+pub fn ascii_symbol(symbol: &str) -> Option<String> {
+    if symbol.is_ascii() {
+        return None;
+    }
+    let mut chars = symbol.chars();
+    let replacement = match (chars.next(), chars.next()) {
+        (Some(c), None) => ascii_char(c),
+        _ => None,
+    };
+    // Keep the width, or the column after a wide symbol is never redrawn
+    let width = Span::raw(symbol).width().max(1);
+    let mut ascii = replacement.unwrap_or('?').to_string();
+    ascii.extend(std::iter::repeat_n(' ', width - 1));
+    Some(ascii)
+}
+
+fn ascii_char(c: char) -> Option<char> {
+    let ascii = match c {
+        // Box drawing, for borders and table lines
+        '─' | '━' | '═' | '┄' | '┅' | '╌' => '-',
+        '│' | '┃' | '║' | '┆' | '┇' | '╎' => '|',
+        '\u{2500}'..='\u{257f}' => '+',
+        // Braille canvas cells (the sky map and charts); the blank pattern stays blank
+        '\u{2800}' => ' ',
+        '\u{2801}'..='\u{28ff}' => '.',
+        // Gauges, bars and the kiosk's block figures
+        '░' => '.',
+        '▒' => ':',
+        // Sparkline steps keep some of their height
+        '▁' => '_',
+        '▂' | '▃' => '-',
+        '▅' | '▆' => '=',
+        '\u{2580}'..='\u{259f}' => '#',
+        '●' | '◉' | '○' | '◦' => 'o',
+        '★' | '☆' => '*',
+        '·' | '•' => '.',
+        '°' => 'd',
+        '↑' | '▲' => '^',
+        '↓' | '▼' => 'v',
+        '→' | '▶' | '►' => '>',
+        '←' | '◀' | '◄' => '<',
+        '—' | '–' => '-',
+        '±' => '+',
+        '×' => 'x',
+        '…' => '.',
+        '⚠' | '⚡' => '!',
+        '☾' => 'C',
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// Backend wrapper that rewrites cells for what the terminal can show: ASCII
+/// instead of Unicode symbols when asked for, and colours reduced to the
+/// detected depth
+pub struct ConsoleBackend<B: Backend> {
+    inner: B,
+    ascii: bool,
+    colors: ColorDepth,
+}
+
+impl<B: Backend> ConsoleBackend<B> {
+    pub fn new(inner: B, ascii: bool, colors: ColorDepth) -> Self {
+        Self {
+            inner,
+            ascii,
+            colors,
+        }
+    }
+
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    fn adapt(&self, cell: &Cell) -> Cell {
+        let mut cell = cell.clone();
+        if self.ascii {
+            if let Some(ascii) = ascii_symbol(cell.symbol()) {
+                cell.set_symbol(&ascii);
+            }
+        }
+        if self.colors < ColorDepth::TrueColor {
+            let highlighted = cell.bg != Color::Reset;
+            match (
+                reduce_color(cell.fg, self.colors),
+                reduce_color(cell.bg, self.colors),
+            ) {
+                (Some(fg), Some(bg)) => {
+                    cell.fg = fg;
+                    cell.bg = bg;
+                }
+                _ => {
+                    // Keep selections and highlights visible without colour
+                    cell.fg = Color::Reset;
+                    cell.bg = Color::Reset;
+                    if highlighted {
+                        cell.modifier.insert(Modifier::REVERSED);
+                    }
+                }
+            }
+        }
+        cell
+    }
+}
+
+impl<B: Backend> Backend for ConsoleBackend<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if !self.ascii && self.colors == ColorDepth::TrueColor {
+            return self.inner.draw(content);
+        }
+        let cells: Vec<(u16, u16, Cell)> = content
+            .map(|(x, y, cell)| (x, y, self.adapt(cell)))
+            .collect();
+        self.inner
+            .draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Read the next terminal event, or `None` for a key release. Windows reports
+/// both the press and the release of every key, where Unix terminals only
+/// report presses, so without this each key acts twice there.
+pub fn read_event() -> io::Result<Option<Event>> {
+    Ok(match event::read()? {
+        Event::Key(key) if key.kind == KeyEventKind::Release => None,
+        event => Some(event),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_detect_color_depth() {
+        let detect = |vars: &[(&str, &str)], windows: bool| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            ColorDepth::from_env(|name| vars.get(name).cloned(), windows)
+        };
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")], false),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(&[("TERM", "xterm-256color")], false), ColorDepth::Ansi256);
+        assert_eq!(detect(&[("TERM", "linux")], false), ColorDepth::Ansi16);
+        assert_eq!(detect(&[("WT_SESSION", "abc")], true), ColorDepth::TrueColor);
+        assert_eq!(detect(&[], true), ColorDepth::Ansi16);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("NO_COLOR", "1")], false),
+            ColorDepth::Monochrome
+        );
+    }
+
+    #[test]
+    fn test_reduce_color() {
+        let red = Color::Rgb(250, 10, 10);
+        assert_eq!(reduce_color(red, ColorDepth::TrueColor), Some(red));
+        assert_eq!(reduce_color(red, ColorDepth::Ansi256), Some(Color::Indexed(196)));
+        assert_eq!(reduce_color(red, ColorDepth::Ansi16), Some(Color::LightRed));
+        assert_eq!(
+            reduce_color(Color::Indexed(244), ColorDepth::Ansi16),
+            Some(Color::DarkGray)
+        );
+        assert_eq!(reduce_color(Color::Cyan, ColorDepth::Ansi16), Some(Color::Cyan));
+        assert_eq!(reduce_color(Color::Cyan, ColorDepth::Monochrome), None);
+    }
+
+    #[test]
+    fn test_ascii_symbols() {
+        assert_eq!(ascii_symbol("a"), None);
+        assert_eq!(ascii_symbol("┌").as_deref(), Some("+"));
+        assert_eq!(ascii_symbol("─").as_deref(), Some("-"));
+        assert_eq!(ascii_symbol("│").as_deref(), Some("|"));
+        assert_eq!(ascii_symbol("⠀").as_deref(), Some(" "));
+        assert_eq!(ascii_symbol("⣿").as_deref(), Some("."));
+        assert_eq!(ascii_symbol("█").as_deref(), Some("#"));
+        assert_eq!(ascii_symbol("▁").as_deref(), Some("_"));
+        assert_eq!(ascii_symbol("°").as_deref(), Some("d"));
+        // Wide symbols keep their width
+        assert_eq!(ascii_symbol("🔕").as_deref(), Some("? "));
+    }
+}
@@ -0,0 +1,147 @@
+//! Large constellations such as Starlink or OneWeb, recognised by the shared
+//! prefix of their members' names, summarised as a whole rather than as
+//! hundreds of separate satellites.
+
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::Satellite;
+
+/// Size of a sky density cell: azimuth by elevation, in degrees
+pub const CELL_AZIMUTH: f64 = 15.0;
+pub const CELL_ELEVATION: f64 = 10.0;
+
+/// Members of one constellation, by index into the satellite list
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constellation {
+    pub name: String,
+    pub members: Vec<usize>,
+}
+
+/// Constellation a satellite belongs to, from names such as "STARLINK-1007"
+/// or "IRIDIUM 106": the leading word, when a number follows it
+pub fn constellation_name(satellite_name: &str) -> Option<String> {
+    let (prefix, rest) = satellite_name.split_once(['-', ' '])?;
+    let numbered = rest.trim_start().starts_with(|c: char| c.is_ascii_digit());
+    if prefix.is_empty() || !numbered || !prefix.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(prefix.to_ascii_uppercase())
+}
+
+/// Constellations with at least `min_size` members, largest first
+pub fn group(satellites: &[Satellite], min_size: usize) -> Vec<Constellation> {
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, satellite) in satellites.iter().enumerate() {
+        if let Some(name) = constellation_name(&satellite.name) {
+            groups.entry(name).or_default().push(index);
+        }
+    }
+    let mut constellations: Vec<Constellation> = groups
+        .into_iter()
+        .filter(|(_, members)| members.len() >= min_size.max(2))
+        .map(|(name, members)| Constellation { name, members })
+        .collect();
+    constellations.sort_by_key(|c| std::cmp::Reverse(c.members.len()));
+    constellations
+}
+
+/// The next `count` passes of any member still to end, by AOS
+pub fn next_passes<'a>(
+    satellites: &'a [Satellite],
+    constellation: &Constellation,
+    now: DateTime<Utc>,
+    count: usize,
+) -> Vec<(&'a Satellite, &'a SatellitePass)> {
+    let mut passes: Vec<_> = constellation
+        .members
+        .iter()
+        .filter_map(|&index| satellites.get(index))
+        .flat_map(|satellite| {
+            satellite
+                .passes
+                .iter()
+                .filter(move |pass| pass.los_time > now)
+                .map(move |pass| (satellite, pass))
+        })
+        .collect();
+    passes.sort_by_key(|(_, pass)| pass.aos_time);
+    passes.truncate(count);
+    passes
+}
+
+/// How many of the given look angles fall in each cell of the sky, keyed by
+/// the cell's lower azimuth and elevation edges; below the horizon is left out
+pub fn density(look_angles: impl IntoIterator<Item = (f64, f64)>) -> BTreeMap<(u16, u16), usize> {
+    let mut cells = BTreeMap::new();
+    for (azimuth, elevation) in look_angles {
+        if elevation <= 0.0 {
+            continue;
+        }
+        let az = (azimuth.rem_euclid(360.0) / CELL_AZIMUTH).floor() * CELL_AZIMUTH;
+        let el = (elevation.min(89.999) / CELL_ELEVATION).floor() * CELL_ELEVATION;
+        *cells.entry((az as u16, el as u16)).or_insert(0) += 1;
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::sample_satellite;
+
+    #[test]
+    fn test_constellation_name() {
+        assert_eq!(
+            constellation_name("STARLINK-1007").as_deref(),
+            Some("STARLINK")
+        );
+        assert_eq!(constellation_name("OneWeb-0012").as_deref(), Some("ONEWEB"));
+        assert_eq!(
+            constellation_name("IRIDIUM 106").as_deref(),
+            Some("IRIDIUM")
+        );
+        assert_eq!(constellation_name("ISS (ZARYA)"), None);
+        assert_eq!(constellation_name("GPS BIIR-2  (PRN 13)"), None);
+        assert_eq!(constellation_name("SO-50"), Some("SO".to_string()));
+        assert_eq!(constellation_name("AO-7"), Some("AO".to_string()));
+        assert_eq!(constellation_name("SAUDISAT"), None);
+    }
+
+    #[test]
+    fn test_group_and_density() {
+        let satellites: Vec<Satellite> = [
+            "STARLINK-1",
+            "STARLINK-2",
+            "STARLINK-3",
+            "ISS (ZARYA)",
+            "SO-50",
+        ]
+        .into_iter()
+        .map(sample_satellite)
+        .collect();
+        let constellations = group(&satellites, 3);
+        assert_eq!(
+            constellations,
+            vec![Constellation {
+                name: "STARLINK".into(),
+                members: vec![0, 1, 2]
+            }]
+        );
+        // A lone amateur satellite never counts, whatever the minimum
+        assert_eq!(group(&satellites, 0).len(), 1);
+
+        let cells = density([
+            (10.0, 5.0),
+            (14.9, 9.9),
+            (15.0, 45.0),
+            (350.0, 90.0),
+            (90.0, -3.0),
+        ]);
+        assert_eq!(cells.get(&(0, 0)), Some(&2));
+        assert_eq!(cells.get(&(15, 40)), Some(&1));
+        assert_eq!(cells.get(&(345, 80)), Some(&1));
+        assert_eq!(cells.values().sum::<usize>(), 4);
+    }
+}
@@ -0,0 +1,362 @@
+//! Machine control over JSON lines, for home-grown automation: requests come
+//! in on stdin or a unix socket as JSON-RPC 2.0 objects, one per line, and
+//! replies and pass events go back the same way.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// JSON-RPC 2.0 error codes
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// Error returned for a request, carried back to the client that sent it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// A method call; requests without an id are notifications and get no reply
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: Value,
+}
+
+impl Request {
+    /// String parameter `name`, if given
+    pub fn str_param(&self, name: &str) -> Result<Option<&str>, RpcError> {
+        match self.params.get(name) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value)),
+            Some(_) => Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("'{}' must be a string", name),
+            )),
+        }
+    }
+}
+
+/// Parse one line; on failure, the error reply to send (with the id, if one was readable)
+pub fn parse_request(line: &str) -> Result<Request, Value> {
+    let value: Value = serde_json::from_str(line)
+        .map_err(|e| error_response(None, &RpcError::new(PARSE_ERROR, e.to_string())))?;
+    let id = value.get("id").cloned();
+    let invalid =
+        |message: &str| error_response(id.clone(), &RpcError::new(INVALID_REQUEST, message));
+
+    let Some(method) = value.get("method").and_then(Value::as_str) else {
+        return Err(invalid("missing method"));
+    };
+    let params = match value.get("params") {
+        None | Some(Value::Null) => json!({}),
+        Some(params @ Value::Object(_)) => params.clone(),
+        Some(_) => return Err(invalid("params must be an object")),
+    };
+    Ok(Request {
+        id,
+        method: method.to_string(),
+        params,
+    })
+}
+
+pub fn result_response(id: Option<Value>, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub fn error_response(id: Option<Value>, error: &RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// A message sent unprompted to every client, such as a pass event
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Something read from a client
+pub enum Incoming {
+    Line(usize, String),
+    Closed(usize),
+}
+
+/// Client number of stdin and stdout; socket clients count up from 1
+pub const STDIO_CLIENT: usize = 0;
+
+/// How long a socket client may keep a write waiting before it is dropped
+const WRITE_TIMEOUT: Duration = Duration::from_millis(250);
+
+type Writers = Arc<Mutex<HashMap<usize, Box<dyn Write + Send>>>>;
+
+/// Clients connected over stdin/stdout or a unix socket
+pub struct ControlChannel {
+    receiver: Receiver<Incoming>,
+    writers: Writers,
+    #[cfg(unix)]
+    socket_path: Option<std::path::PathBuf>,
+}
+
+/// Forward lines from `reader` as client `client` until it closes
+fn spawn_reader(client: usize, reader: impl BufRead + Send + 'static, sender: Sender<Incoming>) {
+    std::thread::spawn(move || {
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if !line.trim().is_empty() && sender.send(Incoming::Line(client, line)).is_err() {
+                return;
+            }
+        }
+        let _ = sender.send(Incoming::Closed(client));
+    });
+}
+
+/// The writing half of a socket client; dropping it hangs up, which also ends
+/// the client's reader
+#[cfg(unix)]
+struct SocketWriter(std::os::unix::net::UnixStream);
+
+#[cfg(unix)]
+impl Write for SocketWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SocketWriter {
+    fn drop(&mut self) {
+        let _ = self.0.shutdown(std::net::Shutdown::Both);
+    }
+}
+
+impl ControlChannel {
+    /// Read requests from stdin and write replies to stdout
+    pub fn stdio() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let writers: Writers = Arc::new(Mutex::new(HashMap::new()));
+        writers
+            .lock()
+            .unwrap()
+            .insert(STDIO_CLIENT, Box::new(std::io::stdout()));
+        spawn_reader(STDIO_CLIENT, BufReader::new(std::io::stdin()), sender);
+        Self {
+            receiver,
+            writers,
+            #[cfg(unix)]
+            socket_path: None,
+        }
+    }
+
+    /// Listen on a unix socket at `path`, replacing a stale one, with any
+    /// number of clients
+    #[cfg(unix)]
+    pub fn unix_socket(path: &std::path::Path) -> anyhow::Result<Self> {
+        use std::os::unix::fs::FileTypeExt;
+        use std::os::unix::net::{UnixListener, UnixStream};
+
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                anyhow::bail!("{} exists and is not a socket", path.display());
+            }
+            if UnixStream::connect(path).is_ok() {
+                anyhow::bail!("crabtrack is already running on {}", path.display());
+            }
+            std::fs::remove_file(path)?;
+        }
+        let listener = UnixListener::bind(path)?;
+        let (sender, receiver) = mpsc::channel();
+        let writers: Writers = Arc::new(Mutex::new(HashMap::new()));
+        let accepted = writers.clone();
+        std::thread::spawn(move || {
+            for (stream, client) in listener.incoming().flatten().zip(1..) {
+                let Ok(writer) = stream.try_clone() else {
+                    continue;
+                };
+                if writer.set_write_timeout(Some(WRITE_TIMEOUT)).is_err() {
+                    continue;
+                }
+                accepted
+                    .lock()
+                    .unwrap()
+                    .insert(client, Box::new(SocketWriter(writer)));
+                spawn_reader(client, BufReader::new(stream), sender.clone());
+            }
+        });
+        Ok(Self {
+            receiver,
+            writers,
+            socket_path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Wait up to `timeout` for input, then take everything else already waiting
+    pub fn poll(&self, timeout: Duration) -> Vec<Incoming> {
+        let mut incoming = match self.receiver.recv_timeout(timeout) {
+            Ok(first) => vec![first],
+            Err(RecvTimeoutError::Timeout) => return Vec::new(),
+            Err(RecvTimeoutError::Disconnected) => {
+                std::thread::sleep(timeout);
+                return Vec::new();
+            }
+        };
+        incoming.extend(self.receiver.try_iter());
+        incoming
+    }
+
+    /// Send `message` to one client, dropping it if it has gone away or
+    /// stopped reading
+    pub fn send(&self, client: usize, message: &Value) {
+        let mut writers = self.writers.lock().unwrap();
+        if let Some(writer) = writers.get_mut(&client) {
+            if writeln!(writer, "{}", message)
+                .and_then(|_| writer.flush())
+                .is_err()
+            {
+                writers.remove(&client);
+            }
+        }
+    }
+
+    /// Send `message` to every client, dropping any that have gone away or
+    /// stopped reading
+    pub fn broadcast(&self, message: &Value) {
+        let mut writers = self.writers.lock().unwrap();
+        writers.retain(|_, writer| {
+            writeln!(writer, "{}", message)
+                .and_then(|_| writer.flush())
+                .is_ok()
+        });
+    }
+
+    pub fn disconnect(&self, client: usize) {
+        self.writers.lock().unwrap().remove(&client);
+    }
+}
+
+impl Drop for ControlChannel {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(path) = &self.socket_path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let request = parse_request(
+            r#"{"jsonrpc": "2.0", "id": 7, "method": "select", "params": {"satellite": "SO-50"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(request.method, "select");
+        assert_eq!(request.str_param("satellite").unwrap(), Some("SO-50"));
+        assert_eq!(request.str_param("other").unwrap(), None);
+
+        let notification = parse_request(r#"{"method": "stop"}"#).unwrap();
+        assert_eq!(notification.id, None);
+        assert_eq!(notification.params, json!({}));
+
+        let bad_param =
+            parse_request(r#"{"id": 1, "method": "select", "params": {"satellite": 5}}"#).unwrap();
+        assert_eq!(
+            bad_param.str_param("satellite").unwrap_err().code,
+            INVALID_PARAMS
+        );
+
+        let error = parse_request("{not json").unwrap_err();
+        assert_eq!(error["error"]["code"], json!(PARSE_ERROR));
+        assert_eq!(error["id"], Value::Null);
+
+        let error = parse_request(r#"{"id": "a", "params": {}}"#).unwrap_err();
+        assert_eq!(error["error"]["code"], json!(INVALID_REQUEST));
+        assert_eq!(error["id"], json!("a"));
+
+        let error = parse_request(r#"{"id": 2, "method": "select", "params": [1]}"#).unwrap_err();
+        assert_eq!(error["error"]["code"], json!(INVALID_REQUEST));
+    }
+
+    #[test]
+    fn test_responses() {
+        assert_eq!(
+            result_response(Some(json!(1)), json!({"ok": true})).to_string(),
+            r#"{"id":1,"jsonrpc":"2.0","result":{"ok":true}}"#
+        );
+        let error = error_response(Some(json!(2)), &RpcError::new(METHOD_NOT_FOUND, "no"));
+        assert_eq!(error["error"]["code"], json!(-32601));
+        assert_eq!(notification("event", json!({}))["method"], json!("event"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_socket_replaces_only_stale_sockets() {
+        let path =
+            std::env::temp_dir().join(format!("crabtrack-control-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // A socket nobody is listening on is left over from a crash
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        let channel = ControlChannel::unix_socket(&path).unwrap();
+
+        let error = ControlChannel::unix_socket(&path).err().unwrap();
+        assert!(error.to_string().contains("already running"));
+        drop(channel);
+
+        std::fs::write(&path, "not a socket").unwrap();
+        let error = ControlChannel::unix_socket(&path).err().unwrap();
+        assert!(error.to_string().contains("not a socket"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_client_that_stops_reading_is_dropped() {
+        let path =
+            std::env::temp_dir().join(format!("crabtrack-stalled-{}.sock", std::process::id()));
+        let channel = ControlChannel::unix_socket(&path).unwrap();
+        let stalled = std::os::unix::net::UnixStream::connect(&path).unwrap();
+        while channel.writers.lock().unwrap().is_empty() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        // Nothing is read, so the socket buffer fills and a write times out
+        let event = notification("event", json!({ "padding": "x".repeat(64 * 1024) }));
+        for _ in 0..64 {
+            channel.broadcast(&event);
+        }
+        assert!(channel.writers.lock().unwrap().is_empty());
+        assert!(matches!(
+            channel.poll(Duration::from_secs(1)).first(),
+            Some(Incoming::Closed(1))
+        ));
+        drop(stalled);
+    }
+}
@@ -0,0 +1,245 @@
+//! Where contacts have actually worked, built from the pass log. Each logged
+//! pass is laid over the sky by azimuth, and the telemetry frames decoded
+//! during it mark the lowest elevation the station heard the satellite at in
+//! each direction: a measured horizon that includes the antenna and
+//! everything around it.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::database::{Database, PassLogEntry};
+use crate::observer::Observer;
+use crate::satellite::Satellite;
+
+pub const SECTOR_DEGREES: f64 = 10.0;
+pub const SECTORS: usize = 36;
+
+/// Passes rated below this are counted as poor; unrated passes count as good
+const GOOD_RATING: i32 = 3;
+
+/// Without recorded positions, a pass further than this from its satellite's
+/// current elements is left out rather than placed in the wrong direction
+const MAX_ELEMENT_AGE_DAYS: i64 = 14;
+
+const TRACK_STEP_SECONDS: i64 = 10;
+
+/// Logged passes and decodes in one slice of azimuth
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sector {
+    pub good_passes: u32,
+    pub poor_passes: u32,
+    pub decodes: u32,
+    pub lowest_decode: Option<f64>, // elevation in degrees
+}
+
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    pub sectors: [Sector; SECTORS],
+    pub passes: usize,
+    pub decodes: Vec<(f64, f64)>, // (azimuth, elevation) of each decoded frame
+    pub skipped: usize,           // logged passes that couldn't be placed
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Self {
+            sectors: [Sector::default(); SECTORS],
+            passes: 0,
+            decodes: Vec::new(),
+            skipped: 0,
+        }
+    }
+}
+
+/// Index of the sector containing an azimuth
+pub fn sector_of(azimuth: f64) -> usize {
+    (azimuth.rem_euclid(360.0) / SECTOR_DEGREES) as usize % SECTORS
+}
+
+impl Coverage {
+    /// Add one pass given its track as (time, azimuth, elevation) in time
+    /// order, and the times frames were decoded during it
+    pub fn add_pass(
+        &mut self,
+        track: &[(DateTime<Utc>, f64, f64)],
+        rating: Option<i32>,
+        decode_times: &[DateTime<Utc>],
+    ) {
+        if track.is_empty() {
+            return;
+        }
+        self.passes += 1;
+        let good = rating.is_none_or(|rating| rating >= GOOD_RATING);
+        let mut crossed = [false; SECTORS];
+        for &(_, azimuth, elevation) in track {
+            if elevation >= 0.0 {
+                crossed[sector_of(azimuth)] = true;
+            }
+        }
+        for (sector, _) in self.sectors.iter_mut().zip(crossed).filter(|(_, c)| *c) {
+            if good {
+                sector.good_passes += 1;
+            } else {
+                sector.poor_passes += 1;
+            }
+        }
+
+        for &time in decode_times {
+            // The track sample nearest the decode, a few seconds away at most
+            let Some(&(_, azimuth, elevation)) = track
+                .iter()
+                .min_by_key(|(at, _, _)| (*at - time).num_milliseconds().abs())
+            else {
+                continue;
+            };
+            let sector = &mut self.sectors[sector_of(azimuth)];
+            sector.decodes += 1;
+            sector.lowest_decode =
+                Some(sector.lowest_decode.map_or(elevation, |e| e.min(elevation)));
+            self.decodes.push((azimuth, elevation));
+        }
+    }
+
+    /// The lowest decoded elevation at the middle of each sector with decodes
+    pub fn contour(&self) -> Vec<(f64, f64)> {
+        self.sectors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, sector)| {
+                let azimuth = (index as f64 + 0.5) * SECTOR_DEGREES;
+                sector
+                    .lowest_decode
+                    .map(|elevation| (azimuth, elevation.max(0.0)))
+            })
+            .collect()
+    }
+
+    /// The contour as an `azimuth,elevation` file for `horizon_file`
+    pub fn horizon_csv(&self) -> String {
+        let mut csv = String::from("azimuth,elevation\n");
+        for (azimuth, elevation) in self.contour() {
+            csv.push_str(&format!("{:.1},{:.1}\n", azimuth, elevation));
+        }
+        csv
+    }
+}
+
+/// Lay every logged pass over the sky. Recorded positions are used where the
+/// pass has them, and the satellite's current elements otherwise.
+pub fn build(
+    database: &Database,
+    satellites: &[Satellite],
+    observer: &Observer,
+) -> Result<Coverage> {
+    let mut coverage = Coverage::default();
+    for entry in database.read_pass_log("")? {
+        match pass_track(database, satellites, observer, &entry) {
+            Some(track) => {
+                let (from, to) = (track[0].0, track[track.len() - 1].0);
+                let decode_times: Vec<DateTime<Utc>> = database
+                    .read_telemetry_times(
+                        &entry.satellite_name,
+                        &from.to_rfc3339(),
+                        &to.to_rfc3339(),
+                    )?
+                    .iter()
+                    .filter_map(|time| DateTime::parse_from_rfc3339(time).ok())
+                    .map(|time| time.with_timezone(&Utc))
+                    .collect();
+                coverage.add_pass(&track, entry.rating, &decode_times);
+            }
+            None => coverage.skipped += 1,
+        }
+    }
+    Ok(coverage)
+}
+
+fn pass_track(
+    database: &Database,
+    satellites: &[Satellite],
+    observer: &Observer,
+    entry: &PassLogEntry,
+) -> Option<Vec<(DateTime<Utc>, f64, f64)>> {
+    let aos = DateTime::parse_from_rfc3339(&entry.aos_time)
+        .ok()?
+        .with_timezone(&Utc);
+    let los = DateTime::parse_from_rfc3339(&entry.los_time)
+        .ok()?
+        .with_timezone(&Utc);
+    let stamp = |time: DateTime<Utc>| time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let recorded = database
+        .read_position_history(&entry.satellite_name, &stamp(aos), &stamp(los))
+        .unwrap_or_default();
+    if !recorded.is_empty() {
+        return Some(
+            recorded
+                .iter()
+                .filter_map(|record| {
+                    let time = DateTime::parse_from_rfc3339(&record.recorded_at).ok()?;
+                    Some((time.with_timezone(&Utc), record.azimuth, record.elevation))
+                })
+                .collect(),
+        );
+    }
+
+    let satellite = satellites
+        .iter()
+        .find(|sat| sat.name == entry.satellite_name)?;
+    if (aos - satellite.epoch).num_days().abs() > MAX_ELEMENT_AGE_DAYS {
+        return None;
+    }
+    let mut track = Vec::new();
+    let mut time = aos;
+    while time <= los {
+        if let Ok(position) = satellite.calculate_position(time, observer) {
+            track.push((time, position.azimuth, position.elevation));
+        }
+        time += Duration::seconds(TRACK_STEP_SECONDS);
+    }
+    (!track.is_empty()).then_some(track)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage() {
+        let start = Utc::now();
+        // North to east, rising to 40° and setting again
+        let track: Vec<_> = (0..=9)
+            .map(|i| {
+                let elevation = 40.0 - (i as f64 - 4.5).abs() * 8.0;
+                (
+                    start + Duration::seconds(i * 10),
+                    i as f64 * 10.0,
+                    elevation,
+                )
+            })
+            .collect();
+        let mut coverage = Coverage::default();
+        coverage.add_pass(
+            &track,
+            None,
+            &[start + Duration::seconds(12), start + Duration::seconds(88)],
+        );
+        coverage.add_pass(&track, Some(1), &[]);
+
+        assert_eq!(coverage.passes, 2);
+        assert_eq!(coverage.sectors[0].good_passes, 1);
+        assert_eq!(coverage.sectors[0].poor_passes, 1);
+        assert_eq!(coverage.sectors[12].good_passes, 0);
+        // Decodes land on the nearest track samples, at 10° and 90° azimuth
+        assert_eq!(coverage.sectors[1].decodes, 1);
+        assert_eq!(coverage.sectors[1].lowest_decode, Some(12.0));
+        assert_eq!(coverage.sectors[9].lowest_decode, Some(4.0));
+        assert_eq!(coverage.contour(), vec![(15.0, 12.0), (95.0, 4.0)]);
+        assert_eq!(
+            coverage.horizon_csv(),
+            "azimuth,elevation\n15.0,12.0\n95.0,4.0\n"
+        );
+
+        assert_eq!(sector_of(-5.0), 35);
+        assert_eq!(sector_of(360.0), 0);
+    }
+}
@@ -0,0 +1,190 @@
+//! Attaching the TUI to a tracking daemon (`crabtrack daemon`), which keeps
+//! driving the rotators and rig when the terminal is closed. The TUI still
+//! predicts and draws everything itself; it hands the hardware to the daemon
+//! and keeps the selection and tracking state of the two in step.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use crate::control;
+
+/// How often the attached TUI asks the daemon for its state
+const STATUS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Socket the daemon listens on unless told otherwise
+pub fn default_socket_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("crabtrack")
+        .join("daemon.sock")
+}
+
+/// What the daemon last reported, or was last told
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DaemonState {
+    pub selected: Option<String>,
+    pub auto_follow: bool,
+    pub tracking: bool,
+    pub rotators: Vec<(String, String)>, // (name, status)
+    pub rig: Option<String>,
+    pub sdr: Option<String>,
+}
+
+/// Apply the result of a `status` request
+fn apply_status(state: &mut DaemonState, result: &Value) {
+    state.selected = result["selected"].as_str().map(str::to_string);
+    state.auto_follow = result["auto_follow"].as_bool().unwrap_or(false);
+    state.tracking = result["tracking"].as_bool().unwrap_or(false);
+    state.rotators = result["rotators"]
+        .as_array()
+        .map(|rotators| {
+            rotators
+                .iter()
+                .filter_map(|r| Some((r["name"].as_str()?.into(), r["status"].as_str()?.into())))
+                .collect()
+        })
+        .unwrap_or_default();
+    state.rig = result["rig"].as_str().map(str::to_string);
+    state.sdr = result["sdr"].as_str().map(str::to_string);
+}
+
+/// Connection from the TUI to a running daemon
+pub struct DaemonClient {
+    pub path: PathBuf,
+    pub state: DaemonState,
+    pub connected: bool,
+    stream: UnixStream,
+    receiver: Receiver<Value>,
+    last_status: Option<Instant>,
+    pending_status: usize, // status replies still to come; only the last is current
+}
+
+impl DaemonClient {
+    pub fn connect(path: &Path) -> Result<Self> {
+        let stream = UnixStream::connect(path).with_context(|| {
+            format!(
+                "no daemon listening at {}; start one with `crabtrack daemon`",
+                path.display()
+            )
+        })?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if let Ok(message) = serde_json::from_str(&line) {
+                    if sender.send(message).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        let mut client = Self {
+            path: path.to_path_buf(),
+            state: DaemonState::default(),
+            connected: true,
+            stream,
+            receiver,
+            last_status: None,
+            pending_status: 0,
+        };
+        client.request_status();
+        Ok(client)
+    }
+
+    fn send(&mut self, message: Value) {
+        if self.connected && writeln!(self.stream, "{}", message).is_err() {
+            self.connected = false;
+        }
+    }
+
+    fn request_status(&mut self) {
+        self.last_status = Some(Instant::now());
+        self.pending_status += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": "status", "method": "status" }));
+    }
+
+    /// Pass on a change made in the TUI. Status replies to requests sent
+    /// before it are stale, so only the one asked for after it is applied
+    pub fn change(&mut self, method: &str, params: Value) {
+        self.send(control::notification(method, params));
+        self.request_status();
+    }
+
+    /// Take in what the daemon has sent, asking for its state now and then;
+    /// true when anything changed
+    pub fn update(&mut self) -> bool {
+        if self
+            .last_status
+            .is_none_or(|last| last.elapsed() >= STATUS_INTERVAL)
+        {
+            self.request_status();
+        }
+        let before = self.state.clone();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(message) => self.handle(&message),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.connected = false;
+                    break;
+                }
+            }
+        }
+        self.state != before
+    }
+
+    fn handle(&mut self, message: &Value) {
+        match (message["id"].as_str(), message["method"].as_str()) {
+            (Some("status"), _) => {
+                self.pending_status = self.pending_status.saturating_sub(1);
+                if self.pending_status == 0 && message["result"].is_object() {
+                    apply_status(&mut self.state, &message["result"]);
+                }
+            }
+            (_, Some("selected")) if self.pending_status == 0 => {
+                self.state.selected = message["params"]["satellite"].as_str().map(str::to_string);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_status() {
+        let mut state = DaemonState::default();
+        apply_status(
+            &mut state,
+            &json!({
+                "selected": "SO-50",
+                "auto_follow": true,
+                "tracking": true,
+                "rotators": [{ "name": "yagi", "status": "Tracking" }],
+                "rig": "IC-9700",
+                "sdr": "436.8023 MHz for SO-50",
+            }),
+        );
+        assert_eq!(state.selected.as_deref(), Some("SO-50"));
+        assert!(state.auto_follow && state.tracking);
+        assert_eq!(
+            state.rotators,
+            vec![("yagi".to_string(), "Tracking".to_string())]
+        );
+        assert_eq!(state.rig.as_deref(), Some("IC-9700"));
+        assert_eq!(state.sdr.as_deref(), Some("436.8023 MHz for SO-50"));
+
+        apply_status(&mut state, &json!({ "selected": null }));
+        assert_eq!(state, DaemonState::default());
+    }
+}
@@ -1,12 +1,24 @@
 use anyhow::Result;
-use duckdb::{params, Connection};
-use std::path::Path;
+use chrono::{DateTime, Utc};
+use duckdb::types::Value;
+use duckdb::{params, AccessMode, Config, Connection};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::pass_prediction::SatellitePass;
 
 /// Satellite details stored in the database
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SatelliteDetails {
+    #[serde(skip)]
     pub id: Option<i64>,
     pub name: String,
+    pub alias: Option<String>, // shown in place of the TLE name
     pub tle_line1: String,
     pub tle_line2: String,
     pub launch_date: Option<String>,
@@ -24,6 +36,7 @@ impl SatelliteDetails {
         Self {
             id: None,
             name,
+            alias: None,
             tle_line1: String::new(),
             tle_line2: String::new(),
             launch_date: None,
@@ -38,32 +51,376 @@ impl SatelliteDetails {
     }
 }
 
+/// A satellite announced for launch whose element sets are not published yet
+#[derive(Debug, Clone)]
+pub struct PendingLaunch {
+    pub id: Option<i64>,
+    pub identifier: String, // NORAD catalog number or international designator
+    pub added_at: String,
+}
+
+/// A transponder or beacon imported from a frequency file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Transponder {
+    #[serde(skip)]
+    pub id: Option<i64>,
+    pub satellite_name: String,
+    pub description: String,
+    pub downlink_mhz: Option<f64>,
+    pub uplink_mhz: Option<f64>,
+    pub downlink_mode: Option<String>,
+    pub uplink_mode: Option<String>,
+    pub inverting: bool,
+    pub ctcss_hz: Option<f64>,
+    pub source: String, // file format it came from, e.g. "sqf"
+}
+
+/// A named set of satellites, e.g. a GPredict module
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SatelliteGroup {
+    pub name: String,
+    pub satellites: Vec<String>,
+}
+
+/// A pass the operator logged, with optional notes and a 1-5 quality rating
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PassLogEntry {
+    pub id: Option<i64>,
+    pub satellite_name: String,
+    pub aos_time: String, // RFC 3339
+    pub los_time: String,
+    pub max_elevation: f64,
+    pub notes: Option<String>,
+    pub rating: Option<i32>,
+    pub recording: Option<String>, // path of the pass's audio recording
+}
+
+/// Pointing corrections for a rotator, measured with the calibration screen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RotatorCalibration {
+    pub rotator_name: String,
+    pub azimuth_offset: f64, // where the antenna points minus where it was sent
+    pub elevation_offset: f64,
+}
+
+/// Passes predicted for a satellite over a stretch of time, as cached
+#[derive(Debug, Clone)]
+pub struct PassPrediction {
+    pub computed_from: DateTime<Utc>,
+    pub computed_until: DateTime<Utc>, // every pass ending before this was found
+    pub passes: Vec<SatellitePass>,
+}
+
+/// How far a rotator's reported position strayed from where it was sent
+/// during one tracked pass. Errors are reported minus commanded, in degrees.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PointingErrorStats {
+    pub rotator_name: String,
+    pub satellite_name: String,
+    pub pass_start: String, // RFC 3339, first sample of the pass
+    pub samples: i64,
+    pub mean_error_deg: f64, // angle between the two directions
+    pub rms_error_deg: f64,
+    pub max_error_deg: f64,
+    pub mean_azimuth_error_deg: f64, // signed; a drifting bias points at a slipping mount
+    pub mean_elevation_error_deg: f64,
+}
+
+/// Manual correction for a satellite whose oscillator is off frequency
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrequencyOffset {
+    pub satellite_name: String,
+    pub transponder: String, // nominal downlink, see radio::transponder_label
+    pub offset_hz: f64,
+}
+
+/// One line of output from an external telemetry decoder
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TelemetryFrame {
+    pub id: Option<i64>,
+    pub satellite_name: String,
+    pub received_at: String, // RFC 3339
+    pub frame: String,       // as printed by the decoder, e.g. hex or decoded fields
+}
+
+/// A satellite's computed position at one moment of a pass, recorded for
+/// comparison with signal logs afterwards
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PositionRecord {
+    pub satellite_name: String,
+    pub recorded_at: String, // RFC 3339 in UTC with milliseconds, so times sort as text
+    pub azimuth: f64,
+    pub elevation: f64,
+    pub range_km: f64,
+    pub range_rate_km_s: f64,
+    pub doppler_hz: Option<f64>, // downlink shift, when a downlink frequency is known
+    pub signal_db: Option<f64>,  // rig S-meter relative to S9, when one was read
+}
+
+/// When and where a satellite's transponder is on, as entered; see `schedule`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperatingRule {
+    pub id: Option<i64>,
+    pub satellite_name: String,
+    pub days: Option<String>,   // e.g. "Sat,Sun" or "Mon-Fri"; every day when None
+    pub hours: Option<String>,  // UTC, e.g. "06:00-18:00"
+    pub region: Option<String>, // sub-satellite point box, "south,north,west,east"
+    pub note: String,
+}
+
+/// Logged passes summarized for one satellite
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SatellitePassStats {
+    pub satellite_name: String,
+    pub passes: i64,
+    pub best_elevation: f64,
+    pub average_rating: Option<f64>,
+}
+
+/// Summary of the pass log for the statistics view
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogStats {
+    pub total_passes: i64,
+    pub satellites: Vec<SatellitePassStats>, // most worked first
+    pub grids: Vec<(String, i64)>,           // 4-character locators mentioned in notes
+    pub passes_by_hour: [i64; 24],           // by UTC hour of AOS
+}
+
+/// Rows returned by an ad-hoc query, with every value rendered as text
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl QueryResult {
+    /// Render as a plain-text table with aligned columns
+    pub fn to_table(&self) -> String {
+        let mut widths: Vec<usize> = self.columns.iter().map(|c| c.chars().count()).collect();
+        for row in &self.rows {
+            for (width, value) in widths.iter_mut().zip(row) {
+                *width = (*width).max(value.chars().count());
+            }
+        }
+
+        let format_row = |values: &[String]| {
+            values
+                .iter()
+                .zip(&widths)
+                .map(|(value, width)| format!("{:<width$}", value, width = width))
+                .collect::<Vec<_>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        };
+
+        let mut out = format_row(&self.columns);
+        out.push('\n');
+        out.push_str(
+            &widths
+                .iter()
+                .map(|width| "-".repeat(*width))
+                .collect::<Vec<_>>()
+                .join("-+-"),
+        );
+        out.push('\n');
+        for row in &self.rows {
+            out.push_str(&format_row(row));
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "({} row{})",
+            self.rows.len(),
+            if self.rows.len() == 1 { "" } else { "s" }
+        ));
+        out
+    }
+}
+
+/// Statements an ad-hoc query may start with; anything else could modify data
+const READ_ONLY_KEYWORDS: &[&str] = &[
+    "SELECT", "WITH", "FROM", "VALUES", "TABLE", "DESCRIBE", "SHOW", "SUMMARIZE", "EXPLAIN",
+];
+
+/// Whether SQL contains a `;` outside of quotes and comments
+fn has_statement_separator(sql: &str) -> bool {
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => return true,
+            '\'' | '"' => {
+                // A doubled quote inside a literal reopens it on the next pass
+                for inner in chars.by_ref() {
+                    if inner == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for inner in chars.by_ref() {
+                    if inner == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut star = false;
+                for inner in chars.by_ref() {
+                    if star && inner == '/' {
+                        break;
+                    }
+                    star = inner == '*';
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+fn format_value(value: Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Boolean(b) => b.to_string(),
+        Value::TinyInt(n) => n.to_string(),
+        Value::SmallInt(n) => n.to_string(),
+        Value::Int(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
+        Value::HugeInt(n) => n.to_string(),
+        Value::UTinyInt(n) => n.to_string(),
+        Value::USmallInt(n) => n.to_string(),
+        Value::UInt(n) => n.to_string(),
+        Value::UBigInt(n) => n.to_string(),
+        Value::Float(n) => n.to_string(),
+        Value::Double(n) => n.to_string(),
+        Value::Decimal(n) => n.to_string(),
+        Value::Text(text) | Value::Enum(text) => text,
+        Value::List(values) | Value::Array(values) => format!(
+            "[{}]",
+            values
+                .into_iter()
+                .map(format_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        other => format!("{:?}", other),
+    }
+}
+
 /// Database manager for satellite details
 pub struct Database {
-    conn: Connection,
+    conn: RefCell<Option<Connection>>,
+    shared: Option<PathBuf>, // opened for each call, so another process can use the file in between
+    depth: Cell<usize>,      // calls in progress, so nested calls share one connection
+}
+
+/// How long a shared database waits for another process to release the file
+const SHARED_OPEN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The connection for one call; a shared database closes it again when the
+/// outermost call is done
+struct Conn<'a> {
+    db: &'a Database,
+    conn: Option<Ref<'a, Connection>>,
+}
+
+impl Deref for Conn<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+impl Drop for Conn<'_> {
+    fn drop(&mut self) {
+        self.conn = None;
+        let depth = self.db.depth.get() - 1;
+        self.db.depth.set(depth);
+        if depth == 0 && self.db.shared.is_some() {
+            self.db.conn.borrow_mut().take();
+        }
+    }
 }
 
 impl Database {
+    fn held(conn: Connection) -> Self {
+        Database {
+            conn: RefCell::new(Some(conn)),
+            shared: None,
+            depth: Cell::new(0),
+        }
+    }
+
     /// Open or create a database at the specified path
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let conn = Connection::open(path)?;
-        let db = Database { conn };
+        let db = Database::held(Connection::open(path)?);
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    /// Open or create a database that two processes take turns with, like the
+    /// daemon and a TUI attached to it. The file is only open while a call
+    /// runs, waiting briefly if the other process has it.
+    pub fn open_shared<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let db = Database {
+            conn: RefCell::new(None),
+            shared: Some(path.as_ref().to_path_buf()),
+            depth: Cell::new(0),
+        };
         db.init_schema()?;
         Ok(db)
     }
 
+    /// Open an existing database that can only be read, for ad-hoc queries.
+    /// External access is turned off too, so a query can't read or write
+    /// other files.
+    pub fn open_read_only<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let config = Config::default()
+            .access_mode(AccessMode::ReadOnly)?
+            .enable_external_access(false)?;
+        Ok(Database::held(Connection::open_with_flags(path, config)?))
+    }
+
     /// Open an in-memory database (useful for testing)
     #[allow(dead_code)]
     pub fn open_in_memory() -> Result<Self> {
-        let conn = Connection::open_in_memory()?;
-        let db = Database { conn };
+        let db = Database::held(Connection::open_in_memory()?);
         db.init_schema()?;
         Ok(db)
     }
 
+    /// The connection for a call, opening a shared database's file if no
+    /// call has it open yet
+    fn conn(&self) -> Result<Conn<'_>> {
+        let closed = self.conn.borrow().is_none();
+        if closed {
+            let path = self.shared.as_ref().expect("only a shared database closes");
+            let started = Instant::now();
+            let conn = loop {
+                match Connection::open(path) {
+                    Ok(conn) => break conn,
+                    Err(_) if started.elapsed() < SHARED_OPEN_TIMEOUT => {
+                        std::thread::sleep(Duration::from_millis(20))
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            };
+            *self.conn.borrow_mut() = Some(conn);
+        }
+        self.depth.set(self.depth.get() + 1);
+        Ok(Conn {
+            db: self,
+            conn: Some(Ref::map(self.conn.borrow(), |conn| conn.as_ref().unwrap())),
+        })
+    }
+
     /// Initialize the database schema
     fn init_schema(&self) -> Result<()> {
-        self.conn.execute_batch(
+        let conn = self.conn()?;
+        conn.execute_batch(
             r#"
             CREATE SEQUENCE IF NOT EXISTS satellite_id_seq START 1;
             CREATE TABLE IF NOT EXISTS satellite_details (
@@ -79,21 +436,150 @@ impl Database {
                 downlink_frequency_mhz DOUBLE,
                 uplink_frequency_mhz DOUBLE,
                 notes VARCHAR
-            );"#,
+            );
+            CREATE SEQUENCE IF NOT EXISTS pending_launch_id_seq START 1;
+            CREATE TABLE IF NOT EXISTS pending_launches (
+                id INTEGER DEFAULT nextval('pending_launch_id_seq'),
+                identifier VARCHAR NOT NULL UNIQUE,
+                added_at VARCHAR NOT NULL
+            );
+            CREATE SEQUENCE IF NOT EXISTS transponder_id_seq START 1;
+            CREATE TABLE IF NOT EXISTS transponders (
+                id INTEGER DEFAULT nextval('transponder_id_seq'),
+                satellite_name VARCHAR NOT NULL,
+                description VARCHAR NOT NULL,
+                downlink_mhz DOUBLE,
+                uplink_mhz DOUBLE,
+                downlink_mode VARCHAR,
+                uplink_mode VARCHAR,
+                inverting BOOLEAN DEFAULT false,
+                ctcss_hz DOUBLE,
+                source VARCHAR NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS satellite_groups (
+                group_name VARCHAR NOT NULL,
+                satellite_name VARCHAR NOT NULL,
+                PRIMARY KEY (group_name, satellite_name)
+            );
+            CREATE SEQUENCE IF NOT EXISTS pass_log_id_seq START 1;
+            CREATE TABLE IF NOT EXISTS pass_log (
+                id INTEGER DEFAULT nextval('pass_log_id_seq'),
+                satellite_name VARCHAR NOT NULL,
+                aos_time VARCHAR NOT NULL,
+                los_time VARCHAR NOT NULL,
+                max_elevation DOUBLE NOT NULL,
+                notes VARCHAR,
+                rating INTEGER,
+                UNIQUE (satellite_name, aos_time)
+            );
+            CREATE TABLE IF NOT EXISTS rotator_calibration (
+                rotator_name VARCHAR PRIMARY KEY,
+                azimuth_offset DOUBLE NOT NULL,
+                elevation_offset DOUBLE NOT NULL,
+                updated_at VARCHAR NOT NULL
+            );
+            CREATE SEQUENCE IF NOT EXISTS telemetry_frame_id_seq START 1;
+            CREATE TABLE IF NOT EXISTS telemetry_frames (
+                id INTEGER DEFAULT nextval('telemetry_frame_id_seq'),
+                satellite_name VARCHAR NOT NULL,
+                received_at VARCHAR NOT NULL,
+                frame VARCHAR NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS frequency_offsets (
+                satellite_name VARCHAR NOT NULL,
+                transponder VARCHAR NOT NULL,
+                offset_hz DOUBLE NOT NULL,
+                updated_at VARCHAR NOT NULL,
+                PRIMARY KEY (satellite_name, transponder)
+            );
+            CREATE TABLE IF NOT EXISTS muted_alerts (
+                satellite_name VARCHAR PRIMARY KEY,
+                muted_at VARCHAR NOT NULL
+            );
+            CREATE SEQUENCE IF NOT EXISTS operating_rule_id_seq START 1;
+            CREATE TABLE IF NOT EXISTS operating_rules (
+                id INTEGER DEFAULT nextval('operating_rule_id_seq'),
+                satellite_name VARCHAR NOT NULL,
+                days VARCHAR,
+                hours VARCHAR,
+                region VARCHAR,
+                note VARCHAR NOT NULL DEFAULT ''
+            );
+            CREATE TABLE IF NOT EXISTS prediction_runs (
+                satellite_name VARCHAR NOT NULL,
+                observer_key VARCHAR NOT NULL,
+                tle_epoch VARCHAR NOT NULL,
+                computed_from VARCHAR NOT NULL,
+                computed_until VARCHAR NOT NULL,
+                PRIMARY KEY (satellite_name, observer_key)
+            );
+            CREATE TABLE IF NOT EXISTS predicted_passes (
+                satellite_name VARCHAR NOT NULL,
+                observer_key VARCHAR NOT NULL,
+                tle_epoch VARCHAR NOT NULL,
+                aos_time VARCHAR NOT NULL,
+                los_time VARCHAR NOT NULL,
+                max_elevation DOUBLE NOT NULL,
+                max_elevation_time VARCHAR NOT NULL,
+                aos_azimuth DOUBLE NOT NULL,
+                max_azimuth DOUBLE NOT NULL,
+                los_azimuth DOUBLE NOT NULL,
+                duration_seconds DOUBLE NOT NULL,
+                max_range_km DOUBLE NOT NULL,
+                sun_elevation DOUBLE NOT NULL,
+                sunlit BOOLEAN NOT NULL,
+                min_moon_separation DOUBLE NOT NULL,
+                moon_illumination DOUBLE NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS pointing_errors (
+                rotator_name VARCHAR NOT NULL,
+                satellite_name VARCHAR NOT NULL,
+                pass_start VARCHAR NOT NULL,
+                samples INTEGER NOT NULL,
+                mean_error_deg DOUBLE NOT NULL,
+                rms_error_deg DOUBLE NOT NULL,
+                max_error_deg DOUBLE NOT NULL,
+                mean_azimuth_error_deg DOUBLE NOT NULL,
+                mean_elevation_error_deg DOUBLE NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS position_history (
+                satellite_name VARCHAR NOT NULL,
+                recorded_at VARCHAR NOT NULL,
+                azimuth DOUBLE NOT NULL,
+                elevation DOUBLE NOT NULL,
+                range_km DOUBLE NOT NULL,
+                range_rate_km_s DOUBLE NOT NULL,
+                doppler_hz DOUBLE
+            );
+            CREATE TABLE IF NOT EXISTS satellite_archive (
+                name VARCHAR PRIMARY KEY,
+                archived_at VARCHAR NOT NULL,
+                details VARCHAR NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS settings (
+                key VARCHAR PRIMARY KEY,
+                value VARCHAR NOT NULL
+            );
+            ALTER TABLE satellite_details ADD COLUMN IF NOT EXISTS updated_at VARCHAR;
+            ALTER TABLE satellite_details ADD COLUMN IF NOT EXISTS alias VARCHAR;
+            ALTER TABLE transponders ADD COLUMN IF NOT EXISTS updated_at VARCHAR;
+            ALTER TABLE position_history ADD COLUMN IF NOT EXISTS signal_db DOUBLE;
+            ALTER TABLE pass_log ADD COLUMN IF NOT EXISTS recording VARCHAR;"#,
         )?;
         Ok(())
     }
 
     /// Create a new satellite details entry
     pub fn create(&self, details: &SatelliteDetails) -> Result<i64> {
+        let conn = self.conn()?;
         // Use RETURNING clause to get the id directly from INSERT
-        let mut stmt = self.conn.prepare(
+        let mut stmt = conn.prepare(
             r#"
             INSERT INTO satellite_details (
                 name, tle_line1, tle_line2, launch_date, launch_site,
                 country_of_origin, operator, satellite_type,
-                downlink_frequency_mhz, uplink_frequency_mhz, notes
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                downlink_frequency_mhz, uplink_frequency_mhz, notes, updated_at, alias
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             RETURNING id
             "#,
         )?;
@@ -111,6 +597,8 @@ impl Database {
                 details.downlink_frequency_mhz,
                 details.uplink_frequency_mhz,
                 details.notes,
+                chrono::Utc::now().to_rfc3339(),
+                details.alias,
             ],
             |row| row.get(0),
         )?;
@@ -120,11 +608,12 @@ impl Database {
 
     /// Read satellite details by name
     pub fn read_by_name(&self, name: &str) -> Result<Option<SatelliteDetails>> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
             r#"
             SELECT id, name, tle_line1, tle_line2, launch_date, launch_site,
                    country_of_origin, operator, satellite_type,
-                   downlink_frequency_mhz, uplink_frequency_mhz, notes
+                   downlink_frequency_mhz, uplink_frequency_mhz, notes, alias
             FROM satellite_details
             WHERE name = ?
             "#,
@@ -144,6 +633,7 @@ impl Database {
                 downlink_frequency_mhz: row.get(9)?,
                 uplink_frequency_mhz: row.get(10)?,
                 notes: row.get(11)?,
+                alias: row.get(12)?,
             })
         });
 
@@ -156,11 +646,12 @@ impl Database {
 
     /// Read satellite details by ID
     pub fn read_by_id(&self, id: i64) -> Result<Option<SatelliteDetails>> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
             r#"
             SELECT id, name, tle_line1, tle_line2, launch_date, launch_site,
                    country_of_origin, operator, satellite_type,
-                   downlink_frequency_mhz, uplink_frequency_mhz, notes
+                   downlink_frequency_mhz, uplink_frequency_mhz, notes, alias
             FROM satellite_details
             WHERE id = ?
             "#,
@@ -180,6 +671,7 @@ impl Database {
                 downlink_frequency_mhz: row.get(9)?,
                 uplink_frequency_mhz: row.get(10)?,
                 notes: row.get(11)?,
+                alias: row.get(12)?,
             })
         });
 
@@ -192,11 +684,12 @@ impl Database {
 
     /// Read all satellite details
     pub fn read_all(&self) -> Result<Vec<SatelliteDetails>> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
             r#"
             SELECT id, name, tle_line1, tle_line2, launch_date, launch_site,
                    country_of_origin, operator, satellite_type,
-                   downlink_frequency_mhz, uplink_frequency_mhz, notes
+                   downlink_frequency_mhz, uplink_frequency_mhz, notes, alias
             FROM satellite_details
             ORDER BY name
             "#,
@@ -216,6 +709,7 @@ impl Database {
                 downlink_frequency_mhz: row.get(9)?,
                 uplink_frequency_mhz: row.get(10)?,
                 notes: row.get(11)?,
+                alias: row.get(12)?,
             })
         })?;
 
@@ -226,14 +720,54 @@ impl Database {
         Ok(results)
     }
 
+    /// Ids of the satellites, in name order, whose name, alias, notes, operator,
+    /// country, type or launch site contain every word of `text`, ignoring case
+    pub fn search_satellites(&self, text: &str) -> Result<Vec<i64>> {
+        let conn = self.conn()?;
+        let words: Vec<String> = text.split_whitespace().map(|w| format!("%{}%", w)).collect();
+        let mut sql = String::from("SELECT id FROM satellite_details WHERE true");
+        for _ in &words {
+            sql.push_str(
+                " AND concat_ws(' ', name, alias, notes, operator, country_of_origin, \
+                 satellite_type, launch_site) ILIKE ?",
+            );
+        }
+        sql.push_str(" ORDER BY name");
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(duckdb::params_from_iter(&words), |row| row.get(0))?;
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+        Ok(ids)
+    }
+
+    /// Display aliases by TLE name, for the satellites that have one
+    pub fn read_aliases(&self) -> Result<HashMap<String, String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT name, alias FROM satellite_details WHERE coalesce(alias, '') <> ''",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        let mut aliases = HashMap::new();
+        for row in rows {
+            let (name, alias) = row?;
+            aliases.insert(name, alias);
+        }
+        Ok(aliases)
+    }
+
     /// Update satellite details
     pub fn update(&self, details: &SatelliteDetails) -> Result<bool> {
+        let conn = self.conn()?;
         let id = match details.id {
             Some(id) => id,
             None => return Ok(false),
         };
 
-        let affected = self.conn.execute(
+        let affected = conn.execute(
             r#"
             UPDATE satellite_details SET
                 name = ?,
@@ -246,7 +780,9 @@ impl Database {
                 satellite_type = ?,
                 downlink_frequency_mhz = ?,
                 uplink_frequency_mhz = ?,
-                notes = ?
+                notes = ?,
+                updated_at = ?,
+                alias = ?
             WHERE id = ?
             "#,
             params![
@@ -261,6 +797,8 @@ impl Database {
                 details.downlink_frequency_mhz,
                 details.uplink_frequency_mhz,
                 details.notes,
+                chrono::Utc::now().to_rfc3339(),
+                details.alias,
                 id,
             ],
         )?;
@@ -270,7 +808,8 @@ impl Database {
 
     /// Delete satellite details by ID
     pub fn delete(&self, id: i64) -> Result<bool> {
-        let affected = self.conn.execute(
+        let conn = self.conn()?;
+        let affected = conn.execute(
             "DELETE FROM satellite_details WHERE id = ?",
             params![id],
         )?;
@@ -279,19 +818,54 @@ impl Database {
 
     /// Delete satellite details by name
     pub fn delete_by_name(&self, name: &str) -> Result<bool> {
-        let affected = self.conn.execute(
+        let conn = self.conn()?;
+        let affected = conn.execute(
             "DELETE FROM satellite_details WHERE name = ?",
             params![name],
         )?;
         Ok(affected > 0)
     }
 
-    /// Insert or update (upsert) satellite details by name
+    /// Move a satellite out of the database into the archive, as JSON, so it no
+    /// longer shows up anywhere but can be brought back with `unarchive`
+    pub fn archive(&self, details: &SatelliteDetails) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO satellite_archive (name, archived_at, details) VALUES (?, ?, ?)",
+            params![
+                details.name,
+                chrono::Utc::now().to_rfc3339(),
+                serde_json::to_string(details)?
+            ],
+        )?;
+        self.delete_by_name(&details.name)?;
+        Ok(())
+    }
+
+    /// Bring an archived satellite back, returning false if none has the name
+    pub fn unarchive(&self, name: &str) -> Result<bool> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT details FROM satellite_archive WHERE name = ?")?;
+        let mut rows = stmt.query_map(params![name], |row| row.get::<_, String>(0))?;
+        let Some(json) = rows.next().transpose()? else {
+            return Ok(false);
+        };
+        self.upsert(&serde_json::from_str(&json)?)?;
+        conn.execute("DELETE FROM satellite_archive WHERE name = ?", params![name])?;
+        Ok(true)
+    }
+
+    /// Insert or update (upsert) satellite details by name, keeping the stored
+    /// alias when `details` has none
     pub fn upsert(&self, details: &SatelliteDetails) -> Result<i64> {
         // Check if satellite exists
         if let Some(existing) = self.read_by_name(&details.name)? {
             let mut updated = details.clone();
             updated.id = existing.id;
+            // Fresh element sets don't know the alias someone gave the satellite
+            if updated.alias.is_none() {
+                updated.alias = existing.alias;
+            }
             self.update(&updated)?;
             Ok(existing.id.unwrap())
         } else {
@@ -299,74 +873,1494 @@ impl Database {
         }
     }
 
-    /// Get count of satellites in database
-    pub fn count(&self) -> Result<i64> {
-        let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM satellite_details",
-            [],
+    /// Add a pending launch to watch for, returning its id
+    pub fn add_pending_launch(&self, identifier: &str) -> Result<i64> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "INSERT INTO pending_launches (identifier, added_at) VALUES (?, ?) RETURNING id",
+        )?;
+        let id = stmt.query_row(
+            params![identifier, chrono::Utc::now().to_rfc3339()],
             |row| row.get(0),
         )?;
-        Ok(count)
+        Ok(id)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Read all pending launches, oldest first
+    pub fn read_pending_launches(&self) -> Result<Vec<PendingLaunch>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, identifier, added_at FROM pending_launches ORDER BY added_at",
+        )?;
 
-    #[test]
-    fn test_create_and_read() {
-        let db = Database::open_in_memory().unwrap();
+        let rows = stmt.query_map([], |row| {
+            Ok(PendingLaunch {
+                id: Some(row.get(0)?),
+                identifier: row.get(1)?,
+                added_at: row.get(2)?,
+            })
+        })?;
 
-        let details = SatelliteDetails {
-            id: None,
-            name: "ISS (ZARYA)".to_string(),
-            tle_line1: "1 25544U 98067A   24001.50000000  .00000000  00000-0  00000-0 0    09".to_string(),
-            tle_line2: "2 25544  51.6400 297.8590 0001218  88.9267 338.1310 15.49000000    04".to_string(),
-            launch_date: Some("1998-11-20".to_string()),
-            launch_site: Some("Baikonur Cosmodrome".to_string()),
-            country_of_origin: Some("Russia/USA".to_string()),
-            operator: Some("NASA/Roscosmos".to_string()),
-            satellite_type: Some("Space Station".to_string()),
-            downlink_frequency_mhz: Some(145.800),
-            uplink_frequency_mhz: Some(145.990),
-            notes: Some("Test notes".to_string()),
-        };
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
 
-        let id = db.create(&details).unwrap();
-        assert!(id > 0);
+    /// Stop watching a pending launch
+    pub fn delete_pending_launch(&self, id: i64) -> Result<bool> {
+        let conn = self.conn()?;
+        let affected = conn.execute(
+            "DELETE FROM pending_launches WHERE id = ?",
+            params![id],
+        )?;
+        Ok(affected > 0)
+    }
 
-        let read = db.read_by_name("ISS (ZARYA)").unwrap().unwrap();
-        assert_eq!(read.name, "ISS (ZARYA)");
-        assert_eq!(read.launch_date, Some("1998-11-20".to_string()));
-        assert_eq!(read.downlink_frequency_mhz, Some(145.800));
+    /// Replace all transponders previously imported from `source`, returning how many were stored
+    pub fn replace_transponders(&self, source: &str, transponders: &[Transponder]) -> Result<usize> {
+        let conn = self.conn()?;
+        conn.execute("DELETE FROM transponders WHERE source = ?", params![source])?;
+
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT INTO transponders (
+                satellite_name, description, downlink_mhz, uplink_mhz,
+                downlink_mode, uplink_mode, inverting, ctcss_hz, source, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )?;
+        let now = chrono::Utc::now().to_rfc3339();
+        for transponder in transponders {
+            stmt.execute(params![
+                transponder.satellite_name,
+                transponder.description,
+                transponder.downlink_mhz,
+                transponder.uplink_mhz,
+                transponder.downlink_mode,
+                transponder.uplink_mode,
+                transponder.inverting,
+                transponder.ctcss_hz,
+                source,
+                now,
+            ])?;
+        }
+        Ok(transponders.len())
     }
 
-    #[test]
-    fn test_update() {
-        let db = Database::open_in_memory().unwrap();
+    /// Replace one satellite's transponders with ones from a team sync,
+    /// keeping the time they were last changed there
+    pub fn replace_synced_transponders(
+        &self,
+        satellite_name: &str,
+        transponders: &[Transponder],
+        updated_at: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "DELETE FROM transponders WHERE satellite_name = ?",
+            params![satellite_name],
+        )?;
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT INTO transponders (
+                satellite_name, description, downlink_mhz, uplink_mhz,
+                downlink_mode, uplink_mode, inverting, ctcss_hz, source, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )?;
+        for transponder in transponders {
+            stmt.execute(params![
+                satellite_name,
+                transponder.description,
+                transponder.downlink_mhz,
+                transponder.uplink_mhz,
+                transponder.downlink_mode,
+                transponder.uplink_mode,
+                transponder.inverting,
+                transponder.ctcss_hz,
+                transponder.source,
+                updated_at,
+            ])?;
+        }
+        Ok(())
+    }
 
-        let mut details = SatelliteDetails::new("TEST SAT".to_string());
-        details.country_of_origin = Some("USA".to_string());
+    /// Set when a satellite's details were last changed, for details pulled
+    /// from a team sync
+    pub fn set_updated_at(&self, satellite_name: &str, updated_at: Option<&str>) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "UPDATE satellite_details SET updated_at = ? WHERE name = ?",
+            params![updated_at, satellite_name],
+        )?;
+        Ok(())
+    }
 
-        let id = db.create(&details).unwrap();
-        details.id = Some(id);
-        details.country_of_origin = Some("Germany".to_string());
+    /// When each satellite's details or transponders last changed, for
+    /// satellites with a recorded time
+    pub fn read_updated_times(&self) -> Result<HashMap<String, String>> {
+        let conn = self.conn()?;
+        // RFC 3339 times in UTC sort as text
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT name, max(updated_at) FROM (
+                SELECT name, updated_at FROM satellite_details
+                UNION ALL
+                SELECT satellite_name, updated_at FROM transponders
+            )
+            WHERE updated_at IS NOT NULL
+            GROUP BY name
+            "#,
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
 
-        assert!(db.update(&details).unwrap());
+        let mut times = HashMap::new();
+        for row in rows {
+            let (name, time) = row?;
+            times.insert(name, time);
+        }
+        Ok(times)
+    }
 
-        let read = db.read_by_id(id).unwrap().unwrap();
-        assert_eq!(read.country_of_origin, Some("Germany".to_string()));
+    /// Read all transponders, grouped by satellite
+    pub fn read_transponders(&self) -> Result<Vec<Transponder>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, satellite_name, description, downlink_mhz, uplink_mhz,
+                   downlink_mode, uplink_mode, inverting, ctcss_hz, source
+            FROM transponders
+            ORDER BY satellite_name, id
+            "#,
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(Transponder {
+                id: Some(row.get(0)?),
+                satellite_name: row.get(1)?,
+                description: row.get(2)?,
+                downlink_mhz: row.get(3)?,
+                uplink_mhz: row.get(4)?,
+                downlink_mode: row.get(5)?,
+                uplink_mode: row.get(6)?,
+                inverting: row.get(7)?,
+                ctcss_hz: row.get(8)?,
+                source: row.get(9)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
     }
 
-    #[test]
-    fn test_delete() {
-        let db = Database::open_in_memory().unwrap();
+    /// Replace the membership of each given group
+    pub fn replace_groups(&self, groups: &[SatelliteGroup]) -> Result<()> {
+        let conn = self.conn()?;
+        for group in groups {
+            conn.execute(
+                "DELETE FROM satellite_groups WHERE group_name = ?",
+                params![group.name],
+            )?;
+            for satellite in &group.satellites {
+                conn.execute(
+                    "INSERT OR IGNORE INTO satellite_groups (group_name, satellite_name) VALUES (?, ?)",
+                    params![group.name, satellite],
+                )?;
+            }
+        }
+        Ok(())
+    }
 
-        let details = SatelliteDetails::new("TO DELETE".to_string());
-        let id = db.create(&details).unwrap();
+    /// Add satellites to a group, creating it if needed and keeping its members
+    pub fn add_to_group(&self, group_name: &str, satellites: &[String]) -> Result<()> {
+        let conn = self.conn()?;
+        for satellite in satellites {
+            conn.execute(
+                "INSERT OR IGNORE INTO satellite_groups (group_name, satellite_name) VALUES (?, ?)",
+                params![group_name, satellite],
+            )?;
+        }
+        Ok(())
+    }
 
-        assert!(db.delete(id).unwrap());
-        assert!(db.read_by_id(id).unwrap().is_none());
+    /// Read all satellite groups, ordered by name
+    pub fn read_groups(&self) -> Result<Vec<SatelliteGroup>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT group_name, satellite_name FROM satellite_groups ORDER BY group_name, satellite_name",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+
+        let mut groups: Vec<SatelliteGroup> = Vec::new();
+        for row in rows {
+            let (group_name, satellite_name) = row?;
+            match groups.last_mut() {
+                Some(group) if group.name == group_name => group.satellites.push(satellite_name),
+                _ => groups.push(SatelliteGroup {
+                    name: group_name,
+                    satellites: vec![satellite_name],
+                }),
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Log a pass, returning its id; logging the same pass twice returns the existing entry
+    pub fn log_pass(&self, entry: &PassLogEntry) -> Result<i64> {
+        let conn = self.conn()?;
+        let existing = conn.query_row(
+            "SELECT id FROM pass_log WHERE satellite_name = ? AND aos_time = ?",
+            params![entry.satellite_name, entry.aos_time],
+            |row| row.get(0),
+        );
+        match existing {
+            Ok(id) => return Ok(id),
+            Err(duckdb::Error::QueryReturnedNoRows) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT INTO pass_log (
+                satellite_name, aos_time, los_time, max_elevation, notes, rating, recording
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )?;
+        let id = stmt.query_row(
+            params![
+                entry.satellite_name,
+                entry.aos_time,
+                entry.los_time,
+                entry.max_elevation,
+                entry.notes,
+                entry.rating,
+                entry.recording,
+            ],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+
+    /// Read logged passes, newest first, optionally filtered by a
+    /// case-insensitive search over satellite names and notes
+    pub fn read_pass_log(&self, search: &str) -> Result<Vec<PassLogEntry>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, satellite_name, aos_time, los_time, max_elevation, notes, rating, recording
+            FROM pass_log
+            WHERE satellite_name ILIKE ? OR COALESCE(notes, '') ILIKE ?
+            ORDER BY aos_time DESC
+            "#,
+        )?;
+
+        let pattern = format!("%{}%", search.trim());
+        let rows = stmt.query_map(params![pattern, pattern], |row| {
+            Ok(PassLogEntry {
+                id: Some(row.get(0)?),
+                satellite_name: row.get(1)?,
+                aos_time: row.get(2)?,
+                los_time: row.get(3)?,
+                max_elevation: row.get(4)?,
+                notes: row.get(5)?,
+                rating: row.get(6)?,
+                recording: row.get(7)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Update the notes and rating of a logged pass
+    pub fn update_pass_log(
+        &self,
+        id: i64,
+        notes: Option<&str>,
+        rating: Option<i32>,
+    ) -> Result<bool> {
+        let conn = self.conn()?;
+        let affected = conn.execute(
+            "UPDATE pass_log SET notes = ?, rating = ? WHERE id = ?",
+            params![notes, rating, id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    /// Link an audio recording to a logged pass
+    pub fn set_pass_recording(&self, id: i64, path: &str) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "UPDATE pass_log SET recording = ? WHERE id = ?",
+            params![path, id],
+        )?;
+        Ok(())
+    }
+
+    /// Store the calibration for a rotator, replacing any earlier one
+    pub fn save_rotator_calibration(&self, calibration: &RotatorCalibration) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO rotator_calibration
+                (rotator_name, azimuth_offset, elevation_offset, updated_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+            params![
+                calibration.rotator_name,
+                calibration.azimuth_offset,
+                calibration.elevation_offset,
+                chrono::Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Read the stored calibration for a rotator; uncalibrated rotators get zero offsets
+    pub fn read_rotator_calibration(&self, rotator_name: &str) -> Result<RotatorCalibration> {
+        let conn = self.conn()?;
+        let result = conn.query_row(
+            "SELECT azimuth_offset, elevation_offset FROM rotator_calibration WHERE rotator_name = ?",
+            params![rotator_name],
+            |row| {
+                Ok(RotatorCalibration {
+                    rotator_name: rotator_name.to_string(),
+                    azimuth_offset: row.get(0)?,
+                    elevation_offset: row.get(1)?,
+                })
+            },
+        );
+        match result {
+            Ok(calibration) => Ok(calibration),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(RotatorCalibration {
+                rotator_name: rotator_name.to_string(),
+                ..Default::default()
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replace the cached passes of a satellite for an observer and its settings
+    pub fn save_predicted_passes(
+        &self,
+        satellite_name: &str,
+        observer_key: &str,
+        tle_epoch: DateTime<Utc>,
+        prediction: &PassPrediction,
+    ) -> Result<()> {
+        let conn = self.conn()?;
+        let epoch = tle_epoch.to_rfc3339();
+        // Readers see the old passes or the new ones, never a mix or none
+        conn.execute_batch("BEGIN TRANSACTION")?;
+        let save = || -> Result<()> {
+            for table in ["prediction_runs", "predicted_passes"] {
+                conn.execute(
+                    &format!(
+                        "DELETE FROM {} WHERE satellite_name = ? AND observer_key = ?",
+                        table
+                    ),
+                    params![satellite_name, observer_key],
+                )?;
+            }
+            conn.execute(
+                r#"
+                INSERT INTO prediction_runs (
+                    satellite_name, observer_key, tle_epoch, computed_from, computed_until
+                ) VALUES (?, ?, ?, ?, ?)
+                "#,
+                params![
+                    satellite_name,
+                    observer_key,
+                    epoch,
+                    prediction.computed_from.to_rfc3339(),
+                    prediction.computed_until.to_rfc3339(),
+                ],
+            )?;
+
+            let mut stmt = conn.prepare(
+                r#"
+                INSERT INTO predicted_passes (
+                    satellite_name, observer_key, tle_epoch, aos_time, los_time,
+                    max_elevation, max_elevation_time, aos_azimuth, max_azimuth, los_azimuth,
+                    duration_seconds, max_range_km, sun_elevation, sunlit,
+                    min_moon_separation, moon_illumination
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )?;
+            for pass in &prediction.passes {
+                stmt.execute(params![
+                    satellite_name,
+                    observer_key,
+                    epoch,
+                    pass.aos_time.to_rfc3339(),
+                    pass.los_time.to_rfc3339(),
+                    pass.max_elevation,
+                    pass.max_elevation_time.to_rfc3339(),
+                    pass.aos_azimuth,
+                    pass.max_azimuth,
+                    pass.los_azimuth,
+                    pass.duration_seconds,
+                    pass.max_range_km,
+                    pass.sun_elevation,
+                    pass.sunlit,
+                    pass.min_moon_separation,
+                    pass.moon_illumination,
+                ])?;
+            }
+            Ok(())
+        };
+        let result = save();
+        conn.execute_batch(if result.is_ok() { "COMMIT" } else { "ROLLBACK" })?;
+        result
+    }
+
+    /// Cached passes of a satellite for an observer and its settings, if
+    /// they were predicted from the same element set
+    pub fn read_predicted_passes(
+        &self,
+        satellite_name: &str,
+        observer_key: &str,
+        tle_epoch: DateTime<Utc>,
+    ) -> Result<Option<PassPrediction>> {
+        let conn = self.conn()?;
+        let epoch = tle_epoch.to_rfc3339();
+        let run = conn.query_row(
+            r#"
+            SELECT computed_from, computed_until FROM prediction_runs
+            WHERE satellite_name = ? AND observer_key = ? AND tle_epoch = ?
+            "#,
+            params![satellite_name, observer_key, epoch],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        );
+        let (from, until) = match run {
+            Ok(run) => run,
+            Err(duckdb::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT aos_time, los_time, max_elevation, max_elevation_time, aos_azimuth,
+                   max_azimuth, los_azimuth, duration_seconds, max_range_km,
+                   sun_elevation, sunlit, min_moon_separation, moon_illumination
+            FROM predicted_passes
+            WHERE satellite_name = ? AND observer_key = ? AND tle_epoch = ?
+            ORDER BY aos_time
+            "#,
+        )?;
+        let rows = stmt.query_map(params![satellite_name, observer_key, epoch], |row| {
+            Ok((
+                [row.get::<_, String>(0)?, row.get(1)?, row.get(3)?],
+                SatellitePass {
+                    aos_time: DateTime::UNIX_EPOCH,
+                    los_time: DateTime::UNIX_EPOCH,
+                    max_elevation: row.get(2)?,
+                    max_elevation_time: DateTime::UNIX_EPOCH,
+                    aos_azimuth: row.get(4)?,
+                    max_azimuth: row.get(5)?,
+                    los_azimuth: row.get(6)?,
+                    duration_seconds: row.get(7)?,
+                    max_range_km: row.get(8)?,
+                    sun_elevation: row.get(9)?,
+                    sunlit: row.get(10)?,
+                    min_moon_separation: row.get(11)?,
+                    moon_illumination: row.get(12)?,
+                },
+            ))
+        })?;
+
+        let time = |text: &str| DateTime::parse_from_rfc3339(text).map(|t| t.with_timezone(&Utc));
+        let mut passes = Vec::new();
+        for row in rows {
+            let ([aos, los, max], mut pass) = row?;
+            pass.aos_time = time(&aos)?;
+            pass.los_time = time(&los)?;
+            pass.max_elevation_time = time(&max)?;
+            passes.push(pass);
+        }
+        Ok(Some(PassPrediction {
+            computed_from: time(&from)?,
+            computed_until: time(&until)?,
+            passes,
+        }))
+    }
+
+    /// Store the pointing error of one tracked pass
+    pub fn save_pointing_errors(&self, stats: &PointingErrorStats) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            r#"
+            INSERT INTO pointing_errors (
+                rotator_name, satellite_name, pass_start, samples, mean_error_deg,
+                rms_error_deg, max_error_deg, mean_azimuth_error_deg, mean_elevation_error_deg
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            params![
+                stats.rotator_name,
+                stats.satellite_name,
+                stats.pass_start,
+                stats.samples,
+                stats.mean_error_deg,
+                stats.rms_error_deg,
+                stats.max_error_deg,
+                stats.mean_azimuth_error_deg,
+                stats.mean_elevation_error_deg,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The latest passes' pointing errors for a rotator, newest first
+    pub fn read_pointing_errors(
+        &self,
+        rotator_name: &str,
+        limit: usize,
+    ) -> Result<Vec<PointingErrorStats>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT rotator_name, satellite_name, pass_start, samples, mean_error_deg,
+                   rms_error_deg, max_error_deg, mean_azimuth_error_deg, mean_elevation_error_deg
+            FROM pointing_errors
+            WHERE rotator_name = ?
+            ORDER BY pass_start DESC
+            LIMIT ?
+            "#,
+        )?;
+        let rows = stmt.query_map(params![rotator_name, limit as i64], |row| {
+            Ok(PointingErrorStats {
+                rotator_name: row.get(0)?,
+                satellite_name: row.get(1)?,
+                pass_start: row.get(2)?,
+                samples: row.get(3)?,
+                mean_error_deg: row.get(4)?,
+                rms_error_deg: row.get(5)?,
+                max_error_deg: row.get(6)?,
+                mean_azimuth_error_deg: row.get(7)?,
+                mean_elevation_error_deg: row.get(8)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Remember the manual offset for a satellite's transponder, replacing any earlier one
+    pub fn save_frequency_offset(&self, offset: &FrequencyOffset) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO frequency_offsets
+                (satellite_name, transponder, offset_hz, updated_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+            params![
+                offset.satellite_name,
+                offset.transponder,
+                offset.offset_hz,
+                chrono::Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Read every remembered frequency offset
+    pub fn read_frequency_offsets(&self) -> Result<Vec<FrequencyOffset>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT satellite_name, transponder, offset_hz FROM frequency_offsets
+             ORDER BY satellite_name",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(FrequencyOffset {
+                satellite_name: row.get(0)?,
+                transponder: row.get(1)?,
+                offset_hz: row.get(2)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Mute or unmute pass alerts for a satellite
+    pub fn set_alerts_muted(&self, satellite_name: &str, muted: bool) -> Result<()> {
+        let conn = self.conn()?;
+        if muted {
+            conn.execute(
+                "INSERT OR REPLACE INTO muted_alerts (satellite_name, muted_at) VALUES (?, ?)",
+                params![satellite_name, chrono::Utc::now().to_rfc3339()],
+            )?;
+        } else {
+            conn.execute(
+                "DELETE FROM muted_alerts WHERE satellite_name = ?",
+                params![satellite_name],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// A value remembered between runs, such as the station profile in use
+    pub fn read_setting(&self, key: &str) -> Result<Option<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?")?;
+        let mut rows = stmt.query_map(params![key], |row| row.get(0))?;
+        Ok(rows.next().transpose()?)
+    }
+
+    pub fn save_setting(&self, key: &str, value: &str) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Names of the satellites whose pass alerts are muted
+    pub fn read_muted_alerts(&self) -> Result<HashSet<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT satellite_name FROM muted_alerts")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+
+        let mut results = HashSet::new();
+        for row in rows {
+            results.insert(row?);
+        }
+        Ok(results)
+    }
+
+    /// Add an operating schedule rule, returning its id
+    pub fn add_operating_rule(&self, rule: &OperatingRule) -> Result<i64> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT INTO operating_rules (satellite_name, days, hours, region, note)
+            VALUES (?, ?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )?;
+        let id = stmt.query_row(
+            params![
+                rule.satellite_name,
+                rule.days,
+                rule.hours,
+                rule.region,
+                rule.note,
+            ],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+
+    /// Read every operating schedule rule, by satellite
+    pub fn read_operating_rules(&self) -> Result<Vec<OperatingRule>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, satellite_name, days, hours, region, note FROM operating_rules
+             ORDER BY satellite_name, id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(OperatingRule {
+                id: Some(row.get(0)?),
+                satellite_name: row.get(1)?,
+                days: row.get(2)?,
+                hours: row.get(3)?,
+                region: row.get(4)?,
+                note: row.get(5)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    pub fn delete_operating_rule(&self, id: i64) -> Result<bool> {
+        let conn = self.conn()?;
+        let affected = conn.execute("DELETE FROM operating_rules WHERE id = ?", params![id])?;
+        Ok(affected > 0)
+    }
+
+    /// Store a decoded telemetry frame
+    pub fn save_telemetry_frame(&self, frame: &TelemetryFrame) -> Result<i64> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT INTO telemetry_frames (satellite_name, received_at, frame)
+            VALUES (?, ?, ?)
+            RETURNING id
+            "#,
+        )?;
+        let id = stmt.query_row(
+            params![frame.satellite_name, frame.received_at, frame.frame],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+
+    /// Read the most recent telemetry frames for a satellite, newest first
+    pub fn read_telemetry_frames(
+        &self,
+        satellite_name: &str,
+        limit: usize,
+    ) -> Result<Vec<TelemetryFrame>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, satellite_name, received_at, frame
+            FROM telemetry_frames
+            WHERE satellite_name = ?
+            ORDER BY received_at DESC, id DESC
+            LIMIT ?
+            "#,
+        )?;
+        let rows = stmt.query_map(params![satellite_name, limit as i64], |row| {
+            Ok(TelemetryFrame {
+                id: Some(row.get(0)?),
+                satellite_name: row.get(1)?,
+                received_at: row.get(2)?,
+                frame: row.get(3)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// When frames were received from a satellite between two RFC 3339 times,
+    /// oldest first
+    pub fn read_telemetry_times(
+        &self,
+        satellite_name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT received_at
+            FROM telemetry_frames
+            WHERE satellite_name = ? AND received_at BETWEEN ? AND ?
+            ORDER BY received_at
+            "#,
+        )?;
+        let rows = stmt.query_map(params![satellite_name, from, to], |row| row.get(0))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Record a satellite's position during a pass
+    pub fn record_position(&self, record: &PositionRecord) -> Result<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            r#"
+            INSERT INTO position_history (
+                satellite_name, recorded_at, azimuth, elevation,
+                range_km, range_rate_km_s, doppler_hz, signal_db
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            params![
+                record.satellite_name,
+                record.recorded_at,
+                record.azimuth,
+                record.elevation,
+                record.range_km,
+                record.range_rate_km_s,
+                record.doppler_hz,
+                record.signal_db,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Positions recorded for a satellite between two times, oldest first.
+    /// The times must be formatted like `PositionRecord::recorded_at`.
+    pub fn read_position_history(
+        &self,
+        satellite_name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<PositionRecord>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT satellite_name, recorded_at, azimuth, elevation,
+                   range_km, range_rate_km_s, doppler_hz, signal_db
+            FROM position_history
+            WHERE satellite_name = ? AND recorded_at BETWEEN ? AND ?
+            ORDER BY recorded_at
+            "#,
+        )?;
+        let rows = stmt.query_map(params![satellite_name, from, to], |row| {
+            Ok(PositionRecord {
+                satellite_name: row.get(0)?,
+                recorded_at: row.get(1)?,
+                azimuth: row.get(2)?,
+                elevation: row.get(3)?,
+                range_km: row.get(4)?,
+                range_rate_km_s: row.get(5)?,
+                doppler_hz: row.get(6)?,
+                signal_db: row.get(7)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Summarize the pass log: passes and best elevation per satellite,
+    /// grid squares noted as contacted, and passes per UTC hour
+    pub fn log_stats(&self) -> Result<LogStats> {
+        let conn = self.conn()?;
+        let mut stats = LogStats::default();
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT satellite_name, COUNT(*), MAX(max_elevation), AVG(rating)
+            FROM pass_log
+            GROUP BY satellite_name
+            ORDER BY COUNT(*) DESC, satellite_name
+            "#,
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SatellitePassStats {
+                satellite_name: row.get(0)?,
+                passes: row.get(1)?,
+                best_elevation: row.get(2)?,
+                average_rating: row.get(3)?,
+            })
+        })?;
+        for row in rows {
+            let satellite = row?;
+            stats.total_passes += satellite.passes;
+            stats.satellites.push(satellite);
+        }
+
+        // Grids are whatever Maidenhead locators the operator typed into the notes
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT substr(grid, 1, 4) AS square, COUNT(*)
+            FROM (
+                SELECT unnest(regexp_extract_all(upper(notes), '\b[A-R]{2}[0-9]{2}(?:[A-X]{2})?\b')) AS grid
+                FROM pass_log
+                WHERE notes IS NOT NULL
+            )
+            GROUP BY square
+            ORDER BY COUNT(*) DESC, square
+            "#,
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        for row in rows {
+            stats.grids.push(row?);
+        }
+
+        // AOS times are stored as UTC RFC 3339, so the hour is at a fixed offset
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT CAST(substr(aos_time, 12, 2) AS INTEGER) AS hour, COUNT(*)
+            FROM pass_log
+            GROUP BY hour
+            "#,
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?;
+        for row in rows {
+            let (hour, count) = row?;
+            if let Some(slot) = stats.passes_by_hour.get_mut(hour as usize) {
+                *slot = count;
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Run an ad-hoc read-only query. Only a single query statement is
+    /// accepted, and it runs inside a transaction that is always rolled back.
+    pub fn query(&self, sql: &str) -> Result<QueryResult> {
+        let conn = self.conn()?;
+        let sql = sql.trim().trim_end_matches(';').trim_end();
+        // DuckDB runs every statement but the last while preparing
+        if has_statement_separator(sql) {
+            return Err(anyhow::anyhow!("only a single statement is allowed"));
+        }
+        let keyword = sql
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        if !READ_ONLY_KEYWORDS.contains(&keyword.as_str()) {
+            return Err(anyhow::anyhow!(
+                "only read-only queries are allowed (start with {})",
+                READ_ONLY_KEYWORDS.join(", ")
+            ));
+        }
+
+        conn.execute_batch("BEGIN TRANSACTION")?;
+        let result = self.run_query(sql);
+        conn.execute_batch("ROLLBACK")?;
+        result
+    }
+
+    fn run_query(&self, sql: &str) -> Result<QueryResult> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(sql)?;
+        let mut result = QueryResult::default();
+
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let values = (0..row.as_ref().column_count())
+                .map(|idx| row.get::<_, Value>(idx).map(format_value))
+                .collect::<duckdb::Result<Vec<_>>>()?;
+            result.rows.push(values);
+        }
+        drop(rows);
+
+        result.columns = stmt.column_names();
+        Ok(result)
+    }
+
+    /// Get count of satellites in database
+    pub fn count(&self) -> Result<i64> {
+        let conn = self.conn()?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM satellite_details",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_and_read() {
+        let db = Database::open_in_memory().unwrap();
+
+        let details = SatelliteDetails {
+            id: None,
+            name: "ISS (ZARYA)".to_string(),
+            alias: None,
+            tle_line1: "1 25544U 98067A   24001.50000000  .00000000  00000-0  00000-0 0    09".to_string(),
+            tle_line2: "2 25544  51.6400 297.8590 0001218  88.9267 338.1310 15.49000000    04".to_string(),
+            launch_date: Some("1998-11-20".to_string()),
+            launch_site: Some("Baikonur Cosmodrome".to_string()),
+            country_of_origin: Some("Russia/USA".to_string()),
+            operator: Some("NASA/Roscosmos".to_string()),
+            satellite_type: Some("Space Station".to_string()),
+            downlink_frequency_mhz: Some(145.800),
+            uplink_frequency_mhz: Some(145.990),
+            notes: Some("Test notes".to_string()),
+        };
+
+        let id = db.create(&details).unwrap();
+        assert!(id > 0);
+
+        let read = db.read_by_name("ISS (ZARYA)").unwrap().unwrap();
+        assert_eq!(read.name, "ISS (ZARYA)");
+        assert_eq!(read.launch_date, Some("1998-11-20".to_string()));
+        assert_eq!(read.downlink_frequency_mhz, Some(145.800));
+    }
+
+    #[test]
+    fn test_alias() {
+        let db = Database::open_in_memory().unwrap();
+
+        let mut details = SatelliteDetails::new("ISS (ZARYA)".to_string());
+        details.alias = Some("ISS".to_string());
+        db.create(&details).unwrap();
+        db.create(&SatelliteDetails::new("SO-50".to_string())).unwrap();
+
+        let read = db.read_by_name("ISS (ZARYA)").unwrap().unwrap();
+        assert_eq!(read.alias, Some("ISS".to_string()));
+        let aliases = db.read_aliases().unwrap();
+        assert_eq!(aliases.len(), 1);
+        assert_eq!(aliases.get("ISS (ZARYA)").map(String::as_str), Some("ISS"));
+    }
+
+    #[test]
+    fn test_upsert_keeps_alias() {
+        let db = Database::open_in_memory().unwrap();
+
+        let mut details = SatelliteDetails::new("ISS (ZARYA)".to_string());
+        details.alias = Some("ISS".to_string());
+        db.create(&details).unwrap();
+
+        // New elements for the satellite, which carry no alias
+        db.upsert(&SatelliteDetails::new("ISS (ZARYA)".to_string())).unwrap();
+        let read = db.read_by_name("ISS (ZARYA)").unwrap().unwrap();
+        assert_eq!(read.alias, Some("ISS".to_string()));
+    }
+
+    #[test]
+    fn test_update() {
+        let db = Database::open_in_memory().unwrap();
+
+        let mut details = SatelliteDetails::new("TEST SAT".to_string());
+        details.country_of_origin = Some("USA".to_string());
+
+        let id = db.create(&details).unwrap();
+        details.id = Some(id);
+        details.country_of_origin = Some("Germany".to_string());
+
+        assert!(db.update(&details).unwrap());
+
+        let read = db.read_by_id(id).unwrap().unwrap();
+        assert_eq!(read.country_of_origin, Some("Germany".to_string()));
+    }
+
+    #[test]
+    fn test_pending_launches() {
+        let db = Database::open_in_memory().unwrap();
+
+        let id = db.add_pending_launch("2026-042").unwrap();
+        db.add_pending_launch("99999").unwrap();
+        assert!(db.add_pending_launch("99999").is_err());
+
+        let pending = db.read_pending_launches().unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].identifier, "2026-042");
+
+        assert!(db.delete_pending_launch(id).unwrap());
+        assert_eq!(db.read_pending_launches().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_replace_transponders() {
+        let db = Database::open_in_memory().unwrap();
+
+        let fm = Transponder {
+            satellite_name: "SO-50".to_string(),
+            description: "FM voice".to_string(),
+            downlink_mhz: Some(436.795),
+            uplink_mhz: Some(145.850),
+            ctcss_hz: Some(67.0),
+            ..Default::default()
+        };
+        assert_eq!(db.replace_transponders("sqf", &[fm.clone(), fm.clone()]).unwrap(), 2);
+        assert_eq!(db.replace_transponders("sqf", &[fm]).unwrap(), 1);
+
+        let stored = db.read_transponders().unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].source, "sqf");
+        assert_eq!(stored[0].ctcss_hz, Some(67.0));
+    }
+
+    #[test]
+    fn test_groups() {
+        let db = Database::open_in_memory().unwrap();
+
+        let group = SatelliteGroup {
+            name: "Amateur".to_string(),
+            satellites: vec!["AO-91".to_string(), "ISS".to_string(), "ISS".to_string()],
+        };
+        db.replace_groups(&[group]).unwrap();
+        db.replace_groups(&[SatelliteGroup {
+            name: "Weather".to_string(),
+            satellites: vec!["NOAA 19".to_string()],
+        }])
+        .unwrap();
+
+        let groups = db.read_groups().unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].satellites, vec!["AO-91", "ISS"]);
+        assert_eq!(groups[1].name, "Weather");
+
+        db.add_to_group("Amateur", &["SO-50".to_string(), "ISS".to_string()])
+            .unwrap();
+        let groups = db.read_groups().unwrap();
+        assert_eq!(groups[0].satellites, vec!["AO-91", "ISS", "SO-50"]);
+    }
+
+    #[test]
+    fn test_pass_log() {
+        let db = Database::open_in_memory().unwrap();
+
+        let entry = PassLogEntry {
+            satellite_name: "AO-91".to_string(),
+            aos_time: "2026-01-01T12:00:00+00:00".to_string(),
+            los_time: "2026-01-01T12:12:00+00:00".to_string(),
+            max_elevation: 54.0,
+            ..Default::default()
+        };
+        let id = db.log_pass(&entry).unwrap();
+        assert_eq!(db.log_pass(&entry).unwrap(), id);
+        db.log_pass(&PassLogEntry {
+            satellite_name: "SO-50".to_string(),
+            aos_time: "2026-01-02T08:00:00+00:00".to_string(),
+            ..entry.clone()
+        })
+        .unwrap();
+
+        assert!(db.update_pass_log(id, Some("Loud and clear"), Some(5)).unwrap());
+
+        let all = db.read_pass_log("").unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].satellite_name, "SO-50");
+
+        let found = db.read_pass_log("loud").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].rating, Some(5));
+        assert_eq!(db.read_pass_log("so-50").unwrap().len(), 1);
+
+        db.set_pass_recording(id, "/tmp/ao_91/20260101T120000Z.wav")
+            .unwrap();
+        let found = db.read_pass_log("ao-91").unwrap();
+        assert_eq!(
+            found[0].recording.as_deref(),
+            Some("/tmp/ao_91/20260101T120000Z.wav")
+        );
+    }
+
+    #[test]
+    fn test_log_stats() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(db.log_stats().unwrap().total_passes, 0);
+
+        let pass = |name: &str, aos: &str, elevation: f64| PassLogEntry {
+            satellite_name: name.to_string(),
+            aos_time: aos.to_string(),
+            los_time: aos.to_string(),
+            max_elevation: elevation,
+            ..Default::default()
+        };
+        let id = db
+            .log_pass(&pass("AO-91", "2026-01-01T12:00:00+00:00", 20.0))
+            .unwrap();
+        db.update_pass_log(id, Some("Worked W1AW in FN31pr, K5XX EM12"), Some(4))
+            .unwrap();
+        db.log_pass(&pass("AO-91", "2026-01-02T12:30:00+00:00", 70.0))
+            .unwrap();
+        let id = db
+            .log_pass(&pass("SO-50", "2026-01-02T03:00:00+00:00", 35.0))
+            .unwrap();
+        db.update_pass_log(id, Some("fn31 again via AO-91"), None)
+            .unwrap();
+
+        let stats = db.log_stats().unwrap();
+        assert_eq!(stats.total_passes, 3);
+        assert_eq!(stats.satellites[0].satellite_name, "AO-91");
+        assert_eq!(stats.satellites[0].passes, 2);
+        assert_eq!(stats.satellites[0].best_elevation, 70.0);
+        assert_eq!(stats.satellites[0].average_rating, Some(4.0));
+        assert_eq!(stats.satellites[1].average_rating, None);
+        assert_eq!(
+            stats.grids,
+            vec![("FN31".to_string(), 2), ("EM12".to_string(), 1)]
+        );
+        assert_eq!(stats.passes_by_hour[12], 2);
+        assert_eq!(stats.passes_by_hour[3], 1);
+    }
+
+    #[test]
+    fn test_rotator_calibration() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(db.read_rotator_calibration("yagi").unwrap().azimuth_offset, 0.0);
+
+        let mut calibration = RotatorCalibration {
+            rotator_name: "yagi".to_string(),
+            azimuth_offset: 4.5,
+            elevation_offset: -1.0,
+        };
+        db.save_rotator_calibration(&calibration).unwrap();
+        calibration.azimuth_offset = 3.0;
+        db.save_rotator_calibration(&calibration).unwrap();
+
+        assert_eq!(db.read_rotator_calibration("yagi").unwrap(), calibration);
+        assert_eq!(db.read_rotator_calibration("other").unwrap().elevation_offset, 0.0);
+    }
+
+    #[test]
+    fn test_pointing_errors() {
+        let db = Database::open_in_memory().unwrap();
+        for (start, bias) in [("2026-05-01T10:00:00Z", 0.5), ("2026-05-03T10:00:00Z", 2.0)] {
+            db.save_pointing_errors(&PointingErrorStats {
+                rotator_name: "yagi".to_string(),
+                satellite_name: "ISS".to_string(),
+                pass_start: start.to_string(),
+                samples: 120,
+                mean_azimuth_error_deg: bias,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        let latest = db.read_pointing_errors("yagi", 1).unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].mean_azimuth_error_deg, 2.0);
+        assert!(db.read_pointing_errors("dish", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_operating_rules() {
+        let db = Database::open_in_memory().unwrap();
+        let rule = OperatingRule {
+            id: None,
+            satellite_name: "AO-91".to_string(),
+            days: Some("Sat,Sun".to_string()),
+            hours: None,
+            region: Some("35,72,-25,45".to_string()),
+            note: "Europe weekends".to_string(),
+        };
+        let id = db.add_operating_rule(&rule).unwrap();
+        assert_eq!(
+            db.read_operating_rules().unwrap(),
+            vec![OperatingRule {
+                id: Some(id),
+                ..rule
+            }]
+        );
+        assert!(db.delete_operating_rule(id).unwrap());
+        assert!(!db.delete_operating_rule(id).unwrap());
+        assert!(db.read_operating_rules().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_position_history() {
+        let db = Database::open_in_memory().unwrap();
+        for (name, time, elevation) in [
+            ("ISS", "2026-05-01T10:00:00.000Z", 2.0),
+            ("ISS", "2026-05-01T10:00:05.000Z", 2.4),
+            ("SO-50", "2026-05-01T10:00:05.000Z", 30.0),
+            ("ISS", "2026-05-01T11:30:00.000Z", 5.0),
+        ] {
+            db.record_position(&PositionRecord {
+                satellite_name: name.to_string(),
+                recorded_at: time.to_string(),
+                elevation,
+                doppler_hz: Some(3500.0),
+                signal_db: (name == "SO-50").then_some(-12.0),
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        let history = db
+            .read_position_history("ISS", "2026-05-01T09:58:00.000Z", "2026-05-01T10:10:00.000Z")
+            .unwrap();
+        let elevations: Vec<f64> = history.iter().map(|record| record.elevation).collect();
+        assert_eq!(elevations, vec![2.0, 2.4]);
+        assert_eq!(history[0].doppler_hz, Some(3500.0));
+        assert_eq!(history[0].signal_db, None);
+        let so50 = db
+            .read_position_history("SO-50", "2026-05-01T09:58:00.000Z", "2026-05-01T10:10:00.000Z")
+            .unwrap();
+        assert_eq!(so50[0].signal_db, Some(-12.0));
+    }
+
+    #[test]
+    fn test_settings() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(db.read_setting("profile").unwrap(), None);
+        db.save_setting("profile", "Home").unwrap();
+        db.save_setting("profile", "Portable").unwrap();
+        assert_eq!(db.read_setting("profile").unwrap().as_deref(), Some("Portable"));
+    }
+
+    #[test]
+    fn test_telemetry_frames() {
+        let db = Database::open_in_memory().unwrap();
+        for (time, frame) in [
+            ("2024-03-01T10:00:00+00:00", "8a 01 02"),
+            ("2024-03-01T10:00:05+00:00", "8a 01 03"),
+        ] {
+            db.save_telemetry_frame(&TelemetryFrame {
+                id: None,
+                satellite_name: "AO-73".to_string(),
+                received_at: time.to_string(),
+                frame: frame.to_string(),
+            })
+            .unwrap();
+        }
+
+        let frames = db.read_telemetry_frames("AO-73", 10).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].frame, "8a 01 03");
+        assert_eq!(db.read_telemetry_frames("AO-73", 1).unwrap().len(), 1);
+        assert!(db.read_telemetry_frames("FO-29", 10).unwrap().is_empty());
+
+        let times = db
+            .read_telemetry_times("AO-73", "2024-03-01T10:00:03+00:00", "2024-03-01T10:01:00+00:00")
+            .unwrap();
+        assert_eq!(times, vec!["2024-03-01T10:00:05+00:00"]);
+    }
+
+    #[test]
+    fn test_frequency_offsets() {
+        let db = Database::open_in_memory().unwrap();
+        assert!(db.read_frequency_offsets().unwrap().is_empty());
+
+        let mut offset = FrequencyOffset {
+            satellite_name: "AO-7".to_string(),
+            transponder: "145.950000".to_string(),
+            offset_hz: 1200.0,
+        };
+        db.save_frequency_offset(&offset).unwrap();
+        offset.offset_hz = 900.0;
+        db.save_frequency_offset(&offset).unwrap();
+        db.save_frequency_offset(&FrequencyOffset {
+            transponder: "29.502500".to_string(),
+            ..offset.clone()
+        })
+        .unwrap();
+
+        let offsets = db.read_frequency_offsets().unwrap();
+        assert_eq!(offsets.len(), 2);
+        assert!(offsets.contains(&offset));
+    }
+
+    #[test]
+    fn test_muted_alerts() {
+        let db = Database::open_in_memory().unwrap();
+        assert!(db.read_muted_alerts().unwrap().is_empty());
+
+        db.set_alerts_muted("NOAA 19", true).unwrap();
+        db.set_alerts_muted("NOAA 19", true).unwrap();
+        db.set_alerts_muted("METEOR-M2 3", true).unwrap();
+        db.set_alerts_muted("METEOR-M2 3", false).unwrap();
+        db.set_alerts_muted("AO-91", false).unwrap();
+
+        let muted = db.read_muted_alerts().unwrap();
+        assert_eq!(muted.len(), 1);
+        assert!(muted.contains("NOAA 19"));
+    }
+
+    #[test]
+    fn test_read_only_query() {
+        let db = Database::open_in_memory().unwrap();
+        db.create(&SatelliteDetails::new("AO-91".to_string())).unwrap();
+
+        let result = db
+            .query("SELECT name, downlink_frequency_mhz FROM satellite_details;")
+            .unwrap();
+        assert_eq!(result.columns, vec!["name", "downlink_frequency_mhz"]);
+        assert_eq!(result.rows, vec![vec!["AO-91".to_string(), "NULL".to_string()]]);
+        assert!(result.to_table().ends_with("(1 row)"));
+
+        assert!(db.query("DELETE FROM satellite_details").is_err());
+        // Even if a data-modifying CTE is accepted, the transaction is rolled back
+        let _ = db.query("WITH gone AS (DELETE FROM satellite_details RETURNING id) SELECT * FROM gone");
+        assert_eq!(db.count().unwrap(), 1);
+
+        // Statements stacked after a query would run while it is prepared
+        assert!(db
+            .query("SELECT 1; COMMIT; DELETE FROM satellite_details")
+            .is_err());
+        assert_eq!(db.count().unwrap(), 1);
+        let result = db
+            .query("SELECT name FROM satellite_details WHERE name != 'a;b' -- x;\n;")
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+    }
+
+    #[test]
+    fn test_open_read_only() {
+        let path = std::env::temp_dir().join(format!("crabtrack-ro-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        Database::open(&path)
+            .unwrap()
+            .create(&SatelliteDetails::new("AO-91".to_string()))
+            .unwrap();
+
+        let db = Database::open_read_only(&path).unwrap();
+        assert_eq!(db.query("SELECT name FROM satellite_details").unwrap().rows.len(), 1);
+        assert!(db.conn().unwrap().execute_batch("DELETE FROM satellite_details").is_err());
+        assert!(db.query("SELECT * FROM read_csv('/etc/passwd')").is_err());
+        drop(db);
+        std::fs::remove_file(&path).unwrap();
+        let _ = std::fs::remove_file(path.with_extension("db.wal"));
+    }
+
+    #[test]
+    fn test_open_shared() {
+        let path = std::env::temp_dir().join(format!("crabtrack-shared-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let shared = Database::open_shared(&path).unwrap();
+        shared.create(&SatelliteDetails::new("AO-91".to_string())).unwrap();
+
+        // The file is closed between calls, so another connection can have it
+        assert_eq!(Database::open(&path).unwrap().count().unwrap(), 1);
+
+        // Nested calls share one connection
+        let details = shared.read_by_name("AO-91").unwrap().unwrap();
+        shared.archive(&details).unwrap();
+        assert_eq!(shared.count().unwrap(), 0);
+        assert!(shared.unarchive("AO-91").unwrap());
+        assert_eq!(shared.count().unwrap(), 1);
+        drop(shared);
+        std::fs::remove_file(&path).unwrap();
+        let _ = std::fs::remove_file(path.with_extension("db.wal"));
+    }
+
+    #[test]
+    fn test_delete() {
+        let db = Database::open_in_memory().unwrap();
+
+        let details = SatelliteDetails::new("TO DELETE".to_string());
+        let id = db.create(&details).unwrap();
+
+        assert!(db.delete(id).unwrap());
+        assert!(db.read_by_id(id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_search_satellites() {
+        let db = Database::open_in_memory().unwrap();
+        let mut fox = SatelliteDetails::new("FOX-1B (AO-91)".to_string());
+        fox.notes = Some("Needs 67.0 Hz CTCSS; weak in winter".to_string());
+        fox.operator = Some("AMSAT".to_string());
+        let fox_id = db.create(&fox).unwrap();
+        let mut so50 = SatelliteDetails::new("SAUDISAT 1C (SO-50)".to_string());
+        so50.notes = Some("Arm with a 74.4 Hz tone".to_string());
+        db.create(&so50).unwrap();
+
+        assert_eq!(db.search_satellites("ctcss").unwrap(), vec![fox_id]);
+        assert_eq!(db.search_satellites("amsat winter").unwrap(), vec![fox_id]);
+        assert_eq!(db.search_satellites("hz").unwrap().len(), 2);
+        assert!(db.search_satellites("amsat tone").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_archive() {
+        let db = Database::open_in_memory().unwrap();
+        let mut details = SatelliteDetails::new("OLD SAT".to_string());
+        details.notes = Some("Kept while archived".to_string());
+        db.create(&details).unwrap();
+
+        db.archive(&details).unwrap();
+        assert!(db.read_by_name("OLD SAT").unwrap().is_none());
+        assert!(!db.unarchive("NEVER ARCHIVED").unwrap());
+        assert!(db.unarchive("OLD SAT").unwrap());
+        let restored = db.read_by_name("OLD SAT").unwrap().unwrap();
+        assert_eq!(restored.notes, details.notes);
+        assert!(!db.unarchive("OLD SAT").unwrap());
     }
 }
@@ -0,0 +1,358 @@
+//! `crabtrack doctor`: checks the configuration, TLE file, database, network
+//! and station hardware one by one and prints what passed and what didn't,
+//! so a setup problem shows up as a line in a report rather than a crash or a
+//! blank panel.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sgp4::Elements;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::antenna::AntennaPattern;
+use crate::celestrak;
+use crate::config::Config;
+use crate::console::ColorDepth;
+use crate::database::Database;
+use crate::hamlib::HamlibConnection;
+use crate::horizon::HorizonMask;
+use crate::noise::NoiseProfile;
+
+/// Elements older than this give noticeably wrong AOS times for low orbits
+const STALE_TLE_DAYS: i64 = 7;
+
+/// Smallest terminal the main screen lays out without squeezing panels
+const MIN_COLUMNS: u16 = 100;
+const MIN_ROWS: u16 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: String,
+    pub status: Status,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    fn from_result(name: impl Into<String>, result: Result<String>) -> Self {
+        match result {
+            Ok(detail) => Self::new(name, Status::Pass, detail),
+            Err(e) => Self::new(name, Status::Fail, format!("{:#}", e)),
+        }
+    }
+}
+
+/// Run every check. `profile` is applied to the configuration as `--profile`
+/// would; without one, the profile remembered in the database is used.
+pub fn run(config_path: &str, profile: Option<&str>, db_path: &Path) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let database = Database::open(db_path);
+    checks.push(match &database {
+        Ok(database) => Check::from_result("Database", database_summary(database, db_path)),
+        Err(e) => Check::new(
+            "Database",
+            Status::Fail,
+            format!(
+                "{} can't be opened, is crabtrack already running? ({:#})",
+                db_path.display(),
+                e
+            ),
+        ),
+    });
+
+    let remembered = database
+        .as_ref()
+        .ok()
+        .and_then(|database| database.read_setting("profile").ok().flatten());
+    let profile = profile.map(str::to_string).or(remembered);
+    let config = Config::load(config_path).and_then(|mut config| {
+        if let Some(profile) = &profile {
+            config.apply_profile(profile)?;
+        }
+        Ok(config)
+    });
+    match &config {
+        Ok(_) => checks.push(Check::new(
+            "Configuration",
+            Status::Pass,
+            match &profile {
+                Some(profile) => format!("{} with the {} profile", config_path, profile),
+                None => config_path.to_string(),
+            },
+        )),
+        Err(e) => checks.push(Check::new(
+            "Configuration",
+            Status::Fail,
+            format!("{}: {:#}", config_path, e),
+        )),
+    }
+
+    if let Ok(config) = &config {
+        checks.push(Check::from_result(
+            "Observer",
+            config
+                .observer
+                .coordinates()
+                .and_then(|(latitude, longitude)| {
+                    if let Some(horizon_file) = &config.observer.horizon_file {
+                        HorizonMask::load_csv(horizon_file)?;
+                    }
+                    if let Some(noise_file) = &config.observer.noise_file {
+                        NoiseProfile::load_csv(noise_file)?;
+                    }
+                    Ok(format!("{:.4}°, {:.4}°", latitude, longitude))
+                }),
+        ));
+        let pattern_files: Vec<_> = config
+            .antenna
+            .iter()
+            .chain(&config.antennas)
+            .filter_map(|antenna| antenna.pattern_file.as_ref())
+            .collect();
+        if !pattern_files.is_empty() {
+            checks.push(Check::from_result(
+                "Antenna patterns",
+                pattern_files
+                    .iter()
+                    .try_for_each(|path| AntennaPattern::load(path).map(|_| ()))
+                    .map(|()| format!("{} loaded", pattern_files.len())),
+            ));
+        }
+        let tle_file = &config.satellites.tle_file;
+        checks.push(match std::fs::read_to_string(tle_file) {
+            Ok(contents) => tle_check(&contents, &config.satellites.tracked_satellites, Utc::now()),
+            Err(e) => Check::new(
+                "TLE file",
+                Status::Warn,
+                format!(
+                    "{} can't be read ({}); it is downloaded on the next start",
+                    tle_file.display(),
+                    e
+                ),
+            ),
+        });
+    }
+
+    checks.push(Check::from_result(
+        "Celestrak",
+        celestrak::fetch_launch(&celestrak::LaunchIdentifier::NoradId(25544)).and_then(|entries| {
+            if entries.is_empty() {
+                anyhow::bail!("reachable, but returned no elements for the ISS");
+            }
+            Ok("reachable".to_string())
+        }),
+    ));
+
+    if let Ok(config) = &config {
+        for rotator in config.all_rotators() {
+            checks.push(Check::from_result(
+                format!("rotctld ({})", rotator.name),
+                HamlibConnection::connect("rotctld", &rotator.host, rotator.port).and_then(
+                    |mut connection| {
+                        connection.send("p")?;
+                        let azimuth = connection.read_line()?;
+                        let elevation = connection.read_line()?;
+                        Ok(format!(
+                            "{}:{} at azimuth {}, elevation {}",
+                            rotator.host, rotator.port, azimuth, elevation
+                        ))
+                    },
+                ),
+            ));
+        }
+        if let Some(rig) = &config.rig {
+            checks.push(Check::from_result(
+                "rigctld",
+                HamlibConnection::connect("rigctld", &rig.host, rig.port).and_then(
+                    |mut connection| {
+                        connection.send("f")?;
+                        let frequency = connection.read_line()?;
+                        Ok(format!("{}:{} on {} Hz", rig.host, rig.port, frequency))
+                    },
+                ),
+            ));
+        }
+        if let Some(sdr) = &config.sdr {
+            checks.push(Check::from_result(
+                "SDR",
+                HamlibConnection::connect("the SDR", &sdr.host, sdr.port).and_then(
+                    |mut connection| {
+                        connection.send("f")?;
+                        let frequency = connection.read_line()?;
+                        Ok(format!("{}:{} on {} Hz", sdr.host, sdr.port, frequency))
+                    },
+                ),
+            ));
+        }
+    }
+
+    checks.push(terminal_check());
+    checks
+}
+
+fn database_summary(database: &Database, db_path: &Path) -> Result<String> {
+    let counts = database.query(
+        "SELECT (SELECT count(*) FROM satellite_details), (SELECT count(*) FROM pass_log)",
+    )?;
+    let row = counts.rows.first().cloned().unwrap_or_default();
+    Ok(format!(
+        "{}: {} satellites, {} logged passes",
+        db_path.display(),
+        row.first().map_or("0", String::as_str),
+        row.get(1).map_or("0", String::as_str)
+    ))
+}
+
+/// Whether the TLE file holds usable elements for the tracked satellites,
+/// and how old they are
+fn tle_check(contents: &str, tracked: &[String], now: DateTime<Utc>) -> Check {
+    let entries = celestrak::parse_entries(contents);
+    if entries.is_empty() {
+        return Check::new("TLE file", Status::Fail, "no element sets found");
+    }
+    let missing: Vec<&str> = tracked
+        .iter()
+        .filter(|name| {
+            !entries
+                .iter()
+                .any(|entry| entry.name.contains(name.as_str()))
+        })
+        .map(String::as_str)
+        .collect();
+    let oldest = entries
+        .iter()
+        .filter(|entry| tracked.is_empty() || tracked.iter().any(|name| entry.name.contains(name)))
+        .filter_map(|entry| {
+            Elements::from_tle(None, entry.tle_line1.as_bytes(), entry.tle_line2.as_bytes()).ok()
+        })
+        .map(|elements| (now - elements.datetime.and_utc()).num_days())
+        .max();
+
+    let mut problems = Vec::new();
+    if !missing.is_empty() {
+        problems.push(format!("not found: {}", missing.join(", ")));
+    }
+    if let Some(days) = oldest.filter(|days| *days > STALE_TLE_DAYS) {
+        problems.push(format!(
+            "oldest elements are {} days old, update them",
+            days
+        ));
+    }
+    let summary = format!("{} element sets", entries.len());
+    if problems.is_empty() {
+        let age = oldest.map_or(String::new(), |days| format!(", oldest {} days", days));
+        Check::new("TLE file", Status::Pass, format!("{}{}", summary, age))
+    } else {
+        Check::new(
+            "TLE file",
+            Status::Warn,
+            format!("{}; {}", summary, problems.join("; ")),
+        )
+    }
+}
+
+/// Whether the main screen will fit and draw properly here
+fn terminal_check() -> Check {
+    if !std::io::stdout().is_terminal() {
+        return Check::new("Terminal", Status::Warn, "output is not a terminal");
+    }
+    let colors = ColorDepth::detect();
+    let mut problems = Vec::new();
+    match crossterm::terminal::size() {
+        Ok((columns, rows)) if columns < MIN_COLUMNS || rows < MIN_ROWS => problems.push(format!(
+            "{}x{} is smaller than {}x{}",
+            columns, rows, MIN_COLUMNS, MIN_ROWS
+        )),
+        Ok(_) => {}
+        Err(e) => problems.push(format!("size unknown ({})", e)),
+    }
+    if colors == ColorDepth::Monochrome {
+        problems.push("no colours (TERM unset or NO_COLOR set)".to_string());
+    }
+    let utf8 = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .is_some_and(|locale| locale.to_uppercase().replace('-', "").contains("UTF8"));
+    if !utf8 && !cfg!(windows) {
+        problems.push("locale is not UTF-8, try --ascii".to_string());
+    }
+    let size = crossterm::terminal::size()
+        .map(|(columns, rows)| format!("{}x{}", columns, rows))
+        .unwrap_or_default();
+    let detail = format!("{} {:?}", size, colors);
+    if problems.is_empty() {
+        Check::new("Terminal", Status::Pass, detail)
+    } else {
+        Check::new(
+            "Terminal",
+            Status::Warn,
+            format!("{}; {}", detail, problems.join("; ")),
+        )
+    }
+}
+
+/// Print the checks and fail if any of them did
+pub fn report(checks: &[Check]) -> Result<()> {
+    for check in checks {
+        let status = match check.status {
+            Status::Pass => "PASS",
+            Status::Warn => "WARN",
+            Status::Fail => "FAIL",
+        };
+        println!("{}  {:<22} {}", status, check.name, check.detail);
+    }
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    let warned = checks.iter().filter(|c| c.status == Status::Warn).count();
+    println!();
+    if failed > 0 {
+        anyhow::bail!("{} of {} checks failed", failed, checks.len());
+    }
+    println!(
+        "All {} checks passed, {} with warnings",
+        checks.len(),
+        warned
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::{SAMPLE_TLE_LINE1, SAMPLE_TLE_LINE2};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_tle_check() {
+        let iss = format!("ISS (ZARYA)\n{}\n{}\n", SAMPLE_TLE_LINE1, SAMPLE_TLE_LINE2);
+        let fresh = Utc.with_ymd_and_hms(2008, 9, 22, 0, 0, 0).unwrap();
+        let check = tle_check(&iss, &["ISS".to_string()], fresh);
+        assert_eq!(check.status, Status::Pass);
+        assert_eq!(check.detail, "1 element sets, oldest 1 days");
+
+        let check = tle_check(&iss, &["ISS".to_string(), "SO-50".to_string()], fresh);
+        assert_eq!(check.status, Status::Warn);
+        assert!(check.detail.contains("not found: SO-50"));
+
+        let later = Utc.with_ymd_and_hms(2008, 10, 20, 0, 0, 0).unwrap();
+        let check = tle_check(&iss, &[], later);
+        assert_eq!(check.status, Status::Warn);
+        assert!(check.detail.contains("29 days old"));
+
+        assert_eq!(tle_check("", &[], later).status, Status::Fail);
+    }
+}
@@ -0,0 +1,224 @@
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+const OPEN_ELEVATION_URL: &str = "https://api.open-elevation.com/api/v1/lookup";
+
+/// Centre of a 4, 6 or 8 character Maidenhead locator as (latitude, longitude)
+pub fn grid_to_latlon(grid: &str) -> Option<(f64, f64)> {
+    let chars: Vec<char> = grid.trim().to_ascii_uppercase().chars().collect();
+    if !matches!(chars.len(), 2 | 4 | 6 | 8) {
+        return None;
+    }
+
+    // (base character, number of values, longitude span, latitude span) per pair
+    let pairs = [
+        ('A', 18, 20.0, 10.0),
+        ('0', 10, 2.0, 1.0),
+        ('A', 24, 2.0 / 24.0, 1.0 / 24.0),
+        ('0', 10, 2.0 / 240.0, 1.0 / 240.0),
+    ];
+    let mut longitude = -180.0;
+    let mut latitude = -90.0;
+    let mut span = (0.0, 0.0);
+    for (pair, (base, count, lon_span, lat_span)) in chars.chunks(2).zip(pairs) {
+        let lon_index = (pair[0] as i32) - (base as i32);
+        let lat_index = (pair[1] as i32) - (base as i32);
+        if !(0..count).contains(&lon_index) || !(0..count).contains(&lat_index) {
+            return None;
+        }
+        longitude += lon_index as f64 * lon_span;
+        latitude += lat_index as f64 * lat_span;
+        span = (lat_span, lon_span);
+    }
+    Some((latitude + span.0 / 2.0, longitude + span.1 / 2.0))
+}
+
+/// Six-character Maidenhead locator of the square containing a position
+pub fn latlon_to_grid(latitude: f64, longitude: f64) -> String {
+    let lon = (longitude + 180.0).rem_euclid(360.0);
+    let lat = (latitude + 90.0).clamp(0.0, 180.0 - 1e-9);
+    let field = |value: f64, span: f64| (value / span).floor() as u8;
+    [
+        b'A' + field(lon, 20.0),
+        b'A' + field(lat, 10.0),
+        b'0' + field(lon % 20.0, 2.0),
+        b'0' + field(lat % 10.0, 1.0),
+        b'a' + field(lon % 2.0, 2.0 / 24.0),
+        b'a' + field(lat % 1.0, 1.0 / 24.0),
+    ]
+    .iter()
+    .map(|&b| b as char)
+    .collect()
+}
+
+/// South-west corner of an SRTM tile from its file name, e.g. "N28W081.hgt"
+/// or USGS-style "n28_w081_1arc_v3.hgt"
+fn hgt_origin(path: &Path) -> Option<(f64, f64)> {
+    let name = path.file_stem()?.to_str()?.to_ascii_uppercase();
+    let lat_pos = name.find(['N', 'S'])?;
+    let lat_digits: String = name[lat_pos + 1..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    let lon_pos = lat_pos + 1 + name[lat_pos + 1..].find(['E', 'W'])?;
+    let lon_digits: String = name[lon_pos + 1..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+
+    let mut lat: f64 = lat_digits.parse().ok()?;
+    let mut lon: f64 = lon_digits.parse().ok()?;
+    if name.as_bytes()[lat_pos] == b'S' {
+        lat = -lat;
+    }
+    if name.as_bytes()[lon_pos] == b'W' {
+        lon = -lon;
+    }
+    Some((lat, lon))
+}
+
+/// Terrain elevation in meters from an SRTM .hgt tile, interpolated between posts
+pub fn hgt_elevation(path: &Path, latitude: f64, longitude: f64) -> Result<f64> {
+    let (south, west) = hgt_origin(path)
+        .with_context(|| format!("can't tell the tile corner from {}", path.display()))?;
+    if !(south..=south + 1.0).contains(&latitude) || !(west..=west + 1.0).contains(&longitude) {
+        bail!(
+            "{:.4}, {:.4} is outside {}",
+            latitude,
+            longitude,
+            path.display()
+        );
+    }
+
+    let data = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let size = ((data.len() / 2) as f64).sqrt() as usize;
+    if size < 2 || size * size * 2 != data.len() {
+        bail!("{} is not a square grid of 16-bit posts", path.display());
+    }
+    let post = |row: usize, col: usize| -> Result<f64> {
+        let offset = (row * size + col) * 2;
+        let value = i16::from_be_bytes([data[offset], data[offset + 1]]);
+        if value == -32768 {
+            bail!("no data at {:.4}, {:.4} (SRTM void)", latitude, longitude);
+        }
+        Ok(value as f64)
+    };
+
+    // Rows run north to south, columns west to east
+    let last = (size - 1) as f64;
+    let y = (south + 1.0 - latitude) * last;
+    let x = (longitude - west) * last;
+    let (row, col) = (
+        (y.floor() as usize).min(size - 2),
+        (x.floor() as usize).min(size - 2),
+    );
+    let (fy, fx) = (y - row as f64, x - col as f64);
+
+    let top = post(row, col)? * (1.0 - fx) + post(row, col + 1)? * fx;
+    let bottom = post(row + 1, col)? * (1.0 - fx) + post(row + 1, col + 1)? * fx;
+    Ok(top * (1.0 - fy) + bottom * fy)
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenElevationResponse {
+    results: Vec<OpenElevationResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenElevationResult {
+    elevation: f64,
+}
+
+/// Terrain elevation in meters from the Open-Elevation API
+pub fn fetch_elevation(latitude: f64, longitude: f64) -> Result<f64> {
+    let url = format!(
+        "{}?locations={:.6},{:.6}",
+        OPEN_ELEVATION_URL, latitude, longitude
+    );
+    let response = ureq::get(&url)
+        .timeout(std::time::Duration::from_secs(15))
+        .call()
+        .map_err(|e| anyhow!("HTTP request failed: {}", e))?;
+
+    let mut body = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| anyhow!("Failed to read response: {}", e))?;
+    parse_open_elevation(&body)
+}
+
+fn parse_open_elevation(json: &str) -> Result<f64> {
+    let response: OpenElevationResponse = serde_json::from_str(json)?;
+    response
+        .results
+        .first()
+        .map(|result| result.elevation)
+        .context("Open-Elevation returned no results")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_to_latlon() {
+        let (lat, lon) = grid_to_latlon("FN31").unwrap();
+        assert_eq!((lat, lon), (41.5, -73.0));
+
+        let (lat, lon) = grid_to_latlon("fn31pr").unwrap();
+        assert!((lat - 41.729167).abs() < 1e-6);
+        assert!((lon - (-72.708333)).abs() < 1e-6);
+
+        let (lat, lon) = grid_to_latlon("JO01ab12").unwrap();
+        assert!((lat - 51.052083).abs() < 1e-6);
+        assert!((lon - 0.0125).abs() < 1e-6);
+
+        assert!(grid_to_latlon("ZZ00").is_none());
+        assert!(grid_to_latlon("FN3").is_none());
+    }
+
+    #[test]
+    fn test_latlon_to_grid() {
+        assert_eq!(latlon_to_grid(41.729167, -72.708333), "FN31pr");
+        assert_eq!(latlon_to_grid(51.052083, 0.0125), "JO01ab");
+        assert_eq!(latlon_to_grid(-90.0, 180.0), "AA00aa");
+        assert_eq!(latlon_to_grid(90.0, -180.0), "AR09ax");
+    }
+
+    #[test]
+    fn test_hgt_origin() {
+        assert_eq!(hgt_origin(Path::new("N28W081.hgt")), Some((28.0, -81.0)));
+        assert_eq!(
+            hgt_origin(Path::new("/dem/s34_e151_1arc_v3.hgt")),
+            Some((-34.0, 151.0))
+        );
+        assert_eq!(hgt_origin(Path::new("terrain.hgt")), None);
+    }
+
+    #[test]
+    fn test_hgt_elevation() {
+        // 3x3 posts: 100 m along the north edge, 0 m along the south edge
+        let posts: [i16; 9] = [100, 100, 100, 50, 50, 50, 0, 0, -32768];
+        let bytes: Vec<u8> = posts.iter().flat_map(|p| p.to_be_bytes()).collect();
+        let path = std::env::temp_dir().join(format!("N40W106-{}.hgt", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+
+        assert_eq!(hgt_elevation(&path, 41.0, -106.0).unwrap(), 100.0);
+        assert_eq!(hgt_elevation(&path, 40.75, -105.75).unwrap(), 75.0);
+        assert!(hgt_elevation(&path, 40.0, -105.0).is_err()); // void post
+        assert!(hgt_elevation(&path, 39.5, -105.5).is_err()); // outside the tile
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_open_elevation() {
+        let json =
+            r#"{"results": [{"latitude": 28.474, "longitude": -80.5772, "elevation": 3.0}]}"#;
+        assert_eq!(parse_open_elevation(json).unwrap(), 3.0);
+        assert!(parse_open_elevation(r#"{"results": []}"#).is_err());
+    }
+}
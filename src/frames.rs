@@ -0,0 +1,249 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use nalgebra::{Matrix3, Vector3};
+
+use crate::pass_prediction::calculate_gmst;
+
+/// CelesTrak's consolidated Earth orientation parameters, updated daily
+pub const EOP_URL: &str = "https://celestrak.org/SpaceData/EOP-All.csv";
+
+const EARTH_ROT_RAD_S: f64 = 7.292115146706979e-5;
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+const MJD_UNIX_EPOCH: f64 = 40587.0;
+
+/// Earth orientation parameters for one instant
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EarthOrientation {
+    pub ut1_minus_utc: f64, // seconds
+    pub x_pole: f64,        // arcseconds
+    pub y_pole: f64,        // arcseconds
+}
+
+/// Daily Earth orientation parameters, interpolated between days
+#[derive(Debug, Clone, Default)]
+pub struct EopTable {
+    days: Vec<(f64, EarthOrientation)>, // (MJD, parameters), sorted by MJD
+}
+
+impl EopTable {
+    /// Parse CelesTrak's EOP CSV (DATE,MJD,X,Y,UT1-UTC,...)
+    pub fn parse_csv(text: &str) -> Result<Self> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines
+            .next()
+            .context("empty EOP file")?
+            .split(',')
+            .map(str::trim)
+            .collect();
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| *h == name)
+                .with_context(|| format!("EOP file has no {} column", name))
+        };
+        let (mjd, x, y, dut1) = (
+            column("MJD")?,
+            column("X")?,
+            column("Y")?,
+            column("UT1-UTC")?,
+        );
+
+        let mut days = Vec::new();
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |index: usize| -> Result<f64> {
+                fields
+                    .get(index)
+                    .and_then(|value| value.parse().ok())
+                    .with_context(|| format!("bad EOP line: {}", line))
+            };
+            days.push((
+                field(mjd)?,
+                EarthOrientation {
+                    ut1_minus_utc: field(dut1)?,
+                    x_pole: field(x)?,
+                    y_pole: field(y)?,
+                },
+            ));
+        }
+        days.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self { days })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::parse_csv(&text)
+    }
+
+    pub fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    /// Parameters at a time, or None outside the table
+    pub fn at(&self, time: DateTime<Utc>) -> Option<EarthOrientation> {
+        let mjd = time.timestamp_millis() as f64 / 86_400_000.0 + MJD_UNIX_EPOCH;
+        let next = self.days.iter().position(|(day, _)| *day >= mjd)?;
+        let (day1, eop1) = self.days[next];
+        if next == 0 {
+            return (day1 == mjd).then_some(eop1);
+        }
+        let (day0, eop0) = self.days[next - 1];
+        let f = (mjd - day0) / (day1 - day0);
+        let lerp = |a: f64, b: f64| a + (b - a) * f;
+        // UT1-UTC jumps by a second at leap seconds; don't interpolate across one
+        let ut1_minus_utc = if (eop1.ut1_minus_utc - eop0.ut1_minus_utc).abs() > 0.5 {
+            eop0.ut1_minus_utc
+        } else {
+            lerp(eop0.ut1_minus_utc, eop1.ut1_minus_utc)
+        };
+        Some(EarthOrientation {
+            ut1_minus_utc,
+            x_pole: lerp(eop0.x_pole, eop1.x_pole),
+            y_pole: lerp(eop0.y_pole, eop1.y_pole),
+        })
+    }
+}
+
+/// Greenwich mean sidereal time (IAU 1982) in radians for a UT1 instant
+pub fn gmst_1982(ut1: DateTime<Utc>) -> f64 {
+    let j2000 = DateTime::parse_from_rfc3339("2000-01-01T12:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    let days = (ut1 - j2000).num_nanoseconds().unwrap_or_default() as f64 / 86_400e9;
+    let t = days / 36525.0;
+    let seconds = 67310.54841 + (876600.0 * 3600.0 + 8640184.812866) * t + 0.093104 * t * t
+        - 6.2e-6 * t * t * t;
+    (seconds / 240.0)
+        .to_radians()
+        .rem_euclid(std::f64::consts::TAU)
+}
+
+/// Rotation from SGP4's TEME frame to the Earth-fixed frame at one instant
+pub struct TemeToEcef {
+    pub gmst: f64,
+    polar_motion: Matrix3<f64>, // PEF -> ITRF; identity without EOP
+}
+
+impl TemeToEcef {
+    /// GMST rotation only, treating UTC as UT1 and ignoring polar motion
+    pub fn simple(time: DateTime<Utc>) -> Self {
+        Self {
+            gmst: calculate_gmst(time),
+            polar_motion: Matrix3::identity(),
+        }
+    }
+
+    /// TEME -> PEF with IAU 1982 GMST of UT1, then PEF -> ITRF with polar motion
+    pub fn precise(time: DateTime<Utc>, eop: EarthOrientation) -> Self {
+        let ut1 = time + chrono::Duration::nanoseconds((eop.ut1_minus_utc * 1e9).round() as i64);
+        let (xp, yp) = (eop.x_pole * ARCSEC_TO_RAD, eop.y_pole * ARCSEC_TO_RAD);
+        #[rustfmt::skip]
+        let pef_from_itrf = Matrix3::new(
+            xp.cos(),            0.0,       -xp.sin(),
+            xp.sin() * yp.sin(), yp.cos(),  xp.cos() * yp.sin(),
+            xp.sin() * yp.cos(), -yp.sin(), xp.cos() * yp.cos(),
+        );
+        Self {
+            gmst: gmst_1982(ut1),
+            polar_motion: pef_from_itrf.transpose(),
+        }
+    }
+
+    fn pef(&self, teme: &Vector3<f64>) -> Vector3<f64> {
+        let (sin, cos) = self.gmst.sin_cos();
+        Vector3::new(
+            teme.x * cos + teme.y * sin,
+            -teme.x * sin + teme.y * cos,
+            teme.z,
+        )
+    }
+
+    pub fn position(&self, teme: &Vector3<f64>) -> Vector3<f64> {
+        self.polar_motion * self.pef(teme)
+    }
+
+    /// Earth-fixed velocity, removing the frame's rotation
+    pub fn velocity(&self, teme_pos: &Vector3<f64>, teme_vel: &Vector3<f64>) -> Vector3<f64> {
+        let pef_pos = self.pef(teme_pos);
+        let pef_vel = self.pef(teme_vel)
+            - Vector3::new(
+                -EARTH_ROT_RAD_S * pef_pos.y,
+                EARTH_ROT_RAD_S * pef_pos.x,
+                0.0,
+            );
+        self.polar_motion * pef_vel
+    }
+
+    /// Earth-fixed position back to TEME
+    pub fn to_teme(&self, ecef: &Vector3<f64>) -> Vector3<f64> {
+        let pef = self.polar_motion.transpose() * ecef;
+        let (sin, cos) = self.gmst.sin_cos();
+        Vector3::new(pef.x * cos - pef.y * sin, pef.x * sin + pef.y * cos, pef.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_gmst_1982() {
+        // Vallado, Fundamentals of Astrodynamics, example 3-5
+        let ut1 = Utc.with_ymd_and_hms(1992, 8, 20, 12, 14, 0).unwrap();
+        assert!((gmst_1982(ut1).to_degrees() - 152.578_788_810).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_teme_to_itrf() {
+        // Vallado's TEME -> ITRF example at 2004-04-06 07:51:28.386009 UTC
+        let time = Utc.with_ymd_and_hms(2004, 4, 6, 7, 51, 28).unwrap()
+            + chrono::Duration::microseconds(386_009);
+        let eop = EarthOrientation {
+            ut1_minus_utc: -0.4399619,
+            x_pole: -0.140682,
+            y_pole: 0.333309,
+        };
+        let frame = TemeToEcef::precise(time, eop);
+
+        let r_teme = Vector3::new(5094.18016210, 6127.64465950, 6380.34453270);
+        let v_teme = Vector3::new(-4.746131487, 0.785818041, 5.531931288);
+        let r_itrf = frame.position(&r_teme);
+        let v_itrf = frame.velocity(&r_teme, &v_teme);
+
+        assert!((r_itrf - Vector3::new(-1033.4793830, 7901.2952754, 6380.3565958)).norm() < 1e-3);
+        assert!((v_itrf - Vector3::new(-3.225636520, -2.872451450, 5.531924446)).norm() < 1e-5);
+        assert!((frame.to_teme(&r_itrf) - r_teme).norm() < 1e-9);
+
+        // Polar motion alone moves a LEO satellite by several meters
+        let simple = TemeToEcef::simple(time).position(&r_teme);
+        assert!((simple - r_itrf).norm() > 0.005);
+    }
+
+    #[test]
+    fn test_eop_table() {
+        let csv = "DATE,MJD,X,Y,UT1-UTC,LOD,DPSI,DEPS,DX,DY,DAT,DATA_TYPE\n\
+            2016-12-30,57752,0.080000,0.250000,0.5900000,0.0010000,0,0,0,0,36,O\n\
+            2016-12-31,57753,0.082000,0.252000,0.5890000,0.0010000,0,0,0,0,36,O\n\
+            2017-01-01,57754,0.084000,0.254000,-0.4120000,0.0010000,0,0,0,0,37,O\n";
+        let table = EopTable::parse_csv(csv).unwrap();
+        assert_eq!(table.len(), 3);
+
+        let noon = Utc.with_ymd_and_hms(2016, 12, 30, 12, 0, 0).unwrap();
+        let eop = table.at(noon).unwrap();
+        assert!((eop.x_pole - 0.081).abs() < 1e-9);
+        assert!((eop.ut1_minus_utc - 0.5895).abs() < 1e-9);
+
+        // Across the leap second UT1-UTC holds instead of sliding through zero
+        let leap = Utc.with_ymd_and_hms(2016, 12, 31, 18, 0, 0).unwrap();
+        assert_eq!(table.at(leap).unwrap().ut1_minus_utc, 0.589);
+
+        assert!(table
+            .at(Utc.with_ymd_and_hms(2017, 2, 1, 0, 0, 0).unwrap())
+            .is_none());
+        assert!(EopTable::parse_csv("DATE,MJD\n").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! Fixed pointing for geostationary satellites such as QO-100: where to aim
+//! the dish, how far to turn the LNB for the satellite's polarization, and
+//! where the transponder lands on the receiver after the LNB's oscillator.
+//! Around the equinoxes the Sun also passes behind each slot for a few
+//! minutes a day, drowning its downlink in noise.
+
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::Vector3;
+
+use crate::astro;
+use crate::config::{GeostationaryConfig, GeostationarySlot};
+use crate::observer::Observer;
+use crate::pass_prediction::look_angles_from_ecef;
+use crate::radio::OrbitClass;
+use crate::satellite::Satellite;
+
+/// Radius of the geostationary orbit (km)
+pub const GEO_RADIUS_KM: f64 = 42164.0;
+
+/// A little over the Sun's fastest apparent motion across the sky, 0.25° a
+/// minute, in degrees a minute
+const SUN_RATE_DEG_PER_MIN: f64 = 0.3;
+
+/// How finely the start and end of a sun outage are found
+const OUTAGE_STEP_SECONDS: i64 = 10;
+
+/// Where to aim a dish at a satellite in a geostationary slot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pointing {
+    pub azimuth: f64, // true, not magnetic
+    pub elevation: f64,
+    pub range_km: f64,
+    pub skew: f64, // LNB rotation in degrees, clockwise seen from behind the dish
+}
+
+/// Pointing from the observer to the slot at `longitude` (east positive)
+pub fn pointing(observer: &Observer, longitude: f64) -> Pointing {
+    let lon = longitude.to_radians();
+    let satellite = Vector3::new(lon.cos(), lon.sin(), 0.0) * GEO_RADIUS_KM * 1000.0;
+    let station = observer.to_ecef();
+    let look = look_angles_from_ecef(&satellite, &station, observer.latitude, observer.longitude);
+
+    // The satellite's vertical polarization lies along the Earth's axis; the
+    // skew is how far that is turned from the local vertical, both seen
+    // across the line of sight
+    let sight = (satellite - station).normalize();
+    let across = |v: Vector3<f64>| (v - sight * v.dot(&sight)).normalize();
+    let (lat, lon) = (
+        observer.latitude.to_radians(),
+        observer.longitude.to_radians(),
+    );
+    let up = across(Vector3::new(
+        lat.cos() * lon.cos(),
+        lat.cos() * lon.sin(),
+        lat.sin(),
+    ));
+    let polarization = across(Vector3::z());
+    let skew = up
+        .cross(&polarization)
+        .dot(&sight)
+        .atan2(up.dot(&polarization))
+        .to_degrees();
+
+    Pointing {
+        azimuth: look.azimuth,
+        elevation: look.elevation,
+        range_km: look.range,
+        // A linear polarization is the same turned half a circle
+        skew: (skew + 90.0).rem_euclid(180.0) - 90.0,
+    }
+}
+
+/// A stretch of time with the Sun behind a geostationary satellite
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunOutage {
+    pub start: DateTime<Utc>,
+    pub peak: DateTime<Utc>, // closest approach
+    pub end: DateTime<Utc>,
+    pub min_separation: f64, // degrees between the Sun and the satellite at the peak
+}
+
+/// Sun outages of the slot at `longitude` between `start` and `end`: times
+/// the Sun comes within `within_deg` of the dish's pointing
+pub fn sun_outages(
+    observer: &Observer,
+    longitude: f64,
+    (start, end): (DateTime<Utc>, DateTime<Utc>),
+    within_deg: f64,
+) -> Vec<SunOutage> {
+    let pointing = pointing(observer, longitude);
+    if pointing.elevation < 0.0 {
+        return Vec::new();
+    }
+    let separation = |time: DateTime<Utc>| {
+        let sun = astro::sun_look_angles(time, observer);
+        astro::angular_separation(
+            pointing.azimuth,
+            pointing.elevation,
+            sun.azimuth,
+            sun.elevation,
+        )
+    };
+
+    let mut outages = Vec::new();
+    let mut current: Option<SunOutage> = None;
+    let mut time = start;
+    while time <= end {
+        let apart = separation(time);
+        let step = if apart <= within_deg {
+            let outage = current.get_or_insert(SunOutage {
+                start: time,
+                peak: time,
+                end: time,
+                min_separation: apart,
+            });
+            if apart < outage.min_separation {
+                outage.peak = time;
+                outage.min_separation = apart;
+            }
+            outage.end = time;
+            OUTAGE_STEP_SECONDS
+        } else {
+            outages.extend(current.take());
+            // However fast the Sun moves it can't get within reach any sooner
+            let minutes = (apart - within_deg) / SUN_RATE_DEG_PER_MIN;
+            ((minutes * 60.0) as i64).max(OUTAGE_STEP_SECONDS)
+        };
+        time += Duration::seconds(step);
+    }
+    outages.extend(current);
+    outages
+}
+
+/// "12.3° clockwise" or "12.3° counter-clockwise", seen from behind the dish
+pub fn describe_skew(skew: f64) -> String {
+    match skew {
+        s if s.abs() < 0.05 => "none".to_string(),
+        s if s > 0.0 => format!("{:.1}° clockwise", s),
+        s => format!("{:.1}° counter-clockwise", -s),
+    }
+}
+
+/// Frequency after a converter whose oscillator runs at `lo_mhz` and is
+/// `offset_hz` off: the IF out of an LNB, or what a transverter takes in
+pub fn intermediate_mhz(rf_mhz: f64, lo_mhz: f64, offset_hz: f64) -> f64 {
+    (rf_mhz - (lo_mhz + offset_hz / 1e6)).abs()
+}
+
+/// How far the LNB's oscillator is off, from where a beacon at `beacon_mhz`
+/// shows up on the receiver
+pub fn lnb_offset_hz(beacon_mhz: f64, lo_mhz: f64, observed_if_mhz: f64) -> f64 {
+    ((beacon_mhz - lo_mhz) - observed_if_mhz) * 1e6
+}
+
+/// The configured slots, then the tracked geostationary satellites not
+/// configured by name, at the longitude they are over now
+pub fn slots(
+    config: &GeostationaryConfig,
+    satellites: &[Satellite],
+    observer: &Observer,
+    now: DateTime<Utc>,
+) -> Vec<GeostationarySlot> {
+    let mut slots = config.satellites.clone();
+    for satellite in satellites {
+        if satellite.orbit_class() != OrbitClass::Geostationary
+            || slots.iter().any(|slot| slot.name == satellite.name)
+        {
+            continue;
+        }
+        if let Ok(position) = satellite.calculate_position(now, observer) {
+            slots.push(GeostationarySlot {
+                name: satellite.name.clone(),
+                longitude: position.longitude,
+                beacon_mhz: None,
+                uplink_offset_mhz: 0.0,
+                segments: Vec::new(),
+            });
+        }
+    }
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qo100_pointing() {
+        // Qatar's own Es'hail-2 slot at 25.9°E, from London
+        let london = Observer::new("London".to_string(), 51.5, -0.13, 20.0);
+        let pointing = pointing(&london, 25.9);
+        assert!((pointing.azimuth - 148.0).abs() < 0.1, "{:?}", pointing);
+        assert!((pointing.elevation - 26.2).abs() < 0.1, "{:?}", pointing);
+        assert!((pointing.range_km - 38_947.0).abs() < 10.0);
+        // East of the observer in the north: turn the LNB counter-clockwise
+        assert!((pointing.skew + 19.3).abs() < 1.0, "{:?}", pointing);
+        assert_eq!(
+            describe_skew(pointing.skew),
+            format!("{:.1}° counter-clockwise", -pointing.skew)
+        );
+
+        // Due south along the same meridian needs no skew
+        let below = Observer::new("Equator".to_string(), 30.0, 25.9, 0.0);
+        let straight = super::pointing(&below, 25.9);
+        assert!(straight.skew.abs() < 0.01);
+        assert!((straight.azimuth - 180.0).abs() < 0.01);
+        assert_eq!(describe_skew(straight.skew), "none");
+
+        // The PSK beacon through a 9750 MHz LNB running 2.3 kHz high
+        assert!((intermediate_mhz(10489.750, 9750.0, 0.0) - 739.750).abs() < 1e-9);
+        let offset = lnb_offset_hz(10489.750, 9750.0, 739.7477);
+        assert!((offset - 2300.0).abs() < 0.01);
+        assert!((intermediate_mhz(10489.750, 9750.0, offset) - 739.7477).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sun_outages() {
+        use chrono::{Datelike, TimeZone, Timelike};
+
+        let london = Observer::new("London".to_string(), 51.5, -0.13, 20.0);
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let outages = sun_outages(&london, 25.9, (start, start + Duration::days(365)), 2.0);
+
+        // About ten mornings either side of each equinox, with the Sun south-east
+        let spring = outages.iter().filter(|outage| outage.peak.month() <= 3).count();
+        let autumn = outages.iter().filter(|outage| outage.peak.month() == 10).count();
+        assert!((8..=12).contains(&spring), "{:?}", outages);
+        assert!((8..=12).contains(&autumn), "{:?}", outages);
+        assert_eq!(spring + autumn, outages.len());
+        for outage in &outages {
+            assert!(outage.start <= outage.peak && outage.peak <= outage.end);
+            assert!(outage.end - outage.start < Duration::minutes(20));
+            assert!(outage.min_separation <= 2.0);
+            assert!((9..=10).contains(&outage.peak.hour()), "{:?}", outage);
+        }
+        // The Sun crosses the satellite itself around 1 March and 12 October
+        let closest = |month| {
+            outages
+                .iter()
+                .filter(|outage| outage.peak.month() == month)
+                .min_by(|a, b| a.min_separation.total_cmp(&b.min_separation))
+                .unwrap()
+        };
+        assert!(closest(3).min_separation < 0.3);
+        assert!(closest(10).min_separation < 0.3);
+        assert!(closest(10).peak.day() >= 10 && closest(10).peak.day() <= 13);
+
+        // No outages for a slot below the horizon
+        let sydney = Observer::new("Sydney".to_string(), -33.9, 151.2, 0.0);
+        assert!(sun_outages(&sydney, 25.9, (start, start + Duration::days(365)), 2.0).is_empty());
+    }
+}
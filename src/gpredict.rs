@@ -0,0 +1,350 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::celestrak;
+use crate::database::{SatelliteDetails, SatelliteGroup, Transponder};
+use crate::transponder::matches_satellite;
+
+/// Everything imported from a GPredict configuration directory
+#[derive(Debug, Default)]
+pub struct GpredictImport {
+    pub satellites: Vec<SatelliteDetails>,
+    pub transponders: Vec<Transponder>,
+    pub groups: Vec<SatelliteGroup>,
+}
+
+type IniSection = (String, HashMap<String, String>);
+
+/// Parse GPredict's INI-style files into (section, key/value) pairs
+fn parse_ini(contents: &str) -> Vec<IniSection> {
+    let mut sections: Vec<IniSection> = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push((name.trim().to_string(), HashMap::new()));
+        } else if let (Some((key, value)), Some(section)) =
+            (line.split_once('='), sections.last_mut())
+        {
+            section
+                .1
+                .insert(key.trim().to_uppercase(), value.trim().to_string());
+        }
+    }
+
+    sections
+}
+
+/// Parse a `<norad>.sat` file into satellite details
+pub fn parse_sat(contents: &str) -> Option<SatelliteDetails> {
+    let sections = parse_ini(contents);
+    let (_, values) = sections.iter().find(|(name, _)| name == "Satellite")?;
+
+    let name = values
+        .get("NAME")
+        .or_else(|| values.get("NICKNAME"))
+        .filter(|name| !name.is_empty())?;
+    let mut details = SatelliteDetails::new(name.clone());
+    details.tle_line1 = values.get("TLE1").cloned().unwrap_or_default();
+    details.tle_line2 = values.get("TLE2").cloned().unwrap_or_default();
+    details.notes = Some("Imported from GPredict".to_string());
+    Some(details)
+}
+
+/// Parse a `<norad>.trsp` file; each section is one transponder with
+/// frequencies in Hz
+pub fn parse_trsp(contents: &str, satellite_name: &str) -> Vec<Transponder> {
+    let frequency = |values: &HashMap<String, String>, key: &str| {
+        values
+            .get(key)
+            .and_then(|hz| hz.parse::<f64>().ok())
+            .filter(|hz| *hz > 0.0)
+            .map(|hz| hz / 1_000_000.0)
+    };
+
+    parse_ini(contents)
+        .into_iter()
+        .map(|(description, values)| {
+            let mode = values.get("MODE").filter(|m| !m.is_empty()).cloned();
+            Transponder {
+                id: None,
+                satellite_name: satellite_name.to_string(),
+                description,
+                downlink_mhz: frequency(&values, "DOWN_LOW"),
+                uplink_mhz: frequency(&values, "UP_LOW"),
+                downlink_mode: mode.clone(),
+                uplink_mode: mode,
+                inverting: values
+                    .get("INVERT")
+                    .is_some_and(|v| v.eq_ignore_ascii_case("true")),
+                ctcss_hz: None,
+                source: "gpredict".to_string(),
+            }
+        })
+        .collect()
+}
+
+/// NORAD catalog numbers listed by a `.mod` module file
+pub fn parse_mod(contents: &str) -> Vec<u32> {
+    parse_ini(contents)
+        .iter()
+        .find_map(|(_, values)| values.get("SATELLITES"))
+        .map(|list| {
+            list.split(';')
+                .filter_map(|id| id.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Files in `dir` with the given extension, as (file stem, contents)
+fn read_files(dir: &Path, extension: &str) -> Result<Vec<(String, String)>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+
+    for entry in std::fs::read_dir(dir).with_context(|| format!("reading '{}'", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == extension) {
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading '{}'", path.display()))?;
+            files.push((stem, contents));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Load satellites (`satdata/*.sat`), transponders (`satdata/*.trsp`), and
+/// groups (`modules/*.mod`) from a GPredict configuration directory such as
+/// `~/.config/Gpredict`
+pub fn load_config_dir<P: AsRef<Path>>(dir: P) -> Result<GpredictImport> {
+    let dir = dir.as_ref();
+    let satdata = dir.join("satdata");
+    let mut import = GpredictImport::default();
+    let mut names: HashMap<u32, String> = HashMap::new();
+
+    for (stem, contents) in read_files(&satdata, "sat")? {
+        if let Some(details) = parse_sat(&contents) {
+            if let Ok(norad_id) = stem.parse() {
+                names.insert(norad_id, details.name.clone());
+            }
+            import.satellites.push(details);
+        }
+    }
+
+    let name_for = |norad_id: u32| {
+        names
+            .get(&norad_id)
+            .cloned()
+            .unwrap_or_else(|| norad_id.to_string())
+    };
+
+    for (stem, contents) in read_files(&satdata, "trsp")? {
+        let Ok(norad_id) = stem.parse() else {
+            continue;
+        };
+        import
+            .transponders
+            .extend(parse_trsp(&contents, &name_for(norad_id)));
+    }
+
+    for (stem, contents) in read_files(&dir.join("modules"), "mod")? {
+        import.groups.push(SatelliteGroup {
+            name: stem,
+            satellites: parse_mod(&contents).into_iter().map(name_for).collect(),
+        });
+    }
+
+    if import.satellites.is_empty() && import.transponders.is_empty() && import.groups.is_empty() {
+        return Err(anyhow::anyhow!(
+            "no GPredict satdata or modules found in '{}'",
+            dir.display()
+        ));
+    }
+
+    Ok(import)
+}
+
+/// Render transponders as a GPredict `.trsp` file, frequencies in Hz
+pub fn format_trsp(transponders: &[Transponder]) -> String {
+    let hz = |mhz: f64| (mhz * 1_000_000.0).round() as u64;
+    let mut used_names: Vec<String> = Vec::new();
+    let mut out = String::new();
+
+    for transponder in transponders {
+        // GPredict keys sections by name, so duplicates need a suffix
+        let mut name = transponder.description.clone();
+        let mut suffix = 2;
+        while used_names.contains(&name) {
+            name = format!("{} ({})", transponder.description, suffix);
+            suffix += 1;
+        }
+        used_names.push(name.clone());
+
+        out.push_str(&format!("[{}]\n", name));
+        if let Some(uplink) = transponder.uplink_mhz {
+            out.push_str(&format!("UP_LOW={}\nUP_HIGH={}\n", hz(uplink), hz(uplink)));
+        }
+        if let Some(downlink) = transponder.downlink_mhz {
+            out.push_str(&format!(
+                "DOWN_LOW={}\nDOWN_HIGH={}\n",
+                hz(downlink),
+                hz(downlink)
+            ));
+        }
+        if let Some(mode) = transponder
+            .downlink_mode
+            .as_ref()
+            .or(transponder.uplink_mode.as_ref())
+        {
+            out.push_str(&format!("MODE={}\n", mode));
+        }
+        out.push_str(&format!("INVERT={}\n\n", transponder.inverting));
+    }
+
+    out
+}
+
+/// Transponders to export for one satellite: imported ones, or a single entry
+/// built from the frequencies entered in the details form
+fn export_transponders(
+    details: &SatelliteDetails,
+    transponders: &[Transponder],
+) -> Vec<Transponder> {
+    let matching: Vec<Transponder> = transponders
+        .iter()
+        .filter(|t| matches_satellite(&t.satellite_name, &details.name))
+        .cloned()
+        .collect();
+    if !matching.is_empty()
+        || (details.downlink_frequency_mhz.is_none() && details.uplink_frequency_mhz.is_none())
+    {
+        return matching;
+    }
+
+    vec![Transponder {
+        satellite_name: details.name.clone(),
+        description: "crabtrack".to_string(),
+        downlink_mhz: details.downlink_frequency_mhz,
+        uplink_mhz: details.uplink_frequency_mhz,
+        ..Default::default()
+    }]
+}
+
+/// Write `<norad>.trsp` files for every satellite with a NORAD ID and at
+/// least one transponder, returning how many files were written
+pub fn export_trsp_files<P: AsRef<Path>>(
+    dir: P,
+    satellites: &[SatelliteDetails],
+    transponders: &[Transponder],
+) -> Result<usize> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).with_context(|| format!("creating '{}'", dir.display()))?;
+
+    let mut written = 0;
+    for details in satellites {
+        let Some(norad_id) = celestrak::norad_id(&details.tle_line1) else {
+            continue;
+        };
+        let exported = export_transponders(details, transponders);
+        if exported.is_empty() {
+            continue;
+        }
+
+        let path = dir.join(format!("{}.trsp", norad_id));
+        std::fs::write(&path, format_trsp(&exported))
+            .with_context(|| format!("writing '{}'", path.display()))?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sat() {
+        let sat = "[Satellite]\n\
+            VERSION=1.1\n\
+            NAME=FOX-1B\n\
+            NICKNAME=AO-91\n\
+            TLE1=1 43017U 17073E   24001.50000000  .00000000  00000-0  00000-0 0  9990\n\
+            TLE2=2 43017  97.6000 100.0000 0010000  90.0000 270.0000 14.80000000 10000\n";
+        let details = parse_sat(sat).unwrap();
+        assert_eq!(details.name, "FOX-1B");
+        assert!(details.tle_line1.starts_with("1 43017U"));
+
+        assert!(parse_sat("[Other]\nNAME=X\n").is_none());
+    }
+
+    #[test]
+    fn test_parse_trsp() {
+        let trsp = "[Mode U/v FM]\n\
+            UP_LOW=435250000\n\
+            DOWN_LOW=145960000\n\
+            MODE=FM\n\
+            \n\
+            [Linear]\n\
+            UP_LOW=145900000\n\
+            DOWN_LOW=435900000\n\
+            MODE=USB\n\
+            INVERT=true\n";
+        let transponders = parse_trsp(trsp, "AO-91");
+        assert_eq!(transponders.len(), 2);
+        assert_eq!(transponders[0].description, "Mode U/v FM");
+        assert_eq!(transponders[0].downlink_mhz, Some(145.96));
+        assert!(!transponders[0].inverting);
+        assert!(transponders[1].inverting);
+        assert_eq!(transponders[1].source, "gpredict");
+    }
+
+    #[test]
+    fn test_trsp_round_trip() {
+        let fm = Transponder {
+            satellite_name: "AO-91".to_string(),
+            description: "FM".to_string(),
+            downlink_mhz: Some(145.96),
+            uplink_mhz: Some(435.25),
+            downlink_mode: Some("FM".to_string()),
+            ..Default::default()
+        };
+        let text = format_trsp(&[fm.clone(), fm]);
+        assert!(text.contains("[FM (2)]"));
+        assert!(text.contains("DOWN_LOW=145960000"));
+
+        let parsed = parse_trsp(&text, "AO-91");
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].uplink_mhz, Some(435.25));
+        assert_eq!(parsed[0].downlink_mode.as_deref(), Some("FM"));
+    }
+
+    #[test]
+    fn test_export_falls_back_to_detail_frequencies() {
+        let mut details = SatelliteDetails::new("ISS (ZARYA)".to_string());
+        assert!(export_transponders(&details, &[]).is_empty());
+
+        details.downlink_frequency_mhz = Some(145.8);
+        let exported = export_transponders(&details, &[]);
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].downlink_mhz, Some(145.8));
+    }
+
+    #[test]
+    fn test_parse_mod() {
+        let module = "[GLOBAL]\nLAYOUT=1;2;3\nSATELLITES=25544;43017;\n";
+        assert_eq!(parse_mod(module), vec![25544, 43017]);
+        assert!(parse_mod("[GLOBAL]\n").is_empty());
+    }
+}
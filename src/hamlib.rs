@@ -0,0 +1,74 @@
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+const TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Line-oriented connection to a Hamlib daemon (rotctld or rigctld)
+pub struct HamlibConnection {
+    daemon: &'static str,
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl HamlibConnection {
+    pub fn connect(daemon: &'static str, host: &str, port: u16) -> Result<Self> {
+        let address = (host, port)
+            .to_socket_addrs()?
+            .next()
+            .with_context(|| format!("resolving {}:{}", host, port))?;
+        let stream = TcpStream::connect_timeout(&address, TIMEOUT)
+            .with_context(|| format!("connecting to {} at {}:{}", daemon, host, port))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        let reader = BufReader::new(stream.try_clone()?);
+        Ok(Self {
+            daemon,
+            stream,
+            reader,
+        })
+    }
+
+    pub fn send(&mut self, command: &str) -> Result<()> {
+        writeln!(self.stream, "{}", command)?;
+        Ok(())
+    }
+
+    pub fn read_line(&mut self) -> Result<String> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(anyhow::anyhow!("{} closed the connection", self.daemon));
+        }
+        Ok(line.trim().to_string())
+    }
+
+    /// Send a set command and check for the `RPRT 0` acknowledgement
+    pub fn command(&mut self, command: &str) -> Result<()> {
+        self.send(command)?;
+        match self.read_line()?.as_str() {
+            "RPRT 0" => Ok(()),
+            reply => Err(anyhow::anyhow!(
+                "{} rejected '{}': {}",
+                self.daemon,
+                command,
+                reply
+            )),
+        }
+    }
+
+    /// Read a multi-line reply until a `RPRT` line or the daemon goes quiet
+    pub fn read_block(&mut self) -> Result<Vec<String>> {
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) if line.starts_with("RPRT") => break,
+                Ok(_) => lines.push(line.trim_end().to_string()),
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(lines)
+    }
+}
@@ -0,0 +1,333 @@
+//! Home Assistant sensors over MQTT. Each tracked satellite gets a binary
+//! sensor that is on while it is above the horizon mask and a sensor with the
+//! minutes to its next AOS, announced through MQTT discovery so they appear
+//! in Home Assistant without any YAML. Only what that needs of MQTT 3.1.1 is
+//! spoken: connect, and retained publishes at QoS 0.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+
+use crate::config::HomeAssistantConfig;
+use crate::satellite::{slug, Satellite, SatellitePosition};
+
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// MQTT strings and binary fields: a two-byte length, then the bytes
+fn put_string(packet: &mut Vec<u8>, text: &[u8]) {
+    packet.extend_from_slice(&(text.len() as u16).to_be_bytes());
+    packet.extend_from_slice(text);
+}
+
+/// A control packet with its remaining length encoded seven bits at a time
+fn packet(kind: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![kind];
+    let mut length = body.len();
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+    packet.extend_from_slice(body);
+    packet
+}
+
+/// CONNECT with a clean session, no keep-alive, and a retained last will
+/// marking the sensors unavailable if crabtrack goes away
+fn connect_packet(config: &HomeAssistantConfig) -> Vec<u8> {
+    let mut flags = 0x02 | 0x04 | 0x20; // clean session, will, will retain
+    if config.username.is_some() {
+        flags |= 0x80;
+    }
+    if config.password.is_some() {
+        flags |= 0x40;
+    }
+    let mut body = Vec::new();
+    put_string(&mut body, b"MQTT");
+    body.extend_from_slice(&[4, flags, 0, 0]);
+    put_string(&mut body, config.client_id.as_bytes());
+    put_string(&mut body, availability_topic(config).as_bytes());
+    put_string(&mut body, b"offline");
+    for field in [&config.username, &config.password].into_iter().flatten() {
+        put_string(&mut body, field.as_bytes());
+    }
+    packet(0x10, &body)
+}
+
+/// Retained PUBLISH at QoS 0
+fn publish_packet(topic: &str, payload: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    put_string(&mut body, topic.as_bytes());
+    body.extend_from_slice(payload);
+    packet(0x31, &body)
+}
+
+fn availability_topic(config: &HomeAssistantConfig) -> String {
+    format!("{}/status", config.topic_prefix)
+}
+
+/// Discovery topics and payloads for one satellite's two sensors. Topics and
+/// ids come from the TLE name, so giving the satellite an alias later only
+/// renames the sensors.
+pub fn discovery(config: &HomeAssistantConfig, name: &str, alias: &str) -> Vec<(String, Value)> {
+    let id = slug(name);
+    let base = format!("{}/{}", config.topic_prefix, id);
+    let device = json!({
+        "identifiers": [config.client_id],
+        "name": "Crabtrack",
+        "manufacturer": "Crabtrack",
+    });
+    vec![
+        (
+            format!(
+                "{}/binary_sensor/{}_{}_visible/config",
+                config.discovery_prefix, config.client_id, id
+            ),
+            json!({
+                "name": format!("{} visible", alias),
+                "unique_id": format!("{}_{}_visible", config.client_id, id),
+                "state_topic": format!("{}/visible", base),
+                "payload_on": "ON",
+                "payload_off": "OFF",
+                "availability_topic": availability_topic(config),
+                "icon": "mdi:satellite-variant",
+                "device": device,
+            }),
+        ),
+        (
+            format!(
+                "{}/sensor/{}_{}_next_pass/config",
+                config.discovery_prefix, config.client_id, id
+            ),
+            json!({
+                "name": format!("{} next pass", alias),
+                "unique_id": format!("{}_{}_next_pass", config.client_id, id),
+                "state_topic": format!("{}/next_pass_minutes", base),
+                "unit_of_measurement": "min",
+                "json_attributes_topic": format!("{}/next_pass", base),
+                "availability_topic": availability_topic(config),
+                "icon": "mdi:timer-outline",
+                "device": device,
+            }),
+        ),
+    ]
+}
+
+/// State topics and payloads for one satellite: whether it is up, minutes to
+/// its next AOS (0 during a pass), and that pass's details as attributes
+pub fn states(
+    config: &HomeAssistantConfig,
+    satellite: &Satellite,
+    position: Option<&SatellitePosition>,
+    now: DateTime<Utc>,
+) -> Vec<(String, String)> {
+    let base = format!("{}/{}", config.topic_prefix, slug(&satellite.name));
+    let visible = position.is_some_and(|position| position.is_visible);
+    let mut states = vec![(
+        format!("{}/visible", base),
+        if visible { "ON" } else { "OFF" }.to_string(),
+    )];
+
+    let pass = satellite.passes.iter().find(|pass| pass.los_time > now);
+    let minutes = pass.map_or(String::new(), |pass| {
+        let seconds = (pass.aos_time - now).num_seconds().max(0);
+        ((seconds + 59) / 60).to_string()
+    });
+    let attributes = pass.map_or(json!({}), |pass| {
+        json!({
+            "aos": pass.aos_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "los": pass.los_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "max_elevation": (pass.max_elevation * 10.0).round() / 10.0,
+            "good": pass.max_elevation >= config.good_pass_elevation,
+        })
+    });
+    states.push((format!("{}/next_pass_minutes", base), minutes));
+    states.push((format!("{}/next_pass", base), attributes.to_string()));
+    states
+}
+
+fn connect(config: &HomeAssistantConfig) -> Result<TcpStream> {
+    let address = (config.host.as_str(), config.port)
+        .to_socket_addrs()?
+        .next()
+        .with_context(|| format!("resolving {}", config.host))?;
+    let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)
+        .with_context(|| format!("connecting to MQTT broker at {}", address))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    stream.write_all(&connect_packet(config))?;
+
+    let mut connack = [0u8; 4];
+    stream.read_exact(&mut connack)?;
+    match connack {
+        [0x20, 2, _, 0] => Ok(stream),
+        [0x20, 2, _, 4 | 5] => bail!("the MQTT broker refused the username or password"),
+        [0x20, 2, _, code] => bail!("the MQTT broker refused the connection ({})", code),
+        _ => bail!("unexpected reply from the MQTT broker"),
+    }
+}
+
+/// Send `packets`, connecting first when `stream` is None
+fn publish(
+    config: &HomeAssistantConfig,
+    stream: &mut Option<TcpStream>,
+    packets: &[Vec<u8>],
+) -> Result<()> {
+    if stream.is_none() {
+        *stream = Some(connect(config)?);
+    }
+    let stream = stream.as_mut().context("not connected")?;
+    for packet in packets {
+        stream.write_all(packet)?;
+    }
+    stream.flush()?;
+    Ok(())
+}
+
+/// Result slot shared with a background publish: the connection to keep, if
+/// it is still good, and the number of satellites published
+type PublishResult = Arc<Mutex<Option<(Option<TcpStream>, Result<usize, String>)>>>;
+
+/// Keeps a broker connection open and publishes the sensors every interval,
+/// each time from a background thread so a slow broker never holds up the UI
+pub struct HomeAssistant {
+    pub config: HomeAssistantConfig,
+    pub status: String,
+    stream: Option<TcpStream>, // None while disconnected or lent to a publish
+    pending: Option<PublishResult>,
+    last_publish: Option<DateTime<Utc>>,
+}
+
+impl HomeAssistant {
+    pub fn new(config: HomeAssistantConfig) -> Self {
+        Self {
+            config,
+            status: "Not connected".to_string(),
+            stream: None,
+            pending: None,
+            last_publish: None,
+        }
+    }
+
+    /// Take the result of the last publish, then start publishing every
+    /// satellite's state once the interval is up, connecting and sending
+    /// discovery first when not connected
+    pub fn update(
+        &mut self,
+        satellites: &[Satellite],
+        positions: &[SatellitePosition],
+        aliases: &HashMap<String, String>,
+        now: DateTime<Utc>,
+    ) {
+        if let Some((stream, result)) = self.pending.as_ref().and_then(|p| p.lock().unwrap().take())
+        {
+            self.pending = None;
+            self.stream = stream;
+            self.status = match result {
+                Ok(count) => format!("Published {} satellites", count),
+                Err(e) => format!("Error: {}", e),
+            };
+        }
+
+        let interval = Duration::seconds(self.config.interval_seconds.max(1) as i64);
+        if self.pending.is_some() || self.last_publish.is_some_and(|last| now - last < interval) {
+            return;
+        }
+        self.last_publish = Some(now);
+
+        let mut packets = Vec::new();
+        if self.stream.is_none() {
+            for satellite in satellites {
+                let alias = aliases.get(&satellite.name).unwrap_or(&satellite.name);
+                for (topic, payload) in discovery(&self.config, &satellite.name, alias) {
+                    packets.push(publish_packet(&topic, payload.to_string().as_bytes()));
+                }
+            }
+            packets.push(publish_packet(&availability_topic(&self.config), b"online"));
+        }
+        for satellite in satellites {
+            let position = positions.iter().find(|pos| pos.name == satellite.name);
+            for (topic, payload) in states(&self.config, satellite, position, now) {
+                packets.push(publish_packet(&topic, payload.as_bytes()));
+            }
+        }
+
+        let slot: PublishResult = Arc::new(Mutex::new(None));
+        self.pending = Some(Arc::clone(&slot));
+        let config = self.config.clone();
+        let mut stream = self.stream.take();
+        let count = satellites.len();
+        std::thread::spawn(move || {
+            let result = publish(&config, &mut stream, &packets).map_err(|e| format!("{:#}", e));
+            if result.is_err() {
+                stream = None;
+            }
+            *slot.lock().unwrap() = Some((stream, result.map(|()| count)));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_home_assistant_mqtt() {
+        assert_eq!(slug("ISS (ZARYA)"), "iss_zarya");
+        assert_eq!(slug("AO-91"), "ao_91");
+        // Lengths of 128 and over take a second byte
+        assert_eq!(&packet(0x30, &[0; 200])[..3], &[0x30, 0xc8, 0x01]);
+
+        let config: HomeAssistantConfig = toml::from_str("port = 0").unwrap();
+        let (topic, payload) = &discovery(&config, "ISS (ZARYA)", "ISS")[1];
+        assert_eq!(topic, "homeassistant/sensor/crabtrack_iss_zarya_next_pass/config");
+        assert_eq!(payload["state_topic"], "crabtrack/iss_zarya/next_pass_minutes");
+        assert_eq!(payload["name"], "ISS next pass");
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut connect = [0u8; 2];
+            stream.read_exact(&mut connect).unwrap();
+            let mut body = vec![0u8; connect[1] as usize];
+            stream.read_exact(&mut body).unwrap();
+            stream.write_all(&[0x20, 2, 0, 0]).unwrap();
+            let mut published = Vec::new();
+            stream.read_to_end(&mut published).unwrap();
+            (connect[0], body, published)
+        });
+
+        let config = HomeAssistantConfig {
+            port,
+            host: "127.0.0.1".to_string(),
+            ..config
+        };
+        let mut home_assistant = HomeAssistant::new(config);
+        let now = Utc::now();
+        home_assistant.update(&[], &[], &HashMap::new(), now);
+        // The publish finishes in the background and is picked up by a later update
+        while home_assistant.pending.is_some() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            home_assistant.update(&[], &[], &HashMap::new(), now);
+        }
+        assert_eq!(home_assistant.status, "Published 0 satellites");
+        drop(home_assistant);
+
+        let (kind, body, published) = broker.join().unwrap();
+        assert_eq!(kind, 0x10);
+        assert_eq!(&body[..8], b"\0\x04MQTT\x04\x26");
+        assert_eq!(published, publish_packet("crabtrack/status", b"online"));
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// The rows of a CSV profile with their line numbers, for error messages.
+/// Blank lines, `#` comments, and a non-numeric header row are skipped; the
+/// flag marks the first row left, which is the only one taken for a header.
+pub fn csv_rows(contents: &str) -> impl Iterator<Item = (usize, bool, &str)> {
+    contents
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .enumerate()
+        .map(|(row, (index, line))| (index + 1, row == 0, line))
+}
+
+/// A quantity that varies with azimuth, such as the height of the horizon or
+/// the noise floor, given at a few azimuths and interpolated between them
+#[derive(Debug, Clone, Default)]
+pub struct AzimuthProfile {
+    points: Vec<(f64, f64)>, // (azimuth, value), sorted by azimuth
+}
+
+impl AzimuthProfile {
+    /// Build a profile from (azimuth, value) pairs, azimuth in degrees
+    pub fn from_points(mut points: Vec<(f64, f64)>) -> Self {
+        for point in points.iter_mut() {
+            point.0 = point.0.rem_euclid(360.0);
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { points }
+    }
+
+    /// Parse CSV with `azimuth,<column>` rows, as read by `csv_rows`
+    pub fn parse_csv(contents: &str, column: &str) -> Result<Self> {
+        let mut points = Vec::new();
+
+        for (line_no, first, line) in csv_rows(contents) {
+            let mut fields = line.split(',').map(str::trim);
+            let (Some(az), Some(value)) = (fields.next(), fields.next()) else {
+                return Err(anyhow::anyhow!(
+                    "line {}: expected 'azimuth,{}'",
+                    line_no,
+                    column
+                ));
+            };
+
+            match (az.parse::<f64>(), value.parse::<f64>()) {
+                (Ok(az), Ok(value)) => points.push((az, value)),
+                _ if first => continue, // header row
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "line {}: invalid azimuth/{} '{}'",
+                        line_no,
+                        column,
+                        line
+                    ))
+                }
+            }
+        }
+
+        Ok(Self::from_points(points))
+    }
+
+    /// The profile as CSV with an `azimuth,<column>` header, in the format
+    /// `parse_csv` reads
+    pub fn to_csv(&self, column: &str) -> String {
+        let mut csv = format!("azimuth,{}\n", column);
+        for (azimuth, value) in &self.points {
+            csv.push_str(&format!("{:.1},{:.1}\n", azimuth, value));
+        }
+        csv
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Set the value at `azimuth`, replacing an earlier point within half a
+    /// degree of it
+    pub fn record(&mut self, azimuth: f64, value: f64) {
+        let azimuth = azimuth.rem_euclid(360.0);
+        self.points.retain(|point| {
+            let apart = (point.0 - azimuth).abs();
+            apart.min(360.0 - apart) >= 0.5
+        });
+        self.points.push((azimuth, value));
+        self.points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    /// Value at the given azimuth, linearly interpolated between profile
+    /// points and wrapping through north; `None` for an empty profile
+    pub fn value_at(&self, azimuth: f64) -> Option<f64> {
+        match self.points.len() {
+            0 => None,
+            1 => Some(self.points[0].1),
+            len => {
+                let az = azimuth.rem_euclid(360.0);
+                let upper = self.points.iter().position(|p| p.0 >= az).unwrap_or(len);
+                let (a, b) = if upper == 0 || upper == len {
+                    // Between the last and first points, across 0°/360°
+                    let last = self.points[len - 1];
+                    let first = self.points[0];
+                    ((last.0 - 360.0, last.1), first)
+                } else {
+                    (self.points[upper - 1], self.points[upper])
+                };
+                let az = if az > b.0 { az - 360.0 } else { az };
+                let span = b.0 - a.0;
+                if span <= 0.0 {
+                    return Some(b.1);
+                }
+                Some(a.1 + (b.1 - a.1) * (az - a.0) / span)
+            }
+        }
+    }
+}
+
+/// Observer horizon profile: minimum usable elevation as a function of azimuth
+#[derive(Debug, Clone, Default)]
+pub struct HorizonMask {
+    profile: AzimuthProfile, // minimum elevation by azimuth
+}
+
+impl HorizonMask {
+    /// Build a mask from (azimuth, elevation) pairs in degrees
+    pub fn from_points(points: Vec<(f64, f64)>) -> Self {
+        Self {
+            profile: AzimuthProfile::from_points(points),
+        }
+    }
+
+    /// Load a mask from a CSV file with `azimuth,elevation` rows
+    pub fn load_csv<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("reading horizon file '{}'", path.display()))?;
+        Self::parse_csv(&contents)
+    }
+
+    fn parse_csv(contents: &str) -> Result<Self> {
+        Ok(Self {
+            profile: AzimuthProfile::parse_csv(contents, "elevation")?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profile.is_empty()
+    }
+
+    /// Minimum usable elevation at the given azimuth; 0° without a profile
+    pub fn min_elevation_at(&self, azimuth: f64) -> f64 {
+        self.profile.value_at(azimuth).unwrap_or(0.0)
+    }
+
+    /// Whether a direction clears the horizon profile
+    pub fn is_clear(&self, azimuth: f64, elevation: f64) -> bool {
+        elevation > self.min_elevation_at(azimuth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_mask() {
+        let mask = HorizonMask::default();
+        assert_eq!(mask.min_elevation_at(123.0), 0.0);
+        assert!(mask.is_clear(0.0, 0.5));
+        assert!(!mask.is_clear(0.0, -0.5));
+    }
+
+    #[test]
+    fn test_interpolation_and_wrap() {
+        let mask = HorizonMask::from_points(vec![(0.0, 10.0), (90.0, 30.0), (270.0, 0.0)]);
+        assert!((mask.min_elevation_at(45.0) - 20.0).abs() < 1e-9);
+        assert!((mask.min_elevation_at(180.0) - 15.0).abs() < 1e-9);
+        // Between 270° (0°) and 360° (10°)
+        assert!((mask.min_elevation_at(315.0) - 5.0).abs() < 1e-9);
+        assert!((mask.min_elevation_at(-45.0) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "azimuth,elevation\n# trees to the north\n0,12.5\n180, 3\n\n";
+        let mask = HorizonMask::parse_csv(csv).unwrap();
+        assert!((mask.min_elevation_at(0.0) - 12.5).abs() < 1e-9);
+        assert!((mask.min_elevation_at(180.0) - 3.0).abs() < 1e-9);
+
+        assert!(HorizonMask::parse_csv("0,5\nnorth,ten\n").is_err());
+    }
+
+    #[test]
+    fn test_header_after_comments() {
+        let csv = "# Horizon surveyed from the back garden\n\nazimuth,elevation\n90,8\n";
+        let mask = HorizonMask::parse_csv(csv).unwrap();
+        assert!((mask.min_elevation_at(90.0) - 8.0).abs() < 1e-9);
+
+        // Only the first row can be a header
+        let error = HorizonMask::parse_csv("# comment\n90,8\nazimuth,elevation\n").unwrap_err();
+        assert!(error.to_string().starts_with("line 3:"));
+    }
+
+    #[test]
+    fn test_record_and_csv() {
+        let mut profile = AzimuthProfile::from_points(vec![(0.0, -120.0), (270.0, -104.0)]);
+        profile.record(-89.75, -100.0);
+        profile.record(180.0, -115.0);
+        assert_eq!(profile.points().len(), 3);
+        assert_eq!(profile.value_at(270.25), Some(-100.0));
+
+        let csv = profile.to_csv("noise_db");
+        assert!(csv.starts_with("azimuth,noise_db\n"));
+        let reloaded = AzimuthProfile::parse_csv(&csv, "noise_db").unwrap();
+        assert_eq!(reloaded.points().len(), 3);
+        assert_eq!(reloaded.value_at(180.0), Some(-115.0));
+        assert_eq!(AzimuthProfile::default().value_at(90.0), None);
+    }
+}
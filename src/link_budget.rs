@@ -0,0 +1,370 @@
+//! Losses on the path between a satellite and the station: free-space
+//! spreading plus absorption by oxygen and water vapour, which is negligible
+//! on 2 m and 70 cm but costs several dB at 10 and 24 GHz when the path runs
+//! low through the atmosphere. Absorption follows the simplified clear-air
+//! model of ITU-R P.676 (Annex 2) for a standard atmosphere at sea level.
+//! The station's antenna adds its gain toward the satellite and whatever is
+//! lost to a polarization that doesn't match the downlink's, which on VHF
+//! keeps turning with Faraday rotation in the ionosphere. What arrives is
+//! weighed against the noise of the sky and the receiver in a narrow SSB/CW
+//! bandwidth to give the link margin.
+
+use crate::config::Polarization;
+use crate::satellite::EARTH_RADIUS_KM;
+
+/// Effective Earth radius for the low-elevation path (km)
+const EFFECTIVE_EARTH_RADIUS_KM: f64 = 8500.0;
+
+/// Surface water vapour density of the standard atmosphere (g/m³)
+const WATER_VAPOUR_DENSITY: f64 = 7.5;
+
+/// Equivalent height of the oxygen layer (km)
+const OXYGEN_HEIGHT_KM: f64 = 6.0;
+
+/// Rough component of the Earth's magnetic field along the path (T)
+const FIELD_ALONG_PATH_T: f64 = 4e-5;
+
+/// Height of the ionosphere taken as a thin shell (km)
+const IONOSPHERE_HEIGHT_KM: f64 = 350.0;
+
+/// How well an antenna rejects the opposite circular sense in practice (dB)
+const CROSS_POLARIZATION_DB: f64 = 20.0;
+
+/// Loss between linear and circular polarization (dB)
+const LINEAR_CIRCULAR_DB: f64 = 3.0;
+
+/// Boltzmann's constant (dBW/K/Hz)
+const BOLTZMANN_DBW: f64 = -228.6;
+
+/// Bandwidth the link margin is worked out in: SSB or CW (Hz)
+const RECEIVE_BANDWIDTH_HZ: f64 = 3000.0;
+
+/// Signal-to-noise ratio a contact needs in that bandwidth (dB)
+const REQUIRED_SNR_DB: f64 = 6.0;
+
+/// Noise temperature of a good preamp and feedline (K)
+const RECEIVER_NOISE_K: f64 = 100.0;
+
+/// Galactic noise seen from the ground on 2 m, falling off steeply above (K)
+const SKY_NOISE_2M_K: f64 = 1000.0;
+
+/// Gain assumed for an antenna that isn't configured, below 1 GHz: a small
+/// handheld yagi (dBi)
+const TYPICAL_VHF_UHF_GAIN_DBI: f64 = 6.0;
+
+/// Diameter of the dish assumed from 1 GHz up (m)
+const TYPICAL_DISH_M: f64 = 0.6;
+
+/// An antenna's gain toward a satellite, next to the best it can do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AntennaGain {
+    pub toward_dbi: f64,
+    pub peak_dbi: f64,
+}
+
+impl AntennaGain {
+    /// Gain lost by the satellite not being where the antenna is best
+    pub fn pointing_loss_db(&self) -> f64 {
+        (self.peak_dbi - self.toward_dbi).max(0.0)
+    }
+}
+
+impl Polarization {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Polarization::Linear => "linear",
+            Polarization::Rhcp => "RHCP",
+            Polarization::Lhcp => "LHCP",
+        }
+    }
+}
+
+/// Loss from the antenna and the downlink not sharing a polarization
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolarizationMismatch {
+    pub antenna: Polarization,
+    pub satellite: Polarization,
+    pub faraday_rotation_deg: f64,
+    pub loss_db: f64,
+}
+
+impl PolarizationMismatch {
+    pub fn new(antenna: Polarization, satellite: Polarization, faraday_rotation_deg: f64) -> Self {
+        use Polarization::*;
+        let loss_db = match (antenna, satellite) {
+            // Small enough to keep an antenna lined up with the satellite's
+            (Linear, Linear) if faraday_rotation_deg < 45.0 => {
+                -20.0 * faraday_rotation_deg.to_radians().cos().log10()
+            }
+            // The plane keeps turning through the pass, fading in and out
+            // around half the power
+            (Linear, _) | (_, Linear) => LINEAR_CIRCULAR_DB,
+            (antenna, satellite) if antenna == satellite => 0.0,
+            _ => CROSS_POLARIZATION_DB,
+        };
+        Self {
+            antenna,
+            satellite,
+            faraday_rotation_deg,
+            loss_db,
+        }
+    }
+
+    /// What to switch the antenna to, when that would help
+    pub fn suggestion(&self) -> Option<String> {
+        use Polarization::*;
+        match (self.antenna, self.satellite) {
+            (Linear, Linear) if self.loss_db >= LINEAR_CIRCULAR_DB => Some(format!(
+                "Faraday rotation {:.0}°: consider switching to circular to ride out fades",
+                self.faraday_rotation_deg
+            )),
+            (Linear, Rhcp | Lhcp) | (Rhcp, Lhcp) | (Lhcp, Rhcp) => Some(format!(
+                "Downlink is {}: consider switching polarization",
+                self.satellite.as_str()
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Faraday rotation of the downlink's polarization in the ionosphere
+/// (degrees), roughly: a fixed field along the path, the vertical TEC in
+/// units of 10^16 electrons/m² stretched over the slant path through a thin
+/// shell, and only the part of the ionosphere below the satellite
+pub fn faraday_rotation_deg(
+    frequency_mhz: f64,
+    elevation: f64,
+    altitude_km: f64,
+    tec_units: f64,
+) -> f64 {
+    let shell = EARTH_RADIUS_KM * elevation.max(0.0).to_radians().cos()
+        / (EARTH_RADIUS_KM + IONOSPHERE_HEIGHT_KM);
+    let slant = 1.0 / (1.0 - shell * shell).sqrt();
+    let below = 1.0 - (-(altitude_km - 100.0).max(0.0) / 300.0).exp();
+    let electrons = tec_units * 1e16 * slant * below;
+    let frequency_hz = frequency_mhz * 1e6;
+    (2.36e4 * FIELD_ALONG_PATH_T * electrons / (frequency_hz * frequency_hz)).to_degrees()
+}
+
+/// What the station's antenna and the noise around it add to a link besides
+/// the path itself
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StationLink {
+    pub antenna_gain: Option<AntennaGain>, // when the antenna's gain is known
+    pub polarization: Option<PolarizationMismatch>, // when the antenna's polarization is known
+    pub noise_rise_db: f64, // noise floor toward the satellite above the quietest direction
+}
+
+impl StationLink {
+    pub fn pointing_loss_db(&self) -> f64 {
+        self.antenna_gain.map_or(0.0, |gain| gain.pointing_loss_db())
+    }
+
+    pub fn polarization_loss_db(&self) -> f64 {
+        self.polarization.map_or(0.0, |mismatch| mismatch.loss_db)
+    }
+}
+
+/// Gain of an antenna typical for `frequency_mhz`, when the station's isn't
+/// known: a handheld yagi on VHF and UHF, or a small dish with 55% efficiency
+pub fn typical_gain_dbi(frequency_mhz: f64) -> f64 {
+    if frequency_mhz < 1000.0 {
+        return TYPICAL_VHF_UHF_GAIN_DBI;
+    }
+    let wavelength_m = 299.792458 / frequency_mhz;
+    10.0 * (0.55 * (std::f64::consts::PI * TYPICAL_DISH_M / wavelength_m).powi(2)).log10()
+}
+
+/// System noise temperature at `frequency_mhz` (K): the receiver plus the
+/// sky, which dominates on 2 m
+pub fn system_noise_k(frequency_mhz: f64) -> f64 {
+    RECEIVER_NOISE_K + SKY_NOISE_2M_K * (145.8 / frequency_mhz).powf(2.5)
+}
+
+/// How far a downlink is above what it takes to work it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkMargin {
+    pub signal_dbw: f64, // at the receiver
+    pub noise_dbw: f64,  // in the receive bandwidth, with the local noise toward the satellite
+    pub margin_db: f64,  // signal-to-noise ratio beyond what a contact needs
+}
+
+impl LinkMargin {
+    /// A satellite radiating `eirp_dbw` toward the station over `path_loss`
+    /// on `frequency_mhz`, received through the station's antenna, or one
+    /// typical for the band when its gain isn't known, less any polarization
+    /// mismatch, over a noise floor raised by local noise in that direction
+    pub fn new(
+        eirp_dbw: f64,
+        path_loss: &PathLoss,
+        frequency_mhz: f64,
+        station: &StationLink,
+    ) -> Self {
+        let gain_dbi = station
+            .antenna_gain
+            .map_or_else(|| typical_gain_dbi(frequency_mhz), |gain| gain.toward_dbi);
+        let signal_dbw =
+            eirp_dbw - path_loss.total_db() + gain_dbi - station.polarization_loss_db();
+        let noise_dbw = BOLTZMANN_DBW
+            + 10.0 * system_noise_k(frequency_mhz).log10()
+            + 10.0 * RECEIVE_BANDWIDTH_HZ.log10()
+            + station.noise_rise_db;
+        Self {
+            signal_dbw,
+            noise_dbw,
+            margin_db: signal_dbw - noise_dbw - REQUIRED_SNR_DB,
+        }
+    }
+}
+
+/// Losses on the path to a satellite at one moment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathLoss {
+    pub free_space_db: f64,
+    pub atmospheric_db: f64,
+}
+
+impl PathLoss {
+    pub fn new(range_km: f64, elevation: f64, frequency_mhz: f64) -> Self {
+        Self {
+            free_space_db: free_space_path_loss_db(range_km, frequency_mhz),
+            atmospheric_db: atmospheric_attenuation_db(elevation, frequency_mhz),
+        }
+    }
+
+    pub fn total_db(&self) -> f64 {
+        self.free_space_db + self.atmospheric_db
+    }
+}
+
+/// Spreading loss between isotropic antennas `range_km` apart
+pub fn free_space_path_loss_db(range_km: f64, frequency_mhz: f64) -> f64 {
+    20.0 * range_km.log10() + 20.0 * frequency_mhz.log10() + 32.45
+}
+
+/// Specific attenuation of dry air and of water vapour at sea level (dB/km)
+fn specific_attenuation(frequency_ghz: f64) -> (f64, f64) {
+    let f = frequency_ghz;
+    let rho = WATER_VAPOUR_DENSITY;
+    let oxygen =
+        (7.19e-3 + 6.09 / (f * f + 0.227) + 4.81 / ((f - 57.0).powi(2) + 1.50)) * f * f * 1e-3;
+    let water = (0.050
+        + 0.0021 * rho
+        + 3.6 / ((f - 22.2).powi(2) + 8.5)
+        + 10.6 / ((f - 183.3).powi(2) + 9.0)
+        + 8.9 / ((f - 325.4).powi(2) + 26.3))
+        * f
+        * f
+        * rho
+        * 1e-4;
+    (oxygen, water)
+}
+
+/// Equivalent height of the water vapour layer (km), raised near its lines
+fn water_vapour_height_km(frequency_ghz: f64) -> f64 {
+    let f = frequency_ghz;
+    1.6 * (1.0
+        + 3.0 / ((f - 22.2).powi(2) + 5.0)
+        + 5.0 / ((f - 183.3).powi(2) + 6.0)
+        + 2.5 / ((f - 325.4).powi(2) + 4.0))
+}
+
+/// Clear-air absorption along the path at `elevation` degrees. Above 10° the
+/// atmosphere is treated as flat layers; below, the Earth's curvature keeps
+/// the path from growing without bound toward the horizon.
+pub fn atmospheric_attenuation_db(elevation: f64, frequency_mhz: f64) -> f64 {
+    // The model covers up to the oxygen lines around 57 GHz
+    let f = (frequency_mhz / 1000.0).min(54.0);
+    let (oxygen, water) = specific_attenuation(f);
+    let water_height = water_vapour_height_km(f);
+    let elevation = elevation.max(0.0).to_radians();
+
+    if elevation > 10f64.to_radians() {
+        return (oxygen * OXYGEN_HEIGHT_KM + water * water_height) / elevation.sin();
+    }
+    let spread = |x: f64| 1.0 / (0.661 * x + 0.339 * (x * x + 5.51).sqrt());
+    let layer = |gamma: f64, height: f64| {
+        gamma
+            * height.sqrt()
+            * spread(elevation.tan() * (EFFECTIVE_EARTH_RADIUS_KM / height).sqrt())
+    };
+    EFFECTIVE_EARTH_RADIUS_KM.sqrt() / elevation.cos()
+        * (layer(oxygen, OXYGEN_HEIGHT_KM) + layer(water, water_height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_loss() {
+        // ISS overhead on 2 m, and QO-100 from Europe on 10 GHz
+        assert!((free_space_path_loss_db(420.0, 145.8) - 128.2).abs() < 0.1);
+        assert!((free_space_path_loss_db(38_000.0, 10_489.75) - 204.5).abs() < 0.1);
+
+        // Zenith absorption: a few hundredths of a dB on VHF, ~0.05 dB at
+        // 10 GHz and ~0.4 dB near the 22 GHz water vapour line
+        assert!(atmospheric_attenuation_db(90.0, 145.8) < 0.05);
+        let x_band = atmospheric_attenuation_db(90.0, 10_489.75);
+        assert!((0.04..0.07).contains(&x_band), "{}", x_band);
+        let k_band = atmospheric_attenuation_db(90.0, 24_048.0);
+        assert!((0.35..0.5).contains(&k_band), "{}", k_band);
+
+        // Lower paths run through more air, but stay finite at the horizon
+        let mut previous = 0.0;
+        for elevation in [90.0, 30.0, 10.5, 10.0, 5.0, 0.0] {
+            let loss = atmospheric_attenuation_db(elevation, 24_048.0);
+            assert!(loss > previous, "{} at {}°", loss, elevation);
+            previous = loss;
+        }
+        assert!(previous < 40.0);
+        // The two models meet at 10°
+        let flat = atmospheric_attenuation_db(10.001, 24_048.0);
+        let curved = atmospheric_attenuation_db(10.0, 24_048.0);
+        assert!((flat - curved).abs() < 0.05, "{} {}", flat, curved);
+        assert!(atmospheric_attenuation_db(5.0, 24_048.0) > 4.0);
+
+        let loss = PathLoss::new(38_000.0, 25.0, 10_489.75);
+        assert!((loss.total_db() - loss.free_space_db - loss.atmospheric_db).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polarization_mismatch() {
+        use Polarization::*;
+
+        // Many turns on 2 m from a LEO satellite, a quarter turn on 70 cm,
+        // next to nothing on 13 cm, and more low in the sky
+        let vhf = faraday_rotation_deg(145.8, 30.0, 420.0, 20.0);
+        assert!((360.0..1440.0).contains(&vhf), "{}", vhf);
+        let uhf = faraday_rotation_deg(435.0, 30.0, 420.0, 20.0);
+        assert!((uhf - vhf * (145.8f64 / 435.0).powi(2)).abs() < 1e-6);
+        assert!(faraday_rotation_deg(2400.0, 30.0, 420.0, 20.0) < 5.0);
+        assert!(faraday_rotation_deg(145.8, 5.0, 420.0, 20.0) > vhf);
+        assert!(faraday_rotation_deg(145.8, 30.0, 35786.0, 20.0) > vhf);
+
+        let mismatch = PolarizationMismatch::new(Linear, Linear, vhf);
+        assert_eq!(mismatch.loss_db, 3.0);
+        assert!(mismatch.suggestion().unwrap().contains("switching to circular"));
+        let mismatch = PolarizationMismatch::new(Linear, Linear, 10.0);
+        assert!((mismatch.loss_db - 0.133).abs() < 0.001);
+        assert_eq!(mismatch.suggestion(), None);
+
+        let mismatch = PolarizationMismatch::new(Linear, Rhcp, vhf);
+        assert_eq!(mismatch.loss_db, 3.0);
+        assert_eq!(
+            mismatch.suggestion().as_deref(),
+            Some("Downlink is RHCP: consider switching polarization")
+        );
+        assert_eq!(PolarizationMismatch::new(Lhcp, Rhcp, 0.0).loss_db, 20.0);
+        assert_eq!(PolarizationMismatch::new(Rhcp, Rhcp, vhf).loss_db, 0.0);
+        // Circular already does what it can for a linear downlink
+        assert_eq!(PolarizationMismatch::new(Rhcp, Linear, vhf).suggestion(), None);
+
+        let station = StationLink {
+            polarization: Some(PolarizationMismatch::new(Lhcp, Rhcp, 0.0)),
+            ..StationLink::default()
+        };
+        assert_eq!(station.pointing_loss_db(), 0.0);
+        assert_eq!(station.polarization_loss_db(), 20.0);
+    }
+}
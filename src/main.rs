@@ -1,19 +1,79 @@
+mod announcer;
+mod antenna;
+mod aprs;
+mod astro;
+mod bandplan;
+mod calendar;
+mod celestrak;
+mod cleanup;
+mod clock;
 mod config;
+mod conjunction;
+mod console;
+mod constellation;
+mod control;
+mod coverage;
+#[cfg(unix)]
+mod daemon;
 mod database;
+mod doctor;
+mod elevation;
+mod frames;
+mod geostationary;
+mod gpredict;
+mod hamlib;
+mod home_assistant;
+mod horizon;
+mod link_budget;
+mod noise;
 mod observer;
+mod occupancy;
+mod orbit_view;
+mod pass_cache;
 mod pass_prediction;
+mod pass_process;
+mod plugins;
 mod radio;
+#[cfg(feature = "recording")]
+mod recorder;
+mod report;
+mod rig;
+mod rotator;
 mod satellite;
+mod satnogs;
+mod schedule;
+mod scheduler;
+mod scripting;
+mod sdr;
+mod skychart;
+mod sync;
+mod telemetry;
+mod text_input;
+mod tle;
+mod tle_diff;
+mod transit;
+mod transponder;
 mod ui;
+mod verify;
+mod waterfall;
 
-use database::{Database, SatelliteDetails};
+use announcer::Announcer;
+use database::{
+    Database, FrequencyOffset, LogStats, OperatingRule, PassLogEntry, PendingLaunch,
+    PointingErrorStats, PositionRecord, RotatorCalibration, SatelliteDetails, SatelliteGroup,
+    TelemetryFrame, Transponder,
+};
 
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
+        EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -21,12 +81,19 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use sgp4::{Constants, Elements, MinutesSinceEpoch};
 use std::fs;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use config::Config;
+use console::{ColorDepth, ConsoleBackend};
 use observer::Observer;
+use pass_cache::PassCache;
 use pass_prediction::{calculate_gmst, calculate_look_angles, SatellitePass};
 use radio::{calculate_doppler_shift, evaluate_communication_window};
+use rig::RigController;
+use sdr::SdrController;
+use rotator::{PassPlan, RotatorController};
+use telemetry::TelemetryDecoder;
+use text_input::TextInput;
 use satellite::{Satellite, SatellitePosition};
 
 #[derive(Parser, Debug)]
@@ -35,8 +102,273 @@ pub struct Args {
     #[arg(short, long, default_value = "config.toml")]
     config: String,
 
+    /// Use the observer, antennas, rotators and rig of a `[[profiles]]` entry,
+    /// and keep using it on later runs
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
     #[arg(short, long)]
     tle: Option<PathBuf>,
+
+    /// Plain labelled text without charts or box drawing, for screen readers
+    /// and braille displays
+    #[arg(long)]
+    accessible: bool,
+
+    /// Draw with ASCII characters only, for consoles without box drawing or
+    /// braille glyphs (such as the legacy Windows console)
+    #[arg(long)]
+    ascii: bool,
+
+    /// Fullscreen wall display: large figures, rotating through the most relevant
+    /// satellites, and no keys but q to quit
+    #[arg(long)]
+    kiosk: bool,
+
+    /// Leave the rotators and rig to a running `crabtrack daemon`, listening on
+    /// SOCKET or its default socket, so closing the terminal doesn't stop them
+    #[arg(long, value_name = "SOCKET", num_args = 0..=1)]
+    attach: Option<Option<PathBuf>>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compare propagation, frame conversion and look angles against reference values
+    Verify,
+    /// Check the configuration, TLE file, database, network, rotctld and rigctld
+    /// connections and terminal, and print what passed and what failed
+    Doctor,
+    /// List passes between two times, e.g. --from "2026-04-21 18:00" --to "2026-04-21 23:00"
+    Passes {
+        /// Start of the window: RFC 3339, or local "YYYY-MM-DD HH:MM" or "YYYY-MM-DD"
+        #[arg(long)]
+        from: String,
+        /// End of the window, in the same formats
+        #[arg(long)]
+        to: String,
+        /// Only list passes of this satellite
+        #[arg(long)]
+        satellite: Option<String>,
+        /// List the passes for this observer instead, and compare several when
+        /// repeated: a `[[profiles]]` name, or "NAME=LAT,LON" or "NAME=GRID"
+        #[arg(long, value_name = "OBSERVER")]
+        observer: Vec<String>,
+    },
+    /// Write a report of every pass over the next few days, grouped by day,
+    /// for posting to a club mailing list or web page
+    Report {
+        /// Number of days to cover, starting now
+        #[arg(long, default_value_t = 7)]
+        days: i64,
+        #[arg(long, value_enum, default_value_t = report::ReportFormat::Text)]
+        format: report::ReportFormat,
+        /// Highlight passes reaching at least this elevation, in degrees
+        #[arg(long, default_value_t = 60.0)]
+        high_elevation: f64,
+        /// Write the report to this file instead of standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Save a polar sky chart of a satellite's next pass, for observation plans
+    Chart {
+        /// Satellite to chart
+        #[arg(long)]
+        satellite: String,
+        /// Chart the first pass after this time rather than now, in the same formats as `passes`
+        #[arg(long)]
+        from: Option<String>,
+        /// File to write: PNG for a .png name, SVG otherwise
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+    },
+    /// Write the Doppler curve expected on a satellite's downlink through a pass,
+    /// for overlaying on the waterfall of a recording
+    Doppler {
+        /// Satellite whose pass to use
+        #[arg(long)]
+        satellite: String,
+        /// When the recording starts, in the same formats as `passes`: the curve
+        /// covers the pass under way then, or the next one, from this time on
+        #[arg(long)]
+        from: Option<String>,
+        /// Downlink in MHz, instead of the configured one
+        #[arg(long)]
+        frequency: Option<f64>,
+        /// Seconds between points
+        #[arg(long, default_value_t = 1.0)]
+        step: f64,
+        /// File to write: JSON for a .json name, CSV otherwise
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+    },
+    /// Screen the tracked satellites against each other for close approaches,
+    /// such as a cubesat against debris in a similar orbit
+    Conjunctions {
+        /// Number of days to screen, starting now
+        #[arg(long, default_value_t = 3)]
+        days: i64,
+        /// Report approaches closer than this, in km
+        #[arg(long, default_value_t = 10.0)]
+        distance: f64,
+        /// Only screen this satellite against the others
+        #[arg(long)]
+        satellite: Option<String>,
+    },
+    /// Show the station's noise profile, or record the noise floor toward
+    /// --azimuth in the noise_file set under [observer]
+    Noise {
+        /// Direction the antenna was pointed, in degrees
+        #[arg(long, requires = "noise_reading", allow_negative_numbers = true)]
+        azimuth: Option<f64>,
+        /// Noise floor in dB, as read off the receiver
+        #[arg(
+            long,
+            group = "noise_reading",
+            requires = "azimuth",
+            allow_negative_numbers = true
+        )]
+        level: Option<f64>,
+        /// An rtl_power or hackrf_sweep CSV scan made pointing that way; its
+        /// median bin is taken as the noise floor
+        #[arg(long, value_name = "FILE", group = "noise_reading", requires = "azimuth")]
+        scan: Option<PathBuf>,
+    },
+    /// List, add or remove operating schedule rules, which say when and where a
+    /// satellite's transponder is on; alerts skip passes when it is known to be off
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
+    },
+    /// Merge satellite details and transponders with the club list set under
+    /// [sync], keeping whichever side changed each satellite last
+    Sync,
+    /// Manage the Earth orientation parameters used with precise_frames
+    Eop {
+        #[command(subcommand)]
+        action: EopAction,
+    },
+    /// Look inside the database
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
+    /// Bring satellites and transponders into the database from another program's files
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+    /// Write the database's satellites and transponders in another program's format
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
+    },
+    /// List database satellites whose elements are older than --days or that the
+    /// TLE file doesn't list, and refresh, archive or purge them
+    Cleanup {
+        /// Elements older than this many days are stale
+        #[arg(long, default_value_t = 30.0)]
+        days: f64,
+        /// Replace stale elements with newer ones from the TLE file or Celestrak
+        #[arg(long, group = "cleanup_action")]
+        refresh: bool,
+        /// Move the listed satellites to the archive, from where --restore brings them back
+        #[arg(long, group = "cleanup_action")]
+        archive: bool,
+        /// Delete the listed satellites
+        #[arg(long, group = "cleanup_action")]
+        purge: bool,
+        /// Bring an archived satellite back
+        #[arg(long, value_name = "NAME", group = "cleanup_action")]
+        restore: Option<String>,
+    },
+    /// Print pass events (alerts, AOS, LOS, elevation milestones) one per line
+    /// instead of starting the TUI, for screen readers and braille displays
+    Events,
+    /// Take JSON-RPC requests (select, track, next_pass...) one per line and
+    /// send replies and pass events back, for automation
+    Control {
+        /// Listen on this unix socket instead of stdin and stdout
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
+    /// Keep predicting passes and driving the rotators and rig in the
+    /// background, serving the control methods on a unix socket for the TUI
+    /// (with --attach) and automation
+    Daemon {
+        /// Socket to listen on, by default daemon.sock next to the database
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+        /// Also serve iCalendar feeds of the passes over HTTP, e.g. 0.0.0.0:8073
+        #[arg(long, value_name = "ADDRESS")]
+        calendar: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ScheduleAction {
+    /// List every rule
+    List,
+    /// Add a rule: the satellite is on when its days, hours and region all match,
+    /// and a satellite with several rules is on when any of them does
+    Add {
+        #[arg(long)]
+        satellite: String,
+        /// Days of the week, e.g. "Sat,Sun" or "Mon-Fri"
+        #[arg(long)]
+        days: Option<String>,
+        /// Hours in UTC, e.g. "06:00-18:00"; "22:00-04:00" runs past midnight
+        #[arg(long)]
+        hours: Option<String>,
+        /// Box the point under the satellite must be in: "south,north,west,east"
+        #[arg(long, allow_hyphen_values = true)]
+        region: Option<String>,
+        #[arg(long, default_value = "")]
+        note: String,
+    },
+    /// Remove a rule by the id shown by `schedule list`
+    Remove { id: i64 },
+}
+
+#[derive(Subcommand, Debug)]
+enum ImportSource {
+    /// Import the transponders of a SatPC32 Doppler.sqf frequency file
+    Sqf {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+    /// Import the satellites, transponders and modules (as groups) of a GPredict
+    /// configuration directory
+    Gpredict {
+        #[arg(value_name = "DIR")]
+        directory: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ExportTarget {
+    /// Write a GPredict .trsp file for each satellite with known transponders
+    Gpredict {
+        #[arg(value_name = "DIR")]
+        directory: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum EopAction {
+    /// Download current Earth orientation parameters from Celestrak
+    Update,
+}
+
+#[derive(Subcommand, Debug)]
+enum DbAction {
+    /// Run a single SQL query on a read-only connection and print the results
+    Query {
+        #[arg(value_name = "SQL")]
+        sql: String,
+    },
 }
 
 /// Application view mode
@@ -45,6 +377,24 @@ pub enum AppMode {
     Normal,
     SatelliteConfig,
     UtilityMenu,
+    TransitFinder,
+    PassWindow,
+    CatalogSearch,
+    TleViewer,
+    GroundTrack,
+    Constellations,
+    OrbitView,
+    PassLog,
+    Stats,
+    Coverage,
+    Telemetry,
+    Satnogs,
+    RotatorCalibration,
+    RotatorSimulation,
+    Kiosk,
+    Focus,
+    BandOccupancy,
+    Geostationary,
 }
 
 /// Represents a TLE data source from Celestrak
@@ -121,6 +471,7 @@ pub struct UtilityMenuState {
     pub status: UtilityMenuStatus,
     pub status_message: Option<String>,
     pub downloaded_count: Option<usize>,
+    pub diff: Option<tle_diff::TleDiff>, // changes made by the last download
     pub download_progress: Option<Arc<Mutex<DownloadProgress>>>,
     pub download_handle: Option<std::thread::JoinHandle<()>>,
 }
@@ -132,6 +483,7 @@ impl UtilityMenuState {
             status: UtilityMenuStatus::Browsing,
             status_message: None,
             downloaded_count: None,
+            diff: None,
             download_progress: None,
             download_handle: None,
         }
@@ -142,11 +494,388 @@ impl UtilityMenuState {
         self.status = UtilityMenuStatus::Browsing;
         self.status_message = None;
         self.downloaded_count = None;
+        self.diff = None;
         self.download_progress = None;
         self.download_handle = None;
     }
 }
 
+/// State for the Sun/Moon transit finder popup
+pub struct TransitFinderState {
+    pub satellite_name: String,
+    pub transits: Vec<transit::Transit>,
+    pub selected_index: usize,
+}
+
+impl TransitFinderState {
+    fn new() -> Self {
+        Self {
+            satellite_name: String::new(),
+            transits: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+/// Which satellite the kiosk display features, and since when
+#[derive(Default)]
+pub struct KioskState {
+    featured_since: Option<std::time::Instant>, // None until the first satellite is featured
+    position: usize, // place in the relevance ranking of the featured satellite
+}
+
+/// Longest window the pass window dialog and `passes` command will search
+const MAX_WINDOW_DAYS: i64 = 31;
+
+/// Settings key of the station profile in use
+const PROFILE_SETTING: &str = "profile";
+
+/// Settings key of the LNB offset measured on the geostationary page
+const LNB_OFFSET_SETTING: &str = "lnb_offset_hz";
+
+/// Which end of the window is being edited in the pass window dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowField {
+    From,
+    To,
+}
+
+/// State for the pass window dialog: passes of every satellite between two times
+pub struct PassWindowState {
+    pub from: String,
+    pub to: String,
+    pub field: WindowField,
+    pub passes: Vec<(String, SatellitePass)>,
+    pub selected_index: usize,
+    pub status_message: Option<String>,
+}
+
+impl PassWindowState {
+    fn new() -> Self {
+        let now = chrono::Local::now();
+        Self {
+            from: now.format("%Y-%m-%d %H:%M").to_string(),
+            to: (now + Duration::days(1)).format("%Y-%m-%d %H:%M").to_string(),
+            field: WindowField::From,
+            passes: Vec::new(),
+            selected_index: 0,
+            status_message: None,
+        }
+    }
+}
+
+/// State for the raw TLE viewer popup
+pub struct TleViewerState {
+    pub status_message: Option<String>,
+    clipboard: Option<arboard::Clipboard>, // kept alive so X11/Wayland selections persist
+}
+
+impl TleViewerState {
+    fn new() -> Self {
+        Self {
+            status_message: None,
+            clipboard: None,
+        }
+    }
+
+    fn copy(&mut self, text: String) -> Result<()> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(arboard::Clipboard::new()?);
+        }
+        if let Some(clipboard) = self.clipboard.as_mut() {
+            clipboard.set_text(text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Which stretch of the selected satellite's track the ground track table covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackSpan {
+    Orbit, // one orbital period from now
+    Pass,  // the current or next pass, AOS to LOS
+}
+
+/// Spacing choices for the ground track table, in seconds
+pub const TRACK_STEPS: [i64; 6] = [10, 30, 60, 120, 300, 600];
+
+/// State for the constellation summary
+#[derive(Default)]
+pub struct ConstellationViewState {
+    pub selected_index: usize,
+}
+
+/// State for the band occupancy view
+#[derive(Default)]
+pub struct BandOccupancyState {
+    pub selected_index: usize,
+}
+
+/// State for the geostationary pointing page
+#[derive(Default)]
+pub struct GeostationaryState {
+    pub selected_index: usize,
+    pub editing: bool, // typing the IF the beacon is heard on
+    pub input_buffer: String,
+    pub lnb_offset_hz: f64,
+    pub status_message: Option<String>,
+    pub sun_outages: Vec<geostationary::SunOutage>, // over the next year, of the selected slot
+}
+
+/// State for the 3D orbit view
+#[derive(Default)]
+pub struct OrbitViewState {
+    pub view: orbit_view::View,
+}
+
+/// State for the ground track table
+pub struct GroundTrackState {
+    pub span: TrackSpan,
+    pub step_index: usize,
+    pub points: Vec<SatellitePosition>,
+    pub selected_index: usize,
+    pub status_message: Option<String>,
+}
+
+impl GroundTrackState {
+    fn new() -> Self {
+        Self {
+            span: TrackSpan::Orbit,
+            step_index: 2,
+            points: Vec::new(),
+            selected_index: 0,
+            status_message: None,
+        }
+    }
+
+    pub fn step_seconds(&self) -> i64 {
+        TRACK_STEPS[self.step_index]
+    }
+}
+
+/// Text being typed in the pass log view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassLogInput {
+    Notes,
+    Search,
+}
+
+/// State for the logged passes view
+pub struct PassLogState {
+    pub entries: Vec<PassLogEntry>,
+    pub selected_index: usize,
+    pub search: String,
+    pub input: Option<PassLogInput>,
+    pub input_buffer: String,
+    pub status_message: Option<String>,
+    pub history: Option<Vec<PositionRecord>>, // recorded positions of the selected pass, plotted
+    #[cfg(feature = "recording")]
+    playback: Option<recorder::AudioThread>, // a recording being played
+}
+
+impl PassLogState {
+    fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            selected_index: 0,
+            search: String::new(),
+            input: None,
+            input_buffer: String::new(),
+            status_message: None,
+            history: None,
+            #[cfg(feature = "recording")]
+            playback: None,
+        }
+    }
+
+    /// Stop the recording being played, returning whether one was
+    #[cfg(feature = "recording")]
+    fn stop_playback(&mut self) -> bool {
+        let Some(player) = self.playback.take() else {
+            return false;
+        };
+        // One that already finished has nothing to stop; dropping it stops the rest
+        !player.finished()
+    }
+
+    #[cfg(not(feature = "recording"))]
+    fn stop_playback(&mut self) -> bool {
+        false
+    }
+
+    fn reload(&mut self, db: &Database) {
+        match db.read_pass_log(&self.search) {
+            Ok(entries) => self.entries = entries,
+            Err(e) => self.status_message = Some(format!("Error reading log: {}", e)),
+        }
+        self.selected_index = self.selected_index.min(self.entries.len().saturating_sub(1));
+    }
+}
+
+/// Frames shown in the telemetry viewer
+const TELEMETRY_VIEW_LIMIT: usize = 500;
+
+/// State for the telemetry viewer of the selected satellite
+pub struct TelemetryViewState {
+    pub satellite_name: String,
+    pub frames: Vec<TelemetryFrame>,
+    pub selected_index: usize,
+    pub status_message: Option<String>,
+}
+
+impl TelemetryViewState {
+    fn new(satellite_name: &str) -> Self {
+        Self {
+            satellite_name: satellite_name.to_string(),
+            frames: Vec::new(),
+            selected_index: 0,
+            status_message: None,
+        }
+    }
+
+    fn reload(&mut self, db: &Database) {
+        match db.read_telemetry_frames(&self.satellite_name, TELEMETRY_VIEW_LIMIT) {
+            Ok(frames) => self.frames = frames,
+            Err(e) => self.status_message = Some(format!("Error reading telemetry: {}", e)),
+        }
+        self.selected_index = self.selected_index.min(self.frames.len().saturating_sub(1));
+    }
+}
+
+/// State for the chart of where logged passes and decodes have worked
+#[derive(Default)]
+pub struct CoverageState {
+    pub coverage: Option<coverage::Coverage>, // None when the pass log could not be read
+    pub status_message: Option<String>,
+}
+
+/// Rotator offset being typed on the calibration screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationField {
+    Azimuth,
+    Elevation,
+}
+
+/// State for the rotator calibration screen
+pub struct RotatorCalibrationState {
+    pub rotator_index: usize,
+    pub selected_index: usize,
+    pub editing: Option<CalibrationField>,
+    pub input_buffer: String,
+    pub reported_position: Option<(f64, f64)>, // last position read back from rotctld
+    pub status_message: Option<String>,
+    pub pointing_errors: Vec<PointingErrorStats>, // latest tracked passes, newest first
+}
+
+impl RotatorCalibrationState {
+    fn new() -> Self {
+        Self {
+            rotator_index: 0,
+            selected_index: 0,
+            editing: None,
+            input_buffer: String::new(),
+            reported_position: None,
+            status_message: None,
+            pointing_errors: Vec::new(),
+        }
+    }
+
+    fn load_pointing_errors(&mut self, db: &Database, rotator_name: &str) {
+        match db.read_pointing_errors(rotator_name, 8) {
+            Ok(errors) => self.pointing_errors = errors,
+            Err(e) => self.status_message = Some(format!("Error reading pointing errors: {}", e)),
+        }
+    }
+}
+
+/// Playback of a simulated pass: the rotator following it, with the radio
+/// settings and mode changes along the way
+pub struct RotatorSimulationState {
+    pub plan: Option<PassPlan>,
+    pub events: Vec<(DateTime<Utc>, String)>, // mode changes and milestones, in time order
+    pub index: usize,                         // current sample (one per simulated second)
+    pub speed: usize,                         // simulated seconds per frame
+    pub paused: bool,
+    pub return_mode: AppMode,
+}
+
+impl RotatorSimulationState {
+    fn new() -> Self {
+        Self {
+            plan: None,
+            events: Vec::new(),
+            index: 0,
+            speed: 2,
+            paused: false,
+            return_mode: AppMode::Normal,
+        }
+    }
+}
+
+/// Result slot shared with a background Celestrak name search
+pub type CatalogSearchResult = Arc<Mutex<Option<Result<Vec<celestrak::CatalogEntry>, String>>>>;
+
+/// Result slot shared with a background SATCAT lookup
+pub type SatcatResult = Arc<Mutex<Option<Result<celestrak::SatcatRecord, String>>>>;
+
+/// Result slot shared with a background elevation API lookup of the observer's altitude
+pub type AltitudeLookup = Arc<Mutex<Option<Result<f64, String>>>>;
+
+/// Result slot shared with a background pending-launch check, keyed by pending launch id
+pub type PendingCheckResult = Arc<Mutex<Option<Vec<(i64, Vec<celestrak::CatalogEntry>)>>>>;
+
+/// Result slot shared with a background SatNOGS request: a status message and the
+/// station's refreshed observations
+pub type SatnogsResult = Arc<Mutex<Option<Result<(String, Vec<satnogs::Observation>), String>>>>;
+
+/// State for the SatNOGS observations popup
+pub struct SatnogsState {
+    pub observations: Vec<satnogs::Observation>,
+    pub selected_index: usize,
+    pub status_message: Option<String>,
+    pub pending: Option<SatnogsResult>,
+}
+
+impl SatnogsState {
+    fn new() -> Self {
+        Self {
+            observations: Vec::new(),
+            selected_index: 0,
+            status_message: None,
+            pending: None,
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+/// State for the Celestrak catalog search popup
+pub struct CatalogSearchState {
+    pub query: String,
+    pub results: Vec<celestrak::CatalogEntry>,
+    pub selected_index: usize,
+    pub status_message: Option<String>,
+    pub pending: Option<CatalogSearchResult>,
+}
+
+impl CatalogSearchState {
+    fn new() -> Self {
+        Self {
+            query: String::new(),
+            results: Vec::new(),
+            selected_index: 0,
+            status_message: None,
+            pending: None,
+        }
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
 /// Editing mode for satellite configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConfigEditMode {
@@ -155,10 +884,36 @@ pub enum ConfigEditMode {
     Add,
 }
 
+/// Text being typed in the satellite list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListInput {
+    Search,
+    Bulk(BulkEdit),
+}
+
+/// Value being typed for every marked satellite at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkEdit {
+    Type,
+    Country,
+    Group,
+}
+
+impl BulkEdit {
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            BulkEdit::Type => "Type",
+            BulkEdit::Country => "Country",
+            BulkEdit::Group => "Add to group",
+        }
+    }
+}
+
 /// Field being edited in satellite config
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConfigField {
     Name,
+    Alias,
     TleLine1,
     TleLine2,
     LaunchDate,
@@ -174,7 +929,8 @@ pub enum ConfigField {
 impl ConfigField {
     fn next(&self) -> Self {
         match self {
-            ConfigField::Name => ConfigField::TleLine1,
+            ConfigField::Name => ConfigField::Alias,
+            ConfigField::Alias => ConfigField::TleLine1,
             ConfigField::TleLine1 => ConfigField::TleLine2,
             ConfigField::TleLine2 => ConfigField::LaunchDate,
             ConfigField::LaunchDate => ConfigField::LaunchSite,
@@ -191,7 +947,8 @@ impl ConfigField {
     fn prev(&self) -> Self {
         match self {
             ConfigField::Name => ConfigField::Notes,
-            ConfigField::TleLine1 => ConfigField::Name,
+            ConfigField::Alias => ConfigField::Name,
+            ConfigField::TleLine1 => ConfigField::Alias,
             ConfigField::TleLine2 => ConfigField::TleLine1,
             ConfigField::LaunchDate => ConfigField::TleLine2,
             ConfigField::LaunchSite => ConfigField::LaunchDate,
@@ -204,9 +961,68 @@ impl ConfigField {
         }
     }
 
+    /// Whether a typed character can be part of the field's value
+    fn accepts(&self, c: char) -> bool {
+        match self {
+            ConfigField::DownlinkFrequency | ConfigField::UplinkFrequency => {
+                c.is_ascii_digit() || ".kmghzKMGHZ ".contains(c)
+            }
+            ConfigField::LaunchDate => c.is_ascii_digit() || c == '-',
+            ConfigField::TleLine1 | ConfigField::TleLine2 => c.is_ascii_graphic() || c == ' ',
+            _ => true,
+        }
+    }
+
+    /// Checks a value before it is stored; empty optional fields are fine
+    fn validate(&self, value: &str) -> std::result::Result<(), String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(());
+        }
+        match self {
+            ConfigField::TleLine1 | ConfigField::TleLine2 => {
+                let number = if *self == ConfigField::TleLine1 { '1' } else { '2' };
+                if !value.starts_with(number) {
+                    return Err(format!("TLE line {} must start with {}", number, number));
+                }
+                match tle::verify_checksum(value) {
+                    tle::ChecksumStatus::Valid => Ok(()),
+                    tle::ChecksumStatus::Mismatch { expected, found } => Err(format!(
+                        "TLE line {} checksum is {} but should be {}",
+                        number, found, expected
+                    )),
+                    tle::ChecksumStatus::Missing => {
+                        Err(format!("TLE line {} must be 69 characters", number))
+                    }
+                }
+            }
+            ConfigField::LaunchDate => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map(|_| ())
+                .map_err(|_| format!("Launch date '{}' is not a YYYY-MM-DD date", value)),
+            ConfigField::DownlinkFrequency | ConfigField::UplinkFrequency => {
+                radio::parse_frequency_mhz(value).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Format or unit reminder shown beside the field while it is edited
+    fn hint(&self) -> Option<&'static str> {
+        match self {
+            ConfigField::LaunchDate => Some("YYYY-MM-DD, PgUp/PgDn: change"),
+            ConfigField::DownlinkFrequency | ConfigField::UplinkFrequency => {
+                Some("MHz, or with a unit: 145800k, 145800000 Hz")
+            }
+            ConfigField::CountryOfOrigin => Some("→: complete"),
+            ConfigField::Alias => Some("shown instead of the TLE name"),
+            _ => None,
+        }
+    }
+
     fn label(&self) -> &'static str {
         match self {
             ConfigField::Name => "Name",
+            ConfigField::Alias => "Alias",
             ConfigField::TleLine1 => "TLE Line 1",
             ConfigField::TleLine2 => "TLE Line 2",
             ConfigField::LaunchDate => "Launch Date",
@@ -221,15 +1037,47 @@ impl ConfigField {
     }
 }
 
-/// State for satellite configuration screen
-pub struct SatelliteConfigState {
+/// Celestrak SATCAT owner codes, offered as completions for the country field
+/// along with the values already in the database
+const OWNER_CODES: &[&str] = &[
+    "AB", "ARGN", "AUS", "BRAZ", "CA", "CIS", "ESA", "FR", "GER", "IND", "ISRA", "IT", "JPN",
+    "NETH", "PRC", "SKOR", "SPN", "SWED", "TURK", "UAE", "UK", "US",
+];
+
+/// Moves the part of a YYYY-MM-DD date under the cursor (year, month or day) by
+/// `delta`, starting from `today` when the text isn't a date yet
+fn step_date(text: &str, cursor: usize, delta: i32, today: chrono::NaiveDate) -> String {
+    use chrono::{Datelike, Months};
+    let date = chrono::NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").unwrap_or(today);
+    let months = Months::new(delta.unsigned_abs());
+    let stepped = match cursor {
+        0..=4 => date
+            .with_year(date.year() + delta)
+            // Feb 29 in a year without one
+            .or_else(|| date.with_day(28).and_then(|d| d.with_year(d.year() + delta))),
+        5..=7 if delta < 0 => date.checked_sub_months(months),
+        5..=7 => date.checked_add_months(months),
+        _ => date.checked_add_signed(Duration::days(delta as i64)),
+    };
+    stepped.unwrap_or(date).format("%Y-%m-%d").to_string()
+}
+
+/// State for satellite configuration screen
+pub struct SatelliteConfigState {
     pub satellites: Vec<SatelliteDetails>,
     pub selected_index: usize,
     pub edit_mode: ConfigEditMode,
     pub current_field: ConfigField,
     pub editing_satellite: SatelliteDetails,
-    pub input_buffer: String,
+    pub input_buffer: TextInput,
     pub status_message: Option<String>,
+    pub enrichment: Option<(i64, SatcatResult)>, // satellite id being looked up
+    pub confirm_delete: bool,                     // asked whether to delete the targets
+    pub last_deleted: Vec<SatelliteDetails>,      // restored by undo
+    pub marked: HashSet<i64>,                     // ids picked for bulk edits
+    pub list_input: Option<ListInput>,            // search or bulk edit being typed
+    pub search: String,                           // last search, repeated by n and N
+    pub search_matches: Vec<i64>,                 // ids matching it, in list order
 }
 
 impl SatelliteConfigState {
@@ -240,13 +1088,156 @@ impl SatelliteConfigState {
             edit_mode: ConfigEditMode::List,
             current_field: ConfigField::Name,
             editing_satellite: SatelliteDetails::default(),
-            input_buffer: String::new(),
+            input_buffer: TextInput::default(),
             status_message: None,
+            enrichment: None,
+            confirm_delete: false,
+            last_deleted: Vec::new(),
+            marked: HashSet::new(),
+            list_input: None,
+            search: String::new(),
+            search_matches: Vec::new(),
+        }
+    }
+
+    /// The marked satellites in list order, or the selected one when none are
+    pub fn targets(&self) -> Vec<&SatelliteDetails> {
+        if self.marked.is_empty() {
+            return self.satellites.get(self.selected_index).into_iter().collect();
+        }
+        self.satellites
+            .iter()
+            .filter(|sat| sat.id.is_some_and(|id| self.marked.contains(&id)))
+            .collect()
+    }
+
+    /// Select the next match of the last search after the selection, or with
+    /// `forward` false the one before it, wrapping around the list. A new search
+    /// starts at the selection itself.
+    fn jump_to_match(&mut self, forward: bool, from_selected: bool) {
+        let selected = self.selected_index;
+        let positions: Vec<usize> = self
+            .satellites
+            .iter()
+            .enumerate()
+            .filter(|(_, sat)| sat.id.is_some_and(|id| self.search_matches.contains(&id)))
+            .map(|(index, _)| index)
+            .collect();
+        let next = if forward {
+            let after = |index: usize| index > selected || (from_selected && index == selected);
+            positions.iter().find(|&&index| after(index)).or(positions.first())
+        } else {
+            positions.iter().rev().find(|&&index| index < selected).or(positions.last())
+        };
+        let Some(&index) = next else {
+            self.status_message = Some(format!("No satellites match '{}'", self.search));
+            return;
+        };
+        self.selected_index = index;
+        let number = positions.iter().position(|&i| i == index).unwrap_or(0) + 1;
+        let notes = self.satellites[index].notes.as_deref().unwrap_or("");
+        self.status_message = Some(format!(
+            "Match {} of {} for '{}' (n/N: next/previous) {}",
+            number,
+            positions.len(),
+            self.search,
+            notes
+        ));
+    }
+
+    /// "ISS (ZARYA)" for one target, "12 satellites" for several
+    pub fn describe_targets(&self) -> String {
+        match self.targets().as_slice() {
+            [sat] => sat.name.clone(),
+            targets => format!("{} satellites", targets.len()),
+        }
+    }
+
+    /// Store the text being edited in the current field if it is valid, or
+    /// show why not and keep it for correction
+    fn commit_field(&mut self) -> bool {
+        if let Err(e) = self.current_field.validate(self.input_buffer.value()) {
+            self.status_message = Some(e);
+            return false;
+        }
+        self.set_field_value(self.current_field, self.input_buffer.value().trim().to_string());
+        true
+    }
+
+    /// The completion offered for what has been typed, in fields that have them
+    pub fn completion(&self) -> Option<String> {
+        if self.current_field != ConfigField::CountryOfOrigin {
+            return None;
+        }
+        let typed = self.input_buffer.value().to_lowercase();
+        if typed.is_empty() {
+            return None;
+        }
+        let mut candidates: Vec<&str> = OWNER_CODES.to_vec();
+        candidates.extend(self.satellites.iter().filter_map(|s| s.country_of_origin.as_deref()));
+        candidates.sort_unstable();
+        candidates
+            .into_iter()
+            .find(|candidate| {
+                candidate.len() > typed.len() && candidate.to_lowercase().starts_with(&typed)
+            })
+            .map(str::to_string)
+    }
+
+    /// Paste into the edit form. A whole element set fills the name and both TLE
+    /// lines at once; anything on a single line goes into the current field.
+    fn paste(&mut self, text: &str) {
+        if self.edit_mode == ConfigEditMode::List {
+            if self.list_input.is_some() {
+                self.input_buffer.insert_str(text.trim());
+            }
+            return;
+        }
+        if !text.trim().contains('\n') {
+            self.input_buffer.insert_str(text.trim_matches(['\r', '\n']));
+            return;
+        }
+
+        match tle::split_pasted(text) {
+            Ok(pasted) => {
+                // A bad value in the current field is kept for correction
+                self.commit_field();
+                if let Some(name) = pasted.name {
+                    self.editing_satellite.name = name;
+                }
+                let problems: Vec<String> = [&pasted.line1, &pasted.line2]
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, line)| match tle::verify_checksum(line) {
+                        tle::ChecksumStatus::Valid => None,
+                        tle::ChecksumStatus::Mismatch { expected, found } => Some(format!(
+                            "line {} checksum is {} but should be {}",
+                            i + 1,
+                            found,
+                            expected
+                        )),
+                        tle::ChecksumStatus::Missing => {
+                            Some(format!("line {} has no checksum", i + 1))
+                        }
+                    })
+                    .collect();
+                self.editing_satellite.tle_line1 = pasted.line1;
+                self.editing_satellite.tle_line2 = pasted.line2;
+                self.input_buffer.set(self.get_field_value(self.current_field));
+                self.status_message = Some(if problems.is_empty() {
+                    format!("Pasted TLE for {}", self.editing_satellite.name)
+                } else {
+                    format!("Pasted TLE, but {}", problems.join(" and "))
+                });
+            }
+            Err(e) => self.status_message = Some(format!("Could not paste TLE: {}", e)),
         }
     }
 
     fn load_from_database(&mut self, db: &Database) -> Result<()> {
         self.satellites = db.read_all()?;
+        let ids: HashSet<i64> = self.satellites.iter().filter_map(|sat| sat.id).collect();
+        self.marked.retain(|id| ids.contains(id));
         if self.selected_index >= self.satellites.len() && !self.satellites.is_empty() {
             self.selected_index = self.satellites.len() - 1;
         }
@@ -256,6 +1247,7 @@ impl SatelliteConfigState {
     fn get_field_value(&self, field: ConfigField) -> String {
         match field {
             ConfigField::Name => self.editing_satellite.name.clone(),
+            ConfigField::Alias => self.editing_satellite.alias.clone().unwrap_or_default(),
             ConfigField::TleLine1 => self.editing_satellite.tle_line1.clone(),
             ConfigField::TleLine2 => self.editing_satellite.tle_line2.clone(),
             ConfigField::LaunchDate => self.editing_satellite.launch_date.clone().unwrap_or_default(),
@@ -276,6 +1268,9 @@ impl SatelliteConfigState {
     fn set_field_value(&mut self, field: ConfigField, value: String) {
         match field {
             ConfigField::Name => self.editing_satellite.name = value,
+            ConfigField::Alias => {
+                self.editing_satellite.alias = if value.is_empty() { None } else { Some(value) }
+            }
             ConfigField::TleLine1 => self.editing_satellite.tle_line1 = value,
             ConfigField::TleLine2 => self.editing_satellite.tle_line2 = value,
             ConfigField::LaunchDate => {
@@ -294,10 +1289,12 @@ impl SatelliteConfigState {
                 self.editing_satellite.satellite_type = if value.is_empty() { None } else { Some(value) }
             }
             ConfigField::DownlinkFrequency => {
-                self.editing_satellite.downlink_frequency_mhz = value.parse().ok()
+                self.editing_satellite.downlink_frequency_mhz =
+                    radio::parse_frequency_mhz(&value).ok()
             }
             ConfigField::UplinkFrequency => {
-                self.editing_satellite.uplink_frequency_mhz = value.parse().ok()
+                self.editing_satellite.uplink_frequency_mhz =
+                    radio::parse_frequency_mhz(&value).ok()
             }
             ConfigField::Notes => {
                 self.editing_satellite.notes = if value.is_empty() { None } else { Some(value) }
@@ -310,13 +1307,72 @@ pub struct AppState {
     pub satellites: Vec<Satellite>,
     pub current_positions: Vec<SatellitePosition>,
     pub selected_satellite: usize,
+    pub auto_follow: bool, // selection follows the highest satellite, or the next to rise
+    pub accessible: bool,  // plain text view for screen readers
     pub observer: Observer,
     pub config: Config,
+    pub config_path: String,
+    pub profile: Option<String>, // station profile applied to `config`
+    pub group_filter: Option<String>, // satellite group picked with a number key
     pub alerts: Vec<Alert>,
+    pub conflicts: Vec<scheduler::PassConflict>, // overlapping alerted or current passes
     pub mode: AppMode,
+    pub status_message: Option<String>, // shown in place of the keys until the next key
     pub sat_config_state: SatelliteConfigState,
     pub database: Database,
     pub utility_menu_state: UtilityMenuState,
+    pub clock: clock::ClockMonitor,
+    pub kiosk_state: KioskState,
+    pub transit_state: TransitFinderState,
+    pub pass_window_state: PassWindowState,
+    pub catalog_search_state: CatalogSearchState,
+    pub tle_viewer_state: TleViewerState,
+    pub ground_track_state: GroundTrackState,
+    pub constellation_state: ConstellationViewState,
+    pub band_occupancy_state: BandOccupancyState,
+    pub geostationary_state: GeostationaryState,
+    pub orbit_view_state: OrbitViewState,
+    pub pass_log_state: PassLogState,
+    pub log_stats: Option<LogStats>, // None when the pass log could not be summarized
+    pub transponders: Vec<Transponder>,
+    pub groups: Vec<SatelliteGroup>,
+    pub aliases: HashMap<String, String>, // display alias by TLE name
+    pub pending_launches: Vec<PendingLaunch>,
+    pub pending_check: Option<PendingCheckResult>,
+    pub last_pending_check: Option<DateTime<Utc>>,
+    pub altitude_lookup: Option<AltitudeLookup>, // after switching to a profile without an altitude
+    pub last_history_record: Option<DateTime<Utc>>,
+    pub history_failing: bool, // recording positions failed, which was reported once
+    pub last_position_refresh: Option<DateTime<Utc>>,
+    pub last_sky_map_refresh: Option<DateTime<Utc>>,
+    pub last_pass_refresh: DateTime<Utc>,
+    pub last_bell_check: Option<DateTime<Utc>>,
+    pub sky_map_positions: Vec<SatellitePosition>, // positions as the sky map last drew them
+    pub elevation_outlooks: HashMap<String, Vec<f64>>, // by satellite, for the positions table
+    pub last_outlook_refresh: Option<DateTime<Utc>>,
+    pub announcer: Announcer,
+    pub rotators: Vec<RotatorController>,
+    pub antenna_patterns: HashMap<PathBuf, antenna::AntennaPattern>, // by pattern_file
+    pub rig: Option<RigController>,
+    pub sdr: Option<SdrController>,
+    #[cfg(feature = "recording")]
+    pub recorder: Option<recorder::PassRecorder>,
+    pub home_assistant: Option<home_assistant::HomeAssistant>,
+    pub frequency_offsets: Vec<FrequencyOffset>,
+    pub muted_alerts: HashSet<String>, // satellites whose passes raise no alerts
+    pub schedule: schedule::Schedule,  // when transponders are known to be on
+    pub decoders: Vec<TelemetryDecoder>,
+    pub telemetry_state: TelemetryViewState,
+    pub coverage_state: CoverageState,
+    pub satnogs_state: SatnogsState,
+    pub direwolf: aprs::DirewolfLauncher,
+    pub calibration_state: RotatorCalibrationState,
+    pub simulation_state: RotatorSimulationState,
+    pub plugins: plugins::PluginRegistry,
+    pub scripts: scripting::ScriptHost,
+    #[cfg(unix)]
+    pub daemon: Option<daemon::DaemonClient>, // attached to a daemon that drives the hardware
+    pub calendar: Option<calendar::CalendarServer>, // pass feeds served by the daemon
 }
 
 #[derive(Clone, Debug)]
@@ -328,10 +1384,51 @@ pub struct Alert {
     pub shown: bool,
 }
 
+/// The alert countdown goes to seconds, and the screen to this tick in
+/// milliseconds, for the last minute before AOS
+pub const FINAL_COUNTDOWN_SECONDS: f64 = 60.0;
+const FINAL_COUNTDOWN_TICK_MS: u64 = 100;
+
+impl Alert {
+    /// Seconds left until AOS, never negative
+    pub fn seconds_until(&self, now: DateTime<Utc>) -> f64 {
+        ((self.pass.aos_time - now).num_milliseconds() as f64 / 1000.0).max(0.0)
+    }
+
+    pub fn in_final_countdown(&self, now: DateTime<Utc>) -> bool {
+        self.seconds_until(now) < FINAL_COUNTDOWN_SECONDS
+    }
+
+    /// "5 minutes", or "42 seconds" in the last minute
+    pub fn countdown(&self, now: DateTime<Utc>) -> String {
+        if self.in_final_countdown(now) {
+            format!("{:.0} seconds", self.seconds_until(now).ceil())
+        } else {
+            format!("{} minutes", self.time_until_minutes)
+        }
+    }
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    let config = match Config::load(&args.config) {
+    if let Some(Command::Verify) = args.command {
+        return verify::report(&verify::run());
+    }
+    if let Some(Command::Doctor) = args.command {
+        let checks = doctor::run(&args.config, args.profile.as_deref(), &database_path());
+        return doctor::report(&checks);
+    }
+    if let Some(Command::Db {
+        action: DbAction::Query { sql },
+    }) = &args.command
+    {
+        let database = Database::open_read_only(database_path())?;
+        println!("{}", database.query(sql)?.to_table());
+        return Ok(());
+    }
+
+    let mut config = match Config::load(&args.config) {
         Ok(cfg) => cfg,
         Err(e) => {
             eprintln!("\nError: Could not load configuration file '{}'", args.config);
@@ -345,26 +1442,166 @@ fn main() -> Result<()> {
         }
     };
 
-    // Create observer
-    let observer = Observer::new(
-        config.observer.name.clone(),
-        config.observer.latitude,
-        config.observer.longitude,
-        config.observer.altitude,
-    );
-
     // Initialize database before satellite loading so we can look up source names
-    let db_path = dirs::data_local_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("crabtrack")
-        .join("satellites.db");
+    let db_path = database_path();
 
     if let Some(parent) = db_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    let database = Database::open(&db_path)?;
-    println!("Database initialized at: {}", db_path.display());
+    // The daemon and a TUI attached to it take turns with the file, since
+    // DuckDB lets one process at a time have it open
+    let shares_database =
+        args.attach.is_some() || matches!(args.command, Some(Command::Daemon { .. }));
+    let database = if shares_database {
+        Database::open_shared(&db_path)?
+    } else {
+        Database::open(&db_path)?
+    };
+    eprintln!("Database initialized at: {}", db_path.display());
+
+    // A profile named on the command line replaces the one used last time
+    let profile = match &args.profile {
+        Some(name) => {
+            config.apply_profile(name)?;
+            database.save_setting(PROFILE_SETTING, name)?;
+            Some(name.clone())
+        }
+        None => database
+            .read_setting(PROFILE_SETTING)?
+            .filter(|name| match config.apply_profile(name) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("Warning: {}; using the station as configured", e);
+                    false
+                }
+            }),
+    };
+
+    let mut observer = build_observer(&config)?;
+    if let Some(Command::Noise {
+        azimuth,
+        level,
+        scan,
+    }) = &args.command
+    {
+        return manage_noise_profile(&config, &observer, *azimuth, *level, scan.as_deref());
+    }
+
+    let eop_path = config
+        .prediction
+        .eop_file
+        .clone()
+        .unwrap_or_else(|| db_path.with_file_name("EOP-All.csv"));
+    if let Some(Command::Eop {
+        action: EopAction::Update,
+    }) = &args.command
+    {
+        let text = celestrak::fetch_text(frames::EOP_URL)?;
+        let table = frames::EopTable::parse_csv(&text)?;
+        std::fs::write(&eop_path, text)?;
+        println!(
+            "Wrote {} days of Earth orientation parameters to {}",
+            table.len(),
+            eop_path.display()
+        );
+        return Ok(());
+    }
+    if config.prediction.precise_frames {
+        let table = frames::EopTable::load(&eop_path).unwrap_or_else(|e| {
+            eprintln!("Warning: Could not load Earth orientation parameters: {:#}", e);
+            eprintln!("         Run `crabtrack eop update` to download them");
+            frames::EopTable::default()
+        });
+        observer.earth_orientation = Some(table);
+    }
+
+    if let Some(Command::Import {
+        source: ImportSource::Sqf { file: sqf_path },
+    }) = &args.command
+    {
+        let transponders = transponder::load_sqf(sqf_path)?;
+        let count = database.replace_transponders("sqf", &transponders)?;
+        println!("Imported {} transponders from {}", count, sqf_path.display());
+        return Ok(());
+    }
+    if let Some(Command::Import {
+        source: ImportSource::Gpredict {
+            directory: gpredict_dir,
+        },
+    }) = &args.command
+    {
+        let import = gpredict::load_config_dir(gpredict_dir)?;
+        for details in &import.satellites {
+            // Keep any details entered by hand; only refresh the elements
+            match database.read_by_name(&details.name)? {
+                Some(mut existing) => {
+                    existing.tle_line1 = details.tle_line1.clone();
+                    existing.tle_line2 = details.tle_line2.clone();
+                    database.update(&existing)?;
+                }
+                None => {
+                    database.create(details)?;
+                }
+            }
+        }
+        let transponder_count = database.replace_transponders("gpredict", &import.transponders)?;
+        database.replace_groups(&import.groups)?;
+        println!(
+            "Imported {} satellites, {} transponders and {} groups from {}",
+            import.satellites.len(),
+            transponder_count,
+            import.groups.len(),
+            gpredict_dir.display()
+        );
+        return Ok(());
+    }
+    if let Some(Command::Schedule { action }) = &args.command {
+        return manage_schedule(&database, action);
+    }
+    if let Some(Command::Sync) = args.command {
+        let sync_config = config
+            .sync
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("set remote under [sync] in the configuration"))?;
+        let merge = sync::run(&database, sync_config, &config.observer.name)?;
+        for record in &merge.pulled {
+            println!("Updated {}", record.details.name);
+        }
+        println!(
+            "Pulled {} and pushed {} satellites; {} in the shared list",
+            merge.pulled.len(),
+            merge.pushed,
+            merge.satellites.len()
+        );
+        return Ok(());
+    }
+    if let Some(Command::Export {
+        target: ExportTarget::Gpredict {
+            directory: export_dir,
+        },
+    }) = &args.command
+    {
+        let count = gpredict::export_trsp_files(
+            export_dir,
+            &database.read_all()?,
+            &database.read_transponders()?,
+        )?;
+        println!("Wrote {} .trsp files to {}", count, export_dir.display());
+        return Ok(());
+    }
+    let groups = database.read_groups().unwrap_or_else(|e| {
+        eprintln!("Warning: Could not load satellite groups from database: {}", e);
+        Vec::new()
+    });
+    let transponders = database.read_transponders().unwrap_or_else(|e| {
+        eprintln!("Warning: Could not load transponders from database: {}", e);
+        Vec::new()
+    });
+    let aliases = database.read_aliases().unwrap_or_else(|e| {
+        eprintln!("Warning: Could not load satellite aliases from database: {}", e);
+        HashMap::new()
+    });
 
     // Build name→satellite_type map from database for staleness grouping
     let db_type_map: std::collections::HashMap<String, String> = database
@@ -379,20 +1616,285 @@ fn main() -> Result<()> {
     let tle_data = if tle_file.exists() {
         fs::read_to_string(&tle_file)?
     } else {
-        println!("No TLE file found at '{}', downloading from Celestrak...", tle_file.display());
+        eprintln!("No TLE file found at '{}', downloading from Celestrak...", tle_file.display());
         if let Some(parent) = tle_file.parent() {
             std::fs::create_dir_all(parent)?;
         }
         let tle_data = download_all_groups()?;
         fs::write(&tle_file, &tle_data)?;
-        println!("TLE file saved to '{}'", tle_file.display());
+        eprintln!("TLE file saved to '{}'", tle_file.display());
         tle_data
     };
 
+    if let Some(Command::Cleanup {
+        days,
+        refresh,
+        archive,
+        purge,
+        restore,
+    }) = &args.command
+    {
+        if let Some(name) = restore {
+            if !database.unarchive(name)? {
+                anyhow::bail!("'{}' is not in the archive", name);
+            }
+            println!("Restored {}", name);
+            return Ok(());
+        }
+        let source = celestrak::parse_entries(&tle_data);
+        let orphans = cleanup::find(&database.read_all()?, &source, *days, Utc::now());
+        if orphans.is_empty() {
+            println!(
+                "Every satellite in the database is in the TLE file with elements under {} days old",
+                days
+            );
+            return Ok(());
+        }
+        for orphan in &orphans {
+            let name = &orphan.details.name;
+            let result = if *refresh {
+                cleanup::fresh_elements(orphan, &source).and_then(|fresh| match fresh {
+                    Some(entry) => {
+                        let mut details = orphan.details.clone();
+                        details.tle_line1 = entry.tle_line1;
+                        details.tle_line2 = entry.tle_line2;
+                        database.update(&details)?;
+                        Ok("refreshed".to_string())
+                    }
+                    None => Ok("no newer elements found".to_string()),
+                })
+            } else if *archive {
+                database.archive(&orphan.details).map(|()| "archived".to_string())
+            } else if *purge {
+                database.delete_by_name(name).map(|_| "purged".to_string())
+            } else {
+                Ok(orphan.reason(*days))
+            };
+            match result {
+                Ok(outcome) => println!("{:<24} {}", name, outcome),
+                Err(e) => println!("{:<24} failed: {:#}", name, e),
+            }
+        }
+        return Ok(());
+    }
+
     let mut satellites = parse_multiple_tles(&tle_data, &config)?;
 
+    if let Some(Command::Passes {
+        from,
+        to,
+        satellite,
+        observer: sites,
+    }) = &args.command
+    {
+        if let Some(name) = satellite {
+            satellites.retain(|sat| {
+                sat.name.eq_ignore_ascii_case(name)
+                    || aliases.get(&sat.name).is_some_and(|alias| alias.eq_ignore_ascii_case(name))
+            });
+            if satellites.is_empty() {
+                anyhow::bail!("'{}' is not among the tracked satellites", name);
+            }
+        }
+        let from = pass_prediction::parse_window_time(from)?;
+        let to = pass_prediction::parse_window_time(to)?;
+        if !sites.is_empty() {
+            let observers = sites
+                .iter()
+                .map(|spec| observer_from_spec(&args.config, spec))
+                .collect::<Result<Vec<_>>>()?;
+            let sets: Vec<_> = passes_for_observers(
+                &satellites,
+                &observers,
+                &config.prediction,
+                &database,
+                from,
+                to,
+            )?
+            .into_iter()
+            .map(|passes| by_alias(passes, &aliases))
+            .collect();
+            for (observer, passes) in observers.iter().zip(&sets) {
+                println!(
+                    "{} ({:.4}°, {:.4}°)",
+                    observer.name, observer.latitude, observer.longitude
+                );
+                print_window_passes(passes);
+                println!();
+            }
+            if observers.len() > 1 {
+                print_observer_comparison(&observers, &sets);
+            }
+            return Ok(());
+        }
+        let cache = PassCache::new(&database, &observer, &config.prediction);
+        let passes =
+            passes_in_window(&satellites, &observer, &config.prediction, &cache, from, to)?;
+        print_window_passes(&by_alias(passes, &aliases));
+        return Ok(());
+    }
+
+    if let Some(Command::Report {
+        days,
+        format,
+        high_elevation,
+        output,
+    }) = &args.command
+    {
+        let from = Utc::now();
+        let passes = passes_in_window(
+            &satellites,
+            &observer,
+            &config.prediction,
+            &PassCache::new(&database, &observer, &config.prediction),
+            from,
+            from + Duration::days(*days),
+        )?;
+        let header = report::ReportHeader {
+            observer: &observer.name,
+            from,
+            days: *days,
+            high_elevation: *high_elevation,
+        };
+        let passes = by_alias(passes, &aliases);
+        let text = report::render(&passes, &header, *format, &chrono::Local);
+        match output {
+            Some(path) => fs::write(path, text)?,
+            None => print!("{}", text),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Chart {
+        satellite,
+        from,
+        output,
+    }) = &args.command
+    {
+        let Some(tracked) = satellites
+            .iter()
+            .find(|sat| sat.name.eq_ignore_ascii_case(satellite))
+        else {
+            anyhow::bail!("'{}' is not among the tracked satellites", satellite);
+        };
+        let from = match from {
+            Some(from) => pass_prediction::parse_window_time(from)?,
+            None => Utc::now(),
+        };
+        let passes = passes_in_window(
+            std::slice::from_ref(tracked),
+            &observer,
+            &config.prediction,
+            &PassCache::new(&database, &observer, &config.prediction),
+            from,
+            from + Duration::days(MAX_WINDOW_DAYS),
+        )?;
+        let Some((_, pass)) = passes.first() else {
+            anyhow::bail!("{} has no pass in the next {} days", tracked.name, MAX_WINDOW_DAYS);
+        };
+        let chart = skychart::PassChart::new(tracked, &observer, pass);
+        let is_png = output
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+        if is_png {
+            fs::write(output, chart.png()?)?;
+        } else {
+            fs::write(output, chart.svg())?;
+        }
+        eprintln!("Saved {}", output.display());
+        return Ok(());
+    }
+
+    if let Some(Command::Doppler {
+        satellite,
+        from,
+        frequency,
+        step,
+        output,
+    }) = &args.command
+    {
+        let Some(tracked) = satellites
+            .iter()
+            .find(|sat| sat.name.eq_ignore_ascii_case(satellite))
+        else {
+            anyhow::bail!("'{}' is not among the tracked satellites", satellite);
+        };
+        if *step <= 0.0 {
+            anyhow::bail!("--step must be more than 0 seconds");
+        }
+        let from = match from {
+            Some(from) => pass_prediction::parse_window_time(from)?,
+            None => Utc::now(),
+        };
+        let passes = passes_in_window(
+            std::slice::from_ref(tracked),
+            &observer,
+            &config.prediction,
+            &PassCache::new(&database, &observer, &config.prediction),
+            from,
+            from + Duration::days(MAX_WINDOW_DAYS),
+        )?;
+        let Some((_, pass)) = passes.iter().find(|(_, pass)| pass.los_time > from) else {
+            anyhow::bail!("{} has no pass in the next {} days", tracked.name, MAX_WINDOW_DAYS);
+        };
+        let downlink_mhz = match frequency {
+            Some(mhz) => *mhz,
+            None => {
+                let (mhz, _) = config.radio.frequencies_for(&tracked.name);
+                let offsets = database.read_frequency_offsets()?;
+                mhz + radio::downlink_offset_hz(&offsets, &tracked.name, mhz) / 1e6
+            }
+        };
+        let curve = waterfall::DopplerCurve::new(
+            tracked,
+            &observer,
+            downlink_mhz,
+            (from.max(pass.aos_time), pass.los_time),
+            Duration::milliseconds((step * 1000.0) as i64),
+        );
+        let is_json = output
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            fs::write(output, serde_json::to_string_pretty(&curve.json())?)?;
+        } else {
+            fs::write(output, curve.csv())?;
+        }
+        eprintln!("Saved {} points to {}", curve.points.len(), output.display());
+        return Ok(());
+    }
+
+    if let Some(Command::Conjunctions {
+        days,
+        distance,
+        satellite,
+    }) = &args.command
+    {
+        if let Some(name) = satellite {
+            if !satellites.iter().any(|sat| sat.name.eq_ignore_ascii_case(name)) {
+                anyhow::bail!("'{}' is not among the tracked satellites", name);
+            }
+        }
+        let from = Utc::now();
+        eprintln!(
+            "Screening {} satellites for approaches within {} km...",
+            satellites.len(),
+            distance
+        );
+        let conjunctions = conjunction::screen(
+            &satellites,
+            from,
+            from + Duration::days(*days),
+            *distance,
+            satellite.as_deref(),
+        );
+        print_conjunctions(&conjunctions);
+        return Ok(());
+    }
+
     // Predict passes for all satellites
-    println!("Predicting passes for {} satellites...", satellites.len());
+    eprintln!("Predicting passes for {} satellites...", satellites.len());
+    let pass_cache = PassCache::new(&database, &observer, &config.prediction);
     let now = Utc::now();
     let mut stale_sources: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
     for satellite in satellites.iter_mut() {
@@ -407,15 +1909,10 @@ fn main() -> Result<()> {
                 *entry = tle_age_days;
             }
         }
-        match predict_passes(
-            &satellite.elements,
-            &satellite.epoch,
-            &observer,
-            &config.prediction,
-        ) {
+        match predict_passes(satellite, &observer, &config.prediction, &pass_cache) {
             Ok(passes) => {
                 satellite.passes = passes;
-                println!(
+                eprintln!(
                     "  {} - Found {} passes",
                     satellite.name,
                     satellite.passes.len()
@@ -441,15 +1938,37 @@ fn main() -> Result<()> {
         .filter_map(|sat| sat.calculate_position(Utc::now(), &observer).ok())
         .collect::<Vec<_>>();
 
+    let frequency_offsets = database.read_frequency_offsets().unwrap_or_else(|e| {
+        eprintln!("Warning: Could not load frequency offsets: {}", e);
+        Vec::new()
+    });
+    let muted_alerts = database.read_muted_alerts().unwrap_or_else(|e| {
+        eprintln!("Warning: Could not load muted alerts: {}", e);
+        HashSet::new()
+    });
+    let schedule = database
+        .read_operating_rules()
+        .and_then(|rules| schedule::Schedule::new(&rules))
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: Could not load operating schedules: {:#}", e);
+            schedule::Schedule::default()
+        });
+
+    let mut warnings = Vec::new();
+    let antenna_patterns = load_antenna_patterns(&config, &mut warnings);
     // Add radio calculations if enabled
     if config.radio.enabled {
+        let orbits = orbit_classes(&satellites);
         for pos in current_positions.iter_mut() {
-            pos.doppler = Some(calculate_doppler_shift(
-                pos,
-                config.radio.downlink_frequency_mhz,
-                config.radio.uplink_frequency_mhz,
-            ));
-            pos.comm_window = Some(evaluate_communication_window(pos));
+            let (mut downlink_mhz, uplink_mhz) = config.radio.frequencies_for(&pos.name);
+            downlink_mhz +=
+                radio::downlink_offset_hz(&frequency_offsets, &pos.name, downlink_mhz) / 1e6;
+            pos.doppler = Some(calculate_doppler_shift(pos, downlink_mhz, uplink_mhz));
+            let orbit = orbits.get(pos.name.as_str()).copied().unwrap_or_default();
+            let station =
+                station_link(&config, &antenna_patterns, &observer.noise, pos, downlink_mhz);
+            let window = evaluate_communication_window(pos, orbit, downlink_mhz, station);
+            pos.comm_window = Some(window);
         }
     }
 
@@ -459,24 +1978,177 @@ fn main() -> Result<()> {
         eprintln!("Warning: Could not load satellite details from database: {}", e);
     }
 
+    let rotators = build_rotators(&config, &database, &mut warnings);
+    print_warnings(&warnings);
+    let rig = config.rig.clone().map(RigController::new);
+    let sdr = config.sdr.clone().map(SdrController::new);
+    #[cfg(not(feature = "recording"))]
+    if config.recording.is_some() {
+        eprintln!("Warning: [recording] needs crabtrack built with --features recording");
+    }
+    #[cfg(feature = "recording")]
+    let recorder = config.recording.clone().map(|recording| {
+        let directory = recording
+            .directory
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| db_path.with_file_name("recordings"));
+        recorder::PassRecorder::new(recording, directory)
+    });
+    let home_assistant = config.home_assistant.clone().map(home_assistant::HomeAssistant::new);
+    let decoders = config.decoders.iter().cloned().map(TelemetryDecoder::new).collect();
+
+    let mut clock = clock::ClockMonitor::new(config.clock.clone());
+    let epochs: Vec<DateTime<Utc>> = satellites.iter().map(|sat| sat.epoch).collect();
+    if let Some(warning) = clock::epoch_warning(&epochs, Utc::now()) {
+        eprintln!("Warning: {}", warning);
+    }
+    clock.update(&epochs, Utc::now());
+
+    let pending_launches = database.read_pending_launches().unwrap_or_else(|e| {
+        eprintln!("Warning: Could not load pending launches: {}", e);
+        Vec::new()
+    });
+
+    let plugins = plugins::PluginRegistry::from_config(&config.plugins)?;
+    let scripts = scripting::ScriptHost::load(&config.scripting.files)?;
+
     let mut app_state = AppState {
         satellites,
         current_positions,
         selected_satellite: 0,
+        auto_follow: config.display.auto_follow,
+        accessible: args.accessible || config.display.accessible,
         observer,
         config,
+        config_path: args.config.clone(),
+        profile,
+        group_filter: None,
         alerts: Vec::new(),
-        mode: AppMode::Normal,
+        conflicts: Vec::new(),
+        mode: if args.kiosk {
+            AppMode::Kiosk
+        } else {
+            AppMode::Normal
+        },
+        status_message: None,
         sat_config_state,
         database,
         utility_menu_state: UtilityMenuState::new(),
+        clock,
+        kiosk_state: KioskState::default(),
+        transit_state: TransitFinderState::new(),
+        pass_window_state: PassWindowState::new(),
+        catalog_search_state: CatalogSearchState::new(),
+        tle_viewer_state: TleViewerState::new(),
+        ground_track_state: GroundTrackState::new(),
+        constellation_state: ConstellationViewState::default(),
+        band_occupancy_state: BandOccupancyState::default(),
+        geostationary_state: GeostationaryState::default(),
+        orbit_view_state: OrbitViewState::default(),
+        pass_log_state: PassLogState::new(),
+        log_stats: None,
+        transponders,
+        groups,
+        aliases,
+        pending_launches,
+        pending_check: None,
+        last_pending_check: None,
+        altitude_lookup: None,
+        last_history_record: None,
+        history_failing: false,
+        last_position_refresh: None,
+        last_sky_map_refresh: None,
+        last_pass_refresh: Utc::now(),
+        last_bell_check: None,
+        sky_map_positions: Vec::new(),
+        elevation_outlooks: HashMap::new(),
+        last_outlook_refresh: None,
+        announcer: Announcer::default(),
+        rotators,
+        antenna_patterns,
+        rig,
+        sdr,
+        #[cfg(feature = "recording")]
+        recorder,
+        home_assistant,
+        frequency_offsets,
+        muted_alerts,
+        schedule,
+        decoders,
+        telemetry_state: TelemetryViewState::new(""),
+        coverage_state: CoverageState::default(),
+        satnogs_state: SatnogsState::new(),
+        direwolf: aprs::DirewolfLauncher::new(),
+        calibration_state: RotatorCalibrationState::new(),
+        simulation_state: RotatorSimulationState::new(),
+        plugins,
+        scripts,
+        #[cfg(unix)]
+        daemon: None,
+        calendar: None,
     };
 
+    if let Some(Command::Events) = args.command {
+        return run_event_stream(&mut app_state);
+    }
+
+    if let Some(Command::Control { socket }) = &args.command {
+        let channel = match socket {
+            #[cfg(unix)]
+            Some(path) => control::ControlChannel::unix_socket(path)?,
+            #[cfg(not(unix))]
+            Some(_) => anyhow::bail!("--socket needs a unix domain socket; use stdin instead"),
+            None => control::ControlChannel::stdio(),
+        };
+        return run_control(&mut app_state, channel);
+    }
+
+    if let Some(Command::Daemon { socket, calendar }) = &args.command {
+        #[cfg(unix)]
+        {
+            let path = socket.clone().unwrap_or_else(daemon::default_socket_path);
+            let channel = control::ControlChannel::unix_socket(&path)?;
+            if let Some(address) = calendar {
+                let server = calendar::CalendarServer::bind(address)?;
+                eprintln!("Serving pass calendars on http://{}/passes.ics", server.address);
+                app_state.calendar = Some(server);
+            }
+            eprintln!("Listening on {}", path.display());
+            return run_control(&mut app_state, channel);
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (socket, calendar);
+            anyhow::bail!("the daemon needs unix domain sockets, which this platform lacks");
+        }
+    }
+
+    if let Some(socket) = &args.attach {
+        #[cfg(unix)]
+        {
+            let path = socket.clone().unwrap_or_else(daemon::default_socket_path);
+            app_state.daemon = Some(daemon::DaemonClient::connect(&path)?);
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = socket;
+            anyhow::bail!("--attach needs unix domain sockets, which this platform lacks");
+        }
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    // The legacy Windows console has no bracketed paste; pasting a TLE there
+    // arrives as typed keys instead
+    let _ = execute!(stdout, EnableBracketedPaste);
+    let backend = ConsoleBackend::new(
+        CrosstermBackend::new(stdout),
+        args.ascii || app_state.config.display.ascii,
+        ColorDepth::detect(),
+    );
     let mut terminal = Terminal::new(backend)?;
 
     // Run TUI
@@ -489,8 +2161,9 @@ fn main() -> Result<()> {
 
     // Restore terminal
     disable_raw_mode()?;
+    let _ = execute!(terminal.backend_mut().inner_mut(), DisableBracketedPaste);
     execute!(
-        terminal.backend_mut(),
+        terminal.backend_mut().inner_mut(),
         LeaveAlternateScreen,
         DisableMouseCapture
     )?;
@@ -525,27 +2198,7 @@ fn parse_multiple_tles(tle_data: &str, config: &Config) -> Result<Vec<Satellite>
             };
 
             if should_track {
-                // Parse epoch from TLE line 1, columns 18-32
-                let epoch_datetime = if tle_line1.len() >= 32 {
-                    let epoch_str = &tle_line1[18..32];
-
-                    if let Ok(epoch_val) = epoch_str.trim().parse::<f64>() {
-                        let year_2digit = (epoch_val / 1000.0).floor() as i32;
-                        let day_of_year = epoch_val % 1000.0;
-
-                        let full_year = if year_2digit >= 57 {
-                            1900 + year_2digit
-                        } else {
-                            2000 + year_2digit
-                        };
-
-                        year_day_to_datetime(full_year, day_of_year)
-                    } else {
-                        Utc::now() // Fallback
-                    }
-                } else {
-                    Utc::now() // Fallback
-                };
+                let epoch_datetime = parse_tle_epoch(tle_line1);
 
                 match Elements::from_tle(
                     Some(name.clone()),
@@ -553,7 +2206,13 @@ fn parse_multiple_tles(tle_data: &str, config: &Config) -> Result<Vec<Satellite>
                     lines[i + 2].as_bytes(),
                 ) {
                     Ok(elements) => {
-                        satellites.push(Satellite::new(name, elements, epoch_datetime));
+                        satellites.push(Satellite::new(
+                            name,
+                            elements,
+                            epoch_datetime,
+                            lines[i + 1].to_string(),
+                            lines[i + 2].to_string(),
+                        ));
                     }
                     Err(e) => {
                         eprintln!("Warning: Failed to parse TLE for {}: {:?}", name, e);
@@ -574,15 +2233,69 @@ fn parse_multiple_tles(tle_data: &str, config: &Config) -> Result<Vec<Satellite>
     Ok(satellites)
 }
 
+/// Parse epoch from TLE line 1, columns 18-32, falling back to now
+fn parse_tle_epoch(tle_line1: &str) -> DateTime<Utc> {
+    if tle_line1.len() >= 32 {
+        let epoch_str = &tle_line1[18..32];
+
+        if let Ok(epoch_val) = epoch_str.trim().parse::<f64>() {
+            let year_2digit = (epoch_val / 1000.0).floor() as i32;
+            let day_of_year = epoch_val % 1000.0;
+
+            let full_year = if year_2digit >= 57 {
+                1900 + year_2digit
+            } else {
+                2000 + year_2digit
+            };
+
+            return year_day_to_datetime(full_year, day_of_year);
+        }
+    }
+    Utc::now() // Fallback
+}
+
+/// The coming `search_days` of passes, reusing those predicted by earlier runs
 fn predict_passes(
+    satellite: &Satellite,
+    observer: &Observer,
+    config: &config::PredictionConfig,
+    cache: &PassCache,
+) -> Result<Vec<SatellitePass>> {
+    let start_time = Utc::now();
+    let end_time = start_time + Duration::days(config.search_days as i64);
+    let filter = config.filter_for(&satellite.name);
+    cache.passes(
+        &satellite.name,
+        satellite.epoch,
+        &filter,
+        (start_time, end_time),
+        config.num_passes,
+        |window, max_passes| {
+            predict_passes_between(
+                &satellite.elements,
+                &satellite.epoch,
+                observer,
+                config,
+                &filter,
+                window,
+                max_passes,
+            )
+        },
+    )
+}
+
+/// Passes that begin within `window`, up to `max_passes`
+fn predict_passes_between(
     elements: &Elements,
     tle_epoch: &DateTime<Utc>,
     observer: &Observer,
     config: &config::PredictionConfig,
+    filter: &config::PassFilter,
+    window: (DateTime<Utc>, DateTime<Utc>),
+    max_passes: usize,
 ) -> Result<Vec<SatellitePass>> {
     let mut passes = Vec::new();
-    let start_time = Utc::now();
-    let end_time = start_time + Duration::days(config.search_days as i64);
+    let (start_time, end_time) = window;
     let observer_ecef = observer.to_ecef();
 
     // Check if TLE is too old
@@ -606,12 +2319,15 @@ fn predict_passes(
     let mut aos_azimuth = 0.0;
     let mut max_azimuth = 0.0;
     let mut max_range = 0.0;
+    let mut max_sat_pos_km = nalgebra::Vector3::zeros();
+    let mut min_moon_separation = f64::MAX;
+    let mut moon_illumination = 0.0;
 
     let constants = Constants::from_elements(elements)?;
     let mut consecutive_errors = 0u32;
     const MAX_CONSECUTIVE_ERRORS: u32 = 10;
 
-    while current_time < end_time && passes.len() < config.num_passes {
+    while current_time < end_time && passes.len() < max_passes {
         // Convert current time to minutes since TLE epoch
         let minutes_since_epoch = calculate_minutes_since_epoch_simple(tle_epoch, current_time);
 
@@ -626,7 +2342,7 @@ fn predict_passes(
                 if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
                     break;
                 }
-                current_time = current_time + time_step;
+                current_time += time_step;
                 continue;
             }
         };
@@ -647,8 +2363,24 @@ fn predict_passes(
             observer.longitude,
         );
 
-        // Check if satellite is above horizon
-        if look_angles.elevation >= config.min_elevation {
+        // Check if satellite is above horizon and clear of local obstructions
+        let min_elevation = config
+            .min_elevation
+            .max(observer.horizon.min_elevation_at(look_angles.azimuth));
+        if look_angles.elevation >= min_elevation {
+            // Track closest approach to the Moon along the pass
+            let moon = astro::moon_look_angles(current_time, observer);
+            let moon_separation = astro::angular_separation(
+                look_angles.azimuth,
+                look_angles.elevation,
+                moon.azimuth,
+                moon.elevation,
+            );
+            if !in_pass || moon_separation < min_moon_separation {
+                min_moon_separation = moon_separation;
+                moon_illumination = astro::moon_illumination(current_time);
+            }
+
             if !in_pass {
                 in_pass = true;
                 pass_start = current_time;
@@ -657,12 +2389,14 @@ fn predict_passes(
                 max_elevation_time = current_time;
                 max_azimuth = look_angles.azimuth;
                 max_range = look_angles.range;
+                max_sat_pos_km = sat_pos / 1000.0;
             } else {
                 if look_angles.elevation > max_elevation {
                     max_elevation = look_angles.elevation;
                     max_elevation_time = current_time;
                     max_azimuth = look_angles.azimuth;
                     max_range = look_angles.range;
+                    max_sat_pos_km = sat_pos / 1000.0;
                 }
             }
         } else if in_pass {
@@ -676,17 +2410,234 @@ fn predict_passes(
                 los_azimuth: look_angles.azimuth,
                 duration_seconds: (current_time - pass_start).num_seconds() as f64,
                 max_range_km: max_range,
+                sun_elevation: astro::sun_look_angles(max_elevation_time, observer).elevation,
+                sunlit: astro::is_sunlit(&max_sat_pos_km, max_elevation_time),
+                min_moon_separation,
+                moon_illumination,
             };
-            passes.push(pass);
+            if pass.matches(filter) {
+                passes.push(pass);
+            }
             in_pass = false;
         }
 
-        current_time = current_time + time_step;
+        current_time += time_step;
+    }
+
+    Ok(passes)
+}
+
+/// Every pass of `satellites` visible between `start` and `end`, ordered by AOS
+fn passes_in_window(
+    satellites: &[Satellite],
+    observer: &Observer,
+    config: &config::PredictionConfig,
+    cache: &PassCache,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<(String, SatellitePass)>> {
+    if end <= start {
+        anyhow::bail!("the window must end after it starts");
+    }
+    if end - start > Duration::days(MAX_WINDOW_DAYS) {
+        anyhow::bail!("the window can be at most {} days long", MAX_WINDOW_DAYS);
     }
 
+    // Start searching early so a pass already underway at the start is reported
+    // with its real AOS rather than one cut off at the window edge
+    let search_start = start - Duration::hours(1);
+    let mut passes = Vec::new();
+    for satellite in satellites {
+        // A satellite whose elements are too far from the window has no passes to report
+        let filter = config.filter_for(&satellite.name);
+        let found = cache.passes(
+            &satellite.name,
+            satellite.epoch,
+            &filter,
+            (search_start, end),
+            usize::MAX,
+            |window, max_passes| {
+                predict_passes_between(
+                    &satellite.elements,
+                    &satellite.epoch,
+                    observer,
+                    config,
+                    &filter,
+                    window,
+                    max_passes,
+                )
+            },
+        );
+        if let Ok(found) = found {
+            passes.extend(
+                found
+                    .into_iter()
+                    .filter(|pass| pass.los_time > start)
+                    .map(|pass| (satellite.name.clone(), pass)),
+            );
+        }
+    }
+    passes.sort_by_key(|(_, pass)| pass.aos_time);
     Ok(passes)
 }
 
+/// Passes in a window for each of several observers, in the order given
+fn passes_for_observers(
+    satellites: &[Satellite],
+    observers: &[Observer],
+    config: &config::PredictionConfig,
+    database: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<Vec<(String, SatellitePass)>>> {
+    observers
+        .iter()
+        .map(|observer| {
+            let cache = PassCache::new(database, observer, config);
+            passes_in_window(satellites, observer, config, &cache, start, end)
+        })
+        .collect()
+}
+
+/// An observer given to `passes --observer`: a `[[profiles]]` entry by name,
+/// or "NAME=LAT,LON" or "NAME=GRID" for a site not in the configuration
+fn observer_from_spec(config_path: &str, spec: &str) -> Result<Observer> {
+    let mut config = Config::load(config_path)?;
+    let Some((name, location)) = spec.split_once('=') else {
+        config.apply_profile(spec)?;
+        return build_observer(&config);
+    };
+    let site = &mut config.observer;
+    site.name = name.trim().to_string();
+    // The configured site's altitude, tile, horizon and noise belong to it alone
+    site.altitude = None;
+    site.dem_file = None;
+    site.horizon_file = None;
+    site.noise_file = None;
+    match location.split_once(',') {
+        Some((latitude, longitude)) => {
+            let parse = |text: &str| {
+                text.trim()
+                    .parse::<f64>()
+                    .map_err(|_| anyhow::anyhow!("'{}' is not NAME=LAT,LON or NAME=GRID", spec))
+            };
+            site.latitude = Some(parse(latitude)?);
+            site.longitude = Some(parse(longitude)?);
+            site.grid = None;
+        }
+        None => {
+            site.latitude = None;
+            site.longitude = None;
+            site.grid = Some(location.trim().to_string());
+        }
+    }
+    build_observer(&config)
+}
+
+/// Passes seen from several observers side by side: each one's maximum
+/// elevation and local AOS time, and the observer with the best view
+fn print_observer_comparison(observers: &[Observer], sets: &[Vec<(String, SatellitePass)>]) {
+    let rows = pass_prediction::compare_observers(sets);
+    println!("Comparison");
+    if rows.is_empty() {
+        println!("No passes in the window");
+        return;
+    }
+    let widths: Vec<usize> = observers.iter().map(|o| o.name.chars().count().max(11)).collect();
+    let mut header = format!("{:<24} {:<16}", "Satellite", "First AOS");
+    for (observer, width) in observers.iter().zip(&widths) {
+        header.push_str(&format!(" {:<width$}", observer.name, width = width));
+    }
+    println!("{}  Best", header);
+    for row in rows {
+        let mut line = format!(
+            "{:<24} {:<16}",
+            row.satellite,
+            row.first_aos().with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+        );
+        for (pass, width) in row.passes.iter().zip(&widths) {
+            let cell = pass.as_ref().map_or(format!("{:>4}", "-"), |pass| {
+                format!(
+                    "{:>4.0}° {}",
+                    pass.max_elevation,
+                    pass.aos_time.with_timezone(&chrono::Local).format("%H:%M")
+                )
+            });
+            line.push_str(&format!(" {:<width$}", cell, width = width));
+        }
+        let best = row
+            .passes
+            .iter()
+            .zip(observers)
+            .filter_map(|(pass, observer)| Some((pass.as_ref()?.max_elevation, &observer.name)))
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, name)| name.as_str())
+            .unwrap_or_default();
+        println!("{}  {}", line, best);
+    }
+}
+
+/// Passes named by their satellites' aliases, for output; matching is done
+/// on the TLE names before this
+fn by_alias(
+    passes: Vec<(String, SatellitePass)>,
+    aliases: &HashMap<String, String>,
+) -> Vec<(String, SatellitePass)> {
+    passes
+        .into_iter()
+        .map(|(name, pass)| (aliases.get(&name).cloned().unwrap_or(name), pass))
+        .collect()
+}
+
+fn print_window_passes(passes: &[(String, SatellitePass)]) {
+    if passes.is_empty() {
+        println!("No passes in the window");
+        return;
+    }
+    println!(
+        "{:<24} {:<19} {:<8} {:>6} {:>5} {:>5} {:>5}  Lighting",
+        "Satellite", "AOS", "LOS", "Max El", "AOS", "LOS", "Mins"
+    );
+    for (name, pass) in passes {
+        let lighting = match pass.is_visual() {
+            true => format!("{}, visible", pass.twilight().as_str()),
+            false => pass.twilight().as_str().to_string(),
+        };
+        println!(
+            "{:<24} {:<19} {:<8} {:>5.1}° {:>4.0}° {:>4.0}° {:>5.1}  {}",
+            name,
+            pass.aos_time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"),
+            pass.los_time.with_timezone(&chrono::Local).format("%H:%M:%S"),
+            pass.max_elevation,
+            pass.aos_azimuth,
+            pass.los_azimuth,
+            pass.duration_minutes(),
+            lighting
+        );
+    }
+}
+
+fn print_conjunctions(conjunctions: &[conjunction::Conjunction]) {
+    if conjunctions.is_empty() {
+        println!("No close approaches in the window");
+        return;
+    }
+    println!(
+        "{:<23} {:<24} {:<24} {:>9} {:>9}",
+        "TCA", "Object", "Object", "Miss km", "Rel km/s"
+    );
+    for conjunction in conjunctions {
+        println!(
+            "{:<23} {:<24} {:<24} {:>9.3} {:>9.3}",
+            conjunction.tca.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S%.3f"),
+            conjunction.first,
+            conjunction.second,
+            conjunction.miss_km,
+            conjunction.relative_velocity_km_s
+        );
+    }
+}
+
 fn calculate_minutes_since_epoch_simple(tle_epoch: &DateTime<Utc>, time: DateTime<Utc>) -> f64 {
     let duration = time.signed_duration_since(*tle_epoch);
     duration.num_milliseconds() as f64 / 60000.0
@@ -703,75 +2654,906 @@ fn year_day_to_datetime(year: i32, day_of_year: f64) -> DateTime<Utc> {
     year_start + Duration::milliseconds((days_into_year * 86400000.0) as i64)
 }
 
-fn update_alerts(app_state: &mut AppState) {
-    if !app_state.config.alerts.enabled {
-        return;
+/// Alerts, conflicts, AOS, LOS and elevation milestones, each reported once
+struct PassEvents {
+    config: config::AnnouncerConfig,
+    detector: Announcer,
+    reported: HashSet<(String, DateTime<Utc>)>,
+}
+
+impl PassEvents {
+    fn new(app_state: &AppState) -> Self {
+        Self {
+            config: app_state.config.announcer.clone().unwrap_or_default(),
+            detector: Announcer::default(),
+            reported: HashSet::new(),
+        }
     }
 
-    let now = Utc::now();
+    /// Events since the last call, as sentences; positions must be fresh
+    fn collect(&mut self, app_state: &AppState, now: DateTime<Utc>) -> Vec<String> {
+        let mut lines = Vec::new();
+        for alert in &app_state.alerts {
+            if self
+                .reported
+                .insert((alert.satellite_name.clone(), alert.pass.aos_time))
+            {
+                lines.push(format!(
+                    "Alert: {} pass in {}, maximum elevation {:.0} degrees, \
+                     rising in the {}",
+                    display_name(app_state, &alert.satellite_name),
+                    alert.countdown(now),
+                    alert.pass.max_elevation,
+                    ui::cardinal_words(alert.pass.aos_azimuth)
+                ));
+            }
+        }
+        for conflict in &app_state.conflicts {
+            if self
+                .reported
+                .insert((conflict.loser.clone(), conflict.winner_pass.aos_time))
+            {
+                lines.push(format!(
+                    "Conflict: {} and {} passes overlap; {} has priority",
+                    display_name(app_state, &conflict.winner),
+                    display_name(app_state, &conflict.loser),
+                    display_name(app_state, &conflict.winner)
+                ));
+            }
+        }
+        let positions = &app_state.current_positions;
+        lines.extend(
+            self.detector
+                .events(&self.config, &app_state.satellites, positions, &app_state.aliases, now),
+        );
+        self.reported
+            .retain(|(_, aos)| *aos > now - Duration::days(1));
+        lines
+    }
+}
+
+/// Print pass events as plain lines until interrupted, for screen readers and
+/// braille displays that follow a terminal's output rather than a full-screen UI
+fn run_event_stream(app_state: &mut AppState) -> Result<()> {
+    let mut events = PassEvents::new(app_state);
+
+    println!(
+        "Watching {} satellites from {}. Press Control C to stop.",
+        app_state.satellites.len(),
+        app_state.observer.name
+    );
+    loop {
+        let now = Utc::now();
+        refresh_positions(app_state, now);
+
+        let time = now.with_timezone(&chrono::Local).format("%H:%M:%S");
+        for line in events.collect(app_state, now) {
+            println!("{} {}", time, line);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(
+            app_state.config.display.refresh_rate,
+        ));
+    }
+}
+
+/// Serve JSON-RPC requests from automation until a client sends `quit` (or,
+/// on stdin, closes it), tracking the selected satellite as the TUI would and
+/// sending pass events to every client
+fn run_control(app_state: &mut AppState, channel: control::ControlChannel) -> Result<()> {
+    let mut events = PassEvents::new(app_state);
+    let mut tracked = None;
+    let refresh = std::time::Duration::from_millis(app_state.config.display.refresh_rate);
+    let mut last_refresh: Option<std::time::Instant> = None;
+
+    loop {
+        if last_refresh.is_none_or(|last| last.elapsed() >= refresh) {
+            last_refresh = Some(std::time::Instant::now());
+            let now = Utc::now();
+            refresh_positions(app_state, now);
+            if app_state.auto_follow {
+                follow_most_relevant(app_state, now);
+            }
+            track_rotator(app_state);
+            update_rig(app_state);
+            update_sdr(app_state);
+            #[cfg(feature = "recording")]
+            record_pass_audio(app_state);
+            publish_home_assistant(app_state, now);
+            update_calendar(app_state, now);
+
+            let time = now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+            for line in events.collect(app_state, now) {
+                let params = serde_json::json!({ "time": time, "message": line });
+                channel.broadcast(&control::notification("event", params));
+            }
+            let selected = app_state
+                .satellites
+                .get(app_state.selected_satellite)
+                .map(|sat| sat.name.clone());
+            if selected != tracked {
+                let params = serde_json::json!({ "satellite": selected });
+                channel.broadcast(&control::notification("selected", params));
+                tracked = selected;
+            }
+        }
+
+        for incoming in channel.poll(refresh / 4) {
+            let (client, line) = match incoming {
+                control::Incoming::Line(client, line) => (client, line),
+                control::Incoming::Closed(control::STDIO_CLIENT) => {
+                    stop_tracking(app_state);
+                    return Ok(());
+                }
+                control::Incoming::Closed(client) => {
+                    channel.disconnect(client);
+                    continue;
+                }
+            };
+            let request = match control::parse_request(&line) {
+                Ok(request) => request,
+                Err(reply) => {
+                    channel.send(client, &reply);
+                    continue;
+                }
+            };
+            let outcome = handle_control_request(app_state, &request);
+            if let Some(id) = request.id {
+                let reply = match &outcome {
+                    Ok(result) => control::result_response(Some(id), result.clone()),
+                    Err(error) => control::error_response(Some(id), error),
+                };
+                channel.send(client, &reply);
+            }
+            if request.method == "quit" && outcome.is_ok() {
+                stop_tracking(app_state);
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Predict the passes the calendar feeds serve once they are due
+fn update_calendar(app_state: &mut AppState, now: DateTime<Utc>) {
+    let Some(server) = &app_state.calendar else {
+        return;
+    };
+    if !server.needs_update(now) {
+        return;
+    }
+    let prediction = &app_state.config.prediction;
+    let days = (prediction.search_days.ceil() as i64).clamp(1, MAX_WINDOW_DAYS);
+    let cache = PassCache::new(&app_state.database, &app_state.observer, prediction);
+    let passes = passes_in_window(
+        &app_state.satellites,
+        &app_state.observer,
+        prediction,
+        &cache,
+        now,
+        now + Duration::days(days),
+    );
+    match passes {
+        Ok(passes) => server.update(
+            &app_state.observer.name,
+            by_alias(passes, &app_state.aliases),
+            now,
+        ),
+        Err(e) => eprintln!("Calendar: {:#}", e),
+    }
+}
+
+fn stop_tracking(app_state: &mut AppState) {
+    for rotator in app_state.rotators.iter_mut() {
+        rotator.tracking = false;
+        rotator.status = "Idle".to_string();
+    }
+}
+
+/// Carry out one control request, returning its result
+fn handle_control_request(
+    app_state: &mut AppState,
+    request: &control::Request,
+) -> std::result::Result<serde_json::Value, control::RpcError> {
+    use control::RpcError;
+    use serde_json::json;
+
+    // The named satellite, or the selected one
+    let satellite_index = |app_state: &AppState| -> std::result::Result<usize, RpcError> {
+        match request.str_param("satellite")? {
+            Some(name) => app_state
+                .satellites
+                .iter()
+                .position(|sat| sat.name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    RpcError::new(
+                        control::INVALID_PARAMS,
+                        format!("'{}' is not among the tracked satellites", name),
+                    )
+                }),
+            None => Ok(app_state.selected_satellite),
+        }
+    };
+    let position_json = |position: &SatellitePosition| {
+        json!({
+            "satellite": position.name,
+            "azimuth": position.azimuth,
+            "elevation": position.elevation,
+            "range_km": position.range_km,
+            "range_rate_km_s": position.range_rate_km_s,
+            "latitude": position.latitude,
+            "longitude": position.longitude,
+            "altitude_km": position.altitude_km,
+            "downlink_mhz": position.doppler.as_ref().map(|d| d.downlink_observed_mhz),
+            "uplink_mhz": position.doppler.as_ref().map(|d| d.uplink_corrected_mhz),
+        })
+    };
+
+    match request.method.as_str() {
+        "satellites" => Ok(app_state
+            .current_positions
+            .iter()
+            .map(position_json)
+            .collect()),
+        "status" => {
+            let satellite = app_state.satellites.get(app_state.selected_satellite);
+            let position = satellite.and_then(|sat| {
+                app_state
+                    .current_positions
+                    .iter()
+                    .find(|pos| pos.name == sat.name)
+            });
+            Ok(json!({
+                "selected": satellite.map(|sat| &sat.name),
+                "auto_follow": app_state.auto_follow,
+                "tracking": app_state.rotators.iter().any(|r| r.tracking),
+                "rotators": app_state
+                    .rotators
+                    .iter()
+                    .map(|r| json!({ "name": r.config.name, "status": r.status }))
+                    .collect::<Vec<_>>(),
+                "rig": app_state.rig.as_ref().map(|rig| &rig.status),
+                "sdr": app_state.sdr.as_ref().map(|sdr| &sdr.status),
+                "recorder": recorder_status(app_state),
+                "position": position.map(position_json),
+            }))
+        }
+        "select" => {
+            if request.str_param("satellite")?.is_none() {
+                return Err(RpcError::new(control::INVALID_PARAMS, "missing 'satellite'"));
+            }
+            app_state.selected_satellite = satellite_index(app_state)?;
+            app_state.auto_follow = false;
+            Ok(json!(app_state.satellites[app_state.selected_satellite].name))
+        }
+        "auto_follow" => {
+            let enabled = match request.params.get("enabled") {
+                None => true,
+                Some(enabled) => enabled.as_bool().ok_or_else(|| {
+                    RpcError::new(control::INVALID_PARAMS, "'enabled' must be true or false")
+                })?,
+            };
+            app_state.auto_follow = enabled;
+            Ok(json!(enabled))
+        }
+        "track" => {
+            if app_state.rotators.is_empty() {
+                return Err(RpcError::new(
+                    control::INVALID_REQUEST,
+                    "no rotator is configured",
+                ));
+            }
+            if request.str_param("satellite")?.is_some() {
+                app_state.selected_satellite = satellite_index(app_state)?;
+                app_state.auto_follow = false;
+            }
+            let name = app_state
+                .satellites
+                .get(app_state.selected_satellite)
+                .ok_or_else(|| RpcError::new(control::INVALID_REQUEST, "no satellite is tracked"))?
+                .name
+                .clone();
+            for rotator in app_state.rotators.iter_mut() {
+                rotator.tracking = true;
+                rotator.status = "Tracking".to_string();
+            }
+            Ok(json!(name))
+        }
+        "stop" => {
+            stop_tracking(app_state);
+            Ok(json!(null))
+        }
+        "next_pass" => {
+            let satellite = app_state
+                .satellites
+                .get(satellite_index(app_state)?)
+                .ok_or_else(|| RpcError::new(control::INVALID_REQUEST, "no satellite is tracked"))?;
+            Ok(satellite
+                .get_current_pass(Utc::now())
+                .or_else(|| satellite.get_next_pass())
+                .map(|pass| {
+                    json!({
+                        "satellite": satellite.name,
+                        "aos": pass.aos_time.to_rfc3339(),
+                        "los": pass.los_time.to_rfc3339(),
+                        "max_elevation": pass.max_elevation,
+                        "max_elevation_time": pass.max_elevation_time.to_rfc3339(),
+                        "aos_azimuth": pass.aos_azimuth,
+                        "los_azimuth": pass.los_azimuth,
+                        "visible": pass.is_visual(),
+                    })
+                })
+                .unwrap_or(json!(null)))
+        }
+        "quit" => Ok(json!(null)),
+        method => Err(RpcError::new(
+            control::METHOD_NOT_FOUND,
+            format!("unknown method '{}'", method),
+        )),
+    }
+}
+
+/// Time ahead the positions table's elevation sparkline covers, and its width
+pub const OUTLOOK_MINUTES: i64 = 60;
+pub const OUTLOOK_COLUMNS: usize = 15;
+
+/// Whether something refreshed at `last` is due again after `interval_ms`.
+/// A tenth of the interval early counts, so a loop running at the same rate
+/// doesn't skip every other refresh through timing jitter.
+fn refresh_due(last: Option<DateTime<Utc>>, interval_ms: u64, now: DateTime<Utc>) -> bool {
+    let early = Duration::milliseconds(interval_ms as i64 * 9 / 10);
+    last.is_none_or(|last| now < last || now - last >= early)
+}
+
+/// Recompute every satellite's position, Doppler, alerts and announcements
+fn refresh_positions(app_state: &mut AppState, now: DateTime<Utc>) {
+    refresh_passes(app_state, now);
+    let display = &app_state.config.display;
+    if !refresh_due(app_state.last_position_refresh, display.position_refresh_ms(), now) {
+        return;
+    }
+    app_state.last_position_refresh = Some(now);
+    app_state.current_positions = app_state
+        .satellites
+        .iter()
+        .filter_map(|sat| match resting_position(app_state, sat, now) {
+            Some(position) => Some(position.clone()),
+            None => sat.calculate_position(now, &app_state.observer).ok(),
+        })
+        .collect();
+
+    // Add radio calculations if enabled
+    if app_state.config.radio.enabled {
+        let orbits = orbit_classes(&app_state.satellites);
+        for pos in app_state.current_positions.iter_mut() {
+            let (mut downlink_mhz, uplink_mhz) = app_state.config.radio.frequencies_for(&pos.name);
+            downlink_mhz +=
+                radio::downlink_offset_hz(&app_state.frequency_offsets, &pos.name, downlink_mhz)
+                    / 1e6;
+            pos.doppler = Some(calculate_doppler_shift(pos, downlink_mhz, uplink_mhz));
+            let orbit = orbits.get(pos.name.as_str()).copied().unwrap_or_default();
+            let station = station_link(
+                &app_state.config,
+                &app_state.antenna_patterns,
+                &app_state.observer.noise,
+                pos,
+                downlink_mhz,
+            );
+            let mut window = evaluate_communication_window(pos, orbit, downlink_mhz, station);
+            if !app_state
+                .schedule
+                .is_operating(&pos.name, now, pos.latitude, pos.longitude)
+            {
+                window.is_viable = false;
+                window.reason = "Transponder off by its operating schedule".to_string();
+            }
+            pos.comm_window = Some(window);
+        }
+    }
+    if refresh_due(
+        app_state.last_sky_map_refresh,
+        app_state.config.display.sky_map_refresh_ms(),
+        now,
+    ) {
+        app_state.last_sky_map_refresh = Some(now);
+        app_state.sky_map_positions = app_state.current_positions.clone();
+    }
+    if refresh_due(app_state.last_outlook_refresh, 60_000, now) {
+        app_state.last_outlook_refresh = Some(now);
+        app_state.elevation_outlooks = app_state
+            .satellites
+            .iter()
+            .map(|sat| {
+                let span = Duration::minutes(OUTLOOK_MINUTES);
+                let observer = &app_state.observer;
+                let outlook = sat.elevation_outlook(observer, now, span, OUTLOOK_COLUMNS);
+                (sat.name.clone(), outlook)
+            })
+            .collect();
+    }
+
+    record_history(app_state, now);
+    update_alerts(app_state);
+    app_state.announcer.update(
+        app_state.config.announcer.as_ref(),
+        &app_state.satellites,
+        &app_state.current_positions,
+        &app_state.aliases,
+        now,
+    );
+    // An attached daemon runs the scripts
+    if !attached(app_state) {
+        run_scripts(app_state, now);
+    }
+}
+
+/// Whether a pass is under way or begins within the power saver's `wake_minutes`
+fn pass_near(app_state: &AppState, satellite: &Satellite, now: DateTime<Utc>) -> bool {
+    let wake = now + Duration::seconds((app_state.config.power.wake_minutes * 60.0) as i64);
+    satellite
+        .passes
+        .iter()
+        .any(|pass| pass.aos_time <= wake && pass.los_time > now)
+}
+
+/// Whether the power saver has slowed down because no satellite has a pass near
+pub fn power_saving(app_state: &AppState, now: DateTime<Utc>) -> bool {
+    app_state.config.power.saver
+        && !app_state
+            .satellites
+            .iter()
+            .any(|satellite| pass_near(app_state, satellite, now))
+}
+
+/// How long to wait for a key between redraws
+fn redraw_interval_ms(app_state: &AppState) -> u64 {
+    let refresh_rate = app_state.config.display.refresh_rate;
+    let now = Utc::now();
+    if app_state.alerts.iter().any(|alert| alert.in_final_countdown(now)) {
+        return refresh_rate.min(FINAL_COUNTDOWN_TICK_MS);
+    }
+    if power_saving(app_state, now) {
+        app_state.config.power.idle_refresh_ms.max(refresh_rate)
+    } else {
+        refresh_rate
+    }
+}
+
+/// Kind of orbit of each satellite, by name, for judging how workable it is
+fn orbit_classes(satellites: &[Satellite]) -> HashMap<&str, radio::OrbitClass> {
+    satellites
+        .iter()
+        .map(|sat| (sat.name.as_str(), sat.orbit_class()))
+        .collect()
+}
+
+/// With the power saver on, a satellite below the horizon and not about to
+/// rise keeps its last position for `below_horizon_seconds`
+fn resting_position<'a>(
+    app_state: &'a AppState,
+    satellite: &Satellite,
+    now: DateTime<Utc>,
+) -> Option<&'a SatellitePosition> {
+    let power = &app_state.config.power;
+    if !power.saver || pass_near(app_state, satellite, now) {
+        return None;
+    }
+    let interval = Duration::seconds(power.below_horizon_seconds as i64);
+    app_state
+        .current_positions
+        .iter()
+        .find(|pos| pos.name == satellite.name)
+        .filter(|pos| !pos.is_visible && now >= pos.time && now - pos.time < interval)
+}
+
+/// Predict the pass tables again every `pass_refresh_seconds`, so passes that
+/// have ended give way to new ones during a long session
+fn refresh_passes(app_state: &mut AppState, now: DateTime<Utc>) {
+    let seconds = app_state.config.display.pass_refresh_seconds;
+    if seconds == 0 || now - app_state.last_pass_refresh < Duration::seconds(seconds as i64) {
+        return;
+    }
+    app_state.last_pass_refresh = now;
+    let cache = PassCache::new(
+        &app_state.database,
+        &app_state.observer,
+        &app_state.config.prediction,
+    );
+    for satellite in app_state.satellites.iter_mut() {
+        if let Ok(passes) = predict_passes(
+            satellite,
+            &app_state.observer,
+            &app_state.config.prediction,
+            &cache,
+        ) {
+            satellite.passes = passes;
+        }
+    }
+}
+
+/// Record the positions of satellites being followed through a pass, every
+/// `interval_seconds`, for plotting from the pass log later
+fn record_history(app_state: &mut AppState, now: DateTime<Utc>) {
+    let history = &app_state.config.history;
+    // An attached daemon records them
+    if !history.record || attached(app_state) {
+        return;
+    }
+    let interval = Duration::seconds(history.interval_seconds.max(1) as i64);
+    if app_state
+        .last_history_record
+        .is_some_and(|last| now - last < interval)
+    {
+        return;
+    }
+    app_state.last_history_record = Some(now);
+
+    let selected = app_state
+        .current_positions
+        .get(app_state.selected_satellite)
+        .map(|pos| pos.name.clone());
+    let recorded_at = now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    for pos in &app_state.current_positions {
+        let followed = if history.satellites.is_empty() {
+            selected.as_deref() == Some(pos.name.as_str())
+        } else {
+            history.satellites.iter().any(|name| name == &pos.name)
+        };
+        if !followed || !pos.is_visible {
+            continue;
+        }
+        let (downlink_mhz, uplink_mhz) = app_state.config.radio.frequencies_for(&pos.name);
+        let doppler_hz = (downlink_mhz > 0.0)
+            .then(|| calculate_doppler_shift(pos, downlink_mhz, uplink_mhz).downlink_shift_hz);
+        let record = PositionRecord {
+            satellite_name: pos.name.clone(),
+            recorded_at: recorded_at.clone(),
+            azimuth: pos.azimuth,
+            elevation: pos.elevation,
+            range_km: pos.range_km,
+            range_rate_km_s: pos.range_rate_km_s,
+            doppler_hz,
+            signal_db: app_state
+                .rig
+                .as_ref()
+                .and_then(|rig| rig.strength_for(&pos.name)),
+        };
+        match app_state.database.record_position(&record) {
+            Ok(()) => app_state.history_failing = false,
+            Err(e) if !app_state.history_failing => {
+                app_state.history_failing = true;
+                app_state.status_message =
+                    Some(format!("Could not record position history: {}", e));
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Call the event scripts and carry out what they ask for
+fn run_scripts(app_state: &mut AppState, now: DateTime<Utc>) {
+    if app_state.scripts.is_empty() {
+        return;
+    }
+    let alerts: Vec<(String, SatellitePass)> = app_state
+        .alerts
+        .iter()
+        .map(|alert| (alert.satellite_name.clone(), alert.pass.clone()))
+        .collect();
+    let (config, groups) = (&app_state.config, &app_state.groups);
+    let metadata = |satellite: &Satellite| {
+        let mut map = rhai::Map::new();
+        let (downlink_mhz, uplink_mhz) = config.radio.frequencies_for(&satellite.name);
+        let bands: rhai::Array = config
+            .satellite_bands(&satellite.name)
+            .into_iter()
+            .map(|band| band.to_string().into())
+            .collect();
+        let groups: rhai::Array = groups
+            .iter()
+            .filter(|g| g.satellites.iter().any(|s| s == &satellite.name))
+            .map(|g| g.name.clone().into())
+            .collect();
+        map.insert("satellite".into(), satellite.name.clone().into());
+        let norad_id = celestrak::norad_id(&satellite.tle_line1).map_or(-1, i64::from);
+        map.insert("norad_id".into(), norad_id.into());
+        map.insert("downlink_mhz".into(), downlink_mhz.into());
+        map.insert("uplink_mhz".into(), uplink_mhz.into());
+        map.insert("bands".into(), bands.into());
+        map.insert("groups".into(), groups.into());
+        map
+    };
+    let actions = app_state.scripts.update(
+        &app_state.satellites,
+        &app_state.current_positions,
+        &alerts,
+        metadata,
+        now,
+    );
+
+    let index_of = |app_state: &AppState, name: &str| {
+        app_state
+            .satellites
+            .iter()
+            .position(|sat| sat.name.eq_ignore_ascii_case(name))
+    };
+    for action in actions {
+        match action {
+            scripting::Action::Track(name) | scripting::Action::Select(name)
+                if index_of(app_state, &name).is_none() =>
+            {
+                app_state
+                    .scripts
+                    .push_log(format!("'{}' is not among the tracked satellites", name));
+            }
+            scripting::Action::Track(name) => {
+                app_state.selected_satellite = index_of(app_state, &name).unwrap_or_default();
+                app_state.auto_follow = false;
+                for rotator in app_state.rotators.iter_mut() {
+                    rotator.tracking = true;
+                    rotator.status = "Tracking".to_string();
+                }
+            }
+            scripting::Action::Select(name) => {
+                app_state.selected_satellite = index_of(app_state, &name).unwrap_or_default();
+                app_state.auto_follow = false;
+            }
+            scripting::Action::Stop => stop_tracking(app_state),
+            scripting::Action::Announce(text) => match &app_state.config.announcer {
+                Some(config) => app_state.announcer.announce(config, &text),
+                None => app_state
+                    .scripts
+                    .push_log(format!("No [announcer] configured to say '{}'", text)),
+            },
+        }
+    }
+}
+
+/// Select the satellite highest above the horizon, or failing that the next to
+/// rise, passing over any that lost a conflict to a pass still to come or under way
+fn follow_most_relevant(app_state: &mut AppState, now: DateTime<Utc>) {
+    let mut ranking =
+        satellite::rank_by_relevance(&app_state.satellites, &app_state.current_positions, now);
+    ranking.retain(|&index| is_shown(app_state, &app_state.satellites[index].name));
+    if let Some(&index) = ranking
+        .iter()
+        .find(|&&index| {
+            !scheduler::yields(&app_state.conflicts, &app_state.satellites[index].name, now)
+        })
+        .or(ranking.first())
+    {
+        app_state.selected_satellite = index;
+    }
+}
+
+/// The alias a satellite is shown by, or its TLE name when it has none
+pub fn display_name<'a>(app_state: &'a AppState, satellite_name: &'a str) -> &'a str {
+    app_state
+        .aliases
+        .get(satellite_name)
+        .map_or(satellite_name, String::as_str)
+}
+
+/// Whether a satellite is in the group picked with a number key, or no group is
+pub fn is_shown(app_state: &AppState, satellite_name: &str) -> bool {
+    let Some(filter) = &app_state.group_filter else {
+        return true;
+    };
+    app_state.groups.iter().any(|group| {
+        group.name.eq_ignore_ascii_case(filter)
+            && group.satellites.iter().any(|name| name == satellite_name)
+    })
+}
+
+/// Indices of the satellites shown in the tracked list, in order
+fn shown_indices(app_state: &AppState) -> Vec<usize> {
+    (0..app_state.satellites.len())
+        .filter(|&index| is_shown(app_state, &app_state.satellites[index].name))
+        .collect()
+}
+
+/// The shown satellite whose next pass begins soonest
+fn soonest_aos(app_state: &AppState, now: DateTime<Utc>) -> Option<usize> {
+    shown_indices(app_state)
+        .into_iter()
+        .filter_map(|index| {
+            let satellite = &app_state.satellites[index];
+            let pass = satellite.passes.iter().find(|pass| pass.aos_time > now)?;
+            Some((index, pass.aos_time))
+        })
+        .min_by_key(|&(_, aos)| aos)
+        .map(|(index, _)| index)
+}
+
+/// The shown satellite highest in the sky right now, if any is up
+fn highest_now(app_state: &AppState) -> Option<usize> {
+    shown_indices(app_state)
+        .into_iter()
+        .filter_map(|index| {
+            let name = &app_state.satellites[index].name;
+            let position = app_state.current_positions.iter().find(|pos| &pos.name == name)?;
+            position.is_visible.then_some((index, position.elevation))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(index, _)| index)
+}
+
+/// Show only the group bound to a number key under `[display.group_keys]`.
+/// 0, or the key of the group already shown, shows every satellite again.
+fn select_group_key(app_state: &mut AppState, key: char) {
+    let group = app_state.config.display.group_keys.get(&key.to_string()).cloned();
+    app_state.group_filter = match group {
+        _ if key == '0' => None,
+        None => return,
+        Some(group) if app_state.group_filter.as_ref() == Some(&group) => None,
+        Some(group) => Some(group),
+    };
+    let shown = shown_indices(app_state);
+    if !shown.contains(&app_state.selected_satellite) {
+        if let Some(&first) = shown.first() {
+            app_state.selected_satellite = first;
+        }
+    }
+}
+
+/// Feature the next of the most relevant satellites once the current one has had its turn
+fn rotate_kiosk(app_state: &mut AppState, now: DateTime<Utc>) {
+    let config = &app_state.config.kiosk;
+    let state = &mut app_state.kiosk_state;
+    let position = match state.featured_since {
+        None => 0,
+        Some(since) if since.elapsed().as_secs() >= config.rotate_seconds => state.position + 1,
+        Some(_) => return,
+    };
+    let mut ranking = satellite::rank_by_relevance(
+        &app_state.satellites,
+        &app_state.current_positions,
+        now,
+    );
+    ranking.truncate(config.rotate_among.max(1));
+    if ranking.is_empty() {
+        return;
+    }
+    state.position = position % ranking.len();
+    state.featured_since = Some(std::time::Instant::now());
+    app_state.selected_satellite = ranking[state.position];
+}
+
+fn update_alerts(app_state: &mut AppState) {
+    if !app_state.config.alerts.enabled {
+        return;
+    }
+
+    let now = Utc::now();
     app_state.alerts.clear();
+    // Passes in progress can conflict with alerted ones too
+    let mut candidates = Vec::new();
 
     for satellite in &app_state.satellites {
+        if app_state.muted_alerts.contains(&satellite.name) {
+            continue;
+        }
+        // Passes while the satellite is known to be switched off don't count
+        let operating = |pass: &SatellitePass| {
+            app_state
+                .schedule
+                .pass_operating(satellite, pass, &app_state.observer)
+        };
+        if let Some(current) = satellite
+            .passes
+            .iter()
+            .find(|pass| pass.aos_time <= now && pass.los_time > now)
+            .filter(|pass| pass.max_elevation >= app_state.config.alerts.min_elevation_for_alert)
+            .filter(|pass| operating(pass))
+        {
+            candidates.push((satellite.name.clone(), current.clone()));
+        }
+
         if let Some(next_pass) = satellite.get_next_pass() {
             // Check if pass meets minimum elevation requirement
-            if next_pass.max_elevation < app_state.config.alerts.min_elevation_for_alert {
+            if next_pass.max_elevation < app_state.config.alerts.min_elevation_for_alert
+                || !operating(next_pass)
+            {
                 continue;
             }
 
             let time_until = next_pass.aos_time.signed_duration_since(now);
             let minutes_until = time_until.num_minutes();
 
-            if minutes_until > 0 && minutes_until <= app_state.config.alerts.alert_before_pass {
+            // Kept through the last minute, which counts down in seconds
+            if time_until > Duration::zero()
+                && minutes_until <= app_state.config.alerts.alert_before_pass
+            {
                 app_state.alerts.push(Alert {
                     satellite_name: satellite.name.clone(),
                     pass: next_pass.clone(),
                     time_until_minutes: minutes_until,
                     shown: false,
                 });
+                candidates.push((satellite.name.clone(), next_pass.clone()));
             }
         }
     }
+
+    app_state.conflicts = scheduler::find_conflicts(&candidates, &app_state.config.alerts.priority);
+}
+
+/// Ring the terminal bell when an alerted pass reaches AOS, if `play_sound`
+/// is set. Called every tick, so it rings within one of AOS.
+fn ring_aos_bell(app_state: &mut AppState, now: DateTime<Utc>) {
+    let Some(last) = app_state.last_bell_check.replace(now) else {
+        return;
+    };
+    let alerts = &app_state.config.alerts;
+    if !alerts.enabled || !alerts.play_sound {
+        return;
+    }
+    let due = app_state
+        .satellites
+        .iter()
+        .filter(|satellite| !app_state.muted_alerts.contains(&satellite.name))
+        .any(|satellite| {
+            satellite.passes.iter().any(|pass| {
+                pass.aos_time > last
+                    && pass.aos_time <= now
+                    && pass.max_elevation >= alerts.min_elevation_for_alert
+                    && app_state
+                        .schedule
+                        .pass_operating(satellite, pass, &app_state.observer)
+            })
+        });
+    if due {
+        use std::io::Write;
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+    }
 }
 
 fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    terminal: &mut Terminal<ConsoleBackend<CrosstermBackend<std::io::Stdout>>>,
     app_state: &mut AppState,
 ) -> Result<()> {
     loop {
+        ring_aos_bell(app_state, Utc::now());
+        poll_pending_launches(app_state);
+        poll_altitude_lookup(app_state);
+        track_rotator(app_state);
+        if attached(app_state) {
+            sync_daemon(app_state);
+        } else {
+            update_rig(app_state);
+            update_sdr(app_state);
+            #[cfg(feature = "recording")]
+            record_pass_audio(app_state);
+            publish_home_assistant(app_state, Utc::now());
+        }
+        run_decoders(app_state);
+        let epochs: Vec<DateTime<Utc>> = app_state.satellites.iter().map(|s| s.epoch).collect();
+        app_state.clock.update(&epochs, Utc::now());
+        if let Some(config) = &app_state.config.aprs {
+            app_state
+                .direwolf
+                .update(config, &app_state.satellites, &app_state.observer, Utc::now());
+        }
+
         match app_state.mode {
             AppMode::Normal => {
-                // Update current positions
                 let now = Utc::now();
-                app_state.current_positions = app_state
-                    .satellites
-                    .iter()
-                    .filter_map(|sat| sat.calculate_position(now, &app_state.observer).ok())
-                    .collect();
-
-                // Add radio calculations if enabled
-                if app_state.config.radio.enabled {
-                    for pos in app_state.current_positions.iter_mut() {
-                        pos.doppler = Some(calculate_doppler_shift(
-                            pos,
-                            app_state.config.radio.downlink_frequency_mhz,
-                            app_state.config.radio.uplink_frequency_mhz,
-                        ));
-                        pos.comm_window = Some(evaluate_communication_window(pos));
-                    }
+                refresh_positions(app_state, now);
+                if app_state.auto_follow {
+                    follow_most_relevant(app_state, now);
                 }
 
-                // Update alerts
-                update_alerts(app_state);
-
                 terminal.draw(|f| {
-                    ui::draw_ui(f, app_state);
+                    if app_state.accessible {
+                        ui::draw_accessible(f, app_state);
+                    } else {
+                        ui::draw_ui(f, app_state);
+                    }
                 })?;
 
                 // Handle input for normal mode
-                if event::poll(std::time::Duration::from_millis(
-                    app_state.config.display.refresh_rate,
-                ))? {
-                    if let Event::Key(key) = event::read()? {
+                if event::poll(std::time::Duration::from_millis(redraw_interval_ms(app_state)))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        app_state.status_message = None;
                         match key.code {
                             KeyCode::Char('q') | KeyCode::Esc => {
                                 return Ok(());
@@ -788,41 +3570,270 @@ fn run_app(
                                 app_state.utility_menu_state.reset();
                                 app_state.mode = AppMode::UtilityMenu;
                             }
+                            KeyCode::Char('s') => {
+                                // Open the Celestrak catalog search popup
+                                app_state.catalog_search_state = CatalogSearchState::new();
+                                app_state.mode = AppMode::CatalogSearch;
+                            }
+                            KeyCode::Char('t') => {
+                                // Search the selected satellite's passes for Sun/Moon transits
+                                let satellite = &app_state.satellites[app_state.selected_satellite];
+                                app_state.transit_state.satellite_name = satellite.name.clone();
+                                app_state.transit_state.transits = transit::find_transits(
+                                    satellite,
+                                    &app_state.observer,
+                                    app_state.config.transits.search_radius_km,
+                                );
+                                app_state.transit_state.selected_index = 0;
+                                app_state.mode = AppMode::TransitFinder;
+                            }
+                            KeyCode::Char('P') => {
+                                app_state.pass_window_state = PassWindowState::new();
+                                app_state.mode = AppMode::PassWindow;
+                            }
+                            KeyCode::Char('l') => {
+                                app_state.pass_log_state.status_message = None;
+                                app_state.pass_log_state.reload(&app_state.database);
+                                app_state.mode = AppMode::PassLog;
+                            }
+                            KeyCode::Char('w') => {
+                                // Log the current or most recent pass as worked
+                                let message = log_recent_pass(app_state);
+                                let state = &mut app_state.pass_log_state;
+                                state.search.clear();
+                                state.selected_index = 0;
+                                state.reload(&app_state.database);
+                                state.status_message = Some(message);
+                                app_state.mode = AppMode::PassLog;
+                            }
+                            KeyCode::Char('S') => {
+                                app_state.log_stats = app_state.database.log_stats().ok();
+                                app_state.mode = AppMode::Stats;
+                            }
+                            KeyCode::Char('T') => {
+                                let satellite = &app_state.satellites[app_state.selected_satellite];
+                                app_state.telemetry_state = TelemetryViewState::new(&satellite.name);
+                                app_state.telemetry_state.reload(&app_state.database);
+                                app_state.mode = AppMode::Telemetry;
+                            }
+                            KeyCode::Char('N') => {
+                                app_state.satnogs_state = SatnogsState::new();
+                                start_satnogs_request(app_state, None);
+                                app_state.mode = AppMode::Satnogs;
+                            }
+                            KeyCode::Char('r') => {
+                                let tracking = !app_state.rotators.iter().any(|r| r.tracking);
+                                for rotator in app_state.rotators.iter_mut() {
+                                    rotator.tracking = tracking;
+                                    let status = if tracking { "Tracking" } else { "Idle" };
+                                    rotator.status = status.to_string();
+                                }
+                            }
+                            KeyCode::Char('O') => {
+                                // Keep the hardware going outside passes, or let it pause again
+                                let override_pause = !(app_state
+                                    .rotators
+                                    .iter()
+                                    .any(|r| r.override_pause)
+                                    || app_state.rig.as_ref().is_some_and(|r| r.override_pause));
+                                for rotator in app_state.rotators.iter_mut() {
+                                    rotator.override_pause = override_pause;
+                                }
+                                if let Some(rig) = app_state.rig.as_mut() {
+                                    rig.override_pause = override_pause;
+                                }
+                            }
+                            KeyCode::Char('z') => {
+                                // Park now and stop tracking until `r`
+                                for rotator in app_state.rotators.iter_mut() {
+                                    rotator.tracking = false;
+                                    if let Err(e) = rotator.park() {
+                                        rotator.status = format!("Error: {}", e);
+                                    }
+                                }
+                            }
+                            KeyCode::Char('d') => {
+                                // Dry run over the current or next pass
+                                let satellite = &app_state.satellites[app_state.selected_satellite];
+                                let pass = satellite
+                                    .get_current_pass(Utc::now())
+                                    .or_else(|| satellite.get_next_pass())
+                                    .cloned();
+                                rehearse_pass(app_state, app_state.selected_satellite, pass);
+                            }
+                            KeyCode::Char('R') if !app_state.rotators.is_empty() => {
+                                app_state.calibration_state = RotatorCalibrationState::new();
+                                app_state.calibration_state.load_pointing_errors(
+                                    &app_state.database,
+                                    &app_state.rotators[0].config.name,
+                                );
+                                app_state.mode = AppMode::RotatorCalibration;
+                            }
+                            KeyCode::Char('[') => {
+                                let step = app_state.config.radio.offset_step_hz;
+                                adjust_frequency_offset(app_state, Some(-step));
+                            }
+                            KeyCode::Char(']') => {
+                                let step = app_state.config.radio.offset_step_hz;
+                                adjust_frequency_offset(app_state, Some(step));
+                            }
+                            KeyCode::Char('\\') => {
+                                adjust_frequency_offset(app_state, None);
+                            }
+                            KeyCode::Char('v') => {
+                                app_state.tle_viewer_state.status_message = None;
+                                app_state.mode = AppMode::TleViewer;
+                            }
+                            KeyCode::Char('g') => {
+                                app_state.ground_track_state.status_message = None;
+                                app_state.ground_track_state.selected_index = 0;
+                                update_ground_track(app_state);
+                                app_state.mode = AppMode::GroundTrack;
+                            }
+                            KeyCode::Char('C') => {
+                                app_state.constellation_state.selected_index = 0;
+                                app_state.mode = AppMode::Constellations;
+                            }
+                            KeyCode::Char('b') => {
+                                app_state.band_occupancy_state.selected_index = 0;
+                                app_state.mode = AppMode::BandOccupancy;
+                            }
+                            KeyCode::Char('G') => {
+                                open_geostationary(app_state);
+                                app_state.mode = AppMode::Geostationary;
+                            }
+                            KeyCode::Char('o') => {
+                                reset_orbit_view(app_state);
+                                app_state.mode = AppMode::OrbitView;
+                            }
+                            KeyCode::Char('a') => {
+                                app_state.auto_follow = !app_state.auto_follow;
+                            }
+                            KeyCode::Char('m') => {
+                                toggle_alerts_muted(app_state);
+                            }
+                            KeyCode::Char('p') => {
+                                app_state.status_message = match switch_profile(app_state) {
+                                    Ok(warnings) if warnings.is_empty() => None,
+                                    Ok(warnings) => Some(warnings.join("; ")),
+                                    Err(e) => Some(format!("Could not switch profile: {:#}", e)),
+                                };
+                            }
+                            KeyCode::Char('f') => {
+                                app_state.mode = AppMode::Focus;
+                            }
+                            KeyCode::Char(key @ '0'..='9') => {
+                                select_group_key(app_state, key);
+                            }
+                            // Choosing a satellite by hand ends auto-follow, and
+                            // skips satellites outside the group shown
                             KeyCode::Up | KeyCode::Char('k') => {
-                                if app_state.selected_satellite > 0 {
-                                    app_state.selected_satellite -= 1;
+                                let selected = app_state.selected_satellite;
+                                let shown = shown_indices(app_state);
+                                if let Some(&index) = shown.iter().rev().find(|&&i| i < selected) {
+                                    app_state.selected_satellite = index;
+                                    app_state.auto_follow = false;
                                 }
                             }
                             KeyCode::Down | KeyCode::Char('j') => {
-                                if app_state.selected_satellite < app_state.satellites.len() - 1 {
-                                    app_state.selected_satellite += 1;
+                                let selected = app_state.selected_satellite;
+                                let shown = shown_indices(app_state);
+                                if let Some(&index) = shown.iter().find(|&&i| i > selected) {
+                                    app_state.selected_satellite = index;
+                                    app_state.auto_follow = false;
                                 }
                             }
                             KeyCode::Home => {
-                                app_state.selected_satellite = 0;
+                                if let Some(&index) = shown_indices(app_state).first() {
+                                    app_state.selected_satellite = index;
+                                    app_state.auto_follow = false;
+                                }
                             }
                             KeyCode::End => {
-                                app_state.selected_satellite = app_state.satellites.len() - 1;
+                                if let Some(&index) = shown_indices(app_state).last() {
+                                    app_state.selected_satellite = index;
+                                    app_state.auto_follow = false;
+                                }
+                            }
+                            KeyCode::Char('n') => {
+                                if let Some(index) = soonest_aos(app_state, Utc::now()) {
+                                    app_state.selected_satellite = index;
+                                    app_state.auto_follow = false;
+                                }
+                            }
+                            KeyCode::Char('h') => {
+                                if let Some(index) = highest_now(app_state) {
+                                    app_state.selected_satellite = index;
+                                    app_state.auto_follow = false;
+                                }
                             }
                             _ => {}
                         }
                     }
                 }
             }
-            AppMode::SatelliteConfig => {
+            AppMode::Kiosk => {
+                let now = Utc::now();
+                refresh_positions(app_state, now);
+                rotate_kiosk(app_state, now);
+
                 terminal.draw(|f| {
-                    ui::draw_satellite_config(f, app_state);
+                    ui::draw_kiosk(f, app_state);
                 })?;
 
-                // Handle input for satellite config mode
-                if event::poll(std::time::Duration::from_millis(100))? {
-                    if let Event::Key(key) = event::read()? {
-                        handle_satellite_config_input(app_state, key.code)?;
+                // Unattended: nothing to prompt for, only a way out
+                if event::poll(std::time::Duration::from_millis(redraw_interval_ms(app_state)))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                            return Ok(());
+                        }
                     }
                 }
             }
-            AppMode::UtilityMenu => {
-                // Poll for background download completion
+            AppMode::Focus => {
+                refresh_positions(app_state, Utc::now());
+
+                terminal.draw(|f| {
+                    ui::draw_focus(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(redraw_interval_ms(app_state)))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        match key.code {
+                            KeyCode::Char('f' | 'q') | KeyCode::Esc => {
+                                app_state.mode = AppMode::Normal;
+                            }
+                            KeyCode::Char('[') => {
+                                let step = app_state.config.radio.offset_step_hz;
+                                adjust_frequency_offset(app_state, Some(-step));
+                            }
+                            KeyCode::Char(']') => {
+                                let step = app_state.config.radio.offset_step_hz;
+                                adjust_frequency_offset(app_state, Some(step));
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            AppMode::SatelliteConfig => {
+                poll_satcat_enrichment(app_state);
+
+                terminal.draw(|f| {
+                    ui::draw_satellite_config(f, app_state);
+                })?;
+
+                // Handle input for satellite config mode
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    match console::read_event()? {
+                        Some(Event::Key(key)) => handle_satellite_config_input(app_state, key)?,
+                        Some(Event::Paste(text)) => app_state.sat_config_state.paste(&text),
+                        _ => {}
+                    }
+                }
+            }
+            AppMode::UtilityMenu => {
+                // Poll for background download completion
                 if app_state.utility_menu_state.status == UtilityMenuStatus::Downloading {
                     let finished = app_state.utility_menu_state.download_progress
                         .as_ref()
@@ -842,14 +3853,18 @@ fn run_app(
                         }
                         match download_result {
                             Ok(tle_data) => {
+                                let diff = group_diff(&app_state.database, source_name, &tle_data);
                                 match parse_and_store_tles(&tle_data, &app_state.database, source_name) {
                                     Ok(count) => {
                                         app_state.utility_menu_state.status = UtilityMenuStatus::Success;
                                         app_state.utility_menu_state.downloaded_count = Some(count);
                                         app_state.utility_menu_state.status_message = Some(format!(
-                                            "Successfully stored {} satellites from {}",
-                                            count, source_name
+                                            "Successfully stored {} satellites from {}: {}",
+                                            count,
+                                            source_name,
+                                            diff.summary()
                                         ));
+                                        app_state.utility_menu_state.diff = Some(diff);
                                     }
                                     Err(e) => {
                                         app_state.utility_menu_state.status = UtilityMenuStatus::Error;
@@ -874,43 +3889,435 @@ fn run_app(
 
                 // Handle input for utility menu mode
                 if event::poll(std::time::Duration::from_millis(100))? {
-                    if let Event::Key(key) = event::read()? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
                         handle_utility_menu_input(app_state, key.code)?;
                     }
                 }
             }
+            AppMode::CatalogSearch => {
+                // Poll for background search completion
+                let finished = app_state
+                    .catalog_search_state
+                    .pending
+                    .as_ref()
+                    .and_then(|p| p.lock().unwrap().take());
+
+                if let Some(search_result) = finished {
+                    let state = &mut app_state.catalog_search_state;
+                    state.pending = None;
+                    state.selected_index = 0;
+                    match search_result {
+                        Ok(results) => {
+                            state.status_message =
+                                Some(format!("{} matches for \"{}\"", results.len(), state.query));
+                            state.results = results;
+                        }
+                        Err(e) => {
+                            state.results.clear();
+                            state.status_message = Some(format!("Search failed: {}", e));
+                        }
+                    }
+                }
+
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_catalog_search(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_catalog_search_input(app_state, key.code)?;
+                    }
+                }
+            }
+            AppMode::TransitFinder => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_transit_finder(f, app_state);
+                })?;
+
+                // Handle input for transit finder mode
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        let state = &mut app_state.transit_state;
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') => {
+                                app_state.mode = AppMode::Normal;
+                            }
+                            KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+                                state.selected_index -= 1;
+                            }
+                            KeyCode::Down | KeyCode::Char('j')
+                                if state.selected_index < state.transits.len().saturating_sub(1) =>
+                            {
+                                state.selected_index += 1;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            AppMode::PassWindow => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_pass_window(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_pass_window_input(app_state, key);
+                    }
+                }
+            }
+            AppMode::PassLog => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_pass_log(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_pass_log_input(app_state, key.code);
+                    }
+                }
+            }
+            AppMode::RotatorCalibration => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_rotator_calibration(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_calibration_input(app_state, key.code);
+                    }
+                }
+            }
+            AppMode::RotatorSimulation => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_rotator_simulation(f, app_state);
+                })?;
+
+                let state = &mut app_state.simulation_state;
+                let sample_count = state.plan.as_ref().map_or(0, |plan| plan.samples.len());
+                if !state.paused {
+                    state.index = (state.index + state.speed).min(sample_count.saturating_sub(1));
+                }
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') => {
+                                app_state.mode = state.return_mode;
+                            }
+                            KeyCode::Char(' ') => state.paused = !state.paused,
+                            KeyCode::Char('+') | KeyCode::Char('=') => {
+                                state.speed = (state.speed * 2).min(64);
+                            }
+                            KeyCode::Char('-') => state.speed = (state.speed / 2).max(1),
+                            KeyCode::Left => state.index = state.index.saturating_sub(10),
+                            KeyCode::Right => {
+                                state.index = (state.index + 10).min(sample_count.saturating_sub(1));
+                            }
+                            KeyCode::Home | KeyCode::Char('0') => state.index = 0,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            AppMode::Stats => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_stats(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') => {
+                                app_state.mode = AppMode::Normal;
+                            }
+                            KeyCode::Char('c') => {
+                                let coverage = coverage::build(
+                                    &app_state.database,
+                                    &app_state.satellites,
+                                    &app_state.observer,
+                                );
+                                app_state.coverage_state = CoverageState {
+                                    status_message: coverage
+                                        .as_ref()
+                                        .err()
+                                        .map(|e| format!("Error reading the pass log: {}", e)),
+                                    coverage: coverage.ok(),
+                                };
+                                app_state.mode = AppMode::Coverage;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            AppMode::Coverage => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_coverage(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') => {
+                                app_state.mode = AppMode::Stats;
+                            }
+                            KeyCode::Char('e') => {
+                                let message = match export_coverage_horizon(app_state) {
+                                    Ok(path) => format!("Saved {}", path.display()),
+                                    Err(e) => format!("Export failed: {}", e),
+                                };
+                                app_state.coverage_state.status_message = Some(message);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            AppMode::Telemetry => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_telemetry(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        let state = &mut app_state.telemetry_state;
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') => {
+                                app_state.mode = AppMode::Normal;
+                            }
+                            KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+                                state.selected_index -= 1;
+                            }
+                            KeyCode::Down | KeyCode::Char('j')
+                                if state.selected_index + 1 < state.frames.len() =>
+                            {
+                                state.selected_index += 1;
+                            }
+                            KeyCode::Home => state.selected_index = 0,
+                            KeyCode::End => {
+                                state.selected_index = state.frames.len().saturating_sub(1);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            AppMode::Satnogs => {
+                let finished = app_state
+                    .satnogs_state
+                    .pending
+                    .as_ref()
+                    .and_then(|p| p.lock().unwrap().take());
+                if let Some(result) = finished {
+                    let state = &mut app_state.satnogs_state;
+                    state.pending = None;
+                    match result {
+                        Ok((message, observations)) => {
+                            state.status_message = Some(message);
+                            state.observations = observations;
+                        }
+                        Err(e) => state.status_message = Some(format!("SatNOGS: {}", e)),
+                    }
+                }
+
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_satnogs(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_satnogs_input(app_state, key.code);
+                    }
+                }
+            }
+            AppMode::GroundTrack => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_ground_track(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_ground_track_input(app_state, key.code);
+                    }
+                }
+            }
+            AppMode::Constellations => {
+                // Visible counts and the density map follow the satellites
+                refresh_positions(app_state, Utc::now());
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_constellations(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(redraw_interval_ms(app_state)))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_constellation_input(app_state, key.code);
+                    }
+                }
+            }
+            AppMode::BandOccupancy => {
+                // Satellites rise and set and their Doppler shifts keep moving
+                refresh_positions(app_state, Utc::now());
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_band_occupancy(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(redraw_interval_ms(app_state)))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_band_occupancy_input(app_state, key.code);
+                    }
+                }
+            }
+            AppMode::Geostationary => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_geostationary(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(redraw_interval_ms(app_state)))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_geostationary_input(app_state, key.code);
+                    }
+                }
+            }
+            AppMode::OrbitView => {
+                // The satellite moves along its orbit and the observer with the Earth
+                refresh_positions(app_state, Utc::now());
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_orbit_view(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(redraw_interval_ms(app_state)))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        handle_orbit_view_input(app_state, key.code);
+                    }
+                }
+            }
+            AppMode::TleViewer => {
+                terminal.draw(|f| {
+                    ui::draw_ui(f, app_state);
+                    ui::draw_tle_viewer(f, app_state);
+                })?;
+
+                if event::poll(std::time::Duration::from_millis(100))? {
+                    if let Some(Event::Key(key)) = console::read_event()? {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') => {
+                                app_state.mode = AppMode::Normal;
+                            }
+                            KeyCode::Char('y') | KeyCode::Char('c') => {
+                                let satellite = &app_state.satellites[app_state.selected_satellite];
+                                let text = format!(
+                                    "{}\n{}\n{}\n",
+                                    satellite.name, satellite.tle_line1, satellite.tle_line2
+                                );
+                                let state = &mut app_state.tle_viewer_state;
+                                state.status_message = Some(match state.copy(text) {
+                                    Ok(()) => "Copied TLE to clipboard".to_string(),
+                                    Err(e) => format!("Clipboard unavailable: {}", e),
+                                });
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
         }
     }
 }
 
-fn handle_satellite_config_input(app_state: &mut AppState, key: KeyCode) -> Result<()> {
+fn handle_satellite_config_input(app_state: &mut AppState, key: KeyEvent) -> Result<()> {
     let state = &mut app_state.sat_config_state;
 
     match state.edit_mode {
+        ConfigEditMode::List if state.confirm_delete => {
+            state.confirm_delete = false;
+            if key.code != KeyCode::Char('y') {
+                state.status_message = Some(format!("Kept: {}", state.describe_targets()));
+                return Ok(());
+            }
+            let mut deleted = Vec::new();
+            for sat in state.targets() {
+                if let Some(id) = sat.id {
+                    if app_state.database.delete(id).is_ok() {
+                        deleted.push(sat.clone());
+                    }
+                }
+            }
+            state.status_message = Some(match deleted.as_slice() {
+                [] => "Failed to delete satellite".to_string(),
+                [sat] => format!("Deleted: {} (u: Undo)", sat.name),
+                deleted => format!("Deleted {} satellites (u: Undo)", deleted.len()),
+            });
+            if !deleted.is_empty() {
+                state.last_deleted = deleted;
+                let _ = state.load_from_database(&app_state.database);
+            }
+        }
+        ConfigEditMode::List if state.list_input.is_some() => {
+            let Some(input) = state.list_input else {
+                return Ok(());
+            };
+            if matches!(key.code, KeyCode::Right | KeyCode::End) && state.input_buffer.at_end() {
+                if let Some(completion) = state.completion() {
+                    state.input_buffer.set(completion);
+                    return Ok(());
+                }
+            }
+            if state.input_buffer.handle_key(key) {
+                return Ok(());
+            }
+            match key.code {
+                KeyCode::Esc => {
+                    state.list_input = None;
+                    state.status_message = Some(match input {
+                        ListInput::Search => "Search cancelled".to_string(),
+                        ListInput::Bulk(_) => "Bulk edit cancelled".to_string(),
+                    });
+                }
+                KeyCode::Enter => {
+                    state.list_input = None;
+                    let value = state.input_buffer.value().trim().to_string();
+                    match input {
+                        ListInput::Search => search_satellites(app_state, value),
+                        ListInput::Bulk(edit) => apply_bulk_edit(app_state, edit, &value),
+                    }
+                }
+                _ => {}
+            }
+        }
         ConfigEditMode::List => {
-            match key {
+            match key.code {
                 KeyCode::Esc | KeyCode::Char('q') => {
                     // Return to normal mode
                     app_state.mode = AppMode::Normal;
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.selected_index > 0 {
-                        state.selected_index -= 1;
-                    }
+                KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+                    state.selected_index -= 1;
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.selected_index < state.satellites.len().saturating_sub(1) {
-                        state.selected_index += 1;
-                    }
+                KeyCode::Down | KeyCode::Char('j') if state.selected_index < state.satellites.len().saturating_sub(1) => {
+                    state.selected_index += 1;
                 }
-                KeyCode::Enter | KeyCode::Char('e') => {
+                KeyCode::Enter | KeyCode::Char('e') if !state.satellites.is_empty() => {
                     // Edit selected satellite
-                    if !state.satellites.is_empty() {
-                        state.editing_satellite = state.satellites[state.selected_index].clone();
-                        state.current_field = ConfigField::Name;
-                        state.input_buffer = state.get_field_value(state.current_field);
-                        state.edit_mode = ConfigEditMode::Edit;
-                    }
+                    state.editing_satellite = state.satellites[state.selected_index].clone();
+                    state.current_field = ConfigField::Name;
+                    state.input_buffer.set(state.get_field_value(state.current_field));
+                    state.edit_mode = ConfigEditMode::Edit;
                 }
                 KeyCode::Char('a') => {
                     // Add new satellite
@@ -919,60 +4326,174 @@ fn handle_satellite_config_input(app_state: &mut AppState, key: KeyCode) -> Resu
                     state.input_buffer.clear();
                     state.edit_mode = ConfigEditMode::Add;
                 }
-                KeyCode::Char('d') | KeyCode::Delete => {
-                    // Delete selected satellite
-                    if !state.satellites.is_empty() {
-                        let sat = &state.satellites[state.selected_index];
-                        if let Some(id) = sat.id {
-                            if app_state.database.delete(id).is_ok() {
-                                state.status_message = Some(format!("Deleted: {}", sat.name));
-                                let _ = state.load_from_database(&app_state.database);
-                            } else {
-                                state.status_message = Some("Failed to delete satellite".to_string());
+                KeyCode::Char('f') if !state.satellites.is_empty() && state.enrichment.is_none() => {
+                    // Fill launch/owner/type details from Celestrak SATCAT
+                    let sat = &state.satellites[state.selected_index];
+                    match (sat.id, celestrak::norad_id(&sat.tle_line1)) {
+                        (Some(id), Some(norad_id)) => {
+                            let slot: SatcatResult = Arc::new(Mutex::new(None));
+                            state.enrichment = Some((id, Arc::clone(&slot)));
+                            state.status_message =
+                                Some(format!("Fetching SATCAT record for {}...", norad_id));
+                            std::thread::spawn(move || {
+                                let result = celestrak::fetch_satcat(norad_id).map_err(|e| e.to_string());
+                                *slot.lock().unwrap() = Some(result);
+                            });
+                        }
+                        _ => {
+                            state.status_message =
+                                Some(format!("{} has no NORAD ID in its TLE", sat.name));
+                        }
+                    }
+                }
+                KeyCode::Char(' ') if !state.satellites.is_empty() => {
+                    // Mark or unmark for bulk edits and move on, so a run is quick to mark
+                    if let Some(id) = state.satellites[state.selected_index].id {
+                        if !state.marked.remove(&id) {
+                            state.marked.insert(id);
+                        }
+                    }
+                    state.selected_index =
+                        (state.selected_index + 1).min(state.satellites.len() - 1);
+                }
+                KeyCode::Char('*') => {
+                    if state.marked.len() == state.satellites.len() {
+                        state.marked.clear();
+                    } else {
+                        state.marked = state.satellites.iter().filter_map(|sat| sat.id).collect();
+                    }
+                }
+                KeyCode::Char('t') | KeyCode::Char('o') | KeyCode::Char('g')
+                    if !state.satellites.is_empty() =>
+                {
+                    let edit = match key.code {
+                        KeyCode::Char('t') => BulkEdit::Type,
+                        KeyCode::Char('o') => BulkEdit::Country,
+                        _ => BulkEdit::Group,
+                    };
+                    // Country offers the same completions as in the edit form
+                    state.current_field = match edit {
+                        BulkEdit::Country => ConfigField::CountryOfOrigin,
+                        _ => ConfigField::Name,
+                    };
+                    state.input_buffer.clear();
+                    state.list_input = Some(ListInput::Bulk(edit));
+                    state.status_message = None;
+                }
+                KeyCode::Char('/') => {
+                    state.current_field = ConfigField::Name;
+                    state.input_buffer.set(state.search.clone());
+                    state.list_input = Some(ListInput::Search);
+                    state.status_message = None;
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') if !state.search_matches.is_empty() => {
+                    state.jump_to_match(key.code == KeyCode::Char('n'), false);
+                }
+                KeyCode::Char('d') | KeyCode::Delete if !state.satellites.is_empty() => {
+                    // Ask first; the answer is handled above
+                    state.confirm_delete = true;
+                    state.status_message = Some(format!(
+                        "Delete {}? y: Delete | any other key: Keep",
+                        state.describe_targets()
+                    ));
+                }
+                KeyCode::Char('u') => {
+                    // Restore the last deleted satellites
+                    if state.last_deleted.is_empty() {
+                        state.status_message = Some("Nothing to undo".to_string());
+                        return Ok(());
+                    }
+                    let (mut restored, mut failed) = (Vec::new(), Vec::new());
+                    for sat in std::mem::take(&mut state.last_deleted) {
+                        match app_state.database.create(&sat) {
+                            Ok(_) => restored.push(sat.name),
+                            Err(e) => {
+                                state.status_message =
+                                    Some(format!("Failed to restore {}: {}", sat.name, e));
+                                failed.push(sat);
                             }
                         }
                     }
+                    let _ = state.load_from_database(&app_state.database);
+                    if let Some(first) = restored.first() {
+                        let position = state.satellites.iter().position(|s| &s.name == first);
+                        state.selected_index = position.unwrap_or(state.selected_index);
+                    }
+                    if failed.is_empty() {
+                        state.status_message = Some(match restored.as_slice() {
+                            [name] => format!("Restored: {}", name),
+                            names => format!("Restored {} satellites", names.len()),
+                        });
+                    }
+                    state.last_deleted = failed;
                 }
                 _ => {}
             }
         }
         ConfigEditMode::Edit | ConfigEditMode::Add => {
-            match key {
+            // Field-specific keys: completion, the date picker, and refusing
+            // characters the field can't hold
+            match key.code {
+                KeyCode::Right | KeyCode::End if state.input_buffer.at_end() => {
+                    if let Some(completion) = state.completion() {
+                        state.input_buffer.set(completion);
+                        return Ok(());
+                    }
+                }
+                KeyCode::PageUp | KeyCode::PageDown
+                    if state.current_field == ConfigField::LaunchDate =>
+                {
+                    let delta = if key.code == KeyCode::PageUp { 1 } else { -1 };
+                    let date = step_date(
+                        state.input_buffer.value(),
+                        state.input_buffer.cursor(),
+                        delta,
+                        Utc::now().date_naive(),
+                    );
+                    state.input_buffer.replace(date);
+                    return Ok(());
+                }
+                KeyCode::Char(c)
+                    if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                        && !state.current_field.accepts(c) =>
+                {
+                    state.status_message = Some(format!(
+                        "'{}' can't be part of {}",
+                        c,
+                        state.current_field.label()
+                    ));
+                    return Ok(());
+                }
+                _ => {}
+            }
+
+            // Cursor movement and editing within the field come next
+            if state.input_buffer.handle_key(key) {
+                return Ok(());
+            }
+            match key.code {
                 KeyCode::Esc => {
                     // Cancel edit and return to list
                     state.edit_mode = ConfigEditMode::List;
                     state.status_message = Some("Edit cancelled".to_string());
                 }
-                KeyCode::Tab => {
-                    // Save current field and move to next
-                    state.set_field_value(state.current_field, state.input_buffer.clone());
-                    state.current_field = state.current_field.next();
-                    state.input_buffer = state.get_field_value(state.current_field);
-                }
-                KeyCode::BackTab => {
-                    // Save current field and move to previous
-                    state.set_field_value(state.current_field, state.input_buffer.clone());
-                    state.current_field = state.current_field.prev();
-                    state.input_buffer = state.get_field_value(state.current_field);
-                }
-                KeyCode::Up => {
-                    // Save current field and move to previous
-                    state.set_field_value(state.current_field, state.input_buffer.clone());
-                    state.current_field = state.current_field.prev();
-                    state.input_buffer = state.get_field_value(state.current_field);
-                }
-                KeyCode::Down => {
-                    // Save current field and move to next
-                    state.set_field_value(state.current_field, state.input_buffer.clone());
-                    state.current_field = state.current_field.next();
-                    state.input_buffer = state.get_field_value(state.current_field);
+                KeyCode::Tab | KeyCode::Down | KeyCode::BackTab | KeyCode::Up => {
+                    // Save current field and move to the next or previous one
+                    if !state.commit_field() {
+                        return Ok(());
+                    }
+                    state.current_field = if matches!(key.code, KeyCode::Tab | KeyCode::Down) {
+                        state.current_field.next()
+                    } else {
+                        state.current_field.prev()
+                    };
+                    state.input_buffer.set(state.get_field_value(state.current_field));
                 }
                 KeyCode::Enter => {
-                    // Save current field value
-                    state.set_field_value(state.current_field, state.input_buffer.clone());
-
-                    // Save to database
-                    if state.editing_satellite.name.is_empty() {
+                    // Save to database once the current field's value is accepted
+                    if !state.commit_field() {
+                        // The problem is shown in the status line
+                    } else if state.editing_satellite.name.is_empty() {
                         state.status_message = Some("Error: Name is required".to_string());
                     } else {
                         let result = if state.edit_mode == ConfigEditMode::Add {
@@ -986,6 +4507,8 @@ fn handle_satellite_config_input(app_state: &mut AppState, key: KeyCode) -> Resu
                             Ok(_) => {
                                 state.status_message = Some(format!("Saved: {}", state.editing_satellite.name));
                                 let _ = state.load_from_database(&app_state.database);
+                                app_state.aliases =
+                                    app_state.database.read_aliases().unwrap_or_default();
                                 state.edit_mode = ConfigEditMode::List;
                             }
                             Err(e) => {
@@ -994,18 +4517,1071 @@ fn handle_satellite_config_input(app_state: &mut AppState, key: KeyCode) -> Resu
                         }
                     }
                 }
-                KeyCode::Char(c) => {
-                    state.input_buffer.push(c);
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the satellites matching `text` in the database and select the first
+/// one from the selection on
+fn search_satellites(app_state: &mut AppState, text: String) {
+    let state = &mut app_state.sat_config_state;
+    if text.is_empty() {
+        state.search.clear();
+        state.search_matches.clear();
+        state.status_message = Some("Search cleared".to_string());
+        return;
+    }
+    match app_state.database.search_satellites(&text) {
+        Ok(ids) => {
+            state.search = text;
+            state.search_matches = ids;
+            state.jump_to_match(true, true);
+        }
+        Err(e) => state.status_message = Some(format!("Search failed: {}", e)),
+    }
+}
+
+/// Set the type or country of every bulk edit target, or add them all to a
+/// group; an empty type or country clears it
+fn apply_bulk_edit(app_state: &mut AppState, edit: BulkEdit, value: &str) {
+    let state = &mut app_state.sat_config_state;
+    let targets: Vec<SatelliteDetails> = state.targets().into_iter().cloned().collect();
+    let what = state.describe_targets();
+
+    let result = match edit {
+        BulkEdit::Group if value.is_empty() => {
+            state.status_message = Some("A group name is needed".to_string());
+            return;
+        }
+        BulkEdit::Group => {
+            let names: Vec<String> = targets.into_iter().map(|sat| sat.name).collect();
+            app_state.database.add_to_group(value, &names).and_then(|()| {
+                app_state.groups = app_state.database.read_groups()?;
+                Ok(())
+            })
+        }
+        BulkEdit::Type | BulkEdit::Country => {
+            let value = (!value.is_empty()).then(|| value.to_string());
+            targets.into_iter().try_for_each(|mut sat| {
+                match edit {
+                    BulkEdit::Type => sat.satellite_type = value.clone(),
+                    _ => sat.country_of_origin = value.clone(),
+                }
+                app_state.database.update(&sat).map(|_| ())
+            })
+        }
+    };
+
+    state.status_message = Some(match result {
+        Ok(()) if edit == BulkEdit::Group => format!("Added {} to group {}", what, value),
+        Ok(()) => format!("{} set for {}", edit.prompt(), what),
+        Err(e) => format!("Error saving: {}", e),
+    });
+    if edit != BulkEdit::Group {
+        let _ = state.load_from_database(&app_state.database);
+    }
+}
+
+/// Nudge (or with None, clear) the selected satellite's downlink correction and remember it
+fn adjust_frequency_offset(app_state: &mut AppState, step_hz: Option<f64>) {
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let (downlink_mhz, _) = app_state.config.radio.frequencies_for(&satellite.name);
+    let current =
+        radio::downlink_offset_hz(&app_state.frequency_offsets, &satellite.name, downlink_mhz);
+    let offset = FrequencyOffset {
+        satellite_name: satellite.name.clone(),
+        transponder: radio::transponder_label(downlink_mhz),
+        offset_hz: step_hz.map_or(0.0, |step| current + step),
+    };
+    if let Err(e) = app_state.database.save_frequency_offset(&offset) {
+        app_state.status_message = Some(format!("Could not save frequency offset: {}", e));
+        return;
+    }
+    app_state.frequency_offsets.retain(|o| {
+        o.satellite_name != offset.satellite_name || o.transponder != offset.transponder
+    });
+    app_state.frequency_offsets.push(offset);
+}
+
+/// Carry out a `schedule` subcommand
+fn manage_schedule(database: &Database, action: &ScheduleAction) -> Result<()> {
+    match action {
+        ScheduleAction::List => {
+            let rules = database.read_operating_rules()?;
+            if rules.is_empty() {
+                println!("No operating schedules; every satellite is taken to be always on");
+            }
+            for rule in &rules {
+                println!(
+                    "{:>4}  {:<24} {}",
+                    rule.id.unwrap_or_default(),
+                    rule.satellite_name,
+                    schedule::describe(rule)
+                );
+            }
+        }
+        ScheduleAction::Add {
+            satellite,
+            days,
+            hours,
+            region,
+            note,
+        } => {
+            if days.is_none() && hours.is_none() && region.is_none() {
+                anyhow::bail!("give at least one of --days, --hours and --region");
+            }
+            let rule = OperatingRule {
+                id: None,
+                satellite_name: satellite.clone(),
+                days: days.clone(),
+                hours: hours.clone(),
+                region: region.clone(),
+                note: note.clone(),
+            };
+            // Check the rule parses before storing it
+            schedule::Schedule::new(std::slice::from_ref(&rule))?;
+            let id = database.add_operating_rule(&rule)?;
+            println!("Added rule {}: {} {}", id, satellite, schedule::describe(&rule));
+        }
+        ScheduleAction::Remove { id } => {
+            if !database.delete_operating_rule(*id)? {
+                anyhow::bail!("no operating schedule rule with id {}", id);
+            }
+            println!("Removed rule {}", id);
+        }
+    }
+    Ok(())
+}
+
+/// Record a noise floor reading in the noise file, given directly or read
+/// from a scan, or without an azimuth list the profile
+fn manage_noise_profile(
+    config: &Config,
+    observer: &Observer,
+    azimuth: Option<f64>,
+    level: Option<f64>,
+    scan: Option<&Path>,
+) -> Result<()> {
+    let Some(azimuth) = azimuth else {
+        let profile = &observer.noise;
+        if profile.points().is_empty() {
+            println!("No noise profile; record readings with --azimuth and --level or --scan");
+        }
+        for &(azimuth, level) in profile.points() {
+            println!(
+                "{:>5.1}°  {:>7.1} dB  {:>+5.1} dB",
+                azimuth,
+                level,
+                profile.rise_db(azimuth)
+            );
+        }
+        return Ok(());
+    };
+    let path = config
+        .observer
+        .noise_file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("set noise_file under [observer] in the configuration"))?;
+    let level = match (level, scan) {
+        (Some(level), _) => level,
+        (None, Some(scan)) => {
+            let contents = fs::read_to_string(scan)
+                .map_err(|e| anyhow::anyhow!("reading {}: {}", scan.display(), e))?;
+            noise::scan_noise_floor_db(&contents)?
+        }
+        (None, None) => anyhow::bail!("give the noise floor with --level or --scan"),
+    };
+    // Read the file again rather than trusting the observer's copy, which is
+    // left empty when the file doesn't exist yet or fails to load
+    let mut profile = if path.exists() {
+        noise::NoiseProfile::load_csv(path)?
+    } else {
+        noise::NoiseProfile::default()
+    };
+    profile.record(azimuth, level);
+    fs::write(path, profile.to_csv())?;
+    println!(
+        "Noise floor toward {:.1}°: {:.1} dB, {:.1} dB above the quietest direction",
+        azimuth.rem_euclid(360.0),
+        level,
+        profile.rise_db(azimuth)
+    );
+    Ok(())
+}
+
+/// Mute the selected satellite's pass alerts, or unmute them if they already are
+fn toggle_alerts_muted(app_state: &mut AppState) {
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let muted = !app_state.muted_alerts.contains(&satellite.name);
+    if let Err(e) = app_state.database.set_alerts_muted(&satellite.name, muted) {
+        app_state.status_message = Some(format!("Could not save muted alerts: {}", e));
+        return;
+    }
+    if muted {
+        app_state.muted_alerts.insert(satellite.name.clone());
+    } else {
+        app_state.muted_alerts.remove(&satellite.name);
+    }
+    update_alerts(app_state);
+}
+
+/// Mode of the transponder in use for a satellite: configured, or the first imported one
+fn transponder_mode(app_state: &AppState, satellite_name: &str) -> Option<String> {
+    app_state
+        .config
+        .radio
+        .for_satellite(satellite_name)
+        .and_then(|radio| radio.mode.clone())
+        .or_else(|| {
+            app_state
+                .transponders
+                .iter()
+                .filter(|t| transponder::matches_satellite(&t.satellite_name, satellite_name))
+                .find_map(|t| t.downlink_mode.clone())
+        })
+}
+
+/// Where the database lives: crabtrack/satellites.db in the local data directory
+fn database_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("crabtrack")
+        .join("satellites.db")
+}
+
+/// Observer for the configured location, with its horizon and noise profiles
+fn build_observer(config: &Config) -> Result<Observer> {
+    let (latitude, longitude) = config.observer.coordinates()?;
+    let mut warnings = Vec::new();
+    let altitude = config
+        .observer
+        .altitude
+        .unwrap_or_else(|| lookup_altitude(&config.observer, latitude, longitude, &mut warnings));
+    let observer = observer_at(config, latitude, longitude, altitude, &mut warnings);
+    print_warnings(&warnings);
+    Ok(observer)
+}
+
+/// Observer for the configured location at `altitude`, with its horizon and
+/// noise profiles; profiles that fail to load are noted in `warnings`
+fn observer_at(
+    config: &Config,
+    latitude: f64,
+    longitude: f64,
+    altitude: f64,
+    warnings: &mut Vec<String>,
+) -> Observer {
+    let mut observer = Observer::new(config.observer.name.clone(), latitude, longitude, altitude);
+    observer.light_time_correction = config.prediction.light_time_correction;
+
+    if let Some(horizon_file) = &config.observer.horizon_file {
+        match horizon::HorizonMask::load_csv(horizon_file) {
+            Ok(mask) => observer.horizon = mask,
+            Err(e) => warnings.push(format!("Could not load horizon profile: {:#}", e)),
+        }
+    }
+    if let Some(noise_file) = &config.observer.noise_file {
+        match noise::NoiseProfile::load_csv(noise_file) {
+            Ok(profile) => observer.noise = profile,
+            Err(e) => warnings.push(format!("Could not load noise profile: {:#}", e)),
+        }
+    }
+    observer
+}
+
+/// Print warnings gathered before the TUI starts, or for a command
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
+/// The pattern files of the configured antennas, by path; files that fail to
+/// load are noted in `warnings`
+fn load_antenna_patterns(
+    config: &Config,
+    warnings: &mut Vec<String>,
+) -> HashMap<PathBuf, antenna::AntennaPattern> {
+    let mut patterns = HashMap::new();
+    for antenna in config.antenna.iter().chain(&config.antennas) {
+        let Some(path) = &antenna.pattern_file else {
+            continue;
+        };
+        match antenna::AntennaPattern::load(path) {
+            Ok(pattern) => {
+                patterns.insert(path.clone(), pattern);
+            }
+            Err(e) => warnings.push(format!("Could not load antenna pattern: {:#}", e)),
+        }
+    }
+    patterns
+}
+
+/// Gain toward a satellite of the antenna used for it, when its pattern or
+/// boresight gain is known, the loss to its polarization when that is, and
+/// the noise in its direction
+fn station_link(
+    config: &Config,
+    patterns: &HashMap<PathBuf, antenna::AntennaPattern>,
+    noise: &noise::NoiseProfile,
+    position: &SatellitePosition,
+    downlink_mhz: f64,
+) -> link_budget::StationLink {
+    let noise_rise_db = noise.rise_db(position.azimuth);
+    let bands = config.satellite_bands(&position.name);
+    let Some(antenna) = config.antenna_for(&position.name, &bands) else {
+        return link_budget::StationLink {
+            noise_rise_db,
+            ..Default::default()
+        };
+    };
+    let pattern = antenna.pattern_file.as_ref().and_then(|path| patterns.get(path));
+    let polarization = antenna.polarization.map(|polarization| {
+        let downlink = config
+            .radio
+            .for_satellite(&position.name)
+            .and_then(|radio| radio.polarization)
+            .unwrap_or(config::Polarization::Linear);
+        let rotation = link_budget::faraday_rotation_deg(
+            downlink_mhz,
+            position.elevation,
+            position.altitude_km,
+            config.radio.tec_units,
+        );
+        link_budget::PolarizationMismatch::new(polarization, downlink, rotation)
+    });
+    link_budget::StationLink {
+        antenna_gain: antenna.gain_toward(pattern, position.azimuth, position.elevation),
+        polarization,
+        noise_rise_db,
+    }
+}
+
+/// A controller for every configured rotator, with its stored calibration;
+/// calibrations that fail to load are noted in `warnings`
+fn build_rotators(
+    config: &Config,
+    database: &Database,
+    warnings: &mut Vec<String>,
+) -> Vec<RotatorController> {
+    config
+        .all_rotators()
+        .into_iter()
+        .map(|rotator_config| {
+            let calibration = database
+                .read_rotator_calibration(&rotator_config.name)
+                .unwrap_or_else(|e| {
+                    warnings.push(format!("Could not load rotator calibration: {}", e));
+                    RotatorCalibration {
+                        rotator_name: rotator_config.name.clone(),
+                        ..Default::default()
+                    }
+                });
+            RotatorController::new(rotator_config, calibration)
+        })
+        .collect()
+}
+
+/// Move to the next `[[profiles]]` entry: reload the configuration file with
+/// that profile applied, then rebuild the observer, rotators and rig and
+/// predict passes for the new location. Returns warnings about files that
+/// could not be loaded for it.
+fn switch_profile(app_state: &mut AppState) -> Result<Vec<String>> {
+    let names: Vec<&str> = app_state
+        .config
+        .profiles
+        .iter()
+        .map(|profile| profile.name.as_str())
+        .collect();
+    if names.is_empty() {
+        anyhow::bail!("add [[profiles]] to the configuration to switch between them");
+    }
+    #[cfg(unix)]
+    if app_state.daemon.is_some() {
+        anyhow::bail!("the daemon drives the hardware; restart it with --profile");
+    }
+    let current = app_state.profile.as_deref().and_then(|name| {
+        names
+            .iter()
+            .position(|candidate| candidate.eq_ignore_ascii_case(name))
+    });
+    let next = names[current.map_or(0, |index| (index + 1) % names.len())].to_string();
+
+    let mut config = Config::load(&app_state.config_path)?;
+    config.apply_profile(&next)?;
+    let (latitude, longitude) = config.observer.coordinates()?;
+    let mut warnings = Vec::new();
+    // The DEM tile is read now, but the elevation API is asked in the background
+    let altitude = config
+        .observer
+        .altitude
+        .or_else(|| dem_altitude(&config.observer, latitude, longitude, &mut warnings));
+    app_state.altitude_lookup = None;
+    if altitude.is_none() && config.observer.elevation_lookup {
+        start_altitude_lookup(app_state, latitude, longitude);
+    }
+    let mut observer = observer_at(
+        &config,
+        latitude,
+        longitude,
+        altitude.unwrap_or(0.0),
+        &mut warnings,
+    );
+    observer.earth_orientation = app_state.observer.earth_orientation.take();
+    app_state.rotators = build_rotators(&config, &app_state.database, &mut warnings);
+    app_state.rig = config.rig.clone().map(RigController::new);
+    app_state.calibration_state = RotatorCalibrationState::new();
+    app_state.observer = observer;
+    app_state.config.observer = config.observer;
+    app_state.config.antenna = config.antenna;
+    app_state.config.antennas = config.antennas;
+    app_state.antenna_patterns = load_antenna_patterns(&app_state.config, &mut warnings);
+    app_state.config.rotator = config.rotator;
+    app_state.config.rotators = config.rotators;
+    app_state.config.rig = config.rig;
+    app_state.config.profiles = config.profiles;
+
+    repredict_passes(app_state);
+    app_state.database.save_setting(PROFILE_SETTING, &next)?;
+    app_state.profile = Some(next);
+    Ok(warnings)
+}
+
+/// Predict every satellite's passes again for the current observer
+fn repredict_passes(app_state: &mut AppState) {
+    let cache = PassCache::new(
+        &app_state.database,
+        &app_state.observer,
+        &app_state.config.prediction,
+    );
+    for satellite in app_state.satellites.iter_mut() {
+        satellite.passes = predict_passes(
+            satellite,
+            &app_state.observer,
+            &app_state.config.prediction,
+            &cache,
+        )
+        .unwrap_or_default();
+    }
+    update_alerts(app_state);
+}
+
+/// Ask the elevation API for the observer's altitude in the background
+fn start_altitude_lookup(app_state: &mut AppState, latitude: f64, longitude: f64) {
+    let slot: AltitudeLookup = Arc::new(Mutex::new(None));
+    app_state.altitude_lookup = Some(Arc::clone(&slot));
+    std::thread::spawn(move || {
+        let result = elevation::fetch_elevation(latitude, longitude).map_err(|e| format!("{:#}", e));
+        *slot.lock().unwrap() = Some(result);
+    });
+}
+
+/// Move the observer to the looked-up altitude once it arrives, and predict
+/// passes again from there
+fn poll_altitude_lookup(app_state: &mut AppState) {
+    let finished = app_state
+        .altitude_lookup
+        .as_ref()
+        .and_then(|slot| slot.lock().unwrap().take());
+    let Some(result) = finished else {
+        return;
+    };
+    app_state.altitude_lookup = None;
+    match result {
+        Ok(altitude) => {
+            app_state.observer.altitude = altitude;
+            repredict_passes(app_state);
+        }
+        Err(e) => app_state.status_message = Some(format!("Could not look up altitude: {}", e)),
+    }
+}
+
+/// Terrain elevation for an observer configured without an altitude: the DEM
+/// tile first, then the elevation API, otherwise sea level
+fn lookup_altitude(
+    observer: &config::ObserverConfig,
+    latitude: f64,
+    longitude: f64,
+    warnings: &mut Vec<String>,
+) -> f64 {
+    if let Some(altitude) = dem_altitude(observer, latitude, longitude, warnings) {
+        return altitude;
+    }
+    if observer.elevation_lookup {
+        match elevation::fetch_elevation(latitude, longitude) {
+            Ok(altitude) => return altitude,
+            Err(e) => warnings.push(format!("Could not look up altitude: {:#}", e)),
+        }
+    }
+    0.0
+}
+
+/// Terrain elevation from the observer's DEM tile, if it has one
+fn dem_altitude(
+    observer: &config::ObserverConfig,
+    latitude: f64,
+    longitude: f64,
+    warnings: &mut Vec<String>,
+) -> Option<f64> {
+    let dem_file = observer.dem_file.as_ref()?;
+    match elevation::hgt_elevation(dem_file, latitude, longitude) {
+        Ok(altitude) => Some(altitude),
+        Err(e) => {
+            warnings.push(format!("Could not read altitude from DEM: {:#}", e));
+            None
+        }
+    }
+}
+
+/// Run telemetry decoders over their satellites' passes and store what they decode
+fn run_decoders(app_state: &mut AppState) {
+    let now = Utc::now();
+    let mut received = false;
+    for decoder in app_state.decoders.iter_mut() {
+        let Some(satellite) = app_state
+            .satellites
+            .iter()
+            .find(|sat| sat.name == decoder.config.satellite)
+        else {
+            continue;
+        };
+        for frame in decoder.update(satellite, now) {
+            if let Err(e) = app_state.database.save_telemetry_frame(&frame) {
+                decoder.status = format!("Error: {}", e);
+            }
+            received |= frame.satellite_name == app_state.telemetry_state.satellite_name;
+        }
+    }
+
+    if received && app_state.mode == AppMode::Telemetry {
+        app_state.telemetry_state.reload(&app_state.database);
+    }
+}
+
+/// Set the rig up for the selected satellite once its pass begins
+fn update_rig(app_state: &mut AppState) {
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let mode = transponder_mode(app_state, &satellite.name);
+    if let Some(rig) = app_state.rig.as_mut() {
+        let now = Utc::now();
+        rig.update(satellite, mode.as_deref(), now);
+        rig.poll_s_meter(satellite, now);
+    }
+}
+
+/// A satellite's configured downlink in MHz, with any saved offset
+fn downlink_mhz(app_state: &AppState, satellite_name: &str) -> f64 {
+    let (downlink_mhz, _) = app_state.config.radio.frequencies_for(satellite_name);
+    downlink_mhz
+        + radio::downlink_offset_hz(&app_state.frequency_offsets, satellite_name, downlink_mhz)
+            / 1e6
+}
+
+/// Keep the SDR on the selected satellite's Doppler-corrected downlink
+fn update_sdr(app_state: &mut AppState) {
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let downlink_mhz = downlink_mhz(app_state, &satellite.name);
+    let mode = transponder_mode(app_state, &satellite.name);
+    if let Some(sdr) = app_state.sdr.as_mut() {
+        sdr.update(satellite, &app_state.observer, downlink_mhz, mode.as_deref(), Utc::now());
+    }
+}
+
+/// The pass recorder's status, when one is configured
+#[cfg(feature = "recording")]
+fn recorder_status(app_state: &AppState) -> Option<&str> {
+    app_state.recorder.as_ref().map(|recorder| recorder.status.as_str())
+}
+
+#[cfg(not(feature = "recording"))]
+fn recorder_status(_app_state: &AppState) -> Option<&str> {
+    None
+}
+
+/// Record the selected satellite's pass and link the file to the pass log at
+/// LOS, with the expected Doppler curve written next to it
+#[cfg(feature = "recording")]
+fn record_pass_audio(app_state: &mut AppState) {
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let downlink = downlink_mhz(app_state, &satellite.name);
+    let Some(recorder) = app_state.recorder.as_mut() else {
+        return;
+    };
+    let Some(entry) = recorder.update(satellite, Utc::now()) else {
+        return;
+    };
+    let path = entry.recording.clone().unwrap_or_default();
+    let times = (
+        DateTime::parse_from_rfc3339(&entry.aos_time),
+        DateTime::parse_from_rfc3339(&entry.los_time),
+    );
+    if let (Ok(aos), Ok(los)) = times {
+        let curve = waterfall::DopplerCurve::new(
+            satellite,
+            &app_state.observer,
+            downlink,
+            (aos.with_timezone(&Utc), los.with_timezone(&Utc)),
+            Duration::seconds(1),
+        );
+        let sidecar = std::path::Path::new(&path).with_extension("doppler.json");
+        let written = serde_json::to_string_pretty(&curve.json())
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(fs::write(&sidecar, json)?));
+        if let Err(e) = written {
+            recorder.status = format!("Error writing {}: {}", sidecar.display(), e);
+        }
+    }
+    let linked = app_state
+        .database
+        .log_pass(&entry)
+        .and_then(|id| app_state.database.set_pass_recording(id, &path));
+    if let Err(e) = linked {
+        recorder.status = format!("Error logging recording: {}", e);
+    }
+    if app_state.mode == AppMode::PassLog {
+        app_state.pass_log_state.reload(&app_state.database);
+    }
+}
+
+fn publish_home_assistant(app_state: &mut AppState, now: DateTime<Utc>) {
+    if let Some(home_assistant) = app_state.home_assistant.as_mut() {
+        home_assistant.update(
+            &app_state.satellites,
+            &app_state.current_positions,
+            &app_state.aliases,
+            now,
+        );
+    }
+}
+
+/// Whether the TUI is attached to a daemon that drives the rotators and rig
+fn attached(app_state: &AppState) -> bool {
+    #[cfg(unix)]
+    return app_state.daemon.is_some();
+    #[cfg(not(unix))]
+    return false;
+}
+
+/// Bring in the selection, tracking and hardware status of the attached
+/// daemon, then pass on changes made here
+#[cfg(unix)]
+fn sync_daemon(app_state: &mut AppState) {
+    use serde_json::json;
+
+    let Some(client) = app_state.daemon.as_mut() else {
+        return;
+    };
+    let changed = client.update();
+    if !client.connected {
+        for rotator in app_state.rotators.iter_mut() {
+            rotator.tracking = false;
+            rotator.status = "Daemon disconnected".to_string();
+        }
+        return;
+    }
+
+    let state = &mut client.state;
+    if changed {
+        let selected = state.selected.as_ref().and_then(|name| {
+            app_state
+                .satellites
+                .iter()
+                .position(|sat| &sat.name == name)
+        });
+        if let Some(index) = selected {
+            app_state.selected_satellite = index;
+        }
+        app_state.auto_follow = state.auto_follow;
+        for rotator in app_state.rotators.iter_mut() {
+            rotator.tracking = state.tracking;
+            if let Some((_, status)) = state
+                .rotators
+                .iter()
+                .find(|(name, _)| *name == rotator.config.name)
+            {
+                rotator.status = status.clone();
+            }
+        }
+        if let (Some(rig), Some(status)) = (app_state.rig.as_mut(), &state.rig) {
+            rig.status = status.clone();
+        }
+        if let (Some(sdr), Some(status)) = (app_state.sdr.as_mut(), &state.sdr) {
+            sdr.status = status.clone();
+        }
+    }
+
+    let selected = app_state
+        .satellites
+        .get(app_state.selected_satellite)
+        .map(|sat| sat.name.clone());
+    let tracking = app_state.rotators.iter().any(|r| r.tracking);
+    if app_state.auto_follow != state.auto_follow {
+        state.auto_follow = app_state.auto_follow;
+        client.change("auto_follow", json!({ "enabled": app_state.auto_follow }));
+    } else if !app_state.auto_follow && selected != state.selected {
+        state.selected = selected.clone();
+        client.change("select", json!({ "satellite": selected }));
+    } else if tracking != state.tracking {
+        state.tracking = tracking;
+        client.change(if tracking { "track" } else { "stop" }, json!({}));
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_daemon(_app_state: &mut AppState) {}
+
+/// Plan the selected satellite's pass on every rotator mapped to it, and
+/// point them at it when tracking is on
+fn track_rotator(app_state: &mut AppState) {
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let now = Utc::now();
+    let bands = app_state.config.satellite_bands(&satellite.name);
+    let mut position = None;
+    let attached = attached(app_state);
+
+    for rotator in app_state.rotators.iter_mut() {
+        if rotator.config.mapping.rank(&satellite.name, &bands).is_none() {
+            rotator.plan = None;
+            if rotator.tracking {
+                rotator.status = format!("Not used for {}", satellite.name);
+            }
+            continue;
+        }
+        rotator.prepare(satellite, &app_state.observer, now);
+
+        // Calibration moves the rotator by hand; don't fight it. An attached
+        // daemon does the pointing
+        if !rotator.tracking || app_state.mode == AppMode::RotatorCalibration || attached {
+            rotator.finish_pointing_log();
+        } else {
+            let position = position.get_or_insert_with(|| {
+                satellite.calculate_position(now, &app_state.observer).ok()
+            });
+            rotator.track(position.as_ref(), now);
+        }
+        if let Some(stats) = rotator.take_finished_pass() {
+            if let Err(e) = app_state.database.save_pointing_errors(&stats) {
+                rotator.status = format!("Error saving pointing errors: {}", e);
+            }
+        }
+    }
+}
+
+fn handle_calibration_input(app_state: &mut AppState, key: KeyCode) {
+    let state = &mut app_state.calibration_state;
+    let rotator_count = app_state.rotators.len();
+    let Some(rotator) = app_state.rotators.get_mut(state.rotator_index) else {
+        app_state.mode = AppMode::Normal;
+        return;
+    };
+
+    if let Some(field) = state.editing {
+        match key {
+            KeyCode::Esc => state.editing = None,
+            KeyCode::Enter => {
+                match state.input_buffer.trim().parse::<f64>() {
+                    Ok(offset) => {
+                        match field {
+                            CalibrationField::Azimuth => {
+                                rotator.calibration.azimuth_offset = offset
+                            }
+                            CalibrationField::Elevation => {
+                                rotator.calibration.elevation_offset = offset
+                            }
+                        }
+                        state.status_message = Some("Offset updated; press s to save".to_string());
+                    }
+                    Err(_) => {
+                        state.status_message =
+                            Some(format!("Invalid offset '{}'", state.input_buffer.trim()));
+                    }
+                }
+                state.editing = None;
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || c == '.' || c == '-' => {
+                state.input_buffer.push(c);
+            }
+            KeyCode::Backspace => {
+                state.input_buffer.pop();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    let targets = rotator::calibration_targets(Utc::now(), &app_state.observer);
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app_state.mode = AppMode::Normal;
+        }
+        KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+            state.selected_index -= 1;
+        }
+        KeyCode::Down | KeyCode::Char('j')
+            if state.selected_index < targets.len().saturating_sub(1) =>
+        {
+            state.selected_index += 1;
+        }
+        KeyCode::Enter | KeyCode::Char('g') => {
+            if let Some((name, azimuth, elevation)) = targets.get(state.selected_index) {
+                // Sent without offsets so the observed error is the full correction
+                state.status_message = Some(match rotator.point(*azimuth, *elevation, true) {
+                    Ok(()) => format!(
+                        "Moving to {}; enter how far the antenna actually points from it",
+                        name
+                    ),
+                    Err(e) => format!("Could not move rotator: {}", e),
+                });
+            }
+        }
+        KeyCode::Tab if rotator_count > 1 => {
+            state.rotator_index = (state.rotator_index + 1) % rotator_count;
+            state.reported_position = None;
+            state.status_message = None;
+            let name = &app_state.rotators[state.rotator_index].config.name;
+            state.load_pointing_errors(&app_state.database, name);
+        }
+        KeyCode::Char('p') => match rotator.read_position() {
+            Ok(position) => state.reported_position = Some(position),
+            Err(e) => state.status_message = Some(format!("Could not read position: {}", e)),
+        },
+        KeyCode::Char('a') => {
+            state.input_buffer = format!("{}", rotator.calibration.azimuth_offset);
+            state.editing = Some(CalibrationField::Azimuth);
+        }
+        KeyCode::Char('e') => {
+            state.input_buffer = format!("{}", rotator.calibration.elevation_offset);
+            state.editing = Some(CalibrationField::Elevation);
+        }
+        KeyCode::Char('s') => {
+            state.status_message = Some(
+                match app_state.database.save_rotator_calibration(&rotator.calibration) {
+                    Ok(()) => format!("Saved calibration for {}", rotator.config.name),
+                    Err(e) => format!("Error saving calibration: {}", e),
+                },
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Log the selected satellite's pass in progress, or its most recent one
+fn log_recent_pass(app_state: &mut AppState) -> String {
+    let now = Utc::now();
+    let satellite = &app_state.satellites[app_state.selected_satellite];
+    let pass = satellite
+        .get_current_pass(now)
+        .or_else(|| satellite.passes.iter().rev().find(|p| p.los_time <= now));
+    let Some(pass) = pass else {
+        return format!("No current or recent pass of {} to log", satellite.name);
+    };
+
+    let entry = PassLogEntry {
+        id: None,
+        satellite_name: satellite.name.clone(),
+        aos_time: pass.aos_time.to_rfc3339(),
+        los_time: pass.los_time.to_rfc3339(),
+        max_elevation: pass.max_elevation,
+        notes: None,
+        rating: None,
+        recording: None,
+    };
+    match app_state.database.log_pass(&entry) {
+        Ok(_) => format!(
+            "Logged {} pass at {}",
+            satellite.name,
+            pass.aos_time.format("%Y-%m-%d %H:%M UTC")
+        ),
+        Err(e) => format!("Error logging pass: {}", e),
+    }
+}
+
+fn handle_pass_log_input(app_state: &mut AppState, key: KeyCode) {
+    let state = &mut app_state.pass_log_state;
+
+    if let Some(input) = state.input {
+        match key {
+            KeyCode::Esc => {
+                state.input = None;
+            }
+            KeyCode::Enter => {
+                let text = std::mem::take(&mut state.input_buffer);
+                state.input = None;
+                match input {
+                    PassLogInput::Search => {
+                        state.search = text;
+                        state.selected_index = 0;
+                    }
+                    PassLogInput::Notes => {
+                        if let Some(entry) = state.entries.get(state.selected_index) {
+                            let notes = (!text.trim().is_empty()).then_some(text.trim());
+                            let id = entry.id.unwrap_or_default();
+                            if let Err(e) =
+                                app_state.database.update_pass_log(id, notes, entry.rating)
+                            {
+                                state.status_message = Some(format!("Error saving notes: {}", e));
+                            }
+                        }
+                    }
+                }
+                state.reload(&app_state.database);
+            }
+            KeyCode::Char(c) => state.input_buffer.push(c),
+            KeyCode::Backspace => {
+                state.input_buffer.pop();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') if state.history.is_some() => {
+            state.history = None;
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.stop_playback();
+            app_state.mode = AppMode::Normal;
+        }
+        KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+            state.selected_index -= 1;
+            state.history = None;
+        }
+        KeyCode::Down | KeyCode::Char('j')
+            if state.selected_index < state.entries.len().saturating_sub(1) =>
+        {
+            state.selected_index += 1;
+            state.history = None;
+        }
+        KeyCode::Char('h') if state.history.is_some() => {
+            state.history = None;
+        }
+        KeyCode::Char('h') if !state.entries.is_empty() => {
+            let entry = &state.entries[state.selected_index];
+            let bound = |text: &str, margin: i64| {
+                DateTime::parse_from_rfc3339(text).map(|time| {
+                    (time.with_timezone(&Utc) + Duration::minutes(margin))
+                        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+                })
+            };
+            // A minute either side, for a pass logged from predicted times
+            let (Ok(from), Ok(to)) = (bound(&entry.aos_time, -1), bound(&entry.los_time, 1)) else {
+                state.status_message = Some("This entry's times can't be read".to_string());
+                return;
+            };
+            match app_state
+                .database
+                .read_position_history(&entry.satellite_name, &from, &to)
+            {
+                Ok(history) if history.is_empty() => {
+                    state.status_message = Some(
+                        "No positions recorded for this pass; set record = true under [history]"
+                            .to_string(),
+                    );
                 }
-                KeyCode::Backspace => {
-                    state.input_buffer.pop();
+                Ok(history) => state.history = Some(history),
+                Err(e) => state.status_message = Some(format!("Error reading history: {}", e)),
+            }
+        }
+        #[cfg(feature = "recording")]
+        KeyCode::Char('p') if state.stop_playback() => {
+            state.status_message = Some("Stopped playback".to_string());
+        }
+        #[cfg(not(feature = "recording"))]
+        KeyCode::Char('p') => {
+            state.status_message =
+                Some("Playback needs crabtrack built with --features recording".to_string());
+        }
+        #[cfg(feature = "recording")]
+        KeyCode::Char('p') if !state.entries.is_empty() => {
+            let entry = &state.entries[state.selected_index];
+            let Some(path) = entry.recording.as_deref().map(std::path::Path::new) else {
+                state.status_message = Some("No recording of this pass".to_string());
+                return;
+            };
+            state.status_message = Some(if !path.exists() {
+                format!("{} is missing", path.display())
+            } else {
+                match recorder::start_playback(path) {
+                    Ok(player) => {
+                        state.playback = Some(player);
+                        format!("Playing {} (p: stop)", path.display())
+                    }
+                    Err(e) => format!("Error: {:#}", e),
                 }
-                _ => {}
+            });
+        }
+        KeyCode::Char('n') | KeyCode::Enter if !state.entries.is_empty() => {
+            state.input_buffer = state.entries[state.selected_index]
+                .notes
+                .clone()
+                .unwrap_or_default();
+            state.input = Some(PassLogInput::Notes);
+        }
+        KeyCode::Char('/') => {
+            state.input_buffer = state.search.clone();
+            state.input = Some(PassLogInput::Search);
+        }
+        KeyCode::Char(c @ '0'..='5') if !state.entries.is_empty() => {
+            // 1-5 rates the pass, 0 clears the rating
+            let entry = &state.entries[state.selected_index];
+            let rating = c.to_digit(10).filter(|r| *r > 0).map(|r| r as i32);
+            let id = entry.id.unwrap_or_default();
+            if let Err(e) = app_state.database.update_pass_log(id, entry.notes.as_deref(), rating) {
+                state.status_message = Some(format!("Error saving rating: {}", e));
             }
+            state.reload(&app_state.database);
         }
+        _ => {}
     }
+}
 
-    Ok(())
+/// Apply a finished SATCAT lookup to the satellite it was started for
+fn poll_satcat_enrichment(app_state: &mut AppState) {
+    let state = &mut app_state.sat_config_state;
+    let Some(result) = state
+        .enrichment
+        .as_ref()
+        .and_then(|(_, slot)| slot.lock().unwrap().take())
+    else {
+        return;
+    };
+    let Some((id, _)) = state.enrichment.take() else {
+        return;
+    };
+    let Some(mut details) = state.satellites.iter().find(|sat| sat.id == Some(id)).cloned() else {
+        return;
+    };
+
+    state.status_message = Some(match result {
+        Ok(record) => {
+            let filled = record.apply_to(&mut details);
+            match app_state.database.update(&details) {
+                Ok(_) => {
+                    let _ = state.load_from_database(&app_state.database);
+                    format!("Filled {} fields for {} from SATCAT", filled, details.name)
+                }
+                Err(e) => format!("Error saving: {}", e),
+            }
+        }
+        Err(e) => format!("SATCAT lookup failed: {}", e),
+    });
 }
 
 fn handle_utility_menu_input(app_state: &mut AppState, key: KeyCode) -> Result<()> {
@@ -1018,15 +5594,11 @@ fn handle_utility_menu_input(app_state: &mut AppState, key: KeyCode) -> Result<(
                     state.reset();
                     app_state.mode = AppMode::Normal;
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.selected_index > 0 {
-                        state.selected_index -= 1;
-                    }
+                KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+                    state.selected_index -= 1;
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.selected_index < TLE_SOURCES.len() - 1 {
-                        state.selected_index += 1;
-                    }
+                KeyCode::Down | KeyCode::Char('j') if state.selected_index < TLE_SOURCES.len() - 1 => {
+                    state.selected_index += 1;
                 }
                 KeyCode::Enter => {
                     let source = &TLE_SOURCES[state.selected_index];
@@ -1064,6 +5636,757 @@ fn handle_utility_menu_input(app_state: &mut AppState, key: KeyCode) -> Result<(
     Ok(())
 }
 
+/// Refresh the station's observations in the background, first scheduling
+/// `schedule` = (NORAD id, start, end) if given
+fn start_satnogs_request(
+    app_state: &mut AppState,
+    schedule: Option<(u32, DateTime<Utc>, DateTime<Utc>)>,
+) {
+    let Some(config) = app_state.config.satnogs.clone() else {
+        app_state.satnogs_state.status_message =
+            Some("Add a [satnogs] section with your ground_station to config.toml".to_string());
+        return;
+    };
+
+    let slot: SatnogsResult = Arc::new(Mutex::new(None));
+    app_state.satnogs_state.pending = Some(Arc::clone(&slot));
+    app_state.satnogs_state.status_message = Some(if schedule.is_some() {
+        "Scheduling observation...".to_string()
+    } else {
+        format!("Fetching observations for station {}...", config.ground_station)
+    });
+
+    std::thread::spawn(move || {
+        let result = (|| {
+            let message = match schedule {
+                Some((norad_id, start, end)) => {
+                    let transmitter =
+                        satnogs::schedule_observation(&config, norad_id, start, end)?;
+                    format!("Scheduled {} at {} UTC", transmitter, start.format("%H:%M"))
+                }
+                None => String::new(),
+            };
+            let observations = satnogs::fetch_observations(&config)?;
+            let message = if message.is_empty() {
+                format!("{} observations scheduled", observations.len())
+            } else {
+                message
+            };
+            anyhow::Ok((message, observations))
+        })();
+        *slot.lock().unwrap() = Some(result.map_err(|e| format!("{:#}", e)));
+    });
+}
+
+/// Recompute the ground track table for the selected satellite
+fn update_ground_track(app_state: &mut AppState) {
+    let state = &mut app_state.ground_track_state;
+    state.points.clear();
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let now = Utc::now();
+    let span = match state.span {
+        TrackSpan::Orbit => {
+            let period = Duration::seconds((satellite.period_minutes() * 60.0) as i64);
+            Some((now, now + period))
+        }
+        TrackSpan::Pass => satellite
+            .get_current_pass(now)
+            .or_else(|| satellite.get_next_pass())
+            .map(|pass| (pass.aos_time, pass.los_time)),
+    };
+    let Some((from, to)) = span else {
+        state.status_message = Some(format!("No upcoming pass of {}", satellite.name));
+        return;
+    };
+    let step = Duration::seconds(state.step_seconds());
+    state.points = satellite.ground_track(&app_state.observer, from, to, step);
+    state.selected_index = state.selected_index.min(state.points.len().saturating_sub(1));
+}
+
+/// Write the measured coverage contour as a horizon file in the current directory
+fn export_coverage_horizon(app_state: &AppState) -> Result<PathBuf> {
+    let Some(coverage) = &app_state.coverage_state.coverage else {
+        anyhow::bail!("the pass log could not be read");
+    };
+    if coverage.contour().is_empty() {
+        anyhow::bail!("no telemetry was decoded during a logged pass");
+    }
+    let path = PathBuf::from(format!(
+        "horizon-measured-{}.csv",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::write(&path, coverage.horizon_csv())?;
+    Ok(path)
+}
+
+/// Write the ground track table to a CSV file in the current directory
+fn export_ground_track(app_state: &AppState) -> Result<PathBuf> {
+    let satellite = &app_state.satellites[app_state.selected_satellite];
+    let path = PathBuf::from(format!(
+        "ground-track-{}-{}.csv",
+        satellite.elements.norad_id,
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::write(&path, satellite::ground_track_csv(&app_state.ground_track_state.points))?;
+    Ok(path)
+}
+
+fn handle_ground_track_input(app_state: &mut AppState, key: KeyCode) {
+    let state = &mut app_state.ground_track_state;
+    let last = state.points.len().saturating_sub(1);
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app_state.mode = AppMode::Normal;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.selected_index = state.selected_index.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.selected_index = (state.selected_index + 1).min(last);
+        }
+        KeyCode::PageUp => state.selected_index = state.selected_index.saturating_sub(10),
+        KeyCode::PageDown => state.selected_index = (state.selected_index + 10).min(last),
+        KeyCode::Home => state.selected_index = 0,
+        KeyCode::End => state.selected_index = last,
+        KeyCode::Tab => {
+            state.span = match state.span {
+                TrackSpan::Orbit => TrackSpan::Pass,
+                TrackSpan::Pass => TrackSpan::Orbit,
+            };
+            state.selected_index = 0;
+            state.status_message = None;
+            update_ground_track(app_state);
+        }
+        KeyCode::Char('+') | KeyCode::Char('=') if state.step_index + 1 < TRACK_STEPS.len() => {
+            state.step_index += 1;
+            update_ground_track(app_state);
+        }
+        KeyCode::Char('-') if state.step_index > 0 => {
+            state.step_index -= 1;
+            update_ground_track(app_state);
+        }
+        KeyCode::Char('r') => update_ground_track(app_state),
+        KeyCode::Char('e') if !state.points.is_empty() => {
+            let message = match export_ground_track(app_state) {
+                Ok(path) => format!("Saved {}", path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            };
+            app_state.ground_track_state.status_message = Some(message);
+        }
+        _ => {}
+    }
+}
+
+fn handle_constellation_input(app_state: &mut AppState, key: KeyCode) {
+    let count = constellation::group(
+        &app_state.satellites,
+        app_state.config.display.constellation_min_size,
+    )
+    .len();
+    let state = &mut app_state.constellation_state;
+    match key {
+        KeyCode::Char('q') | KeyCode::Esc => app_state.mode = AppMode::Normal,
+        KeyCode::Down | KeyCode::Char('j') if state.selected_index + 1 < count => {
+            state.selected_index += 1;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.selected_index = state.selected_index.saturating_sub(1);
+        }
+        _ => {}
+    }
+}
+
+/// Every known downlink of the satellites above the horizon, by frequency:
+/// transponders, and the downlinks set under [radio.satellites] and in the
+/// satellite editor
+pub fn band_occupancy(app_state: &AppState) -> Vec<occupancy::Downlink> {
+    let mut known: Vec<(String, String, f64)> = Vec::new();
+    for (name, radio) in &app_state.config.radio.satellites {
+        if let Some(mhz) = radio.downlink_frequency_mhz {
+            known.push((name.clone(), "Configured".to_string(), mhz));
+        }
+    }
+    for details in &app_state.sat_config_state.satellites {
+        if let Some(mhz) = details.downlink_frequency_mhz {
+            known.push((details.name.clone(), "Satellite details".to_string(), mhz));
+        }
+    }
+    occupancy::downlinks(&app_state.current_positions, &app_state.transponders, &known)
+}
+
+fn handle_band_occupancy_input(app_state: &mut AppState, key: KeyCode) {
+    let downlinks = band_occupancy(app_state);
+    let state = &mut app_state.band_occupancy_state;
+    match key {
+        KeyCode::Char('q' | 'b') | KeyCode::Esc => app_state.mode = AppMode::Normal,
+        KeyCode::Down | KeyCode::Char('j') if state.selected_index + 1 < downlinks.len() => {
+            state.selected_index += 1;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.selected_index = state.selected_index.saturating_sub(1);
+        }
+        KeyCode::Enter => {
+            // Select the satellite the downlink belongs to
+            let selected = downlinks.get(state.selected_index).and_then(|downlink| {
+                app_state.satellites.iter().position(|sat| sat.name == downlink.satellite)
+            });
+            if let Some(index) = selected {
+                app_state.selected_satellite = index;
+                app_state.auto_follow = false;
+                app_state.mode = AppMode::Normal;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Geostationary slots shown on the pointing page
+pub fn geostationary_slots(app_state: &AppState) -> Vec<config::GeostationarySlot> {
+    geostationary::slots(
+        &app_state.config.geostationary,
+        &app_state.satellites,
+        &app_state.observer,
+        Utc::now(),
+    )
+}
+
+/// Start on the first slot with the LNB offset measured last, if any
+fn open_geostationary(app_state: &mut AppState) {
+    let measured = app_state.database.read_setting(LNB_OFFSET_SETTING).ok().flatten();
+    app_state.geostationary_state = GeostationaryState {
+        lnb_offset_hz: measured
+            .and_then(|hz| hz.parse().ok())
+            .unwrap_or(app_state.config.geostationary.lnb_offset_hz),
+        ..GeostationaryState::default()
+    };
+    update_sun_outages(app_state);
+}
+
+/// Predict the sun outages of the selected slot over the next year
+fn update_sun_outages(app_state: &mut AppState) {
+    let slots = geostationary_slots(app_state);
+    let state = &mut app_state.geostationary_state;
+    let now = Utc::now();
+    state.sun_outages = slots
+        .get(state.selected_index)
+        .map(|slot| {
+            geostationary::sun_outages(
+                &app_state.observer,
+                slot.longitude,
+                (now, now + Duration::days(365)),
+                app_state.config.geostationary.sun_outage_deg,
+            )
+        })
+        .unwrap_or_default();
+}
+
+fn save_lnb_offset(app_state: &mut AppState, offset_hz: f64) {
+    let state = &mut app_state.geostationary_state;
+    state.lnb_offset_hz = offset_hz;
+    let saved = app_state
+        .database
+        .save_setting(LNB_OFFSET_SETTING, &offset_hz.to_string());
+    state.status_message = Some(match saved {
+        Ok(()) => format!("LNB offset {:+.0} Hz saved", offset_hz),
+        Err(e) => format!("Error saving LNB offset: {}", e),
+    });
+}
+
+fn handle_geostationary_input(app_state: &mut AppState, key: KeyCode) {
+    let slots = geostationary_slots(app_state);
+    let lnb_lo_mhz = app_state.config.geostationary.lnb_lo_mhz;
+    let state = &mut app_state.geostationary_state;
+    let beacon_mhz = slots
+        .get(state.selected_index)
+        .and_then(|slot| slot.beacon_mhz);
+
+    if state.editing {
+        match key {
+            KeyCode::Esc => state.editing = false,
+            KeyCode::Enter => {
+                state.editing = false;
+                let observed = state.input_buffer.trim().to_string();
+                match (observed.parse::<f64>(), beacon_mhz) {
+                    (Ok(observed_mhz), Some(beacon_mhz)) => {
+                        let offset =
+                            geostationary::lnb_offset_hz(beacon_mhz, lnb_lo_mhz, observed_mhz);
+                        save_lnb_offset(app_state, offset);
+                    }
+                    _ => state.status_message = Some(format!("Invalid frequency '{}'", observed)),
+                }
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => state.input_buffer.push(c),
+            KeyCode::Backspace => {
+                state.input_buffer.pop();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    match key {
+        KeyCode::Char('q' | 'G') | KeyCode::Esc => app_state.mode = AppMode::Normal,
+        KeyCode::Down | KeyCode::Char('j') if state.selected_index + 1 < slots.len() => {
+            state.selected_index += 1;
+            state.status_message = None;
+            update_sun_outages(app_state);
+        }
+        KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+            state.selected_index -= 1;
+            state.status_message = None;
+            update_sun_outages(app_state);
+        }
+        KeyCode::Char('o') => match beacon_mhz {
+            Some(beacon_mhz) => {
+                let expected =
+                    geostationary::intermediate_mhz(beacon_mhz, lnb_lo_mhz, state.lnb_offset_hz);
+                state.input_buffer = format!("{:.4}", expected);
+                state.editing = true;
+            }
+            None => {
+                state.status_message = Some("No beacon is configured for this satellite".into());
+            }
+        },
+        KeyCode::Char('0') => save_lnb_offset(app_state, 0.0),
+        _ => {}
+    }
+}
+
+/// Look down on the observer's current position
+fn reset_orbit_view(app_state: &mut AppState) {
+    let observer = orbit_view::observer_position(&app_state.observer, Utc::now());
+    app_state.orbit_view_state.view = orbit_view::View::facing(&observer);
+}
+
+fn handle_orbit_view_input(app_state: &mut AppState, key: KeyCode) {
+    let view = &mut app_state.orbit_view_state.view;
+    match key {
+        KeyCode::Char('q') | KeyCode::Esc => app_state.mode = AppMode::Normal,
+        KeyCode::Left | KeyCode::Char('h') => view.longitude -= 15.0,
+        KeyCode::Right | KeyCode::Char('l') => view.longitude += 15.0,
+        KeyCode::Up | KeyCode::Char('k') => view.latitude = (view.latitude + 15.0).min(90.0),
+        KeyCode::Down | KeyCode::Char('j') => view.latitude = (view.latitude - 15.0).max(-90.0),
+        KeyCode::Char('r') => reset_orbit_view(app_state),
+        _ => {}
+    }
+}
+
+fn handle_satnogs_input(app_state: &mut AppState, key: KeyCode) {
+    if app_state.satnogs_state.is_busy() {
+        if key == KeyCode::Esc {
+            app_state.mode = AppMode::Normal;
+        }
+        return;
+    }
+
+    let rows = satnogs::schedule_rows(
+        &app_state.satellites,
+        &app_state.satnogs_state.observations,
+        Utc::now(),
+    );
+    let state = &mut app_state.satnogs_state;
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app_state.mode = AppMode::Normal;
+        }
+        KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+            state.selected_index -= 1;
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.selected_index + 1 < rows.len() => {
+            state.selected_index += 1;
+        }
+        KeyCode::Char('r') => start_satnogs_request(app_state, None),
+        KeyCode::Enter => match rows.get(state.selected_index) {
+            Some(row) if row.observation.is_some() => {
+                state.status_message = Some("Already scheduled".to_string());
+            }
+            Some(row) => match row.norad_id {
+                Some(norad_id) => {
+                    let schedule = (norad_id, row.start.max(Utc::now()), row.end);
+                    start_satnogs_request(app_state, Some(schedule));
+                }
+                None => state.status_message = Some("No NORAD id in this TLE".to_string()),
+            },
+            None => {}
+        },
+        _ => {}
+    }
+}
+
+/// Save a sky chart of a pass as SVG and PNG in the current directory
+fn export_pass_chart(app_state: &AppState, name: &str, pass: &SatellitePass) -> Result<String> {
+    let satellite = app_state
+        .satellites
+        .iter()
+        .find(|sat| sat.name == name)
+        .ok_or_else(|| anyhow::anyhow!("{} is no longer tracked", name))?;
+    let chart = skychart::PassChart::new(satellite, &app_state.observer, pass);
+    let stem = format!(
+        "pass-{}-{}",
+        satellite.elements.norad_id,
+        pass.aos_time.with_timezone(&chrono::Local).format("%Y%m%d-%H%M")
+    );
+    fs::write(format!("{}.svg", stem), chart.svg())?;
+    fs::write(format!("{}.png", stem), chart.png()?)?;
+    Ok(stem)
+}
+
+/// Play a pass through at accelerated time: the rotator following it with
+/// its configured limits, the radio settings and the mode changes on the way
+fn rehearse_pass(app_state: &mut AppState, satellite_index: usize, pass: Option<SatellitePass>) {
+    let satellite = &app_state.satellites[satellite_index];
+    let bands = app_state.config.satellite_bands(&satellite.name);
+    let rotator_config = app_state
+        .rotators
+        .iter()
+        .filter_map(|rotator| {
+            rotator
+                .config
+                .mapping
+                .rank(&satellite.name, &bands)
+                .map(|rank| (rank, rotator))
+        })
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, rotator)| rotator.config.clone())
+        .unwrap_or_default();
+
+    let mut state = RotatorSimulationState::new();
+    if let Some(pass) = &pass {
+        let plan = rotator::plan_pass(satellite, pass, &app_state.observer, &rotator_config);
+        state.events = rehearsal_events(app_state, satellite, pass, &plan);
+        state.plan = Some(plan);
+    }
+    app_state.simulation_state = state;
+    app_state.mode = AppMode::RotatorSimulation;
+}
+
+/// What changes during a pass: where the rig switches mode, where the
+/// recommended mode changes with elevation, TCA and any swing through the stop
+fn rehearsal_events(
+    app_state: &AppState,
+    satellite: &Satellite,
+    pass: &SatellitePass,
+    plan: &PassPlan,
+) -> Vec<(DateTime<Utc>, String)> {
+    let transponder_mode = app_state
+        .config
+        .radio
+        .for_satellite(&satellite.name)
+        .and_then(|radio| radio.mode.clone());
+    let aos = match (&app_state.config.rig, &transponder_mode) {
+        (Some(rig), Some(mode)) if rig.set_mode_at_aos => format!("AOS, rig switches to {}", mode),
+        _ => "AOS".to_string(),
+    };
+    let mut events = vec![(pass.aos_time, aos)];
+
+    let orbit = satellite.orbit_class();
+    let downlink_mhz = downlink_mhz(app_state, &satellite.name);
+    let mut recommended: Option<String> = None;
+    for sample in &plan.samples {
+        let Ok(position) = satellite.calculate_position(sample.time, &app_state.observer) else {
+            continue;
+        };
+        let station = station_link(
+            &app_state.config,
+            &app_state.antenna_patterns,
+            &app_state.observer.noise,
+            &position,
+            downlink_mhz,
+        );
+        let mode =
+            evaluate_communication_window(&position, orbit, downlink_mhz, station).recommended_mode;
+        if mode != recommended {
+            let description = match &mode {
+                Some(mode) => format!("{} recommended", mode),
+                None => "Too low for a reliable contact".to_string(),
+            };
+            // The first sample only sets the starting point unless it is usable
+            if sample.time > pass.aos_time || mode.is_some() {
+                events.push((sample.time, description));
+            }
+            recommended = mode;
+        }
+    }
+
+    events.push((
+        pass.max_elevation_time,
+        format!("TCA at {:.0}°, Doppler crosses zero", pass.max_elevation),
+    ));
+    if let Some(sample) = plan.samples.iter().find(|sample| sample.through_stop) {
+        events.push((sample.time, "Rotator swings through the azimuth stop".to_string()));
+    }
+    events.push((pass.los_time, "LOS".to_string()));
+    events.sort_by_key(|(time, _)| *time);
+    events
+}
+
+fn handle_pass_window_input(app_state: &mut AppState, key: KeyEvent) {
+    if key.code == KeyCode::Char('e') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        let state = &app_state.pass_window_state;
+        if let Some((name, pass)) = state.passes.get(state.selected_index) {
+            let message = match export_pass_chart(app_state, name, pass) {
+                Ok(stem) => format!("Saved {0}.svg and {0}.png", stem),
+                Err(e) => format!("Chart export failed: {:#}", e),
+            };
+            app_state.pass_window_state.status_message = Some(message);
+        }
+        return;
+    }
+    if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        let state = &app_state.pass_window_state;
+        if let Some((name, pass)) = state.passes.get(state.selected_index).cloned() {
+            match app_state.satellites.iter().position(|sat| sat.name == name) {
+                Some(index) => {
+                    rehearse_pass(app_state, index, Some(pass));
+                    app_state.simulation_state.return_mode = AppMode::PassWindow;
+                }
+                None => {
+                    app_state.pass_window_state.status_message =
+                        Some(format!("{} is no longer tracked", name));
+                }
+            }
+        }
+        return;
+    }
+
+    let state = &mut app_state.pass_window_state;
+    let field = match state.field {
+        WindowField::From => &mut state.from,
+        WindowField::To => &mut state.to,
+    };
+
+    match key.code {
+        KeyCode::Esc => {
+            app_state.mode = AppMode::Normal;
+        }
+        KeyCode::Tab | KeyCode::BackTab => {
+            state.field = match state.field {
+                WindowField::From => WindowField::To,
+                WindowField::To => WindowField::From,
+            };
+        }
+        KeyCode::Up if state.selected_index > 0 => {
+            state.selected_index -= 1;
+        }
+        KeyCode::Down if state.selected_index < state.passes.len().saturating_sub(1) => {
+            state.selected_index += 1;
+        }
+        KeyCode::Enter => {
+            let window = pass_prediction::parse_window_time(&state.from).and_then(|from| {
+                Ok((from, pass_prediction::parse_window_time(&state.to)?))
+            });
+            let result = window.and_then(|(from, to)| {
+                let cache = PassCache::new(
+                    &app_state.database,
+                    &app_state.observer,
+                    &app_state.config.prediction,
+                );
+                passes_in_window(
+                    &app_state.satellites,
+                    &app_state.observer,
+                    &app_state.config.prediction,
+                    &cache,
+                    from,
+                    to,
+                )
+            });
+            let state = &mut app_state.pass_window_state;
+            state.selected_index = 0;
+            match result {
+                Ok(passes) => {
+                    state.status_message = Some(format!("{} passes in the window", passes.len()));
+                    state.passes = passes;
+                }
+                Err(e) => {
+                    state.passes.clear();
+                    state.status_message = Some(format!("{:#}", e));
+                }
+            }
+        }
+        KeyCode::Char(c) => {
+            field.push(c);
+        }
+        KeyCode::Backspace => {
+            field.pop();
+        }
+        _ => {}
+    }
+}
+
+fn handle_catalog_search_input(app_state: &mut AppState, key: KeyCode) -> Result<()> {
+    let state = &mut app_state.catalog_search_state;
+
+    if state.is_searching() {
+        // Ignore input while a search is in flight
+        return Ok(());
+    }
+
+    match key {
+        KeyCode::Esc => {
+            app_state.mode = AppMode::Normal;
+        }
+        KeyCode::Up if state.selected_index > 0 => {
+            state.selected_index -= 1;
+        }
+        KeyCode::Down if state.selected_index < state.results.len().saturating_sub(1) => {
+            state.selected_index += 1;
+        }
+        KeyCode::Enter if !state.results.is_empty() => {
+            // Add the highlighted result to the database and tracked set
+            let entry = state.results[state.selected_index].clone();
+            let message = match track_catalog_entry(app_state, &entry) {
+                Ok(()) => format!("Added {} ({}) to tracking", entry.name, entry.norad_id),
+                Err(e) => format!("Failed to add {}: {}", entry.name, e),
+            };
+            app_state.catalog_search_state.status_message = Some(message);
+        }
+        KeyCode::Tab => {
+            // Watch for a launch that has no element sets yet
+            let message = match celestrak::LaunchIdentifier::parse(&state.query) {
+                Some(_) => match app_state.database.add_pending_launch(state.query.trim()) {
+                    Ok(_) => {
+                        app_state.pending_launches =
+                            app_state.database.read_pending_launches().unwrap_or_default();
+                        // Check right away in case element sets already exist
+                        app_state.last_pending_check = None;
+                        format!("Watching {} as a pending launch", state.query.trim())
+                    }
+                    Err(e) => format!("Failed to add pending launch: {}", e),
+                },
+                None => "Enter a NORAD ID or designator (e.g. 2026-042) to watch".to_string(),
+            };
+            app_state.catalog_search_state.status_message = Some(message);
+        }
+        KeyCode::Enter if !state.query.trim().is_empty() => {
+            let slot: CatalogSearchResult = Arc::new(Mutex::new(None));
+            state.pending = Some(Arc::clone(&slot));
+            state.status_message = Some(format!("Searching Celestrak for \"{}\"...", state.query));
+
+            let query = state.query.clone();
+            std::thread::spawn(move || {
+                let result = celestrak::search_by_name(&query).map_err(|e| e.to_string());
+                *slot.lock().unwrap() = Some(result);
+            });
+        }
+        KeyCode::Char(c) => {
+            // Editing the query starts a new search
+            state.query.push(c);
+            state.results.clear();
+        }
+        KeyCode::Backspace => {
+            state.query.pop();
+            state.results.clear();
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Periodically look for element sets of pending launches in the background and
+/// start tracking any that have appeared
+fn poll_pending_launches(app_state: &mut AppState) {
+    let finished = app_state
+        .pending_check
+        .as_ref()
+        .and_then(|p| p.lock().unwrap().take());
+
+    if let Some(results) = finished {
+        app_state.pending_check = None;
+        for (pending_id, entries) in results {
+            if entries.is_empty() {
+                continue;
+            }
+            let tracked = entries
+                .iter()
+                .all(|entry| track_catalog_entry(app_state, entry).is_ok());
+            if tracked {
+                let _ = app_state.database.delete_pending_launch(pending_id);
+            }
+        }
+        app_state.pending_launches = app_state.database.read_pending_launches().unwrap_or_default();
+        return;
+    }
+
+    if app_state.pending_check.is_some() || app_state.pending_launches.is_empty() {
+        return;
+    }
+
+    let now = Utc::now();
+    let interval = Duration::minutes(app_state.config.satellites.pending_check_minutes as i64);
+    if app_state
+        .last_pending_check
+        .is_some_and(|last| now - last < interval)
+    {
+        return;
+    }
+    app_state.last_pending_check = Some(now);
+
+    let launches: Vec<(i64, celestrak::LaunchIdentifier)> = app_state
+        .pending_launches
+        .iter()
+        .filter_map(|p| Some((p.id?, celestrak::LaunchIdentifier::parse(&p.identifier)?)))
+        .collect();
+    let slot: PendingCheckResult = Arc::new(Mutex::new(None));
+    app_state.pending_check = Some(Arc::clone(&slot));
+
+    std::thread::spawn(move || {
+        let results = launches
+            .into_iter()
+            .map(|(id, identifier)| (id, celestrak::fetch_launch(&identifier).unwrap_or_default()))
+            .collect();
+        *slot.lock().unwrap() = Some(results);
+    });
+}
+
+/// Store a catalog entry in the database and start tracking it
+fn track_catalog_entry(app_state: &mut AppState, entry: &celestrak::CatalogEntry) -> Result<()> {
+    let mut details = app_state
+        .database
+        .read_by_name(&entry.name)?
+        .unwrap_or_else(|| SatelliteDetails::new(entry.name.clone()));
+    details.tle_line1 = entry.tle_line1.clone();
+    details.tle_line2 = entry.tle_line2.clone();
+    if details.notes.is_none() {
+        details.notes = Some(format!("Added from Celestrak search (NORAD {})", entry.norad_id));
+    }
+    app_state.database.upsert(&details)?;
+
+    if app_state.satellites.iter().any(|s| s.name == entry.name) {
+        return Ok(());
+    }
+
+    let elements = Elements::from_tle(
+        Some(entry.name.clone()),
+        entry.tle_line1.as_bytes(),
+        entry.tle_line2.as_bytes(),
+    )
+    .map_err(|e| anyhow::anyhow!("invalid TLE: {:?}", e))?;
+    let mut satellite = Satellite::new(
+        entry.name.clone(),
+        elements,
+        parse_tle_epoch(&entry.tle_line1),
+        entry.tle_line1.clone(),
+        entry.tle_line2.clone(),
+    );
+    let cache = PassCache::new(
+        &app_state.database,
+        &app_state.observer,
+        &app_state.config.prediction,
+    );
+    satellite.passes = predict_passes(
+        &satellite,
+        &app_state.observer,
+        &app_state.config.prediction,
+        &cache,
+    )?;
+    app_state.satellites.push(satellite);
+
+    Ok(())
+}
+
 /// Download TLE data from Celestrak, reporting byte progress via shared state
 fn download_tle_from_celestrak(group: &str, _progress: Arc<Mutex<DownloadProgress>>) -> Result<String> {
     use std::io::Read;
@@ -1167,6 +6490,25 @@ fn download_all_groups() -> Result<String> {
     Ok(all_data)
 }
 
+/// Compare a freshly downloaded group with the element sets stored from it before
+fn group_diff(database: &Database, source_name: &str, tle_data: &str) -> tle_diff::TleDiff {
+    let stored: Vec<celestrak::CatalogEntry> = database
+        .read_all()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|details| details.satellite_type.as_deref() == Some(source_name))
+        .filter_map(|details| {
+            Some(celestrak::CatalogEntry {
+                norad_id: celestrak::norad_id(&details.tle_line1)?,
+                name: details.name,
+                tle_line1: details.tle_line1,
+                tle_line2: details.tle_line2,
+            })
+        })
+        .collect();
+    tle_diff::diff(&stored, &celestrak::parse_entries(tle_data))
+}
+
 /// Parse TLE data and store satellites in database
 fn parse_and_store_tles(
     tle_data: &str,
@@ -1194,6 +6536,7 @@ fn parse_and_store_tles(
             let details = SatelliteDetails {
                 id: None,
                 name: name.clone(),
+                alias: None,
                 tle_line1,
                 tle_line2,
                 launch_date: None,
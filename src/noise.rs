@@ -0,0 +1,117 @@
+//! The station's noise environment: how high the noise floor is in each
+//! direction, for stations with a neighbour's solar inverter, a plasma TV or
+//! a busy road on one side. A satellite in a noisy direction is heard as if
+//! its signal were weaker by as much as the floor is above the quietest
+//! direction. Levels are entered by hand or read from an `rtl_power` or
+//! `hackrf_sweep` scan made with the antenna pointed that way.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::horizon::AzimuthProfile;
+
+/// Noise floor as a function of azimuth
+#[derive(Debug, Clone, Default)]
+pub struct NoiseProfile {
+    profile: AzimuthProfile, // noise floor in dB by azimuth
+}
+
+impl NoiseProfile {
+    /// Load a profile from a CSV file with `azimuth,noise_db` rows
+    pub fn load_csv<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("reading noise file '{}'", path.display()))?;
+        Ok(Self {
+            profile: AzimuthProfile::parse_csv(&contents, "noise_db")?,
+        })
+    }
+
+    /// The profile as CSV, in the format `load_csv` reads
+    pub fn to_csv(&self) -> String {
+        self.profile.to_csv("noise_db")
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        self.profile.points()
+    }
+
+    /// Set the noise floor toward `azimuth`, replacing an earlier level
+    /// within half a degree of it
+    pub fn record(&mut self, azimuth: f64, level_db: f64) {
+        self.profile.record(azimuth, level_db);
+    }
+
+    /// How far the noise floor toward `azimuth` is above the quietest
+    /// direction (dB); 0 without a profile
+    pub fn rise_db(&self, azimuth: f64) -> f64 {
+        let quietest = self
+            .points()
+            .iter()
+            .map(|p| p.1)
+            .fold(f64::INFINITY, f64::min);
+        self.profile
+            .value_at(azimuth)
+            .map_or(0.0, |level| (level - quietest).max(0.0))
+    }
+}
+
+/// Noise floor of a power scan from `rtl_power` or `hackrf_sweep`: CSV rows
+/// of date, time, low and high frequency, bin width and sample count, then
+/// the power of each bin in dB. The median bin is the floor, so carriers in
+/// the scan don't raise it.
+pub fn scan_noise_floor_db(contents: &str) -> Result<f64> {
+    let mut bins = Vec::new();
+    for (line_no, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        for field in line.split(',').skip(6) {
+            let power = field.trim().parse::<f64>().with_context(|| {
+                format!("line {}: invalid power '{}'", line_no + 1, field.trim())
+            })?;
+            // Bins with nothing in them come out as -inf or nan
+            if power.is_finite() {
+                bins.push(power);
+            }
+        }
+    }
+    if bins.is_empty() {
+        anyhow::bail!("no power readings in the scan");
+    }
+    bins.sort_by(f64::total_cmp);
+    Ok(bins[bins.len() / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noise_profile() {
+        // A solar inverter to the west, quiet elsewhere
+        let profile = NoiseProfile {
+            profile: AzimuthProfile::from_points(vec![
+                (0.0, -120.0),
+                (90.0, -118.0),
+                (270.0, -104.0),
+            ]),
+        };
+        assert!((profile.rise_db(270.0) - 16.0).abs() < 1e-9);
+        // Between 270° (-104) and 360° (-120)
+        assert!((profile.rise_db(315.0) - 8.0).abs() < 1e-9);
+        assert_eq!(profile.rise_db(0.0), 0.0);
+        assert_eq!(NoiseProfile::default().rise_db(270.0), 0.0);
+    }
+
+    #[test]
+    fn test_scan_noise_floor() {
+        // A 2 m sweep with one strong carrier
+        let scan = "2026-10-16, 12:00:00, 144000000, 146000000, 500000.00, 10, \
+                    -42.1, -41.8, -12.5, -42.0\n\
+                    2026-10-16, 12:00:01, 144000000, 146000000, 500000.00, 10, \
+                    -41.9, -nan, -42.3, -41.7\n";
+        assert_eq!(scan_noise_floor_db(scan).unwrap(), -41.9);
+        assert!(scan_noise_floor_db("").is_err());
+    }
+}
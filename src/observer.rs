@@ -1,10 +1,18 @@
 use nalgebra::Vector3;
 
+use crate::frames::EopTable;
+use crate::horizon::HorizonMask;
+use crate::noise::NoiseProfile;
+
 pub struct Observer {
     pub name: String,
     pub latitude: f64,  // degrees
     pub longitude: f64, // degrees
     pub altitude: f64,  // meters
+    pub horizon: HorizonMask,
+    pub noise: NoiseProfile, // noise floor by azimuth
+    pub earth_orientation: Option<EopTable>, // precise TEME -> ITRF with polar motion when set
+    pub light_time_correction: bool, // aim and tune at where the received signal left from
 }
 
 impl Observer {
@@ -12,8 +20,12 @@ impl Observer {
         Self {
             name,
             latitude: lat,
-            longitude: lon,
+            longitude: normalize_longitude(lon),
             altitude: alt,
+            horizon: HorizonMask::default(),
+            noise: NoiseProfile::default(),
+            earth_orientation: None,
+            light_time_correction: false,
         }
     }
 
@@ -35,9 +47,20 @@ impl Observer {
     }
 }
 
+/// Wraps a longitude into [-180, 180), so 180°E and 180°W are the same value
+pub fn normalize_longitude(longitude: f64) -> f64 {
+    let wrapped = (longitude + 180.0).rem_euclid(360.0);
+    if wrapped >= 360.0 {
+        -180.0
+    } else {
+        wrapped - 180.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_observer_creation() {
@@ -81,6 +104,16 @@ mod tests {
         assert!((radius_high - radius_low - 1000.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_normalize_longitude() {
+        assert_eq!(normalize_longitude(190.0), -170.0);
+        assert_eq!(normalize_longitude(180.0), -180.0);
+        assert_eq!(normalize_longitude(-180.0), -180.0);
+        assert_eq!(normalize_longitude(-540.0), -180.0);
+        assert_eq!(normalize_longitude(-1e-15 - 180.0), -180.0);
+        assert_eq!(Observer::new("Test".to_string(), 0.0, 359.0, 0.0).longitude, -1.0);
+    }
+
     #[test]
     fn test_latitude_range() {
         for lat in [-90.0, -45.0, 0.0, 45.0, 90.0].iter() {
@@ -90,4 +123,40 @@ mod tests {
             assert!(radius > 6300.0 && radius < 6400.0);
         }
     }
-}
\ No newline at end of file
+
+    proptest! {
+        #[test]
+        fn prop_ecef_radius_between_axes(
+            lat in -90.0..=90.0f64,
+            lon in -180.0..=180.0f64,
+            alt in -500.0..9000.0f64,
+        ) {
+            let radius = Observer::new("Test".to_string(), lat, lon, alt).to_ecef().norm();
+            prop_assert!(radius >= 6_356_752.314 + alt - 1e-6);
+            prop_assert!(radius <= 6_378_137.0 + alt + 1e-6);
+        }
+
+        #[test]
+        fn prop_longitude_wraps(
+            lat in -90.0..=90.0f64,
+            lon in -180.0..=180.0f64,
+            turns in -2i32..=2,
+        ) {
+            let ecef = Observer::new("Test".to_string(), lat, lon, 0.0).to_ecef();
+            let wrapped = lon + 360.0 * turns as f64;
+            let other = Observer::new("Test".to_string(), lat, wrapped, 0.0).to_ecef();
+            prop_assert!((ecef - other).norm() < 1e-6);
+        }
+
+        #[test]
+        fn prop_hemispheres_are_symmetric(
+            lat in 0.0..=90.0f64,
+            lon in -180.0..=180.0f64,
+        ) {
+            let north = Observer::new("Test".to_string(), lat, lon, 0.0).to_ecef();
+            let south = Observer::new("Test".to_string(), -lat, lon, 0.0).to_ecef();
+            prop_assert!((north.x - south.x).abs() < 1e-6 && (north.y - south.y).abs() < 1e-6);
+            prop_assert!((north.z + south.z).abs() < 1e-6);
+        }
+    }
+}
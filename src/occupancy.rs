@@ -0,0 +1,143 @@
+//! Which satellite a signal belongs to: every known downlink of the satellites
+//! above the horizon, sorted by the frequency it is heard on right now, for
+//! matching a trace on an SDR waterfall to a bird.
+
+use crate::database::Transponder;
+use crate::radio::calculate_doppler_shift;
+use crate::satellite::SatellitePosition;
+use crate::transponder;
+
+/// One downlink of a visible satellite
+#[derive(Debug, Clone, PartialEq)]
+pub struct Downlink {
+    pub satellite: String,
+    pub description: String, // transponder or beacon, or where the frequency is from
+    pub mode: Option<String>,
+    pub nominal_mhz: f64,
+    pub observed_mhz: f64, // with the Doppler shift at the satellite's current range rate
+    pub elevation: f64,
+}
+
+impl Downlink {
+    pub fn shift_khz(&self) -> f64 {
+        (self.observed_mhz - self.nominal_mhz) * 1000.0
+    }
+}
+
+/// Downlinks of the visible satellites in `positions`, ordered by observed
+/// frequency. `known` adds frequencies from elsewhere as (satellite, label,
+/// MHz); one within a kHz of a transponder's downlink is left out as the same.
+pub fn downlinks(
+    positions: &[SatellitePosition],
+    transponders: &[Transponder],
+    known: &[(String, String, f64)],
+) -> Vec<Downlink> {
+    let mut downlinks: Vec<Downlink> = Vec::new();
+    for position in positions.iter().filter(|pos| pos.is_visible) {
+        let mut add = |description: &str, mode: Option<&String>, nominal_mhz: f64| {
+            let duplicate = downlinks.iter().any(|downlink| {
+                downlink.satellite == position.name
+                    && (downlink.nominal_mhz - nominal_mhz).abs() < 0.001
+            });
+            if !duplicate {
+                downlinks.push(Downlink {
+                    satellite: position.name.clone(),
+                    description: description.to_string(),
+                    mode: mode.cloned(),
+                    nominal_mhz,
+                    observed_mhz: calculate_doppler_shift(position, nominal_mhz, 0.0)
+                        .downlink_observed_mhz,
+                    elevation: position.elevation,
+                });
+            }
+        };
+        for t in transponders
+            .iter()
+            .filter(|t| transponder::matches_satellite(&t.satellite_name, &position.name))
+        {
+            if let Some(downlink_mhz) = t.downlink_mhz {
+                add(&t.description, t.downlink_mode.as_ref(), downlink_mhz);
+            }
+        }
+        for (_, label, mhz) in known.iter().filter(|(name, _, _)| *name == position.name) {
+            add(label, None, *mhz);
+        }
+    }
+    downlinks.sort_by(|a, b| a.observed_mhz.total_cmp(&b.observed_mhz));
+    downlinks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn position(name: &str, elevation: f64, range_rate_km_s: f64) -> SatellitePosition {
+        SatellitePosition {
+            name: name.to_string(),
+            time: Utc::now(),
+            latitude: 0.0,
+            longitude: 0.0,
+            altitude_km: 500.0,
+            velocity_km_s: 7.6,
+            range_rate_km_s,
+            azimuth: 0.0,
+            elevation,
+            range_km: 1000.0,
+            is_visible: elevation > 0.0,
+            doppler: None,
+            comm_window: None,
+        }
+    }
+
+    #[test]
+    fn test_band_occupancy() {
+        let transponder = |satellite: &str, description: &str, downlink_mhz: f64| Transponder {
+            satellite_name: satellite.to_string(),
+            description: description.to_string(),
+            downlink_mhz: Some(downlink_mhz),
+            downlink_mode: Some("FM".to_string()),
+            ..Transponder::default()
+        };
+        let transponders = [
+            transponder("SO-50", "FM voice", 436.795),
+            transponder("AO-91", "FM voice", 145.960),
+            transponder("ISS", "APRS", 145.825),
+        ];
+        let positions = [
+            position("SAUDISAT 1C (SO-50)", 30.0, -5.0),
+            position("FOX-1B (AO-91)", 10.0, 3.0),
+            // Below the horizon, so not listed
+            position("ISS (ZARYA)", -20.0, 0.0),
+        ];
+        let known = [
+            (
+                "SAUDISAT 1C (SO-50)".to_string(),
+                "Configured".to_string(),
+                436.7951,
+            ),
+            (
+                "FOX-1B (AO-91)".to_string(),
+                "Database".to_string(),
+                435.250,
+            ),
+        ];
+
+        let downlinks = downlinks(&positions, &transponders, &known);
+        let listed: Vec<(&str, &str)> = downlinks
+            .iter()
+            .map(|d| (d.satellite.as_str(), d.description.as_str()))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                ("FOX-1B (AO-91)", "FM voice"),
+                ("FOX-1B (AO-91)", "Database"),
+                ("SAUDISAT 1C (SO-50)", "FM voice"),
+            ]
+        );
+        // Approaching at 5 km/s raises 436.795 MHz by about 7.3 kHz
+        assert!((downlinks[2].shift_khz() - 7.285).abs() < 0.01);
+        assert!(downlinks[0].shift_khz() < 0.0);
+    }
+}
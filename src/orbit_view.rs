@@ -0,0 +1,141 @@
+//! Geometry for the orbit view: the selected satellite's orbit, the observer
+//! and the Sun in the inertial frame, seen from any direction with an
+//! orthographic projection. The Earth's rotation carries the observer through
+//! the orbit plane twice a day, and passes bunch up around those two times.
+
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::Vector3;
+
+use crate::frames::TemeToEcef;
+use crate::observer::Observer;
+use crate::satellite::Satellite;
+
+pub const EARTH_RADIUS_KM: f64 = 6378.137;
+
+/// Direction the Earth is seen from, in degrees of longitude in the inertial
+/// frame and latitude above the equator
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct View {
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+impl View {
+    /// Looking straight down on `position`, such as the observer's
+    pub fn facing(position: &Vector3<f64>) -> Self {
+        Self {
+            longitude: position.y.atan2(position.x).to_degrees(),
+            latitude: (position.z / position.norm()).asin().to_degrees(),
+        }
+    }
+
+    /// Unit vectors toward the viewer, to the right, and up the screen
+    fn axes(&self) -> (Vector3<f64>, Vector3<f64>, Vector3<f64>) {
+        let (sin_lon, cos_lon) = self.longitude.to_radians().sin_cos();
+        let (sin_lat, cos_lat) = self.latitude.to_radians().sin_cos();
+        let toward = Vector3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat);
+        let right = Vector3::new(-sin_lon, cos_lon, 0.0);
+        (toward, right, toward.cross(&right))
+    }
+
+    /// Screen position in km, and whether the point can be seen rather than
+    /// being hidden behind the Earth
+    pub fn project(&self, point: &Vector3<f64>) -> (f64, f64, bool) {
+        let (toward, right, up) = self.axes();
+        let (x, y) = (point.dot(&right), point.dot(&up));
+        let hidden = point.dot(&toward) < 0.0 && x.hypot(y) < EARTH_RADIUS_KM;
+        (x, y, !hidden)
+    }
+}
+
+/// `count` points around one orbit of `satellite` from `from`, in km
+pub fn orbit_points(satellite: &Satellite, from: DateTime<Utc>, count: usize) -> Vec<Vector3<f64>> {
+    let period_ms = satellite.period_minutes() * 60000.0;
+    (0..=count)
+        .filter_map(|step| {
+            let offset = Duration::milliseconds((period_ms * step as f64 / count as f64) as i64);
+            satellite
+                .teme_state(from + offset)
+                .ok()
+                .map(|(position, _)| position)
+        })
+        .collect()
+}
+
+/// Where the observer is at `time`, in km in the inertial frame
+pub fn observer_position(observer: &Observer, time: DateTime<Utc>) -> Vector3<f64> {
+    TemeToEcef::simple(time).to_teme(&observer.to_ecef()) / 1000.0
+}
+
+/// Times in the day after `from` when the observer crosses the orbit plane
+pub fn plane_crossings(
+    satellite: &Satellite,
+    observer: &Observer,
+    from: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    // Height above the plane, sampled each minute; the plane itself drifts
+    // a few degrees a day, so it is taken afresh at each sample
+    let side = |time: DateTime<Utc>| {
+        let (position, velocity) = satellite.teme_state(time).ok()?;
+        let normal = position.cross(&velocity).normalize();
+        Some(observer_position(observer, time).dot(&normal))
+    };
+    let mut crossings = Vec::new();
+    let mut previous = side(from).map(|height| (from, height));
+    for minute in 1..=1440 {
+        let time = from + Duration::minutes(minute);
+        let current = side(time);
+        if let (Some((before, h0)), Some(h1)) = (previous, current) {
+            if (h0 < 0.0) != (h1 < 0.0) {
+                let fraction = h0 / (h0 - h1);
+                crossings.push(before + Duration::milliseconds((fraction * 60000.0) as i64));
+            }
+        }
+        previous = current.map(|height| (time, height));
+    }
+    crossings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::sample_satellite;
+
+    #[test]
+    fn test_projection_and_crossings() {
+        let view = View {
+            longitude: 0.0,
+            latitude: 0.0,
+        };
+        // From over the equator at 0°: +y to the right, +z up, -x hidden
+        assert_eq!(
+            view.project(&Vector3::new(7000.0, 0.0, 0.0)),
+            (0.0, 0.0, true)
+        );
+        let (x, y, visible) = view.project(&Vector3::new(0.0, 7000.0, 1000.0));
+        assert!((x - 7000.0).abs() < 1e-9 && (y - 1000.0).abs() < 1e-9 && visible);
+        assert!(!view.project(&Vector3::new(-7000.0, 10.0, 10.0)).2);
+        // Behind the Earth but off to the side of it
+        assert!(view.project(&Vector3::new(-7000.0, 0.0, 7000.0)).2);
+
+        let facing = View::facing(&Vector3::new(0.0, 1.0, 1.0));
+        assert!((facing.longitude - 90.0).abs() < 1e-9);
+        assert!((facing.latitude - 45.0).abs() < 1e-9);
+
+        let iss = sample_satellite("ISS");
+        let epoch = iss.epoch;
+
+        let orbit = orbit_points(&iss, epoch, 90);
+        assert_eq!(orbit.len(), 91);
+        // Back where it started after one period
+        assert!((orbit[0] - orbit[90]).norm() < 100.0);
+
+        // Below the orbit's inclination the observer meets the plane twice a day
+        let observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+        let crossings = plane_crossings(&iss, &observer, epoch);
+        assert_eq!(crossings.len(), 2, "{:?}", crossings);
+        // Above it, never
+        let polar = Observer::new("Polar".to_string(), 80.0, 0.0, 0.0);
+        assert!(plane_crossings(&iss, &polar, epoch).is_empty());
+    }
+}
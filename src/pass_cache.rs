@@ -0,0 +1,212 @@
+//! Predicted passes kept in the database between runs. Each satellite's
+//! passes are stored with the element set they came from and a key for the
+//! observer and prediction settings, so a restart reuses them and only
+//! predicts the stretch of time not covered yet. New elements, a move, or
+//! changed settings start over.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::config::{PassFilter, PredictionConfig};
+use crate::database::{Database, PassPrediction};
+use crate::observer::Observer;
+use crate::pass_prediction::SatellitePass;
+
+/// A cached prediction is extended from this long before its end, so a pass
+/// underway there is found again with its real AOS
+const OVERLAP_MINUTES: i64 = 60;
+
+pub struct PassCache<'a> {
+    database: &'a Database,
+    observer_key: String,
+}
+
+impl<'a> PassCache<'a> {
+    pub fn new(database: &'a Database, observer: &Observer, config: &PredictionConfig) -> Self {
+        let settings = format!(
+            "{:.6} {:.6} {:.1} {:?} {} {}",
+            observer.latitude,
+            observer.longitude,
+            observer.altitude,
+            observer.horizon,
+            config.min_elevation,
+            config.time_step
+        );
+        Self {
+            database,
+            observer_key: format!("{:016x}", fnv1a(&settings)),
+        }
+    }
+
+    /// Passes ending within `window`, up to `max_passes`, taken from the
+    /// cache where it covers the window and from `predict` for the rest
+    pub fn passes(
+        &self,
+        satellite_name: &str,
+        tle_epoch: DateTime<Utc>,
+        filter: &PassFilter,
+        window: (DateTime<Utc>, DateTime<Utc>),
+        max_passes: usize,
+        predict: impl Fn((DateTime<Utc>, DateTime<Utc>), usize) -> Result<Vec<SatellitePass>>,
+    ) -> Result<Vec<SatellitePass>> {
+        let (start, end) = window;
+        let key = format!("{} {:?}", self.observer_key, filter);
+        // A cache that can't be read or written only means predicting again
+        let stored = self
+            .database
+            .read_predicted_passes(satellite_name, &key, tle_epoch)
+            .ok()
+            .flatten();
+        let stored_until = stored.as_ref().map(|run| run.computed_until);
+        let cached = stored.filter(|run| run.computed_from <= start && run.computed_until > start);
+
+        let mut passes: Vec<SatellitePass> = match &cached {
+            Some(run) => run
+                .passes
+                .iter()
+                .filter(|pass| pass.los_time > start && pass.los_time <= end)
+                .take(max_passes)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        if let Some(run) = &cached {
+            if run.computed_until >= end || passes.len() >= max_passes {
+                return Ok(passes);
+            }
+        }
+
+        let from = match &cached {
+            Some(run) => run.computed_until - Duration::minutes(OVERLAP_MINUTES),
+            None => start,
+        };
+        // Passes already cached, or cut short at the start of the search, are skipped
+        let after = passes.last().map_or(from, |pass| pass.los_time.max(from));
+        let found = predict((from, end), max_passes)?;
+        passes.extend(
+            found
+                .into_iter()
+                .filter(|pass| cached.is_none() || pass.aos_time > after),
+        );
+        passes.truncate(max_passes);
+
+        let prediction = PassPrediction {
+            computed_from: start,
+            // The search stops at the last pass once it has found enough
+            computed_until: match passes.last() {
+                Some(last) if passes.len() >= max_passes => last.los_time,
+                _ => end,
+            },
+            passes,
+        };
+        // A window in the past doesn't replace a prediction reaching further ahead
+        if stored_until.is_none_or(|until| until <= prediction.computed_until) {
+            let _ =
+                self.database
+                    .save_predicted_passes(satellite_name, &key, tle_epoch, &prediction);
+        }
+        Ok(prediction.passes)
+    }
+}
+
+/// 64-bit FNV-1a, a hash that stays the same across Rust releases, unlike
+/// the standard library's, so keys stored by one build match the next
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::sample_pass;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_pass_cache() {
+        let database = Database::open_in_memory().unwrap();
+        let observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+        let config: PredictionConfig = toml::from_str(
+            "num_passes = 10\nmin_elevation = 0.0\nsearch_days = 1.0\ntime_step = 10.0",
+        )
+        .unwrap();
+        let cache = PassCache::new(&database, &observer, &config);
+        let filter = PassFilter::default();
+        let epoch = Utc::now() - Duration::days(1);
+        let start = Utc::now();
+
+        // A pass every three hours, and a record of each window predicted
+        let searches = RefCell::new(Vec::new());
+        let predict = |window: (DateTime<Utc>, DateTime<Utc>), max: usize| {
+            searches.borrow_mut().push(window);
+            let mut passes = Vec::new();
+            let mut aos = start + Duration::hours(1);
+            while aos + Duration::minutes(10) <= window.1 && passes.len() < max {
+                if aos >= window.0 {
+                    passes.push(sample_pass("ISS", aos, aos + Duration::minutes(10), 45.0).1);
+                }
+                aos += Duration::hours(3);
+            }
+            Ok(passes)
+        };
+
+        let day = (start, start + Duration::days(1));
+        let first = cache
+            .passes("ISS", epoch, &filter, day, 100, predict)
+            .unwrap();
+        assert_eq!(first.len(), 8);
+        // The same window again comes from the cache
+        let again = cache
+            .passes("ISS", epoch, &filter, day, 100, predict)
+            .unwrap();
+        assert_eq!(again.len(), 8);
+        assert_eq!(searches.borrow().len(), 1);
+
+        // Later on, only the new part is predicted
+        let later = (start + Duration::hours(5), start + Duration::hours(29));
+        let passes = cache
+            .passes("ISS", epoch, &filter, later, 100, predict)
+            .unwrap();
+        assert_eq!(searches.borrow().len(), 2);
+        assert_eq!(
+            searches.borrow()[1].0,
+            day.1 - Duration::minutes(OVERLAP_MINUTES)
+        );
+        let aos: Vec<_> = passes.iter().map(|p| p.aos_time).collect();
+        let expected: Vec<_> = (2..10)
+            .map(|i| start + Duration::hours(1 + 3 * i))
+            .collect();
+        assert_eq!(aos, expected);
+
+        // A window in the past is predicted but not kept
+        let past = (start - Duration::days(2), start - Duration::days(1));
+        cache
+            .passes("ISS", epoch, &filter, past, 100, predict)
+            .unwrap();
+        cache
+            .passes("ISS", epoch, &filter, later, 100, predict)
+            .unwrap();
+        assert_eq!(searches.borrow().len(), 3);
+
+        // New elements start over
+        cache
+            .passes(
+                "ISS",
+                epoch + Duration::hours(12),
+                &filter,
+                later,
+                100,
+                predict,
+            )
+            .unwrap();
+        assert_eq!(searches.borrow().len(), 4);
+        assert_eq!(searches.borrow()[3], later);
+    }
+}
@@ -1,6 +1,15 @@
-use chrono::{DateTime, Utc};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use nalgebra::Vector3;
 
+use crate::astro::TwilightState;
+use crate::config::{LightingFilter, PassFilter};
+
+/// Passes closer than this to a bright Moon are flagged
+pub const MOON_WARNING_SEPARATION_DEG: f64 = 10.0;
+/// Illuminated fraction above which the Moon is treated as near full
+pub const MOON_WARNING_ILLUMINATION: f64 = 0.85;
+
 #[derive(Debug, Clone)]
 pub struct SatellitePass {
     pub aos_time: DateTime<Utc>, // Acquisition of Signal
@@ -12,6 +21,10 @@ pub struct SatellitePass {
     pub los_azimuth: f64,
     pub duration_seconds: f64,
     pub max_range_km: f64,
+    pub sun_elevation: f64, // Sun elevation at the observer at culmination
+    pub sunlit: bool,       // Satellite illuminated at culmination
+    pub min_moon_separation: f64, // Closest approach to the Moon during the pass (degrees)
+    pub moon_illumination: f64,   // Illuminated fraction of the Moon at closest approach
 }
 
 #[derive(Debug)]
@@ -25,6 +38,98 @@ impl SatellitePass {
     pub fn duration_minutes(&self) -> f64 {
         self.duration_seconds / 60.0
     }
+
+    pub fn twilight(&self) -> TwilightState {
+        TwilightState::from_sun_elevation(self.sun_elevation)
+    }
+
+    /// A sunlit satellite against a dark enough sky can be spotted by eye
+    pub fn is_visual(&self) -> bool {
+        self.sunlit && self.twilight().is_dark_enough()
+    }
+
+    /// Whether the pass satisfies the given prediction filter
+    pub fn matches(&self, filter: &PassFilter) -> bool {
+        if filter
+            .min_duration_minutes
+            .is_some_and(|min| self.duration_minutes() < min)
+        {
+            return false;
+        }
+        if filter
+            .min_max_elevation
+            .is_some_and(|min| self.max_elevation < min)
+        {
+            return false;
+        }
+        match filter.lighting.unwrap_or_default() {
+            LightingFilter::Any => true,
+            LightingFilter::Daylight => self.twilight() == TwilightState::Day,
+            LightingFilter::Night => self.twilight() != TwilightState::Day,
+        }
+    }
+
+    /// Satellite track passes close to a near-full Moon
+    pub fn near_full_moon(&self) -> bool {
+        self.min_moon_separation <= MOON_WARNING_SEPARATION_DEG
+            && self.moon_illumination >= MOON_WARNING_ILLUMINATION
+    }
+}
+
+/// A named pass from `aos` to `los` that culminates halfway, rising in the
+/// north and setting in the south, for tests that need a pass but not its
+/// geometry
+#[cfg(test)]
+pub(crate) fn sample_pass(
+    name: &str,
+    aos: DateTime<Utc>,
+    los: DateTime<Utc>,
+    max_elevation: f64,
+) -> (String, SatellitePass) {
+    let pass = SatellitePass {
+        aos_time: aos,
+        los_time: los,
+        max_elevation,
+        max_elevation_time: aos + (los - aos) / 2,
+        aos_azimuth: 0.0,
+        max_azimuth: 90.0,
+        los_azimuth: 180.0,
+        duration_seconds: (los - aos).num_milliseconds() as f64 / 1000.0,
+        max_range_km: 1000.0,
+        sun_elevation: 10.0,
+        sunlit: true,
+        min_moon_separation: 90.0,
+        moon_illumination: 0.5,
+    };
+    (name.to_string(), pass)
+}
+
+/// ISS elements from September 2008 that the tests propagate
+#[cfg(test)]
+pub(crate) const SAMPLE_TLE_LINE1: &str =
+    "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927";
+#[cfg(test)]
+pub(crate) const SAMPLE_TLE_LINE2: &str =
+    "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537";
+
+/// A satellite on the sample ISS elements under `name`, for tests that need
+/// something to propagate
+#[cfg(test)]
+pub(crate) fn sample_satellite(name: &str) -> crate::satellite::Satellite {
+    let elements = sgp4::Elements::from_tle(
+        Some(name.to_string()),
+        SAMPLE_TLE_LINE1.as_bytes(),
+        SAMPLE_TLE_LINE2.as_bytes(),
+    )
+    .unwrap();
+    let epoch = elements.datetime.and_utc();
+    crate::satellite::Satellite::new(
+        name.to_string(),
+        elements,
+        epoch,
+        SAMPLE_TLE_LINE1.to_string(),
+        SAMPLE_TLE_LINE2.to_string(),
+    )
 }
 
 pub fn calculate_look_angles(
@@ -36,7 +141,16 @@ pub fn calculate_look_angles(
 ) -> LookAngles {
     // Convert satellite ECI to ECEF
     let sat_ecef = eci_to_ecef(sat_pos_eci, gmst);
+    look_angles_from_ecef(&sat_ecef, observer_ecef, observer_lat, observer_lon)
+}
 
+/// Look angles to an Earth-fixed position (meters)
+pub fn look_angles_from_ecef(
+    sat_ecef: &Vector3<f64>,
+    observer_ecef: &Vector3<f64>,
+    observer_lat: f64,
+    observer_lon: f64,
+) -> LookAngles {
     // Range vector from observer to satellite
     let range_vec = sat_ecef - observer_ecef;
     let range_km = range_vec.norm() / 1000.0;
@@ -45,25 +159,23 @@ pub fn calculate_look_angles(
     let lat_rad = observer_lat.to_radians();
     let lon_rad = observer_lon.to_radians();
 
-    let south = range_vec.x * lat_rad.cos() * lon_rad.cos()
-        + range_vec.y * lat_rad.cos() * lon_rad.sin()
-        - range_vec.z * lat_rad.sin();
+    let south = range_vec.x * lat_rad.sin() * lon_rad.cos()
+        + range_vec.y * lat_rad.sin() * lon_rad.sin()
+        - range_vec.z * lat_rad.cos();
 
     let east = -range_vec.x * lon_rad.sin() + range_vec.y * lon_rad.cos();
 
-    let zenith = range_vec.x * lat_rad.sin() * lon_rad.cos()
-        + range_vec.y * lat_rad.sin() * lon_rad.sin()
-        + range_vec.z * lat_rad.cos();
+    let zenith = range_vec.x * lat_rad.cos() * lon_rad.cos()
+        + range_vec.y * lat_rad.cos() * lon_rad.sin()
+        + range_vec.z * lat_rad.sin();
 
-    // Calculate azimuth and elevation
-    let azimuth = east.atan2(-south).to_degrees();
-    let azimuth = if azimuth < 0.0 {
-        azimuth + 360.0
-    } else {
-        azimuth
-    };
+    // Calculate azimuth and elevation. At the poles every direction is south
+    // (or north), so azimuth there is measured from the observer's meridian.
+    let azimuth = normalize_azimuth(east.atan2(-south).to_degrees());
 
-    let elevation = (zenith / range_km / 1000.0).asin().to_degrees();
+    // atan2 rather than asin(zenith / range), which loses precision and can
+    // round to NaN straight overhead
+    let elevation = zenith.atan2(south.hypot(east)).to_degrees();
 
     LookAngles {
         azimuth,
@@ -72,6 +184,89 @@ pub fn calculate_look_angles(
     }
 }
 
+/// One pass of a satellite as seen from several observers, for comparing sites
+#[derive(Debug, Clone)]
+pub struct ObserverComparison {
+    pub satellite: String,
+    pub passes: Vec<Option<SatellitePass>>, // one per observer, None where it isn't seen
+}
+
+impl ObserverComparison {
+    /// AOS at whichever observer sees the satellite first
+    pub fn first_aos(&self) -> DateTime<Utc> {
+        self.passes
+            .iter()
+            .flatten()
+            .map(|pass| pass.aos_time)
+            .min()
+            .unwrap_or_default()
+    }
+}
+
+/// Line up the pass sets of several observers: passes of a satellite that
+/// overlap in time at different observers are the same pass, seen from each.
+/// Rows are ordered by the earliest AOS.
+pub fn compare_observers(sets: &[Vec<(String, SatellitePass)>]) -> Vec<ObserverComparison> {
+    let mut rows: Vec<ObserverComparison> = Vec::new();
+    for (index, set) in sets.iter().enumerate() {
+        for (satellite, pass) in set {
+            let same = rows.iter_mut().find(|row| {
+                row.satellite == *satellite
+                    && row.passes[index].is_none()
+                    && row.passes.iter().flatten().any(|other| {
+                        other.aos_time < pass.los_time && pass.aos_time < other.los_time
+                    })
+            });
+            match same {
+                Some(row) => row.passes[index] = Some(pass.clone()),
+                None => {
+                    let mut passes = vec![None; sets.len()];
+                    passes[index] = Some(pass.clone());
+                    rows.push(ObserverComparison {
+                        satellite: satellite.clone(),
+                        passes,
+                    });
+                }
+            }
+        }
+    }
+    rows.sort_by_key(ObserverComparison::first_aos);
+    rows
+}
+
+/// Parses a window boundary: RFC 3339, or a local "YYYY-MM-DD HH:MM[:SS]" or "YYYY-MM-DD"
+pub fn parse_window_time(text: &str) -> Result<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .with_context(|| format!("'{}' is not a date and time like 2026-04-21 18:00", text))?;
+    // Take the earlier reading when a DST change makes the local time ambiguous
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .with_context(|| format!("'{}' does not exist in the local time zone", text))
+}
+
+/// Wraps an azimuth into [0, 360), including values that round up to 360
+pub fn normalize_azimuth(azimuth: f64) -> f64 {
+    let azimuth = azimuth.rem_euclid(360.0);
+    if azimuth >= 360.0 {
+        0.0
+    } else {
+        azimuth
+    }
+}
+
 fn eci_to_ecef(eci: &Vector3<f64>, gmst: f64) -> Vector3<f64> {
     let cos_gmst = gmst.cos();
     let sin_gmst = gmst.sin();
@@ -95,7 +290,7 @@ pub fn calculate_gmst(time: DateTime<Utc>) -> f64 {
 
     // GMST calculation (simplified)
     let gmst_hours = 18.697374558 + 24.06570982441908 * jd_epoch;
-    let gmst_hours = gmst_hours % 24.0;
+    let gmst_hours = gmst_hours.rem_euclid(24.0);
 
     (gmst_hours * 15.0).to_radians() // Convert hours to radians
 }
@@ -104,28 +299,86 @@ pub fn calculate_gmst(time: DateTime<Utc>) -> f64 {
 mod tests {
     use super::*;
     use chrono::TimeZone;
+    use proptest::prelude::*;
 
     #[test]
     fn test_satellite_pass_duration() {
+        let aos = Utc.with_ymd_and_hms(2026, 4, 24, 10, 0, 0).unwrap();
+        let (_, pass) = sample_pass("ISS", aos, aos + chrono::Duration::minutes(10), 45.0);
+        assert!((pass.duration_minutes() - 10.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_visual_pass_requires_darkness_and_sunlight() {
+        let aos = Utc.with_ymd_and_hms(2026, 4, 24, 10, 0, 0).unwrap();
+        let (_, mut pass) = sample_pass("ISS", aos, aos + chrono::Duration::minutes(10), 45.0);
+        pass.sun_elevation = 10.0;
+        pass.sunlit = true;
+        assert!(!pass.is_visual());
+
+        pass.sun_elevation = -8.0;
+        assert!(pass.is_visual());
+
+        pass.sunlit = false;
+        assert!(!pass.is_visual());
+    }
+
+    #[test]
+    fn test_near_full_moon_flag() {
+        let aos = Utc.with_ymd_and_hms(2026, 4, 24, 10, 0, 0).unwrap();
+        let (_, pass) = sample_pass("ISS", aos, aos + chrono::Duration::minutes(10), 45.0);
+        let mut pass = SatellitePass {
+            min_moon_separation: 4.0,
+            moon_illumination: 0.95,
+            ..pass
+        };
+        assert!(pass.near_full_moon());
+
+        pass.moon_illumination = 0.3;
+        assert!(!pass.near_full_moon());
+
+        pass.moon_illumination = 0.95;
+        pass.min_moon_separation = 25.0;
+        assert!(!pass.near_full_moon());
+    }
+
+    #[test]
+    fn test_pass_filter() {
+        let aos = Utc.with_ymd_and_hms(2026, 4, 24, 10, 0, 0).unwrap();
+        let (_, pass) = sample_pass("ISS", aos, aos + chrono::Duration::minutes(10), 45.0);
+        // At night
         let pass = SatellitePass {
-            aos_time: Utc.with_ymd_and_hms(2026, 4, 24, 10, 0, 0).unwrap(),
-            los_time: Utc.with_ymd_and_hms(2026, 4, 24, 10, 10, 0).unwrap(),
-            max_elevation: 45.0,
-            max_elevation_time: Utc.with_ymd_and_hms(2026, 4, 24, 10, 5, 0).unwrap(),
-            aos_azimuth: 90.0,
-            max_azimuth: 180.0,
-            los_azimuth: 270.0,
-            duration_seconds: 600.0,
-            max_range_km: 1000.0,
+            sun_elevation: -20.0,
+            ..pass
         };
-        assert!((pass.duration_minutes() - 10.0).abs() < 0.001);
+
+        assert!(pass.matches(&PassFilter::default()));
+
+        let global = PassFilter {
+            min_duration_minutes: Some(5.0),
+            min_max_elevation: Some(30.0),
+            lighting: Some(LightingFilter::Night),
+        };
+        assert!(pass.matches(&global));
+
+        let strict = PassFilter {
+            min_max_elevation: Some(60.0),
+            ..Default::default()
+        };
+        assert!(!pass.matches(&global.merged_with(Some(&strict))));
+
+        let daylight = PassFilter {
+            lighting: Some(LightingFilter::Daylight),
+            ..Default::default()
+        };
+        assert!(!pass.matches(&daylight));
     }
 
     #[test]
     fn test_look_angles_below_horizon() {
         // Satellite directly above observer at 90 degree elevation
-        let sat_pos = Vector3::new(0.0, 0.0, 400000.0); // 400km altitude in meters
-        let observer = Vector3::new(0.0, 0.0, 0.0);
+        let sat_pos = Vector3::new(6378137.0 + 400000.0, 0.0, 0.0); // 400km altitude in meters
+        let observer = Vector3::new(6378137.0, 0.0, 0.0);
         let gmst = 0.0;
 
         let angles = calculate_look_angles(&sat_pos, &observer, gmst, 0.0, 0.0);
@@ -134,10 +387,10 @@ mod tests {
 
     #[test]
     fn test_look_angles_horizontal() {
-        // Satellite at horizon
+        // Satellite at horizon, due east of an observer on the equator
         let range_km = 2000.0;
-        let sat_pos = Vector3::new(range_km * 1000.0, 0.0, 0.0);
-        let observer = Vector3::new(0.0, 0.0, 0.0);
+        let sat_pos = Vector3::new(6378137.0, range_km * 1000.0, 0.0);
+        let observer = Vector3::new(6378137.0, 0.0, 0.0);
         let gmst = 0.0;
 
         let angles = calculate_look_angles(&sat_pos, &observer, gmst, 0.0, 0.0);
@@ -154,6 +407,30 @@ mod tests {
         assert!(angles.azimuth >= 0.0 && angles.azimuth < 360.0);
     }
 
+    #[test]
+    fn test_look_angles_geostationary_reference() {
+        use crate::observer::Observer;
+
+        // Dish pointing to geostationary slots, against the spherical-Earth
+        // formulas dish alignment calculators use. The ellipsoid puts the
+        // station up to ~0.2° off those.
+        let cases = [
+            // (latitude, longitude, slot longitude, elevation, azimuth)
+            (51.5, -0.13, 25.9, 26.22, 148.03), // London to QO-100
+            (28.474, -80.577, -75.2, 56.25, 168.83), // Cape Canaveral to GOES-East
+            (40.0, -105.0, -137.0, 33.25, 224.19), // Colorado to GOES-West
+            (-33.87, 151.21, 166.0, 47.54, 25.35), // Sydney, looking north-east
+        ];
+        for (lat, lon, slot, elevation, azimuth) in cases {
+            let observer = Observer::new("Test".to_string(), lat, lon, 0.0);
+            let slot = f64::to_radians(slot);
+            let sat_pos = Vector3::new(slot.cos(), slot.sin(), 0.0) * 42_164_000.0;
+            let angles = calculate_look_angles(&sat_pos, &observer.to_ecef(), 0.0, lat, lon);
+            assert!((angles.elevation - elevation).abs() < 0.3, "{:?}", (lat, lon, angles));
+            assert!((angles.azimuth - azimuth).abs() < 0.3, "{:?}", (lat, lon, angles));
+        }
+    }
+
     #[test]
     fn test_gmst_known_value() {
         let j2000 = chrono::NaiveDate::from_ymd_opt(2000, 1, 1)
@@ -208,4 +485,176 @@ mod tests {
         // Range should be approximately 7000 - 6371 = 629 km
         assert!(angles.range > 600.0 && angles.range < 700.0);
     }
+
+    #[test]
+    fn test_parse_window_time() {
+        assert_eq!(
+            parse_window_time("2026-04-21T18:00:00Z").unwrap(),
+            Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_window_time("2026-04-21T20:00:00+02:00").unwrap(),
+            Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 0).unwrap()
+        );
+
+        // Bare dates and times are local
+        let local = |h, m, s| {
+            Local
+                .with_ymd_and_hms(2026, 4, 21, h, m, s)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        assert_eq!(parse_window_time(" 2026-04-21 18:30 ").unwrap(), local(18, 30, 0));
+        assert_eq!(parse_window_time("2026-04-21 18:30:15").unwrap(), local(18, 30, 15));
+        assert_eq!(parse_window_time("2026-04-21").unwrap(), local(0, 0, 0));
+
+        assert!(parse_window_time("last tuesday").is_err());
+        assert!(parse_window_time("2026-02-30").is_err());
+    }
+
+    #[test]
+    fn test_compare_observers() {
+        let at = |name: &str, minute: u32, max_elevation: f64| {
+            let aos = Utc.with_ymd_and_hms(2026, 4, 24, 10, minute, 0).unwrap();
+            sample_pass(name, aos, aos + chrono::Duration::minutes(10), max_elevation)
+        };
+        let school_a = vec![at("ISS", 2, 60.0), at("ISS", 40, 15.0)];
+        // The second site sees the first pass a minute earlier, lower, and
+        // misses the second, but catches SO-50
+        let school_b = vec![at("ISS", 1, 25.0), at("SO-50", 20, 30.0)];
+
+        let rows = compare_observers(&[school_a, school_b]);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].satellite, "ISS");
+        assert_eq!(rows[0].first_aos(), at("ISS", 1, 0.0).1.aos_time);
+        assert_eq!(rows[0].passes[0].as_ref().unwrap().max_elevation, 60.0);
+        assert_eq!(rows[0].passes[1].as_ref().unwrap().max_elevation, 25.0);
+        assert_eq!(rows[1].satellite, "SO-50");
+        assert!(rows[1].passes[0].is_none());
+        assert!(rows[2].passes[1].is_none());
+    }
+
+    #[test]
+    fn test_normalize_azimuth() {
+        assert_eq!(normalize_azimuth(-90.0), 270.0);
+        assert_eq!(normalize_azimuth(360.0), 0.0);
+        assert_eq!(normalize_azimuth(725.0), 5.0);
+        assert_eq!(normalize_azimuth(-1e-15), 0.0);
+    }
+
+    #[test]
+    fn test_polar_observer_azimuth_follows_meridian() {
+        // At the North Pole everything is south; azimuth is measured from the
+        // observer's own meridian, so a target toward 90°E is 180° for an
+        // observer on 90°E and 90° for one on 0°
+        let target = Vector3::new(0.0, 7_000_000.0, 0.0);
+        let on_90e = look_angles_from_ecef(&target, &observer_ecef(90.0, 90.0, 0.0), 90.0, 90.0);
+        assert!((on_90e.azimuth - 180.0).abs() < 1e-9);
+        let on_0 = look_angles_from_ecef(&target, &observer_ecef(90.0, 0.0, 0.0), 90.0, 0.0);
+        assert!((on_0.azimuth - 90.0).abs() < 1e-9);
+        assert!((on_0.elevation - on_90e.elevation).abs() < 1e-9);
+
+        // Just off the pole, north points at the pole and azimuths stay continuous
+        let near = look_angles_from_ecef(
+            &Vector3::new(0.0, 0.0, 7_000_000.0),
+            &observer_ecef(89.9999, 45.0, 0.0),
+            89.9999,
+            45.0,
+        );
+        assert!(near.azimuth < 1e-6 || near.azimuth > 360.0 - 1e-6);
+    }
+
+    #[test]
+    fn test_antimeridian_observer() {
+        // Observers either side of the date line see a satellite over it symmetrically
+        let sat = observer_ecef(0.0, 180.0, 800_000.0);
+        let west = look_angles_from_ecef(&sat, &observer_ecef(0.0, 179.0, 0.0), 0.0, 179.0);
+        let east = look_angles_from_ecef(&sat, &observer_ecef(0.0, -179.0, 0.0), 0.0, -179.0);
+        assert!((west.azimuth - 90.0).abs() < 1e-6);
+        assert!((east.azimuth - 270.0).abs() < 1e-6);
+        assert!((west.elevation - east.elevation).abs() < 1e-9);
+        assert!((west.range - east.range).abs() < 1e-9);
+    }
+
+    fn observer_ecef(lat: f64, lon: f64, alt: f64) -> Vector3<f64> {
+        crate::observer::Observer::new("Test".to_string(), lat, lon, alt).to_ecef()
+    }
+
+    fn local_up(lat: f64, lon: f64) -> Vector3<f64> {
+        let (lat, lon) = (lat.to_radians(), lon.to_radians());
+        Vector3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_look_angles_in_range(
+            lat in -90.0..=90.0f64,
+            lon in -180.0..=180.0f64,
+            alt in -500.0..9000.0f64,
+            sat in prop::array::uniform3(-5e7..5e7f64),
+        ) {
+            let observer = observer_ecef(lat, lon, alt);
+            let sat = Vector3::from(sat);
+            prop_assume!((sat - observer).norm() > 1.0);
+
+            let angles = look_angles_from_ecef(&sat, &observer, lat, lon);
+            prop_assert!((0.0..360.0).contains(&angles.azimuth), "azimuth {}", angles.azimuth);
+            prop_assert!(
+                (-90.0..=90.0).contains(&angles.elevation),
+                "elevation {}",
+                angles.elevation
+            );
+            prop_assert!((angles.range * 1000.0 - (sat - observer).norm()).abs() < 1e-6);
+        }
+
+        #[test]
+        fn prop_overhead_is_zenith(
+            lat in -90.0..=90.0f64,
+            lon in -180.0..=180.0f64,
+            height in 1.0..4e7f64,
+        ) {
+            // Rounding must not push the sine of the elevation past 1 and give NaN
+            let observer = observer_ecef(lat, lon, 0.0);
+            let sat = observer + local_up(lat, lon) * height;
+            let angles = look_angles_from_ecef(&sat, &observer, lat, lon);
+            prop_assert!((angles.elevation - 90.0).abs() < 1e-6, "elevation {}", angles.elevation);
+        }
+
+        #[test]
+        fn prop_antimeridian_is_continuous(
+            lat in -90.0..=90.0f64,
+            sat in prop::array::uniform3(-5e7..5e7f64),
+        ) {
+            let sat = Vector3::from(sat);
+            let east = look_angles_from_ecef(&sat, &observer_ecef(lat, 180.0, 0.0), lat, 180.0);
+            let west = look_angles_from_ecef(&sat, &observer_ecef(lat, -180.0, 0.0), lat, -180.0);
+            let azimuth_diff = (east.azimuth - west.azimuth).abs();
+            prop_assert!(azimuth_diff.min(360.0 - azimuth_diff) < 1e-6);
+            prop_assert!((east.elevation - west.elevation).abs() < 1e-6);
+            prop_assert!((east.range - west.range).abs() < 1e-6);
+        }
+
+        #[test]
+        fn prop_eci_to_ecef_is_a_rotation(
+            eci in prop::array::uniform3(-5e7..5e7f64),
+            a in -10.0..10.0f64,
+            b in -10.0..10.0f64,
+        ) {
+            let eci = Vector3::from(eci);
+            let ecef = eci_to_ecef(&eci, a);
+            prop_assert!((ecef.norm() - eci.norm()).abs() < 1e-6);
+            prop_assert_eq!(ecef.z, eci.z);
+
+            // Rotations about the pole compose, and undo each other
+            prop_assert!((eci_to_ecef(&ecef, b) - eci_to_ecef(&eci, a + b)).norm() < 1e-6);
+            prop_assert!((eci_to_ecef(&ecef, -a) - eci).norm() < 1e-6);
+        }
+
+        #[test]
+        fn prop_gmst_in_range(seconds in -1_500_000_000i64..3_000_000_000i64) {
+            let time = Utc.timestamp_opt(seconds, 0).unwrap();
+            let gmst = calculate_gmst(time);
+            prop_assert!((0.0..std::f64::consts::TAU).contains(&gmst), "gmst {}", gmst);
+        }
+    }
 }
@@ -0,0 +1,130 @@
+//! External commands run through a pass, such as telemetry decoders, their
+//! sources and Direwolf. A command is split into a program and arguments
+//! with shell quoting rules but run without a shell, in a process group of
+//! its own, so stopping it also stops anything it started, such as the rest
+//! of a pipeline in a wrapper script.
+
+use std::process::{Child, ChildStdout, Command};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+/// How long an interrupted command gets to finish writing before it is killed
+const STOP_GRACE: Duration = Duration::from_secs(2);
+
+/// Build `command`, quoted as in a shell, with each `(placeholder, value)`
+/// replaced in its arguments. A value with spaces stays one argument. There
+/// are no pipes, redirections or variables.
+pub fn command(command: &str, substitutions: &[(&str, &str)]) -> Result<Command> {
+    let words =
+        shlex::split(command).with_context(|| format!("unmatched quote in '{}'", command))?;
+    let mut words = words.into_iter().map(|word| {
+        substitutions
+            .iter()
+            .fold(word, |word, (placeholder, value)| word.replace(placeholder, value))
+    });
+    let program = words.next().context("the command is empty")?;
+    let mut command = Command::new(program);
+    command.args(words);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    Ok(command)
+}
+
+/// A command started for a pass. Dropping it stops the command.
+#[derive(Debug)]
+pub struct PassProcess {
+    child: Option<Child>, // None once stopped
+}
+
+impl PassProcess {
+    pub fn spawn(command: &mut Command) -> Result<Self> {
+        let child = command
+            .spawn()
+            .with_context(|| format!("starting '{}'", command.get_program().to_string_lossy()))?;
+        Ok(Self { child: Some(child) })
+    }
+
+    pub fn take_stdout(&mut self) -> Option<ChildStdout> {
+        self.child.as_mut()?.stdout.take()
+    }
+
+    /// Interrupt the command's process group as Ctrl-C would, so it can
+    /// finish what it is writing. What is left of it after a grace period is
+    /// killed and reaped on a thread of its own, so the caller carries on.
+    pub fn stop(&mut self) -> Option<JoinHandle<()>> {
+        let mut child = self.child.take()?;
+        #[cfg(unix)]
+        signal_group(&child, libc::SIGINT);
+        Some(std::thread::spawn(move || {
+            let deadline = Instant::now() + STOP_GRACE;
+            while matches!(child.try_wait(), Ok(None)) && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            #[cfg(unix)]
+            signal_group(&child, libc::SIGKILL);
+            let _ = child.kill();
+            let _ = child.wait();
+        }))
+    }
+}
+
+impl Drop for PassProcess {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Send `signal` to every process in the child's group, which the child leads
+#[cfg(unix)]
+fn signal_group(child: &Child, signal: libc::c_int) {
+    // SAFETY: kill(2) takes no pointers; a group that is already gone only
+    // makes it return ESRCH
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), signal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_quoting() {
+        let arecord = command(
+            "arecord -D 'plughw:CARD=Device,DEV=0' -d {seconds} {file}",
+            &[("{file}", "/home/me/my passes/a.wav"), ("{seconds}", "600")],
+        )
+        .unwrap();
+        assert_eq!(arecord.get_program(), "arecord");
+        let args: Vec<_> = arecord.get_args().collect();
+        assert_eq!(
+            args,
+            ["-D", "plughw:CARD=Device,DEV=0", "-d", "600", "/home/me/my passes/a.wav"]
+        );
+
+        assert!(command("arecord -D 'plughw", &[]).is_err());
+        assert!(command("  ", &[]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stop_does_not_wait() {
+        let mut process =
+            PassProcess::spawn(&mut command("sh -c 'trap \"\" INT; sleep 60'", &[]).unwrap())
+                .unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        let started = Instant::now();
+        let stopping = process.stop().unwrap();
+        assert!(started.elapsed() < STOP_GRACE);
+        assert!(process.stop().is_none());
+
+        // Ignoring the interrupt only lasts the grace period
+        stopping.join().unwrap();
+        assert!(started.elapsed() >= STOP_GRACE);
+    }
+}
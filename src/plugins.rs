@@ -0,0 +1,237 @@
+//! Compiled-in plugins that add computed fields to the selected satellite's
+//! position and notes to its passes, which the UI shows without knowing what
+//! they are. Enable them by name under `[plugins]`; settings for a plugin go
+//! in a table of its own name, e.g. `[plugins.overhead]`.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Duration;
+
+use crate::config::PluginsConfig;
+use crate::observer::Observer;
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::{Satellite, SatellitePosition};
+
+const EARTH_RADIUS_KM: f64 = 6378.137;
+
+/// A labelled value computed by a plugin
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub label: String,
+    pub value: String,
+}
+
+/// A custom calculation. Both hooks default to adding nothing, so a plugin
+/// implements only the ones it needs.
+pub trait Plugin {
+    /// Extra fields for a satellite's current position
+    fn position_fields(
+        &self,
+        _satellite: &Satellite,
+        _position: &SatellitePosition,
+        _observer: &Observer,
+    ) -> Vec<Field> {
+        Vec::new()
+    }
+
+    /// Notes on a pass, one short sentence each
+    fn pass_annotations(
+        &self,
+        _satellite: &Satellite,
+        _pass: &SatellitePass,
+        _observer: &Observer,
+    ) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+type Constructor = fn(&toml::Table) -> Result<Box<dyn Plugin>>;
+
+/// Every plugin that can be enabled, by name
+const BUILTIN: &[(&str, Constructor)] = &[
+    ("footprint", |_| Ok(Box::new(Footprint))),
+    ("overhead", |settings| {
+        Ok(Box::new(Overhead::new(settings)?))
+    }),
+];
+
+/// The enabled plugins, in the order they were listed
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Box<dyn Plugin>>,
+}
+
+impl PluginRegistry {
+    pub fn from_config(config: &PluginsConfig) -> Result<Self> {
+        let empty = toml::Table::new();
+        let mut plugins = Vec::new();
+        for name in &config.enabled {
+            let Some((_, constructor)) = BUILTIN.iter().find(|(builtin, _)| builtin == name) else {
+                let known: Vec<&str> = BUILTIN.iter().map(|(builtin, _)| *builtin).collect();
+                return Err(anyhow!(
+                    "[plugins] unknown plugin '{}'; available: {}",
+                    name,
+                    known.join(", ")
+                ));
+            };
+            let settings = config.settings.get(name).unwrap_or(&empty);
+            plugins.push(constructor(settings).with_context(|| format!("[plugins.{}]", name))?);
+        }
+        Ok(Self { plugins })
+    }
+
+    pub fn position_fields(
+        &self,
+        satellite: &Satellite,
+        position: &SatellitePosition,
+        observer: &Observer,
+    ) -> Vec<Field> {
+        self.plugins
+            .iter()
+            .flat_map(|plugin| plugin.position_fields(satellite, position, observer))
+            .collect()
+    }
+
+    pub fn pass_annotations(
+        &self,
+        satellite: &Satellite,
+        pass: &SatellitePass,
+        observer: &Observer,
+    ) -> Vec<String> {
+        self.plugins
+            .iter()
+            .flat_map(|plugin| plugin.pass_annotations(satellite, pass, observer))
+            .collect()
+    }
+}
+
+/// Radius of the area on the ground that can see the satellite, which is how
+/// far apart two stations can be and still work each other through it
+struct Footprint;
+
+impl Plugin for Footprint {
+    fn position_fields(
+        &self,
+        _satellite: &Satellite,
+        position: &SatellitePosition,
+        _observer: &Observer,
+    ) -> Vec<Field> {
+        let angle = (EARTH_RADIUS_KM / (EARTH_RADIUS_KM + position.altitude_km)).acos();
+        vec![Field {
+            label: "Footprint".to_string(),
+            value: format!("{:.0} km radius", EARTH_RADIUS_KM * angle),
+        }]
+    }
+}
+
+/// Warns of passes that go nearly overhead, where an az/el rotator has to
+/// swing its azimuth quickly around culmination
+struct Overhead {
+    min_elevation: f64,
+}
+
+impl Overhead {
+    fn new(settings: &toml::Table) -> Result<Self> {
+        let min_elevation = match settings.get("min_elevation") {
+            None => 80.0,
+            Some(value) => value
+                .as_float()
+                .or_else(|| value.as_integer().map(|v| v as f64))
+                .ok_or_else(|| anyhow!("min_elevation must be a number"))?,
+        };
+        Ok(Self { min_elevation })
+    }
+}
+
+impl Plugin for Overhead {
+    fn pass_annotations(
+        &self,
+        satellite: &Satellite,
+        pass: &SatellitePass,
+        observer: &Observer,
+    ) -> Vec<String> {
+        if pass.max_elevation < self.min_elevation {
+            return Vec::new();
+        }
+        let at = |offset: i64| {
+            satellite
+                .calculate_position(
+                    pass.max_elevation_time + Duration::seconds(offset),
+                    observer,
+                )
+                .ok()
+        };
+        let rate = match (at(-5), at(5)) {
+            (Some(before), Some(after)) => {
+                let turn = (after.azimuth - before.azimuth + 540.0).rem_euclid(360.0) - 180.0;
+                turn.abs() / 10.0
+            }
+            _ => return Vec::new(),
+        };
+        vec![format!(
+            "Near overhead: azimuth turns {:.1}°/s at culmination",
+            rate
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::{sample_pass, sample_satellite};
+
+    #[test]
+    fn test_registry() {
+        let iss = sample_satellite("ISS");
+        let epoch = iss.epoch;
+        let observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+
+        let config: PluginsConfig = toml::from_str(
+            "enabled = [\"footprint\", \"overhead\"]\n[overhead]\nmin_elevation = 0\n",
+        )
+        .unwrap();
+        let registry = PluginRegistry::from_config(&config).unwrap();
+
+        let position = iss.calculate_position(epoch, &observer).unwrap();
+        let fields = registry.position_fields(&iss, &position, &observer);
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].label, "Footprint");
+        // About 2,200 km for the ISS at 350 km in 2008
+        let radius: f64 = fields[0].value.split(' ').next().unwrap().parse().unwrap();
+        assert!((2000.0..2400.0).contains(&radius), "{}", radius);
+
+        // Culmination of the highest pass in the day after epoch
+        let culmination = (0..2880)
+            .map(|step| epoch + Duration::seconds(step * 30))
+            .max_by(|a, b| {
+                let elevation = |time| iss.calculate_position(time, &observer).unwrap().elevation;
+                elevation(*a).total_cmp(&elevation(*b))
+            })
+            .unwrap();
+        let max_elevation = iss
+            .calculate_position(culmination, &observer)
+            .unwrap()
+            .elevation;
+        let (_, pass) = sample_pass(
+            "ISS",
+            culmination - Duration::minutes(5),
+            culmination + Duration::minutes(5),
+            max_elevation,
+        );
+        let notes = registry.pass_annotations(&iss, &pass, &observer);
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].starts_with("Near overhead"));
+
+        assert!(PluginRegistry::default()
+            .pass_annotations(&iss, &pass, &observer)
+            .is_empty());
+
+        let unknown: PluginsConfig = toml::from_str("enabled = [\"nope\"]").unwrap();
+        let error = PluginRegistry::from_config(&unknown).err().unwrap();
+        assert!(error.to_string().contains("footprint, overhead"));
+
+        let bad: PluginsConfig =
+            toml::from_str("enabled = [\"overhead\"]\n[overhead]\nmin_elevation = \"high\"\n")
+                .unwrap();
+        assert!(PluginRegistry::from_config(&bad).is_err());
+    }
+}
@@ -1,10 +1,15 @@
+use chrono::{DateTime, Utc};
+
+use crate::config::SatelliteRadioConfig;
+use crate::database::FrequencyOffset;
+use crate::link_budget::{LinkMargin, PathLoss, StationLink};
+use crate::pass_prediction::SatellitePass;
 use crate::satellite::SatellitePosition;
 
 const SPEED_OF_LIGHT: f64 = 299792458.0; // m/s
 
 #[derive(Debug, Clone)]
 pub struct DopplerShift {
-    #[allow(dead_code)]
     pub downlink_frequency_mhz: f64,
     pub downlink_shift_hz: f64,
     pub downlink_observed_mhz: f64,
@@ -20,9 +25,13 @@ pub struct CommunicationWindow {
     pub reason: String,
     pub signal_strength_estimate: SignalStrength,
     pub recommended_mode: Option<String>,
+    pub path_loss: Option<PathLoss>, // on the downlink, while above the horizon
+    pub margin: Option<LinkMargin>,  // of the downlink, while above the horizon
+    pub station: StationLink, // gain and polarization of the antenna for it
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Strongest first, so the weaker of two estimates is the greater
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SignalStrength {
     Excellent,
     Good,
@@ -32,6 +41,22 @@ pub enum SignalStrength {
 }
 
 impl SignalStrength {
+    /// The tier a downlink with `margin_db` to spare reaches
+    fn from_margin(margin_db: f64) -> Self {
+        let [excellent_db, good_db, fair_db, poor_db] = MARGIN_TIERS_DB;
+        if margin_db >= excellent_db {
+            SignalStrength::Excellent
+        } else if margin_db >= good_db {
+            SignalStrength::Good
+        } else if margin_db >= fair_db {
+            SignalStrength::Fair
+        } else if margin_db >= poor_db {
+            SignalStrength::Poor
+        } else {
+            SignalStrength::NoSignal
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             SignalStrength::Excellent => "Excellent",
@@ -43,6 +68,181 @@ impl SignalStrength {
     }
 }
 
+/// Kind of orbit, which decides what counts as near or far and how low a
+/// satellite can still be worked
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OrbitClass {
+    #[default]
+    Leo,
+    Heo { apogee_km: f64 }, // elliptical or otherwise above LEO, e.g. GTO or Molniya
+    Geostationary,
+}
+
+impl OrbitClass {
+    /// Classify from the mean motion (revolutions per day), eccentricity and
+    /// apogee altitude (km)
+    pub fn new(mean_motion: f64, eccentricity: f64, apogee_km: f64) -> Self {
+        if (mean_motion - 1.0027).abs() < 0.05 && eccentricity < 0.05 {
+            OrbitClass::Geostationary
+        } else if apogee_km > 2000.0 {
+            OrbitClass::Heo { apogee_km }
+        } else {
+            OrbitClass::Leo
+        }
+    }
+
+    /// Ranges (km) within which a visible satellite counts as Excellent, Good and Fair
+    fn range_limits_km(&self) -> [f64; 3] {
+        match self {
+            OrbitClass::Leo => [2000.0, 2500.0, 3000.0],
+            // Built to be worked out to apogee, so judged against the distance there
+            OrbitClass::Heo { apogee_km } => [0.6, 0.9, 1.2].map(|f| f * apogee_km),
+            // The range only varies from 35,786 km overhead to ~41,700 km at the horizon
+            OrbitClass::Geostationary => [f64::INFINITY; 3],
+        }
+    }
+
+    /// Downlink power typically radiated toward the ground (dBW): a watt into
+    /// a simple antenna from LEO, more from further out, and a QO-100 class
+    /// transponder from geostationary orbit
+    fn typical_eirp_dbw(&self) -> f64 {
+        match self {
+            OrbitClass::Leo => 0.0,
+            OrbitClass::Heo { .. } => 15.0,
+            OrbitClass::Geostationary => 30.0,
+        }
+    }
+
+    /// A LEO pass is too short and fast to work near the horizon, while a slow
+    /// or fixed satellite can be worked through a carefully aimed dish down to 5°
+    fn min_viable_elevation(&self) -> f64 {
+        match self {
+            OrbitClass::Leo => 10.0,
+            _ => 5.0,
+        }
+    }
+}
+
+impl SatelliteRadioConfig {
+    pub fn is_fm(&self) -> bool {
+        self.mode
+            .as_deref()
+            .is_some_and(|mode| mode.eq_ignore_ascii_case("FM"))
+    }
+
+    /// Access tone for the uplink, e.g. "67.0 Hz CTCSS" or "DCS 023"
+    pub fn tone_description(&self) -> Option<String> {
+        match (self.ctcss_hz, self.dcs_code) {
+            (Some(hz), _) => Some(format!("{:.1} Hz CTCSS", hz)),
+            (None, Some(code)) => Some(format!("DCS {:03}", code)),
+            (None, None) => None,
+        }
+    }
+
+    /// Arming instruction for transponders that need a separate tone to switch on
+    pub fn arming_description(&self, uplink_frequency_mhz: f64) -> Option<String> {
+        self.arm_ctcss_hz.map(|hz| {
+            format!(
+                "{:.1} Hz on {:.3} MHz to arm",
+                hz,
+                self.arm_frequency_mhz.unwrap_or(uplink_frequency_mhz)
+            )
+        })
+    }
+}
+
+/// Where the operator is within a pass in progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassPhase {
+    pub elapsed_seconds: i64,
+    pub remaining_seconds: i64,
+    pub approaching: bool,
+    pub before_tca: bool,
+}
+
+impl PassPhase {
+    pub fn new(pass: &SatellitePass, time: DateTime<Utc>, range_rate_km_s: f64) -> Self {
+        Self {
+            elapsed_seconds: (time - pass.aos_time).num_seconds().max(0),
+            remaining_seconds: (pass.los_time - time).num_seconds().max(0),
+            approaching: range_rate_km_s < 0.0,
+            before_tca: time < pass.max_elevation_time,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "AOS+{}  {}, {} TCA",
+            format_mm_ss(self.elapsed_seconds as f64),
+            if self.approaching {
+                "approaching"
+            } else {
+                "receding"
+            },
+            if self.before_tca { "before" } else { "after" }
+        )
+    }
+}
+
+/// Manual tuning suggestion for radios without CAT control
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningAid {
+    pub channel_offset_hz: f64, // Doppler offset rounded to the nearest memory channel
+    pub seconds_to_next_step: Option<f64>,
+}
+
+impl TuningAid {
+    /// `shift_rate_hz_s` is how fast the downlink shift is changing
+    pub fn new(shift_hz: f64, shift_rate_hz_s: f64, step_hz: f64) -> Self {
+        let channel_offset_hz = (shift_hz / step_hz).round() * step_hz;
+        let seconds_to_next_step = (shift_rate_hz_s.abs() > f64::EPSILON).then(|| {
+            let boundary = channel_offset_hz + step_hz.copysign(shift_rate_hz_s) / 2.0;
+            ((boundary - shift_hz) / shift_rate_hz_s).max(0.0)
+        });
+        Self {
+            channel_offset_hz,
+            seconds_to_next_step,
+        }
+    }
+}
+
+/// Converts a frequency to MHz from a bare number or one with a unit, e.g.
+/// "145.800", "145800k", "145.8 MHz" or "145800000". Bare numbers are taken by
+/// size: below 100000 as MHz, below 1000000 as kHz, and otherwise as Hz.
+pub fn parse_frequency_mhz(text: &str) -> Result<f64, String> {
+    let text = text.trim().to_ascii_lowercase();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("'{}' is not a frequency", text))?;
+    let mhz = match unit.trim() {
+        "" if value < 1e5 => value,
+        "" if value < 1e6 => value / 1e3,
+        "" | "hz" => value / 1e6,
+        "k" | "khz" => value / 1e3,
+        "m" | "mhz" => value,
+        "g" | "ghz" => value * 1e3,
+        other => return Err(format!("unknown frequency unit '{}'", other)),
+    };
+    if mhz <= 0.0 {
+        return Err(format!("'{}' is not a frequency", text));
+    }
+    Ok(mhz)
+}
+
+/// A frequency as whole Hz, for SDR software that tunes in Hz
+pub fn format_hz(mhz: f64) -> String {
+    format!("{:.0} Hz", mhz * 1e6)
+}
+
+pub fn format_mm_ss(seconds: f64) -> String {
+    let seconds = seconds.max(0.0).round() as i64;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
 pub fn calculate_doppler_shift(
     position: &SatellitePosition,
     downlink_freq_mhz: f64,
@@ -67,44 +267,87 @@ pub fn calculate_doppler_shift(
     }
 }
 
-pub fn evaluate_communication_window(position: &SatellitePosition) -> CommunicationWindow {
+/// Link margin a downlink needs for Excellent, Good, Fair and Poor (dB)
+const MARGIN_TIERS_DB: [f64; 4] = [20.0, 12.0, 6.0, 0.0];
+
+/// Key under which a transponder's manual offset is remembered: its nominal downlink
+pub fn transponder_label(downlink_freq_mhz: f64) -> String {
+    format!("{:.6}", downlink_freq_mhz)
+}
+
+/// Remembered manual correction for a satellite's downlink, in Hz
+pub fn downlink_offset_hz(
+    offsets: &[FrequencyOffset],
+    satellite_name: &str,
+    downlink_freq_mhz: f64,
+) -> f64 {
+    let transponder = transponder_label(downlink_freq_mhz);
+    offsets
+        .iter()
+        .find(|o| o.satellite_name == satellite_name && o.transponder == transponder)
+        .map_or(0.0, |o| o.offset_hz)
+}
+
+/// How workable a satellite is from where it is now, for its kind of orbit,
+/// the downlink in use and the antenna receiving it
+pub fn evaluate_communication_window(
+    position: &SatellitePosition,
+    orbit: OrbitClass,
+    downlink_mhz: f64,
+    station: StationLink,
+) -> CommunicationWindow {
     if !position.is_visible {
         return CommunicationWindow {
             is_viable: false,
             reason: "Satellite below horizon".to_string(),
             signal_strength_estimate: SignalStrength::NoSignal,
             recommended_mode: None,
+            path_loss: None,
+            margin: None,
+            station,
         };
     }
 
     let elevation = position.elevation;
     let range_km = position.range_km;
 
+    let [excellent_km, good_km, fair_km] = orbit.range_limits_km();
+    let min_elevation = orbit.min_viable_elevation();
+    // From S band up, the long, low path through the atmosphere costs more
+    let fair_elevation = if downlink_mhz >= 2000.0 { 20.0 } else { 15.0 };
+
     // Evaluate signal strength based on elevation and range
-    let signal_strength = if elevation >= 45.0 && range_km < 2000.0 {
+    let by_geometry = if elevation >= 45.0 && range_km < excellent_km {
         SignalStrength::Excellent
-    } else if elevation >= 30.0 && range_km < 2500.0 {
+    } else if elevation >= 30.0 && range_km < good_km {
         SignalStrength::Good
-    } else if elevation >= 15.0 && range_km < 3000.0 {
+    } else if elevation >= fair_elevation && range_km < fair_km {
         SignalStrength::Fair
     } else if elevation >= 5.0 {
         SignalStrength::Poor
     } else {
         SignalStrength::NoSignal
     };
-
+    // and on the link margin: what the satellite typically radiates, less the
+    // path loss with what the atmosphere absorbs low in the sky on the
+    // microwave bands, plus the antenna's actual gain toward the satellite,
+    // less what a polarization mismatch loses, over the noise floor with any
+    // local noise in the satellite's direction
+    let path_loss = PathLoss::new(range_km, elevation, downlink_mhz);
+    let margin = LinkMargin::new(orbit.typical_eirp_dbw(), &path_loss, downlink_mhz, &station);
+    let signal_strength = by_geometry.max(SignalStrength::from_margin(margin.margin_db));
     // Determine if communication is viable
-    let is_viable = elevation >= 10.0 && signal_strength != SignalStrength::NoSignal;
-
-    // Recommend mode based on conditions
-    let recommended_mode = if elevation >= 30.0 {
-        Some("FM/SSB".to_string())
-    } else if elevation >= 15.0 {
-        Some("SSB".to_string())
-    } else if elevation >= 10.0 {
-        Some("SSB (difficult)".to_string())
-    } else {
-        None
+    let is_viable = elevation >= min_elevation && signal_strength != SignalStrength::NoSignal;
+
+    // Recommend mode based on conditions; transponders beyond LEO carry no FM
+    let recommended_mode = match orbit {
+        OrbitClass::Leo if elevation >= 30.0 => Some("FM/SSB".to_string()),
+        OrbitClass::Leo if elevation >= 15.0 => Some("SSB".to_string()),
+        OrbitClass::Leo if elevation >= 10.0 => Some("SSB (difficult)".to_string()),
+        OrbitClass::Leo => None,
+        _ if elevation >= 15.0 => Some("SSB/CW".to_string()),
+        _ if elevation >= min_elevation => Some("SSB/CW (difficult)".to_string()),
+        _ => None,
     };
 
     let reason = if is_viable {
@@ -112,6 +355,39 @@ pub fn evaluate_communication_window(position: &SatellitePosition) -> Communicat
             "Good pass - El: {:.1}°, Range: {:.0}km",
             elevation, range_km
         )
+    } else if margin.margin_db < 0.0 {
+        // Name the largest loss, if any stands out
+        let absorption_db = path_loss.atmospheric_db;
+        let pointing_loss_db = station.pointing_loss_db();
+        let polarization_loss_db = station.polarization_loss_db();
+        let noise_rise_db = station.noise_rise_db;
+        let largest_db = absorption_db
+            .max(pointing_loss_db)
+            .max(polarization_loss_db)
+            .max(noise_rise_db);
+        if largest_db < 1.0 {
+            format!(
+                "Downlink is {:.1} dB short at {:.0}km",
+                -margin.margin_db, range_km
+            )
+        } else if noise_rise_db > absorption_db.max(pointing_loss_db).max(polarization_loss_db) {
+            format!(
+                "Noise floor is {:.1} dB up toward {:.0}°",
+                noise_rise_db, position.azimuth
+            )
+        } else if polarization_loss_db > absorption_db.max(pointing_loss_db) {
+            format!("Polarization mismatch loses {:.1} dB", polarization_loss_db)
+        } else if pointing_loss_db > absorption_db {
+            format!(
+                "Antenna is {:.1} dB below its peak toward the satellite",
+                pointing_loss_db
+            )
+        } else {
+            format!(
+                "Atmosphere absorbs {:.1} dB at {:.1}° elevation",
+                absorption_db, elevation
+            )
+        }
     } else {
         format!("Elevation too low ({:.1}°) for reliable contact", elevation)
     };
@@ -121,5 +397,280 @@ pub fn evaluate_communication_window(position: &SatellitePosition) -> Communicat
         reason,
         signal_strength_estimate: signal_strength,
         recommended_mode,
+        path_loss: Some(path_loss),
+        margin: Some(margin),
+        station,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Polarization;
+    use crate::link_budget::{AntennaGain, PolarizationMismatch};
+    use crate::pass_prediction::sample_pass;
+
+    #[test]
+    fn test_parse_frequency_mhz() {
+        for text in ["145.800", "145800k", "145800 kHz", "145.8 MHz", "145800000", "145800"] {
+            assert!(
+                (parse_frequency_mhz(text).unwrap() - 145.8).abs() < 1e-9,
+                "{}",
+                text
+            );
+        }
+        assert_eq!(parse_frequency_mhz("10.4895G").unwrap(), 10489.5);
+        assert_eq!(parse_frequency_mhz("10489.5").unwrap(), 10489.5);
+        assert_eq!(parse_frequency_mhz("29400000 Hz").unwrap(), 29.4);
+        assert!(parse_frequency_mhz("145.8 furlongs").is_err());
+        assert!(parse_frequency_mhz("MHz").is_err());
+        assert!(parse_frequency_mhz("0").is_err());
+        assert_eq!(format_hz(145.8), "145800000 Hz");
+    }
+
+    fn so50() -> SatelliteRadioConfig {
+        SatelliteRadioConfig {
+            downlink_frequency_mhz: Some(436.795),
+            uplink_frequency_mhz: Some(145.850),
+            mode: Some("fm".to_string()),
+            ctcss_hz: Some(67.0),
+            arm_ctcss_hz: Some(74.4),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_downlink_offset() {
+        let offsets = vec![FrequencyOffset {
+            satellite_name: "AO-7".to_string(),
+            transponder: transponder_label(145.95),
+            offset_hz: -800.0,
+        }];
+        assert_eq!(downlink_offset_hz(&offsets, "AO-7", 145.950), -800.0);
+        assert_eq!(downlink_offset_hz(&offsets, "AO-7", 29.5025), 0.0);
+        assert_eq!(downlink_offset_hz(&offsets, "FO-29", 145.950), 0.0);
+    }
+
+    #[test]
+    fn test_tone_and_arming() {
+        let radio = so50();
+        assert!(radio.is_fm());
+        assert_eq!(radio.tone_description().as_deref(), Some("67.0 Hz CTCSS"));
+        assert_eq!(
+            radio.arming_description(145.850).as_deref(),
+            Some("74.4 Hz on 145.850 MHz to arm")
+        );
+    }
+
+    #[test]
+    fn test_tuning_aid_steps_down_during_pass() {
+        // +7.2 kHz and falling at 20 Hz/s: on the +5 kHz channel until +2.5 kHz
+        let aid = TuningAid::new(7_200.0, -20.0, 5_000.0);
+        assert_eq!(aid.channel_offset_hz, 5_000.0);
+        assert!((aid.seconds_to_next_step.unwrap() - 235.0).abs() < 1e-9);
+
+        assert!(TuningAid::new(0.0, 0.0, 5_000.0)
+            .seconds_to_next_step
+            .is_none());
+    }
+
+    #[test]
+    fn test_pass_phase() {
+        let aos = Utc::now();
+        let (_, pass) = sample_pass("ISS", aos, aos + chrono::Duration::minutes(10), 45.0);
+        let phase = PassPhase::new(&pass, aos + chrono::Duration::seconds(192), -3.5);
+        assert_eq!(phase.remaining_seconds, 408);
+        assert_eq!(phase.describe(), "AOS+03:12  approaching, before TCA");
+    }
+
+    fn at(elevation: f64, range_km: f64) -> SatellitePosition {
+        SatellitePosition {
+            name: "TEST".to_string(),
+            time: Utc::now(),
+            latitude: 0.0,
+            longitude: 0.0,
+            altitude_km: 35786.0,
+            velocity_km_s: 3.07,
+            range_rate_km_s: 0.0,
+            azimuth: 180.0,
+            elevation,
+            range_km,
+            is_visible: elevation > 0.0,
+            doppler: None,
+            comm_window: None,
+        }
+    }
+
+    #[test]
+    fn test_communication_window_by_orbit() {
+        assert_eq!(OrbitClass::new(15.5, 0.0006, 420.0), OrbitClass::Leo);
+        assert_eq!(OrbitClass::new(1.0027, 0.0002, 35790.0), OrbitClass::Geostationary);
+        let gto = OrbitClass::new(2.3, 0.72, 35000.0);
+        assert_eq!(gto, OrbitClass::Heo { apogee_km: 35000.0 });
+
+        // QO-100 from mid-latitudes: always too far for a LEO satellite's downlink
+        let qo100 = at(25.0, 38500.0);
+        let geo = OrbitClass::Geostationary;
+        let none = StationLink::default();
+        let window = evaluate_communication_window(&qo100, OrbitClass::Leo, 10489.5, none);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::NoSignal);
+        assert!(window.reason.starts_with("Downlink is 3.1 dB short"));
+        let window = evaluate_communication_window(&qo100, geo, 10489.5, none);
+        assert!(window.is_viable);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Fair);
+        assert_eq!(window.recommended_mode.as_deref(), Some("SSB/CW"));
+
+        // Low in the sky it's still workable, but weaker from S band up
+        let low = at(17.0, 40500.0);
+        let window = evaluate_communication_window(&low, geo, 10489.5, none);
+        assert!(window.is_viable);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Poor);
+        let window = evaluate_communication_window(&low, geo, 435.0, none);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Fair);
+
+        let window = evaluate_communication_window(&at(50.0, 30000.0), gto, 145.9, none);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Good);
+        let leo = OrbitClass::Leo;
+        let window = evaluate_communication_window(&at(7.0, 2900.0), leo, 145.9, none);
+        assert!(!window.is_viable);
+        assert!(evaluate_communication_window(&at(7.0, 40000.0), gto, 145.9, none).is_viable);
+    }
+
+    #[test]
+    fn test_atmospheric_absorption() {
+        let geo = OrbitClass::Geostationary;
+        let gto = OrbitClass::Heo { apogee_km: 41000.0 };
+        let none = StationLink::default();
+
+        // Next to nothing on 10 GHz high in the sky, more near the 22 GHz water vapour line
+        let qo100 = at(25.0, 38500.0);
+        let window = evaluate_communication_window(&qo100, geo, 10489.5, none);
+        assert!(window.path_loss.unwrap().atmospheric_db < 0.2);
+        let window = evaluate_communication_window(&qo100, geo, 24048.0, none);
+        assert!(window.path_loss.unwrap().atmospheric_db > 1.0);
+
+        // Low in the sky it eats into the margin, and near the horizon
+        // swallows a 24 GHz downlink that 10 GHz still gets through
+        let low = at(6.0, 41000.0);
+        let x_band = evaluate_communication_window(&low, gto, 10489.5, none);
+        let k_band = evaluate_communication_window(&low, gto, 24048.0, none);
+        assert!(x_band.margin.unwrap().margin_db - k_band.margin.unwrap().margin_db > 3.0);
+        let horizon = at(1.0, 41000.0);
+        let window = evaluate_communication_window(&horizon, gto, 10489.5, none);
+        assert!(window.margin.unwrap().margin_db > 0.0);
+        let window = evaluate_communication_window(&horizon, gto, 24048.0, none);
+        assert!(!window.is_viable);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::NoSignal);
+        assert!(window.reason.starts_with("Atmosphere absorbs"), "{}", window.reason);
+    }
+
+    #[test]
+    fn test_antenna_gain_in_margin() {
+        let gain = |toward_dbi, peak_dbi| StationLink {
+            antenna_gain: Some(AntennaGain {
+                toward_dbi,
+                peak_dbi,
+            }),
+            ..StationLink::default()
+        };
+
+        // A fixed eggbeater well below its peak still hears the ISS overhead
+        let overhead = at(60.0, 480.0);
+        let leo = OrbitClass::Leo;
+        let best = evaluate_communication_window(&overhead, leo, 145.9, gain(2.5, 3.0));
+        let window = evaluate_communication_window(&overhead, leo, 145.9, gain(-4.0, 3.0));
+        assert!(window.is_viable);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Excellent);
+        let lost_db = best.margin.unwrap().margin_db - window.margin.unwrap().margin_db;
+        assert!((lost_db - 6.5).abs() < 1e-9);
+
+        // A dish loses a geostationary satellite that drifts out of its beam
+        let qo100 = at(50.0, 37000.0);
+        let geo = OrbitClass::Geostationary;
+        let window = evaluate_communication_window(&qo100, geo, 10489.5, gain(33.0, 34.0));
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Excellent);
+        let window = evaluate_communication_window(&qo100, geo, 10489.5, gain(20.0, 34.0));
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Good);
+        let window = evaluate_communication_window(&qo100, geo, 10489.5, gain(3.0, 34.0));
+        assert!(!window.is_viable);
+        assert_eq!(window.reason, "Antenna is 31.0 dB below its peak toward the satellite");
+    }
+
+    #[test]
+    fn test_polarization_in_margin() {
+        let leo = OrbitClass::Leo;
+        let station = |antenna, satellite| StationLink {
+            polarization: Some(PolarizationMismatch::new(antenna, satellite, 0.0)),
+            ..StationLink::default()
+        };
+        let matched = station(Polarization::Rhcp, Polarization::Rhcp);
+        let linear = station(Polarization::Linear, Polarization::Rhcp);
+        let crossed = station(Polarization::Lhcp, Polarization::Rhcp);
+
+        // A linear yagi on a circular 70 cm downlink gives away 3 dB of margin,
+        // not the pass: workable high up and still low down
+        let high = at(30.0, 1000.0);
+        let best = evaluate_communication_window(&high, leo, 436.795, matched);
+        let window = evaluate_communication_window(&high, leo, 436.795, linear);
+        assert!(window.is_viable);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Good);
+        let lost_db = best.margin.unwrap().margin_db - window.margin.unwrap().margin_db;
+        assert!((lost_db - 3.0).abs() < 1e-9);
+        let window = evaluate_communication_window(&at(12.0, 2500.0), leo, 436.795, linear);
+        assert!(window.is_viable);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Poor);
+
+        // Crossed circular senses lose the ISS overhead to a Good signal, and a
+        // distant pass altogether
+        let window = evaluate_communication_window(&at(60.0, 480.0), leo, 145.9, crossed);
+        assert!(window.is_viable);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Good);
+        let window = evaluate_communication_window(&at(12.0, 2800.0), leo, 145.9, crossed);
+        assert!(!window.is_viable);
+        assert_eq!(window.reason, "Polarization mismatch loses 20.0 dB");
+    }
+
+    #[test]
+    fn test_noise_rise_in_margin() {
+        let leo = OrbitClass::Leo;
+        let noisy = |noise_rise_db| StationLink {
+            noise_rise_db,
+            ..StationLink::default()
+        };
+
+        // A couple of dB of inverter hash costs the ISS overhead nothing that shows
+        let overhead = at(60.0, 480.0);
+        let quiet = evaluate_communication_window(&overhead, leo, 145.9, noisy(0.0));
+        let window = evaluate_communication_window(&overhead, leo, 145.9, noisy(2.0));
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Excellent);
+        let lost_db = quiet.margin.unwrap().margin_db - window.margin.unwrap().margin_db;
+        assert!((lost_db - 2.0).abs() < 1e-9);
+
+        // Toward a noisy horizon a distant pass is weaker, then lost
+        let distant = at(12.0, 2800.0);
+        let window = evaluate_communication_window(&distant, leo, 145.9, noisy(14.0));
+        assert!(window.is_viable);
+        assert_eq!(window.signal_strength_estimate, SignalStrength::Poor);
+        let window = evaluate_communication_window(&distant, leo, 145.9, noisy(20.0));
+        assert!(!window.is_viable);
+        assert_eq!(window.reason, "Noise floor is 20.0 dB up toward 180°");
+    }
+
+    #[test]
+    fn test_format_mm_ss() {
+        assert_eq!(format_mm_ss(192.4), "03:12");
+        assert_eq!(format_mm_ss(-5.0), "00:00");
+    }
+
+    #[test]
+    fn test_dcs_without_arming() {
+        let radio = SatelliteRadioConfig {
+            mode: Some("SSB".to_string()),
+            dcs_code: Some(23),
+            ..Default::default()
+        };
+        assert!(!radio.is_fm());
+        assert_eq!(radio.tone_description().as_deref(), Some("DCS 023"));
+        assert!(radio.arming_description(145.850).is_none());
     }
 }
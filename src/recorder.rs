@@ -0,0 +1,586 @@
+//! Records the rig's audio through each pass of the selected satellite. The
+//! audio comes from an input device, such as the rig's USB sound card,
+//! through cpal, which uses ALSA, Core Audio or WASAPI as the system has.
+//! Crabtrack writes it to a WAV file from AOS to LOS, links the file to the
+//! pass log and plays it back from there on the default output device.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+
+use crate::config::RecordingConfig;
+use crate::database::PassLogEntry;
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::{self, Satellite};
+
+/// How often an audio thread checks whether it should stop
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Where a pass is recorded: `<directory>/<satellite>/<AOS>.wav`
+pub fn recording_path(directory: &Path, satellite_name: &str, aos_time: DateTime<Utc>) -> PathBuf {
+    directory
+        .join(satellite::slug(satellite_name))
+        .join(format!("{}.wav", aos_time.format("%Y%m%dT%H%M%SZ")))
+}
+
+/// The input device called `name`, or the system's default input
+fn input_device(name: Option<&str>) -> Result<cpal::Device> {
+    let host = cpal::default_host();
+    let Some(name) = name else {
+        return host.default_input_device().context("no audio input device");
+    };
+    let devices: Vec<cpal::Device> = host
+        .input_devices()
+        .context("listing audio input devices")?
+        .collect();
+    let names: Vec<String> = devices.iter().filter_map(|device| device.name().ok()).collect();
+    devices
+        .into_iter()
+        .find(|device| device.name().is_ok_and(|n| n == name))
+        .with_context(|| format!("no audio input called '{}' (found: {})", name, names.join(", ")))
+}
+
+/// Keep the first error an audio callback runs into
+fn note_failure(failure: &Mutex<Option<String>>, message: String) {
+    let mut failure = failure.lock().unwrap();
+    if failure.is_none() {
+        *failure = Some(message);
+    }
+}
+
+/// An open stream, kept by the thread that opened it until it is stopped or
+/// has nothing more to do
+trait AudioStream {
+    fn done(&self) -> bool;
+    fn close(self) -> Result<()>;
+}
+
+/// A cpal stream run on a thread of its own, since a stream can't be moved
+/// between threads on every host. Dropping it stops the stream.
+pub struct AudioThread {
+    stop: mpsc::Sender<()>,
+    thread: Option<JoinHandle<Result<()>>>,
+}
+
+impl AudioThread {
+    /// Run `open` on a new thread, returning once the stream it opens has started
+    fn spawn<S, F>(open: F) -> Result<Self>
+    where
+        S: AudioStream,
+        F: FnOnce() -> Result<S> + Send + 'static,
+    {
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let (stop, stop_rx) = mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || {
+            let stream = match open() {
+                Ok(stream) => {
+                    let _ = ready_tx.send(Ok(()));
+                    stream
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return Ok(());
+                }
+            };
+            while !stream.done() {
+                if !matches!(
+                    stop_rx.recv_timeout(POLL_INTERVAL),
+                    Err(mpsc::RecvTimeoutError::Timeout)
+                ) {
+                    break;
+                }
+            }
+            stream.close()
+        });
+        ready_rx.recv().context("the audio thread ended")??;
+        Ok(Self {
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Whether the stream has stopped by itself, at the end of a recording
+    /// played back or on an error
+    pub fn finished(&self) -> bool {
+        !matches!(&self.thread, Some(thread) if !thread.is_finished())
+    }
+
+    /// Stop the stream and wait for it to close
+    fn finish(&mut self) -> Result<()> {
+        let _ = self.stop.send(());
+        match self.thread.take() {
+            Some(thread) => thread
+                .join()
+                .map_err(|_| anyhow!("the audio thread panicked"))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for AudioThread {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+type WavFile = hound::WavWriter<BufWriter<File>>;
+
+/// A WAV file of 16-bit samples written from the audio callback, which
+/// notes the first error and stops writing rather than panicking
+struct WavSink {
+    writer: Mutex<Option<WavFile>>,
+    failure: Mutex<Option<String>>,
+}
+
+impl WavSink {
+    fn create(path: &Path, channels: u16, sample_rate: u32) -> Result<Self> {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let writer = hound::WavWriter::create(path, spec)
+            .with_context(|| format!("creating {}", path.display()))?;
+        Ok(Self {
+            writer: Mutex::new(Some(writer)),
+            failure: Mutex::new(None),
+        })
+    }
+
+    /// Append interleaved samples in the device's own format
+    fn write<T>(&self, samples: &[T])
+    where
+        T: SizedSample,
+        i16: FromSample<T>,
+    {
+        let mut writer = self.writer.lock().unwrap();
+        let Some(wav) = writer.as_mut() else {
+            return;
+        };
+        let written = samples
+            .iter()
+            .try_for_each(|&sample| wav.write_sample(sample.to_sample::<i16>()));
+        if let Err(e) = written {
+            *writer = None;
+            note_failure(&self.failure, format!("writing the WAV file: {}", e));
+        }
+    }
+
+    fn failed(&self) -> bool {
+        self.failure.lock().unwrap().is_some()
+    }
+
+    /// Write the WAV header's final lengths, and report any error on the way
+    fn finish(&self) -> Result<()> {
+        if let Some(writer) = self.writer.lock().unwrap().take() {
+            writer.finalize().context("finishing the WAV file")?;
+        }
+        match self.failure.lock().unwrap().take() {
+            Some(message) => Err(anyhow!(message)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Audio being captured into a WAV file
+struct CaptureStream {
+    stream: cpal::Stream,
+    sink: Arc<WavSink>,
+}
+
+impl CaptureStream {
+    /// Capture from the input `device` into a new WAV file at `path`, at the
+    /// device's own sample rate and channel count
+    fn open(device: Option<&str>, path: &Path) -> Result<Self> {
+        let device = input_device(device)?;
+        let config = device
+            .default_input_config()
+            .context("reading the audio input's format")?;
+        let sink = Arc::new(WavSink::create(
+            path,
+            config.channels(),
+            config.sample_rate().0,
+        )?);
+        let stream_config = config.config();
+        let stream = match config.sample_format() {
+            cpal::SampleFormat::I8 => input_stream::<i8>(&device, &stream_config, &sink),
+            cpal::SampleFormat::I16 => input_stream::<i16>(&device, &stream_config, &sink),
+            cpal::SampleFormat::I32 => input_stream::<i32>(&device, &stream_config, &sink),
+            cpal::SampleFormat::U8 => input_stream::<u8>(&device, &stream_config, &sink),
+            cpal::SampleFormat::U16 => input_stream::<u16>(&device, &stream_config, &sink),
+            cpal::SampleFormat::F32 => input_stream::<f32>(&device, &stream_config, &sink),
+            cpal::SampleFormat::F64 => input_stream::<f64>(&device, &stream_config, &sink),
+            other => Err(anyhow!("unsupported audio sample format {:?}", other)),
+        }?;
+        stream.play().context("starting the audio input")?;
+        Ok(Self { stream, sink })
+    }
+}
+
+impl AudioStream for CaptureStream {
+    fn done(&self) -> bool {
+        self.sink.failed()
+    }
+
+    /// The stream goes first, so nothing is written after the header is finished
+    fn close(self) -> Result<()> {
+        drop(self.stream);
+        self.sink.finish()
+    }
+}
+
+fn input_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sink: &Arc<WavSink>,
+) -> Result<cpal::Stream>
+where
+    T: SizedSample,
+    i16: FromSample<T>,
+{
+    let data_sink = Arc::clone(sink);
+    let error_sink = Arc::clone(sink);
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| data_sink.write(data),
+            move |e| note_failure(&error_sink.failure, format!("audio input: {}", e)),
+            None,
+        )
+        .context("opening the audio input")
+}
+
+/// Start capturing from the input `device`, or the default one, into a WAV file at `path`
+pub fn start_capture(device: Option<&str>, path: &Path) -> Result<AudioThread> {
+    let device = device.map(str::to_string);
+    let path = path.to_path_buf();
+    AudioThread::spawn(move || CaptureStream::open(device.as_deref(), &path))
+}
+
+/// A recording loaded for playback, as interleaved samples from -1 to 1
+struct Clip {
+    samples: Vec<f32>,
+    channels: usize,
+    sample_rate: u32,
+}
+
+impl Clip {
+    fn load(path: &Path) -> Result<Self> {
+        let reader =
+            hound::WavReader::open(path).with_context(|| format!("opening {}", path.display()))?;
+        let spec = reader.spec();
+        let samples: hound::Result<Vec<f32>> = match spec.sample_format {
+            hound::SampleFormat::Float => reader.into_samples::<f32>().collect(),
+            hound::SampleFormat::Int => {
+                let scale = 2f32.powi(spec.bits_per_sample as i32 - 1);
+                reader
+                    .into_samples::<i32>()
+                    .map(|sample| sample.map(|s| s as f32 / scale))
+                    .collect()
+            }
+        };
+        Ok(Self {
+            samples: samples.with_context(|| format!("reading {}", path.display()))?,
+            channels: spec.channels.max(1) as usize,
+            sample_rate: spec.sample_rate,
+        })
+    }
+
+    fn frames(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    /// The sample of `frame` for output `channel`, repeating the last channel
+    /// of the recording on outputs beyond it
+    fn sample(&self, frame: usize, channel: usize) -> f32 {
+        self.samples[frame * self.channels + channel.min(self.channels - 1)]
+    }
+}
+
+/// A recording being played back
+struct PlaybackStream {
+    _stream: cpal::Stream,
+    ended: Arc<AtomicBool>,
+    failure: Arc<Mutex<Option<String>>>,
+}
+
+impl PlaybackStream {
+    /// Play `path` on the default output, taking the nearest recorded sample
+    /// for each one the device asks for at its own rate
+    fn open(path: &Path) -> Result<Self> {
+        let clip = Clip::load(path)?;
+        let device = cpal::default_host()
+            .default_output_device()
+            .context("no audio output device")?;
+        let config = device
+            .default_output_config()
+            .context("reading the audio output's format")?;
+        let ended = Arc::new(AtomicBool::new(false));
+        let failure = Arc::new(Mutex::new(None));
+        let player = Player {
+            step: clip.sample_rate as f64 / config.sample_rate().0 as f64,
+            clip,
+            channels: config.channels().max(1) as usize,
+            position: 0,
+            ended: Arc::clone(&ended),
+        };
+        let output = config.config();
+        let stream = match config.sample_format() {
+            cpal::SampleFormat::I16 => output_stream::<i16>(&device, &output, player, &failure),
+            cpal::SampleFormat::I32 => output_stream::<i32>(&device, &output, player, &failure),
+            cpal::SampleFormat::U16 => output_stream::<u16>(&device, &output, player, &failure),
+            cpal::SampleFormat::F32 => output_stream::<f32>(&device, &output, player, &failure),
+            cpal::SampleFormat::F64 => output_stream::<f64>(&device, &output, player, &failure),
+            other => Err(anyhow!("unsupported audio sample format {:?}", other)),
+        }?;
+        stream.play().context("starting the audio output")?;
+        Ok(Self {
+            _stream: stream,
+            ended,
+            failure,
+        })
+    }
+}
+
+impl AudioStream for PlaybackStream {
+    fn done(&self) -> bool {
+        self.ended.load(Ordering::Relaxed) || self.failure.lock().unwrap().is_some()
+    }
+
+    fn close(self) -> Result<()> {
+        match self.failure.lock().unwrap().take() {
+            Some(message) => Err(anyhow!(message)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Feeds a clip to an output with its own rate and channel count
+struct Player {
+    clip: Clip,
+    channels: usize,         // of the output
+    step: f64,               // recorded frames per output frame
+    position: usize,         // output frames played
+    ended: Arc<AtomicBool>,
+}
+
+impl Player {
+    /// Fill interleaved `output` with the next frames, then silence once the clip has ended
+    fn fill<T: SizedSample + FromSample<f32>>(&mut self, output: &mut [T]) {
+        for frame in output.chunks_mut(self.channels) {
+            let source = (self.position as f64 * self.step) as usize;
+            self.position += 1;
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                *sample = if source < self.clip.frames() {
+                    T::from_sample(self.clip.sample(source, channel))
+                } else {
+                    T::EQUILIBRIUM
+                };
+            }
+            if source >= self.clip.frames() {
+                self.ended.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+fn output_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut player: Player,
+    failure: &Arc<Mutex<Option<String>>>,
+) -> Result<cpal::Stream>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let failure = Arc::clone(failure);
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| player.fill(data),
+            move |e| note_failure(&failure, format!("audio output: {}", e)),
+            None,
+        )
+        .context("opening the audio output")
+}
+
+/// Start playing the recording at `path` on the default output device
+pub fn start_playback(path: &Path) -> Result<AudioThread> {
+    let path = path.to_path_buf();
+    AudioThread::spawn(move || PlaybackStream::open(&path))
+}
+
+/// The capture running for one pass
+struct RecordingSession {
+    entry: PassLogEntry,
+    path: PathBuf,
+    los_time: DateTime<Utc>,
+    capture: AudioThread,
+}
+
+impl RecordingSession {
+    fn start(
+        config: &RecordingConfig,
+        path: PathBuf,
+        satellite_name: &str,
+        pass: &SatellitePass,
+    ) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let capture = start_capture(config.device.as_deref(), &path)?;
+        Ok(Self {
+            entry: PassLogEntry {
+                satellite_name: satellite_name.to_string(),
+                aos_time: pass.aos_time.to_rfc3339(),
+                los_time: pass.los_time.to_rfc3339(),
+                max_elevation: pass.max_elevation,
+                recording: Some(path.to_string_lossy().into_owned()),
+                ..Default::default()
+            },
+            path,
+            los_time: pass.los_time,
+            capture,
+        })
+    }
+
+    fn finished(&self, now: DateTime<Utc>) -> bool {
+        now >= self.los_time || self.capture.finished()
+    }
+
+    /// Stop the capture and return the pass log entry for it, if any audio was written
+    fn stop(mut self) -> Result<PassLogEntry> {
+        let stopped = self.capture.finish();
+        let written = hound::WavReader::open(&self.path).is_ok_and(|wav| wav.duration() > 0);
+        if !written {
+            stopped?;
+            anyhow::bail!("nothing was written to {}", self.path.display());
+        }
+        Ok(std::mem::take(&mut self.entry))
+    }
+}
+
+/// Records passes of the selected satellite from AOS to LOS
+pub struct PassRecorder {
+    pub config: RecordingConfig,
+    pub directory: PathBuf,
+    pub status: String,
+    session: Option<RecordingSession>,
+    attempted_pass: Option<(String, DateTime<Utc>)>, // (satellite, AOS) last started
+}
+
+impl PassRecorder {
+    pub fn new(config: RecordingConfig, directory: PathBuf) -> Self {
+        Self {
+            config,
+            directory,
+            status: "Waiting for AOS".to_string(),
+            session: None,
+            attempted_pass: None,
+        }
+    }
+
+    pub fn recording(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Start recording when a pass of `satellite` begins and stop at LOS, or
+    /// as soon as another satellite is selected. Returns the pass log entry
+    /// of a finished recording.
+    pub fn update(&mut self, satellite: &Satellite, now: DateTime<Utc>) -> Option<PassLogEntry> {
+        let mut finished = None;
+        if let Some(session) = self.session.take() {
+            if session.entry.satellite_name == satellite.name && !session.finished(now) {
+                self.session = Some(session);
+            } else {
+                let path = session.path.clone();
+                self.status = match session.stop() {
+                    Ok(entry) => {
+                        finished = Some(entry);
+                        format!("Recorded {}", path.display())
+                    }
+                    Err(e) => format!("Error: {:#}", e),
+                };
+            }
+        }
+
+        if self.session.is_none() {
+            if let Some(pass) = satellite.get_current_pass(now) {
+                // One start per pass, so a missing device isn't retried every frame
+                let key = (satellite.name.clone(), pass.aos_time);
+                if self.attempted_pass.as_ref() != Some(&key) {
+                    self.attempted_pass = Some(key);
+                    let path = recording_path(&self.directory, &satellite.name, pass.aos_time);
+                    match RecordingSession::start(&self.config, path, &satellite.name, pass) {
+                        Ok(session) => {
+                            self.status = format!("Recording {}", satellite.name);
+                            self.session = Some(session);
+                        }
+                        Err(e) => self.status = format!("Error: {:#}", e),
+                    }
+                }
+            }
+        }
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_recording_path() {
+        let aos = Utc.with_ymd_and_hms(2026, 10, 16, 12, 30, 5).unwrap();
+        let directory = Path::new("/home/me/crabtrack's recordings");
+        assert_eq!(
+            recording_path(directory, "ISS (ZARYA)", aos),
+            directory.join("iss_zarya").join("20261016T123005Z.wav")
+        );
+    }
+
+    #[test]
+    fn test_wav_written_and_played_back() {
+        let path = std::env::temp_dir().join(format!("crabtrack-wav-{}.wav", std::process::id()));
+
+        // Float and unsigned device formats both end up as 16-bit samples
+        let sink = WavSink::create(&path, 1, 48000).unwrap();
+        sink.write(&[0.0f32, 0.5, -0.5]);
+        sink.write(&[128u8, 255]);
+        sink.finish().unwrap();
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().sample_rate, 48000);
+        let samples: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+        assert_eq!(samples, [0, 16384, -16384, 0, 32512]);
+
+        // Played on a stereo output at twice the rate, each sample fills two
+        // frames on both channels, then silence
+        let clip = Clip::load(&path).unwrap();
+        assert_eq!(clip.frames(), 5);
+        let mut player = Player {
+            clip,
+            channels: 2,
+            step: 0.5,
+            position: 0,
+            ended: Arc::new(AtomicBool::new(false)),
+        };
+        let mut output = [1.0f32; 24];
+        player.fill(&mut output);
+        assert_eq!(output[..8], [0.0, 0.0, 0.0, 0.0, 0.5, 0.5, 0.5, 0.5]);
+        assert_eq!(output[20..], [0.0; 4]);
+        assert!(player.ended.load(Ordering::Relaxed));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,260 @@
+//! Pass reports for posting to a club mailing list or web page: every pass of
+//! the tracked satellites over the coming days, grouped by day, with the high
+//! passes that are worth getting up for picked out.
+
+use chrono::{DateTime, TimeZone, Utc};
+use clap::ValueEnum;
+use std::fmt::Write;
+
+use crate::pass_prediction::SatellitePass;
+use crate::ui::azimuth_to_cardinal;
+
+/// Output format of `crabtrack report`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Text,
+    Markdown,
+    Html,
+}
+
+/// What the report is about, for its heading
+pub struct ReportHeader<'a> {
+    pub observer: &'a str,
+    pub from: DateTime<Utc>,
+    pub days: i64,
+    pub high_elevation: f64, // passes reaching this are highlighted
+}
+
+/// One formatted table row
+struct Row {
+    satellite: String,
+    aos: String,
+    los: String,
+    minutes: String,
+    max_elevation: String,
+    direction: String,
+    visible: bool,
+    high: bool,
+}
+
+const COLUMNS: [&str; 6] = ["Satellite", "AOS", "LOS", "Mins", "Max El", "Direction"];
+
+/// Render `passes`, ordered by AOS, in `tz`
+pub fn render<Tz: TimeZone>(
+    passes: &[(String, SatellitePass)],
+    header: &ReportHeader,
+    format: ReportFormat,
+    tz: &Tz,
+) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let title = format!(
+        "Satellite passes for {}, {} {} from {}",
+        header.observer,
+        header.days,
+        if header.days == 1 { "day" } else { "days" },
+        header.from.with_timezone(tz).format("%Y-%m-%d %H:%M %Z")
+    );
+    let legend = format!(
+        "passes reaching {:.0}° or more. Those marked (visible) can be seen by eye.",
+        header.high_elevation
+    );
+
+    // Group by the local date of AOS
+    let mut days: Vec<(String, Vec<Row>)> = Vec::new();
+    for (name, pass) in passes {
+        let aos = pass.aos_time.with_timezone(tz);
+        let day = aos.format("%A %Y-%m-%d").to_string();
+        let row = Row {
+            satellite: name.clone(),
+            aos: aos.format("%H:%M:%S").to_string(),
+            los: pass.los_time.with_timezone(tz).format("%H:%M:%S").to_string(),
+            minutes: format!("{:.1}", pass.duration_minutes()),
+            max_elevation: format!("{:.0}°", pass.max_elevation),
+            direction: format!(
+                "{} to {}",
+                azimuth_to_cardinal(pass.aos_azimuth),
+                azimuth_to_cardinal(pass.los_azimuth)
+            ),
+            visible: pass.is_visual(),
+            high: pass.max_elevation >= header.high_elevation,
+        };
+        match days.last_mut() {
+            Some((last, rows)) if *last == day => rows.push(row),
+            _ => days.push((day, vec![row])),
+        }
+    }
+
+    match format {
+        ReportFormat::Text => text(&title, &legend, &days),
+        ReportFormat::Markdown => markdown(&title, &legend, &days),
+        ReportFormat::Html => html(&title, &legend, &days),
+    }
+}
+
+fn cells(row: &Row) -> [String; 6] {
+    let mut satellite = row.satellite.clone();
+    if row.visible {
+        satellite.push_str(" (visible)");
+    }
+    [
+        satellite,
+        row.aos.clone(),
+        row.los.clone(),
+        row.minutes.clone(),
+        row.max_elevation.clone(),
+        row.direction.clone(),
+    ]
+}
+
+fn text(title: &str, legend: &str, days: &[(String, Vec<Row>)]) -> String {
+    let mut out = format!("{}\n{}\n", title, "=".repeat(title.chars().count()));
+    if days.is_empty() {
+        out.push_str("\nNo passes.\n");
+        return out;
+    }
+    let _ = writeln!(out, "\n* marks {}", legend);
+    for (day, rows) in days {
+        let _ = writeln!(out, "\n{}\n{}", day, "-".repeat(day.chars().count()));
+        let _ = writeln!(
+            out,
+            "  {:<34} {:<8} {:<8} {:>5} {:>6}  {}",
+            COLUMNS[0], COLUMNS[1], COLUMNS[2], COLUMNS[3], COLUMNS[4], COLUMNS[5]
+        );
+        for row in rows {
+            let [satellite, aos, los, minutes, max_elevation, direction] = cells(row);
+            let _ = writeln!(
+                out,
+                "{} {:<34} {:<8} {:<8} {:>5} {:>6}  {}",
+                if row.high { "*" } else { " " },
+                satellite,
+                aos,
+                los,
+                minutes,
+                max_elevation,
+                direction
+            );
+        }
+    }
+    out
+}
+
+fn markdown(title: &str, legend: &str, days: &[(String, Vec<Row>)]) -> String {
+    let mut out = format!("# {}\n", title);
+    if days.is_empty() {
+        out.push_str("\nNo passes.\n");
+        return out;
+    }
+    let _ = writeln!(out, "\n**Bold** marks {}", legend);
+    for (day, rows) in days {
+        let _ = writeln!(out, "\n## {}\n", day);
+        let _ = writeln!(out, "| {} |", COLUMNS.join(" | "));
+        let _ = writeln!(out, "|{}", "---|".repeat(COLUMNS.len()));
+        for row in rows {
+            let cells = cells(row).map(|cell| {
+                let cell = cell.replace('|', "\\|");
+                if row.high {
+                    format!("**{}**", cell)
+                } else {
+                    cell
+                }
+            });
+            let _ = writeln!(out, "| {} |", cells.join(" | "));
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html(title: &str, legend: &str, days: &[(String, Vec<Row>)]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+         <style>\n\
+         body {{ font-family: sans-serif; }}\n\
+         table {{ border-collapse: collapse; margin-bottom: 1em; }}\n\
+         th, td {{ padding: 0.2em 0.8em; text-align: left; border-bottom: 1px solid #ccc; }}\n\
+         tr.high {{ font-weight: bold; background: #fff3c4; }}\n\
+         </style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape_html(title)
+    );
+    if days.is_empty() {
+        out.push_str("<p>No passes.</p>\n</body>\n</html>\n");
+        return out;
+    }
+    let _ = writeln!(out, "<p>Highlighted rows mark {}</p>", escape_html(legend));
+    for (day, rows) in days {
+        let _ = writeln!(out, "<h2>{}</h2>\n<table>", escape_html(day));
+        let header: String = COLUMNS.iter().map(|c| format!("<th>{}</th>", c)).collect();
+        let _ = writeln!(out, "<tr>{}</tr>", header);
+        for row in rows {
+            let cells: String = cells(row)
+                .iter()
+                .map(|cell| format!("<td>{}</td>", escape_html(cell)))
+                .collect();
+            let class = if row.high { " class=\"high\"" } else { "" };
+            let _ = writeln!(out, "<tr{}>{}</tr>", class, cells);
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::sample_pass;
+    use chrono::Duration;
+
+    #[test]
+    fn test_report_groups_by_day_and_highlights() {
+        let from = Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 0).unwrap();
+        // Rising in the southwest and setting in the north
+        let pass = |name, aos, max_elevation| {
+            let (name, pass) = sample_pass(name, aos, aos + Duration::minutes(10), max_elevation);
+            let pass = SatellitePass {
+                aos_azimuth: 225.0,
+                los_azimuth: 20.0,
+                ..pass
+            };
+            (name, pass)
+        };
+        let passes = vec![
+            pass("ISS (ZARYA)", from + Duration::hours(1), 72.0),
+            pass("SO-50", from + Duration::hours(2), 15.0),
+            pass("AO-91", from + Duration::hours(8), 40.0),
+        ];
+        let header = ReportHeader {
+            observer: "W1AW",
+            from,
+            days: 2,
+            high_elevation: 60.0,
+        };
+
+        let text = render(&passes, &header, ReportFormat::Text, &Utc);
+        assert!(text.contains("Tuesday 2026-04-21"));
+        assert!(text.contains("Wednesday 2026-04-22"));
+        assert!(text.contains("* ISS (ZARYA)"));
+        assert!(text.contains("  SO-50"));
+        assert!(text.contains("SW to N"));
+
+        let markdown = render(&passes, &header, ReportFormat::Markdown, &Utc);
+        assert_eq!(markdown.matches("\n## ").count(), 2);
+        assert!(markdown.contains("| **ISS (ZARYA)** | **19:00:00** |"));
+        assert!(markdown.contains("| SO-50 | 20:00:00 |"));
+
+        let html = render(&passes, &header, ReportFormat::Html, &Utc);
+        assert_eq!(html.matches("<tr class=\"high\">").count(), 1);
+        assert_eq!(html.matches("<table>").count(), 2);
+
+        let empty = render(&[], &header, ReportFormat::Text, &Utc);
+        assert!(empty.contains("No passes."));
+    }
+}
@@ -0,0 +1,343 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::config::RigConfig;
+use crate::hamlib::HamlibConnection;
+use crate::satellite::Satellite;
+
+/// What the connected rig reports it can do
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RigCapabilities {
+    pub model: String,
+    pub modes: Vec<String>, // Hamlib mode names, e.g. "USB", "FM", "PKTUSB"
+}
+
+impl RigCapabilities {
+    fn supports(&self, mode: &str) -> bool {
+        self.modes.is_empty() || self.modes.iter().any(|m| m == mode)
+    }
+}
+
+/// Parse the output of rigctld's `\dump_caps`
+pub fn parse_caps(lines: &[String]) -> RigCapabilities {
+    let mut caps = RigCapabilities::default();
+    for line in lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key.trim() {
+            "Model name" => caps.model = value.trim().to_string(),
+            "Mode list" => {
+                caps.modes = value.split_whitespace().map(str::to_string).collect();
+            }
+            _ => {}
+        }
+    }
+    caps
+}
+
+impl RigConfig {
+    /// Hamlib mode and passband for a transponder mode such as "FM", "USB"
+    /// or "BPSK", or None if the rig can't do it
+    pub fn mode_for(
+        &self,
+        transponder_mode: &str,
+        caps: &RigCapabilities,
+    ) -> Option<(String, u32)> {
+        let mode = transponder_mode
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .find(|token| !token.is_empty())?
+            .to_uppercase();
+
+        let (hamlib_mode, passband) = match mode.as_str() {
+            "FM" | "FMN" | "NFM" | "AFSK" | "APRS" | "PACKET" | "SSTV" => {
+                ("FM", self.fm_passband_hz)
+            }
+            "USB" | "SSB" => ("USB", self.ssb_passband_hz),
+            "LSB" => ("LSB", self.ssb_passband_hz),
+            "CW" => ("CW", self.cw_passband_hz),
+            "BPSK" | "PSK" | "FSK" | "GMSK" | "DATA" | "DIGI" if caps.supports("PKTUSB") => {
+                ("PKTUSB", self.data_passband_hz)
+            }
+            "BPSK" | "PSK" | "FSK" | "GMSK" | "DATA" | "DIGI" => ("USB", self.data_passband_hz),
+            _ => return None,
+        };
+        caps.supports(hamlib_mode)
+            .then(|| (hamlib_mode.to_string(), passband))
+    }
+}
+
+/// Minimal client for Hamlib's rigctld network protocol
+pub struct Rigctld {
+    connection: HamlibConnection,
+}
+
+impl Rigctld {
+    pub fn connect(host: &str, port: u16) -> Result<Self> {
+        Ok(Self {
+            connection: HamlibConnection::connect("rigctld", host, port)?,
+        })
+    }
+
+    pub fn capabilities(&mut self) -> Result<RigCapabilities> {
+        self.connection.send("\\dump_caps")?;
+        Ok(parse_caps(&self.connection.read_block()?))
+    }
+
+    pub fn set_mode(&mut self, mode: &str, passband_hz: u32) -> Result<()> {
+        self.connection
+            .command(&format!("M {} {}", mode, passband_hz))
+    }
+
+    /// Received signal strength in dB relative to S9
+    pub fn strength(&mut self) -> Result<f64> {
+        self.connection.send("l STRENGTH")?;
+        let reply = self.connection.read_line()?;
+        reply
+            .parse()
+            .map_err(|_| anyhow::anyhow!("rigctld gave no signal strength: {}", reply))
+    }
+}
+
+/// One S-meter reading taken during a pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalReading {
+    pub satellite_name: String,
+    pub time: DateTime<Utc>,
+    pub strength_db: f64, // relative to S9
+}
+
+/// An S-meter reading in S-units, six dB apart, e.g. "S7" or "S9+20"
+pub fn s_units(strength_db: f64) -> String {
+    if strength_db > 0.0 {
+        format!("S9+{:.0}", strength_db)
+    } else {
+        format!("S{:.0}", (9.0 + strength_db / 6.0).max(0.0))
+    }
+}
+
+/// Sets the rig up for the selected satellite's transponder when a pass begins
+pub struct RigController {
+    pub config: RigConfig,
+    pub capabilities: Option<RigCapabilities>,
+    pub status: String,
+    pub signal: Option<SignalReading>,
+    pub override_pause: bool, // keep reading the S-meter between passes too
+    client: Option<Rigctld>,
+    last_meter_read: Option<DateTime<Utc>>,
+    configured_pass: Option<(String, DateTime<Utc>)>, // (satellite, AOS) already set up
+}
+
+impl RigController {
+    pub fn new(config: RigConfig) -> Self {
+        Self {
+            config,
+            capabilities: None,
+            status: "Idle".to_string(),
+            signal: None,
+            override_pause: false,
+            client: None,
+            last_meter_read: None,
+            configured_pass: None,
+        }
+    }
+
+    fn client(&mut self) -> Result<&mut Rigctld> {
+        if self.client.is_none() {
+            let mut client = Rigctld::connect(&self.config.host, self.config.port)?;
+            self.capabilities = Some(client.capabilities()?);
+            self.client = Some(client);
+        }
+        self.client.as_mut().context("rigctld not connected")
+    }
+
+    /// Switch mode and filter once per pass, as soon as the pass is under way
+    pub fn update(
+        &mut self,
+        satellite: &Satellite,
+        transponder_mode: Option<&str>,
+        now: DateTime<Utc>,
+    ) {
+        if !self.config.set_mode_at_aos {
+            return;
+        }
+        let Some(pass) = satellite.get_current_pass(now) else {
+            return;
+        };
+        let key = (satellite.name.clone(), pass.aos_time);
+        if self.configured_pass.as_ref() == Some(&key) {
+            return;
+        }
+        // One attempt per pass; a missing rigctld shouldn't stall every frame
+        self.configured_pass = Some(key);
+
+        let Some(transponder_mode) = transponder_mode else {
+            self.status = format!("No transponder mode for {}", satellite.name);
+            return;
+        };
+        if let Err(e) = self.client() {
+            self.client = None;
+            self.status = format!("Error: {}", e);
+            return;
+        }
+
+        let caps = self.capabilities.clone().unwrap_or_default();
+        let Some((mode, passband)) = self.config.mode_for(transponder_mode, &caps) else {
+            self.status = format!("{} can't do {}", caps.model, transponder_mode);
+            return;
+        };
+        let result = self
+            .client()
+            .and_then(|client| client.set_mode(&mode, passband));
+        self.status = match result {
+            Ok(()) => format!("{} {} Hz for {}", mode, passband, satellite.name),
+            Err(e) => {
+                self.client = None;
+                format!("Error: {}", e)
+            }
+        };
+    }
+
+    /// Read the S-meter every `s_meter_interval_ms` while a pass of the
+    /// satellite is under way, or all the time when overridden
+    pub fn poll_s_meter(&mut self, satellite: &Satellite, now: DateTime<Utc>) {
+        let interval = Duration::milliseconds(self.config.s_meter_interval_ms as i64);
+        let in_pass = self.override_pause || satellite.get_current_pass(now).is_some();
+        if self.config.s_meter_interval_ms == 0 || !in_pass {
+            self.signal = None;
+            return;
+        }
+        // A missing rigctld is retried once per interval too
+        if self.last_meter_read.is_some_and(|last| now - last < interval) {
+            return;
+        }
+        self.last_meter_read = Some(now);
+
+        match self.client().and_then(|client| client.strength()) {
+            Ok(strength_db) => {
+                self.signal = Some(SignalReading {
+                    satellite_name: satellite.name.clone(),
+                    time: now,
+                    strength_db,
+                });
+            }
+            Err(e) => {
+                self.client = None;
+                self.signal = None;
+                self.status = format!("Error: {}", e);
+            }
+        }
+    }
+
+    /// The latest S-meter reading, in dB relative to S9, if it was taken
+    /// on this satellite
+    pub fn strength_for(&self, satellite_name: &str) -> Option<f64> {
+        self.signal
+            .as_ref()
+            .filter(|signal| signal.satellite_name == satellite_name)
+            .map(|signal| signal.strength_db)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    fn caps_lines() -> Vec<String> {
+        [
+            "Caps dump for model: 3081",
+            "Model name:\tIC-9700",
+            "Mfg name:\tIcom",
+            "Mode list: AM CW USB LSB RTTY FM CWR RTTYR PKTLSB PKTUSB PKTFM ",
+            "Overall backend warnings: 0",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_parse_caps() {
+        let caps = parse_caps(&caps_lines());
+        assert_eq!(caps.model, "IC-9700");
+        assert!(caps.modes.contains(&"PKTUSB".to_string()));
+        assert_eq!(caps.modes.len(), 11);
+    }
+
+    #[test]
+    fn test_mode_for_transponder() {
+        let config = RigConfig::default();
+        let caps = parse_caps(&caps_lines());
+        assert_eq!(
+            config.mode_for("FM", &caps),
+            Some(("FM".to_string(), 15000))
+        );
+        assert_eq!(
+            config.mode_for("usb/cw", &caps),
+            Some(("USB".to_string(), 2400))
+        );
+        assert_eq!(
+            config.mode_for("BPSK", &caps),
+            Some(("PKTUSB".to_string(), 3000))
+        );
+
+        let basic = RigCapabilities {
+            model: "FT-817".to_string(),
+            modes: vec!["USB".to_string(), "LSB".to_string(), "FM".to_string()],
+        };
+        assert_eq!(
+            config.mode_for("BPSK", &basic),
+            Some(("USB".to_string(), 3000))
+        );
+        assert_eq!(config.mode_for("CW", &basic), None);
+        assert_eq!(config.mode_for("", &basic), None);
+    }
+
+    #[test]
+    fn test_rigctld_protocol() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut commands = Vec::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                let reply = if line.trim() == "\\dump_caps" {
+                    "Model name:\tIC-9700\nMode list: USB FM\nRPRT 0\n".to_string()
+                } else if line.trim() == "l STRENGTH" {
+                    "-12\n".to_string()
+                } else {
+                    "RPRT 0\n".to_string()
+                };
+                writer.write_all(reply.as_bytes()).unwrap();
+                commands.push(line.trim().to_string());
+                line.clear();
+            }
+            commands
+        });
+
+        let mut client = Rigctld::connect("127.0.0.1", port).unwrap();
+        let caps = client.capabilities().unwrap();
+        assert_eq!(caps.modes, vec!["USB", "FM"]);
+        client.set_mode("FM", 15000).unwrap();
+        assert_eq!(client.strength().unwrap(), -12.0);
+        drop(client);
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec!["\\dump_caps", "M FM 15000", "l STRENGTH"]
+        );
+    }
+
+    #[test]
+    fn test_s_units() {
+        assert_eq!(s_units(0.0), "S9");
+        assert_eq!(s_units(-12.0), "S7");
+        assert_eq!(s_units(20.0), "S9+20");
+        assert_eq!(s_units(-60.0), "S0");
+    }
+}
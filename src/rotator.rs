@@ -0,0 +1,758 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, Utc};
+
+use crate::astro;
+use crate::config::RotatorConfig;
+use crate::database::{PointingErrorStats, RotatorCalibration};
+use crate::hamlib::HamlibConnection;
+use crate::observer::Observer;
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::{Satellite, SatellitePosition};
+
+impl RotatorCalibration {
+    /// Position to command so the antenna ends up pointing at the target.
+    /// Offsets are how far the antenna actually points from where it was sent.
+    pub fn corrected(&self, azimuth: f64, elevation: f64) -> (f64, f64) {
+        (
+            (azimuth - self.azimuth_offset).rem_euclid(360.0),
+            (elevation - self.elevation_offset).clamp(0.0, 90.0),
+        )
+    }
+}
+
+/// Minimal client for Hamlib's rotctld network protocol
+pub struct Rotctld {
+    connection: HamlibConnection,
+}
+
+impl Rotctld {
+    pub fn connect(host: &str, port: u16) -> Result<Self> {
+        Ok(Self {
+            connection: HamlibConnection::connect("rotctld", host, port)?,
+        })
+    }
+
+    pub fn set_position(&mut self, azimuth: f64, elevation: f64) -> Result<()> {
+        self.connection
+            .command(&format!("P {:.1} {:.1}", azimuth, elevation))
+    }
+
+    pub fn get_position(&mut self) -> Result<(f64, f64)> {
+        self.connection.send("p")?;
+        let azimuth = self.connection.read_line()?;
+        let elevation = self.connection.read_line()?;
+        parse_position(&azimuth, &elevation)
+    }
+}
+
+fn parse_position(azimuth: &str, elevation: &str) -> Result<(f64, f64)> {
+    if azimuth.starts_with("RPRT") {
+        return Err(anyhow::anyhow!("rotctld error: {}", azimuth));
+    }
+    Ok((
+        azimuth
+            .parse()
+            .with_context(|| format!("invalid azimuth '{}'", azimuth))?,
+        elevation
+            .parse()
+            .with_context(|| format!("invalid elevation '{}'", elevation))?,
+    ))
+}
+
+/// Commanded and reported positions compared through one tracked pass
+#[derive(Debug, Clone)]
+pub struct PointingLog {
+    satellite_name: String,
+    started: DateTime<Utc>,
+    errors: Vec<(f64, f64, f64)>, // azimuth, elevation and total error, degrees
+}
+
+impl PointingLog {
+    fn new(satellite_name: &str, started: DateTime<Utc>) -> Self {
+        Self {
+            satellite_name: satellite_name.to_string(),
+            started,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Compare where the rotator reports it points with where it was last
+    /// sent, both as rotator axes
+    fn add(&mut self, commanded: (f64, f64), reported: (f64, f64)) {
+        let (commanded_az, commanded_el) = from_axes(commanded.0, commanded.1);
+        let (reported_az, reported_el) = from_axes(reported.0, reported.1);
+        let azimuth_error = (reported_az - commanded_az + 180.0).rem_euclid(360.0) - 180.0;
+        let total = astro::angular_separation(commanded_az, commanded_el, reported_az, reported_el);
+        self.errors
+            .push((azimuth_error, reported_el - commanded_el, total));
+    }
+
+    /// Statistics for the pass, or None with too few samples to mean anything
+    pub fn stats(&self, rotator_name: &str) -> Option<PointingErrorStats> {
+        if self.errors.len() < 3 {
+            return None;
+        }
+        let count = self.errors.len() as f64;
+        let mean = |value: fn(&(f64, f64, f64)) -> f64| {
+            self.errors.iter().map(value).sum::<f64>() / count
+        };
+        Some(PointingErrorStats {
+            rotator_name: rotator_name.to_string(),
+            satellite_name: self.satellite_name.clone(),
+            pass_start: self.started.to_rfc3339(),
+            samples: self.errors.len() as i64,
+            mean_error_deg: mean(|e| e.2),
+            rms_error_deg: mean(|e| e.2 * e.2).sqrt(),
+            max_error_deg: self.errors.iter().map(|e| e.2).fold(0.0, f64::max),
+            mean_azimuth_error_deg: mean(|e| e.0),
+            mean_elevation_error_deg: mean(|e| e.1),
+        })
+    }
+}
+
+/// Drives a rotator through rotctld, following the selected satellite when
+/// tracking is switched on
+pub struct RotatorController {
+    pub config: RotatorConfig,
+    pub calibration: RotatorCalibration,
+    pub tracking: bool,
+    pub override_pause: bool, // keep following below the horizon mask too
+    pub status: String,
+    pub plan: Option<PassPlan>, // upcoming or current pass of the selected satellite
+    client: Option<Rotctld>,
+    last_command: Option<(f64, f64)>,
+    last_update: Option<DateTime<Utc>>,
+    pointing_log: Option<PointingLog>, // the pass being tracked
+    finished_pass: Option<PointingErrorStats>,
+    parked: bool,
+}
+
+impl RotatorController {
+    pub fn new(config: RotatorConfig, calibration: RotatorCalibration) -> Self {
+        Self {
+            config,
+            calibration,
+            tracking: false,
+            override_pause: false,
+            status: "Idle".to_string(),
+            plan: None,
+            client: None,
+            last_command: None,
+            last_update: None,
+            pointing_log: None,
+            finished_pass: None,
+            parked: false,
+        }
+    }
+
+    fn client(&mut self) -> Result<&mut Rotctld> {
+        if self.client.is_none() {
+            self.client = Some(Rotctld::connect(&self.config.host, self.config.port)?);
+        }
+        self.client.as_mut().context("rotctld not connected")
+    }
+
+    /// Send the rotator to an az/el, reconnecting if needed. Calibration
+    /// offsets are applied unless `raw` is set (used while calibrating).
+    pub fn point(&mut self, azimuth: f64, elevation: f64, raw: bool) -> Result<()> {
+        if raw {
+            self.send(azimuth, elevation)
+        } else {
+            let (azimuth, elevation) = self.calibration.corrected(azimuth, elevation);
+            self.send(azimuth, elevation)
+        }
+    }
+
+    /// Command raw rotator axes
+    fn send(&mut self, azimuth: f64, elevation: f64) -> Result<()> {
+        let result = self
+            .client()
+            .and_then(|client| client.set_position(azimuth, elevation));
+        match &result {
+            Ok(()) => {
+                self.last_command = Some((azimuth, elevation));
+                self.status = format!("Az {:.1}° El {:.1}°", azimuth, elevation);
+            }
+            Err(e) => {
+                self.client = None;
+                self.status = format!("Error: {}", e);
+            }
+        }
+        result
+    }
+
+    /// Send the rotator to its configured park position
+    pub fn park(&mut self) -> Result<()> {
+        let (azimuth, elevation) = self.config.park.context("no park position configured")?;
+        self.point(azimuth, elevation, false)?;
+        self.parked = true;
+        self.status = format!("Parked at Az {:.1}° El {:.1}°", azimuth, elevation);
+        Ok(())
+    }
+
+    /// Ask the rotator where it currently points
+    pub fn read_position(&mut self) -> Result<(f64, f64)> {
+        let result = self.client().and_then(Rotctld::get_position);
+        if result.is_err() {
+            self.client = None;
+        }
+        result
+    }
+
+    /// End the pass being logged, keeping its statistics for `take_finished_pass`
+    pub fn finish_pointing_log(&mut self) {
+        if let Some(log) = self.pointing_log.take() {
+            self.finished_pass = log.stats(&self.config.name).or(self.finished_pass.take());
+        }
+    }
+
+    /// Pointing error statistics of the last pass tracked to its end
+    pub fn take_finished_pass(&mut self) -> Option<PointingErrorStats> {
+        self.finished_pass.take()
+    }
+
+    /// Plan the selected satellite's current or next pass, so flip decisions
+    /// and warnings are ready before AOS. Replanning happens once per pass.
+    pub fn prepare(&mut self, satellite: &Satellite, observer: &Observer, now: DateTime<Utc>) {
+        let Some(pass) = satellite
+            .get_current_pass(now)
+            .or_else(|| satellite.get_next_pass())
+        else {
+            self.plan = None;
+            return;
+        };
+        let planned = self.plan.as_ref().is_some_and(|plan| {
+            plan.satellite_name == satellite.name && plan.aos_time == pass.aos_time
+        });
+        if !planned {
+            self.plan = Some(plan_pass(satellite, pass, observer, &self.config));
+        }
+    }
+
+    /// Follow a satellite while it is clear of the horizon mask and above the
+    /// configured minimum elevation, parking in between unless overridden
+    pub fn track(&mut self, position: Option<&SatellitePosition>, now: DateTime<Utc>) {
+        if !self.tracking {
+            return;
+        }
+        let Some(position) = position else {
+            return;
+        };
+        if self.last_update.is_some_and(|last| {
+            now - last < Duration::seconds(self.config.update_interval_seconds as i64)
+        }) {
+            return;
+        }
+        self.last_update = Some(now);
+
+        let clear = position.is_visible && position.elevation >= self.config.min_elevation;
+        if !clear && !self.override_pause {
+            self.finish_pointing_log();
+            if self.config.park.is_some() && !self.parked && self.park().is_err() {
+                return;
+            }
+            self.status = if self.parked {
+                format!("Parked, waiting for {}", position.name)
+            } else {
+                format!("Waiting for {}", position.name)
+            };
+            return;
+        }
+        self.parked = false;
+        if self
+            .pointing_log
+            .as_ref()
+            .is_some_and(|log| log.satellite_name != position.name)
+        {
+            self.finish_pointing_log();
+        }
+        // Where the rotator got to since the last command, read back before
+        // sending the next one
+        if let Some(commanded) = self.last_command {
+            if let Ok(reported) = self.read_position() {
+                self.pointing_log
+                    .get_or_insert_with(|| PointingLog::new(&position.name, now))
+                    .add(commanded, reported);
+            }
+        }
+        // Follow the plan's flip decision and azimuth wrap so the rotator
+        // takes the same path the simulation checked
+        let plan = self
+            .plan
+            .as_ref()
+            .filter(|plan| plan.satellite_name == position.name);
+        let flip = plan.is_some_and(|plan| plan.flip);
+        let (azimuth, elevation) = self
+            .calibration
+            .corrected(position.azimuth, position.elevation);
+        let (azimuth, elevation) = to_axes(azimuth, elevation, flip);
+        let reference = plan
+            .and_then(|plan| plan.sample_at(now))
+            .map(|sample| sample.rotator.0)
+            .or(self.last_command.map(|last| last.0))
+            .unwrap_or(azimuth);
+        let target = (
+            self.config.nearest_azimuth(azimuth, reference),
+            elevation.clamp(0.0, self.config.max_elevation),
+        );
+
+        if self.last_command.is_some_and(|last| {
+            (last.0 - target.0).abs().max((last.1 - target.1).abs()) < self.config.tolerance_deg
+        }) {
+            return;
+        }
+        if let Err(e) = self.send(target.0, target.1) {
+            self.status = format!("Error: {}", e);
+        }
+    }
+}
+
+/// One second of a simulated pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanSample {
+    pub time: DateTime<Utc>,
+    pub target: (f64, f64),   // where the satellite is (az, el)
+    pub rotator: (f64, f64),  // rotator axes; elevation goes past 90° when flipped
+    pub pointing: (f64, f64), // where the antenna actually points
+    pub error_deg: f64,
+    pub slewing: bool,      // rotator still catching up with the target
+    pub through_stop: bool, // the target wrapped past the azimuth stop here
+}
+
+/// How a rotator would follow one pass given its limits and slew rates
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassPlan {
+    pub satellite_name: String,
+    pub aos_time: DateTime<Utc>,
+    pub flip: bool, // drive az+180, el=180-el for the whole pass
+    pub samples: Vec<PlanSample>,
+    pub max_error_deg: f64,
+}
+
+impl PassPlan {
+    pub fn sample_at(&self, time: DateTime<Utc>) -> Option<&PlanSample> {
+        let offset = (time - self.aos_time).num_seconds();
+        usize::try_from(offset)
+            .ok()
+            .and_then(|idx| self.samples.get(idx))
+    }
+
+    /// Why the rotator would lose the satellite during this pass, if it would
+    pub fn warning(&self, max_error_deg: f64) -> Option<String> {
+        if self.max_error_deg <= max_error_deg {
+            return None;
+        }
+        let worst = self
+            .samples
+            .iter()
+            .max_by(|a, b| a.error_deg.total_cmp(&b.error_deg))?;
+
+        Some(
+            match self.samples.iter().find(|sample| sample.through_stop) {
+                Some(swing) => format!(
+                    "{} pass crosses the azimuth stop at {}; up to {:.0}° off target",
+                    self.satellite_name,
+                    swing.time.with_timezone(&Local).format("%H:%M:%S"),
+                    self.max_error_deg
+                ),
+                None => format!(
+                    "{} pass is too fast for the rotator near {}; up to {:.0}° behind",
+                    self.satellite_name,
+                    worst.time.with_timezone(&Local).format("%H:%M:%S"),
+                    self.max_error_deg
+                ),
+            },
+        )
+    }
+}
+
+impl RotatorConfig {
+    fn can_flip(&self) -> bool {
+        self.max_elevation >= 180.0
+    }
+
+    /// The representation of `azimuth` within the rotator's travel closest to `current`
+    fn nearest_azimuth(&self, azimuth: f64, current: f64) -> f64 {
+        let base = azimuth.rem_euclid(360.0);
+        (-1..=2)
+            .map(|turns| base + 360.0 * turns as f64)
+            .filter(|candidate| (self.min_azimuth..=self.max_azimuth).contains(candidate))
+            .min_by(|a, b| (a - current).abs().total_cmp(&(b - current).abs()))
+            .unwrap_or_else(|| base.clamp(self.min_azimuth, self.max_azimuth))
+    }
+}
+
+fn to_axes(azimuth: f64, elevation: f64, flip: bool) -> (f64, f64) {
+    if flip {
+        ((azimuth + 180.0).rem_euclid(360.0), 180.0 - elevation)
+    } else {
+        (azimuth, elevation)
+    }
+}
+
+fn from_axes(azimuth: f64, elevation: f64) -> (f64, f64) {
+    if elevation > 90.0 {
+        ((azimuth + 180.0).rem_euclid(360.0), 180.0 - elevation)
+    } else {
+        (azimuth.rem_euclid(360.0), elevation)
+    }
+}
+
+/// Follow a 1 Hz target track with the rotator's travel limits and slew rates
+fn simulate(
+    track: &[(DateTime<Utc>, f64, f64)],
+    config: &RotatorConfig,
+    flip: bool,
+) -> Vec<PlanSample> {
+    let Some(&(_, first_az, first_el)) = track.first() else {
+        return Vec::new();
+    };
+
+    // Start from whichever representation of the first azimuth needs the
+    // fewest long swings through the stop over the whole pass
+    let axes_track = |start: f64| {
+        let mut previous = start;
+        track
+            .iter()
+            .map(|&(_, az, el)| {
+                let (az, el) = to_axes(az, el.max(0.0), flip);
+                previous = config.nearest_azimuth(az, previous);
+                (previous, el.clamp(0.0, config.max_elevation))
+            })
+            .collect::<Vec<_>>()
+    };
+    let swings = |axes: &[(f64, f64)]| {
+        axes.windows(2)
+            .filter(|w| (w[1].0 - w[0].0).abs() > 180.0)
+            .count()
+    };
+    let first_axis = to_axes(first_az, first_el, flip).0.rem_euclid(360.0);
+    let axes = (-1..=2)
+        .map(|turns| first_axis + 360.0 * turns as f64)
+        .filter(|start| (config.min_azimuth..=config.max_azimuth).contains(start))
+        .map(axes_track)
+        .min_by_key(|axes| swings(axes))
+        .unwrap_or_else(|| axes_track(first_axis));
+
+    let step =
+        |current: f64, target: f64, rate: f64| current + (target - current).clamp(-rate, rate);
+    let mut rotator = axes[0];
+    let mut previous = axes[0];
+    track
+        .iter()
+        .zip(&axes)
+        .map(|(&(time, az, el), &wanted)| {
+            let through_stop = (wanted.0 - previous.0).abs() > 180.0;
+            previous = wanted;
+            rotator = (
+                step(rotator.0, wanted.0, config.azimuth_rate_deg_s),
+                step(rotator.1, wanted.1, config.elevation_rate_deg_s),
+            );
+            let pointing = from_axes(rotator.0, rotator.1);
+            PlanSample {
+                time,
+                target: (az, el),
+                rotator,
+                pointing,
+                error_deg: astro::angular_separation(pointing.0, pointing.1, az, el.max(0.0)),
+                slewing: (rotator.0 - wanted.0).abs() > 0.1 || (rotator.1 - wanted.1).abs() > 0.1,
+                through_stop,
+            }
+        })
+        .collect()
+}
+
+fn plan_track(
+    satellite_name: &str,
+    track: &[(DateTime<Utc>, f64, f64)],
+    config: &RotatorConfig,
+) -> PassPlan {
+    let max_error =
+        |samples: &[PlanSample]| samples.iter().map(|s| s.error_deg).fold(0.0, f64::max);
+
+    let normal = simulate(track, config, false);
+    let flipped = config.can_flip().then(|| simulate(track, config, true));
+    let (flip, samples) = match flipped {
+        Some(flipped) if max_error(&flipped) + 0.5 < max_error(&normal) => (true, flipped),
+        _ => (false, normal),
+    };
+
+    PassPlan {
+        satellite_name: satellite_name.to_string(),
+        aos_time: track.first().map_or_else(Utc::now, |&(time, _, _)| time),
+        flip,
+        max_error_deg: max_error(&samples),
+        samples,
+    }
+}
+
+/// Simulate the rotator following a pass, choosing flip mode when it keeps
+/// the antenna closer to the satellite than normal mode
+pub fn plan_pass(
+    satellite: &Satellite,
+    pass: &SatellitePass,
+    observer: &Observer,
+    config: &RotatorConfig,
+) -> PassPlan {
+    let mut track = Vec::new();
+    let mut time = pass.aos_time;
+    while time <= pass.los_time {
+        if let Ok(position) = satellite.calculate_position(time, observer) {
+            track.push((time, position.azimuth, position.elevation));
+        }
+        time += Duration::seconds(1);
+    }
+    plan_track(&satellite.name, &track, config)
+}
+
+/// Reference directions for calibration: the Sun and Moon when they are up,
+/// plus the cardinal points on the horizon and the zenith
+pub fn calibration_targets(
+    time: DateTime<Utc>,
+    observer: &Observer,
+) -> Vec<(&'static str, f64, f64)> {
+    let sun = astro::sun_look_angles(time, observer);
+    let moon = astro::moon_look_angles(time, observer);
+
+    let mut targets = Vec::new();
+    if sun.elevation > 0.0 {
+        targets.push(("Sun", sun.azimuth, sun.elevation));
+    }
+    if moon.elevation > 0.0 {
+        targets.push(("Moon", moon.azimuth, moon.elevation));
+    }
+    targets.push(("North horizon", 0.0, 0.0));
+    targets.push(("East horizon", 90.0, 0.0));
+    targets.push(("South horizon", 180.0, 0.0));
+    targets.push(("West horizon", 270.0, 0.0));
+    targets.push(("Zenith", 0.0, 90.0));
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_calibration_correction() {
+        let calibration = RotatorCalibration {
+            rotator_name: "yagi".to_string(),
+            azimuth_offset: 3.0,
+            elevation_offset: -2.0,
+        };
+        assert_eq!(calibration.corrected(180.0, 45.0), (177.0, 47.0));
+        assert_eq!(calibration.corrected(1.0, 89.0), (358.0, 90.0));
+    }
+
+    /// A pass crossing due north: azimuth runs 300° -> 0° -> 60°
+    fn northbound_track() -> Vec<(DateTime<Utc>, f64, f64)> {
+        let start = Utc::now();
+        (0..=120)
+            .map(|i| {
+                let fraction = i as f64 / 120.0;
+                let elevation = 60.0 * (fraction * std::f64::consts::PI).sin();
+                (
+                    start + Duration::seconds(i),
+                    (300.0 + 120.0 * fraction).rem_euclid(360.0),
+                    elevation,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_azimuth() {
+        let config = RotatorConfig {
+            max_azimuth: 450.0,
+            ..Default::default()
+        };
+        assert_eq!(config.nearest_azimuth(10.0, 350.0), 370.0);
+        assert_eq!(config.nearest_azimuth(350.0, 20.0), 350.0);
+        assert_eq!(RotatorConfig::default().nearest_azimuth(10.0, 350.0), 10.0);
+    }
+
+    #[test]
+    fn test_plan_crossing_stop() {
+        let track = northbound_track();
+
+        // 0-360° rotator without flip has to swing all the way round
+        let plan = plan_track("AO-91", &track, &RotatorConfig::default());
+        assert!(!plan.flip);
+        assert!(plan.max_error_deg > 90.0);
+        assert!(plan.warning(10.0).unwrap().contains("azimuth stop"));
+        assert_eq!(plan.sample_at(track[5].0), plan.samples.get(5));
+
+        // Flipping over the top keeps the antenna on the satellite
+        let flip_config = RotatorConfig {
+            max_elevation: 180.0,
+            ..Default::default()
+        };
+        let plan = plan_track("AO-91", &track, &flip_config);
+        assert!(plan.flip);
+        assert!(plan.max_error_deg < 5.0, "max error {}", plan.max_error_deg);
+        assert_eq!(plan.warning(10.0), None);
+
+        // Overlap travel past 360° avoids the swing without flipping
+        let overlap_config = RotatorConfig {
+            max_azimuth: 450.0,
+            ..Default::default()
+        };
+        let plan = plan_track("AO-91", &track, &overlap_config);
+        assert!(!plan.flip);
+        assert!(plan.max_error_deg < 5.0, "max error {}", plan.max_error_deg);
+    }
+
+    #[test]
+    fn test_pointing_log() {
+        let start = Utc::now();
+        let mut log = PointingLog::new("ISS", start);
+        log.add((10.0, 20.0), (12.0, 20.0));
+        log.add((359.0, 30.0), (1.0, 30.0)); // across north, still 2° east
+        assert_eq!(log.stats("yagi"), None);
+        // Flipped over the top: the same direction as (200°, 60°)
+        log.add((20.0, 120.0), (22.0, 120.0));
+
+        let stats = log.stats("yagi").unwrap();
+        assert_eq!((stats.rotator_name.as_str(), stats.samples), ("yagi", 3));
+        assert!((stats.mean_azimuth_error_deg - 2.0).abs() < 1e-9);
+        assert!(stats.mean_elevation_error_deg.abs() < 1e-9);
+        // 2° of azimuth is less of an angle higher up
+        assert!((stats.max_error_deg - 2.0 * 20f64.to_radians().cos()).abs() < 0.01);
+        assert!(stats.mean_error_deg < stats.rms_error_deg);
+        assert!(stats.rms_error_deg < stats.max_error_deg);
+    }
+
+    #[test]
+    fn test_plan_too_fast_near_zenith() {
+        // Overhead pass: azimuth jumps 180° within a few seconds at culmination
+        let start = Utc::now();
+        let track: Vec<_> = (0..=120)
+            .map(|i| {
+                let fraction = i as f64 / 120.0;
+                let azimuth = if i < 60 { 100.0 } else { 280.0 };
+                let elevation = 89.0 * (fraction * std::f64::consts::PI).sin();
+                (start + Duration::seconds(i), azimuth, elevation)
+            })
+            .collect();
+        let slow = RotatorConfig {
+            azimuth_rate_deg_s: 2.0,
+            elevation_rate_deg_s: 2.0,
+            ..Default::default()
+        };
+
+        let plan = plan_track("ISS", &track, &slow);
+        assert!(plan.samples.iter().any(|sample| sample.slewing));
+        assert!(plan.warning(10.0).unwrap().contains("too fast"));
+    }
+
+    #[test]
+    fn test_parse_position() {
+        assert_eq!(
+            parse_position("180.000000", "45.000000").unwrap(),
+            (180.0, 45.0)
+        );
+        assert!(parse_position("RPRT -1", "").is_err());
+    }
+
+    #[test]
+    fn test_rotctld_protocol() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut commands = Vec::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                let reply = if line.trim() == "p" {
+                    "123.4\n56.7\n"
+                } else {
+                    "RPRT 0\n"
+                };
+                writer.write_all(reply.as_bytes()).unwrap();
+                commands.push(line.trim().to_string());
+                line.clear();
+            }
+            commands
+        });
+
+        let mut client = Rotctld::connect("127.0.0.1", port).unwrap();
+        client.set_position(90.0, 10.0).unwrap();
+        assert_eq!(client.get_position().unwrap(), (123.4, 56.7));
+        drop(client);
+
+        assert_eq!(server.join().unwrap(), vec!["P 90.0 10.0", "p"]);
+    }
+
+    #[test]
+    fn test_park_between_passes() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut commands = Vec::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                let reply = if line.trim() == "p" {
+                    "180.0\n0.0\n"
+                } else {
+                    "RPRT 0\n"
+                };
+                writer.write_all(reply.as_bytes()).unwrap();
+                commands.push(line.trim().to_string());
+                line.clear();
+            }
+            commands
+        });
+
+        let config = RotatorConfig {
+            port,
+            host: "127.0.0.1".to_string(),
+            park: Some((180.0, 0.0)),
+            ..Default::default()
+        };
+        let mut rotator = RotatorController::new(config, RotatorCalibration::default());
+        rotator.tracking = true;
+        let start = Utc::now();
+        let position = |seconds: i64, azimuth: f64, elevation: f64, is_visible: bool| {
+            SatellitePosition {
+                name: "ISS".to_string(),
+                time: start + Duration::seconds(seconds),
+                latitude: 0.0,
+                longitude: 0.0,
+                altitude_km: 420.0,
+                velocity_km_s: 7.7,
+                range_rate_km_s: 0.0,
+                azimuth,
+                elevation,
+                range_km: 2000.0,
+                is_visible,
+                doppler: None,
+                comm_window: None,
+            }
+        };
+
+        let at = |seconds: i64| start + Duration::seconds(seconds);
+
+        // Up, but behind the mask: park once and wait
+        rotator.track(Some(&position(0, 90.0, 5.0, false)), at(0));
+        rotator.track(Some(&position(10, 92.0, 6.0, false)), at(10));
+        assert_eq!(rotator.status, "Parked, waiting for ISS");
+        // Clear of the mask: follow it
+        rotator.track(Some(&position(20, 95.0, 12.0, true)), at(20));
+        // Overridden: keep following after it drops behind the mask
+        rotator.override_pause = true;
+        rotator.track(Some(&position(30, 100.0, 4.0, false)), at(30));
+        drop(rotator);
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec!["P 180.0 0.0", "p", "P 95.0 12.0", "p", "P 100.0 4.0"]
+        );
+    }
+}
@@ -1,11 +1,28 @@
 use anyhow::Result;
-use chrono::{DateTime, Datelike, Utc};
+use chrono::{DateTime, Utc};
 use nalgebra::Vector3;
 use sgp4::{Constants, Elements, MinutesSinceEpoch};
+use std::f64::consts::TAU;
 
-use crate::observer::Observer;
-use crate::pass_prediction::{calculate_gmst, calculate_look_angles, SatellitePass};
-use crate::radio::{CommunicationWindow, DopplerShift};
+use crate::elevation::latlon_to_grid;
+use crate::observer::{normalize_longitude, Observer};
+use crate::frames::TemeToEcef;
+use crate::pass_prediction::{look_angles_from_ecef, SatellitePass};
+use crate::radio::{CommunicationWindow, DopplerShift, OrbitClass};
+
+const EARTH_ROT_RAD_S: f64 = 7.2921150e-5;
+const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458;
+const EARTH_MU_KM3_S2: f64 = 398600.4418;
+pub const EARTH_RADIUS_KM: f64 = 6378.137;
+
+/// SGP4's along-track error at the element epoch, and how fast it grows per
+/// day either side without drag (km)
+const ALONG_TRACK_KM_AT_EPOCH: f64 = 1.0;
+const ALONG_TRACK_KM_PER_DAY: f64 = 2.0;
+
+/// Share of the drag term that turns out wrong, mostly from forecasts of
+/// the upper atmosphere's density
+const DRAG_UNCERTAINTY: f64 = 0.2;
 
 #[derive(Clone)]
 pub struct Satellite {
@@ -13,6 +30,8 @@ pub struct Satellite {
     pub elements: Elements,
     pub passes: Vec<SatellitePass>,
     pub epoch: DateTime<Utc>, // Add this field
+    pub tle_line1: String,
+    pub tle_line2: String,
 }
 
 #[derive(Debug, Clone)]
@@ -34,12 +53,20 @@ pub struct SatellitePosition {
 }
 
 impl Satellite {
-    pub fn new(name: String, elements: Elements, epoch: DateTime<Utc>) -> Self {
+    pub fn new(
+        name: String,
+        elements: Elements,
+        epoch: DateTime<Utc>,
+        tle_line1: String,
+        tle_line2: String,
+    ) -> Self {
         Self {
             name,
             elements,
             passes: Vec::new(),
             epoch,
+            tle_line1,
+            tle_line2,
         }
     }
 
@@ -49,18 +76,50 @@ impl Satellite {
         observer: &Observer,
     ) -> Result<SatellitePosition> {
         let constants = Constants::from_elements(&self.elements)?;
-        let epoch_time = self.elements.epoch();
-        let minutes_since_epoch = self.calculate_minutes_since_epoch(time, epoch_time);
+        let minutes_since_epoch =
+            (time - self.elements.datetime.and_utc()).num_milliseconds() as f64 / 60000.0;
 
-        let prediction = constants.propagate(MinutesSinceEpoch(minutes_since_epoch))?;
+        let observer_ecef = observer.to_ecef();
+        let frame = match &observer.earth_orientation {
+            Some(eop) => TemeToEcef::precise(time, eop.at(time).unwrap_or_default()),
+            None => TemeToEcef::simple(time),
+        };
 
-        // Get position in ECI (km)
-        let sat_pos_km = Vector3::new(
+        let mut prediction = constants.propagate(MinutesSinceEpoch(minutes_since_epoch))?;
+        let mut light_time_s = 0.0;
+        let observer_eci_km = frame.to_teme(&observer_ecef) / 1000.0;
+        if observer.light_time_correction {
+            // The signal received now left the satellite one light-time ago;
+            // three iterations converge far below a nanosecond
+            for _ in 0..3 {
+                let emitted_from = Vector3::new(
+                    prediction.position[0],
+                    prediction.position[1],
+                    prediction.position[2],
+                );
+                light_time_s = (emitted_from - observer_eci_km).norm() / SPEED_OF_LIGHT_KM_S;
+                prediction = constants.propagate(MinutesSinceEpoch(
+                    minutes_since_epoch - light_time_s / 60.0,
+                ))?;
+            }
+        }
+
+        // Get position in ECI (km), where the satellite was when the signal left it
+        let emitted_pos_km = Vector3::new(
             prediction.position[0],
             prediction.position[1],
             prediction.position[2],
         );
 
+        // Aberration: the observer moves with the Earth's rotation while the
+        // signal is in flight, displacing the apparent direction by v·τ
+        let observer_vel_eci_km_s = Vector3::new(
+            -EARTH_ROT_RAD_S * observer_eci_km.y,
+            EARTH_ROT_RAD_S * observer_eci_km.x,
+            0.0,
+        );
+        let sat_pos_km = emitted_pos_km + observer_vel_eci_km_s * light_time_s;
+
         // Get velocity in ECI (km/s)
         let sat_vel_km_s = Vector3::new(
             prediction.velocity[0],
@@ -70,42 +129,18 @@ impl Satellite {
 
         let velocity_km_s = sat_vel_km_s.norm();
 
-        // Convert to meters for calculations
-        let sat_pos = sat_pos_km * 1000.0;
-
-        // Calculate look angles
-        let observer_ecef = observer.to_ecef();
-        let gmst = calculate_gmst(time);
-        let look_angles = calculate_look_angles(
-            &sat_pos,
+        // Calculate look angles from the Earth-fixed position (meters)
+        let sat_ecef_km = frame.position(&sat_pos_km);
+        let look_angles = look_angles_from_ecef(
+            &(sat_ecef_km * 1000.0),
             &observer_ecef,
-            gmst,
             observer.latitude,
             observer.longitude,
         );
 
         // Compute range rate via dot product of relative velocity with unit range vector.
-        // Convert satellite ECI velocity to ECEF: v_ecef = R(gmst)*v_eci - ω×r_ecef
         // Observer is fixed on Earth so v_obs_ecef = 0.
-        const EARTH_ROT_RAD_S: f64 = 7.2921150e-5;
-        let gmst_cos = gmst.cos();
-        let gmst_sin = gmst.sin();
-        let sat_ecef_km = Vector3::new(
-            sat_pos_km.x * gmst_cos + sat_pos_km.y * gmst_sin,
-            -sat_pos_km.x * gmst_sin + sat_pos_km.y * gmst_cos,
-            sat_pos_km.z,
-        );
-        let vel_rotated = Vector3::new(
-            sat_vel_km_s.x * gmst_cos + sat_vel_km_s.y * gmst_sin,
-            -sat_vel_km_s.x * gmst_sin + sat_vel_km_s.y * gmst_cos,
-            sat_vel_km_s.z,
-        );
-        let sat_vel_ecef_km_s = vel_rotated
-            - Vector3::new(
-                -EARTH_ROT_RAD_S * sat_ecef_km.y,
-                EARTH_ROT_RAD_S * sat_ecef_km.x,
-                0.0,
-            );
+        let sat_vel_ecef_km_s = frame.velocity(&sat_pos_km, &sat_vel_km_s);
         let obs_ecef_km = observer_ecef / 1000.0;
         let range_vec_km = sat_ecef_km - obs_ecef_km;
         let range_norm = range_vec_km.norm();
@@ -115,8 +150,8 @@ impl Satellite {
             0.0
         };
 
-        // Convert ECI to geodetic coordinates
-        let (lat, lon, alt_km) = eci_to_geodetic(&sat_pos_km, gmst);
+        // Convert to geodetic coordinates
+        let (lat, lon, alt_km) = ecef_to_geodetic(&frame.position(&emitted_pos_km));
 
         Ok(SatellitePosition {
             name: self.name.clone(),
@@ -129,61 +164,214 @@ impl Satellite {
             azimuth: look_angles.azimuth,
             elevation: look_angles.elevation,
             range_km: look_angles.range,
-            is_visible: look_angles.elevation > 0.0,
+            is_visible: observer
+                .horizon
+                .is_clear(look_angles.azimuth, look_angles.elevation),
             doppler: None,
             comm_window: None,
         })
     }
 
-    pub fn get_next_pass(&self) -> Option<&SatellitePass> {
-        let now = Utc::now();
-        self.passes.iter().find(|pass| pass.aos_time > now)
+    /// Position (km) and velocity (km/s) in the inertial TEME frame
+    pub fn teme_state(&self, time: DateTime<Utc>) -> Result<(Vector3<f64>, Vector3<f64>)> {
+        let constants = Constants::from_elements(&self.elements)?;
+        let minutes_since_epoch =
+            (time - self.elements.datetime.and_utc()).num_milliseconds() as f64 / 60000.0;
+        let prediction = constants.propagate(MinutesSinceEpoch(minutes_since_epoch))?;
+        Ok((
+            Vector3::from(prediction.position),
+            Vector3::from(prediction.velocity),
+        ))
     }
 
-    fn calculate_minutes_since_epoch(&self, time: DateTime<Utc>, epoch_day_of_year: f64) -> f64 {
-        let current_year = time.year();
+    /// Minutes per orbit, from the mean motion in revolutions per day
+    pub fn period_minutes(&self) -> f64 {
+        1440.0 / self.elements.mean_motion
+    }
 
-        let mut epoch_time = year_day_to_datetime(current_year, epoch_day_of_year);
+    /// Estimated along-track position error (km) of the elements at a time,
+    /// growing with their age and, quadratically, with the drag term
+    pub fn along_track_uncertainty_km(&self, time: DateTime<Utc>) -> f64 {
+        let days = ((time - self.epoch).num_seconds() as f64 / 86400.0).abs();
+        // The TLE carries half the mean motion's rate of change, so drag moves
+        // the satellite mean_motion_dot * days² revolutions along its orbit
+        let drag_km = DRAG_UNCERTAINTY
+            * self.elements.mean_motion_dot.abs()
+            * days.powi(2)
+            * TAU
+            * self.semi_major_axis_km();
+        ALONG_TRACK_KM_AT_EPOCH + ALONG_TRACK_KM_PER_DAY * days + drag_km
+    }
 
-        let diff_current = (time.timestamp() - epoch_time.timestamp()).abs();
+    /// How far off (seconds) an AOS or LOS predicted for a time may be, being
+    /// the along-track error divided by the orbital speed
+    pub fn timing_uncertainty_seconds(&self, time: DateTime<Utc>) -> f64 {
+        let speed_km_s = TAU * self.semi_major_axis_km() / (self.period_minutes() * 60.0);
+        self.along_track_uncertainty_km(time) / speed_km_s
+    }
 
-        let epoch_time_prev = year_day_to_datetime(current_year - 1, epoch_day_of_year);
-        let diff_prev = (time.timestamp() - epoch_time_prev.timestamp()).abs();
+    /// Which kind of orbit the elements describe
+    pub fn orbit_class(&self) -> OrbitClass {
+        let apogee_km =
+            self.semi_major_axis_km() * (1.0 + self.elements.eccentricity) - EARTH_RADIUS_KM;
+        OrbitClass::new(self.elements.mean_motion, self.elements.eccentricity, apogee_km)
+    }
 
-        let epoch_time_next = year_day_to_datetime(current_year + 1, epoch_day_of_year);
-        let diff_next = (time.timestamp() - epoch_time_next.timestamp()).abs();
+    fn semi_major_axis_km(&self) -> f64 {
+        (EARTH_MU_KM3_S2 * (self.period_minutes() * 60.0 / TAU).powi(2)).cbrt()
+    }
+
+    /// Positions every `step` from `from` to `to` inclusive, for a ground track table
+    pub fn ground_track(
+        &self,
+        observer: &Observer,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        step: chrono::Duration,
+    ) -> Vec<SatellitePosition> {
+        let mut points = Vec::new();
+        let mut time = from;
+        while time <= to {
+            if let Ok(position) = self.calculate_position(time, observer) {
+                points.push(position);
+            }
+            time += step;
+        }
+        points
+    }
 
-        if diff_prev < diff_current && diff_prev < diff_next {
-            epoch_time = epoch_time_prev;
-        } else if diff_next < diff_current && diff_next < diff_prev {
-            epoch_time = epoch_time_next;
+    /// Highest elevation in each of `columns` equal slices of `span` from
+    /// `from`, sampled every minute, for an at-a-glance outlook
+    pub fn elevation_outlook(
+        &self,
+        observer: &Observer,
+        from: DateTime<Utc>,
+        span: chrono::Duration,
+        columns: usize,
+    ) -> Vec<f64> {
+        let minutes = span.num_minutes().max(1);
+        let mut outlook = vec![-90.0; columns];
+        for minute in 0..=minutes {
+            let time = from + chrono::Duration::minutes(minute);
+            let Ok(position) = self.calculate_position(time, observer) else {
+                continue;
+            };
+            let column = (minute as usize * columns / minutes as usize).min(columns - 1);
+            outlook[column] = f64::max(outlook[column], position.elevation);
         }
+        outlook
+    }
+
+    pub fn get_next_pass(&self) -> Option<&SatellitePass> {
+        let now = Utc::now();
+        self.passes.iter().find(|pass| pass.aos_time > now)
+    }
 
-        let duration = time.signed_duration_since(epoch_time);
-        duration.num_milliseconds() as f64 / 60000.0
+    /// Pass in progress at the given time, if any
+    pub fn get_current_pass(&self, time: DateTime<Utc>) -> Option<&SatellitePass> {
+        self.passes
+            .iter()
+            .find(|pass| pass.aos_time <= time && time < pass.los_time)
     }
 }
 
-fn year_day_to_datetime(year: i32, day_of_year: f64) -> DateTime<Utc> {
-    use chrono::Duration;
-    let year_start = chrono::NaiveDate::from_ymd_opt(year, 1, 1)
-        .unwrap()
-        .and_hms_opt(0, 0, 0)
-        .unwrap()
-        .and_utc();
+/// Indices of `satellites` from most to least worth watching at `now`: those
+/// above the horizon by elevation, then the rest by their next AOS, and last
+/// those with no upcoming pass
+pub fn rank_by_relevance(
+    satellites: &[Satellite],
+    positions: &[SatellitePosition],
+    now: DateTime<Utc>,
+) -> Vec<usize> {
+    let key = |satellite: &Satellite| {
+        let elevation = positions
+            .iter()
+            .find(|pos| pos.name == satellite.name)
+            .map(|pos| pos.elevation)
+            .filter(|elevation| *elevation > 0.0);
+        match elevation {
+            Some(elevation) => (0, -elevation),
+            None => match satellite.passes.iter().find(|pass| pass.aos_time > now) {
+                Some(pass) => (1, (pass.aos_time - now).num_seconds() as f64),
+                None => (2, 0.0),
+            },
+        }
+    };
+
+    let mut ranked: Vec<(usize, (u8, f64))> = satellites
+        .iter()
+        .enumerate()
+        .map(|(index, satellite)| (index, key(satellite)))
+        .collect();
+    ranked.sort_by(|a, b| a.1 .0.cmp(&b.1 .0).then(a.1 .1.total_cmp(&b.1 .1)));
+    ranked.into_iter().map(|(index, _)| index).collect()
+}
 
-    let days_into_year = day_of_year - 1.0;
-    year_start + Duration::milliseconds((days_into_year * 86400000.0) as i64)
+/// Splits a ground track of (latitude, longitude) points wherever it crosses the
+/// antimeridian, ending and starting the pieces at ±180° so a map doesn't draw a
+/// line straight across the world
+pub fn split_ground_track(points: &[(f64, f64)]) -> Vec<Vec<(f64, f64)>> {
+    let mut segments: Vec<Vec<(f64, f64)>> = Vec::new();
+    let mut current: Vec<(f64, f64)> = Vec::new();
+
+    for &(lat, lon) in points {
+        if let Some(&(prev_lat, prev_lon)) = current.last() {
+            let delta = lon - prev_lon;
+            if delta.abs() > 180.0 {
+                // Going east past 180° reappears at -180°, and vice versa
+                let edge = if delta < 0.0 { 180.0 } else { -180.0 };
+                let unwrapped = lon + 2.0 * edge;
+                let t = (edge - prev_lon) / (unwrapped - prev_lon);
+                let crossing_lat = prev_lat + (lat - prev_lat) * t;
+                current.push((crossing_lat, edge));
+                segments.push(std::mem::take(&mut current));
+                current.push((crossing_lat, -edge));
+            }
+        }
+        current.push((lat, lon));
+    }
+    if !current.is_empty() {
+        segments.push(current);
+    }
+    segments
 }
 
-fn eci_to_geodetic(eci: &Vector3<f64>, gmst: f64) -> (f64, f64, f64) {
-    // Convert ECI to ECEF
-    let cos_gmst = gmst.cos();
-    let sin_gmst = gmst.sin();
+/// A ground track as CSV: UTC time, sub-satellite point and grid square, and
+/// where the satellite appears from the observer
+pub fn ground_track_csv(points: &[SatellitePosition]) -> String {
+    let mut csv =
+        String::from("time_utc,latitude,longitude,altitude_km,grid,azimuth,elevation,range_km\n");
+    for point in points {
+        csv.push_str(&format!(
+            "{},{:.4},{:.4},{:.1},{},{:.1},{:.1},{:.1}\n",
+            point.time.format("%Y-%m-%dT%H:%M:%SZ"),
+            point.latitude,
+            point.longitude,
+            point.altitude_km,
+            latlon_to_grid(point.latitude, point.longitude),
+            point.azimuth,
+            point.elevation,
+            point.range_km
+        ));
+    }
+    csv
+}
+
+/// Satellite name as it appears in topics, entity ids and URLs, e.g. "iss_zarya"
+pub fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    slug.trim_end_matches('_').to_string()
+}
 
-    let x = eci.x * cos_gmst + eci.y * sin_gmst;
-    let y = -eci.x * sin_gmst + eci.y * cos_gmst;
-    let z = eci.z;
+fn ecef_to_geodetic(ecef: &Vector3<f64>) -> (f64, f64, f64) {
+    let (x, y, z) = (ecef.x, ecef.y, ecef.z);
 
     // WGS84 parameters
     const A: f64 = 6378.137; // km
@@ -193,20 +381,245 @@ fn eci_to_geodetic(eci: &Vector3<f64>, gmst: f64) -> (f64, f64, f64) {
     // Calculate longitude
     let lon = y.atan2(x);
 
-    // Iteratively calculate latitude
+    // Iteratively calculate latitude; atan2 keeps this defined on the polar axis
     let p = (x * x + y * y).sqrt();
-    let mut lat = (z / p).atan();
+    let mut lat = z.atan2(p * (1.0 - E2));
 
     for _ in 0..5 {
         let sin_lat = lat.sin();
         let n = A / (1.0 - E2 * sin_lat * sin_lat).sqrt();
-        let h = p / lat.cos() - n;
-        lat = (z / p / (1.0 - E2 * n / (n + h))).atan();
+        lat = (z + E2 * n * sin_lat).atan2(p);
     }
 
-    let sin_lat = lat.sin();
+    // Unlike p / cos(lat) - n, this stays accurate near the poles
+    let (sin_lat, cos_lat) = lat.sin_cos();
     let n = A / (1.0 - E2 * sin_lat * sin_lat).sqrt();
-    let alt = p / lat.cos() - n;
+    let alt = p * cos_lat + z * sin_lat - A * A / n;
+
+    (lat.to_degrees(), normalize_longitude(lon.to_degrees()), alt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::{sample_pass, sample_satellite};
+    use chrono::TimeZone;
+    use proptest::prelude::*;
+
+    fn satellite(name: &str, line1: &str, line2: &str) -> Satellite {
+        let elements =
+            Elements::from_tle(Some(name.to_string()), line1.as_bytes(), line2.as_bytes()).unwrap();
+        let epoch = elements.datetime.and_utc();
+        Satellite::new(name.to_string(), elements, epoch, line1.to_string(), line2.to_string())
+    }
+
+    fn angular_separation(a: &SatellitePosition, b: &SatellitePosition) -> f64 {
+        let unit = |pos: &SatellitePosition| {
+            let (az, el) = (pos.azimuth.to_radians(), pos.elevation.to_radians());
+            Vector3::new(el.cos() * az.sin(), el.cos() * az.cos(), el.sin())
+        };
+        unit(a).dot(&unit(b)).clamp(-1.0, 1.0).acos().to_degrees()
+    }
+
+    #[test]
+    fn test_light_time_correction_leo() {
+        let iss = sample_satellite("ISS (ZARYA)");
+        let mut observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+        let time = Utc.with_ymd_and_hms(2008, 9, 20, 12, 30, 0).unwrap();
+
+        let geometric = iss.calculate_position(time, &observer).unwrap();
+        observer.light_time_correction = true;
+        let corrected = iss.calculate_position(time, &observer).unwrap();
+
+        // The range shrinks or grows by how far the satellite moved along the
+        // line of sight during the light-time
+        let light_time_s = geometric.range_km / SPEED_OF_LIGHT_KM_S;
+        let expected_range = geometric.range_km - geometric.range_rate_km_s * light_time_s;
+        assert!(light_time_s > 0.001);
+        assert!((corrected.range_km - expected_range).abs() < 0.005);
+
+        // A few arcseconds: enough to matter only for very narrow beams
+        let shift = angular_separation(&geometric, &corrected);
+        assert!(shift > 1e-5 && shift < 0.01, "shift {}°", shift);
+        assert!((corrected.range_rate_km_s - geometric.range_rate_km_s).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_light_time_correction_geostationary() {
+        let goes = satellite(
+            "GOES 16",
+            "1 41866U 16071A   24060.50000000 -.00000096  00000-0  00000+0 0  9992",
+            "2 41866   0.0301 279.5800 0000962 314.6000 150.2000  1.00271000 26000",
+        );
+        let mut observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+        let time = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+
+        let geometric = goes.calculate_position(time, &observer).unwrap();
+        observer.light_time_correction = true;
+        let corrected = goes.calculate_position(time, &observer).unwrap();
+
+        // During the 0.14 s light-time the satellite travels ~430 m in inertial
+        // space but the observer only ~50 m, so a geostationary satellite
+        // appears about two arcseconds behind its geometric position
+        assert!(geometric.range_km / SPEED_OF_LIGHT_KM_S > 0.1);
+        let shift = angular_separation(&geometric, &corrected);
+        assert!(shift > 3e-4 && shift < 8e-4, "shift {}°", shift);
+        assert!((corrected.range_km - geometric.range_km).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_ground_track() {
+        let iss = sample_satellite("ISS (ZARYA)");
+        assert!((iss.period_minutes() - 91.6).abs() < 0.1);
+
+        // At the TLE epoch the argument of latitude (perigee 130.5° plus mean
+        // anomaly 325.0°) is about 95.6°, so the ISS is just past its most
+        // northerly point, near 51.4°N
+        let observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+        let at_epoch = iss
+            .calculate_position(iss.elements.datetime.and_utc(), &observer)
+            .unwrap();
+        assert!((at_epoch.latitude - 51.4).abs() < 0.3, "{}", at_epoch.latitude);
+
+        let from = Utc.with_ymd_and_hms(2008, 9, 20, 12, 0, 0).unwrap();
+        let to = from + chrono::Duration::minutes(10);
+        let track = iss.ground_track(&observer, from, to, chrono::Duration::minutes(1));
+        assert_eq!(track.len(), 11);
+        assert!(track.iter().all(|point| point.latitude.abs() <= 51.7));
+        assert!(track.iter().all(|point| (330.0..370.0).contains(&point.altitude_km)));
+
+        let csv = ground_track_csv(&track);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 12);
+        assert!(lines[0].starts_with("time_utc,latitude,longitude"));
+        assert!(lines[1].starts_with("2008-09-20T12:00:00Z,"));
+        assert_eq!(lines[1].split(',').count(), 8);
+    }
+
+    #[test]
+    fn test_elevation_outlook() {
+        let iss = sample_satellite("ISS (ZARYA)");
+        let observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+        let from = Utc.with_ymd_and_hms(2008, 9, 20, 12, 0, 0).unwrap();
+        let outlook = iss.elevation_outlook(&observer, from, chrono::Duration::minutes(60), 15);
+        assert_eq!(outlook.len(), 15);
+        // Each column is the highest of its four minutes
+        let points = iss.ground_track(
+            &observer,
+            from + chrono::Duration::minutes(4),
+            from + chrono::Duration::minutes(7),
+            chrono::Duration::minutes(1),
+        );
+        let highest = points.iter().map(|p| p.elevation).fold(f64::MIN, f64::max);
+        assert!((outlook[1] - highest).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_propagation_uncertainty() {
+        let iss = sample_satellite("ISS (ZARYA)");
+        assert!((iss.semi_major_axis_km() - 6730.0).abs() < 2.0);
+        assert_eq!(iss.along_track_uncertainty_km(iss.epoch), 1.0);
+
+        // Ten days out: 20 km from age and about 18 km from drag, either side
+        let later = iss.along_track_uncertainty_km(iss.epoch + chrono::Duration::days(10));
+        let earlier = iss.along_track_uncertainty_km(iss.epoch - chrono::Duration::days(10));
+        assert!((later - 39.4).abs() < 0.5, "{}", later);
+        assert_eq!(later, earlier);
+        // At 7.7 km/s that is about five seconds of AOS
+        let seconds = iss.timing_uncertainty_seconds(iss.epoch + chrono::Duration::days(10));
+        assert!((seconds - 5.1).abs() < 0.2, "{}", seconds);
+    }
+
+    #[test]
+    fn test_rank_by_relevance() {
+        let now = Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 0).unwrap();
+        let pass_at = |minutes: i64| {
+            let aos = now + chrono::Duration::minutes(minutes);
+            sample_pass("ISS", aos, aos + chrono::Duration::minutes(10), 45.0).1
+        };
+        let with_pass = |name: &str, minutes: Option<i64>| {
+            let mut sat = sample_satellite(name);
+            sat.passes = minutes.into_iter().map(pass_at).collect();
+            sat
+        };
+        let satellites = vec![
+            with_pass("NONE", None),
+            with_pass("LATER", Some(90)),
+            with_pass("LOW", Some(-3)),
+            with_pass("SOON", Some(5)),
+            with_pass("HIGH", Some(-5)),
+        ];
+
+        let iss = sample_satellite("ISS");
+        let observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+        let position = |name: &str, elevation: f64| SatellitePosition {
+            name: name.to_string(),
+            elevation,
+            ..iss.calculate_position(now, &observer).unwrap()
+        };
+        let positions = vec![
+            position("NONE", -40.0),
+            position("LATER", -20.0),
+            position("LOW", 12.0),
+            position("SOON", -1.0),
+            position("HIGH", 60.0),
+        ];
+
+        assert_eq!(rank_by_relevance(&satellites, &positions, now), vec![4, 2, 3, 1, 0]);
+    }
+
+    #[test]
+    fn test_split_ground_track() {
+        // Eastbound over the date line
+        let track = [(10.0, 170.0), (12.0, 178.0), (14.0, -174.0), (16.0, -166.0)];
+        let segments = split_ground_track(&track);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].last().unwrap(), &(12.5, 180.0));
+        assert_eq!(segments[1][0], (12.5, -180.0));
+        assert_eq!(segments[1].len(), 3);
+
+        // Westbound
+        let segments = split_ground_track(&[(0.0, -175.0), (-2.0, 175.0)]);
+        assert_eq!(
+            segments,
+            vec![
+                vec![(0.0, -175.0), (-1.0, -180.0)],
+                vec![(-1.0, 180.0), (-2.0, 175.0)]
+            ]
+        );
+
+        // Over the pole longitude jumps by ~180° but nothing crosses the date line
+        let segments = split_ground_track(&[(89.0, 10.0), (89.0, -170.0), (88.0, -169.0)]);
+        assert_eq!(segments.len(), 1);
+
+        assert!(split_ground_track(&[]).is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_geodetic_round_trip(
+            lat in -90.0..=90.0f64,
+            lon in -180.0..=180.0f64,
+            alt in -500.0..4e7f64,
+        ) {
+            let ecef = Observer::new("Test".to_string(), lat, lon, alt).to_ecef();
+            let (lat2, lon2, alt_km) = ecef_to_geodetic(&(ecef / 1000.0));
+
+            prop_assert!((lat2 - lat).abs() < 1e-7, "latitude {} -> {}", lat, lat2);
+            prop_assert!((alt_km * 1000.0 - alt).abs() < 1e-3, "altitude {} -> {}", alt, alt_km);
+            prop_assert!((-180.0..180.0).contains(&lon2));
+            // Longitude is meaningless at the poles, and 180 and -180 are the same place
+            if lat.abs() < 89.999 {
+                let diff = (lon2 - lon).rem_euclid(360.0);
+                prop_assert!(diff.min(360.0 - diff) < 1e-7, "longitude {} -> {}", lon, lon2);
+            }
+        }
 
-    (lat.to_degrees(), lon.to_degrees(), alt)
+        #[test]
+        fn prop_geodetic_on_polar_axis(z in prop_oneof![-5e4..-6000.0f64, 6000.0..5e4f64]) {
+            let (lat, _, alt) = ecef_to_geodetic(&Vector3::new(0.0, 0.0, z));
+            prop_assert_eq!(lat, 90.0f64.copysign(z));
+            prop_assert!((alt - (z.abs() - 6_356.752_314_245)).abs() < 1e-6, "altitude {}", alt);
+        }
+    }
 }
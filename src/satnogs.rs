@@ -0,0 +1,275 @@
+use std::io::Read;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::celestrak;
+use crate::config::SatnogsConfig;
+use crate::satellite::Satellite;
+
+/// How far ahead predicted passes are listed next to scheduled observations
+pub const SCHEDULE_WINDOW_HOURS: i64 = 24;
+
+/// An observation scheduled on the SatNOGS Network
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    pub id: u64,
+    pub norad_id: u32,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub transmitter: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawObservation {
+    id: u64,
+    norad_cat_id: u32,
+    start: String,
+    end: String,
+    #[serde(default)]
+    transmitter_description: Option<String>,
+}
+
+/// A transmitter listed for a satellite in SatNOGS DB
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Transmitter {
+    pub uuid: String,
+    pub description: String,
+    #[serde(default)]
+    pub alive: bool,
+    #[serde(default)]
+    pub status: String, // "active", "inactive" or "invalid"
+    #[serde(default)]
+    pub downlink_low: Option<u64>, // Hz
+}
+
+/// A predicted pass or a scheduled observation, listed by start time
+#[derive(Debug, Clone)]
+pub struct ScheduleRow {
+    pub satellite_name: String,
+    pub norad_id: Option<u32>,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub max_elevation: Option<f64>, // None for observations of untracked satellites
+    pub observation: Option<Observation>,
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .with_context(|| format!("invalid time '{}'", value))?
+        .with_timezone(&Utc))
+}
+
+/// Parse the JSON list returned by the Network observations endpoint
+pub fn parse_observations(json: &str) -> Result<Vec<Observation>> {
+    let raw: Vec<RawObservation> = serde_json::from_str(json)?;
+    raw.into_iter()
+        .map(|obs| {
+            Ok(Observation {
+                id: obs.id,
+                norad_id: obs.norad_cat_id,
+                start: parse_time(&obs.start)?,
+                end: parse_time(&obs.end)?,
+                transmitter: obs.transmitter_description.unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Best transmitter to observe: alive and active, preferring ones with a downlink
+pub fn pick_transmitter(transmitters: &[Transmitter]) -> Option<&Transmitter> {
+    transmitters
+        .iter()
+        .filter(|t| t.alive && t.status == "active")
+        .max_by_key(|t| t.downlink_low.is_some())
+}
+
+/// Body of a request scheduling one observation
+pub fn observation_request(
+    ground_station: u32,
+    transmitter_uuid: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> String {
+    serde_json::json!([{
+        "ground_station": ground_station,
+        "transmitter_uuid": transmitter_uuid,
+        "start": start.format("%Y-%m-%d %H:%M:%S").to_string(),
+        "end": end.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }])
+    .to_string()
+}
+
+fn get(url: &str, config: &SatnogsConfig) -> Result<String> {
+    let mut request = ureq::get(url).timeout(std::time::Duration::from_secs(30));
+    if let Some(token) = &config.api_token {
+        request = request.set("Authorization", &format!("Token {}", token));
+    }
+    let response = request
+        .call()
+        .map_err(|e| anyhow!("HTTP request failed: {}", e))?;
+
+    let mut body = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| anyhow!("Failed to read response: {}", e))?;
+    Ok(body)
+}
+
+/// Future observations scheduled on the configured ground station
+pub fn fetch_observations(config: &SatnogsConfig) -> Result<Vec<Observation>> {
+    let url = format!(
+        "{}/observations/?ground_station={}&status=future&format=json",
+        config.network_url, config.ground_station
+    );
+    let mut observations = parse_observations(&get(&url, config)?)?;
+    observations.sort_by_key(|obs| obs.start);
+    Ok(observations)
+}
+
+/// Transmitters SatNOGS DB knows for a satellite
+pub fn fetch_transmitters(config: &SatnogsConfig, norad_id: u32) -> Result<Vec<Transmitter>> {
+    let url = format!(
+        "{}/transmitters/?satellite__norad_cat_id={}&format=json",
+        config.db_url, norad_id
+    );
+    Ok(serde_json::from_str(&get(&url, config)?)?)
+}
+
+/// Schedule an observation of a pass on the configured ground station
+pub fn schedule_observation(
+    config: &SatnogsConfig,
+    norad_id: u32,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<String> {
+    let token = config
+        .api_token
+        .as_deref()
+        .context("scheduling needs api_token in [satnogs]")?;
+    let transmitters = fetch_transmitters(config, norad_id)?;
+    let transmitter = pick_transmitter(&transmitters)
+        .with_context(|| format!("no active transmitter for NORAD {} in SatNOGS DB", norad_id))?;
+
+    ureq::post(&format!("{}/observations/", config.network_url))
+        .timeout(std::time::Duration::from_secs(30))
+        .set("Authorization", &format!("Token {}", token))
+        .set("Content-Type", "application/json")
+        .send_string(&observation_request(
+            config.ground_station,
+            &transmitter.uuid,
+            start,
+            end,
+        ))
+        .map_err(|e| anyhow!("SatNOGS rejected the observation: {}", e))?;
+    Ok(transmitter.description.clone())
+}
+
+/// Predicted passes over the next day next to the station's scheduled observations
+pub fn schedule_rows(
+    satellites: &[Satellite],
+    observations: &[Observation],
+    now: DateTime<Utc>,
+) -> Vec<ScheduleRow> {
+    let horizon = now + Duration::hours(SCHEDULE_WINDOW_HOURS);
+    let mut rows: Vec<ScheduleRow> = Vec::new();
+
+    for satellite in satellites {
+        let norad_id = celestrak::norad_id(&satellite.tle_line1);
+        for pass in satellite
+            .passes
+            .iter()
+            .filter(|p| p.los_time > now && p.aos_time < horizon)
+        {
+            let observation = observations.iter().find(|obs| {
+                Some(obs.norad_id) == norad_id
+                    && obs.start < pass.los_time
+                    && obs.end > pass.aos_time
+            });
+            rows.push(ScheduleRow {
+                satellite_name: satellite.name.clone(),
+                norad_id,
+                start: pass.aos_time,
+                end: pass.los_time,
+                max_elevation: Some(pass.max_elevation),
+                observation: observation.cloned(),
+            });
+        }
+    }
+
+    // Observations of satellites crabtrack isn't tracking
+    for obs in observations {
+        if !rows.iter().any(|row| row.observation.as_ref() == Some(obs)) {
+            rows.push(ScheduleRow {
+                satellite_name: format!("NORAD {}", obs.norad_id),
+                norad_id: Some(obs.norad_id),
+                start: obs.start,
+                end: obs.end,
+                max_elevation: None,
+                observation: Some(obs.clone()),
+            });
+        }
+    }
+
+    rows.sort_by_key(|row| row.start);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const OBSERVATIONS: &str = r#"[
+        {"id": 9876543, "start": "2024-03-01T10:02:00Z", "end": "2024-03-01T10:12:00Z",
+         "ground_station": 1234, "norad_cat_id": 43017, "status": "future",
+         "transmitter_description": "Mode U/V FM"},
+        {"id": 9876544, "start": "2024-03-01T12:00:00Z", "end": "2024-03-01T12:08:00Z",
+         "ground_station": 1234, "norad_cat_id": 25544, "status": "future"}
+    ]"#;
+
+    #[test]
+    fn test_parse_observations() {
+        let observations = parse_observations(OBSERVATIONS).unwrap();
+        assert_eq!(observations.len(), 2);
+        assert_eq!(observations[0].id, 9876543);
+        assert_eq!(observations[0].norad_id, 43017);
+        assert_eq!(
+            observations[0].start,
+            Utc.with_ymd_and_hms(2024, 3, 1, 10, 2, 0).unwrap()
+        );
+        assert_eq!(observations[0].transmitter, "Mode U/V FM");
+        assert_eq!(observations[1].transmitter, "");
+        assert!(parse_observations("{\"detail\": \"Invalid token.\"}").is_err());
+    }
+
+    #[test]
+    fn test_pick_transmitter() {
+        let transmitters: Vec<Transmitter> = serde_json::from_str(
+            r#"[
+                {"uuid": "a", "description": "Beacon", "alive": false, "status": "active",
+                 "downlink_low": 145900000},
+                {"uuid": "b", "description": "Uplink only", "alive": true, "status": "active",
+                 "downlink_low": null},
+                {"uuid": "c", "description": "FM", "alive": true, "status": "active",
+                 "downlink_low": 435350000}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(pick_transmitter(&transmitters).unwrap().uuid, "c");
+        assert!(pick_transmitter(&transmitters[..1]).is_none());
+    }
+
+    #[test]
+    fn test_observation_request() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 10, 2, 0).unwrap();
+        let body = observation_request(1234, "abc", start, start + Duration::minutes(10));
+        let value: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(value[0]["ground_station"], 1234);
+        assert_eq!(value[0]["transmitter_uuid"], "abc");
+        assert_eq!(value[0]["start"], "2024-03-01 10:02:00");
+        assert_eq!(value[0]["end"], "2024-03-01 10:12:00");
+    }
+}
@@ -0,0 +1,287 @@
+//! Operating schedules: when and where a satellite's transponder is known to
+//! be switched on, such as "weekends over Europe". A satellite without rules
+//! is always on; one with rules is on whenever any of them matches, judged by
+//! the day and time in UTC and the point on the ground under the satellite.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc, Weekday};
+use std::collections::HashMap;
+
+use crate::database::OperatingRule;
+use crate::observer::{normalize_longitude, Observer};
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::Satellite;
+
+/// How often a pass is checked against the rules
+const PASS_STEP_SECONDS: i64 = 30;
+
+/// Box the sub-satellite point must be in. Longitudes run east from `west`
+/// to `east`, so a box may straddle 180°.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Region {
+    pub south: f64,
+    pub north: f64,
+    pub west: f64,
+    pub east: f64,
+}
+
+impl Region {
+    fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        if latitude < self.south || latitude > self.north {
+            return false;
+        }
+        let longitude = normalize_longitude(longitude);
+        if self.west <= self.east {
+            (self.west..=self.east).contains(&longitude)
+        } else {
+            longitude >= self.west || longitude <= self.east
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    days: Vec<Weekday>, // empty for every day
+    hours: Option<(NaiveTime, NaiveTime)>,
+    region: Option<Region>,
+}
+
+impl Rule {
+    fn parse(record: &OperatingRule) -> Result<Self> {
+        Ok(Self {
+            days: match &record.days {
+                Some(days) => parse_days(days)?,
+                None => Vec::new(),
+            },
+            hours: record.hours.as_deref().map(parse_hours).transpose()?,
+            region: record.region.as_deref().map(parse_region).transpose()?,
+        })
+    }
+
+    fn matches(&self, time: DateTime<Utc>, latitude: f64, longitude: f64) -> bool {
+        if !self.days.is_empty() && !self.days.contains(&time.weekday()) {
+            return false;
+        }
+        if let Some((start, end)) = self.hours {
+            let now = time.time();
+            // A window such as 22:00-04:00 runs past midnight
+            let within = match start <= end {
+                true => start <= now && now < end,
+                false => now >= start || now < end,
+            };
+            if !within {
+                return false;
+            }
+        }
+        self.region
+            .is_none_or(|region| region.contains(latitude, longitude))
+    }
+}
+
+/// Days such as "Sat,Sun" or "Mon-Fri"
+pub fn parse_days(text: &str) -> Result<Vec<Weekday>> {
+    let day = |name: &str| {
+        name.trim()
+            .parse::<Weekday>()
+            .map_err(|_| anyhow!("'{}' is not a day of the week", name.trim()))
+    };
+    let mut days = Vec::new();
+    for part in text.split(',') {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (mut current, last) = (day(first)?, day(last)?);
+                days.push(current);
+                while current != last {
+                    current = current.succ();
+                    days.push(current);
+                }
+            }
+            None => days.push(day(part)?),
+        }
+    }
+    Ok(days)
+}
+
+/// UTC hours such as "06:00-18:00"
+pub fn parse_hours(text: &str) -> Result<(NaiveTime, NaiveTime)> {
+    let (start, end) = text
+        .split_once('-')
+        .ok_or_else(|| anyhow!("hours must look like 06:00-18:00"))?;
+    let time = |value: &str| {
+        NaiveTime::parse_from_str(value.trim(), "%H:%M")
+            .with_context(|| format!("'{}' is not a time like 06:00", value.trim()))
+    };
+    Ok((time(start)?, time(end)?))
+}
+
+/// A region as "south,north,west,east" in degrees
+pub fn parse_region(text: &str) -> Result<Region> {
+    let values = text
+        .split(',')
+        .map(|value| value.trim().parse::<f64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| anyhow!("region must be four numbers: south,north,west,east"))?;
+    let [south, north, west, east] = values[..] else {
+        return Err(anyhow!(
+            "region must be four numbers: south,north,west,east"
+        ));
+    };
+    if !(-90.0..=90.0).contains(&south) || !(-90.0..=90.0).contains(&north) || south > north {
+        return Err(anyhow!(
+            "region latitudes must run south to north within ±90°"
+        ));
+    }
+    Ok(Region {
+        south,
+        north,
+        west: normalize_longitude(west),
+        east: normalize_longitude(east),
+    })
+}
+
+/// Every satellite's rules
+#[derive(Debug, Default)]
+pub struct Schedule {
+    rules: HashMap<String, Vec<Rule>>,
+}
+
+impl Schedule {
+    pub fn new(records: &[OperatingRule]) -> Result<Self> {
+        let mut rules: HashMap<String, Vec<Rule>> = HashMap::new();
+        for record in records {
+            let rule = Rule::parse(record)
+                .with_context(|| format!("schedule rule {:?}", record.id.unwrap_or_default()))?;
+            rules
+                .entry(record.satellite_name.clone())
+                .or_default()
+                .push(rule);
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether the satellite is on at `time` over the given point
+    pub fn is_operating(
+        &self,
+        satellite_name: &str,
+        time: DateTime<Utc>,
+        latitude: f64,
+        longitude: f64,
+    ) -> bool {
+        self.rules.get(satellite_name).is_none_or(|rules| {
+            rules
+                .iter()
+                .any(|rule| rule.matches(time, latitude, longitude))
+        })
+    }
+
+    /// Whether the satellite is on for at least part of the pass
+    pub fn pass_operating(
+        &self,
+        satellite: &Satellite,
+        pass: &SatellitePass,
+        observer: &Observer,
+    ) -> bool {
+        if !self.rules.contains_key(&satellite.name) {
+            return true;
+        }
+        let mut time = pass.aos_time;
+        while time <= pass.los_time {
+            if let Ok(position) = satellite.calculate_position(time, observer) {
+                if self.is_operating(&satellite.name, time, position.latitude, position.longitude) {
+                    return true;
+                }
+            }
+            time += Duration::seconds(PASS_STEP_SECONDS);
+        }
+        false
+    }
+}
+
+/// A stored rule in words, for listing
+pub fn describe(record: &OperatingRule) -> String {
+    let mut parts = Vec::new();
+    if let Some(days) = &record.days {
+        parts.push(format!("on {}", days));
+    }
+    if let Some(hours) = &record.hours {
+        parts.push(format!("{} UTC", hours));
+    }
+    if let Some(region) = &record.region {
+        parts.push(format!("over {}", region));
+    }
+    let mut text = parts.join(", ");
+    if !record.note.is_empty() {
+        text.push_str(&format!(" ({})", record.note));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record(days: Option<&str>, hours: Option<&str>, region: Option<&str>) -> OperatingRule {
+        OperatingRule {
+            id: None,
+            satellite_name: "SAT".to_string(),
+            days: days.map(str::to_string),
+            hours: hours.map(str::to_string),
+            region: region.map(str::to_string),
+            note: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse_days("Mon-Wed, sat").unwrap(),
+            vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Sat]
+        );
+        assert_eq!(parse_days("Fri-Mon").unwrap().len(), 4);
+        assert!(parse_days("Funday").is_err());
+        assert!(parse_hours("6-18").is_err());
+        assert!(parse_region("35,72,-25").is_err());
+        assert!(parse_region("72,35,-25,45").is_err());
+        assert_eq!(
+            parse_region("35, 72, 170, -170").unwrap(),
+            Region {
+                south: 35.0,
+                north: 72.0,
+                west: 170.0,
+                east: -170.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_schedule() {
+        // Weekends over Europe, plus weeknights anywhere
+        let schedule = Schedule::new(&[
+            record(Some("Sat,Sun"), None, Some("35,72,-25,45")),
+            record(Some("Mon-Fri"), Some("22:00-04:00"), None),
+        ])
+        .unwrap();
+        let saturday = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
+        let monday_noon = Utc.with_ymd_and_hms(2026, 4, 27, 12, 0, 0).unwrap();
+        let tuesday_early = Utc.with_ymd_and_hms(2026, 4, 28, 2, 30, 0).unwrap();
+
+        assert!(schedule.is_operating("SAT", saturday, 50.0, 10.0));
+        assert!(!schedule.is_operating("SAT", saturday, 40.0, -75.0));
+        assert!(!schedule.is_operating("SAT", monday_noon, 50.0, 10.0));
+        assert!(schedule.is_operating("SAT", tuesday_early, 40.0, -75.0));
+        // No rules, always on
+        assert!(schedule.is_operating("OTHER", monday_noon, 0.0, 0.0));
+
+        let pacific = Schedule::new(&[record(None, None, Some("-10,10,170,-170"))]).unwrap();
+        assert!(pacific.is_operating("SAT", saturday, 0.0, 179.0));
+        assert!(pacific.is_operating("SAT", saturday, 0.0, -175.0));
+        assert!(!pacific.is_operating("SAT", saturday, 0.0, 160.0));
+
+        assert!(Schedule::new(&[record(Some("Someday"), None, None)]).is_err());
+        assert_eq!(
+            describe(&record(Some("Sat,Sun"), Some("06:00-18:00"), None)),
+            "on Sat,Sun, 06:00-18:00 UTC"
+        );
+    }
+}
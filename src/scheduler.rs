@@ -0,0 +1,119 @@
+//! Deciding between passes that overlap in time.
+//!
+//! A station usually has one rotator and one radio for satellite work, so two
+//! passes that are above the horizon at the same time conflict. The alerts
+//! show each conflict, and auto-follow keeps the rotator on the pass that
+//! wins it: the satellite listed first in `[alerts] priority`, or failing
+//! that the one whose pass culminates higher.
+
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+
+use crate::pass_prediction::SatellitePass;
+
+/// Two passes that overlap in time, the preferred one first
+#[derive(Debug, Clone)]
+pub struct PassConflict {
+    pub winner: String,
+    pub winner_pass: SatellitePass,
+    pub loser: String,
+    pub loser_pass: SatellitePass,
+    pub by_priority: bool, // decided by the priority list rather than by elevation
+}
+
+/// Orders two passes by the priority list (earlier is preferred, unlisted
+/// satellites come last) and then by maximum elevation
+fn compare(
+    priority: &[String],
+    a: (&str, &SatellitePass),
+    b: (&str, &SatellitePass),
+) -> (Ordering, bool) {
+    let rank = |name: &str| {
+        priority
+            .iter()
+            .position(|listed| listed == name)
+            .unwrap_or(usize::MAX)
+    };
+    match rank(a.0).cmp(&rank(b.0)) {
+        Ordering::Equal => (b.1.max_elevation.total_cmp(&a.1.max_elevation), false),
+        ordering => (ordering, true),
+    }
+}
+
+/// Every pair of `passes` (satellite name and pass) that are up at the same time
+pub fn find_conflicts(
+    passes: &[(String, SatellitePass)],
+    priority: &[String],
+) -> Vec<PassConflict> {
+    let mut conflicts = Vec::new();
+    for (i, (name_a, pass_a)) in passes.iter().enumerate() {
+        for (name_b, pass_b) in &passes[i + 1..] {
+            if name_a == name_b
+                || pass_a.aos_time >= pass_b.los_time
+                || pass_b.aos_time >= pass_a.los_time
+            {
+                continue;
+            }
+            let (ordering, by_priority) = compare(priority, (name_a, pass_a), (name_b, pass_b));
+            let ((winner, winner_pass), (loser, loser_pass)) = if ordering == Ordering::Greater {
+                ((name_b, pass_b), (name_a, pass_a))
+            } else {
+                ((name_a, pass_a), (name_b, pass_b))
+            };
+            conflicts.push(PassConflict {
+                winner: winner.clone(),
+                winner_pass: winner_pass.clone(),
+                loser: loser.clone(),
+                loser_pass: loser_pass.clone(),
+                by_priority,
+            });
+        }
+    }
+    conflicts.sort_by_key(|conflict| {
+        conflict
+            .winner_pass
+            .aos_time
+            .min(conflict.loser_pass.aos_time)
+    });
+    conflicts
+}
+
+/// Whether `satellite` should give way, having lost a conflict to a pass that isn't over yet
+pub fn yields(conflicts: &[PassConflict], satellite: &str, now: DateTime<Utc>) -> bool {
+    conflicts
+        .iter()
+        .any(|conflict| conflict.loser == satellite && conflict.winner_pass.los_time > now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::sample_pass;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_find_conflicts() {
+        let t0 = Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 0).unwrap();
+        let passes = vec![
+            sample_pass("ISS (ZARYA)", t0, t0 + Duration::minutes(10), 35.0),
+            sample_pass("SO-50", t0 + Duration::minutes(8), t0 + Duration::minutes(20), 60.0),
+            sample_pass("AO-91", t0 + Duration::minutes(20), t0 + Duration::minutes(30), 80.0),
+        ];
+
+        // Only ISS and SO-50 overlap; SO-50 ends as AO-91 rises. Higher wins.
+        let conflicts = find_conflicts(&passes, &[]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].winner, "SO-50");
+        assert_eq!(conflicts[0].loser, "ISS (ZARYA)");
+        assert!(!conflicts[0].by_priority);
+
+        // A listed satellite beats a higher unlisted one
+        let conflicts = find_conflicts(&passes, &["ISS (ZARYA)".to_string()]);
+        assert_eq!(conflicts[0].winner, "ISS (ZARYA)");
+        assert!(conflicts[0].by_priority);
+
+        assert!(yields(&conflicts, "SO-50", t0 + Duration::minutes(9)));
+        assert!(!yields(&conflicts, "SO-50", t0 + Duration::minutes(10)));
+        assert!(!yields(&conflicts, "ISS (ZARYA)", t0));
+    }
+}
@@ -0,0 +1,320 @@
+//! Event scripts in Rhai for station logic the settings can't express, such as
+//! "only rotate if max elevation > 25 and the band is 70cm". A script defines
+//! any of `on_aos(event)`, `on_los(event)` and `on_alert(event)`; each event
+//! is a map of the satellite's metadata, position and pass, and the script
+//! acts through `track(name)`, `stop()`, `select(name)` and `announce(text)`.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Local, Utc};
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::{Satellite, SatellitePosition};
+
+/// Keeps a runaway loop in a script from freezing the tracker
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Lines of script output and errors kept for display
+const LOG_LINES: usize = 20;
+
+/// Something a script asked for, carried out by the caller
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Track(String), // select the satellite and start the rotators
+    Stop,
+    Select(String),
+    Announce(String),
+}
+
+struct Script {
+    path: PathBuf,
+    ast: AST,
+    scope: Scope<'static>,
+}
+
+/// Loaded scripts and the event state they are called from
+pub struct ScriptHost {
+    engine: Engine,
+    scripts: Vec<Script>,
+    actions: Arc<Mutex<Vec<Action>>>,
+    output: Arc<Mutex<Vec<String>>>,
+    pub log: VecDeque<String>, // recent output and errors, newest last
+    last_elevation: HashMap<String, f64>,
+    alerted: HashSet<(String, DateTime<Utc>)>,
+}
+
+fn pass_fields(map: &mut Map, pass: &SatellitePass) {
+    map.insert("aos".into(), pass.aos_time.to_rfc3339().into());
+    map.insert("los".into(), pass.los_time.to_rfc3339().into());
+    map.insert("max_elevation".into(), pass.max_elevation.into());
+    map.insert(
+        "max_elevation_time".into(),
+        pass.max_elevation_time.to_rfc3339().into(),
+    );
+    map.insert("aos_azimuth".into(), pass.aos_azimuth.into());
+    map.insert("los_azimuth".into(), pass.los_azimuth.into());
+    map.insert("duration_minutes".into(), pass.duration_minutes().into());
+    map.insert("visible".into(), pass.is_visual().into());
+}
+
+fn position_fields(map: &mut Map, position: &SatellitePosition) {
+    map.insert("azimuth".into(), position.azimuth.into());
+    map.insert("elevation".into(), position.elevation.into());
+    map.insert("range_km".into(), position.range_km.into());
+    map.insert("range_rate_km_s".into(), position.range_rate_km_s.into());
+    map.insert("latitude".into(), position.latitude.into());
+    map.insert("longitude".into(), position.longitude.into());
+    map.insert("altitude_km".into(), position.altitude_km.into());
+}
+
+impl ScriptHost {
+    /// Compile `paths` and run their top-level statements once
+    pub fn load(paths: &[PathBuf]) -> Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+
+        let actions = Arc::new(Mutex::new(Vec::new()));
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let push = |actions: &Arc<Mutex<Vec<Action>>>| {
+            let actions = actions.clone();
+            move |action: Action| actions.lock().unwrap().push(action)
+        };
+        let track = push(&actions);
+        engine.register_fn("track", move |name: &str| track(Action::Track(name.into())));
+        let stop = push(&actions);
+        engine.register_fn("stop", move || stop(Action::Stop));
+        let select = push(&actions);
+        engine.register_fn("select", move |name: &str| {
+            select(Action::Select(name.into()))
+        });
+        let announce = push(&actions);
+        engine.register_fn("announce", move |text: &str| {
+            announce(Action::Announce(text.into()))
+        });
+        let printed = output.clone();
+        engine.on_print(move |text| printed.lock().unwrap().push(text.to_string()));
+
+        let mut scripts = Vec::new();
+        for path in paths {
+            let ast = engine
+                .compile_file(path.clone())
+                .map_err(|e| anyhow!("script {}: {}", path.display(), e))?;
+            let mut scope = Scope::new();
+            engine
+                .run_ast_with_scope(&mut scope, &ast)
+                .map_err(|e| anyhow!("script {}: {}", path.display(), e))?;
+            scripts.push(Script {
+                path: path.clone(),
+                ast,
+                scope,
+            });
+        }
+
+        Ok(Self {
+            engine,
+            scripts,
+            actions,
+            output,
+            log: VecDeque::new(),
+            last_elevation: HashMap::new(),
+            alerted: HashSet::new(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    /// Add a line to the log shown in the alerts panel
+    pub fn push_log(&mut self, line: String) {
+        let time = Local::now().format("%H:%M:%S");
+        self.log.push_back(format!("{} {}", time, line));
+        while self.log.len() > LOG_LINES {
+            self.log.pop_front();
+        }
+    }
+
+    /// Call `function` in every script that defines it
+    fn call(&mut self, function: &str, event: &Map) {
+        let mut errors = Vec::new();
+        for script in self.scripts.iter_mut() {
+            if !script.ast.iter_functions().any(|f| f.name == function) {
+                continue;
+            }
+            let result = self.engine.call_fn_with_options::<Dynamic>(
+                CallFnOptions::new().eval_ast(false),
+                &mut script.scope,
+                &script.ast,
+                function,
+                (Dynamic::from_map(event.clone()),),
+            );
+            if let Err(e) = result {
+                errors.push(format!("{} in {}: {}", function, script.path.display(), e));
+            }
+        }
+        let printed: Vec<String> = self.output.lock().unwrap().drain(..).collect();
+        for line in printed.into_iter().chain(errors) {
+            self.push_log(line);
+        }
+    }
+
+    /// Call the handlers for AOS, LOS and new alerts since the last update,
+    /// returning what the scripts asked for. `metadata` describes a satellite
+    /// (bands, groups, frequencies) for its events.
+    pub fn update(
+        &mut self,
+        satellites: &[Satellite],
+        positions: &[SatellitePosition],
+        alerts: &[(String, SatellitePass)],
+        metadata: impl Fn(&Satellite) -> Map,
+        now: DateTime<Utc>,
+    ) -> Vec<Action> {
+        if self.scripts.is_empty() {
+            return Vec::new();
+        }
+
+        for (name, pass) in alerts {
+            if !self.alerted.insert((name.clone(), pass.aos_time)) {
+                continue;
+            }
+            let Some(satellite) = satellites.iter().find(|sat| &sat.name == name) else {
+                continue;
+            };
+            let mut event = metadata(satellite);
+            pass_fields(&mut event, pass);
+            let minutes = (pass.aos_time - now).num_seconds() as f64 / 60.0;
+            event.insert("minutes_to_aos".into(), minutes.into());
+            self.call("on_alert", &event);
+        }
+        self.alerted
+            .retain(|(_, aos)| *aos > now - Duration::days(1));
+
+        for position in positions {
+            let previous = self
+                .last_elevation
+                .insert(position.name.clone(), position.elevation);
+            let function = match previous {
+                Some(previous) if previous <= 0.0 && position.elevation > 0.0 => "on_aos",
+                Some(previous) if previous > 0.0 && position.elevation <= 0.0 => "on_los",
+                _ => continue,
+            };
+            let Some(satellite) = satellites.iter().find(|sat| sat.name == position.name) else {
+                continue;
+            };
+            let mut event = metadata(satellite);
+            position_fields(&mut event, position);
+            // The pass under way, or at LOS the one just ended
+            if let Some(pass) = satellite
+                .passes
+                .iter()
+                .rev()
+                .find(|pass| pass.aos_time <= now + Duration::minutes(1))
+            {
+                pass_fields(&mut event, pass);
+            }
+            self.call(function, &event);
+        }
+
+        std::mem::take(&mut *self.actions.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::{sample_pass, sample_satellite};
+    use chrono::TimeZone;
+
+    fn write_script(name: &str, source: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("crabtrack-{}-{}.rhai", name, std::process::id()));
+        std::fs::write(&path, source).unwrap();
+        path
+    }
+
+    fn position(name: &str, elevation: f64) -> SatellitePosition {
+        SatellitePosition {
+            name: name.to_string(),
+            time: Utc::now(),
+            latitude: 0.0,
+            longitude: 0.0,
+            altitude_km: 420.0,
+            velocity_km_s: 7.7,
+            range_rate_km_s: 0.0,
+            azimuth: 180.0,
+            elevation,
+            range_km: 1000.0,
+            is_visible: elevation > 0.0,
+            doppler: None,
+            comm_window: None,
+        }
+    }
+
+    #[test]
+    fn test_script_events() {
+        let path = write_script(
+            "events",
+            r#"
+            let aos_count = 0;
+            fn on_aos(event) {
+                print(`AOS ${event.satellite} band ${event.bands[0]}`);
+                if event.max_elevation > 25.0 && "70cm" in event.bands {
+                    track(event.satellite);
+                }
+            }
+            fn on_los(event) { stop(); }
+            fn on_alert(event) { announce(`${event.satellite} soon`); }
+            "#,
+        );
+        let mut host = ScriptHost::load(std::slice::from_ref(&path)).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let now = Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 0).unwrap();
+        let mut iss = sample_satellite("ISS");
+        let (_, pass) = sample_pass("ISS", now, now + Duration::minutes(10), 40.0);
+        iss.passes = vec![pass.clone()];
+        let satellites = vec![iss];
+        let metadata = |satellite: &Satellite| {
+            let mut map = Map::new();
+            map.insert("satellite".into(), satellite.name.clone().into());
+            map.insert(
+                "bands".into(),
+                vec![Dynamic::from("70cm".to_string())].into(),
+            );
+            map
+        };
+
+        // First sighting sets the baseline; the alert is new
+        let alerts = vec![("ISS".to_string(), pass)];
+        let actions = host.update(
+            &satellites,
+            &[position("ISS", -5.0)],
+            &alerts,
+            metadata,
+            now,
+        );
+        assert_eq!(actions, vec![Action::Announce("ISS soon".into())]);
+
+        // Rising through the horizon, with the alert already handled
+        let actions = host.update(&satellites, &[position("ISS", 1.0)], &alerts, metadata, now);
+        assert_eq!(actions, vec![Action::Track("ISS".into())]);
+        assert!(host.log.back().unwrap().ends_with("AOS ISS band 70cm"));
+
+        let actions = host.update(&satellites, &[position("ISS", -1.0)], &[], metadata, now);
+        assert_eq!(actions, vec![Action::Stop]);
+
+        let broken = write_script("broken", "fn on_aos(event) { event.nope.call() }");
+        let mut host = ScriptHost::load(std::slice::from_ref(&broken)).unwrap();
+        std::fs::remove_file(broken).unwrap();
+        host.update(&satellites, &[position("ISS", -1.0)], &[], metadata, now);
+        host.update(&satellites, &[position("ISS", 1.0)], &[], metadata, now);
+        assert!(host.log.back().unwrap().contains("on_aos"));
+
+        let invalid = write_script("invalid", "fn on_aos(event) {");
+        assert!(ScriptHost::load(std::slice::from_ref(&invalid)).is_err());
+        std::fs::remove_file(invalid).unwrap();
+    }
+}
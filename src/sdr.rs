@@ -0,0 +1,194 @@
+//! Keeps an SDR receiver on the selected satellite's downlink during a pass.
+//! GQRX's remote control and SDR++'s rigctl server both speak a subset of
+//! Hamlib's rigctld protocol: `F <Hz>` tunes and `M <mode> <passband>` sets
+//! the demodulator, each acknowledged with `RPRT 0`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::config::SdrConfig;
+use crate::hamlib::HamlibConnection;
+use crate::observer::Observer;
+use crate::radio::calculate_doppler_shift;
+use crate::satellite::Satellite;
+
+/// Demodulator for a transponder mode such as "FM", "USB" or "BPSK", in the
+/// names both receivers accept. Data modes are received on USB.
+pub fn demodulator(transponder_mode: &str) -> Option<&'static str> {
+    let mode = transponder_mode
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|token| !token.is_empty())?
+        .to_uppercase();
+    match mode.as_str() {
+        "FM" | "FMN" | "NFM" | "AFSK" | "APRS" | "PACKET" | "SSTV" => Some("FM"),
+        "USB" | "SSB" | "BPSK" | "PSK" | "FSK" | "GMSK" | "DATA" | "DIGI" => Some("USB"),
+        "LSB" => Some("LSB"),
+        "CW" => Some("CW"),
+        _ => None,
+    }
+}
+
+/// Minimal client for the GQRX / SDR++ remote control protocol
+pub struct SdrClient {
+    connection: HamlibConnection,
+}
+
+impl SdrClient {
+    pub fn connect(host: &str, port: u16) -> Result<Self> {
+        Ok(Self {
+            connection: HamlibConnection::connect("the SDR", host, port)?,
+        })
+    }
+
+    pub fn set_frequency(&mut self, frequency_hz: u64) -> Result<()> {
+        self.connection.command(&format!("F {}", frequency_hz))
+    }
+
+    /// Switch the demodulator; a passband of 0 keeps the receiver's default
+    pub fn set_mode(&mut self, mode: &str) -> Result<()> {
+        self.connection.command(&format!("M {} 0", mode))
+    }
+}
+
+/// Retunes the SDR to the Doppler-corrected downlink while the selected
+/// satellite is in a pass
+pub struct SdrController {
+    pub config: SdrConfig,
+    pub status: String,
+    pub tuned_hz: Option<u64>,
+    client: Option<SdrClient>,
+    last_update: Option<DateTime<Utc>>,
+    configured_pass: Option<(String, DateTime<Utc>)>, // (satellite, AOS) whose mode is set
+}
+
+impl SdrController {
+    pub fn new(config: SdrConfig) -> Self {
+        Self {
+            config,
+            status: "Idle".to_string(),
+            tuned_hz: None,
+            client: None,
+            last_update: None,
+            configured_pass: None,
+        }
+    }
+
+    fn client(&mut self) -> Result<&mut SdrClient> {
+        if self.client.is_none() {
+            self.client = Some(SdrClient::connect(&self.config.host, self.config.port)?);
+        }
+        self.client.as_mut().context("SDR not connected")
+    }
+
+    /// Tune to `downlink_mhz` as heard from the satellite now, every
+    /// `interval_ms` during its passes, and set the demodulator once per pass
+    pub fn update(
+        &mut self,
+        satellite: &Satellite,
+        observer: &Observer,
+        downlink_mhz: f64,
+        transponder_mode: Option<&str>,
+        now: DateTime<Utc>,
+    ) {
+        let Some(pass) = satellite.get_current_pass(now) else {
+            self.tuned_hz = None;
+            return;
+        };
+        // A missing receiver is retried once per interval too
+        let interval = Duration::milliseconds(self.config.interval_ms as i64);
+        if self.last_update.is_some_and(|last| now - last < interval) {
+            return;
+        }
+        self.last_update = Some(now);
+        let Ok(position) = satellite.calculate_position(now, observer) else {
+            return;
+        };
+
+        let key = (satellite.name.clone(), pass.aos_time);
+        if self.config.set_mode && self.configured_pass.as_ref() != Some(&key) {
+            let mode = transponder_mode.and_then(demodulator);
+            if let Some(mode) = mode {
+                if let Err(e) = self.client().and_then(|client| client.set_mode(mode)) {
+                    self.client = None;
+                    self.status = format!("Error: {}", e);
+                    return;
+                }
+            }
+            self.configured_pass = Some(key);
+            // A new pass starts from a fresh tuning
+            self.tuned_hz = None;
+        }
+
+        let observed_mhz =
+            calculate_doppler_shift(&position, downlink_mhz, 0.0).downlink_observed_mhz;
+        let frequency_hz = (observed_mhz * 1e6).round() as u64;
+        let step = self
+            .tuned_hz
+            .map_or(u64::MAX, |tuned| tuned.abs_diff(frequency_hz));
+        if step < self.config.min_step_hz {
+            return;
+        }
+        let result = self
+            .client()
+            .and_then(|client| client.set_frequency(frequency_hz));
+        self.status = match result {
+            Ok(()) => {
+                self.tuned_hz = Some(frequency_hz);
+                format!("{:.4} MHz for {}", observed_mhz, satellite.name)
+            }
+            Err(e) => {
+                self.client = None;
+                self.tuned_hz = None;
+                format!("Error: {}", e)
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_sdr_protocol() {
+        assert_eq!(demodulator("FM"), Some("FM"));
+        assert_eq!(demodulator("usb/cw"), Some("USB"));
+        assert_eq!(demodulator("BPSK 1200"), Some("USB"));
+        assert_eq!(demodulator(""), None);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut commands = Vec::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                // GQRX answers RPRT 1 to modes it doesn't know
+                let reply = if line.trim() == "M PKTUSB 0" {
+                    "RPRT 1\n"
+                } else {
+                    "RPRT 0\n"
+                };
+                writer.write_all(reply.as_bytes()).unwrap();
+                commands.push(line.trim().to_string());
+                line.clear();
+            }
+            commands
+        });
+
+        let mut client = SdrClient::connect("127.0.0.1", port).unwrap();
+        client.set_mode("FM").unwrap();
+        client.set_frequency(436_802_285).unwrap();
+        assert!(client.set_mode("PKTUSB").is_err());
+        drop(client);
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec!["M FM 0", "F 436802285", "M PKTUSB 0"]
+        );
+    }
+}
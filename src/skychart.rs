@@ -0,0 +1,464 @@
+//! Polar sky charts of a single pass, as SVG or PNG, for observation plans
+//! and for sharing. North is up and east to the right, as on the sky map,
+//! with the zenith in the middle and the horizon at the edge.
+
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+
+use crate::observer::Observer;
+use crate::pass_prediction::SatellitePass;
+use crate::satellite::Satellite;
+
+const WIDTH: u32 = 480;
+const HEIGHT: u32 = 520;
+const CENTER: (f64, f64) = (240.0, 280.0);
+const RADIUS: f64 = 200.0;
+
+/// Where the satellite is in the sky at one moment of the pass
+#[derive(Debug, Clone, Copy)]
+pub struct ChartPoint {
+    pub time: DateTime<Utc>,
+    pub azimuth: f64,
+    pub elevation: f64,
+}
+
+/// A pass to chart: its trajectory and what to call it
+pub struct PassChart {
+    pub title: String,
+    pub subtitle: String,
+    pub track: Vec<ChartPoint>,
+    pub max_elevation: f64,
+}
+
+impl PassChart {
+    pub fn new(satellite: &Satellite, observer: &Observer, pass: &SatellitePass) -> Self {
+        let track = satellite
+            .ground_track(
+                observer,
+                pass.aos_time,
+                pass.los_time,
+                chrono::Duration::seconds(10),
+            )
+            .into_iter()
+            .map(|pos| ChartPoint {
+                time: pos.time,
+                azimuth: pos.azimuth,
+                elevation: pos.elevation.max(0.0),
+            })
+            .collect();
+        let aos = pass.aos_time.with_timezone(&Local);
+        Self {
+            title: format!("{} {}", satellite.name, aos.format("%Y-%m-%d %H:%M")),
+            subtitle: format!(
+                "From {}, max elevation {:.0}°, times {}",
+                observer.name,
+                pass.max_elevation,
+                aos.format("%Z")
+            ),
+            track,
+            max_elevation: pass.max_elevation,
+        }
+    }
+
+    /// Points to label with their time: AOS, LOS, and about eight in between
+    fn labelled(&self) -> Vec<usize> {
+        let count = self.track.len();
+        if count == 0 {
+            return Vec::new();
+        }
+        let step = (count / 8).max(1);
+        let mut indices: Vec<usize> = (0..count).step_by(step).collect();
+        if indices.last() != Some(&(count - 1)) {
+            // Keep the LOS label clear of the one before it
+            if count - 1 - indices[indices.len() - 1] <= step / 2 && indices.len() > 1 {
+                indices.pop();
+            }
+            indices.push(count - 1);
+        }
+        indices
+    }
+
+    fn culmination(&self) -> Option<&ChartPoint> {
+        self.track
+            .iter()
+            .max_by(|a, b| a.elevation.total_cmp(&b.elevation))
+    }
+
+    /// The chart as a standalone SVG document
+    pub fn svg(&self) -> String {
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+             viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n\
+             <text x=\"12\" y=\"22\" font-size=\"16\" font-weight=\"bold\">{2}</text>\n\
+             <text x=\"12\" y=\"40\" font-size=\"12\" fill=\"#555\">{3}</text>\n",
+            WIDTH,
+            HEIGHT,
+            escape_xml(&self.title),
+            escape_xml(&self.subtitle)
+        );
+        let (cx, cy) = CENTER;
+        for (elevation, color) in [(0.0, "#333"), (30.0, "#bbb"), (60.0, "#bbb")] {
+            let r = RADIUS * (90.0 - elevation) / 90.0;
+            out.push_str(&format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{:.1}\" fill=\"none\" stroke=\"{}\"/>\n",
+                cx, cy, r, color
+            ));
+            if elevation > 0.0 {
+                out.push_str(&format!(
+                    "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\" fill=\"#999\">{:.0}°</text>\n",
+                    cx + 3.0,
+                    cy - r - 3.0,
+                    elevation
+                ));
+            }
+        }
+        out.push_str(&format!(
+            "<path d=\"M{0} {1}V{2}M{3} {4}H{5}\" stroke=\"#bbb\"/>\n",
+            cx,
+            cy - RADIUS,
+            cy + RADIUS,
+            cx - RADIUS,
+            cy,
+            cx + RADIUS
+        ));
+        for (label, azimuth) in [("N", 0.0), ("E", 90.0), ("S", 180.0), ("W", 270.0)] {
+            let (x, y) = chart_point(azimuth, -6.0);
+            out.push_str(&format!(
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"14\" font-weight=\"bold\" \
+                 text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+                x, y, label
+            ));
+        }
+
+        let path: Vec<String> = self
+            .track
+            .iter()
+            .map(|point| {
+                let (x, y) = chart_point(point.azimuth, point.elevation);
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect();
+        out.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"#0070c0\" stroke-width=\"2.5\"/>\n",
+            path.join(" ")
+        ));
+
+        let last = self.track.len().saturating_sub(1);
+        for index in self.labelled() {
+            let point = &self.track[index];
+            let (x, y) = chart_point(point.azimuth, point.elevation);
+            let (color, prefix) = match index {
+                0 => ("#1a8f3c", "AOS "),
+                i if i == last => ("#c0392b", "LOS "),
+                _ => ("#0070c0", ""),
+            };
+            out.push_str(&format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"4\" fill=\"{}\"/>\n\
+                 <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"11\">{}{}</text>\n",
+                x,
+                y,
+                color,
+                x + 7.0,
+                y + 4.0,
+                prefix,
+                point.time.with_timezone(&Local).format("%H:%M:%S")
+            ));
+        }
+        if let Some(top) = self.culmination() {
+            let (x, y) = chart_point(top.azimuth, top.elevation);
+            out.push_str(&format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"5\" fill=\"none\" stroke=\"#e67e22\" \
+                 stroke-width=\"2\"/>\n\
+                 <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"11\" fill=\"#e67e22\" \
+                 text-anchor=\"end\">max {:.0}°</text>\n",
+                x,
+                y,
+                x - 8.0,
+                y + 4.0,
+                self.max_elevation
+            ));
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// The chart as a PNG image
+    pub fn png(&self) -> Result<Vec<u8>> {
+        let mut canvas = Canvas::new(WIDTH, HEIGHT);
+        canvas.text(12, 12, &self.title, 2, BLACK);
+        canvas.text(12, 34, &self.subtitle, 1, GRAY);
+
+        let (cx, cy) = CENTER;
+        for (elevation, color) in [(0.0, DARK), (30.0, LIGHT), (60.0, LIGHT)] {
+            let r = RADIUS * (90.0 - elevation) / 90.0;
+            canvas.circle(cx, cy, r, color);
+        }
+        canvas.line((cx, cy - RADIUS), (cx, cy + RADIUS), LIGHT, 1.0);
+        canvas.line((cx - RADIUS, cy), (cx + RADIUS, cy), LIGHT, 1.0);
+        for (label, azimuth) in [("N", 0.0), ("E", 90.0), ("S", 180.0), ("W", 270.0)] {
+            let (x, y) = chart_point(azimuth, -6.0);
+            canvas.text(x as i32 - 5, y as i32 - 7, label, 2, BLACK);
+        }
+
+        for pair in self.track.windows(2) {
+            let a = chart_point(pair[0].azimuth, pair[0].elevation);
+            let b = chart_point(pair[1].azimuth, pair[1].elevation);
+            canvas.line(a, b, BLUE, 2.5);
+        }
+
+        let last = self.track.len().saturating_sub(1);
+        for index in self.labelled() {
+            let point = &self.track[index];
+            let (x, y) = chart_point(point.azimuth, point.elevation);
+            let (color, prefix) = match index {
+                0 => (GREEN, "AOS "),
+                i if i == last => (RED, "LOS "),
+                _ => (BLUE, ""),
+            };
+            canvas.dot(x, y, 4.0, color);
+            let label = format!(
+                "{}{}",
+                prefix,
+                point.time.with_timezone(&Local).format("%H:%M:%S")
+            );
+            canvas.text(x as i32 + 7, y as i32 - 3, &label, 1, BLACK);
+        }
+        if let Some(top) = self.culmination() {
+            let (x, y) = chart_point(top.azimuth, top.elevation);
+            canvas.dot(x, y, 5.0, ORANGE);
+            let label = format!("MAX {:.0}°", self.max_elevation);
+            let width = label.chars().count() as i32 * 6;
+            canvas.text(x as i32 - 8 - width, y as i32 - 3, &label, 1, ORANGE);
+        }
+        canvas.encode()
+    }
+}
+
+/// Pixel position of an azimuth/elevation on the chart
+fn chart_point(azimuth: f64, elevation: f64) -> (f64, f64) {
+    let r = RADIUS * (90.0 - elevation) / 90.0;
+    let az = azimuth.to_radians();
+    (CENTER.0 + r * az.sin(), CENTER.1 - r * az.cos())
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+type Rgb = [u8; 3];
+const BLACK: Rgb = [0, 0, 0];
+const GRAY: Rgb = [85, 85, 85];
+const DARK: Rgb = [51, 51, 51];
+const LIGHT: Rgb = [187, 187, 187];
+const BLUE: Rgb = [0, 112, 192];
+const GREEN: Rgb = [26, 143, 60];
+const RED: Rgb = [192, 57, 43];
+const ORANGE: Rgb = [230, 126, 34];
+
+/// A white RGB image to draw the chart on
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![255; (width * height * 3) as usize],
+        }
+    }
+
+    fn set(&mut self, x: i32, y: i32, color: Rgb) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        let offset = ((y as u32 * self.width + x as u32) * 3) as usize;
+        self.pixels[offset..offset + 3].copy_from_slice(&color);
+    }
+
+    fn dot(&mut self, x: f64, y: f64, radius: f64, color: Rgb) {
+        let r = radius.ceil() as i32;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if ((dx * dx + dy * dy) as f64) <= radius * radius {
+                    self.set(x.round() as i32 + dx, y.round() as i32 + dy, color);
+                }
+            }
+        }
+    }
+
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), color: Rgb, width: f64) {
+        let length = (to.0 - from.0).hypot(to.1 - from.1);
+        let steps = (length * 2.0).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let x = from.0 + (to.0 - from.0) * t;
+            let y = from.1 + (to.1 - from.1) * t;
+            self.dot(x, y, width / 2.0, color);
+        }
+    }
+
+    fn circle(&mut self, cx: f64, cy: f64, radius: f64, color: Rgb) {
+        let steps = (radius * 8.0) as usize;
+        for step in 0..steps {
+            let angle = step as f64 / steps as f64 * std::f64::consts::TAU;
+            let x = cx + radius * angle.cos();
+            let y = cy + radius * angle.sin();
+            self.set(x.round() as i32, y.round() as i32, color);
+        }
+    }
+
+    /// Text in the 5x7 font, `scale` pixels per font pixel, from the top left corner
+    fn text(&mut self, x: i32, y: i32, text: &str, scale: i32, color: Rgb) {
+        for (i, c) in text.chars().enumerate() {
+            let rows = glyph(c);
+            let left = x + i as i32 * 6 * scale;
+            for (row, bits) in rows.iter().enumerate() {
+                for col in 0..5 {
+                    if bits & (0x10 >> col) == 0 {
+                        continue;
+                    }
+                    for sy in 0..scale {
+                        for sx in 0..scale {
+                            self.set(left + col * scale + sx, y + row as i32 * scale + sy, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+        Ok(bytes)
+    }
+}
+
+/// Rows of a character in a 5x7 pixel font, top row first and the leftmost
+/// pixel in bit 4. Lowercase is drawn as uppercase; anything else is blank.
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        'A' => [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '°' => [0x0C, 0x12, 0x12, 0x0C, 0x00, 0x00, 0x00],
+        _ => [0; 7],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn chart() -> PassChart {
+        let aos = Utc.with_ymd_and_hms(2026, 4, 21, 18, 0, 0).unwrap();
+        // Rising in the south-west, over the zenith, setting in the north-east
+        let track = (0..=60)
+            .map(|i| {
+                let t = i as f64 / 60.0;
+                let (azimuth, elevation) = if t < 0.5 {
+                    (225.0, t * 180.0)
+                } else {
+                    (45.0, (1.0 - t) * 180.0)
+                };
+                ChartPoint {
+                    time: aos + Duration::seconds(i * 10),
+                    azimuth,
+                    elevation,
+                }
+            })
+            .collect();
+        PassChart {
+            title: "ISS (ZARYA) <test>".to_string(),
+            subtitle: "From W1AW".to_string(),
+            track,
+            max_elevation: 90.0,
+        }
+    }
+
+    #[test]
+    fn test_chart_geometry() {
+        let (x, y) = chart_point(0.0, 0.0);
+        assert!((x - CENTER.0).abs() < 1e-9 && (y - (CENTER.1 - RADIUS)).abs() < 1e-9);
+        let (x, y) = chart_point(90.0, 90.0);
+        assert!((x - CENTER.0).abs() < 1e-9 && (y - CENTER.1).abs() < 1e-9);
+        let (x, _) = chart_point(90.0, 45.0);
+        assert!((x - (CENTER.0 + RADIUS / 2.0)).abs() < 1e-9);
+
+        // AOS and LOS are always labelled, with a handful in between
+        let labels = chart().labelled();
+        assert_eq!(labels.first(), Some(&0));
+        assert_eq!(labels.last(), Some(&60));
+        assert!((6..=10).contains(&labels.len()));
+    }
+
+    #[test]
+    fn test_svg_and_png() {
+        let chart = chart();
+        let svg = chart.svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("ISS (ZARYA) &lt;test&gt;"));
+        assert!(svg.contains("<polyline points=\""));
+        assert_eq!(svg.matches("AOS ").count(), 1);
+        assert_eq!(svg.matches("LOS ").count(), 1);
+        assert!(svg.trim_end().ends_with("</svg>"));
+
+        let png = chart.png().unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        assert!(png.len() > 1000);
+    }
+}
@@ -0,0 +1,398 @@
+//! Two-way sync of satellite details and transponders with a list shared by a
+//! club. The list is one JSON file, kept either in a directory (pulled and
+//! pushed with git when the directory is a checkout) or on a WebDAV server.
+//! Each satellite is merged as a whole: whichever side changed it last wins.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::SyncConfig;
+use crate::database::{Database, SatelliteDetails, Transponder};
+
+/// Name of the shared file in a sync directory
+pub const FILE_NAME: &str = "crabtrack-sync.json";
+
+/// One satellite's shared details and transponders
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub updated_at: Option<String>, // RFC 3339; unknown for rows older than sync
+    #[serde(flatten)]
+    pub details: SatelliteDetails,
+    #[serde(default)]
+    pub transponders: Vec<Transponder>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Document {
+    satellites: Vec<Record>,
+}
+
+/// Outcome of merging the two sides
+#[derive(Debug, Default)]
+pub struct Merge {
+    pub pulled: Vec<Record>,     // newer on the remote, to store locally
+    pub pushed: usize,           // newer here, or missing on the remote
+    pub satellites: Vec<Record>, // the merged list, by name, to write back
+}
+
+/// Whether `a` was changed after `b`; a record with no time is the oldest
+fn newer(a: &Record, b: &Record) -> bool {
+    let time = |record: &Record| {
+        record
+            .updated_at
+            .as_deref()
+            .and_then(|text| DateTime::parse_from_rfc3339(text).ok())
+    };
+    time(a) > time(b)
+}
+
+/// Merge the local and remote lists. When both were changed at the same time
+/// the remote is kept, so repeated syncs settle.
+pub fn merge(local: Vec<Record>, remote: Vec<Record>) -> Merge {
+    let mut remote: BTreeMap<String, Record> = remote
+        .into_iter()
+        .map(|record| (record.details.name.clone(), record))
+        .collect();
+    let mut merge = Merge::default();
+    let mut merged = BTreeMap::new();
+    for record in local {
+        let name = record.details.name.clone();
+        match remote.remove(&name) {
+            Some(theirs) if newer(&record, &theirs) || theirs == record => {
+                if theirs != record {
+                    merge.pushed += 1;
+                }
+                merged.insert(name, record);
+            }
+            Some(theirs) => {
+                merge.pulled.push(theirs.clone());
+                merged.insert(name, theirs);
+            }
+            None => {
+                merge.pushed += 1;
+                merged.insert(name, record);
+            }
+        }
+    }
+    for (name, theirs) in remote {
+        merge.pulled.push(theirs.clone());
+        merged.insert(name, theirs);
+    }
+    merge.satellites = merged.into_values().collect();
+    merge
+}
+
+/// Every satellite in the database with details or transponders
+pub fn local_records(database: &Database) -> Result<Vec<Record>> {
+    let times = database.read_updated_times()?;
+    let mut records: BTreeMap<String, Record> = BTreeMap::new();
+    for mut details in database.read_all()? {
+        details.id = None;
+        records.insert(
+            details.name.clone(),
+            Record {
+                updated_at: times.get(&details.name).cloned(),
+                details,
+                transponders: Vec::new(),
+            },
+        );
+    }
+    for mut transponder in database.read_transponders()? {
+        transponder.id = None;
+        let name = transponder.satellite_name.clone();
+        records
+            .entry(name.clone())
+            .or_insert_with(|| Record {
+                updated_at: times.get(&name).cloned(),
+                details: SatelliteDetails::new(name),
+                transponders: Vec::new(),
+            })
+            .transponders
+            .push(transponder);
+    }
+    Ok(records.into_values().collect())
+}
+
+/// Store records pulled from the remote, keeping the times they were changed
+fn store(database: &Database, records: &[Record]) -> Result<()> {
+    for record in records {
+        let name = &record.details.name;
+        database.upsert(&record.details)?;
+        let updated_at = record.updated_at.as_deref();
+        database.replace_synced_transponders(name, &record.transponders, updated_at)?;
+        database.set_updated_at(name, updated_at)?;
+    }
+    Ok(())
+}
+
+/// Where the shared file lives
+enum Remote {
+    Directory(PathBuf),
+    WebDav(String),
+}
+
+impl Remote {
+    fn new(config: &SyncConfig) -> Self {
+        if config.remote.starts_with("http://") || config.remote.starts_with("https://") {
+            Remote::WebDav(config.remote.clone())
+        } else {
+            Remote::Directory(PathBuf::from(&config.remote))
+        }
+    }
+}
+
+fn is_git_checkout(directory: &Path) -> bool {
+    directory.join(".git").exists()
+}
+
+fn git(directory: &Path, args: &[&str]) -> Result<bool> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()
+        .context("could not run git")?;
+    Ok(output.status.success())
+}
+
+/// Basic authentication header for WebDAV
+fn authorization(config: &SyncConfig) -> Option<String> {
+    let username = config.username.as_deref()?;
+    let password = config.password.as_deref().unwrap_or_default();
+    Some(format!(
+        "Basic {}",
+        base64(format!("{}:{}", username, password).as_bytes())
+    ))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::new();
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (i, &byte)| {
+            value | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(ALPHABET[(value >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn read_remote(remote: &Remote, config: &SyncConfig) -> Result<Document> {
+    let text = match remote {
+        Remote::Directory(directory) => {
+            let path = directory.join(FILE_NAME);
+            if !path.exists() {
+                return Ok(Document::default());
+            }
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("could not read {}", path.display()))?
+        }
+        Remote::WebDav(url) => {
+            let mut request = ureq::get(url).timeout(std::time::Duration::from_secs(30));
+            if let Some(header) = authorization(config) {
+                request = request.set("Authorization", &header);
+            }
+            match request.call() {
+                Ok(response) => {
+                    let mut body = String::new();
+                    response
+                        .into_reader()
+                        .read_to_string(&mut body)
+                        .map_err(|e| anyhow!("Failed to read response: {}", e))?;
+                    body
+                }
+                // Nobody has synced yet
+                Err(ureq::Error::Status(404, _)) => return Ok(Document::default()),
+                Err(e) => bail!("HTTP request failed: {}", e),
+            }
+        }
+    };
+    serde_json::from_str(&text).context("the shared satellite list is not valid JSON")
+}
+
+fn write_remote(remote: &Remote, config: &SyncConfig, document: &Document) -> Result<()> {
+    let text = serde_json::to_string_pretty(document)? + "\n";
+    match remote {
+        Remote::Directory(directory) => {
+            let path = directory.join(FILE_NAME);
+            std::fs::write(&path, text)
+                .with_context(|| format!("could not write {}", path.display()))?;
+        }
+        Remote::WebDav(url) => {
+            let mut request = ureq::put(url)
+                .timeout(std::time::Duration::from_secs(30))
+                .set("Content-Type", "application/json");
+            if let Some(header) = authorization(config) {
+                request = request.set("Authorization", &header);
+            }
+            request
+                .send_string(&text)
+                .map_err(|e| anyhow!("HTTP request failed: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Pull the shared list, merge it with the database, and push the result back
+pub fn run(database: &Database, config: &SyncConfig, station: &str) -> Result<Merge> {
+    let remote = Remote::new(config);
+    let checkout = match &remote {
+        Remote::Directory(directory) if is_git_checkout(directory) => Some(directory.as_path()),
+        _ => None,
+    };
+    // A checkout without a remote is still committed to, as a history of changes
+    let upstream = match checkout {
+        Some(directory) => git(directory, &["remote", "get-url", "origin"])?,
+        None => false,
+    };
+    if let Some(directory) = checkout.filter(|_| upstream) {
+        if !git(directory, &["pull", "--quiet", "--ff-only"])? {
+            bail!("git pull in {} failed", directory.display());
+        }
+    }
+
+    let document = read_remote(&remote, config)?;
+    let merge = merge(local_records(database)?, document.satellites);
+    store(database, &merge.pulled)?;
+    if merge.pushed == 0 {
+        return Ok(merge);
+    }
+    write_remote(
+        &remote,
+        config,
+        &Document {
+            satellites: merge.satellites.clone(),
+        },
+    )?;
+
+    if let Some(directory) = checkout {
+        let message = format!("Sync satellites from {}", station);
+        if !(git(directory, &["add", FILE_NAME])?
+            && git(directory, &["commit", "--quiet", "-m", &message])?
+            && (!upstream || git(directory, &["push", "--quiet"])?))
+        {
+            bail!(
+                "could not commit and push {} in {}",
+                FILE_NAME,
+                directory.display()
+            );
+        }
+    }
+    Ok(merge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, updated_at: Option<&str>, notes: &str) -> Record {
+        let mut details = SatelliteDetails::new(name.to_string());
+        details.notes = Some(notes.to_string());
+        Record {
+            updated_at: updated_at.map(str::to_string),
+            details,
+            transponders: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let local = vec![
+            record("AO-91", Some("2026-05-02T10:00:00+00:00"), "ours"),
+            record("ISS", Some("2026-05-01T10:00:00+00:00"), "ours"),
+            record("SO-50", None, "ours"),
+            record("LOCAL", Some("2026-05-01T10:00:00+00:00"), "ours"),
+        ];
+        let remote = vec![
+            record("AO-91", Some("2026-05-01T12:00:00+02:00"), "theirs"),
+            record("ISS", Some("2026-05-02T10:00:00+00:00"), "theirs"),
+            record("SO-50", Some("2026-01-01T00:00:00+00:00"), "theirs"),
+            record("REMOTE", None, "theirs"),
+        ];
+        let merge = merge(local, remote);
+
+        let names: Vec<&str> = merge
+            .pulled
+            .iter()
+            .map(|r| r.details.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["ISS", "SO-50", "REMOTE"]);
+        assert_eq!(merge.pushed, 2); // AO-91 and LOCAL
+        let merged: Vec<(&str, &str)> = merge
+            .satellites
+            .iter()
+            .map(|r| (r.details.name.as_str(), r.details.notes.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            merged,
+            vec![
+                ("AO-91", "ours"),
+                ("ISS", "theirs"),
+                ("LOCAL", "ours"),
+                ("REMOTE", "theirs"),
+                ("SO-50", "theirs"),
+            ]
+        );
+
+        // Merging again with the result changes nothing
+        let again = super::merge(merge.satellites.clone(), merge.satellites);
+        assert!(again.pulled.is_empty());
+        assert_eq!(again.pushed, 0);
+
+        assert_eq!(base64(b"user:pass"), "dXNlcjpwYXNz");
+        assert_eq!(base64(b"ab"), "YWI=");
+    }
+
+    #[test]
+    fn test_sync_directory() {
+        let directory = std::env::temp_dir().join(format!("crabtrack-sync-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let config = SyncConfig {
+            remote: directory.display().to_string(),
+            username: None,
+            password: None,
+        };
+
+        let first = Database::open_in_memory().unwrap();
+        let mut details = SatelliteDetails::new("AO-91".to_string());
+        details.notes = Some("Weekends only".to_string());
+        first.create(&details).unwrap();
+        let transponder = Transponder {
+            satellite_name: "AO-91".to_string(),
+            description: "FM".to_string(),
+            downlink_mhz: Some(145.96),
+            source: "sqf".to_string(),
+            ..Default::default()
+        };
+        first
+            .replace_transponders("sqf", std::slice::from_ref(&transponder))
+            .unwrap();
+        assert_eq!(run(&first, &config, "first").unwrap().pushed, 1);
+
+        // A second station picks it up
+        let second = Database::open_in_memory().unwrap();
+        let merge = run(&second, &config, "second").unwrap();
+        assert_eq!((merge.pulled.len(), merge.pushed), (1, 0));
+        let stored = second.read_by_name("AO-91").unwrap().unwrap();
+        assert_eq!(stored.notes.as_deref(), Some("Weekends only"));
+        let transponders = second.read_transponders().unwrap();
+        assert_eq!(transponders.len(), 1);
+        assert_eq!(transponders[0].downlink_mhz, Some(145.96));
+        // ...with the first station's time, so syncing again does nothing
+        let merge = run(&second, &config, "second").unwrap();
+        assert_eq!((merge.pulled.len(), merge.pushed), (0, 0));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
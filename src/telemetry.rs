@@ -0,0 +1,207 @@
+use std::io::{BufRead, BufReader};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+use crate::config::DecoderConfig;
+use crate::database::TelemetryFrame;
+use crate::pass_process::{self, PassProcess};
+use crate::satellite::Satellite;
+
+/// Source and decoder processes running for one pass
+struct DecoderSession {
+    aos_time: DateTime<Utc>,
+    los_time: DateTime<Utc>,
+    _processes: Vec<PassProcess>, // source, then decoder; stopped on drop
+    frames: Arc<Mutex<Vec<TelemetryFrame>>>,
+}
+
+impl DecoderSession {
+    fn start(
+        config: &DecoderConfig,
+        aos_time: DateTime<Utc>,
+        los_time: DateTime<Utc>,
+    ) -> Result<Self> {
+        let mut source = pass_process::command(&config.source, &[])
+            .and_then(|mut command| {
+                PassProcess::spawn(
+                    command
+                        .stdin(Stdio::null())
+                        .stdout(Stdio::piped())
+                        .stderr(Stdio::null()),
+                )
+            })
+            .context("source")?;
+        let source_output = source.take_stdout().context("source has no stdout")?;
+
+        // A failed start drops the source, which stops it
+        let mut decoder = pass_process::command(&config.decoder, &[])
+            .and_then(|mut command| {
+                PassProcess::spawn(
+                    command
+                        .stdin(source_output)
+                        .stdout(Stdio::piped())
+                        .stderr(Stdio::null()),
+                )
+            })
+            .context("decoder")?;
+        let decoder_output = decoder.take_stdout().context("decoder has no stdout")?;
+
+        let frames = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&frames);
+        let satellite_name = config.satellite.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(decoder_output).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                sink.lock().unwrap().push(TelemetryFrame {
+                    id: None,
+                    satellite_name: satellite_name.clone(),
+                    received_at: Utc::now().to_rfc3339(),
+                    frame: line.to_string(),
+                });
+            }
+        });
+
+        Ok(Self {
+            aos_time,
+            los_time,
+            _processes: vec![source, decoder],
+            frames,
+        })
+    }
+
+    fn take_frames(&self) -> Vec<TelemetryFrame> {
+        std::mem::take(&mut *self.frames.lock().unwrap())
+    }
+}
+
+/// Runs a configured decoder from AOS to LOS of its satellite's passes
+pub struct TelemetryDecoder {
+    pub config: DecoderConfig,
+    pub status: String,
+    session: Option<DecoderSession>,
+    attempted_aos: Option<DateTime<Utc>>, // pass the decoder was last started for
+}
+
+impl TelemetryDecoder {
+    pub fn new(config: DecoderConfig) -> Self {
+        Self {
+            config,
+            status: "Waiting for AOS".to_string(),
+            session: None,
+            attempted_aos: None,
+        }
+    }
+
+    /// Start or stop the decoder around passes and collect frames decoded since the last call
+    pub fn update(&mut self, satellite: &Satellite, now: DateTime<Utc>) -> Vec<TelemetryFrame> {
+        let frames = self
+            .session
+            .as_ref()
+            .map(DecoderSession::take_frames)
+            .unwrap_or_default();
+
+        if self.session.as_ref().is_some_and(|s| now >= s.los_time) {
+            self.session = None;
+            self.status = "Waiting for AOS".to_string();
+        }
+
+        if self.session.is_none() {
+            if let Some(pass) = satellite.get_current_pass(now) {
+                // One start per pass, so a broken command isn't retried every frame
+                if self.attempted_aos != Some(pass.aos_time) {
+                    self.attempted_aos = Some(pass.aos_time);
+                    match DecoderSession::start(&self.config, pass.aos_time, pass.los_time) {
+                        Ok(session) => {
+                            self.session = Some(session);
+                            self.status = "Decoding".to_string();
+                        }
+                        Err(e) => self.status = format!("Error: {:#}", e),
+                    }
+                }
+            }
+        }
+
+        if let Some(session) = &self.session {
+            if !frames.is_empty() {
+                self.status = format!("Decoding since {} UTC", session.aos_time.format("%H:%M:%S"));
+            }
+        }
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn wait_for_frames(session: &DecoderSession, count: usize) -> Vec<TelemetryFrame> {
+        let mut frames = Vec::new();
+        for _ in 0..100 {
+            frames.extend(session.take_frames());
+            if frames.len() >= count {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        frames
+    }
+
+    #[test]
+    fn test_source_piped_into_decoder() {
+        let config = DecoderConfig {
+            satellite: "AO-73".to_string(),
+            source: "printf 'a1b2\\n\\nc3d4\\n'".to_string(),
+            decoder: "tr a-z A-Z".to_string(),
+        };
+        let now = Utc::now();
+        let session = DecoderSession::start(&config, now, now + Duration::minutes(10)).unwrap();
+
+        let frames = wait_for_frames(&session, 2);
+        let lines: Vec<&str> = frames.iter().map(|f| f.frame.as_str()).collect();
+        assert_eq!(lines, vec!["A1B2", "C3D4"]);
+        assert!(frames.iter().all(|f| f.satellite_name == "AO-73"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_stop_ends_whole_pipeline() {
+        // A wrapper script whose pipeline outlives it unless its group is stopped
+        let pid_file =
+            std::env::temp_dir().join(format!("crabtrack-decoder-{}.pid", std::process::id()));
+        let config = DecoderConfig {
+            satellite: "AO-73".to_string(),
+            source: format!(
+                "sh -c 'sleep 60 & echo $! > \"{}\"; echo a1; wait'",
+                pid_file.display()
+            ),
+            decoder: "cat".to_string(),
+        };
+        let now = Utc::now();
+        let session = DecoderSession::start(&config, now, now + Duration::minutes(10)).unwrap();
+        assert_eq!(wait_for_frames(&session, 1).len(), 1);
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        drop(session);
+        std::fs::remove_file(&pid_file).unwrap();
+
+        // Stopping finishes in the background: soon gone, or a zombie waiting
+        // for init to reap it
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid.trim())) {
+            if stat.contains(") Z ") {
+                break;
+            }
+            assert!(std::time::Instant::now() < deadline, "{}", stat);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+}
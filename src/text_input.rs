@@ -0,0 +1,211 @@
+//! Single-line text entry with a cursor, for forms where retyping a field to
+//! fix one character (a 69-column TLE line, say) isn't reasonable.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Text being edited and the cursor position within it, in characters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextInput {
+    text: String,
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn value(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the text, leaving the cursor at its end
+    pub fn set(&mut self, text: String) {
+        self.cursor = text.chars().count();
+        self.text = text;
+    }
+
+    /// Replace the text, keeping the cursor where it was as far as possible
+    pub fn replace(&mut self, text: String) {
+        self.cursor = self.cursor.min(text.chars().count());
+        self.text = text;
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.cursor == self.text.chars().count()
+    }
+
+    pub fn clear(&mut self) {
+        self.set(String::new());
+    }
+
+    fn byte_offset(&self, cursor: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.text.len(), |(offset, _)| offset)
+    }
+
+    pub fn insert_str(&mut self, s: &str) {
+        let offset = self.byte_offset(self.cursor);
+        self.text.insert_str(offset, s);
+        self.cursor += s.chars().count();
+    }
+
+    /// Remove the characters between two cursor positions
+    fn remove(&mut self, from: usize, to: usize) {
+        let range = self.byte_offset(from)..self.byte_offset(to);
+        self.text.replace_range(range, "");
+        self.cursor = from;
+    }
+
+    /// Start of the word before the cursor: back over spaces, then over the word
+    fn word_left(&self) -> usize {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut cursor = self.cursor;
+        while cursor > 0 && chars[cursor - 1].is_whitespace() {
+            cursor -= 1;
+        }
+        while cursor > 0 && !chars[cursor - 1].is_whitespace() {
+            cursor -= 1;
+        }
+        cursor
+    }
+
+    /// Start of the next word: forward over the rest of this word, then over spaces
+    fn word_right(&self) -> usize {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut cursor = self.cursor;
+        while cursor < chars.len() && !chars[cursor].is_whitespace() {
+            cursor += 1;
+        }
+        while cursor < chars.len() && chars[cursor].is_whitespace() {
+            cursor += 1;
+        }
+        cursor
+    }
+
+    /// Applies a cursor movement or edit. Returns false for keys that aren't
+    /// editing keys, so the form can use them (Tab, Enter, Esc, Up, Down...).
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let len = self.text.chars().count();
+        let word = key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Left if word => self.cursor = self.word_left(),
+            KeyCode::Right if word => self.cursor = self.word_right(),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(len),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = len,
+            // Readline's word motions, for terminals that don't report Ctrl+arrows
+            KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::ALT) => {
+                self.cursor = self.word_left()
+            }
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::ALT) => {
+                self.cursor = self.word_right()
+            }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => self.cursor = 0,
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.cursor = len
+            }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.remove(self.word_left(), self.cursor)
+            }
+            KeyCode::Backspace if self.cursor > 0 => self.remove(self.cursor - 1, self.cursor),
+            KeyCode::Backspace => {}
+            KeyCode::Delete if self.cursor < len => self.remove(self.cursor, self.cursor + 1),
+            KeyCode::Delete => {}
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.insert_str(c.encode_utf8(&mut [0; 4]))
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// The text with a `|` at the cursor, scrolled to keep the cursor in view
+    /// when it is longer than `width` characters
+    pub fn display(&self, width: usize) -> String {
+        let mut chars: Vec<char> = self.text.chars().collect();
+        chars.insert(self.cursor, '|');
+        let start = (self.cursor + 1)
+            .saturating_sub(width)
+            .min(chars.len().saturating_sub(width));
+        chars.iter().skip(start).take(width).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn input(text: &str) -> TextInput {
+        let mut input = TextInput::default();
+        input.set(text.to_string());
+        input
+    }
+
+    #[test]
+    fn test_cursor_editing() {
+        let mut input = input("1 25544U 98067A");
+        input.handle_key(key(KeyCode::Home));
+        input.handle_key(key(KeyCode::Right));
+        input.handle_key(key(KeyCode::Right));
+        input.handle_key(key(KeyCode::Delete));
+        input.handle_key(key(KeyCode::Char('3')));
+        assert_eq!(input.value(), "1 35544U 98067A");
+
+        input.handle_key(key(KeyCode::End));
+        input.handle_key(key(KeyCode::Backspace));
+        input.handle_key(key(KeyCode::Char('B')));
+        assert_eq!(input.value(), "1 35544U 98067B");
+
+        // Out-of-range edits do nothing
+        input.handle_key(key(KeyCode::Delete));
+        input.handle_key(key(KeyCode::Home));
+        input.handle_key(key(KeyCode::Backspace));
+        assert_eq!(input.value(), "1 35544U 98067B");
+
+        assert!(!input.handle_key(key(KeyCode::Tab)));
+        assert!(!input.handle_key(key(KeyCode::Enter)));
+    }
+
+    #[test]
+    fn test_word_movement() {
+        let ctrl = |code| KeyEvent::new(code, KeyModifiers::CONTROL);
+        let mut input = input("2 25544  51.6416 247.4627");
+        input.handle_key(ctrl(KeyCode::Left));
+        assert_eq!(input.display(80), "2 25544  51.6416 |247.4627");
+        input.handle_key(ctrl(KeyCode::Left));
+        input.handle_key(ctrl(KeyCode::Left));
+        assert_eq!(input.display(80), "2 |25544  51.6416 247.4627");
+        input.handle_key(ctrl(KeyCode::Right));
+        assert_eq!(input.display(80), "2 25544  |51.6416 247.4627");
+
+        input.handle_key(key(KeyCode::End));
+        input.handle_key(ctrl(KeyCode::Char('w')));
+        assert_eq!(input.value(), "2 25544  51.6416 ");
+    }
+
+    #[test]
+    fn test_multibyte_and_display() {
+        let mut name = input("Küken");
+        name.handle_key(key(KeyCode::Left));
+        name.handle_key(key(KeyCode::Left));
+        name.handle_key(key(KeyCode::Left));
+        name.handle_key(key(KeyCode::Backspace));
+        assert_eq!(name.value(), "Kken");
+
+        // Long text scrolls to keep the cursor visible
+        let mut long = input("0123456789");
+        assert_eq!(long.display(5), "6789|");
+        long.handle_key(key(KeyCode::Home));
+        assert_eq!(long.display(5), "|0123");
+    }
+}
@@ -0,0 +1,111 @@
+/// Modulo-10 checksum over the first 68 columns of a TLE line:
+/// digits count their value, minus signs count one, everything else zero
+pub fn checksum(line: &str) -> Option<u32> {
+    let data = line.get(..68)?;
+    Some(
+        data.chars()
+            .map(|c| match c {
+                '0'..='9' => c.to_digit(10).unwrap_or(0),
+                '-' => 1,
+                _ => 0,
+            })
+            .sum::<u32>()
+            % 10,
+    )
+}
+
+/// Result of checking the checksum digit in column 69
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    Valid,
+    Mismatch { expected: u32, found: u32 },
+    Missing, // line too short or no digit in column 69
+}
+
+pub fn verify_checksum(line: &str) -> ChecksumStatus {
+    let found = line.trim_end().chars().nth(68).and_then(|c| c.to_digit(10));
+    match (checksum(line), found) {
+        (Some(expected), Some(found)) if expected == found => ChecksumStatus::Valid,
+        (Some(expected), Some(found)) => ChecksumStatus::Mismatch { expected, found },
+        _ => ChecksumStatus::Missing,
+    }
+}
+
+/// An element set pasted as text, with or without its name line
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastedTle {
+    pub name: Option<String>,
+    pub line1: String,
+    pub line2: String,
+}
+
+/// Splits pasted text into an element set's name, line 1 and line 2. Blank lines
+/// and surrounding whitespace are ignored, and a 3LE name's leading "0 " is dropped.
+pub fn split_pasted(text: &str) -> Result<PastedTle, String> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let (name, line1, line2) = match lines.as_slice() {
+        [line1, line2] => (None, *line1, *line2),
+        [name, line1, line2] => {
+            let name = name.strip_prefix("0 ").unwrap_or(name).trim();
+            (Some(name.to_string()), *line1, *line2)
+        }
+        _ => return Err(format!("expected 2 or 3 lines, got {}", lines.len())),
+    };
+    if !line1.starts_with("1 ") || !line2.starts_with("2 ") {
+        return Err("lines must start with \"1 \" and \"2 \"".to_string());
+    }
+    if line1.get(2..7) != line2.get(2..7) {
+        return Err("lines 1 and 2 have different catalog numbers".to_string());
+    }
+    Ok(PastedTle {
+        name,
+        line1: line1.to_string(),
+        line2: line2.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::{SAMPLE_TLE_LINE1 as ISS_LINE1, SAMPLE_TLE_LINE2 as ISS_LINE2};
+
+    #[test]
+    fn test_valid_checksums() {
+        assert_eq!(verify_checksum(ISS_LINE1), ChecksumStatus::Valid);
+        assert_eq!(verify_checksum(ISS_LINE2), ChecksumStatus::Valid);
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        let corrupted = ISS_LINE1.replacen("08264", "08265", 1);
+        assert_eq!(
+            verify_checksum(&corrupted),
+            ChecksumStatus::Mismatch {
+                expected: 8,
+                found: 7
+            }
+        );
+        assert_eq!(verify_checksum("1 25544U"), ChecksumStatus::Missing);
+    }
+
+    #[test]
+    fn test_split_pasted() {
+        let three_line = format!("0 ISS (ZARYA)\r\n{}\r\n{}\r\n", ISS_LINE1, ISS_LINE2);
+        let pasted = split_pasted(&three_line).unwrap();
+        assert_eq!(pasted.name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(pasted.line1, ISS_LINE1);
+        assert_eq!(pasted.line2, ISS_LINE2);
+
+        let two_line = format!("  {}\n\n{}  ", ISS_LINE1, ISS_LINE2);
+        assert_eq!(split_pasted(&two_line).unwrap().name, None);
+
+        assert!(split_pasted(ISS_LINE1).is_err());
+        assert!(split_pasted(&format!("{}\n{}", ISS_LINE2, ISS_LINE1)).is_err());
+        let other_object = ISS_LINE2.replacen("25544", "25545", 1);
+        assert!(split_pasted(&format!("{}\n{}", ISS_LINE1, other_object)).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! What changed in a TLE group between two downloads: satellites that
+//! appeared or disappeared, how many element sets moved to a newer epoch,
+//! and element sets whose orbit jumped further than drag explains, which
+//! usually means the satellite maneuvered.
+
+use chrono::{DateTime, Utc};
+use sgp4::Elements;
+
+use crate::celestrak::CatalogEntry;
+
+/// Mean motion further than this from what the old elements' drag term
+/// predicts, in revolutions per day, counts as a maneuver. An ISS reboost
+/// changes it by several times this.
+const MEAN_MOTION_JUMP: f64 = 0.001;
+
+/// Inclination change, in degrees, beyond the scatter between element sets
+const INCLINATION_JUMP: f64 = 0.05;
+
+/// An element set that changed more than drag accounts for
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementJump {
+    pub name: String,
+    pub norad_id: u32,
+    pub old_epoch: DateTime<Utc>,
+    pub new_epoch: DateTime<Utc>,
+    pub mean_motion_change: f64, // rev/day, beyond the drag prediction
+    pub inclination_change: f64, // degrees
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TleDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>, // decayed, or dropped from the group
+    pub updated: usize,       // newer epoch than before
+    pub unchanged: usize,
+    pub jumps: Vec<ElementJump>,
+}
+
+impl TleDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated == 0
+    }
+
+    /// One line for a status message, e.g. "2 added, 1 removed, 40 updated, 1 maneuvered"
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "no changes".to_string();
+        }
+        let mut parts = Vec::new();
+        for (count, label) in [
+            (self.added.len(), "added"),
+            (self.removed.len(), "removed"),
+            (self.updated, "updated"),
+            (self.jumps.len(), "maneuvered"),
+        ] {
+            if count > 0 {
+                parts.push(format!("{} {}", count, label));
+            }
+        }
+        parts.join(", ")
+    }
+}
+
+/// Compare the element sets a group had with the ones just downloaded,
+/// matching satellites by NORAD id
+pub fn diff(old: &[CatalogEntry], new: &[CatalogEntry]) -> TleDiff {
+    let mut result = TleDiff::default();
+    let elements = |entry: &CatalogEntry| {
+        Elements::from_tle(None, entry.tle_line1.as_bytes(), entry.tle_line2.as_bytes()).ok()
+    };
+
+    for entry in new {
+        let Some(previous) = old.iter().find(|o| o.norad_id == entry.norad_id) else {
+            result.added.push(entry.name.clone());
+            continue;
+        };
+        let (Some(before), Some(after)) = (elements(previous), elements(entry)) else {
+            continue;
+        };
+        if after.datetime <= before.datetime {
+            result.unchanged += 1;
+            continue;
+        }
+        result.updated += 1;
+
+        // The TLE carries half the first derivative of mean motion
+        let days = (after.datetime - before.datetime).num_seconds() as f64 / 86400.0;
+        let expected = before.mean_motion + 2.0 * before.mean_motion_dot * days;
+        let mean_motion_change = after.mean_motion - expected;
+        let inclination_change = after.inclination - before.inclination;
+        if mean_motion_change.abs() > MEAN_MOTION_JUMP
+            || inclination_change.abs() > INCLINATION_JUMP
+        {
+            result.jumps.push(ElementJump {
+                name: entry.name.clone(),
+                norad_id: entry.norad_id,
+                old_epoch: before.datetime.and_utc(),
+                new_epoch: after.datetime.and_utc(),
+                mean_motion_change,
+                inclination_change,
+            });
+        }
+    }
+
+    result.removed = old
+        .iter()
+        .filter(|o| !new.iter().any(|entry| entry.norad_id == o.norad_id))
+        .map(|o| o.name.clone())
+        .collect();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tle;
+
+    /// An ISS element set with its epoch day and mean motion replaced
+    fn iss(epoch_day: &str, mean_motion: &str) -> CatalogEntry {
+        let with_checksum = |line: String| {
+            let sum = tle::checksum(&line).unwrap();
+            format!("{}{}", &line[..68], sum)
+        };
+        let line1 = format!(
+            "1 25544U 98067A   08{}  .00000000  00000-0 -11606-4 0  292",
+            epoch_day
+        );
+        let line2 = format!(
+            "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 {}56353",
+            mean_motion
+        );
+        CatalogEntry {
+            name: "ISS (ZARYA)".to_string(),
+            norad_id: 25544,
+            tle_line1: with_checksum(format!("{:<68}", line1)),
+            tle_line2: with_checksum(format!("{:<68}", line2)),
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let decayed = CatalogEntry {
+            norad_id: 99999,
+            name: "DECAYED".to_string(),
+            ..iss("264.51782528", "15.72125391")
+        };
+        let old = vec![iss("264.51782528", "15.72125391"), decayed];
+
+        // Same epoch: nothing changed
+        let same = diff(&old[..1], &old[..1]);
+        assert!(same.is_empty());
+        assert_eq!(same.unchanged, 1);
+        assert_eq!(same.summary(), "no changes");
+
+        // A day later with the same mean motion: updated, no maneuver
+        let quiet = diff(&old, &[iss("265.51782528", "15.72125391")]);
+        assert_eq!(quiet.updated, 1);
+        assert_eq!(quiet.removed, vec!["DECAYED"]);
+        assert!(quiet.jumps.is_empty());
+        assert_eq!(quiet.summary(), "1 removed, 1 updated");
+
+        // A reboost raises the orbit and slows it down
+        let reboost = diff(&old[..1], &[iss("266.51782528", "15.71625391")]);
+        assert_eq!(reboost.jumps.len(), 1);
+        assert!((reboost.jumps[0].mean_motion_change + 0.005).abs() < 1e-6);
+
+        let added = diff(&[], &old);
+        assert_eq!(added.added, vec!["ISS (ZARYA)", "DECAYED"]);
+    }
+}
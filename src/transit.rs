@@ -0,0 +1,188 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::astro;
+use crate::observer::Observer;
+use crate::satellite::Satellite;
+
+const SUN_RADIUS_KM: f64 = 696_000.0;
+const MOON_RADIUS_KM: f64 = 1_737.4;
+
+/// Body a satellite can be seen crossing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitBody {
+    Sun,
+    Moon,
+}
+
+impl TransitBody {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TransitBody::Sun => "Sun",
+            TransitBody::Moon => "Moon",
+        }
+    }
+
+    /// Azimuth, elevation, and apparent angular radius (degrees) of the body
+    fn look(&self, time: DateTime<Utc>, observer: &Observer) -> (f64, f64, f64) {
+        let (angles, radius_km) = match self {
+            TransitBody::Sun => (astro::sun_look_angles(time, observer), SUN_RADIUS_KM),
+            TransitBody::Moon => (astro::moon_look_angles(time, observer), MOON_RADIUS_KM),
+        };
+        let angular_radius = (radius_km / angles.range).asin().to_degrees();
+        (angles.azimuth, angles.elevation, angular_radius)
+    }
+}
+
+/// A close approach of the satellite to the Sun or Moon as seen by the observer
+#[derive(Debug, Clone)]
+pub struct Transit {
+    pub body: TransitBody,
+    pub center_time: DateTime<Utc>,
+    pub separation_deg: f64,
+    pub body_radius_deg: f64,
+    pub satellite_elevation: f64,
+    pub range_km: f64,
+    pub offset_km: f64, // Approximate distance from the observer to the center line
+}
+
+impl Transit {
+    /// The satellite crosses the disk from the observer's location
+    pub fn is_on_disk(&self) -> bool {
+        self.separation_deg <= self.body_radius_deg
+    }
+}
+
+/// Angular separation between the satellite and a body, plus the context
+/// needed to build a `Transit`
+fn separation_at(
+    satellite: &Satellite,
+    body: TransitBody,
+    time: DateTime<Utc>,
+    observer: &Observer,
+) -> Option<(f64, f64, f64, f64)> {
+    let position = satellite.calculate_position(time, observer).ok()?;
+    let (body_az, body_el, body_radius) = body.look(time, observer);
+    if body_el <= 0.0 || position.elevation <= 0.0 {
+        return None;
+    }
+    let separation =
+        astro::angular_separation(position.azimuth, position.elevation, body_az, body_el);
+    Some((separation, body_radius, position.elevation, position.range_km))
+}
+
+/// Search the satellite's predicted passes for Sun and Moon transits whose
+/// center line falls within `search_radius_km` of the observer
+pub fn find_transits(
+    satellite: &Satellite,
+    observer: &Observer,
+    search_radius_km: f64,
+) -> Vec<Transit> {
+    let coarse_step = Duration::seconds(1);
+    let fine_step = Duration::milliseconds(50);
+    let mut transits = Vec::new();
+
+    for pass in &satellite.passes {
+        for body in [TransitBody::Sun, TransitBody::Moon] {
+            let mut previous: Option<f64> = None;
+            let mut falling = false;
+            let mut time = pass.aos_time;
+
+            while time <= pass.los_time {
+                let Some((separation, ..)) = separation_at(satellite, body, time, observer) else {
+                    previous = None;
+                    time += coarse_step;
+                    continue;
+                };
+
+                // A local minimum sits between the previous two coarse samples
+                if let Some(prev) = previous {
+                    if separation > prev && falling {
+                        if let Some(transit) = refine_minimum(
+                            satellite,
+                            body,
+                            time - coarse_step * 2,
+                            time,
+                            fine_step,
+                            observer,
+                        ) {
+                            if transit.offset_km <= search_radius_km {
+                                transits.push(transit);
+                            }
+                        }
+                    }
+                    falling = separation < prev;
+                }
+
+                previous = Some(separation);
+                time += coarse_step;
+            }
+        }
+    }
+
+    transits.sort_by_key(|t| t.center_time);
+    transits
+}
+
+/// Find the closest approach within a short window at fine time resolution
+fn refine_minimum(
+    satellite: &Satellite,
+    body: TransitBody,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    step: Duration,
+    observer: &Observer,
+) -> Option<Transit> {
+    let mut best: Option<Transit> = None;
+    let mut time = start;
+
+    while time <= end {
+        if let Some((separation, body_radius, elevation, range_km)) =
+            separation_at(satellite, body, time, observer)
+        {
+            if best.as_ref().is_none_or(|b| separation < b.separation_deg) {
+                best = Some(Transit {
+                    body,
+                    center_time: time,
+                    separation_deg: separation,
+                    body_radius_deg: body_radius,
+                    satellite_elevation: elevation,
+                    range_km,
+                    offset_km: range_km * separation.to_radians().sin(),
+                });
+            }
+        }
+        time += step;
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_transit_on_disk() {
+        let transit = Transit {
+            body: TransitBody::Sun,
+            center_time: Utc.with_ymd_and_hms(2026, 4, 24, 10, 0, 0).unwrap(),
+            separation_deg: 0.1,
+            body_radius_deg: 0.27,
+            satellite_elevation: 55.0,
+            range_km: 480.0,
+            offset_km: 480.0 * 0.1_f64.to_radians().sin(),
+        };
+        assert!(transit.is_on_disk());
+        assert!(transit.offset_km < 1.0);
+    }
+
+    #[test]
+    fn test_sun_angular_radius() {
+        let observer = Observer::new("Greenwich".to_string(), 51.4779, 0.0, 0.0);
+        let noon = Utc.with_ymd_and_hms(2024, 6, 21, 12, 0, 0).unwrap();
+        let (_, elevation, radius) = TransitBody::Sun.look(noon, &observer);
+        assert!(elevation > 0.0);
+        assert!((radius - 0.262).abs() < 0.01, "radius {}", radius);
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::database::Transponder;
+
+/// Whether a transponder file entry names the given tracked satellite.
+/// Frequency files use short names ("AO-91") while TLEs often carry the
+/// full designation ("FOX-1B (AO-91)"), so whole-word matches count too.
+pub fn matches_satellite(transponder_name: &str, satellite_name: &str) -> bool {
+    let wanted = transponder_name.trim().to_uppercase();
+    let name = satellite_name.to_uppercase();
+    if wanted.is_empty() {
+        return false;
+    }
+
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '-';
+    name.match_indices(&wanted).any(|(start, _)| {
+        let before = name[..start].chars().next_back();
+        let after = name[start + wanted.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+/// Pull a CTCSS tone such as "67.0 Hz", "67Hz" or "T67" out of free text
+fn parse_ctcss(text: &str) -> Option<f64> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    tokens.iter().enumerate().find_map(|(idx, token)| {
+        let token = token.trim_matches(|c: char| c == ',' || c == '(' || c == ')');
+        let upper = token.to_uppercase();
+        let value = if let Some(number) = upper.strip_suffix("HZ") {
+            number.parse().ok()
+        } else if let Some(number) = upper.strip_prefix('T') {
+            number.parse().ok()
+        } else if tokens
+            .get(idx + 1)
+            .is_some_and(|next| next.to_uppercase().starts_with("HZ"))
+        {
+            token.parse().ok()
+        } else {
+            None
+        };
+        value.filter(|hz: &f64| (60.0..=260.0).contains(hz))
+    })
+}
+
+/// Load a SatPC32 Doppler.sqf frequency file
+pub fn load_sqf<P: AsRef<Path>>(path: P) -> Result<Vec<Transponder>> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("reading frequency file '{}'", path.display()))?;
+    parse_sqf(&contents)
+}
+
+/// Parse Doppler.sqf lines of the form
+/// `name,downlink kHz,uplink kHz,downlink mode,uplink mode,NOR|REV,rx offset,tx offset,comment`.
+/// Blank lines and `;` comments are skipped; a zero frequency means none.
+pub fn parse_sqf(contents: &str) -> Result<Vec<Transponder>> {
+    let mut transponders = Vec::new();
+
+    for (line_no, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() < 6 {
+            return Err(anyhow::anyhow!(
+                "line {}: expected at least 6 comma-separated fields",
+                line_no + 1
+            ));
+        }
+
+        let frequency = |field: &str| -> Result<Option<f64>> {
+            let khz: f64 = field
+                .parse()
+                .with_context(|| format!("line {}: invalid frequency '{}'", line_no + 1, field))?;
+            Ok((khz > 0.0).then_some(khz / 1000.0))
+        };
+        let mode = |field: &str| (!field.is_empty()).then(|| field.to_uppercase());
+
+        let downlink_mode = mode(fields[3]);
+        let uplink_mode = mode(fields[4]);
+        let comment = fields
+            .get(8..)
+            .map(|rest| rest.join(","))
+            .unwrap_or_default();
+        let description = if !comment.is_empty() {
+            comment.clone()
+        } else {
+            match (&downlink_mode, &uplink_mode) {
+                (Some(down), Some(up)) if down != up => format!("{}/{}", up, down),
+                (Some(mode), _) | (None, Some(mode)) => mode.clone(),
+                (None, None) => "Unknown".to_string(),
+            }
+        };
+
+        transponders.push(Transponder {
+            id: None,
+            satellite_name: fields[0].to_string(),
+            description,
+            downlink_mhz: frequency(fields[1])?,
+            uplink_mhz: frequency(fields[2])?,
+            downlink_mode,
+            uplink_mode,
+            inverting: fields[5].eq_ignore_ascii_case("REV"),
+            ctcss_hz: parse_ctcss(&comment),
+            source: "sqf".to_string(),
+        });
+    }
+
+    Ok(transponders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQF: &str = "; SatPC32 frequency file\n\
+        AO-7,145950,432150,USB,LSB,REV,0,0,Mode B\n\
+        SO-50,436795,145850,FM,FM,NOR,0,0,67.0 Hz\n\
+        AO-91,145960,435250,FM,FM,NOR,0,0\n\
+        ISS,145800,0,FM,,NOR,0,0,SSTV\n";
+
+    #[test]
+    fn test_parse_sqf() {
+        let transponders = parse_sqf(SQF).unwrap();
+        assert_eq!(transponders.len(), 4);
+
+        let ao7 = &transponders[0];
+        assert!(ao7.inverting);
+        assert_eq!(ao7.downlink_mhz, Some(145.95));
+        assert_eq!(ao7.description, "Mode B");
+
+        assert_eq!(transponders[1].ctcss_hz, Some(67.0));
+        assert_eq!(transponders[2].description, "FM");
+        assert_eq!(transponders[3].uplink_mhz, None);
+
+        assert!(parse_sqf("AO-7,145950,432150").is_err());
+        assert!(parse_sqf("AO-7,abc,432150,USB,LSB,REV").is_err());
+    }
+
+    #[test]
+    fn test_parse_ctcss() {
+        assert_eq!(parse_ctcss("tone 67Hz"), Some(67.0));
+        assert_eq!(parse_ctcss("FM (T74.4)"), Some(74.4));
+        assert_eq!(parse_ctcss("Mode B"), None);
+        assert_eq!(parse_ctcss("9600 Hz"), None);
+    }
+
+    #[test]
+    fn test_matches_satellite() {
+        assert!(matches_satellite("AO-91", "FOX-1B (AO-91)"));
+        assert!(matches_satellite("iss", "ISS (ZARYA)"));
+        assert!(!matches_satellite("AO-7", "AO-73 (FUNCUBE-1)"));
+        assert!(!matches_satellite("", "ISS"));
+    }
+}
@@ -1,19 +1,52 @@
-use chrono::{Local, Utc};
+use chrono::{DateTime, Duration, Local, Utc};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Table},
+    widgets::{
+        Bar, BarChart, BarGroup, Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Table,
+        TableState,
+    },
 };
 
-use crate::radio::SignalStrength;
-use crate::{AppState, ConfigEditMode, ConfigField, TLE_SOURCES, UtilityMenuStatus};
+use crate::antenna;
+use crate::config::SkyMapMarker;
+use crate::constellation;
+use crate::coverage;
+use crate::database::PositionRecord;
+use crate::pass_prediction::SatellitePass;
+use crate::aprs;
+use crate::astro::{self, TwilightState};
+use crate::bandplan;
+use crate::elevation;
+use crate::geostationary;
+use crate::radio::{
+    self, PassPhase, SignalStrength, TuningAid, calculate_doppler_shift, format_mm_ss,
+};
+use crate::tle::{self, ChecksumStatus};
+use crate::tle_diff;
+use crate::transponder;
+use crate::rig;
+use crate::rotator;
+use crate::satnogs;
+use crate::satellite::SatellitePosition;
+use crate::{
+    AppState, CalibrationField, ConfigEditMode, ConfigField, ListInput, PassLogInput, TrackSpan,
+    TLE_SOURCES, UtilityMenuStatus, WindowField,
+};
 
 pub fn draw_ui(f: &mut Frame, app_state: &AppState) {
-    let has_alerts = !app_state.alerts.is_empty();
+    let clock_warnings = app_state.clock.warnings();
+    let has_alerts = !app_state.alerts.is_empty() || !app_state.conflicts.is_empty();
     let show_radio = app_state.config.radio.enabled && app_state.config.radio.show_doppler;
     let show_sky_map = app_state.config.display.show_sky_map;
+    let show_aprs = app_state.config.aprs.as_ref().is_some_and(|aprs| {
+        app_state
+            .satellites
+            .get(app_state.selected_satellite)
+            .is_some_and(|sat| aprs.digipeater_for(&sat.name).is_some())
+    });
 
     // Main horizontal split
     let main_chunks = if show_sky_map {
@@ -36,12 +69,21 @@ pub fn draw_ui(f: &mut Frame, app_state: &AppState) {
         Constraint::Length(5), // Header
     ];
 
+    if !clock_warnings.is_empty() {
+        left_constraints.push(Constraint::Length(clock_warnings.len() as u16 + 2)); // Clock banner
+    }
+
     if has_alerts {
-        left_constraints.push(Constraint::Length(4)); // Alerts
+        let lines = (app_state.alerts.len() + app_state.conflicts.len()).max(2);
+        left_constraints.push(Constraint::Length(lines.min(6) as u16 + 2)); // Alerts
     }
 
     if show_radio {
-        left_constraints.push(Constraint::Length(10)); // Radio info
+        left_constraints.push(Constraint::Length(13)); // Radio info
+    }
+
+    if show_aprs {
+        left_constraints.push(Constraint::Length(6)); // APRS helper
     }
 
     left_constraints.push(Constraint::Length(12)); // Real-time positions
@@ -60,6 +102,12 @@ pub fn draw_ui(f: &mut Frame, app_state: &AppState) {
     draw_header(f, left_chunks[chunk_idx], app_state);
     chunk_idx += 1;
 
+    // Draw the clock banner above everything that depends on the time
+    if !clock_warnings.is_empty() {
+        draw_clock_banner(f, left_chunks[chunk_idx], &clock_warnings);
+        chunk_idx += 1;
+    }
+
     // Draw alerts if any
     if has_alerts {
         draw_alerts(f, left_chunks[chunk_idx], app_state);
@@ -72,6 +120,12 @@ pub fn draw_ui(f: &mut Frame, app_state: &AppState) {
         chunk_idx += 1;
     }
 
+    // Draw APRS helper for digipeater satellites
+    if show_aprs {
+        draw_aprs_panel(f, left_chunks[chunk_idx], app_state);
+        chunk_idx += 1;
+    }
+
     // Draw real-time positions
     draw_realtime_positions(f, left_chunks[chunk_idx], app_state);
     chunk_idx += 1;
@@ -81,7 +135,7 @@ pub fn draw_ui(f: &mut Frame, app_state: &AppState) {
     chunk_idx += 1;
 
     // Draw footer
-    draw_footer(f, left_chunks[chunk_idx]);
+    draw_footer(f, left_chunks[chunk_idx], app_state);
 
     // Draw sky map and detailed info on right side if enabled
     // Draw sky map and detailed info on right side if enabled
@@ -102,25 +156,75 @@ pub fn draw_ui(f: &mut Frame, app_state: &AppState) {
 }
 
 fn draw_header(f: &mut Frame, area: Rect, app_state: &AppState) {
+    let sun_elevation = astro::sun_look_angles(Utc::now(), &app_state.observer).elevation;
+    let twilight = TwilightState::from_sun_elevation(sun_elevation);
+
     let header_text = vec![
-        Line::from(vec![
-            Span::styled("Observer: ", Style::default().fg(Color::Cyan)),
-            Span::raw(format!("{} ", app_state.observer.name)),
-            Span::styled("Location: ", Style::default().fg(Color::Cyan)),
-            Span::raw(format!(
-                "{:.4}°N, {:.4}°E, {:.0}m",
-                app_state.observer.latitude,
-                app_state.observer.longitude,
-                app_state.observer.altitude
-            )),
-        ]),
-        Line::from(vec![
-            Span::styled("Tracking: ", Style::default().fg(Color::Cyan)),
-            Span::raw(format!("{} satellites", app_state.satellites.len())),
-            Span::raw("  "),
-            Span::styled("Time: ", Style::default().fg(Color::Cyan)),
-            Span::raw(Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string()),
-        ]),
+        Line::from(rig_spans(
+            vec![
+                Span::styled("Observer: ", Style::default().fg(Color::Cyan)),
+                Span::raw(match &app_state.profile {
+                    Some(profile) => format!("{} ({}) ", app_state.observer.name, profile),
+                    None => format!("{} ", app_state.observer.name),
+                }),
+                Span::styled("Location: ", Style::default().fg(Color::Cyan)),
+                Span::raw(format!(
+                    "{:.4}°N, {:.4}°E, {:.0}m",
+                    app_state.observer.latitude,
+                    app_state.observer.longitude,
+                    app_state.observer.altitude
+                )),
+            ],
+            app_state,
+        )),
+        Line::from(time_source_spans(
+            vec![
+                Span::styled("Tracking: ", Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{} satellites", app_state.satellites.len())),
+                Span::raw(match &app_state.group_filter {
+                    Some(group) => {
+                        let shown = app_state
+                            .satellites
+                            .iter()
+                            .filter(|sat| crate::is_shown(app_state, &sat.name))
+                            .count();
+                        format!(", {} shown in {}", shown, group)
+                    }
+                    None => String::new(),
+                }),
+                Span::raw(if app_state.pending_launches.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({} pending launch)", app_state.pending_launches.len())
+                }),
+                Span::styled(
+                    if app_state.auto_follow { " AUTO-FOLLOW" } else { "" },
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    if crate::power_saving(app_state, Utc::now()) {
+                        " POWER SAVE"
+                    } else {
+                        ""
+                    },
+                    Style::default().fg(Color::Green),
+                ),
+                Span::raw("  "),
+                Span::styled("Time: ", Style::default().fg(Color::Cyan)),
+                Span::raw(Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string()),
+            ],
+            app_state,
+        )),
+        Line::from(rotator_spans(
+            vec![
+                Span::styled("Sun: ", Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{:.1}°  ", sun_elevation)),
+                Span::styled(twilight.as_str(), Style::default().fg(twilight_color(twilight))),
+            ],
+            app_state,
+        )),
     ];
 
     let header = Paragraph::new(header_text).block(
@@ -133,11 +237,151 @@ fn draw_header(f: &mut Frame, area: Rect, app_state: &AppState) {
     f.render_widget(header, area);
 }
 
+/// Append the time daemon's source and clock offset to a header line
+fn time_source_spans<'a>(mut spans: Vec<Span<'a>>, app_state: &'a AppState) -> Vec<Span<'a>> {
+    let (text, color) = match &app_state.clock.time_source {
+        None => return spans,
+        Some(Err(_)) => ("no time source".to_string(), Color::Gray),
+        Some(Ok(status)) => {
+            let color = match status.offset_seconds {
+                _ if !status.synchronized => Color::Red,
+                Some(offset) if offset.abs() > app_state.clock.config.max_offset_seconds => {
+                    Color::Red
+                }
+                // Doppler and AOS countdowns want the clock within a fraction of a second
+                Some(offset) if offset.abs() > 0.5 => Color::Yellow,
+                _ => Color::Green,
+            };
+            (status.label(), color)
+        }
+    };
+    spans.push(Span::styled(format!(" ({})", text), Style::default().fg(color)));
+    spans
+}
+
+/// Append the rig model and last mode change, the SDR tuning and whether a
+/// pass is being recorded to a header line
+fn rig_spans<'a>(mut spans: Vec<Span<'a>>, app_state: &'a AppState) -> Vec<Span<'a>> {
+    if let Some(rig) = &app_state.rig {
+        let label = match &rig.capabilities {
+            Some(caps) if !caps.model.is_empty() => format!("  {}: ", caps.model),
+            _ => "  Rig: ".to_string(),
+        };
+        let color = if rig.status.starts_with("Error") {
+            Color::Red
+        } else {
+            Color::Gray
+        };
+        spans.push(Span::styled(label, Style::default().fg(Color::Cyan)));
+        spans.push(Span::styled(rig.status.as_str(), Style::default().fg(color)));
+    }
+    if let Some(sdr) = &app_state.sdr {
+        let color = if sdr.status.starts_with("Error") {
+            Color::Red
+        } else if sdr.tuned_hz.is_some() {
+            Color::Green
+        } else {
+            Color::Gray
+        };
+        spans.push(Span::styled("  SDR: ", Style::default().fg(Color::Cyan)));
+        spans.push(Span::styled(sdr.status.as_str(), Style::default().fg(color)));
+    }
+    #[cfg(feature = "recording")]
+    if let Some(recorder) = &app_state.recorder {
+        if recorder.recording() {
+            spans.push(Span::styled("  ● REC", Style::default().fg(Color::Red)));
+        } else if recorder.status.starts_with("Error") {
+            spans.push(Span::styled(
+                format!("  Recorder: {}", recorder.status),
+                Style::default().fg(Color::Red),
+            ));
+        }
+    }
+    spans
+}
+
+/// Append the status of each configured rotator to a header line
+fn rotator_spans<'a>(mut spans: Vec<Span<'a>>, app_state: &'a AppState) -> Vec<Span<'a>> {
+    for rotator in &app_state.rotators {
+        let color = if rotator.status.starts_with("Error") {
+            Color::Red
+        } else if rotator.tracking {
+            Color::Green
+        } else {
+            Color::Gray
+        };
+        let label = if app_state.rotators.len() > 1 {
+            format!("{}: ", rotator.config.name)
+        } else {
+            "Rotator: ".to_string()
+        };
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(label, Style::default().fg(Color::Cyan)));
+        spans.push(Span::styled(rotator.status.as_str(), Style::default().fg(color)));
+        if rotator.override_pause {
+            spans.push(Span::styled(" [override]", Style::default().fg(Color::Yellow)));
+        }
+
+        if let Some(plan) = &rotator.plan {
+            if plan.flip {
+                spans.push(Span::styled(" [flip]", Style::default().fg(Color::Magenta)));
+            }
+            if let Some(warning) = plan.warning(rotator.config.max_pointing_error_deg) {
+                spans.push(Span::styled(
+                    format!("  ⚠ {}", warning),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+    }
+    spans
+}
+
+fn twilight_color(twilight: TwilightState) -> Color {
+    match twilight {
+        TwilightState::Day => Color::Yellow,
+        TwilightState::Civil => Color::LightYellow,
+        TwilightState::Nautical => Color::LightBlue,
+        TwilightState::Astronomical => Color::Blue,
+        TwilightState::Night => Color::Magenta,
+    }
+}
+
 fn draw_alerts(f: &mut Frame, area: Rect, app_state: &AppState) {
-    let alert_lines: Vec<Line> = app_state
+    let now = Utc::now();
+    // The last minute before AOS counts down in tenths and flashes twice a second
+    let flash_on = now.timestamp_subsec_millis() < 500;
+    let final_countdown = app_state
+        .alerts
+        .iter()
+        .any(|alert| alert.in_final_countdown(now));
+    let mut alert_lines: Vec<Line> = app_state
         .alerts
         .iter()
         .map(|alert| {
+            if alert.in_final_countdown(now) {
+                let style = if flash_on {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                };
+                return Line::from(vec![Span::styled(
+                    format!(
+                        "⚠ AOS: {} in {:.1} s (Max El: {:.1}°)",
+                        crate::display_name(app_state, &alert.satellite_name),
+                        alert.seconds_until(now),
+                        alert.pass.max_elevation
+                    ),
+                    style,
+                )]);
+            }
             Line::from(vec![
                 Span::styled(
                     "⚠ ALERT: ",
@@ -146,23 +390,194 @@ fn draw_alerts(f: &mut Frame, area: Rect, app_state: &AppState) {
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(format!(
-                    "{} pass in {} minutes (Max El: {:.1}°)",
-                    alert.satellite_name, alert.time_until_minutes, alert.pass.max_elevation
+                    "{} pass in {} (Max El: {:.1}°)",
+                    crate::display_name(app_state, &alert.satellite_name),
+                    alert.countdown(now),
+                    alert.pass.max_elevation
                 )),
             ])
         })
         .collect();
 
+    // Overlapping passes, with the one that keeps the rotator first
+    let pass_times = |pass: &SatellitePass| {
+        format!(
+            "{}-{}",
+            pass.aos_time.with_timezone(&Local).format("%H:%M"),
+            pass.los_time.with_timezone(&Local).format("%H:%M")
+        )
+    };
+    alert_lines.extend(app_state.conflicts.iter().map(|conflict| {
+        Line::from(vec![
+            Span::styled(
+                "⚡ CONFLICT: ",
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "{} {} overlaps {} {}; {} wins on {}",
+                conflict.winner,
+                pass_times(&conflict.winner_pass),
+                conflict.loser,
+                pass_times(&conflict.loser_pass),
+                conflict.winner,
+                if conflict.by_priority { "priority" } else { "elevation" }
+            )),
+        ])
+    }));
+
+    // Latest output of the event scripts
+    if let Some(line) = app_state.scripts.log.back() {
+        alert_lines.push(Line::from(vec![
+            Span::styled(
+                "SCRIPT: ",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(line.as_str()),
+        ]));
+    }
+
+    let border = if final_countdown && flash_on {
+        Color::Red
+    } else {
+        Color::Yellow
+    };
     let alerts = Paragraph::new(alert_lines).block(
         Block::default()
             .borders(Borders::ALL)
             .title("Upcoming pass alerts")
-            .style(Style::default().fg(Color::Yellow)),
+            .style(Style::default().fg(border)),
     );
 
     f.render_widget(alerts, area);
 }
 
+fn draw_clock_banner(f: &mut Frame, area: Rect, warnings: &[String]) {
+    let lines: Vec<Line> = warnings
+        .iter()
+        .map(|warning| {
+            Line::from(vec![
+                Span::styled("⚠ CLOCK: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(warning.as_str()),
+            ])
+        })
+        .collect();
+
+    let banner = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Predictions may be wrong")
+            .style(Style::default().fg(Color::White).bg(Color::Red)),
+    );
+
+    f.render_widget(banner, area);
+}
+
+/// Path, Doppler-corrected frequency and usable pass time for an APRS digipeater
+fn draw_aprs_panel(f: &mut Frame, area: Rect, app_state: &AppState) {
+    let Some(config) = &app_state.config.aprs else {
+        return;
+    };
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let Some(digipeater) = config.digipeater_for(&satellite.name) else {
+        return;
+    };
+    let now = Utc::now();
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Path:     ", Style::default().fg(Color::Cyan)),
+        Span::styled(
+            digipeater.path_for(&config.callsign),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ])];
+
+    let mut frequency = vec![
+        Span::styled("Freq:     ", Style::default().fg(Color::Cyan)),
+        Span::raw(format!("{:.3} MHz", digipeater.frequency_mhz)),
+    ];
+    if let Ok(pos) = satellite.calculate_position(now, &app_state.observer) {
+        let doppler =
+            calculate_doppler_shift(&pos, digipeater.frequency_mhz, digipeater.frequency_mhz);
+        frequency.push(Span::styled(
+            format!(
+                "  RX {:.4}  TX {:.4}",
+                doppler.downlink_observed_mhz, doppler.uplink_corrected_mhz
+            ),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    lines.push(Line::from(frequency));
+
+    let pass = satellite
+        .get_current_pass(now)
+        .or_else(|| satellite.get_next_pass());
+    let window = pass.and_then(|pass| {
+        aprs::high_elevation_window(satellite, pass, &app_state.observer, config.min_elevation)
+    });
+    let (usable, style) = match window {
+        Some((start, end)) if now >= start && now <= end => (
+            format!(
+                "{} left above {:.0}°",
+                format_mm_ss((end - now).num_seconds() as f64),
+                config.min_elevation
+            ),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Some((start, end)) if now < start => {
+            let minutes_until = (start - now).num_minutes();
+            let starts_in = if minutes_until > 60 {
+                format!("{}h {}m", minutes_until / 60, minutes_until % 60)
+            } else {
+                format_mm_ss((start - now).num_seconds() as f64)
+            };
+            (
+                format!(
+                    "{} above {:.0}° starts in {}",
+                    format_mm_ss((end - start).num_seconds() as f64),
+                    config.min_elevation,
+                    starts_in
+                ),
+                Style::default().fg(Color::Yellow),
+            )
+        }
+        Some(_) => (
+            "Past the usable part of this pass".to_string(),
+            Style::default().fg(Color::Gray),
+        ),
+        None => (
+            format!("Next pass stays below {:.0}°", config.min_elevation),
+            Style::default().fg(Color::Gray),
+        ),
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Usable:   ", Style::default().fg(Color::Cyan)),
+        Span::styled(usable, style),
+    ]));
+
+    if config.direwolf_command.is_some() {
+        lines.push(Line::from(vec![
+            Span::styled("Direwolf: ", Style::default().fg(Color::Cyan)),
+            Span::raw(app_state.direwolf.status.as_str()),
+        ]));
+    }
+
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("APRS")
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(panel, area);
+}
+
 fn draw_radio_info(f: &mut Frame, area: Rect, app_state: &AppState) {
     if app_state.current_positions.is_empty() {
         return;
@@ -174,25 +589,132 @@ fn draw_radio_info(f: &mut Frame, area: Rect, app_state: &AppState) {
 
     let mut info_lines = vec![Line::from(vec![
         Span::styled("Satellite: ", Style::default().fg(Color::Cyan)),
-        Span::raw(&selected_pos.name),
+        Span::raw(crate::display_name(app_state, &selected_pos.name)),
     ])];
 
     if let Some(doppler) = &selected_pos.doppler {
         info_lines.push(Line::from(vec![
             Span::styled("Downlink: ", Style::default().fg(Color::Green)),
             Span::raw(format!(
-                "{:.6} MHz (shift: {:+.0} Hz)",
-                doppler.downlink_observed_mhz, doppler.downlink_shift_hz
+                "{:.6} MHz = {} (shift: {:+.0} Hz)",
+                doppler.downlink_observed_mhz,
+                radio::format_hz(doppler.downlink_observed_mhz),
+                doppler.downlink_shift_hz
             )),
         ]));
 
+        let (nominal_downlink_mhz, _) = app_state.config.radio.frequencies_for(&selected_pos.name);
+        let offset_hz = radio::downlink_offset_hz(
+            &app_state.frequency_offsets,
+            &selected_pos.name,
+            nominal_downlink_mhz,
+        );
+        if offset_hz != 0.0 {
+            info_lines.push(Line::from(vec![
+                Span::styled("Offset:   ", Style::default().fg(Color::Green)),
+                Span::raw(format!("{:+.0} Hz", offset_hz)),
+                Span::styled("  (remembered, [/] to adjust)", Style::default().fg(Color::Gray)),
+            ]));
+        }
+
         info_lines.push(Line::from(vec![
             Span::styled("Uplink:   ", Style::default().fg(Color::Yellow)),
             Span::raw(format!(
-                "{:.6} MHz (correct to: {:.6} MHz)",
-                doppler.uplink_frequency_mhz, doppler.uplink_corrected_mhz
+                "{:.6} MHz (correct to: {:.6} MHz = {})",
+                doppler.uplink_frequency_mhz,
+                doppler.uplink_corrected_mhz,
+                radio::format_hz(doppler.uplink_corrected_mhz)
             )),
         ]));
+
+        if let Some(warning) = bandplan::uplink_warning(doppler.uplink_frequency_mhz) {
+            info_lines.push(Line::from(vec![
+                Span::styled(
+                    "⚠ Band:   ",
+                    Style::default()
+                        .fg(Color::Red)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(warning, Style::default().fg(Color::Red)),
+            ]));
+        }
+
+        if let Some(radio) = app_state.config.radio.for_satellite(&selected_pos.name) {
+            // Tones matter most while an FM pass is underway
+            let tone_style = if radio.is_fm() && selected_pos.is_visible {
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+
+            let mut xpdr_spans = vec![
+                Span::styled("Xpdr:     ", Style::default().fg(Color::Cyan)),
+                Span::raw(radio.mode.clone().unwrap_or_else(|| "-".to_string())),
+            ];
+            if let Some(tone) = radio.tone_description() {
+                xpdr_spans.push(Span::raw("  Tone: "));
+                xpdr_spans.push(Span::styled(tone, tone_style));
+            }
+            info_lines.push(Line::from(xpdr_spans));
+
+            if let Some(arming) = radio.arming_description(doppler.uplink_frequency_mhz) {
+                info_lines.push(Line::from(vec![
+                    Span::styled("Arm:      ", Style::default().fg(Color::Cyan)),
+                    Span::styled(arming, tone_style),
+                ]));
+            }
+        }
+
+        let now = Utc::now();
+        let satellite = app_state.satellites.get(app_state.selected_satellite);
+        if let Some((satellite, pass)) =
+            satellite.and_then(|sat| sat.get_current_pass(now).map(|pass| (sat, pass)))
+        {
+            let phase = PassPhase::new(pass, now, selected_pos.range_rate_km_s);
+            info_lines.push(Line::from(vec![
+                Span::styled("Phase:    ", Style::default().fg(Color::Cyan)),
+                Span::raw(phase.describe()),
+                Span::styled(
+                    format!("  LOS in {}", format_mm_ss(phase.remaining_seconds as f64)),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]));
+
+            // Downlink shift one second apart gives the rate the operator has to follow
+            let shift_at = |time| {
+                satellite
+                    .calculate_position(time, &app_state.observer)
+                    .ok()
+                    .map(|pos| {
+                        calculate_doppler_shift(
+                            &pos,
+                            doppler.downlink_frequency_mhz,
+                            doppler.uplink_frequency_mhz,
+                        )
+                        .downlink_shift_hz
+                    })
+            };
+            if let (Some(shift), Some(next_shift)) =
+                (shift_at(now), shift_at(now + Duration::seconds(1)))
+            {
+                let step_hz = app_state.config.radio.tuning_step_khz * 1000.0;
+                let aid = TuningAid::new(shift, next_shift - shift, step_hz);
+                let next_step = aid
+                    .seconds_to_next_step
+                    .map(|secs| format!(", next step in {}", format_mm_ss(secs)))
+                    .unwrap_or_default();
+                info_lines.push(Line::from(vec![
+                    Span::styled("Tune:     ", Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        format!("{:+.1} kHz channel", aid.channel_offset_hz / 1000.0),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(next_step),
+                ]));
+            }
+        }
     }
 
     if let Some(comm) = &selected_pos.comm_window {
@@ -209,7 +731,7 @@ fn draw_radio_info(f: &mut Frame, area: Rect, app_state: &AppState) {
             SignalStrength::NoSignal => Color::Red,
         };
 
-        info_lines.push(Line::from(vec![
+        let mut status_spans = vec![
             Span::styled("Status:   ", Style::default().fg(Color::Cyan)),
             Span::styled(
                 if comm.is_viable {
@@ -226,7 +748,57 @@ fn draw_radio_info(f: &mut Frame, area: Rect, app_state: &AppState) {
                 comm.signal_strength_estimate.as_str(),
                 Style::default().fg(signal_color),
             ),
-        ]));
+        ];
+        // Path loss on the downlink, how much of it the atmosphere absorbs,
+        // the antenna's gain toward the satellite, the local noise there and
+        // the margin all that leaves
+        if let Some(loss) = &comm.path_loss {
+            status_spans.push(Span::styled(
+                format!(
+                    "  Loss: {:.0} dB ({:.1} air)",
+                    loss.total_db(),
+                    loss.atmospheric_db
+                ),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        if let Some(gain) = &comm.station.antenna_gain {
+            status_spans.push(Span::styled(
+                format!("  Ant: {:+.1} dBi", gain.toward_dbi),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        if comm.station.noise_rise_db >= 0.1 {
+            status_spans.push(Span::styled(
+                format!("  Noise: +{:.1} dB", comm.station.noise_rise_db),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        if let Some(margin) = &comm.margin {
+            status_spans.push(Span::styled(
+                format!("  Margin: {:+.0} dB", margin.margin_db),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        info_lines.push(Line::from(status_spans));
+        // What the rig actually hears, next to the prediction above
+        if let Some(strength_db) = app_state
+            .rig
+            .as_ref()
+            .and_then(|rig| rig.strength_for(&selected_pos.name))
+        {
+            info_lines.push(Line::from(vec![
+                Span::styled("S-meter:  ", Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    rig::s_units(strength_db),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  ({:+.0} dB re S9)", strength_db),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]));
+        }
 
         if let Some(mode) = &comm.recommended_mode {
             info_lines.push(Line::from(vec![
@@ -235,6 +807,20 @@ fn draw_radio_info(f: &mut Frame, area: Rect, app_state: &AppState) {
             ]));
         }
 
+        let mismatch = comm.station.polarization.filter(|_| selected_pos.is_visible);
+        if let Some((mismatch, hint)) =
+            mismatch.and_then(|mismatch| mismatch.suggestion().map(|hint| (mismatch, hint)))
+        {
+            info_lines.push(Line::from(vec![
+                Span::styled("Pol:      ", Style::default().fg(Color::Cyan)),
+                Span::styled(hint, Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!(" ({:.1} dB lost)", mismatch.loss_db),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]));
+        }
+
         info_lines.push(Line::from(vec![
             Span::styled("Info:     ", Style::default().fg(Color::Gray)),
             Span::raw(&comm.reason),
@@ -265,7 +851,9 @@ fn draw_realtime_positions(f: &mut Frame, area: Rect, app_state: &AppState) {
         "Az",
         "El",
         "Range",
+        "Rng rate",
         "Status",
+        "Next hour",
     ]
     .iter()
     .map(|h| {
@@ -284,7 +872,10 @@ fn draw_realtime_positions(f: &mut Frame, area: Rect, app_state: &AppState) {
         &app_state.current_positions[app_state.selected_satellite..=app_state.selected_satellite]
     };
 
-    let rows = positions_to_show.iter().enumerate().map(|(_idx, pos)| {
+    let shown = positions_to_show
+        .iter()
+        .filter(|pos| crate::is_shown(app_state, &pos.name));
+    let rows = shown.map(|pos| {
         let status = if pos.is_visible {
             ("VISIBLE", Color::Green)
         } else {
@@ -298,7 +889,7 @@ fn draw_realtime_positions(f: &mut Frame, area: Rect, app_state: &AppState) {
                 .current_positions
                 .iter()
                 .position(|p| p.name == pos.name)
-                .map_or(false, |idx| idx == app_state.selected_satellite)
+                .is_some_and(|idx| idx == app_state.selected_satellite)
         };
 
         let style = if is_selected {
@@ -309,8 +900,16 @@ fn draw_realtime_positions(f: &mut Frame, area: Rect, app_state: &AppState) {
             Style::default()
         };
 
+        // Muted satellites are marked so a missing alert isn't a surprise
+        let name = crate::display_name(app_state, &pos.name);
+        let name = if app_state.muted_alerts.contains(&pos.name) {
+            format!("{} 🔕", name)
+        } else {
+            name.to_string()
+        };
+
         let cells = vec![
-            Cell::from(pos.name.clone()).style(style),
+            Cell::from(name).style(style),
             Cell::from(format!("{:.2}°", pos.latitude)),
             Cell::from(format!("{:.2}°", pos.longitude)),
             Cell::from(format!("{:.0} km", pos.altitude_km)),
@@ -318,7 +917,10 @@ fn draw_realtime_positions(f: &mut Frame, area: Rect, app_state: &AppState) {
             Cell::from(format!("{:.0}°", pos.azimuth)),
             Cell::from(format!("{:.1}°", pos.elevation)),
             Cell::from(format!("{:.0} km", pos.range_km)),
+            Cell::from(format!("{:+.2} km/s", pos.range_rate_km_s))
+                .style(Style::default().fg(range_rate_color(pos.range_rate_km_s))),
             Cell::from(status.0).style(Style::default().fg(status.1)),
+            elevation_sparkline(app_state, &pos.name),
         ];
 
         Row::new(cells).height(1).style(style)
@@ -335,7 +937,9 @@ fn draw_realtime_positions(f: &mut Frame, area: Rect, app_state: &AppState) {
             Constraint::Length(6),
             Constraint::Length(7),
             Constraint::Length(10),
+            Constraint::Length(11),
             Constraint::Length(14),
+            Constraint::Length(crate::OUTLOOK_COLUMNS as u16),
         ],
     )
     .header(header)
@@ -349,13 +953,71 @@ fn draw_realtime_positions(f: &mut Frame, area: Rect, app_state: &AppState) {
     f.render_widget(table, area);
 }
 
+/// Approaching satellites, whose downlink is shifted up, in green; receding
+/// ones in yellow
+fn range_rate_color(range_rate_km_s: f64) -> Color {
+    if range_rate_km_s < 0.0 {
+        Color::Green
+    } else {
+        Color::Yellow
+    }
+}
+
+/// Which way the satellite is moving relative to the observer, with the
+/// downlink Doppler shift it implies, whose sign should be the opposite
+fn range_rate_note(position: &SatellitePosition) -> String {
+    let direction = if position.range_rate_km_s < 0.0 {
+        "approaching"
+    } else {
+        "receding"
+    };
+    match &position.doppler {
+        Some(doppler) => format!(
+            " {}, downlink {:+.1} kHz",
+            direction,
+            doppler.downlink_shift_hz / 1000.0
+        ),
+        None => format!(" {}", direction),
+    }
+}
+
+/// Highest elevation over each few minutes of the coming hour, taller for
+/// higher passes, bright when one is worth an alert
+fn elevation_sparkline<'a>(app_state: &AppState, name: &str) -> Cell<'a> {
+    let Some(outlook) = app_state.elevation_outlooks.get(name) else {
+        return Cell::from("");
+    };
+    let line: String = outlook
+        .iter()
+        .map(|&elevation| match elevation {
+            e if e < 0.0 => '·',
+            e if e < 5.0 => '▁',
+            e if e < 10.0 => '▂',
+            e if e < 20.0 => '▃',
+            e if e < 30.0 => '▅',
+            e if e < 45.0 => '▆',
+            e if e < 60.0 => '▇',
+            _ => '█',
+        })
+        .collect();
+    let highest = outlook.iter().copied().fold(f64::MIN, f64::max);
+    let color = if highest >= app_state.config.alerts.min_elevation_for_alert {
+        Color::Yellow
+    } else if highest >= 0.0 {
+        Color::Green
+    } else {
+        Color::DarkGray
+    };
+    Cell::from(line).style(Style::default().fg(color))
+}
+
 fn draw_pass_table(f: &mut Frame, area: Rect, app_state: &AppState) {
     let selected_satellite = &app_state.satellites[app_state.selected_satellite];
     let passes = &selected_satellite.passes;
 
     let header_cells = [
-        "#", "AOS Time", "Max Time", "LOS Time", "Duration", "Max El", "AOS Az", "Max Az",
-        "LOS Az", "Range",
+        "#", "AOS Time", "±AOS", "Max Time", "LOS Time", "Duration", "Max El", "AOS Az",
+        "Max Az", "LOS Az", "Range", "Sky", "Moon",
     ]
     .iter()
     .map(|h| {
@@ -390,6 +1052,37 @@ fn draw_pass_table(f: &mut Frame, area: Rect, app_state: &AppState) {
             Style::default().fg(Color::DarkGray)
         };
 
+        // Visual passes (sunlit satellite, dark sky) stand out for naked-eye spotting
+        let sky_cell = if pass.is_visual() {
+            Cell::from("Visual").style(
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Cell::from(pass.twilight().short_str().to_string())
+                .style(Style::default().fg(twilight_color(pass.twilight())))
+        };
+
+        // Closest approach to the Moon, highlighted when it is near full
+        let moon_cell = if pass.near_full_moon() {
+            Cell::from(format!("☾ {:.0}°", pass.min_moon_separation)).style(
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Cell::from(format!("{:.0}°", pass.min_moon_separation))
+        };
+
+        // Slack to allow around AOS for the elements' age and drag
+        let slack = selected_satellite.timing_uncertainty_seconds(pass.aos_time);
+        let slack_cell = if slack >= 90.0 {
+            Cell::from(format!("±{:.0}m", slack / 60.0)).style(Style::default().fg(Color::Red))
+        } else {
+            Cell::from(format!("±{:.0}s", slack.max(1.0)))
+        };
+
         let cells = vec![
             Cell::from(format!("{}", i + 1)),
             Cell::from(
@@ -398,6 +1091,7 @@ fn draw_pass_table(f: &mut Frame, area: Rect, app_state: &AppState) {
                     .format("%m/%d %H:%M")
                     .to_string(),
             ),
+            slack_cell,
             Cell::from(
                 pass.max_elevation_time
                     .with_timezone(&Local)
@@ -416,6 +1110,8 @@ fn draw_pass_table(f: &mut Frame, area: Rect, app_state: &AppState) {
             Cell::from(format!("{:.0}°", pass.max_azimuth)),
             Cell::from(format!("{:.0}°", pass.los_azimuth)),
             Cell::from(format!("{:.0} km", pass.max_range_km)),
+            sky_cell,
+            moon_cell,
         ];
 
         Row::new(cells).height(1).style(style)
@@ -438,6 +1134,7 @@ fn draw_pass_table(f: &mut Frame, area: Rect, app_state: &AppState) {
         [
             Constraint::Length(3),
             Constraint::Length(12),
+            Constraint::Length(6),
             Constraint::Length(10),
             Constraint::Length(12),
             Constraint::Length(10),
@@ -446,15 +1143,20 @@ fn draw_pass_table(f: &mut Frame, area: Rect, app_state: &AppState) {
             Constraint::Length(8),
             Constraint::Length(8),
             Constraint::Length(10),
-        ],
+            Constraint::Length(7),
+            Constraint::Length(6),
+        ],
     )
     .header(header)
     .block(
         Block::default()
             .borders(Borders::ALL)
             .title(format!(
-                "Passes for: {}{}",
-                selected_satellite.name, next_pass_info
+                "Passes for: {}{}, elements {:.1} days old, ±{:.0} km along track now",
+                crate::display_name(app_state, &selected_satellite.name),
+                next_pass_info,
+                (now - selected_satellite.epoch).num_hours() as f64 / 24.0,
+                selected_satellite.along_track_uncertainty_km(now)
             ))
             .style(Style::default().fg(Color::White)),
     );
@@ -486,6 +1188,16 @@ fn draw_sky_map(f: &mut Frame, area: Rect, app_state: &AppState) {
         height: size + 2, // Add back the border space
     };
 
+    let marker_style = app_state.config.display.sky_map_marker;
+    let crowded: std::collections::HashSet<&str> = constellation::group(
+        &app_state.satellites,
+        app_state.config.display.constellation_min_size,
+    )
+    .iter()
+    .flat_map(|group| group.members.iter())
+    .filter_map(|&index| app_state.satellites.get(index))
+    .map(|satellite| satellite.name.as_str())
+    .collect();
     let canvas = Canvas::default()
         .block(
             Block::default()
@@ -495,7 +1207,11 @@ fn draw_sky_map(f: &mut Frame, area: Rect, app_state: &AppState) {
         )
         .x_bounds([-1.2, 1.2])
         .y_bounds([-1.2, 1.2])
-        .marker(symbols::Marker::Braille)
+        .marker(match marker_style {
+            SkyMapMarker::Braille => symbols::Marker::Braille,
+            SkyMapMarker::Block => symbols::Marker::Block,
+            SkyMapMarker::Dot | SkyMapMarker::Letters => symbols::Marker::Dot,
+        })
         .paint(|ctx| {
             // Draw horizon circle (outer edge)
             ctx.draw(&Circle {
@@ -557,10 +1273,80 @@ fn draw_sky_map(f: &mut Frame, area: Rect, app_state: &AppState) {
                 color: Color::Gray,
             });
 
+            // Draw the local horizon profile, if one is configured
+            if !app_state.observer.horizon.is_empty() {
+                let mask_point = |az: f64| {
+                    let el = app_state.observer.horizon.min_elevation_at(az).clamp(0.0, 90.0);
+                    sky_map_point(az, el)
+                };
+                for step in 0..72 {
+                    let (x1, y1) = mask_point(step as f64 * 5.0);
+                    let (x2, y2) = mask_point((step + 1) as f64 * 5.0);
+                    ctx.draw(&CanvasLine {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        color: Color::DarkGray,
+                    });
+                }
+            }
+
+            // Draw the antenna beam cone around its pointing direction
+            let selected = app_state.sky_map_positions.get(app_state.selected_satellite);
+            let antenna = selected.and_then(|pos| {
+                app_state
+                    .config
+                    .antenna_for(&pos.name, &app_state.config.satellite_bands(&pos.name))
+            });
+            if let Some(antenna) = antenna {
+                let target = selected
+                    .map(|pos| (pos.azimuth, pos.elevation))
+                    .unwrap_or((0.0, 90.0));
+                let pointing = antenna.pointing(target.0, target.1);
+                let outline = antenna.beam_outline(pointing, 48);
+                for segment in outline.windows(2) {
+                    let (az1, el1) = segment[0];
+                    let (az2, el2) = segment[1];
+                    if el1 < 0.0 || el2 < 0.0 {
+                        continue;
+                    }
+                    let (x1, y1) = sky_map_point(az1, el1);
+                    let (x2, y2) = sky_map_point(az2, el2);
+                    ctx.draw(&CanvasLine {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        color: Color::Blue,
+                    });
+                }
+            }
+
+            // Members of large constellations are shaded by how crowded
+            // their part of the sky is rather than drawn one by one
+            paint_density(
+                ctx,
+                &constellation::density(
+                    app_state
+                        .sky_map_positions
+                        .iter()
+                        .enumerate()
+                        .filter(|(idx, pos)| {
+                            crowded.contains(pos.name.as_str())
+                                && *idx != app_state.selected_satellite
+                        })
+                        .map(|(_, pos)| (pos.azimuth, pos.elevation)),
+                ),
+            );
+
             // Draw satellites
-            for (idx, pos) in app_state.current_positions.iter().enumerate() {
-                if !pos.is_visible {
-                    continue; // Skip satellites below horizon
+            for (idx, pos) in app_state.sky_map_positions.iter().enumerate() {
+                if !pos.is_visible || !crate::is_shown(app_state, &pos.name) {
+                    continue; // Skip satellites below horizon or outside the group shown
+                }
+                if crowded.contains(pos.name.as_str()) && idx != app_state.selected_satellite {
+                    continue;
                 }
 
                 // Convert azimuth/elevation to x,y coordinates
@@ -573,9 +1359,9 @@ fn draw_sky_map(f: &mut Frame, area: Rect, app_state: &AppState) {
                 let radius = (90.0 - pos.elevation) / 90.0;
 
                 // Convert to cartesian (rotate so North is up)
-                // Azimuth 0° (North) should point up (negative y)
+                // Azimuth 0° (North) should point up (canvas y grows upward)
                 let x = radius * azimuth_rad.sin();
-                let y = -radius * azimuth_rad.cos();
+                let y = radius * azimuth_rad.cos();
 
                 // Determine color based on selection and signal
                 let color = if idx == app_state.selected_satellite {
@@ -588,16 +1374,29 @@ fn draw_sky_map(f: &mut Frame, area: Rect, app_state: &AppState) {
                     Color::Red
                 };
 
-                // Draw satellite as a circle
-                ctx.draw(&Circle {
-                    x,
-                    y,
-                    radius: 0.05,
-                    color,
-                });
-
-                // Draw satellite marker
-                ctx.print(x, y, "●");
+                match marker_style {
+                    SkyMapMarker::Braille => {
+                        // Draw satellite as a circle
+                        ctx.draw(&Circle {
+                            x,
+                            y,
+                            radius: 0.05,
+                            color,
+                        });
+
+                        // Draw satellite marker
+                        ctx.print(x, y, "●");
+                    }
+                    // A circle drawn with coarser markers would hide the grid around it
+                    SkyMapMarker::Block | SkyMapMarker::Dot => {
+                        ctx.print(x, y, Span::styled("●", Style::default().fg(color)));
+                    }
+                    SkyMapMarker::Letters => {
+                        let letter = sky_map_letter(idx).to_string();
+                        let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+                        ctx.print(x, y, Span::styled(letter, style));
+                    }
+                }
             }
 
             // Draw labels for cardinal directions
@@ -639,31 +1438,41 @@ fn draw_sky_map(f: &mut Frame, area: Rect, app_state: &AppState) {
             Span::raw("El<20°"),
         ])];
 
-        // Add satellite names (limit to available space)
+        // Add names of satellites above the horizon (limit to available space)
         let max_sat_lines = legend_area.height.saturating_sub(1).min(4) as usize;
         for (idx, pos) in app_state
-            .current_positions
+            .sky_map_positions
             .iter()
             .enumerate()
+            .filter(|(idx, pos)| {
+                pos.is_visible
+                    && (!crowded.contains(pos.name.as_str())
+                        || *idx == app_state.selected_satellite)
+            })
             .take(max_sat_lines)
         {
-            if pos.is_visible {
-                let color = if idx == app_state.selected_satellite {
-                    Color::Cyan
-                } else {
-                    Color::White
-                };
+            let color = if idx == app_state.selected_satellite {
+                Color::Cyan
+            } else {
+                Color::White
+            };
 
-                legend_lines.push(Line::from(vec![
-                    Span::styled("● ", Style::default().fg(color)),
-                    Span::raw(format!(
-                        "{} ({:.0}°/{:.0}°)",
-                        pos.name.chars().take(10).collect::<String>(),
-                        pos.azimuth,
-                        pos.elevation
-                    )),
-                ]));
-            }
+            let marker = match marker_style {
+                SkyMapMarker::Letters => format!("{} ", sky_map_letter(idx)),
+                _ => "● ".to_string(),
+            };
+            legend_lines.push(Line::from(vec![
+                Span::styled(marker, Style::default().fg(color)),
+                Span::raw(format!(
+                    "{} ({:.0}°/{:.0}°)",
+                    crate::display_name(app_state, &pos.name)
+                        .chars()
+                        .take(10)
+                        .collect::<String>(),
+                    pos.azimuth,
+                    pos.elevation
+                )),
+            ]));
         }
 
         let legend = Paragraph::new(legend_lines).style(Style::default().fg(Color::White));
@@ -672,6 +1481,23 @@ fn draw_sky_map(f: &mut Frame, area: Rect, app_state: &AppState) {
     }
 }
 
+/// Letter marking a satellite on the sky map, by its index in the positions
+/// table: A to Z, then a to z, then `*` for the rest
+fn sky_map_letter(index: usize) -> char {
+    match index {
+        0..=25 => (b'A' + index as u8) as char,
+        26..=51 => (b'a' + (index - 26) as u8) as char,
+        _ => '*',
+    }
+}
+
+/// Project an azimuth/elevation onto the polar sky map (North up, zenith at center)
+fn sky_map_point(azimuth: f64, elevation: f64) -> (f64, f64) {
+    let radius = (90.0 - elevation) / 90.0;
+    let azimuth_rad = azimuth.to_radians();
+    (radius * azimuth_rad.sin(), radius * azimuth_rad.cos())
+}
+
 fn draw_satellite_details(f: &mut Frame, area: Rect, app_state: &AppState) {
     if app_state.current_positions.is_empty() || app_state.satellites.is_empty() {
         let empty = Paragraph::new("No satellite data available").block(
@@ -697,8 +1523,30 @@ fn draw_satellite_details(f: &mut Frame, area: Rect, app_state: &AppState) {
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw(&selected_pos.name),
+            Span::raw(crate::display_name(app_state, &selected_pos.name)),
         ]),
+    ];
+    if app_state.aliases.contains_key(&selected_pos.name) {
+        detail_lines.push(Line::from(vec![
+            Span::styled("TLE name: ", Style::default().fg(Color::Cyan)),
+            Span::raw(&selected_pos.name),
+        ]));
+    }
+
+    let groups: Vec<&str> = app_state
+        .groups
+        .iter()
+        .filter(|g| g.satellites.iter().any(|s| s == &selected_satellite.name))
+        .map(|g| g.name.as_str())
+        .collect();
+    if !groups.is_empty() {
+        detail_lines.push(Line::from(vec![
+            Span::styled("Groups:    ", Style::default().fg(Color::Cyan)),
+            Span::raw(groups.join(", ")),
+        ]));
+    }
+
+    detail_lines.extend([
         Line::from(""),
         Line::from(vec![Span::styled(
             "Position:",
@@ -771,6 +1619,14 @@ fn draw_satellite_details(f: &mut Frame, area: Rect, app_state: &AppState) {
                 Style::default().fg(Color::White),
             ),
         ]),
+        Line::from(vec![
+            Span::raw("  Rng rate:  "),
+            Span::styled(
+                format!("{:+.3} km/s", selected_pos.range_rate_km_s),
+                Style::default().fg(range_rate_color(selected_pos.range_rate_km_s)),
+            ),
+            Span::raw(range_rate_note(selected_pos)),
+        ]),
         Line::from(vec![
             Span::raw("  Status:    "),
             Span::styled(
@@ -786,7 +1642,18 @@ fn draw_satellite_details(f: &mut Frame, area: Rect, app_state: &AppState) {
                 }),
             ),
         ]),
-    ];
+    ]);
+
+    for field in app_state.plugins.position_fields(
+        selected_satellite,
+        selected_pos,
+        &app_state.observer,
+    ) {
+        detail_lines.push(Line::from(vec![
+            Span::raw(format!("  {:<11}", format!("{}:", field.label))),
+            Span::styled(field.value, Style::default().fg(Color::White)),
+        ]));
+    }
 
     // Add next pass info
     if let Some(next_pass) = selected_satellite.get_next_pass() {
@@ -837,6 +1704,94 @@ fn draw_satellite_details(f: &mut Frame, area: Rect, app_state: &AppState) {
                 Style::default().fg(Color::White),
             ),
         ]));
+
+        let bands = app_state.config.satellite_bands(&selected_satellite.name);
+        let antenna = app_state
+            .config
+            .antenna_for(&selected_satellite.name, &bands);
+        if let Some(antenna) = antenna.filter(|a| a.fixed) {
+            let coverage = antenna::pass_beam_coverage(
+                selected_satellite,
+                next_pass,
+                &app_state.observer,
+                antenna,
+            );
+            detail_lines.push(Line::from(vec![
+                Span::raw("  In beam:   "),
+                Span::styled(
+                    format!(
+                        "{:.1} of {:.1} min ({:.0}%)",
+                        coverage.inside_seconds / 60.0,
+                        coverage.total_seconds / 60.0,
+                        coverage.fraction() * 100.0
+                    ),
+                    Style::default().fg(Color::Blue),
+                ),
+            ]));
+        }
+
+        for note in app_state.plugins.pass_annotations(
+            selected_satellite,
+            next_pass,
+            &app_state.observer,
+        ) {
+            detail_lines.push(Line::from(vec![
+                Span::raw("  Note:      "),
+                Span::styled(note, Style::default().fg(Color::Magenta)),
+            ]));
+        }
+
+        if next_pass.near_full_moon() {
+            detail_lines.push(Line::from(vec![
+                Span::raw("  Moon:      "),
+                Span::styled(
+                    format!(
+                        "{:.0}° from {:.0}% Moon",
+                        next_pass.min_moon_separation,
+                        next_pass.moon_illumination * 100.0
+                    ),
+                    Style::default().fg(Color::LightYellow),
+                ),
+            ]));
+        }
+    }
+
+    let transponders: Vec<_> = app_state
+        .transponders
+        .iter()
+        .filter(|t| transponder::matches_satellite(&t.satellite_name, &selected_satellite.name))
+        .collect();
+    if !transponders.is_empty() {
+        detail_lines.push(Line::from(""));
+        detail_lines.push(Line::from(vec![Span::styled(
+            "Transponders:",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        let mhz = |freq: Option<f64>| {
+            freq.map(|f| format!("{:.3}", f))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        for t in transponders {
+            let mut spans = vec![
+                Span::raw(format!("  {:<12}", t.description)),
+                Span::styled(
+                    format!("↓{} ↑{}", mhz(t.downlink_mhz), mhz(t.uplink_mhz)),
+                    Style::default().fg(Color::White),
+                ),
+            ];
+            if t.inverting {
+                spans.push(Span::styled(" inv", Style::default().fg(Color::Gray)));
+            }
+            if let Some(hz) = t.ctcss_hz {
+                spans.push(Span::styled(
+                    format!(" {:.1} Hz", hz),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+            detail_lines.push(Line::from(spans));
+        }
     }
 
     let details = Paragraph::new(detail_lines).block(
@@ -849,23 +1804,32 @@ fn draw_satellite_details(f: &mut Frame, area: Rect, app_state: &AppState) {
     f.render_widget(details, area);
 }
 
-fn azimuth_to_cardinal(azimuth: f64) -> &'static str {
-    let az = azimuth % 360.0;
+/// Eight-point compass direction of an azimuth, e.g. "SE"
+pub fn azimuth_to_cardinal(azimuth: f64) -> &'static str {
+    let az = azimuth.rem_euclid(360.0);
     match az {
-        a if a >= 337.5 || a < 22.5 => "N",
-        a if a >= 22.5 && a < 67.5 => "NE",
-        a if a >= 67.5 && a < 112.5 => "E",
-        a if a >= 112.5 && a < 157.5 => "SE",
-        a if a >= 157.5 && a < 202.5 => "S",
-        a if a >= 202.5 && a < 247.5 => "SW",
-        a if a >= 247.5 && a < 292.5 => "W",
-        a if a >= 292.5 && a < 337.5 => "NW",
+        a if !(22.5..337.5).contains(&a) => "N",
+        a if (22.5..67.5).contains(&a) => "NE",
+        a if (67.5..112.5).contains(&a) => "E",
+        a if (112.5..157.5).contains(&a) => "SE",
+        a if (157.5..202.5).contains(&a) => "S",
+        a if (202.5..247.5).contains(&a) => "SW",
+        a if (247.5..292.5).contains(&a) => "W",
+        a if (292.5..337.5).contains(&a) => "NW",
         _ => "?",
     }
 }
 
-fn draw_footer(f: &mut Frame, area: Rect) {
-    let footer = Paragraph::new("↑/↓ or j/k: Select | c: Config | u: Utilities | q/ESC: Quit | Home/End: First/Last")
+fn draw_footer(f: &mut Frame, area: Rect, app_state: &AppState) {
+    if let Some(message) = &app_state.status_message {
+        let status = Paragraph::new(message.as_str())
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(status, area);
+        return;
+    }
+    let footer = Paragraph::new("↑/↓ or j/k: Select | n: Next AOS | h: Highest | c: Config | u: Utilities | s: Search | t: Transits | P: Pass window | v: TLE | g: Ground track | o: Orbit | C: Constellations | b: Band occupancy | G: Geostationary | w: Log pass | l: Logs | S: Stats | T: Telemetry | N: SatNOGS | r/R: Rotator | z: Park | O: Override pause | d: Rehearse pass | a: Auto-follow | m: Mute alerts | p: Profile | 1-9/0: Group | f: Focus | [/]: Offset | q/ESC: Quit | Home/End: First/Last")
         .style(Style::default().fg(Color::Gray))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
@@ -904,7 +1868,7 @@ fn draw_satellite_list(f: &mut Frame, area: Rect, app_state: &AppState) {
             Constraint::Length(3), // Header
             Constraint::Min(10),   // Content
             Constraint::Length(3), // Status
-            Constraint::Length(3), // Footer
+            Constraint::Length(4), // Footer, two lines of keys
         ])
         .split(area);
 
@@ -916,7 +1880,10 @@ fn draw_satellite_list(f: &mut Frame, area: Rect, app_state: &AppState) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw(format!(" ({} satellites)", state.satellites.len())),
+        Span::raw(match state.marked.len() {
+            0 => format!(" ({} satellites)", state.satellites.len()),
+            marked => format!(" ({} satellites, {} marked)", state.satellites.len(), marked),
+        }),
     ]))
     .alignment(Alignment::Center)
     .block(
@@ -940,7 +1907,7 @@ fn draw_satellite_list(f: &mut Frame, area: Rect, app_state: &AppState) {
                 );
         f.render_widget(empty_msg, chunks[1]);
     } else {
-        let header_cells = ["Name", "Type", "Country", "Operator", "Downlink", "Uplink"]
+        let header_cells = ["", "Name", "Type", "Country", "Operator", "Downlink", "Uplink"]
             .iter()
             .map(|h| {
                 Cell::from(*h).style(
@@ -954,15 +1921,21 @@ fn draw_satellite_list(f: &mut Frame, area: Rect, app_state: &AppState) {
 
         let rows = state.satellites.iter().enumerate().map(|(idx, sat)| {
             let is_selected = idx == state.selected_index;
+            let is_marked = sat.id.is_some_and(|id| state.marked.contains(&id));
             let style = if is_selected {
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD)
+            } else if is_marked {
+                Style::default().fg(Color::Yellow)
+            } else if sat.id.is_some_and(|id| state.search_matches.contains(&id)) {
+                Style::default().fg(Color::Green)
             } else {
                 Style::default()
             };
 
             let cells = vec![
+                Cell::from(if is_marked { "✓" } else { "" }),
                 Cell::from(truncate_string(&sat.name, 20)),
                 Cell::from(truncate_string(
                     sat.satellite_type.as_deref().unwrap_or("-"),
@@ -991,6 +1964,7 @@ fn draw_satellite_list(f: &mut Frame, area: Rect, app_state: &AppState) {
         let table = Table::new(
             rows,
             [
+                Constraint::Length(1),
                 Constraint::Length(22),
                 Constraint::Length(17),
                 Constraint::Length(14),
@@ -1007,11 +1981,25 @@ fn draw_satellite_list(f: &mut Frame, area: Rect, app_state: &AppState) {
                 .style(Style::default().fg(Color::White)),
         );
 
-        f.render_widget(table, chunks[1]);
+        // Keep the selection in view in long imported lists
+        let mut table_state = TableState::default().with_selected(Some(state.selected_index));
+        f.render_stateful_widget(table, chunks[1], &mut table_state);
     }
 
-    // Status message
-    let status_text = state.status_message.as_deref().unwrap_or("");
+    // Status message, or the search or bulk edit value being typed
+    let status_text = match state.list_input {
+        Some(ListInput::Search) => format!(
+            "Search name, alias, notes, operator, country and type: {}",
+            state.input_buffer.display(40)
+        ),
+        Some(ListInput::Bulk(edit)) => format!(
+            "{} for {}: {}",
+            edit.prompt(),
+            state.describe_targets(),
+            state.input_buffer.display(40)
+        ),
+        None => state.status_message.clone().unwrap_or_default(),
+    };
     let status = Paragraph::new(status_text)
         .style(Style::default().fg(Color::Yellow))
         .alignment(Alignment::Center)
@@ -1020,9 +2008,10 @@ fn draw_satellite_list(f: &mut Frame, area: Rect, app_state: &AppState) {
 
     // Footer with keybindings
     let footer =
-        Paragraph::new("a: Add | e/Enter: Edit | d/Del: Delete | ↑/↓: Navigate | q/ESC: Back")
+        Paragraph::new("a: Add | e/Enter: Edit | f: Fetch SATCAT | /: Search | n/N: Next/prev match | Space: Mark | *: Mark all | t: Type | o: Country | g: Group | d/Del: Delete | u: Undo delete | q/ESC: Back")
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true })
             .block(Block::default().borders(Borders::ALL));
     f.render_widget(footer, chunks[3]);
 }
@@ -1066,6 +2055,7 @@ fn draw_satellite_edit_form(f: &mut Frame, area: Rect, app_state: &AppState) {
     // Form fields
     let fields = [
         ConfigField::Name,
+        ConfigField::Alias,
         ConfigField::TleLine1,
         ConfigField::TleLine2,
         ConfigField::LaunchDate,
@@ -1083,9 +2073,9 @@ fn draw_satellite_edit_form(f: &mut Frame, area: Rect, app_state: &AppState) {
         .map(|field| {
             let is_current = *field == state.current_field;
             let value = if is_current {
-                format!("{}|", state.input_buffer)
+                state.input_buffer.display(55)
             } else {
-                state.get_field_value(*field)
+                truncate_string(&state.get_field_value(*field), 55)
             };
 
             let label_style = if is_current {
@@ -1106,12 +2096,30 @@ fn draw_satellite_edit_form(f: &mut Frame, area: Rect, app_state: &AppState) {
 
             let indicator = if is_current { "> " } else { "  " };
 
-            Line::from(vec![
+            let mut spans = vec![
                 Span::styled(indicator, label_style),
                 Span::styled(format!("{:16}", field.label()), label_style),
                 Span::raw(": "),
-                Span::styled(truncate_string(&value, 55), value_style),
-            ])
+                Span::styled(value, value_style),
+            ];
+            if is_current {
+                // The rest of a completion, then the field's format or unit
+                if let Some(completion) = state
+                    .completion()
+                    .filter(|_| state.input_buffer.at_end())
+                {
+                    let typed = state.input_buffer.value().chars().count();
+                    let rest: String = completion.chars().skip(typed).collect();
+                    spans.push(Span::styled(rest, Style::default().fg(Color::DarkGray)));
+                }
+                if let Some(hint) = field.hint() {
+                    spans.push(Span::styled(
+                        format!("  ({})", hint),
+                        Style::default().fg(Color::Gray),
+                    ));
+                }
+            }
+            Line::from(spans)
         })
         .collect();
 
@@ -1132,7 +2140,7 @@ fn draw_satellite_edit_form(f: &mut Frame, area: Rect, app_state: &AppState) {
     f.render_widget(status, chunks[2]);
 
     // Footer
-    let footer = Paragraph::new("Tab/↑↓: Next/Prev field | Enter: Save | ESC: Cancel")
+    let footer = Paragraph::new("Tab/↑↓: Next/Prev field | ←/→ Home/End, Ctrl+←/→: Move | Paste a TLE to fill both lines | Enter: Save | ESC: Cancel")
         .style(Style::default().fg(Color::Gray))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
@@ -1244,7 +2252,12 @@ pub fn draw_utility_menu(f: &mut Frame, app_state: &AppState) {
                 .title("Celestrak TLE Sources")
                 .style(Style::default().fg(Color::White)),
         );
-    f.render_widget(table, chunks[1]);
+    match &state.diff {
+        Some(diff) if state.status == UtilityMenuStatus::Success => {
+            draw_tle_diff(f, chunks[1], diff)
+        }
+        _ => f.render_widget(table, chunks[1]),
+    }
 
     // Status area — progress bar while downloading, text otherwise
     if state.status == UtilityMenuStatus::Downloading {
@@ -1312,3 +2325,3319 @@ pub fn draw_utility_menu(f: &mut Frame, app_state: &AppState) {
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(footer, chunks[3]);
 }
+
+/// What the last download changed: satellites added and removed, and orbits
+/// that jumped
+fn draw_tle_diff(f: &mut Frame, area: Rect, diff: &tle_diff::TleDiff) {
+    let names = |names: &[String]| {
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("Added ({}): ", diff.added.len()),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(names(&diff.added)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("Removed ({}): ", diff.removed.len()),
+                Style::default().fg(Color::Red),
+            ),
+            Span::raw(names(&diff.removed)),
+        ]),
+        Line::from(vec![
+            Span::styled("Epochs: ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!(
+                "{} updated, {} unchanged",
+                diff.updated, diff.unchanged
+            )),
+        ]),
+        Line::from(""),
+    ];
+    if diff.jumps.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No orbit changed more than drag explains",
+            Style::default().fg(Color::Gray),
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            "Orbit changes beyond drag (likely maneuvers):",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    for jump in &diff.jumps {
+        lines.push(Line::from(format!(
+            "  {} ({}): mean motion {:+.4} rev/day, inclination {:+.3}° between {} and {}",
+            jump.name,
+            jump.norad_id,
+            jump.mean_motion_change,
+            jump.inclination_change,
+            jump.old_epoch.format("%m-%d %H:%M"),
+            jump.new_epoch.format("%m-%d %H:%M")
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Changes since the last download")
+                .style(Style::default().fg(Color::White)),
+        );
+    f.render_widget(paragraph, area);
+}
+
+/// Draw the raw TLE of the selected satellite with decoded epoch and checksums
+pub fn draw_tle_viewer(f: &mut Frame, app_state: &AppState) {
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let state = &app_state.tle_viewer_state;
+
+    let area = centered_rect(80, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(9),    // TLE and decoded fields
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let checksum_span = |line: &str| match tle::verify_checksum(line) {
+        ChecksumStatus::Valid => Span::styled("checksum OK", Style::default().fg(Color::Green)),
+        ChecksumStatus::Mismatch { expected, found } => Span::styled(
+            format!("checksum MISMATCH (expected {}, found {})", expected, found),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        ChecksumStatus::Missing => {
+            Span::styled("checksum missing", Style::default().fg(Color::Yellow))
+        }
+    };
+
+    let age_days = (Utc::now() - satellite.epoch).num_seconds() as f64 / 86400.0;
+    let tle_style = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let lines = vec![
+        Line::from(Span::styled(satellite.name.clone(), tle_style)),
+        Line::from(Span::styled(satellite.tle_line1.clone(), tle_style)),
+        Line::from(Span::styled(satellite.tle_line2.clone(), tle_style)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Epoch:  ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!(
+                "{} ({:.1} days old)",
+                satellite.epoch.format("%Y-%m-%d %H:%M:%S UTC"),
+                age_days
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Line 1: ", Style::default().fg(Color::Cyan)),
+            checksum_span(&satellite.tle_line1),
+        ]),
+        Line::from(vec![
+            Span::styled("Line 2: ", Style::default().fg(Color::Cyan)),
+            checksum_span(&satellite.tle_line2),
+        ]),
+        Line::from(Span::styled(
+            state.status_message.clone().unwrap_or_default(),
+            Style::default().fg(Color::Yellow),
+        )),
+    ];
+
+    let body = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Two-Line Elements")
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(body, chunks[0]);
+
+    let footer = Paragraph::new("y/c: Copy to clipboard | q/ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[1]);
+}
+
+pub fn draw_ground_track(f: &mut Frame, app_state: &AppState) {
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let state = &app_state.ground_track_state;
+
+    let area = centered_rect(80, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(6),    // Track points
+            Constraint::Length(3), // Status message
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+    // The world map goes beside the table when there is room for both
+    let (table_area, map_area) = if chunks[0].width >= 110 {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(72), Constraint::Min(30)])
+            .split(chunks[0]);
+        (columns[0], Some(columns[1]))
+    } else {
+        (chunks[0], None)
+    };
+
+    let header_cells = [
+        "Time",
+        "Latitude",
+        "Longitude",
+        "Alt km",
+        "Grid",
+        "Az",
+        "El",
+    ]
+    .iter()
+    .map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let rows = state.points.iter().enumerate().map(|(idx, point)| {
+        let style = if idx == state.selected_index {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else if point.is_visible {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default()
+        };
+        let hemisphere = |value: f64, positive: char, negative: char| {
+            let letter = if value < 0.0 { negative } else { positive };
+            format!("{:.2}°{}", value.abs(), letter)
+        };
+        let cells = vec![
+            Cell::from(
+                point
+                    .time
+                    .with_timezone(&Local)
+                    .format("%m/%d %H:%M:%S")
+                    .to_string(),
+            ),
+            Cell::from(hemisphere(point.latitude, 'N', 'S')),
+            Cell::from(hemisphere(point.longitude, 'E', 'W')),
+            Cell::from(format!("{:.0}", point.altitude_km)),
+            Cell::from(elevation::latlon_to_grid(point.latitude, point.longitude)),
+            Cell::from(format!("{:.0}°", point.azimuth)),
+            Cell::from(format!("{:.1}°", point.elevation)),
+        ];
+        Row::new(cells).height(1).style(style)
+    });
+
+    let span = match state.span {
+        TrackSpan::Orbit => "next orbit",
+        TrackSpan::Pass => "next pass",
+    };
+    let title = format!(
+        "Ground track of {} ({}, every {}; green = above horizon)",
+        crate::display_name(app_state, &satellite.name),
+        span,
+        format_step(state.step_seconds())
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(15),
+            Constraint::Length(10),
+            Constraint::Length(11),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Min(6),
+        ],
+    )
+    .header(header_row)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().fg(Color::White)),
+    );
+    let mut table_state = TableState::default();
+    if !state.points.is_empty() {
+        table_state.select(Some(state.selected_index));
+    }
+    f.render_stateful_widget(table, table_area, &mut table_state);
+    if let Some(map_area) = map_area {
+        draw_world_map(f, map_area, app_state);
+    }
+
+    let status = Paragraph::new(state.status_message.as_deref().unwrap_or_default())
+        .style(Style::default().fg(Color::Yellow))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[1]);
+
+    let footer = Paragraph::new(
+        "↑/↓: Scroll | Tab: Orbit/pass | +/-: Interval | e: Export CSV | r: Refresh | q/ESC: Close",
+    )
+    .style(Style::default().fg(Color::Gray))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
+
+/// The ground track on a world map with the night side shaded, the day/night
+/// terminator and the sub-solar point, so eclipse entry and exit and gray-line
+/// conditions can be seen
+fn draw_world_map(f: &mut Frame, area: Rect, app_state: &AppState) {
+    use ratatui::symbols;
+    use ratatui::widgets::canvas::{Canvas, Line as CanvasLine, Map, MapResolution, Points};
+
+    let state = &app_state.ground_track_state;
+    let satellite = &app_state.satellites[app_state.selected_satellite];
+    let now = Utc::now();
+    let subsolar = astro::subsolar_point(now);
+
+    let mut night = Vec::new();
+    for lat in (-88..=88).step_by(4) {
+        for lon in (-178..=178).step_by(4) {
+            if astro::is_night(lat as f64, lon as f64, subsolar) {
+                night.push((lon as f64, lat as f64));
+            }
+        }
+    }
+    let terminator: Vec<(f64, f64)> = astro::terminator(now, 1.0);
+    // Runs of the track in sunlight and in the Earth's shadow, each sharing its
+    // last point with the next so the line is unbroken, then split at the
+    // antimeridian
+    let mut runs: Vec<(bool, Vec<(f64, f64)>)> = Vec::new();
+    for point in &state.points {
+        let lit = satellite
+            .teme_state(point.time)
+            .map(|(position, _)| astro::is_sunlit(&position, point.time))
+            .unwrap_or(true);
+        let coords = (point.latitude, point.longitude);
+        match runs.last_mut() {
+            Some((run_lit, run)) if *run_lit == lit => run.push(coords),
+            Some((_, run)) => {
+                run.push(coords);
+                runs.push((lit, vec![coords]));
+            }
+            None => runs.push((lit, vec![coords])),
+        }
+    }
+    let track = runs
+        .iter()
+        .map(|(lit, run)| {
+            let color = if *lit { Color::Yellow } else { Color::DarkGray };
+            (color, crate::satellite::split_ground_track(run))
+        })
+        .collect::<Vec<_>>();
+    let selected = state
+        .points
+        .get(state.selected_index)
+        .map(|point| (point.longitude, point.latitude));
+
+    let canvas = Canvas::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Map (yellow: sunlit, grey: eclipsed)")
+                .style(Style::default().fg(Color::White)),
+        )
+        .x_bounds([-180.0, 180.0])
+        .y_bounds([-90.0, 90.0])
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            ctx.draw(&Points {
+                coords: &night,
+                color: Color::DarkGray,
+            });
+            ctx.draw(&Map {
+                resolution: MapResolution::High,
+                color: Color::Gray,
+            });
+            ctx.layer();
+            ctx.draw(&Points {
+                coords: &terminator,
+                color: Color::Magenta,
+            });
+            for (color, segments) in &track {
+                for segment in segments {
+                    for pair in segment.windows(2) {
+                        ctx.draw(&CanvasLine {
+                            x1: pair[0].1,
+                            y1: pair[0].0,
+                            x2: pair[1].1,
+                            y2: pair[1].0,
+                            color: *color,
+                        });
+                    }
+                }
+            }
+            ctx.print(
+                subsolar.1,
+                subsolar.0,
+                Span::styled("☼", Style::default().fg(Color::Yellow)),
+            );
+            ctx.print(
+                app_state.observer.longitude,
+                app_state.observer.latitude,
+                Span::styled("+", Style::default().fg(Color::Green)),
+            );
+            if let Some((lon, lat)) = selected {
+                ctx.print(lon, lat, Span::styled("●", Style::default().fg(Color::Cyan)));
+            }
+        });
+    f.render_widget(canvas, area);
+}
+
+/// Shade the sky cells holding satellites, brighter the more there are
+fn paint_density(
+    ctx: &mut ratatui::widgets::canvas::Context,
+    cells: &std::collections::BTreeMap<(u16, u16), usize>,
+) {
+    use ratatui::widgets::canvas::Points;
+
+    for (&(azimuth, elevation), &count) in cells {
+        let color = match count {
+            1 => Color::Blue,
+            2..=3 => Color::Cyan,
+            4..=7 => Color::Yellow,
+            _ => Color::Red,
+        };
+        // Fill the cell finely enough for every braille dot in it
+        let mut coords = Vec::new();
+        for step_el in 0..20 {
+            let el = elevation as f64 + constellation::CELL_ELEVATION * step_el as f64 / 20.0;
+            let steps_az = (40.0 * (90.0 - el) / 90.0).ceil().max(1.0) as usize;
+            for step_az in 0..steps_az {
+                let az = azimuth as f64
+                    + constellation::CELL_AZIMUTH * step_az as f64 / steps_az as f64;
+                coords.push(sky_map_point(az, el));
+            }
+        }
+        ctx.draw(&Points {
+            coords: &coords,
+            color,
+        });
+    }
+}
+
+/// Downlinks of the satellites above the horizon by the frequency they are
+/// heard on now, to tell which one a signal on a waterfall belongs to
+pub fn draw_band_occupancy(f: &mut Frame, app_state: &AppState) {
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // Downlinks
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let footer = Paragraph::new("↑/↓: Select | Enter: Select satellite | q/ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[1]);
+
+    let title = "Downlinks of visible satellites by frequency heard now";
+    let downlinks = crate::band_occupancy(app_state);
+    if downlinks.is_empty() {
+        let message = Paragraph::new(
+            "No satellite with a known downlink is above the horizon. Downlinks come from \
+             imported transponders, [radio.satellites] and the satellite editor.",
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(message, chunks[0]);
+        return;
+    }
+    let selected_index = app_state
+        .band_occupancy_state
+        .selected_index
+        .min(downlinks.len() - 1);
+
+    let header_cells = ["Heard on", "Shift", "Nominal", "Satellite", "Downlink", "Mode", "El"]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+    let rows = downlinks.iter().enumerate().map(|(idx, downlink)| {
+        let style = if idx == selected_index {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(format!("{:.4} MHz", downlink.observed_mhz)),
+            Cell::from(format!("{:+.1} kHz", downlink.shift_khz())),
+            Cell::from(format!("{:.4}", downlink.nominal_mhz)),
+            Cell::from(truncate_string(
+                crate::display_name(app_state, &downlink.satellite),
+                22,
+            )),
+            Cell::from(truncate_string(&downlink.description, 24)),
+            Cell::from(downlink.mode.clone().unwrap_or_else(|| "-".to_string())),
+            Cell::from(format!("{:.0}°", downlink.elevation)),
+        ])
+        .style(style)
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(11),
+            Constraint::Length(10),
+            Constraint::Length(23),
+            Constraint::Min(12),
+            Constraint::Length(6),
+            Constraint::Length(5),
+        ],
+    )
+    .header(Row::new(header_cells).bottom_margin(1))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().fg(Color::White)),
+    );
+    let mut table_state = TableState::default().with_selected(Some(selected_index));
+    f.render_stateful_widget(table, chunks[0], &mut table_state);
+}
+
+/// Fixed dish pointing for geostationary satellites such as QO-100, with the
+/// LNB's skew and where each part of the transponder lands on the receiver
+pub fn draw_geostationary(f: &mut Frame, app_state: &AppState) {
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(10), // Slots and pointing
+            Constraint::Min(5),     // Passband
+            Constraint::Length(8),  // Sun outages
+            Constraint::Length(3),  // Footer
+        ])
+        .split(area);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(28), Constraint::Min(30)])
+        .split(chunks[0]);
+
+    let state = &app_state.geostationary_state;
+    let footer_text = if state.editing {
+        "Type the IF the beacon is heard on, in MHz | Enter: Set offset | ESC: Cancel"
+    } else {
+        "↑/↓: Select | o: Measure LNB offset on the beacon | 0: Clear offset | q/ESC: Close"
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[3]);
+
+    let slots = crate::geostationary_slots(app_state);
+    if slots.is_empty() {
+        let message = Paragraph::new(
+            "No geostationary satellites. Add them under [[geostationary.satellites]] or \
+             track one.",
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Geostationary"));
+        f.render_widget(message, chunks[0]);
+        return;
+    }
+    let selected_index = state.selected_index.min(slots.len() - 1);
+    let slot = &slots[selected_index];
+
+    let rows = slots.iter().enumerate().map(|(idx, slot)| {
+        let style = if idx == selected_index {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let longitude = format!(
+            "{:.1}°{}",
+            slot.longitude.abs(),
+            if slot.longitude < 0.0 { "W" } else { "E" }
+        );
+        Row::new(vec![
+            Cell::from(truncate_string(&slot.name, 16)),
+            Cell::from(longitude),
+        ])
+        .style(style)
+    });
+    let table = Table::new(rows, [Constraint::Min(10), Constraint::Length(7)]).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Geostationary")
+            .style(Style::default().fg(Color::White)),
+    );
+    let mut table_state = TableState::default().with_selected(Some(selected_index));
+    f.render_stateful_widget(table, top[0], &mut table_state);
+
+    let settings = &app_state.config.geostationary;
+    let pointing = geostationary::pointing(&app_state.observer, slot.longitude);
+    let mut lines = vec![
+        Line::from(format!(
+            "Azimuth {:.1}° {} (true)   Elevation {:.1}°   Range {:.0} km",
+            pointing.azimuth,
+            azimuth_to_cardinal(pointing.azimuth),
+            pointing.elevation,
+            pointing.range_km
+        )),
+        Line::from(format!(
+            "LNB skew: {} (seen from behind the dish)",
+            geostationary::describe_skew(pointing.skew)
+        )),
+        Line::from(format!(
+            "LNB LO {:.3} MHz, {:+.0} Hz off",
+            settings.lnb_lo_mhz, state.lnb_offset_hz
+        )),
+    ];
+    if let Some(beacon_mhz) = slot.beacon_mhz {
+        let beacon_if =
+            geostationary::intermediate_mhz(beacon_mhz, settings.lnb_lo_mhz, state.lnb_offset_hz);
+        lines.push(Line::from(format!(
+            "Beacon {:.4} MHz, heard on {:.4} MHz IF",
+            beacon_mhz, beacon_if
+        )));
+    }
+    let now = Utc::now();
+    if pointing.elevation < 0.0 {
+        lines.push(Line::from(Span::styled(
+            "Below the horizon from here",
+            Style::default().fg(Color::Red),
+        )));
+    } else if let Some(outage) = state
+        .sun_outages
+        .iter()
+        .find(|outage| outage.start <= now && now <= outage.end)
+    {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Sun outage until {}",
+                outage.end.with_timezone(&Local).format("%H:%M:%S")
+            ),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+    }
+    if state.editing {
+        lines.push(Line::from(Span::styled(
+            format!("Beacon heard on: {}_ MHz", state.input_buffer),
+            Style::default().fg(Color::Yellow),
+        )));
+    } else if let Some(message) = &state.status_message {
+        lines.push(Line::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Green),
+        )));
+    }
+    let details = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Pointing at {}", slot.name)),
+        );
+    f.render_widget(details, top[1]);
+
+    // A transverter or upconverter's IF only matters when there is one
+    let transverter = settings.uplink_lo_mhz > 0.0;
+    let mut headers = vec!["Segment", "Downlink", "Receiver IF", "Uplink"];
+    if transverter {
+        headers.push("Transmit IF");
+    }
+    let header_cells = headers.iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    let range = |from: f64, to: f64| format!("{:.3}-{:.3}", from, to);
+    let rows = slot.segments.iter().map(|segment| {
+        let (from, to) = (segment.from_mhz, segment.to_mhz);
+        let receive = |rf: f64| {
+            geostationary::intermediate_mhz(rf, settings.lnb_lo_mhz, state.lnb_offset_hz)
+        };
+        let mut cells = vec![
+            Cell::from(segment.label.clone()),
+            Cell::from(range(from, to)),
+            Cell::from(range(receive(from), receive(to))),
+        ];
+        if slot.uplink_offset_mhz != 0.0 {
+            let (up_from, up_to) = (from + slot.uplink_offset_mhz, to + slot.uplink_offset_mhz);
+            cells.push(Cell::from(range(up_from, up_to)));
+            if transverter {
+                let transmit =
+                    |rf: f64| geostationary::intermediate_mhz(rf, settings.uplink_lo_mhz, 0.0);
+                cells.push(Cell::from(range(transmit(up_from), transmit(up_to))));
+            }
+        }
+        Row::new(cells)
+    });
+    let mut widths = vec![
+        Constraint::Min(16),
+        Constraint::Length(22),
+        Constraint::Length(20),
+        Constraint::Length(20),
+    ];
+    if transverter {
+        widths.push(Constraint::Length(20));
+    }
+    let passband = if slot.segments.is_empty() {
+        "Passband: none configured"
+    } else {
+        "Passband (MHz)"
+    };
+    let table = Table::new(rows, widths)
+        .header(Row::new(header_cells).bottom_margin(1))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(passband)
+                .style(Style::default().fg(Color::White)),
+        );
+    f.render_widget(table, chunks[1]);
+
+    let upcoming: Vec<Line> = state
+        .sun_outages
+        .iter()
+        .filter(|outage| outage.end >= now)
+        .take(chunks[2].height.saturating_sub(2) as usize)
+        .map(|outage| {
+            let start = outage.start.with_timezone(&Local);
+            Line::from(format!(
+                "{}  {}-{}  closest {:.1}° at {}",
+                start.format("%a %Y-%m-%d"),
+                start.format("%H:%M"),
+                outage.end.with_timezone(&Local).format("%H:%M"),
+                outage.min_separation,
+                outage.peak.with_timezone(&Local).format("%H:%M:%S")
+            ))
+        })
+        .collect();
+    let title = format!("Sun outages (Sun within {:.1}°)", settings.sun_outage_deg);
+    let outages = if upcoming.is_empty() {
+        Paragraph::new("None in the next year")
+    } else {
+        Paragraph::new(upcoming)
+    };
+    f.render_widget(
+        outages.block(Block::default().borders(Borders::ALL).title(title)),
+        chunks[2],
+    );
+}
+
+/// Large constellations: members, how many are up, where they crowd the sky,
+/// and the next passes of any of them
+pub fn draw_constellations(f: &mut Frame, app_state: &AppState) {
+    use ratatui::widgets::canvas::{Canvas, Circle};
+
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let constellations = constellation::group(
+        &app_state.satellites,
+        app_state.config.display.constellation_min_size,
+    );
+    let now = Utc::now();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(10),    // Constellations and density map
+            Constraint::Length(13), // Next passes
+            Constraint::Length(3),  // Footer
+        ])
+        .split(area);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[0]);
+
+    let footer = Paragraph::new("↑/↓: Select constellation | q/ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+
+    if constellations.is_empty() {
+        let message = Paragraph::new(format!(
+            "No constellations among the tracked satellites. Names sharing a prefix, \
+             such as STARLINK-1007, count once at least {} are tracked \
+             (constellation_min_size under [display]).",
+            app_state.config.display.constellation_min_size
+        ))
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Constellations"));
+        f.render_widget(message, chunks[0]);
+        return;
+    }
+    let selected_index = app_state
+        .constellation_state
+        .selected_index
+        .min(constellations.len() - 1);
+    let selected = &constellations[selected_index];
+
+    let look_angles = |members: &[usize]| -> Vec<(f64, f64)> {
+        members
+            .iter()
+            .filter_map(|&index| app_state.satellites.get(index))
+            .filter_map(|satellite| {
+                app_state
+                    .current_positions
+                    .iter()
+                    .find(|pos| pos.name == satellite.name)
+            })
+            .map(|pos| (pos.azimuth, pos.elevation))
+            .collect()
+    };
+
+    let header_cells = ["Constellation", "Members", "Up now", "Next AOS", "Max El"]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+    let rows = constellations.iter().enumerate().map(|(idx, group)| {
+        let up = look_angles(&group.members)
+            .iter()
+            .filter(|(_, elevation)| *elevation > 0.0)
+            .count();
+        let next = constellation::next_passes(&app_state.satellites, group, now, 1);
+        let (aos, max_el) = match next.first() {
+            Some((_, pass)) if pass.aos_time <= now => ("now".to_string(), pass.max_elevation),
+            Some((_, pass)) => (
+                pass.aos_time.with_timezone(&Local).format("%H:%M:%S").to_string(),
+                pass.max_elevation,
+            ),
+            None => ("-".to_string(), f64::NAN),
+        };
+        let style = if idx == selected_index {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(group.name.clone()),
+            Cell::from(group.members.len().to_string()),
+            Cell::from(up.to_string()),
+            Cell::from(aos),
+            Cell::from(if max_el.is_nan() {
+                "-".to_string()
+            } else {
+                format!("{:.0}°", max_el)
+            }),
+        ])
+        .style(style)
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(14),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(9),
+            Constraint::Length(6),
+        ],
+    )
+    .header(Row::new(header_cells).bottom_margin(1))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Constellations")
+            .style(Style::default().fg(Color::White)),
+    );
+    let mut table_state = TableState::default();
+    table_state.select(Some(selected_index));
+    f.render_stateful_widget(table, top[0], &mut table_state);
+
+    let cells = constellation::density(look_angles(&selected.members));
+    let canvas = Canvas::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} density (blue 1, cyan 2+, yellow 4+, red 8+)", selected.name))
+                .style(Style::default().fg(Color::White)),
+        )
+        .x_bounds([-1.2, 1.2])
+        .y_bounds([-1.2, 1.2])
+        .paint(|ctx| {
+            paint_density(ctx, &cells);
+            ctx.layer();
+            for radius in [1.0, 0.667, 0.333] {
+                ctx.draw(&Circle {
+                    x: 0.0,
+                    y: 0.0,
+                    radius,
+                    color: if radius == 1.0 { Color::White } else { Color::DarkGray },
+                });
+            }
+            ctx.print(0.0, 1.05, "N");
+            ctx.print(1.05, 0.0, "E");
+            ctx.print(0.0, -1.05, "S");
+            ctx.print(-1.05, 0.0, "W");
+        });
+    f.render_widget(canvas, top[1]);
+
+    let passes = constellation::next_passes(&app_state.satellites, selected, now, 10);
+    let rows = passes.iter().map(|(satellite, pass)| {
+        let style = if pass.aos_time <= now {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(crate::display_name(app_state, &satellite.name).to_string()),
+            Cell::from(pass.aos_time.with_timezone(&Local).format("%m/%d %H:%M:%S").to_string()),
+            Cell::from(pass.los_time.with_timezone(&Local).format("%H:%M:%S").to_string()),
+            Cell::from(format!("{:.0}°", pass.max_elevation)),
+            Cell::from(format!(
+                "{} to {}",
+                azimuth_to_cardinal(pass.aos_azimuth),
+                azimuth_to_cardinal(pass.los_azimuth)
+            )),
+        ])
+        .style(style)
+    });
+    let header_cells = ["Satellite", "AOS", "LOS", "Max El", "Direction"]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(20),
+            Constraint::Length(15),
+            Constraint::Length(9),
+            Constraint::Length(7),
+            Constraint::Length(10),
+        ],
+    )
+    .header(Row::new(header_cells).bottom_margin(1))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Next passes of any {} satellite (green = up now)", selected.name))
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(table, chunks[1]);
+}
+
+/// The selected satellite's orbit around the Earth as seen from space, with
+/// the observer's path over the next day and where it meets the orbit plane
+pub fn draw_orbit_view(f: &mut Frame, app_state: &AppState) {
+    use crate::orbit_view::{self, EARTH_RADIUS_KM};
+    use nalgebra::Vector3;
+    use ratatui::symbols;
+    use ratatui::widgets::canvas::{Canvas, Circle, Line as CanvasLine, Points};
+
+    let area = centered_rect(80, 85, f.area());
+    f.render_widget(Clear, area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(10), Constraint::Length(4)])
+        .split(area);
+
+    let satellite = &app_state.satellites[app_state.selected_satellite];
+    let observer = &app_state.observer;
+    let view = app_state.orbit_view_state.view;
+    let now = Utc::now();
+
+    let orbit = orbit_view::orbit_points(satellite, now, 180);
+    let observer_path: Vec<Vector3<f64>> = (0..=144)
+        .map(|step| orbit_view::observer_position(observer, now + Duration::minutes(step * 10)))
+        .collect();
+    let equator: Vec<Vector3<f64>> = (0..=120)
+        .map(|step| {
+            let angle = (step as f64 * 3.0).to_radians();
+            Vector3::new(angle.cos(), angle.sin(), 0.0) * EARTH_RADIUS_KM
+        })
+        .collect();
+    let crossings = orbit_view::plane_crossings(satellite, observer, now);
+    let sun = astro::sun_position_eci(now).normalize();
+
+    // Split into points in view and points behind the Earth
+    let project = |points: &[Vector3<f64>]| {
+        let mut visible = Vec::new();
+        let mut hidden = Vec::new();
+        for point in points {
+            let (x, y, seen) = view.project(point);
+            if seen {
+                visible.push((x, y));
+            } else {
+                hidden.push((x, y));
+            }
+        }
+        (visible, hidden)
+    };
+    let (orbit_visible, orbit_hidden) = project(&orbit);
+    let (path_visible, path_hidden) = project(&observer_path);
+    let (equator_visible, _) = project(&equator);
+
+    // Braille cells are about twice as tall as wide; keep the Earth round
+    let extent = orbit.iter().map(|p| p.norm()).fold(EARTH_RADIUS_KM, f64::max) * 1.15;
+    let inner_width = chunks[0].width.saturating_sub(2).max(1) as f64;
+    let inner_height = chunks[0].height.saturating_sub(2).max(1) as f64;
+    let aspect = inner_width / (2.0 * inner_height);
+    let (x_extent, y_extent) = if aspect >= 1.0 {
+        (extent * aspect, extent)
+    } else {
+        (extent, extent / aspect)
+    };
+
+    let marker = |position: &Vector3<f64>, color: Color, label: &str| {
+        let (x, y, seen) = view.project(position);
+        let color = if seen { color } else { Color::DarkGray };
+        (x, y, Span::styled(format!("● {}", label), Style::default().fg(color)))
+    };
+    let satellite_marker = satellite
+        .teme_state(now)
+        .ok()
+        .map(|(position, _)| marker(&position, Color::Yellow, &satellite.name));
+    let observer_marker = marker(
+        &orbit_view::observer_position(observer, now),
+        Color::Green,
+        &observer.name,
+    );
+
+    let title = format!(
+        "Orbit of {} ({:.0} min period, {:.1}° inclination; grey = behind the Earth)",
+        crate::display_name(app_state, &satellite.name),
+        satellite.period_minutes(),
+        satellite.elements.inclination
+    );
+    let canvas = Canvas::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().fg(Color::White)),
+        )
+        .x_bounds([-x_extent, x_extent])
+        .y_bounds([-y_extent, y_extent])
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            ctx.draw(&Circle {
+                x: 0.0,
+                y: 0.0,
+                radius: EARTH_RADIUS_KM,
+                color: Color::Blue,
+            });
+            ctx.draw(&Points {
+                coords: &equator_visible,
+                color: Color::DarkGray,
+            });
+            let (x1, y1, _) = view.project(&(sun * EARTH_RADIUS_KM * 1.1));
+            let (x2, y2, _) = view.project(&(sun * extent * 0.9));
+            ctx.draw(&CanvasLine {
+                x1,
+                y1,
+                x2,
+                y2,
+                color: Color::Yellow,
+            });
+            ctx.print(x2, y2, Span::styled("Sun", Style::default().fg(Color::Yellow)));
+            ctx.layer();
+
+            ctx.draw(&Points {
+                coords: &orbit_hidden,
+                color: Color::DarkGray,
+            });
+            ctx.draw(&Points {
+                coords: &path_hidden,
+                color: Color::DarkGray,
+            });
+            ctx.draw(&Points {
+                coords: &orbit_visible,
+                color: Color::Cyan,
+            });
+            ctx.draw(&Points {
+                coords: &path_visible,
+                color: Color::Green,
+            });
+            for time in &crossings {
+                let (x, y, _) = view.project(&orbit_view::observer_position(observer, *time));
+                ctx.print(x, y, Span::styled("×", Style::default().fg(Color::Magenta)));
+            }
+            ctx.layer();
+
+            let (x, y, label) = observer_marker.clone();
+            ctx.print(x, y, label);
+            if let Some((x, y, label)) = satellite_marker.clone() {
+                ctx.print(x, y, label);
+            }
+        });
+    f.render_widget(canvas, chunks[0]);
+
+    let crossing_text = if crossings.is_empty() {
+        "You stay on one side of the orbit plane all day, so passes are low and come from \
+         the side nearest it"
+            .to_string()
+    } else {
+        let times: Vec<String> = crossings
+            .iter()
+            .map(|time| time.with_timezone(&Local).format("%H:%M").to_string())
+            .collect();
+        format!(
+            "You cross the orbit plane (×) at {}: passes cluster around these times",
+            times.join(" and ")
+        )
+    };
+    let footer = Paragraph::new(vec![
+        Line::from(Span::styled(crossing_text, Style::default().fg(Color::Magenta))),
+        Line::from("←/→ ↑/↓: Rotate view | r: Look down on observer | q/ESC: Close"),
+    ])
+    .style(Style::default().fg(Color::Gray))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[1]);
+}
+
+/// A ground track interval as "30 s" or "5 min"
+fn format_step(seconds: i64) -> String {
+    if seconds < 60 {
+        format!("{} s", seconds)
+    } else {
+        format!("{} min", seconds / 60)
+    }
+}
+
+/// Draw the Sun/Moon transit finder for the selected satellite
+pub fn draw_transit_finder(f: &mut Frame, app_state: &AppState) {
+    let state = &app_state.transit_state;
+
+    let area = centered_rect(70, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(6),    // Transit list
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "Sun/Moon Transits",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            " for {} (within {:.1} km)",
+            state.satellite_name, app_state.config.transits.search_radius_km
+        )),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(header, chunks[0]);
+
+    if state.transits.is_empty() {
+        let empty = Paragraph::new("No transits found in the predicted passes")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Gray))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Transits")
+                    .style(Style::default().fg(Color::White)),
+            );
+        f.render_widget(empty, chunks[1]);
+    } else {
+        let header_cells = ["Body", "Center Time", "Sep", "Disk", "Sat El", "Range", "Offset"]
+            .iter()
+            .map(|h| {
+                Cell::from(*h).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            });
+        let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let rows = state.transits.iter().enumerate().map(|(idx, transit)| {
+            let style = if idx == state.selected_index {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else if transit.is_on_disk() {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+
+            let cells = vec![
+                Cell::from(transit.body.as_str().to_string()),
+                Cell::from(
+                    transit
+                        .center_time
+                        .with_timezone(&Local)
+                        .format("%m/%d %H:%M:%S%.1f")
+                        .to_string(),
+                ),
+                Cell::from(format!("{:.2}°", transit.separation_deg)),
+                Cell::from(format!("{:.2}°", transit.body_radius_deg)),
+                Cell::from(format!("{:.1}°", transit.satellite_elevation)),
+                Cell::from(format!("{:.0} km", transit.range_km)),
+                Cell::from(format!("{:.2} km", transit.offset_km)),
+            ];
+
+            Row::new(cells).height(1).style(style)
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(20),
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Length(8),
+                Constraint::Length(9),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header_row)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Transits (green = crosses the disk)")
+                .style(Style::default().fg(Color::White)),
+        );
+        f.render_widget(table, chunks[1]);
+    }
+
+    let footer = Paragraph::new("j/k/↑↓: Navigate | q/ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
+
+/// Draw the pass window dialog: every satellite's passes between two times
+pub fn draw_pass_window(f: &mut Frame, app_state: &AppState) {
+    let state = &app_state.pass_window_state;
+
+    let area = centered_rect(80, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // From / to inputs
+            Constraint::Min(6),    // Passes
+            Constraint::Length(3), // Status message
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let field_span = |label: &'static str, value: &str, active: bool| {
+        let (text, style) = if active {
+            (
+                format!("{}|", value),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (value.to_string(), Style::default().fg(Color::Gray))
+        };
+        vec![
+            Span::styled(label, Style::default().fg(Color::Yellow)),
+            Span::styled(text, style),
+        ]
+    };
+    let mut spans = field_span("From: ", &state.from, state.field == WindowField::From);
+    spans.push(Span::raw("    "));
+    spans.extend(field_span("To: ", &state.to, state.field == WindowField::To));
+    let input = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Passes between (local time)")
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(input, chunks[0]);
+
+    let header_cells = ["Satellite", "AOS", "LOS", "Max El", "AOS Az", "LOS Az", "Lighting"]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let rows = state.passes.iter().enumerate().map(|(idx, (name, pass))| {
+        let style = if idx == state.selected_index {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else if pass.is_visual() {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default()
+        };
+
+        let cells = vec![
+            Cell::from(truncate_string(name, 20)),
+            Cell::from(
+                pass.aos_time
+                    .with_timezone(&Local)
+                    .format("%m/%d %H:%M:%S")
+                    .to_string(),
+            ),
+            Cell::from(pass.los_time.with_timezone(&Local).format("%H:%M:%S").to_string()),
+            Cell::from(format!("{:.1}°", pass.max_elevation)),
+            Cell::from(format!("{:.0}°", pass.aos_azimuth)),
+            Cell::from(format!("{:.0}°", pass.los_azimuth)),
+            Cell::from(pass.twilight().short_str().to_string()),
+        ];
+        Row::new(cells).height(1).style(style)
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(21),
+            Constraint::Length(15),
+            Constraint::Length(9),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Min(8),
+        ],
+    )
+    .header(header_row)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Passes (green = visible)")
+            .style(Style::default().fg(Color::White)),
+    );
+    let mut table_state = TableState::default();
+    if !state.passes.is_empty() {
+        table_state.select(Some(state.selected_index));
+    }
+    f.render_stateful_widget(table, chunks[1], &mut table_state);
+
+    let status = Paragraph::new(
+        state
+            .status_message
+            .as_deref()
+            .unwrap_or("Enter a window, e.g. 2026-04-21 18:00, and press Enter"),
+    )
+    .style(Style::default().fg(Color::Gray))
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[2]);
+
+    let footer = Paragraph::new("Tab: From/To | Enter: Search | ↑↓: Navigate | Ctrl+E: Export sky chart | Ctrl+R: Rehearse | ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[3]);
+}
+
+/// Draw the Celestrak catalog search popup
+/// Draw the logged passes view with notes and ratings
+pub fn draw_pass_log(f: &mut Frame, app_state: &AppState) {
+    let state = &app_state.pass_log_state;
+
+    let area = centered_rect(85, 75, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Search / notes input
+            Constraint::Min(6),    // Log entries
+            Constraint::Length(3), // Status message
+            Constraint::Length(4), // Footer
+        ])
+        .split(area);
+
+    let (label, text) = match state.input {
+        Some(PassLogInput::Notes) => ("Notes: ", format!("{}|", state.input_buffer)),
+        Some(PassLogInput::Search) => ("Search: ", format!("{}|", state.input_buffer)),
+        None if state.search.is_empty() => ("Search: ", "(all passes)".to_string()),
+        None => ("Search: ", state.search.clone()),
+    };
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(label, Style::default().fg(Color::Yellow)),
+        Span::styled(
+            text,
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Pass Log")
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(input, chunks[0]);
+
+    let header_cells = ["AOS (UTC)", "Satellite", "Max El", "Rating", "Rec", "Notes"]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let rows = state.entries.iter().enumerate().map(|(idx, entry)| {
+        let is_selected = idx == state.selected_index;
+        let style = if is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let indicator = if is_selected { "> " } else { "  " };
+        let aos = chrono::DateTime::parse_from_rfc3339(&entry.aos_time)
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| entry.aos_time.clone());
+        let rating = entry
+            .rating
+            .map(|r| "★".repeat(r.clamp(0, 5) as usize))
+            .unwrap_or_default();
+
+        Row::new(vec![
+            Cell::from(format!("{}{}", indicator, aos)),
+            Cell::from(truncate_string(&entry.satellite_name, 20)),
+            Cell::from(format!("{:.1}°", entry.max_elevation)),
+            Cell::from(rating).style(Style::default().fg(Color::LightYellow)),
+            Cell::from(if entry.recording.is_some() { "♪" } else { "" })
+                .style(Style::default().fg(Color::Green)),
+            Cell::from(entry.notes.clone().unwrap_or_default()),
+        ])
+        .height(1)
+        .style(style)
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(20),
+            Constraint::Length(22),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(3),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header_row)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} passes", state.entries.len()))
+            .style(Style::default().fg(Color::White)),
+    );
+    match &state.history {
+        Some(history) => {
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .split(chunks[1]);
+            f.render_widget(table, split[0]);
+            draw_position_history(f, split[1], history);
+        }
+        None => f.render_widget(table, chunks[1]),
+    }
+
+    let status = Paragraph::new(state.status_message.as_deref().unwrap_or(""))
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[2]);
+
+    let footer_text = if state.input.is_some() {
+        "Enter: Save | ESC: Cancel"
+    } else {
+        "↑/↓: Navigate | n/Enter: Notes | 1-5: Rate | 0: Clear rating | /: Search | h: Recorded positions | p: Play recording | ESC: Close"
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[3]);
+}
+
+/// Plot the elevation, Doppler shift and S-meter recorded through a logged pass,
+/// against minutes from the first recorded position
+fn draw_position_history(f: &mut Frame, area: Rect, history: &[PositionRecord]) {
+    let times: Vec<Option<DateTime<Utc>>> = history
+        .iter()
+        .map(|record| {
+            DateTime::parse_from_rfc3339(&record.recorded_at)
+                .ok()
+                .map(|time| time.with_timezone(&Utc))
+        })
+        .collect();
+    let Some(start) = times.iter().flatten().min().copied() else {
+        return;
+    };
+    let minutes = |time: &DateTime<Utc>| (*time - start).num_milliseconds() as f64 / 60000.0;
+    let series = |value: &dyn Fn(&PositionRecord) -> Option<f64>| -> Vec<(f64, f64)> {
+        history
+            .iter()
+            .zip(&times)
+            .filter_map(|(record, time)| Some((minutes(time.as_ref()?), value(record)?)))
+            .collect()
+    };
+    let elevation = series(&|record| Some(record.elevation));
+    let doppler = series(&|record| record.doppler_hz.map(|hz| hz / 1000.0));
+    let signal = series(&|record| record.signal_db);
+    let span = elevation.iter().map(|(t, _)| *t).fold(1.0, f64::max);
+
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(if signal.is_empty() {
+            vec![Constraint::Percentage(50), Constraint::Percentage(50)]
+        } else {
+            vec![Constraint::Ratio(1, 3); 3]
+        })
+        .split(area);
+    let name = &history[0].satellite_name;
+    let peak = elevation.iter().map(|(_, e)| *e).fold(0.0, f64::max);
+    f.render_widget(
+        history_chart(
+            format!("{} elevation, max {:.1}°", name, peak),
+            &elevation,
+            Color::Yellow,
+            span,
+            [0.0, 90.0],
+            "°",
+        ),
+        halves[0],
+    );
+    if doppler.is_empty() {
+        let note = Paragraph::new("No downlink frequency was set, so no Doppler was recorded")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).title("Doppler"));
+        f.render_widget(note, halves[1]);
+    } else {
+        let limit = doppler.iter().map(|(_, d)| d.abs()).fold(1.0, f64::max).ceil();
+        f.render_widget(
+            history_chart(
+                "Downlink Doppler, kHz".to_string(),
+                &doppler,
+                Color::Cyan,
+                span,
+                [-limit, limit],
+                "",
+            ),
+            halves[1],
+        );
+    }
+    // Measured signal, to hold against the elevation and range it was heard at
+    if !signal.is_empty() {
+        let low = signal.iter().map(|(_, s)| *s).fold(f64::INFINITY, f64::min);
+        let high = signal.iter().map(|(_, s)| *s).fold(f64::NEG_INFINITY, f64::max);
+        f.render_widget(
+            history_chart(
+                "S-meter, dB re S9".to_string(),
+                &signal,
+                Color::Green,
+                span,
+                [low.floor() - 1.0, high.ceil() + 1.0],
+                "",
+            ),
+            halves[2],
+        );
+    }
+}
+
+/// One recorded quantity against minutes into the pass
+fn history_chart<'a>(
+    title: String,
+    data: &'a [(f64, f64)],
+    color: Color,
+    span: f64,
+    bounds: [f64; 2],
+    unit: &str,
+) -> ratatui::widgets::Chart<'a> {
+    use ratatui::symbols;
+    use ratatui::widgets::{Axis, Chart, Dataset, GraphType};
+
+    Chart::new(vec![Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(data)])
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .x_axis(
+        Axis::default()
+            .title("min")
+            .style(Style::default().fg(Color::Gray))
+            .bounds([0.0, span])
+            .labels(["0".to_string(), format!("{:.0}", span)]),
+    )
+    .y_axis(
+        Axis::default()
+            .style(Style::default().fg(Color::Gray))
+            .bounds(bounds)
+            .labels([
+                format!("{:.0}{}", bounds[0], unit),
+                format!("{:.0}{}", bounds[1], unit),
+            ]),
+    )
+}
+
+/// Draw the telemetry frames stored for the selected satellite
+pub fn draw_telemetry(f: &mut Frame, app_state: &AppState) {
+    let state = &app_state.telemetry_state;
+
+    let area = centered_rect(85, 75, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Decoder status
+            Constraint::Min(6),    // Frames
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let decoder = app_state
+        .decoders
+        .iter()
+        .find(|d| d.config.satellite == state.satellite_name);
+    let status = match (decoder, &state.status_message) {
+        (_, Some(message)) => Span::styled(message.as_str(), Style::default().fg(Color::Red)),
+        (Some(decoder), None) => Span::styled(
+            format!("{}  ({})", decoder.status, decoder.config.decoder),
+            Style::default().fg(Color::White),
+        ),
+        (None, None) => Span::styled(
+            "No decoder configured for this satellite",
+            Style::default().fg(Color::Gray),
+        ),
+    };
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("Decoder: ", Style::default().fg(Color::Yellow)),
+        status,
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Telemetry - {}", state.satellite_name))
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(header, chunks[0]);
+
+    let header_cells = ["Received (UTC)", "Frame"].iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let rows = state.frames.iter().enumerate().map(|(idx, frame)| {
+        let is_selected = idx == state.selected_index;
+        let style = if is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let indicator = if is_selected { "> " } else { "  " };
+        let received = chrono::DateTime::parse_from_rfc3339(&frame.received_at)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|_| frame.received_at.clone());
+
+        Row::new(vec![
+            Cell::from(format!("{}{}", indicator, received)),
+            Cell::from(frame.frame.as_str()),
+        ])
+        .height(1)
+        .style(style)
+    });
+
+    let table = Table::new(rows, [Constraint::Length(23), Constraint::Min(20)])
+        .header(header_row)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} frames", state.frames.len()))
+                .style(Style::default().fg(Color::White)),
+        );
+    let mut table_state = TableState::default().with_selected(Some(state.selected_index));
+    f.render_stateful_widget(table, chunks[1], &mut table_state);
+
+    let footer = Paragraph::new("↑/↓: Navigate | Home/End: Newest/Oldest | ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
+
+/// Draw predicted passes next to the SatNOGS station's scheduled observations
+pub fn draw_satnogs(f: &mut Frame, app_state: &AppState) {
+    let state = &app_state.satnogs_state;
+
+    let area = centered_rect(85, 75, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(6),    // Passes and observations
+            Constraint::Length(3), // Status message
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let header_cells = ["Start (UTC)", "End", "Satellite", "Max El", "SatNOGS"]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let rows = satnogs::schedule_rows(&app_state.satellites, &state.observations, Utc::now());
+    let table_rows = rows.iter().enumerate().map(|(idx, row)| {
+        let is_selected = idx == state.selected_index;
+        let style = if is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let indicator = if is_selected { "> " } else { "  " };
+        let (scheduled, scheduled_style) = match &row.observation {
+            Some(obs) if obs.transmitter.is_empty() => {
+                (format!("#{}", obs.id), Style::default().fg(Color::Green))
+            }
+            Some(obs) => (
+                format!("#{} {}", obs.id, obs.transmitter),
+                Style::default().fg(Color::Green),
+            ),
+            None => ("-".to_string(), Style::default().fg(Color::DarkGray)),
+        };
+
+        Row::new(vec![
+            Cell::from(format!("{}{}", indicator, row.start.format("%m-%d %H:%M"))),
+            Cell::from(row.end.format("%H:%M").to_string()),
+            Cell::from(truncate_string(&row.satellite_name, 20)),
+            Cell::from(
+                row.max_elevation
+                    .map(|el| format!("{:.0}°", el))
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cell::from(scheduled).style(scheduled_style),
+        ])
+        .height(1)
+        .style(style)
+    });
+
+    let title = match &app_state.config.satnogs {
+        Some(config) => format!("SatNOGS station {}", config.ground_station),
+        None => "SatNOGS".to_string(),
+    };
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Length(15),
+            Constraint::Length(6),
+            Constraint::Length(22),
+            Constraint::Length(7),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header_row)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    let mut table_state = TableState::default().with_selected(Some(state.selected_index));
+    f.render_stateful_widget(table, chunks[0], &mut table_state);
+
+    let status = Paragraph::new(state.status_message.as_deref().unwrap_or(""))
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[1]);
+
+    let footer = Paragraph::new("↑/↓: Navigate | Enter: Schedule pass | r: Refresh | ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
+
+/// Draw the rotator calibration screen: reference targets and offsets
+pub fn draw_rotator_calibration(f: &mut Frame, app_state: &AppState) {
+    let state = &app_state.calibration_state;
+    let Some(rotator) = app_state.rotators.get(state.rotator_index) else {
+        return;
+    };
+
+    let area = centered_rect(70, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(8),     // Targets
+            Constraint::Length(6),  // Offsets and rotator position
+            Constraint::Length(11), // Pointing error of recent passes
+            Constraint::Length(3),  // Status message
+            Constraint::Length(3),  // Footer
+        ])
+        .split(area);
+
+    let targets = rotator::calibration_targets(Utc::now(), &app_state.observer);
+    let items: Vec<Line> = targets
+        .iter()
+        .enumerate()
+        .map(|(idx, (name, azimuth, elevation))| {
+            let is_selected = idx == state.selected_index;
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(
+                format!(
+                    "{}{:<16} Az {:>6.1}°  El {:>5.1}°",
+                    if is_selected { "> " } else { "  " },
+                    name,
+                    azimuth,
+                    elevation
+                ),
+                style,
+            ))
+        })
+        .collect();
+    let list = Paragraph::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(if app_state.rotators.len() > 1 {
+                format!("Calibrate {} (Tab: next rotator)", rotator.config.name)
+            } else {
+                format!("Calibrate {}", rotator.config.name)
+            })
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(list, chunks[0]);
+
+    let offset_span = |field: CalibrationField, value: f64| {
+        if state.editing == Some(field) {
+            Span::styled(
+                format!("{}|", state.input_buffer),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::raw(format!("{:+.1}°", value))
+        }
+    };
+    let offsets = vec![
+        Line::from(vec![
+            Span::styled("Azimuth offset:   ", Style::default().fg(Color::Cyan)),
+            offset_span(CalibrationField::Azimuth, rotator.calibration.azimuth_offset),
+        ]),
+        Line::from(vec![
+            Span::styled("Elevation offset: ", Style::default().fg(Color::Cyan)),
+            offset_span(CalibrationField::Elevation, rotator.calibration.elevation_offset),
+        ]),
+        Line::from(vec![
+            Span::styled("Rotator reports:  ", Style::default().fg(Color::Cyan)),
+            Span::raw(match state.reported_position {
+                Some((azimuth, elevation)) => format!("Az {:.1}°  El {:.1}°", azimuth, elevation),
+                None => "unknown (press p)".to_string(),
+            }),
+        ]),
+    ];
+    let offsets = Paragraph::new(offsets).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Offsets (antenna minus commanded)")
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(offsets, chunks[1]);
+
+    // Oldest at the top, so a bias creeping one way reads as a trend
+    let header = Row::new(
+        ["Pass (UTC)", "Satellite", "Samples", "Mean", "RMS", "Max", "Az bias", "El bias"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow))),
+    );
+    let rows = state.pointing_errors.iter().rev().map(|stats| {
+        let start = DateTime::parse_from_rfc3339(&stats.pass_start)
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| stats.pass_start.clone());
+        let max_style = if stats.max_error_deg > rotator.config.max_pointing_error_deg {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(start),
+            Cell::from(truncate_string(&stats.satellite_name, 14)),
+            Cell::from(stats.samples.to_string()),
+            Cell::from(format!("{:.1}°", stats.mean_error_deg)),
+            Cell::from(format!("{:.1}°", stats.rms_error_deg)),
+            Cell::from(format!("{:.1}°", stats.max_error_deg)).style(max_style),
+            Cell::from(format!("{:+.1}°", stats.mean_azimuth_error_deg)),
+            Cell::from(format!("{:+.1}°", stats.mean_elevation_error_deg)),
+        ])
+    });
+    let title = if state.pointing_errors.is_empty() {
+        "Pointing error (recorded while tracking passes)"
+    } else {
+        "Pointing error, reported minus commanded"
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(17),
+            Constraint::Length(15),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(table, chunks[2]);
+
+    let status = Paragraph::new(state.status_message.as_deref().unwrap_or(""))
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[3]);
+
+    let footer_text = if state.editing.is_some() {
+        "Enter: Apply | ESC: Cancel"
+    } else {
+        "↑/↓: Target | Enter: Move | p: Read position | a/e: Edit offsets | s: Save | ESC: Close"
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[4]);
+}
+
+/// Doppler-corrected downlink and uplink at a moment of a rehearsed pass
+fn rehearsal_doppler_line<'a>(
+    app_state: &AppState,
+    satellite_name: &str,
+    time: DateTime<Utc>,
+) -> Line<'a> {
+    let position = app_state
+        .satellites
+        .iter()
+        .find(|sat| sat.name == satellite_name)
+        .and_then(|sat| sat.calculate_position(time, &app_state.observer).ok());
+    let (downlink_mhz, uplink_mhz) = app_state.config.radio.frequencies_for(satellite_name);
+    let text = position.map_or(String::new(), |position| {
+        let doppler = calculate_doppler_shift(&position, downlink_mhz, uplink_mhz);
+        format!(
+            "Down {:.6} MHz ({:+.0} Hz)  Up {:.6} MHz",
+            doppler.downlink_observed_mhz,
+            doppler.downlink_shift_hz,
+            doppler.uplink_corrected_mhz
+        )
+    });
+    Line::from(vec![
+        Span::styled("Doppler:   ", Style::default().fg(Color::Cyan)),
+        Span::raw(text),
+    ])
+}
+
+/// Draw the rehearsal of a pass: the rotator following it, with Doppler and
+/// the mode changes along the way
+pub fn draw_rotator_simulation(f: &mut Frame, app_state: &AppState) {
+    use ratatui::symbols;
+    use ratatui::widgets::canvas::{Canvas, Circle, Line as CanvasLine, Points};
+
+    let state = &app_state.simulation_state;
+
+    let area = centered_rect(80, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(11), // Pass, rotator and radio readout
+            Constraint::Min(10),   // Compass and elevation gauge
+            Constraint::Length(3), // Progress
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let footer = Paragraph::new("Space: Pause | +/-: Speed | ←/→: Seek | 0: Restart | q/ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[3]);
+
+    let Some((plan, sample)) = state
+        .plan
+        .as_ref()
+        .and_then(|plan| plan.samples.get(state.index).map(|sample| (plan, sample)))
+    else {
+        let message = Paragraph::new("No upcoming pass to rehearse for this satellite")
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Pass rehearsal"),
+            );
+        f.render_widget(message, chunks[0].union(chunks[2]));
+        return;
+    };
+
+    // Antenna beam decides when the pointing error starts to matter
+    let bands = app_state.config.satellite_bands(&plan.satellite_name);
+    let half_beam = app_state
+        .config
+        .antenna_for(&plan.satellite_name, &bands)
+        .map_or(10.0, |antenna| antenna.beamwidth_deg / 2.0);
+    let error_color = if sample.error_deg <= half_beam {
+        Color::Green
+    } else {
+        Color::Red
+    };
+    let simulated_rotator = app_state
+        .rotators
+        .iter()
+        .find(|rotator| rotator.config.mapping.rank(&plan.satellite_name, &bands).is_some());
+    let max_error_deg =
+        simulated_rotator.map_or(half_beam, |rotator| rotator.config.max_pointing_error_deg);
+    let aos = plan.samples[0].time;
+    let elapsed = (sample.time - aos).num_seconds();
+
+    let readout = vec![
+        Line::from(vec![
+            Span::styled("Satellite: ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!(
+                "{}  AOS {}  ",
+                plan.satellite_name,
+                aos.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+            )),
+            Span::styled(
+                if plan.flip { "FLIP mode" } else { "Normal mode" },
+                Style::default()
+                    .fg(if plan.flip { Color::Magenta } else { Color::White })
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Time:      ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!(
+                "T+{}  ({}x{})",
+                format_mm_ss(elapsed as f64),
+                state.speed * 10,
+                if state.paused { ", paused" } else { "" }
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Target:    ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!(
+                "Az {:>6.1}°  El {:>5.1}°",
+                sample.target.0, sample.target.1
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Rotator:   ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!(
+                "Az {:>6.1}°  El {:>5.1}°  ",
+                sample.rotator.0, sample.rotator.1
+            )),
+            Span::styled(
+                if sample.slewing { "SLEWING" } else { "" },
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Error:     ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{:.1}°", sample.error_deg),
+                Style::default().fg(error_color).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!("  (max {:.1}° this pass)", plan.max_error_deg)),
+        ]),
+        rehearsal_doppler_line(app_state, &plan.satellite_name, sample.time),
+        Line::from(vec![
+            Span::styled("Last:      ", Style::default().fg(Color::Cyan)),
+            Span::raw(
+                state
+                    .events
+                    .iter()
+                    .rev()
+                    .find(|(time, _)| *time <= sample.time)
+                    .map_or("-", |(_, event)| event.as_str()),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Next:      ", Style::default().fg(Color::Cyan)),
+            Span::raw(
+                state
+                    .events
+                    .iter()
+                    .find(|(time, _)| *time > sample.time)
+                    .map(|(time, event)| {
+                        format!(
+                            "{} at T+{} (in {})",
+                            event,
+                            format_mm_ss((*time - aos).num_seconds() as f64),
+                            format_mm_ss((*time - sample.time).num_seconds() as f64)
+                        )
+                    })
+                    .unwrap_or_default(),
+            ),
+        ]),
+        Line::from(Span::styled(
+            plan.warning(max_error_deg)
+                .map(|warning| format!("⚠ {}", warning))
+                .unwrap_or_default(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+    let readout = Paragraph::new(readout).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Pass rehearsal")
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(readout, chunks[0]);
+
+    let gauge_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+
+    // Pass track seen so far, plotted like the sky map
+    let track: Vec<(f64, f64)> = plan.samples[..=state.index]
+        .iter()
+        .map(|s| sky_map_point(s.target.0, s.target.1.max(0.0)))
+        .collect();
+    let needle = |azimuth: f64| {
+        let azimuth = azimuth.to_radians();
+        (azimuth.sin(), azimuth.cos())
+    };
+    let target_needle = needle(sample.target.0);
+    let rotator_needle = needle(sample.pointing.0);
+
+    let compass = Canvas::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Azimuth (yellow: satellite, needle: antenna)")
+                .style(Style::default().fg(Color::White)),
+        )
+        .x_bounds([-1.2, 1.2])
+        .y_bounds([-1.2, 1.2])
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            ctx.draw(&Circle {
+                x: 0.0,
+                y: 0.0,
+                radius: 1.0,
+                color: Color::White,
+            });
+            ctx.draw(&Points {
+                coords: &track,
+                color: Color::DarkGray,
+            });
+            ctx.draw(&CanvasLine {
+                x1: 0.0,
+                y1: 0.0,
+                x2: target_needle.0,
+                y2: target_needle.1,
+                color: Color::Yellow,
+            });
+            ctx.draw(&CanvasLine {
+                x1: 0.0,
+                y1: 0.0,
+                x2: rotator_needle.0 * 0.9,
+                y2: rotator_needle.1 * 0.9,
+                color: error_color,
+            });
+            ctx.print(-0.03, 1.1, "N");
+            ctx.print(1.1, 0.0, "E");
+            ctx.print(-0.03, -1.15, "S");
+            ctx.print(-1.15, 0.0, "W");
+        });
+    f.render_widget(compass, gauge_chunks[0]);
+
+    // Elevation axis: a half dial from 0° (right) over 90° to 180° (left)
+    // so flipped passes show the rotator working past vertical
+    let dial = |elevation: f64| {
+        let angle = elevation.to_radians();
+        (angle.cos(), angle.sin())
+    };
+    let target_axis = if plan.flip {
+        180.0 - sample.target.1.max(0.0)
+    } else {
+        sample.target.1.max(0.0)
+    };
+    let target_dial = dial(target_axis);
+    let rotator_dial = dial(sample.rotator.1);
+    let max_elevation = simulated_rotator.map_or(90.0, |rotator| rotator.config.max_elevation);
+
+    let elevation = Canvas::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Elevation axis")
+                .style(Style::default().fg(Color::White)),
+        )
+        .x_bounds([-1.2, 1.2])
+        .y_bounds([-0.2, 1.2])
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            for step in 0..36 {
+                let (x1, y1) = dial(step as f64 * 5.0);
+                let (x2, y2) = dial((step + 1) as f64 * 5.0);
+                let color = if (step as f64 * 5.0) < max_elevation {
+                    Color::White
+                } else {
+                    Color::DarkGray // beyond the rotator's travel
+                };
+                ctx.draw(&CanvasLine {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color,
+                });
+            }
+            ctx.draw(&CanvasLine {
+                x1: 0.0,
+                y1: 0.0,
+                x2: target_dial.0,
+                y2: target_dial.1,
+                color: Color::Yellow,
+            });
+            ctx.draw(&CanvasLine {
+                x1: 0.0,
+                y1: 0.0,
+                x2: rotator_dial.0 * 0.9,
+                y2: rotator_dial.1 * 0.9,
+                color: error_color,
+            });
+            ctx.print(1.05, 0.0, "0°");
+            ctx.print(-0.05, 1.1, "90°");
+            ctx.print(-1.2, 0.0, "180°");
+        });
+    f.render_widget(elevation, gauge_chunks[1]);
+
+    let progress = Gauge::default()
+        .block(Block::default().borders(Borders::ALL))
+        .gauge_style(Style::default().fg(Color::Cyan))
+        .ratio((state.index as f64 / (plan.samples.len().max(2) - 1) as f64).clamp(0.0, 1.0))
+        .label(format!(
+            "{} / {}",
+            format_mm_ss(elapsed as f64),
+            format_mm_ss((plan.samples.len().saturating_sub(1)) as f64)
+        ));
+    f.render_widget(progress, chunks[2]);
+}
+
+/// Draw statistics summarized from the pass log
+pub fn draw_stats(f: &mut Frame, app_state: &AppState) {
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(8),     // Per-satellite table and grids
+            Constraint::Length(10), // Busiest hours
+            Constraint::Length(3),  // Footer
+        ])
+        .split(area);
+
+    let footer = Paragraph::new("c: Coverage chart  q/ESC: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+
+    let stats = match &app_state.log_stats {
+        Some(stats) if stats.total_passes > 0 => stats,
+        other => {
+            let message = if other.is_some() {
+                "No passes logged yet. Press w during or after a pass to log it."
+            } else {
+                "Unable to read the pass log from the database."
+            };
+            let body = Paragraph::new(message)
+                .style(Style::default().fg(Color::Yellow))
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Statistics")
+                        .style(Style::default().fg(Color::Cyan)),
+                );
+            f.render_widget(body, chunks[0].union(chunks[1]));
+            return;
+        }
+    };
+
+    let top_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(chunks[0]);
+
+    let header_cells = ["Satellite", "Passes", "Best El", "Rating"].iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+    let max_passes = stats.satellites.first().map_or(1, |s| s.passes.max(1));
+    let rows = stats.satellites.iter().map(|satellite| {
+        // Inline bar so the busiest satellites stand out
+        let bar_width = (satellite.passes * 10 / max_passes).max(1) as usize;
+        Row::new(vec![
+            Cell::from(truncate_string(&satellite.satellite_name, 22)),
+            Cell::from(Line::from(vec![
+                Span::styled("█".repeat(bar_width), Style::default().fg(Color::Green)),
+                Span::raw(format!(" {}", satellite.passes)),
+            ])),
+            Cell::from(format!("{:.1}°", satellite.best_elevation)),
+            Cell::from(
+                satellite
+                    .average_rating
+                    .map(|r| format!("{:.1}★", r))
+                    .unwrap_or_else(|| "-".to_string()),
+            )
+            .style(Style::default().fg(Color::LightYellow)),
+        ])
+        .height(1)
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(18),
+            Constraint::Length(16),
+            Constraint::Length(8),
+            Constraint::Length(7),
+        ],
+    )
+    .header(header_row)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Passes worked ({} total)", stats.total_passes))
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(table, top_chunks[0]);
+
+    let grid_lines: Vec<Line> = if stats.grids.is_empty() {
+        vec![Line::from(Span::styled(
+            "Note grids like FN31 in pass notes",
+            Style::default().fg(Color::Gray),
+        ))]
+    } else {
+        stats
+            .grids
+            .iter()
+            .map(|(grid, count)| {
+                Line::from(vec![
+                    Span::styled(format!("{:<6}", grid), Style::default().fg(Color::Cyan)),
+                    Span::raw(format!("{} pass(es)", count)),
+                ])
+            })
+            .collect()
+    };
+    let grids = Paragraph::new(grid_lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Grids contacted ({})", stats.grids.len()))
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(grids, top_chunks[1]);
+
+    let bars: Vec<Bar> = stats
+        .passes_by_hour
+        .iter()
+        .enumerate()
+        .map(|(hour, count)| {
+            Bar::default()
+                .value(*count as u64)
+                .label(Line::from(format!("{:02}", hour)))
+                .text_value(if *count > 0 { count.to_string() } else { String::new() })
+        })
+        .collect();
+    let hours = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Busiest hours (UTC AOS)")
+                .style(Style::default().fg(Color::White)),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(2)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(Color::Cyan));
+    f.render_widget(hours, chunks[1]);
+}
+
+/// Polar chart of where logged passes have worked, with the lowest elevation
+/// telemetry was decoded at in each direction
+pub fn draw_coverage(f: &mut Frame, app_state: &AppState) {
+    use ratatui::symbols;
+    use ratatui::widgets::canvas::{Canvas, Circle, Line as CanvasLine, Points};
+
+    let state = &app_state.coverage_state;
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(10), Constraint::Length(3)])
+        .split(area);
+
+    let footer_text = state
+        .status_message
+        .clone()
+        .unwrap_or_else(|| "e: Save contour as horizon file  q/ESC: Back".to_string());
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[1]);
+
+    let Some(coverage) = state.coverage.as_ref().filter(|c| c.passes > 0) else {
+        let message = if state.coverage.is_some() {
+            "No logged pass could be placed. Log passes with w, and set record = true \
+             under [history] for passes older than the current TLEs."
+        } else {
+            "Unable to read the pass log from the database."
+        };
+        let body = Paragraph::new(message)
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Coverage")
+                    .style(Style::default().fg(Color::Cyan)),
+            );
+        f.render_widget(body, chunks[0]);
+        return;
+    };
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[0]);
+
+    let canvas = Canvas::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Coverage from {} logged passes", coverage.passes))
+                .style(Style::default().fg(Color::White)),
+        )
+        .x_bounds([-1.2, 1.2])
+        .y_bounds([-1.2, 1.2])
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            ctx.draw(&Circle {
+                x: 0.0,
+                y: 0.0,
+                radius: 1.0,
+                color: Color::White,
+            });
+            for radius in [0.333, 0.667] {
+                ctx.draw(&Circle {
+                    x: 0.0,
+                    y: 0.0,
+                    radius,
+                    color: Color::DarkGray,
+                });
+            }
+
+            // The configured horizon profile, to compare with the measured one
+            if !app_state.observer.horizon.is_empty() {
+                let mask_point = |az: f64| {
+                    let el = app_state.observer.horizon.min_elevation_at(az).clamp(0.0, 90.0);
+                    sky_map_point(az, el)
+                };
+                for step in 0..72 {
+                    let (x1, y1) = mask_point(step as f64 * 5.0);
+                    let (x2, y2) = mask_point((step + 1) as f64 * 5.0);
+                    ctx.draw(&CanvasLine {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        color: Color::DarkGray,
+                    });
+                }
+            }
+
+            // Outside the horizon, each sector passes went through: green where
+            // they mostly went well, red where they mostly didn't
+            for (index, sector) in coverage.sectors.iter().enumerate() {
+                if sector.good_passes + sector.poor_passes == 0 {
+                    continue;
+                }
+                let color = if sector.good_passes >= sector.poor_passes {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                let from = index as f64 * coverage::SECTOR_DEGREES;
+                let rim = |az: f64| {
+                    let (x, y) = sky_map_point(az, 0.0);
+                    (x * 1.08, y * 1.08)
+                };
+                let (x1, y1) = rim(from + 1.0);
+                let (x2, y2) = rim(from + coverage::SECTOR_DEGREES - 1.0);
+                ctx.draw(&CanvasLine {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color,
+                });
+            }
+
+            let decodes: Vec<(f64, f64)> = coverage
+                .decodes
+                .iter()
+                .map(|&(az, el)| sky_map_point(az, el.max(0.0)))
+                .collect();
+            ctx.draw(&Points {
+                coords: &decodes,
+                color: Color::Cyan,
+            });
+
+            // The measured horizon, joined only between neighbouring sectors
+            let contour = coverage.contour();
+            for (i, &(az1, el1)) in contour.iter().enumerate() {
+                let (az2, el2) = contour[(i + 1) % contour.len()];
+                let gap = (az2 - az1).rem_euclid(360.0);
+                if contour.len() < 2 || gap > coverage::SECTOR_DEGREES + 0.1 {
+                    continue;
+                }
+                let (x1, y1) = sky_map_point(az1, el1);
+                let (x2, y2) = sky_map_point(az2, el2);
+                ctx.draw(&CanvasLine {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color: Color::Yellow,
+                });
+            }
+
+            ctx.print(0.0, 1.12, "N");
+            ctx.print(1.12, 0.0, "E");
+            ctx.print(0.0, -1.15, "S");
+            ctx.print(-1.15, 0.0, "W");
+        });
+    f.render_widget(canvas, columns[0]);
+
+    let header_cells = ["Azimuth", "Good", "Poor", "Decodes", "Lowest"].iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+    let rows = coverage
+        .sectors
+        .iter()
+        .enumerate()
+        .filter(|(_, sector)| sector.good_passes + sector.poor_passes + sector.decodes > 0)
+        .map(|(index, sector)| {
+            let from = index as f64 * coverage::SECTOR_DEGREES;
+            let masked = app_state
+                .observer
+                .horizon
+                .min_elevation_at(from + coverage::SECTOR_DEGREES / 2.0);
+            // Decodes below the configured horizon mean the mask is too strict
+            let lowest_style = match sector.lowest_decode {
+                Some(el) if el < masked => Style::default().fg(Color::LightGreen),
+                _ => Style::default(),
+            };
+            Row::new(vec![
+                Cell::from(format!(
+                    "{:03.0}-{:03.0}° {}",
+                    from,
+                    from + coverage::SECTOR_DEGREES,
+                    azimuth_to_cardinal(from + coverage::SECTOR_DEGREES / 2.0)
+                )),
+                Cell::from(sector.good_passes.to_string()),
+                Cell::from(sector.poor_passes.to_string()),
+                Cell::from(sector.decodes.to_string()),
+                Cell::from(
+                    sector
+                        .lowest_decode
+                        .map(|el| format!("{:.1}°", el))
+                        .unwrap_or_else(|| "-".to_string()),
+                )
+                .style(lowest_style),
+            ])
+            .height(1)
+        });
+    let title = if coverage.skipped > 0 {
+        format!("Sectors ({} passes too old to place)", coverage.skipped)
+    } else {
+        "Sectors".to_string()
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Length(8),
+            Constraint::Length(7),
+        ],
+    )
+    .header(header_row)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(table, columns[1]);
+}
+
+pub fn draw_catalog_search(f: &mut Frame, app_state: &AppState) {
+    let state = &app_state.catalog_search_state;
+
+    let area = centered_rect(70, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Query input
+            Constraint::Min(6),    // Results
+            Constraint::Length(3), // Status message
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("Name: ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{}|", state.query),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Search Celestrak catalog")
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(input, chunks[0]);
+
+    let header_cells = ["Name", "NORAD ID", "Tracked"].iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let rows = state.results.iter().enumerate().map(|(idx, entry)| {
+        let is_selected = idx == state.selected_index;
+        let style = if is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let indicator = if is_selected { "> " } else { "  " };
+        let tracked = app_state.satellites.iter().any(|s| s.name == entry.name);
+
+        let cells = vec![
+            Cell::from(format!("{}{}", indicator, truncate_string(&entry.name, 30))),
+            Cell::from(entry.norad_id.to_string()),
+            Cell::from(if tracked { "yes" } else { "" }),
+        ];
+
+        Row::new(cells).height(1).style(style)
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(34),
+            Constraint::Length(10),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header_row)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Matches")
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(table, chunks[1]);
+
+    let status_text = state.status_message.as_deref().unwrap_or("");
+    let status = Paragraph::new(status_text)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[2]);
+
+    let footer_text = if state.is_searching() {
+        "Please wait..."
+    } else if state.results.is_empty() {
+        "Type a name | Enter: Search | Tab: Watch NORAD ID/designator as pending | ESC: Close"
+    } else {
+        "↑/↓: Navigate | Enter: Add & track | Type: New search | ESC: Close"
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[3]);
+}
+/// Rows of a 3x5 block glyph, for figures readable across a room
+fn big_glyph(c: char) -> [&'static str; 5] {
+    match c {
+        '0' => ["███", "█ █", "█ █", "█ █", "███"],
+        '1' => ["  █", "  █", "  █", "  █", "  █"],
+        '2' => ["███", "  █", "███", "█  ", "███"],
+        '3' => ["███", "  █", "███", "  █", "███"],
+        '4' => ["█ █", "█ █", "███", "  █", "  █"],
+        '5' => ["███", "█  ", "███", "  █", "███"],
+        '6' => ["███", "█  ", "███", "█ █", "███"],
+        '7' => ["███", "  █", "  █", "  █", "  █"],
+        '8' => ["███", "█ █", "███", "█ █", "███"],
+        '9' => ["███", "█ █", "███", "  █", "███"],
+        ':' => ["   ", " █ ", "   ", " █ ", "   "],
+        '-' => ["   ", "   ", "███", "   ", "   "],
+        '.' => ["   ", "   ", "   ", "   ", " █ "],
+        _ => ["   ", "   ", "   ", "   ", "   "],
+    }
+}
+
+/// Five lines spelling `text` in block glyphs
+fn big_text(text: &str, color: Color) -> Vec<Line<'static>> {
+    (0..5)
+        .map(|row| {
+            let line: Vec<&str> = text.chars().map(|c| big_glyph(c)[row]).collect();
+            Line::styled(line.join(" "), Style::default().fg(color))
+        })
+        .collect()
+}
+
+/// Draw the unattended wall display: the featured satellite in large figures,
+/// the most relevant satellites and the sky map, without menus or key help
+pub fn draw_kiosk(f: &mut Frame, app_state: &AppState) {
+    let now = Utc::now();
+    let clock_warnings = app_state.clock.warnings();
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(f.area());
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),                           // Station and time
+            Constraint::Length(clock_warnings.len() as u16), // Clock warnings
+            Constraint::Length(9),                           // Featured satellite
+            Constraint::Min(5),                              // Ranked satellites
+        ])
+        .split(columns[0]);
+
+    let mut status = time_source_spans(
+        vec![
+            Span::styled(
+                app_state.observer.name.clone(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "  {}  {}",
+                Local::now().format("%Y-%m-%d %H:%M:%S %Z"),
+                now.format("%H:%M:%S UTC")
+            )),
+        ],
+        app_state,
+    );
+    if let Some(alert) = app_state.alerts.first() {
+        status.push(Span::styled(
+            format!(
+                "  ⚠ {} in {}",
+                crate::display_name(app_state, &alert.satellite_name),
+                alert.countdown(now)
+            ),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(status)), rows[0]);
+
+    let warnings: Vec<Line> = clock_warnings
+        .iter()
+        .map(|warning| Line::styled(warning.clone(), Style::default().fg(Color::Red)))
+        .collect();
+    f.render_widget(Paragraph::new(warnings), rows[1]);
+
+    draw_kiosk_featured(f, rows[2], app_state, now);
+    draw_kiosk_ranking(f, rows[3], app_state, now);
+    draw_sky_map(f, columns[1], app_state);
+}
+
+/// Draw the whole terminal for the selected satellite during a pass: big
+/// figures, its track across the sky, radio and station state
+pub fn draw_focus(f: &mut Frame, app_state: &AppState) {
+    let now = Utc::now();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(9), // Azimuth, elevation and countdown
+            Constraint::Min(10),   // Sky track and radio
+            Constraint::Length(4), // Rotator and rig
+            Constraint::Length(1), // Keys
+        ])
+        .split(f.area());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(rows[1]);
+
+    draw_kiosk_featured(f, rows[0], app_state, now);
+    draw_pass_track(f, columns[0], app_state, now);
+    draw_radio_info(f, columns[1], app_state);
+
+    let station = Paragraph::new(vec![
+        Line::from(rig_spans(
+            vec![Span::styled("Station:", Style::default().fg(Color::Cyan))],
+            app_state,
+        )),
+        Line::from(rotator_spans(Vec::new(), app_state)),
+    ])
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(station, rows[2]);
+
+    let keys = Paragraph::new("[/]: Offset  f/q/ESC: Back")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(keys, rows[3]);
+}
+
+/// Polar plot of the selected satellite's current or next pass, with where
+/// it is now
+fn draw_pass_track(f: &mut Frame, area: Rect, app_state: &AppState, now: DateTime<Utc>) {
+    use ratatui::symbols;
+    use ratatui::widgets::canvas::{Canvas, Circle, Line as CanvasLine};
+
+    let Some(satellite) = app_state.satellites.get(app_state.selected_satellite) else {
+        return;
+    };
+    let pass = satellite.passes.iter().find(|pass| pass.los_time > now);
+    let track = pass.map_or_else(Vec::new, |pass| {
+        satellite.ground_track(
+            &app_state.observer,
+            pass.aos_time,
+            pass.los_time,
+            Duration::seconds(10),
+        )
+    });
+    let position = app_state.current_positions.get(app_state.selected_satellite);
+    let title = match pass {
+        Some(pass) => format!(
+            "Pass {} to {}, max {:.0}°",
+            pass.aos_time.with_timezone(&Local).format("%H:%M"),
+            pass.los_time.with_timezone(&Local).format("%H:%M"),
+            pass.max_elevation
+        ),
+        None => "No pass predicted".to_string(),
+    };
+
+    let canvas = Canvas::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().fg(Color::White)),
+        )
+        .x_bounds([-1.2, 1.2])
+        .y_bounds([-1.2, 1.2])
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            ctx.draw(&Circle {
+                x: 0.0,
+                y: 0.0,
+                radius: 1.0,
+                color: Color::White,
+            });
+            for radius in [0.333, 0.667] {
+                ctx.draw(&Circle {
+                    x: 0.0,
+                    y: 0.0,
+                    radius,
+                    color: Color::DarkGray,
+                });
+            }
+
+            // The part of the pass already flown is dimmed
+            for segment in track.windows(2) {
+                let (a, b) = (&segment[0], &segment[1]);
+                if a.elevation < 0.0 || b.elevation < 0.0 {
+                    continue;
+                }
+                let (x1, y1) = sky_map_point(a.azimuth, a.elevation);
+                let (x2, y2) = sky_map_point(b.azimuth, b.elevation);
+                let color = if b.time <= now {
+                    Color::DarkGray
+                } else {
+                    Color::Yellow
+                };
+                ctx.draw(&CanvasLine {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color,
+                });
+            }
+
+            if let (Some(first), Some(last)) = (track.first(), track.last()) {
+                let (x, y) = sky_map_point(first.azimuth, first.elevation.max(0.0));
+                ctx.print(x, y, Span::styled("AOS", Style::default().fg(Color::Gray)));
+                let (x, y) = sky_map_point(last.azimuth, last.elevation.max(0.0));
+                ctx.print(x, y, Span::styled("LOS", Style::default().fg(Color::Gray)));
+            }
+            if let Some(position) = position.filter(|position| position.elevation > 0.0) {
+                let (x, y) = sky_map_point(position.azimuth, position.elevation);
+                ctx.print(
+                    x,
+                    y,
+                    Span::styled(
+                        "●",
+                        Style::default()
+                            .fg(Color::Green)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                );
+            }
+
+            ctx.print(0.0, 1.12, "N");
+            ctx.print(1.12, 0.0, "E");
+            ctx.print(0.0, -1.15, "S");
+            ctx.print(-1.15, 0.0, "W");
+        });
+    f.render_widget(canvas, area);
+}
+
+fn draw_kiosk_featured(f: &mut Frame, area: Rect, app_state: &AppState, now: DateTime<Utc>) {
+    let (Some(satellite), Some(position)) = (
+        app_state.satellites.get(app_state.selected_satellite),
+        app_state
+            .current_positions
+            .get(app_state.selected_satellite),
+    ) else {
+        return;
+    };
+
+    let up = position.elevation > 0.0;
+    let countdown = if up {
+        satellite
+            .passes
+            .iter()
+            .find(|pass| pass.aos_time <= now && pass.los_time > now)
+            .map(|pass| ("LOS", (pass.los_time - now).num_seconds()))
+    } else {
+        satellite
+            .passes
+            .iter()
+            .find(|pass| pass.aos_time > now)
+            .map(|pass| ("AOS", (pass.aos_time - now).num_seconds()))
+    };
+    let color = if up { Color::Green } else { Color::Gray };
+
+    let block = Block::default().borders(Borders::ALL).title(Span::styled(
+        format!(" {} ", crate::display_name(app_state, &satellite.name)),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    ));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let figures = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+        ])
+        .split(inner);
+
+    let figure = |label: String, value: String, color: Color| {
+        let mut lines = vec![Line::styled(label, Style::default().fg(Color::Cyan))];
+        lines.extend(big_text(&value, color));
+        Paragraph::new(lines).alignment(Alignment::Center)
+    };
+
+    f.render_widget(
+        figure(
+            format!("AZ {}", azimuth_to_cardinal(position.azimuth)),
+            format!("{:.0}", position.azimuth),
+            color,
+        ),
+        figures[0],
+    );
+    f.render_widget(
+        figure(
+            "EL".to_string(),
+            format!("{:.0}", position.elevation),
+            color,
+        ),
+        figures[1],
+    );
+    let (label, value) = match countdown {
+        // Hours away reads better as HH:MM than as hundreds of minutes
+        Some((event, seconds)) if seconds >= 3600 => (
+            format!("{} in (h:m)", event),
+            format!("{}:{:02}", seconds / 3600, seconds % 3600 / 60),
+        ),
+        Some((event, seconds)) => (format!("{} in", event), format_mm_ss(seconds as f64)),
+        None => ("No pass".to_string(), "--:--".to_string()),
+    };
+    f.render_widget(
+        figure(label, value, if up { Color::Yellow } else { Color::White }),
+        figures[2],
+    );
+}
+
+fn draw_kiosk_ranking(f: &mut Frame, area: Rect, app_state: &AppState, now: DateTime<Utc>) {
+    let ranking = crate::satellite::rank_by_relevance(
+        &app_state.satellites,
+        &app_state.current_positions,
+        now,
+    );
+
+    let header = Row::new(["Satellite", "El", "Az", "Range", "Next AOS", "Max El"]).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows = ranking.iter().filter_map(|&index| {
+        let satellite = app_state.satellites.get(index)?;
+        let position = app_state.current_positions.get(index)?;
+        let next = satellite.passes.iter().find(|pass| pass.los_time > now);
+        let style = if index == app_state.selected_satellite {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else if position.elevation > 0.0 {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default()
+        };
+        Some(
+            Row::new(vec![
+                crate::display_name(app_state, &satellite.name).to_string(),
+                format!("{:.1}°", position.elevation),
+                format!("{:.0}°", position.azimuth),
+                format!("{:.0} km", position.range_km),
+                next.map_or("-".to_string(), |pass| {
+                    pass.aos_time
+                        .with_timezone(&Local)
+                        .format("%H:%M")
+                        .to_string()
+                }),
+                next.map_or("-".to_string(), |pass| {
+                    format!("{:.0}°", pass.max_elevation)
+                }),
+            ])
+            .style(style),
+        )
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(16),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(7),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::TOP));
+    f.render_widget(table, area);
+}
+
+/// Compass direction spelled out, which screen readers pronounce better than "SE"
+pub fn cardinal_words(azimuth: f64) -> &'static str {
+    match azimuth_to_cardinal(azimuth) {
+        "N" => "north",
+        "NE" => "north-east",
+        "E" => "east",
+        "SE" => "south-east",
+        "S" => "south",
+        "SW" => "south-west",
+        "W" => "west",
+        _ => "north-west",
+    }
+}
+
+/// Draw the main screen as labelled sentences and a borderless table, with no
+/// charts or box drawing for a screen reader to stumble over
+pub fn draw_accessible(f: &mut Frame, app_state: &AppState) {
+    let now = Utc::now();
+    let mut lines = vec![Line::from(format!(
+        "Crabtrack. Observer {} at latitude {:.3}, longitude {:.3}. Time {}.",
+        app_state.observer.name,
+        app_state.observer.latitude,
+        app_state.observer.longitude,
+        Local::now().format("%H:%M:%S %Z")
+    ))];
+    for warning in app_state.clock.warnings() {
+        lines.push(Line::from(format!("Warning: {}.", warning)));
+    }
+    if let Some(message) = &app_state.status_message {
+        lines.push(Line::from(format!("Status: {}.", message)));
+    }
+    for alert in &app_state.alerts {
+        lines.push(Line::from(format!(
+            "Alert: {} pass in {}, maximum elevation {:.0} degrees.",
+            crate::display_name(app_state, &alert.satellite_name),
+            alert.countdown(Utc::now()),
+            alert.pass.max_elevation
+        )));
+    }
+    for conflict in &app_state.conflicts {
+        lines.push(Line::from(format!(
+            "Conflict: {} and {} passes overlap; {} has priority.",
+            conflict.winner, conflict.loser, conflict.winner
+        )));
+    }
+    lines.push(Line::from(""));
+
+    if let (Some(satellite), Some(position)) = (
+        app_state.satellites.get(app_state.selected_satellite),
+        app_state
+            .current_positions
+            .get(app_state.selected_satellite),
+    ) {
+        lines.push(Line::from(format!(
+            "Selected satellite: {}, {} of {}{}.",
+            crate::display_name(app_state, &satellite.name),
+            app_state.selected_satellite + 1,
+            app_state.satellites.len(),
+            if app_state.auto_follow {
+                ", auto-follow on"
+            } else {
+                ""
+            }
+        )));
+        lines.push(Line::from(format!(
+            "Position: azimuth {:.0} degrees {}, elevation {:.1} degrees, range {:.0} km, {}.",
+            position.azimuth,
+            cardinal_words(position.azimuth),
+            position.elevation,
+            position.range_km,
+            if position.elevation > 0.0 {
+                "above the horizon"
+            } else {
+                "below the horizon"
+            }
+        )));
+        if let Some(doppler) = &position.doppler {
+            lines.push(Line::from(format!(
+                "Radio: receive on {:.6} megahertz, transmit on {:.6} megahertz.",
+                doppler.downlink_observed_mhz, doppler.uplink_corrected_mhz
+            )));
+        }
+        match satellite.passes.iter().find(|pass| pass.los_time > now) {
+            Some(pass) => {
+                lines.push(Line::from(format!(
+                "{}: AOS {} in the {}, maximum elevation {:.0} degrees at {}, LOS {} in the {}.",
+                if pass.aos_time <= now {
+                    "Current pass"
+                } else {
+                    "Next pass"
+                },
+                pass.aos_time.with_timezone(&Local).format("%H:%M"),
+                cardinal_words(pass.aos_azimuth),
+                pass.max_elevation,
+                pass.max_elevation_time.with_timezone(&Local).format("%H:%M"),
+                pass.los_time.with_timezone(&Local).format("%H:%M"),
+                cardinal_words(pass.los_azimuth)
+            )))
+            }
+            None => lines.push(Line::from("Next pass: none predicted.")),
+        }
+    }
+
+    let summary_height = lines.len() as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(summary_height),
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+    f.render_widget(
+        Paragraph::new("Satellites, most relevant first:"),
+        chunks[1],
+    );
+
+    let ranking = crate::satellite::rank_by_relevance(
+        &app_state.satellites,
+        &app_state.current_positions,
+        now,
+    );
+    let rows = ranking.iter().filter_map(|&index| {
+        let satellite = app_state.satellites.get(index)?;
+        let position = app_state.current_positions.get(index)?;
+        let next = satellite.passes.iter().find(|pass| pass.los_time > now);
+        let marker = if index == app_state.selected_satellite {
+            "selected"
+        } else {
+            ""
+        };
+        Some(Row::new(vec![
+            crate::display_name(app_state, &satellite.name).to_string(),
+            format!("{:.0} degrees", position.elevation),
+            format!("{:.0} degrees", position.azimuth),
+            next.map_or("none".to_string(), |pass| {
+                pass.aos_time
+                    .with_timezone(&Local)
+                    .format("%H:%M")
+                    .to_string()
+            }),
+            next.map_or("none".to_string(), |pass| {
+                format!("{:.0} degrees", pass.max_elevation)
+            }),
+            marker.to_string(),
+        ]))
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(16),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(9),
+            Constraint::Length(14),
+            Constraint::Length(8),
+        ],
+    )
+    .header(Row::new([
+        "Name",
+        "Elevation",
+        "Azimuth",
+        "Next AOS",
+        "Max elevation",
+        "",
+    ]));
+    f.render_widget(table, chunks[2]);
+
+    f.render_widget(
+        Paragraph::new(
+            "Keys: up and down select, a auto-follow, m mute alerts, P pass window, \
+             l pass log, w log pass, u utilities, q quit.",
+        ),
+        chunks[3],
+    );
+}
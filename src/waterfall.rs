@@ -0,0 +1,155 @@
+//! The Doppler curve predicted for a pass, as CSV or JSON, for overlaying the
+//! expected trace on the waterfall of a recording. Times are given both in
+//! UTC and in seconds from the start, which is how waterfall plots (SatNOGS
+//! artifacts, GNU Radio or custom scripts) lay out their time axis.
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+
+use crate::observer::Observer;
+use crate::radio::calculate_doppler_shift;
+use crate::satellite::Satellite;
+
+/// The expected downlink at one moment of the pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct DopplerPoint {
+    pub time: DateTime<Utc>,
+    pub seconds: f64, // from the start of the curve
+    pub azimuth: f64,
+    pub elevation: f64,
+    pub range_rate_km_s: f64,
+    pub doppler_hz: f64, // shift from the nominal downlink
+    pub frequency_hz: f64,
+}
+
+/// Expected Doppler trace of a satellite's downlink over a stretch of a pass
+#[derive(Debug, Clone)]
+pub struct DopplerCurve {
+    pub satellite: String,
+    pub observer: String,
+    pub downlink_mhz: f64,
+    pub step_seconds: f64,
+    pub points: Vec<DopplerPoint>,
+}
+
+impl DopplerCurve {
+    pub fn new(
+        satellite: &Satellite,
+        observer: &Observer,
+        downlink_mhz: f64,
+        (start, end): (DateTime<Utc>, DateTime<Utc>),
+        step: Duration,
+    ) -> Self {
+        let points = satellite
+            .ground_track(observer, start, end, step)
+            .into_iter()
+            .map(|position| {
+                let observed_mhz =
+                    calculate_doppler_shift(&position, downlink_mhz, 0.0).downlink_observed_mhz;
+                DopplerPoint {
+                    time: position.time,
+                    seconds: (position.time - start).num_milliseconds() as f64 / 1000.0,
+                    azimuth: position.azimuth,
+                    elevation: position.elevation,
+                    range_rate_km_s: position.range_rate_km_s,
+                    doppler_hz: (observed_mhz - downlink_mhz) * 1e6,
+                    frequency_hz: observed_mhz * 1e6,
+                }
+            })
+            .collect();
+        Self {
+            satellite: satellite.name.clone(),
+            observer: observer.name.clone(),
+            downlink_mhz,
+            step_seconds: step.num_milliseconds() as f64 / 1000.0,
+            points,
+        }
+    }
+
+    pub fn csv(&self) -> String {
+        let mut csv = String::from(
+            "time_utc,seconds,azimuth,elevation,range_rate_km_s,doppler_hz,frequency_hz\n",
+        );
+        for point in &self.points {
+            csv.push_str(&format!(
+                "{},{:.3},{:.1},{:.1},{:.4},{:.1},{:.1}\n",
+                point.time.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+                point.seconds,
+                point.azimuth,
+                point.elevation,
+                point.range_rate_km_s,
+                point.doppler_hz,
+                point.frequency_hz
+            ));
+        }
+        csv
+    }
+
+    pub fn json(&self) -> Value {
+        let points: Vec<Value> = self
+            .points
+            .iter()
+            .map(|point| {
+                json!({
+                    "time": point.time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                    "seconds": point.seconds,
+                    "azimuth": point.azimuth,
+                    "elevation": point.elevation,
+                    "range_rate_km_s": point.range_rate_km_s,
+                    "doppler_hz": point.doppler_hz,
+                    "frequency_hz": point.frequency_hz,
+                })
+            })
+            .collect();
+        json!({
+            "satellite": self.satellite,
+            "observer": self.observer,
+            "downlink_hz": self.downlink_mhz * 1e6,
+            "start": self.points.first().map(|point| {
+                point.time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+            }),
+            "step_seconds": self.step_seconds,
+            "points": points,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_prediction::sample_satellite;
+
+    #[test]
+    fn test_doppler_curve() {
+        let satellite = sample_satellite("ISS");
+        let start = satellite.epoch;
+        let observer = Observer::new("Test".to_string(), 40.0, -75.0, 0.0);
+        let curve = DopplerCurve::new(
+            &satellite,
+            &observer,
+            145.8,
+            (start, start + Duration::seconds(10)),
+            Duration::seconds(5),
+        );
+
+        assert_eq!(curve.points.len(), 3);
+        assert_eq!(curve.points[2].seconds, 10.0);
+        for point in &curve.points {
+            // Shift has the opposite sign to the range rate, and at most ~3.6 kHz on 2 m
+            assert!(point.doppler_hz * point.range_rate_km_s <= 0.0);
+            assert!(point.doppler_hz.abs() < 4000.0);
+            assert!((point.frequency_hz - 145.8e6 - point.doppler_hz).abs() < 0.01);
+        }
+
+        let csv = curve.csv();
+        assert_eq!(csv.lines().count(), 4);
+        assert_eq!(
+            csv.lines().nth(3).unwrap().split(',').nth(1),
+            Some("10.000")
+        );
+        let json = curve.json();
+        assert_eq!(json["downlink_hz"], 145.8e6);
+        assert_eq!(json["points"].as_array().unwrap().len(), 3);
+        assert_eq!(json["points"][1]["seconds"], 5.0);
+    }
+}